@@ -1,4 +1,5 @@
-use super::{SubscriptionTier, User};
+use super::{AuthError, SubscriptionTier, User};
+use crate::utils::error::AppError;
 use crate::AppState;
 use tauri::State;
 use tracing::{error, info};
@@ -8,14 +9,11 @@ pub async fn login(
     state: State<'_, AppState>,
     email: String,
     password: String,
-) -> Result<User, String> {
+) -> Result<User, AppError> {
     info!("Login attempt for user: {}", email);
 
     // Get Supabase client
-    let supabase_client = state
-        .auth
-        .get_supabase_client()
-        .map_err(|e| e.to_string())?;
+    let supabase_client = state.auth.get_supabase_client()?;
 
     // Authenticate with Supabase
     let session = supabase_client
@@ -23,7 +21,7 @@ pub async fn login(
         .await
         .map_err(|e| {
             error!("Supabase sign-in failed: {}", e);
-            e.to_string()
+            e
         })?;
 
     // Fetch user's license tier from database
@@ -60,7 +58,8 @@ pub async fn login(
         expires_at: session.expires_at,
     };
 
-    state.auth.login(user.clone()).map_err(|e| e.to_string())?;
+    state.auth.login(user.clone())?;
+    state.feature_gate.refresh(supabase_client, &user).await;
 
     info!("Login successful for user: {}", user.email);
     Ok(user)
@@ -71,14 +70,11 @@ pub async fn signup(
     state: State<'_, AppState>,
     email: String,
     password: String,
-) -> Result<User, String> {
+) -> Result<User, AppError> {
     info!("Signup attempt for user: {}", email);
 
     // Get Supabase client
-    let supabase_client = state
-        .auth
-        .get_supabase_client()
-        .map_err(|e| e.to_string())?;
+    let supabase_client = state.auth.get_supabase_client()?;
 
     // Create account with Supabase
     let session = supabase_client
@@ -86,7 +82,7 @@ pub async fn signup(
         .await
         .map_err(|e| {
             error!("Supabase sign-up failed: {}", e);
-            e.to_string()
+            e
         })?;
 
     // Fetch user's license tier from database (should be created by trigger)
@@ -119,62 +115,57 @@ pub async fn signup(
         expires_at: session.expires_at,
     };
 
-    state.auth.login(user.clone()).map_err(|e| e.to_string())?;
+    state.auth.login(user.clone())?;
+    state.feature_gate.refresh(supabase_client, &user).await;
 
     info!("Signup successful for user: {}", user.email);
     Ok(user)
 }
 
 #[tauri::command]
-pub async fn logout(state: State<'_, AppState>) -> Result<(), String> {
-    state.auth.logout().map_err(|e| e.to_string())
+pub async fn logout(state: State<'_, AppState>) -> Result<(), AppError> {
+    state.auth.logout()?;
+    state.feature_gate.clear();
+    Ok(())
 }
 
 #[tauri::command]
-pub async fn get_user_status(state: State<'_, AppState>) -> Result<Option<User>, String> {
-    state.auth.get_current_user().map_err(|e| e.to_string())
+pub async fn get_user_status(state: State<'_, AppState>) -> Result<Option<User>, AppError> {
+    Ok(state.auth.get_current_user()?)
 }
 
 #[tauri::command]
 pub async fn get_license_info(
     state: State<'_, AppState>,
-) -> Result<Option<crate::supabase::License>, String> {
+) -> Result<Option<crate::supabase::License>, AppError> {
     // Get current user
-    let user = state.auth.get_current_user().map_err(|e| e.to_string())?;
+    let user = state.auth.get_current_user()?;
 
     if let Some(user) = user {
         // Get Supabase client
-        let supabase_client = state
-            .auth
-            .get_supabase_client()
-            .map_err(|e| e.to_string())?;
+        let supabase_client = state.auth.get_supabase_client()?;
 
         // Fetch license from database
-        supabase_client
+        Ok(supabase_client
             .get_user_license(&user.id, &user.access_token)
-            .await
-            .map_err(|e| e.to_string())
+            .await?)
     } else {
         Ok(None)
     }
 }
 
 #[tauri::command]
-pub async fn refresh_token(state: State<'_, AppState>) -> Result<User, String> {
+pub async fn refresh_token(state: State<'_, AppState>) -> Result<User, AppError> {
     // Get current user
     let current_user = state
         .auth
-        .get_current_user()
-        .map_err(|e| e.to_string())?
-        .ok_or_else(|| "No user logged in".to_string())?;
+        .get_current_user()?
+        .ok_or(AuthError::NotAuthenticated)?;
 
     info!("Refreshing token for user: {}", current_user.email);
 
     // Get Supabase client
-    let supabase_client = state
-        .auth
-        .get_supabase_client()
-        .map_err(|e| e.to_string())?;
+    let supabase_client = state.auth.get_supabase_client()?;
 
     // Refresh the session with Supabase
     let session = supabase_client
@@ -182,7 +173,7 @@ pub async fn refresh_token(state: State<'_, AppState>) -> Result<User, String> {
         .await
         .map_err(|e| {
             error!("Token refresh failed: {}", e);
-            e.to_string()
+            e
         })?;
 
     // Update user with new tokens
@@ -196,10 +187,7 @@ pub async fn refresh_token(state: State<'_, AppState>) -> Result<User, String> {
     };
 
     // Update stored user
-    state
-        .auth
-        .login(updated_user.clone())
-        .map_err(|e| e.to_string())?;
+    state.auth.login(updated_user.clone())?;
 
     info!("Token refresh successful for user: {}", updated_user.email);
     Ok(updated_user)
@@ -214,23 +202,19 @@ pub struct LicenseInfoResponse {
 }
 
 #[tauri::command]
-pub async fn get_user_license(state: State<'_, AppState>) -> Result<LicenseInfoResponse, String> {
+pub async fn get_user_license(state: State<'_, AppState>) -> Result<LicenseInfoResponse, AppError> {
     // Get current user
-    let user = state.auth.get_current_user().map_err(|e| e.to_string())?;
+    let user = state.auth.get_current_user()?;
 
-    let user = user.ok_or_else(|| "User not authenticated".to_string())?;
+    let user = user.ok_or(AuthError::NotAuthenticated)?;
 
     // Get Supabase client
-    let supabase_client = state
-        .auth
-        .get_supabase_client()
-        .map_err(|e| e.to_string())?;
+    let supabase_client = state.auth.get_supabase_client()?;
 
     // Fetch license from database
     let license = supabase_client
         .get_user_license(&user.id, &user.access_token)
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
 
     match license {
         Some(license) => {
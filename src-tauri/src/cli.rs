@@ -0,0 +1,154 @@
+//! Headless command line mode
+//!
+//! Runs against the same library as the app without opening a window, so
+//! scripts can list clips:
+//!
+//! ```text
+//! lolshorts --list-clips [game_id]
+//! ```
+//!
+//! Results are printed to stdout as JSON and errors to stderr. Composing and
+//! uploading are metered per signed-in user and the command line has no way
+//! to sign in, so those stay in the app.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::path::Path;
+
+use crate::storage::{ClipMetadata, Storage};
+
+const USAGE: &str = "\
+Usage:
+  lolshorts                                  Start the app
+  lolshorts --list-clips [game_id]           List saved clips as JSON
+  lolshorts --help                           Show this message";
+
+#[derive(Debug, PartialEq)]
+pub enum CliCommand {
+    ListClips { game_id: Option<String> },
+    Help,
+}
+
+#[derive(Serialize)]
+struct GameClips {
+    game_id: String,
+    clips: Vec<ClipMetadata>,
+}
+
+/// Parse the command line (without the program name)
+///
+/// Returns None when no CLI command was given and the app should start.
+pub fn parse(args: impl IntoIterator<Item = String>) -> Option<Result<CliCommand>> {
+    let mut args = args.into_iter();
+    let command = args.next()?;
+    let rest: Vec<String> = args.collect();
+
+    let parsed = match command.as_str() {
+        "--list-clips" => match rest.as_slice() {
+            [] => Ok(CliCommand::ListClips { game_id: None }),
+            [game_id] => Ok(CliCommand::ListClips {
+                game_id: Some(game_id.clone()),
+            }),
+            _ => Err(anyhow::anyhow!("--list-clips takes at most one game id")),
+        },
+        "--help" | "-h" => Ok(CliCommand::Help),
+        // Anything else (e.g. arguments added by the OS) starts the app
+        _ => return None,
+    };
+
+    Some(parsed)
+}
+
+/// Run a CLI command, returning the process exit code
+pub async fn run(command: Result<CliCommand>, app_data_dir: &Path) -> i32 {
+    let result = match command {
+        Ok(CliCommand::Help) => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Ok(command) => execute(command, app_data_dir).await,
+        Err(e) => Err(e.context(USAGE)),
+    };
+
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            1
+        }
+    }
+}
+
+async fn execute(command: CliCommand, app_data_dir: &Path) -> Result<()> {
+    let storage = Storage::new(app_data_dir).context("Failed to open library")?;
+
+    match command {
+        CliCommand::ListClips { game_id } => {
+            let game_ids = match game_id {
+                Some(game_id) => vec![game_id],
+                None => storage.list_games()?,
+            };
+            let games = game_ids
+                .into_iter()
+                .map(|game_id| {
+                    let clips = storage.load_clip_metadata(&game_id)?;
+                    Ok(GameClips { game_id, clips })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            print_json(&games)
+        }
+        CliCommand::Help => unreachable!("handled in run"),
+    }
+}
+
+/// Attach to the console the command was run from
+///
+/// Release builds use the Windows GUI subsystem, which has no console, so
+/// output would otherwise be lost.
+#[cfg(windows)]
+pub fn attach_console() {
+    use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+    // Fails when not started from a console; output is then discarded
+    let _ = unsafe { AttachConsole(ATTACH_PARENT_PROCESS) };
+}
+
+fn print_json(value: &impl Serialize) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Option<Result<CliCommand>> {
+        parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_no_command_starts_app() {
+        assert!(parse_args(&[]).is_none());
+        assert!(parse_args(&["-psn_0_12345"]).is_none());
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(
+            parse_args(&["--list-clips"]).unwrap().unwrap(),
+            CliCommand::ListClips { game_id: None }
+        );
+        assert_eq!(
+            parse_args(&["--list-clips", "game_1"]).unwrap().unwrap(),
+            CliCommand::ListClips {
+                game_id: Some("game_1".to_string())
+            }
+        );
+        assert_eq!(parse_args(&["--help"]).unwrap().unwrap(), CliCommand::Help);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_args(&["--list-clips", "a", "b"]).unwrap().is_err());
+    }
+}
@@ -0,0 +1,446 @@
+/// Cloud backup of clip metadata, templates and settings (PRO)
+///
+/// Backed-up documents are mirrored to `<user_id>/<key>` in the backup bucket,
+/// alongside a manifest recording each document's `updated_at`. Locally,
+/// `updated_at` is the file's modification time. On sync the newer side wins;
+/// downloaded files get their mtime set to the remote `updated_at` so they
+/// aren't uploaded straight back. Deletions are not propagated.
+///
+/// Event hooks in the settings run programs on this machine, so they're
+/// never uploaded and a restore keeps the local ones.
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::time::SystemTime;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{debug, info};
+
+use crate::auth::User;
+use crate::supabase::SupabaseClient;
+
+/// Storage bucket holding per-user backups
+pub const BACKUP_BUCKET: &str = "user-backups";
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Key under which `RecordingSettings` is backed up
+const SETTINGS_KEY: &str = "settings.json";
+
+/// Timestamps closer than this are considered equal (filesystem mtime precision)
+const TIMESTAMP_TOLERANCE_SECS: i64 = 2;
+
+/// Remote manifest: document key -> last `updated_at`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncManifest {
+    pub items: HashMap<String, DateTime<Utc>>,
+}
+
+/// What to do with a document during sync
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncAction {
+    Upload,
+    Download,
+    Skip,
+}
+
+/// Decide sync direction from local and remote `updated_at` (newer wins)
+pub fn resolve(local: Option<DateTime<Utc>>, remote: Option<DateTime<Utc>>) -> SyncAction {
+    match (local, remote) {
+        (Some(_), None) => SyncAction::Upload,
+        (None, Some(_)) => SyncAction::Download,
+        (None, None) => SyncAction::Skip,
+        (Some(local), Some(remote)) => {
+            let diff = (local - remote).num_seconds();
+            if diff > TIMESTAMP_TOLERANCE_SECS {
+                SyncAction::Upload
+            } else if diff < -TIMESTAMP_TOLERANCE_SECS {
+                SyncAction::Download
+            } else {
+                SyncAction::Skip
+            }
+        }
+    }
+}
+
+/// Result of a sync run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncReport {
+    pub uploaded: usize,
+    pub downloaded: usize,
+    pub unchanged: usize,
+    /// Settings were replaced by a newer remote copy and must be reloaded
+    pub settings_updated: bool,
+    pub synced_at: DateTime<Utc>,
+}
+
+/// Syncs local metadata documents with the user's backup bucket
+pub struct CloudBackup {
+    base_path: PathBuf,
+    settings_path: PathBuf,
+    running: Mutex<()>,
+    last_report: RwLock<Option<SyncReport>>,
+}
+
+impl CloudBackup {
+    /// `base_path` is the storage root; `settings_path` the settings file
+    pub fn new(base_path: impl AsRef<Path>, settings_path: impl AsRef<Path>) -> Self {
+        Self {
+            base_path: base_path.as_ref().to_path_buf(),
+            settings_path: settings_path.as_ref().to_path_buf(),
+            running: Mutex::new(()),
+            last_report: RwLock::new(None),
+        }
+    }
+
+    /// Result of the most recent successful sync
+    pub async fn last_report(&self) -> Option<SyncReport> {
+        self.last_report.read().await.clone()
+    }
+
+    /// Sync all documents with the user's backup folder
+    pub async fn sync(&self, client: &SupabaseClient, user: &User) -> Result<SyncReport> {
+        let _guard = self
+            .running
+            .try_lock()
+            .map_err(|_| anyhow::anyhow!("Cloud sync is already in progress"))?;
+
+        let token = &user.access_token;
+        let manifest_path = format!("{}/{}", user.id, MANIFEST_FILE);
+
+        let mut manifest: SyncManifest = match client
+            .download_object(BACKUP_BUCKET, &manifest_path, token)
+            .await?
+        {
+            Some(bytes) => serde_json::from_slice(&bytes).context("Invalid backup manifest")?,
+            None => SyncManifest::default(),
+        };
+
+        let local = self.local_documents()?;
+        let keys: BTreeSet<&String> = local.keys().chain(manifest.items.keys()).collect();
+
+        let mut report = SyncReport {
+            uploaded: 0,
+            downloaded: 0,
+            unchanged: 0,
+            settings_updated: false,
+            synced_at: Utc::now(),
+        };
+        let mut uploads = Vec::new();
+        let mut downloads = Vec::new();
+
+        for key in keys {
+            let local_updated = local.get(key).map(|(_, updated)| *updated);
+            match resolve(local_updated, manifest.items.get(key).copied()) {
+                SyncAction::Upload => uploads.push(key.clone()),
+                SyncAction::Download => downloads.push(key.clone()),
+                SyncAction::Skip => report.unchanged += 1,
+            }
+        }
+
+        for key in uploads {
+            let (path, updated_at) = &local[&key];
+            let mut bytes = fs::read(path).with_context(|| format!("Failed to read {:?}", path))?;
+            if key == SETTINGS_KEY {
+                bytes = with_plugins(&bytes, None)?;
+            }
+
+            client
+                .upload_object(
+                    BACKUP_BUCKET,
+                    &format!("{}/{}", user.id, key),
+                    bytes,
+                    "application/json",
+                    token,
+                )
+                .await?;
+
+            debug!("Backed up {}", key);
+            manifest.items.insert(key, *updated_at);
+            report.uploaded += 1;
+        }
+
+        for key in downloads {
+            let Some(path) = self.key_to_path(&key) else {
+                debug!("Skipping unrecognized backup key: {}", key);
+                continue;
+            };
+
+            let Some(mut bytes) = client
+                .download_object(BACKUP_BUCKET, &format!("{}/{}", user.id, key), token)
+                .await?
+            else {
+                // Listed in the manifest but missing from the bucket
+                manifest.items.remove(&key);
+                continue;
+            };
+
+            // Only restore documents that still parse
+            serde_json::from_slice::<serde_json::Value>(&bytes)
+                .with_context(|| format!("Backup of {} is not valid JSON", key))?;
+            if key == SETTINGS_KEY {
+                bytes = with_plugins(&bytes, local_plugins(&path))?;
+            }
+
+            write_with_mtime(&path, &bytes, manifest.items[&key])?;
+            debug!("Restored {} from backup", key);
+
+            report.settings_updated |= key == SETTINGS_KEY;
+            report.downloaded += 1;
+        }
+
+        if report.uploaded > 0 || report.downloaded > 0 {
+            client
+                .upload_object(
+                    BACKUP_BUCKET,
+                    &manifest_path,
+                    serde_json::to_vec_pretty(&manifest)?,
+                    "application/json",
+                    token,
+                )
+                .await?;
+        }
+
+        info!(
+            "Cloud sync finished: {} uploaded, {} downloaded, {} unchanged",
+            report.uploaded, report.downloaded, report.unchanged
+        );
+
+        *self.last_report.write().await = Some(report.clone());
+        Ok(report)
+    }
+
+    /// Collect backed-up documents: key -> (path, updated_at)
+    ///
+    /// - `clips/<game_id>/clips.json` (clip index)
+    /// - `clips/<game_id>/clips/<clip>.json` (ClipMetadataV2)
+    /// - `templates/<template_id>.json` (canvas templates)
+    /// - `settings.json` (RecordingSettings)
+    fn local_documents(&self) -> Result<HashMap<String, (PathBuf, DateTime<Utc>)>> {
+        let mut documents = HashMap::new();
+
+        let clips_dir = self.base_path.join("clips");
+        if clips_dir.exists() {
+            for game in fs::read_dir(&clips_dir)? {
+                let game = game?;
+                if !game.file_type()?.is_dir() {
+                    continue;
+                }
+                let game_id = game.file_name().to_string_lossy().to_string();
+
+                self.add_document(
+                    &mut documents,
+                    format!("clips/{}/clips.json", game_id),
+                    game.path().join("clips.json"),
+                )?;
+
+                for (name, path) in json_files(&game.path().join("clips"))? {
+                    self.add_document(
+                        &mut documents,
+                        format!("clips/{}/clips/{}", game_id, name),
+                        path,
+                    )?;
+                }
+            }
+        }
+
+        for (name, path) in json_files(&self.base_path.join("templates"))? {
+            self.add_document(&mut documents, format!("templates/{}", name), path)?;
+        }
+
+        self.add_document(
+            &mut documents,
+            SETTINGS_KEY.to_string(),
+            self.settings_path.clone(),
+        )?;
+
+        Ok(documents)
+    }
+
+    fn add_document(
+        &self,
+        documents: &mut HashMap<String, (PathBuf, DateTime<Utc>)>,
+        key: String,
+        path: PathBuf,
+    ) -> Result<()> {
+        if path.is_file() {
+            let modified = fs::metadata(&path)?.modified()?;
+            documents.insert(key, (path, DateTime::<Utc>::from(modified)));
+        }
+        Ok(())
+    }
+
+    /// Map a backup key back to a local path, rejecting anything unexpected
+    fn key_to_path(&self, key: &str) -> Option<PathBuf> {
+        if key == SETTINGS_KEY {
+            return Some(self.settings_path.clone());
+        }
+
+        let relative = Path::new(key);
+        let is_safe = relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)));
+        let is_known = key.starts_with("clips/") || key.starts_with("templates/");
+
+        if is_safe && is_known && key.ends_with(".json") {
+            Some(self.base_path.join(relative))
+        } else {
+            None
+        }
+    }
+}
+
+/// `*.json` files directly inside `dir` as (file name, path)
+fn json_files(dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().and_then(|e| e.to_str()) == Some("json") {
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                files.push((name.to_string(), path.clone()));
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Settings JSON with its `plugins` section replaced (or removed for None)
+fn with_plugins(settings: &[u8], plugins: Option<serde_json::Value>) -> Result<Vec<u8>> {
+    let mut value: serde_json::Value =
+        serde_json::from_slice(settings).context("Settings are not valid JSON")?;
+    if let Some(object) = value.as_object_mut() {
+        match plugins {
+            Some(plugins) => object.insert("plugins".to_string(), plugins),
+            None => object.remove("plugins"),
+        };
+    }
+    Ok(serde_json::to_vec_pretty(&value)?)
+}
+
+/// `plugins` section of the local settings file, if it has one
+fn local_plugins(settings_path: &Path) -> Option<serde_json::Value> {
+    let contents = fs::read(settings_path).ok()?;
+    let mut value: serde_json::Value = serde_json::from_slice(&contents).ok()?;
+    value.as_object_mut()?.remove("plugins")
+}
+
+/// Write a restored document and stamp it with the remote `updated_at`
+fn write_with_mtime(path: &Path, bytes: &[u8], updated_at: DateTime<Utc>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, bytes).with_context(|| format!("Failed to write {:?}", path))?;
+
+    let file = fs::File::options().write(true).open(path)?;
+    file.set_modified(SystemTime::from(updated_at))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_resolve_newer_wins() {
+        let now = Utc::now();
+        let older = now - chrono::Duration::minutes(5);
+
+        assert_eq!(resolve(Some(now), Some(older)), SyncAction::Upload);
+        assert_eq!(resolve(Some(older), Some(now)), SyncAction::Download);
+        assert_eq!(resolve(Some(now), None), SyncAction::Upload);
+        assert_eq!(resolve(None, Some(now)), SyncAction::Download);
+        assert_eq!(
+            resolve(Some(now), Some(now - chrono::Duration::seconds(1))),
+            SyncAction::Skip
+        );
+    }
+
+    #[test]
+    fn test_local_documents() {
+        let temp_dir = TempDir::new().unwrap();
+        let base = temp_dir.path();
+        fs::create_dir_all(base.join("clips/game_1/clips")).unwrap();
+        fs::create_dir_all(base.join("templates")).unwrap();
+        fs::write(base.join("clips/game_1/clips.json"), "[]").unwrap();
+        fs::write(base.join("clips/game_1/clips/clip_1.json"), "{}").unwrap();
+        fs::write(base.join("clips/game_1/clips/clip_1.mp4"), "video").unwrap();
+        fs::write(base.join("templates/t1.json"), "{}").unwrap();
+        fs::write(base.join("settings.json"), "{}").unwrap();
+
+        let backup = CloudBackup::new(base, base.join("settings.json"));
+        let documents = backup.local_documents().unwrap();
+
+        let mut keys: Vec<_> = documents.keys().cloned().collect();
+        keys.sort();
+        assert_eq!(
+            keys,
+            vec![
+                "clips/game_1/clips.json",
+                "clips/game_1/clips/clip_1.json",
+                "settings.json",
+                "templates/t1.json",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_key_to_path_rejects_traversal() {
+        let temp_dir = TempDir::new().unwrap();
+        let backup = CloudBackup::new(temp_dir.path(), temp_dir.path().join("settings.json"));
+
+        assert!(backup.key_to_path("templates/t1.json").is_some());
+        assert!(backup.key_to_path("templates/../../evil.json").is_none());
+        assert!(backup.key_to_path("/etc/passwd.json").is_none());
+        assert!(backup.key_to_path("recordings/x.json").is_none());
+        assert_eq!(
+            backup.key_to_path(SETTINGS_KEY),
+            Some(temp_dir.path().join("settings.json"))
+        );
+    }
+
+    #[test]
+    fn test_event_hooks_stay_local() {
+        let temp_dir = TempDir::new().unwrap();
+        let settings_path = temp_dir.path().join("settings.json");
+        let hooks = serde_json::json!({ "event_hooks": [{ "name": "local", "command": "a.exe" }] });
+        fs::write(
+            &settings_path,
+            serde_json::json!({ "plugins": hooks }).to_string(),
+        )
+        .unwrap();
+
+        let remote = serde_json::json!({
+            "plugins": { "event_hooks": [{ "name": "remote", "command": "b.exe" }] }
+        })
+        .to_string();
+
+        let uploaded: serde_json::Value =
+            serde_json::from_slice(&with_plugins(remote.as_bytes(), None).unwrap()).unwrap();
+        assert!(uploaded.get("plugins").is_none());
+
+        let restored: serde_json::Value = serde_json::from_slice(
+            &with_plugins(remote.as_bytes(), local_plugins(&settings_path)).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(restored["plugins"], hooks);
+    }
+
+    #[test]
+    fn test_write_with_mtime() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("templates/t1.json");
+        let updated_at = Utc::now() - chrono::Duration::days(1);
+
+        write_with_mtime(&path, b"{}", updated_at).unwrap();
+
+        let modified = DateTime::<Utc>::from(fs::metadata(&path).unwrap().modified().unwrap());
+        assert_eq!(resolve(Some(modified), Some(updated_at)), SyncAction::Skip);
+    }
+}
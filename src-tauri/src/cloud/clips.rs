@@ -0,0 +1,313 @@
+/// Cloud clip storage for cross-device access
+///
+/// Clips are uploaded on request (never automatically) to
+/// `<user_id>/clips/<file_name>` in the clips bucket. FREE users get a fixed
+/// storage quota; `Feature::UnlimitedStorage` lifts it.
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::auth::User;
+use crate::supabase::SupabaseClient;
+
+/// Storage bucket holding uploaded clips
+pub const CLIPS_BUCKET: &str = "user-clips";
+
+/// Cloud storage available without `Feature::UnlimitedStorage` (1 GB)
+pub const FREE_STORAGE_QUOTA_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Event emitted while a clip is uploaded or downloaded
+pub const PROGRESS_EVENT: &str = "cloud-clip-progress";
+
+/// A clip stored in the cloud
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloudClip {
+    pub name: String,
+    pub size_bytes: u64,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Cloud storage used by the current user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloudStorageUsage {
+    pub used_bytes: u64,
+    /// `None` when storage is unlimited
+    pub quota_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferDirection {
+    Upload,
+    Download,
+}
+
+/// Payload of [`PROGRESS_EVENT`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CloudTransferProgress {
+    pub clip_name: String,
+    pub direction: TransferDirection,
+    pub bytes_transferred: u64,
+    pub total_bytes: u64,
+    pub percentage: f64,
+}
+
+/// Rate-limits progress callbacks to one per whole percent
+pub struct ProgressThrottle {
+    last_percent: AtomicU64,
+}
+
+impl ProgressThrottle {
+    pub fn new() -> Self {
+        Self {
+            last_percent: AtomicU64::new(u64::MAX),
+        }
+    }
+
+    /// Returns the percentage if it advanced since the last reported value
+    pub fn advance(&self, transferred: u64, total: u64) -> Option<f64> {
+        let percentage = if total == 0 {
+            0.0
+        } else {
+            (transferred as f64 / total as f64 * 100.0).min(100.0)
+        };
+
+        let whole = percentage as u64;
+        let previous = self.last_percent.swap(whole, Ordering::Relaxed);
+        (previous != whole).then_some(percentage)
+    }
+}
+
+impl Default for ProgressThrottle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Clip names are plain file names (no folders, no traversal)
+pub fn validate_clip_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+
+    if !valid {
+        anyhow::bail!("Invalid clip name: {}", name);
+    }
+    Ok(())
+}
+
+/// Reject an upload that would exceed the quota
+pub fn check_quota(used_bytes: u64, upload_bytes: u64, quota_bytes: Option<u64>) -> Result<()> {
+    if let Some(quota) = quota_bytes {
+        if used_bytes.saturating_add(upload_bytes) > quota {
+            anyhow::bail!(
+                "Cloud storage quota exceeded: {:.1} MB used of {:.1} MB, clip needs {:.1} MB. \
+                 Upgrade to PRO for unlimited storage.",
+                used_bytes as f64 / 1_048_576.0,
+                quota as f64 / 1_048_576.0,
+                upload_bytes as f64 / 1_048_576.0
+            );
+        }
+    }
+    Ok(())
+}
+
+fn clips_folder(user: &User) -> String {
+    format!("{}/clips", user.id)
+}
+
+/// List the user's cloud clips (most recently updated first)
+pub async fn list_clips(client: &SupabaseClient, user: &User) -> Result<Vec<CloudClip>> {
+    let objects = client
+        .list_objects(CLIPS_BUCKET, &clips_folder(user), &user.access_token)
+        .await?;
+
+    Ok(objects
+        .into_iter()
+        .filter(|o| !o.is_folder())
+        .map(|o| CloudClip {
+            size_bytes: o.size_bytes(),
+            name: o.name,
+            updated_at: o.updated_at,
+        })
+        .collect())
+}
+
+/// Total cloud storage used by the user
+pub async fn storage_usage(
+    client: &SupabaseClient,
+    user: &User,
+    quota_bytes: Option<u64>,
+) -> Result<CloudStorageUsage> {
+    let used_bytes = list_clips(client, user)
+        .await?
+        .iter()
+        .map(|c| c.size_bytes)
+        .sum();
+
+    Ok(CloudStorageUsage {
+        used_bytes,
+        quota_bytes,
+    })
+}
+
+/// Upload a local clip, checking the quota first
+///
+/// Re-uploading a clip with the same name replaces it, so its current size
+/// doesn't count against the quota.
+pub async fn upload_clip(
+    client: &SupabaseClient,
+    user: &User,
+    file_path: &Path,
+    quota_bytes: Option<u64>,
+    on_progress: impl Fn(u64, u64) + Send + Sync + 'static,
+) -> Result<CloudClip> {
+    let name = file_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .context("Invalid clip file name")?
+        .to_string();
+    validate_clip_name(&name)?;
+
+    let size_bytes = tokio::fs::metadata(file_path)
+        .await
+        .with_context(|| format!("Clip not found: {:?}", file_path))?
+        .len();
+
+    if quota_bytes.is_some() {
+        let used_bytes: u64 = list_clips(client, user)
+            .await?
+            .iter()
+            .filter(|c| c.name != name)
+            .map(|c| c.size_bytes)
+            .sum();
+        check_quota(used_bytes, size_bytes, quota_bytes)?;
+    }
+
+    client
+        .upload_file(
+            CLIPS_BUCKET,
+            &format!("{}/{}", clips_folder(user), name),
+            file_path,
+            "video/mp4",
+            &user.access_token,
+            on_progress,
+        )
+        .await?;
+
+    tracing::info!("Uploaded clip to cloud: {}", name);
+
+    Ok(CloudClip {
+        name,
+        size_bytes,
+        updated_at: Some(Utc::now()),
+    })
+}
+
+/// Download a cloud clip into `dest_dir`, returning the local path
+///
+/// A file already there with the same name is kept; the download gets a
+/// numbered name instead.
+pub async fn download_clip(
+    client: &SupabaseClient,
+    user: &User,
+    name: &str,
+    dest_dir: &Path,
+    on_progress: impl Fn(u64, u64),
+) -> Result<PathBuf> {
+    validate_clip_name(name)?;
+
+    tokio::fs::create_dir_all(dest_dir)
+        .await
+        .with_context(|| format!("Failed to create {:?}", dest_dir))?;
+    let dest = unique_path(dest_dir, name);
+
+    client
+        .download_file(
+            CLIPS_BUCKET,
+            &format!("{}/{}", clips_folder(user), name),
+            &dest,
+            &user.access_token,
+            on_progress,
+        )
+        .await?;
+
+    tracing::info!("Downloaded cloud clip {} to {:?}", name, dest);
+    Ok(dest)
+}
+
+/// `dir/name`, or `dir/name (2)`, `dir/name (3)`... if that is taken
+fn unique_path(dir: &Path, name: &str) -> PathBuf {
+    let path = dir.join(name);
+    if !path.exists() {
+        return path;
+    }
+
+    let name = Path::new(name);
+    let stem = name
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = name
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+
+    (2..)
+        .map(|n| dir.join(format!("{} ({}){}", stem, n, extension)))
+        .find(|path| !path.exists())
+        .expect("unbounded range")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_clip_name() {
+        assert!(validate_clip_name("clip_20240101_pentakill.mp4").is_ok());
+        assert!(validate_clip_name("../secret.mp4").is_err());
+        assert!(validate_clip_name("folder/clip.mp4").is_err());
+        assert!(validate_clip_name(".hidden").is_err());
+        assert!(validate_clip_name("").is_err());
+    }
+
+    #[test]
+    fn test_unique_path() {
+        let dir = std::env::temp_dir().join("lolshorts_test_cloud_unique_path");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        assert_eq!(unique_path(&dir, "clip.mp4"), dir.join("clip.mp4"));
+        std::fs::write(dir.join("clip.mp4"), b"first").unwrap();
+        assert_eq!(unique_path(&dir, "clip.mp4"), dir.join("clip (2).mp4"));
+        std::fs::write(dir.join("clip (2).mp4"), b"second").unwrap();
+        assert_eq!(unique_path(&dir, "clip.mp4"), dir.join("clip (3).mp4"));
+
+        // Cleanup
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_check_quota() {
+        let gb = FREE_STORAGE_QUOTA_BYTES;
+        assert!(check_quota(0, 100, Some(gb)).is_ok());
+        assert!(check_quota(gb - 100, 100, Some(gb)).is_ok());
+        assert!(check_quota(gb - 100, 101, Some(gb)).is_err());
+        // Unlimited storage
+        assert!(check_quota(gb * 10, gb, None).is_ok());
+    }
+
+    #[test]
+    fn test_progress_throttle() {
+        let throttle = ProgressThrottle::new();
+        assert_eq!(throttle.advance(0, 1000), Some(0.0));
+        assert_eq!(throttle.advance(5, 1000), None); // Still 0%
+        assert_eq!(throttle.advance(10, 1000), Some(1.0));
+        assert_eq!(throttle.advance(1000, 1000), Some(100.0));
+    }
+}
@@ -0,0 +1,176 @@
+use super::clips::{self, CloudTransferProgress, ProgressThrottle, TransferDirection};
+use super::share;
+use super::{ClipShare, CloudClip, CloudStorageUsage, SyncReport};
+use crate::auth::middleware::require_auth;
+use crate::feature_gate::Feature;
+use crate::quota::MeteredFeature;
+use crate::utils::error::AppError;
+use crate::utils::security;
+use crate::AppState;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, State};
+
+/// Sync clip metadata, templates and settings with the cloud backup (PRO)
+#[tauri::command]
+pub async fn sync_now(state: State<'_, AppState>) -> Result<SyncReport, AppError> {
+    super::run_sync(&state).await.map_err(AppError::from)
+}
+
+/// Get the result of the last successful cloud sync
+#[tauri::command]
+pub async fn get_last_sync(state: State<'_, AppState>) -> Result<Option<SyncReport>, AppError> {
+    // Require authentication
+    require_auth(&state.auth)?;
+    Ok(state.cloud_backup.last_report().await)
+}
+
+/// Upload a local clip to cloud storage
+///
+/// Emits `cloud-clip-progress` events while uploading. FREE users are
+/// limited to a fixed storage quota.
+#[tauri::command]
+pub async fn upload_clip_to_cloud(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    clip_path: String,
+) -> Result<CloudClip, AppError> {
+    // Require authentication
+    let user = require_auth(&state.auth)?;
+    let client = state.auth.get_supabase_client()?;
+
+    let clip_path = security::validate_video_input_path(&clip_path)?;
+    let clip_name = clip_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    state.quota.check(MeteredFeature::CloudUpload)?;
+
+    let on_progress = progress_emitter(app, clip_name, TransferDirection::Upload);
+
+    let clip = clips::upload_clip(
+        client,
+        &user,
+        &clip_path,
+        super::storage_quota(&state.feature_gate),
+        on_progress,
+    )
+    .await?;
+
+    match state.quota.record(MeteredFeature::CloudUpload) {
+        Ok(status) => state.notification_center.quota_recorded(&status),
+        Err(e) => tracing::error!("Failed to record cloud upload usage: {}", e),
+    }
+
+    Ok(clip)
+}
+
+/// List clips stored in the cloud for the current user
+#[tauri::command]
+pub async fn list_cloud_clips(state: State<'_, AppState>) -> Result<Vec<CloudClip>, AppError> {
+    // Require authentication
+    let user = require_auth(&state.auth)?;
+    let client = state.auth.get_supabase_client()?;
+
+    clips::list_clips(client, &user)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Download a cloud clip into the `cloud_downloads` folder of the app data
+///
+/// Kept out of the clips folder so the download isn't taken for a game.
+/// Emits `cloud-clip-progress` events while downloading.
+#[tauri::command]
+pub async fn download_cloud_clip(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    name: String,
+) -> Result<PathBuf, AppError> {
+    // Require authentication
+    let user = require_auth(&state.auth)?;
+    let client = state.auth.get_supabase_client()?;
+
+    let dest_dir = state.storage.base_path().join("cloud_downloads");
+    let on_progress = progress_emitter(app, name.clone(), TransferDirection::Download);
+
+    clips::download_clip(client, &user, &name, &dest_dir, on_progress)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Get cloud storage usage and quota for the current user
+#[tauri::command]
+pub async fn get_cloud_storage_usage(
+    state: State<'_, AppState>,
+) -> Result<CloudStorageUsage, AppError> {
+    // Require authentication
+    let user = require_auth(&state.auth)?;
+    let client = state.auth.get_supabase_client()?;
+
+    clips::storage_usage(client, &user, super::storage_quota(&state.feature_gate))
+        .await
+        .map_err(AppError::from)
+}
+
+/// Share a clip through a temporary signed link
+///
+/// Links expire after 24 hours by default; longer expiry (up to 7 days)
+/// requires PRO.
+#[tauri::command]
+pub async fn share_clip(
+    state: State<'_, AppState>,
+    clip_path: String,
+    expiry_hours: Option<u32>,
+) -> Result<ClipShare, AppError> {
+    // Require authentication
+    let user = require_auth(&state.auth)?;
+    let client = state.auth.get_supabase_client()?;
+
+    let clip_path = security::validate_video_input_path(&clip_path)?;
+
+    let expiry_hours = share::resolve_expiry_hours(
+        expiry_hours,
+        state.feature_gate.is_available(Feature::ExtendedShareLinks),
+    )?;
+
+    share::share_clip(client, &user, &clip_path, expiry_hours)
+        .await
+        .map_err(AppError::from)
+}
+
+/// List clips the current user has shared, newest first
+#[tauri::command]
+pub async fn list_clip_shares(state: State<'_, AppState>) -> Result<Vec<ClipShare>, AppError> {
+    // Require authentication
+    let user = require_auth(&state.auth)?;
+    let client = state.auth.get_supabase_client()?;
+
+    share::list_shares(client, &user)
+        .await
+        .map_err(AppError::from)
+}
+
+/// Build a progress callback that emits throttled transfer events
+fn progress_emitter(
+    app: AppHandle,
+    clip_name: String,
+    direction: TransferDirection,
+) -> impl Fn(u64, u64) + Send + Sync + 'static {
+    let throttle = ProgressThrottle::new();
+
+    move |transferred, total| {
+        if let Some(percentage) = throttle.advance(transferred, total) {
+            let progress = CloudTransferProgress {
+                clip_name: clip_name.clone(),
+                direction,
+                bytes_transferred: transferred,
+                total_bytes: total,
+                percentage,
+            };
+            if let Err(e) = app.emit(clips::PROGRESS_EVENT, progress) {
+                tracing::debug!("Failed to emit cloud progress: {}", e);
+            }
+        }
+    }
+}
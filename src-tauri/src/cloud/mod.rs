@@ -0,0 +1,68 @@
+pub mod backup;
+pub mod clips;
+pub mod commands;
+pub mod share;
+
+pub use backup::{CloudBackup, SyncReport};
+pub use clips::{CloudClip, CloudStorageUsage};
+pub use share::ClipShare;
+
+use crate::auth::middleware::require_auth;
+use crate::feature_gate::{Feature, FeatureGate};
+use crate::settings::apply::apply_settings;
+use crate::settings::models::RecordingSettings;
+use crate::AppState;
+use anyhow::Result;
+
+/// Run a cloud sync for the logged-in PRO user
+///
+/// If a newer copy of the settings was restored, it's applied to the
+/// running app.
+pub async fn run_sync(state: &AppState) -> Result<SyncReport> {
+    let user = require_auth(&state.auth)?;
+    state.feature_gate.require(Feature::CloudBackup)?;
+    let client = state.auth.get_supabase_client()?;
+
+    let report = state.cloud_backup.sync(client, &user).await?;
+
+    if report.settings_updated {
+        let restored = RecordingSettings::load()?;
+        apply_settings(state, &restored).await?;
+        tracing::info!("Applied settings restored from cloud backup");
+    }
+
+    Ok(report)
+}
+
+/// Cloud clip storage quota for the current user (`None` = unlimited)
+pub fn storage_quota(feature_gate: &FeatureGate) -> Option<u64> {
+    if feature_gate.is_available(Feature::UnlimitedStorage) {
+        None
+    } else {
+        Some(clips::FREE_STORAGE_QUOTA_BYTES)
+    }
+}
+
+/// Periodically sync in the background while auto-sync is enabled
+///
+/// Runs silently for FREE or logged-out users (the sync is simply skipped).
+pub fn start_background_sync(state: AppState) {
+    tokio::spawn(async move {
+        loop {
+            let sync_settings = state.recording_settings.read().await.cloud_sync.clone();
+            let interval_minutes = sync_settings.interval_minutes.max(5) as u64;
+            tokio::time::sleep(std::time::Duration::from_secs(interval_minutes * 60)).await;
+
+            if !sync_settings.auto_sync
+                || !state.auth.is_authenticated()
+                || !state.feature_gate.is_available(Feature::CloudBackup)
+            {
+                continue;
+            }
+
+            if let Err(e) = run_sync(&state).await {
+                tracing::warn!("Background cloud sync failed: {}", e);
+            }
+        }
+    });
+}
@@ -0,0 +1,161 @@
+/// Temporary share links for clips
+///
+/// A shared clip is uploaded to `<user_id>/<share_id>/<file_name>` in the
+/// shares bucket and handed out as a signed URL. Objects in that bucket are
+/// removed server-side once they're older than the longest allowed expiry.
+/// Every share is recorded in the `clip_shares` table.
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::clips::validate_clip_name;
+use crate::auth::User;
+use crate::supabase::SupabaseClient;
+
+/// Storage bucket holding shared clips
+pub const SHARES_BUCKET: &str = "shared-clips";
+
+/// Table recording created shares
+pub const SHARES_TABLE: &str = "clip_shares";
+
+/// Link lifetime without `Feature::ExtendedShareLinks`
+pub const DEFAULT_EXPIRY_HOURS: u32 = 24;
+
+/// Longest link lifetime with `Feature::ExtendedShareLinks` (7 days)
+pub const MAX_EXTENDED_EXPIRY_HOURS: u32 = 7 * 24;
+
+/// A created share link
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipShare {
+    pub share_id: String,
+    pub user_id: String,
+    pub clip_name: String,
+    pub url: String,
+    pub size_bytes: u64,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Link lifetime in hours for a requested expiry
+///
+/// FREE users always get [`DEFAULT_EXPIRY_HOURS`]; asking for more fails so
+/// the frontend can show the upgrade prompt.
+pub fn resolve_expiry_hours(requested: Option<u32>, extended_allowed: bool) -> Result<u32> {
+    let hours = requested.unwrap_or(DEFAULT_EXPIRY_HOURS);
+
+    if hours == 0 {
+        anyhow::bail!("Share link expiry must be at least 1 hour");
+    }
+    if hours > DEFAULT_EXPIRY_HOURS && !extended_allowed {
+        anyhow::bail!(
+            "Share links longer than {} hours require PRO",
+            DEFAULT_EXPIRY_HOURS
+        );
+    }
+    if hours > MAX_EXTENDED_EXPIRY_HOURS {
+        anyhow::bail!(
+            "Share link expiry can't exceed {} hours",
+            MAX_EXTENDED_EXPIRY_HOURS
+        );
+    }
+
+    Ok(hours)
+}
+
+/// Upload a clip to the shares bucket and create a signed link
+pub async fn share_clip(
+    client: &SupabaseClient,
+    user: &User,
+    file_path: &Path,
+    expiry_hours: u32,
+) -> Result<ClipShare> {
+    let clip_name = file_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .context("Invalid clip file name")?
+        .to_string();
+    validate_clip_name(&clip_name)?;
+
+    let size_bytes = tokio::fs::metadata(file_path)
+        .await
+        .with_context(|| format!("Clip not found: {:?}", file_path))?
+        .len();
+
+    let share_id = uuid::Uuid::new_v4().to_string();
+    let object_path = format!("{}/{}/{}", user.id, share_id, clip_name);
+
+    client
+        .upload_file(
+            SHARES_BUCKET,
+            &object_path,
+            file_path,
+            "video/mp4",
+            &user.access_token,
+            |_, _| {},
+        )
+        .await?;
+
+    let expires_in_secs = u64::from(expiry_hours) * 3600;
+    let url = client
+        .create_signed_url(
+            SHARES_BUCKET,
+            &object_path,
+            expires_in_secs,
+            &user.access_token,
+        )
+        .await?;
+
+    let created_at = Utc::now();
+    let share = ClipShare {
+        share_id,
+        user_id: user.id.clone(),
+        clip_name,
+        url,
+        size_bytes,
+        created_at,
+        expires_at: created_at + Duration::hours(i64::from(expiry_hours)),
+    };
+
+    // The link works even if tracking fails
+    if let Err(e) = client
+        .insert(SHARES_TABLE, &share, &user.access_token)
+        .await
+    {
+        tracing::warn!("Failed to record clip share {}: {}", share.share_id, e);
+    }
+
+    tracing::info!(
+        "Shared clip {} for {}h ({})",
+        share.clip_name,
+        expiry_hours,
+        share.share_id
+    );
+    Ok(share)
+}
+
+/// The user's shares, newest first
+pub async fn list_shares(client: &SupabaseClient, user: &User) -> Result<Vec<ClipShare>> {
+    Ok(client
+        .table(SHARES_TABLE)
+        .eq("user_id", &user.id)
+        .order("created_at", false)
+        .auth(&user.access_token)
+        .execute()
+        .await?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_expiry_hours() {
+        assert_eq!(resolve_expiry_hours(None, false).unwrap(), 24);
+        assert_eq!(resolve_expiry_hours(Some(6), false).unwrap(), 6);
+        assert!(resolve_expiry_hours(Some(48), false).is_err());
+        assert_eq!(resolve_expiry_hours(Some(48), true).unwrap(), 48);
+        assert!(resolve_expiry_hours(Some(24 * 8), true).is_err());
+        assert!(resolve_expiry_hours(Some(0), true).is_err());
+    }
+}
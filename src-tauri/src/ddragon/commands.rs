@@ -0,0 +1,32 @@
+use super::ChampionAssets;
+use crate::AppState;
+use tauri::State;
+
+/// Icon and splash art of `champion` for the current patch
+///
+/// Downloaded on first use; `champion` is a display name ("Kai'Sa") or a
+/// Data Dragon id ("Kaisa").
+#[tauri::command]
+pub async fn get_champion_assets(
+    state: State<'_, AppState>,
+    champion: String,
+) -> Result<ChampionAssets, String> {
+    // FREE tier feature - no authentication required
+    state
+        .ddragon
+        .champion_assets(&champion)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Path of the icon of item `item_id` for the current patch
+#[tauri::command]
+pub async fn get_item_icon(state: State<'_, AppState>, item_id: u32) -> Result<String, String> {
+    // FREE tier feature - no authentication required
+    state
+        .ddragon
+        .item_icon(item_id)
+        .await
+        .map(|path| path.to_string_lossy().to_string())
+        .map_err(|e| e.to_string())
+}
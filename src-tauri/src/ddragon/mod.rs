@@ -0,0 +1,321 @@
+pub mod commands;
+
+/// Champion and item art from Data Dragon
+///
+/// Riot publishes the art of every patch on Data Dragon. What the app uses
+/// is downloaded on first use into `<version>/` under the cache folder, so
+/// canvas templates and thumbnails keep working offline. Without a
+/// connection the newest patch already cached is used; older patches are
+/// removed once the current one is cached.
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+use crate::lcu::patch::latest_ddragon_version;
+
+const DDRAGON_CDN: &str = "https://ddragon.leagueoflegends.com/cdn";
+
+/// Champion list of a patch, kept next to its art
+const CHAMPION_LIST_FILE: &str = "champion.json";
+
+const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Cached art of one champion
+#[derive(Debug, Clone, Serialize)]
+pub struct ChampionAssets {
+    /// Display name ("Kai'Sa")
+    pub name: String,
+    /// Data Dragon id ("Kaisa")
+    pub id: String,
+    /// Data Dragon version the art is from ("14.20.1")
+    pub version: String,
+    /// Square icon (120x120 PNG)
+    pub icon_path: String,
+    /// Default skin splash art (1215x717 JPG)
+    pub splash_path: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChampionList {
+    data: HashMap<String, Champion>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Champion {
+    id: String,
+    name: String,
+}
+
+/// The patch art is taken from
+struct Patch {
+    version: String,
+    champions: Vec<Champion>,
+}
+
+pub struct DataDragon {
+    dir: PathBuf,
+    client: reqwest::Client,
+    /// Looked up on first use
+    patch: Mutex<Option<Arc<Patch>>>,
+}
+
+impl DataDragon {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            client: reqwest::Client::new(),
+            patch: Mutex::new(None),
+        }
+    }
+
+    /// Icon and splash art of `champion`, by name ("Kai'Sa") or id ("Kaisa")
+    pub async fn champion_assets(&self, champion: &str) -> Result<ChampionAssets> {
+        let patch = self.patch().await?;
+        let found = find_champion(&patch.champions, champion)
+            .with_context(|| format!("Unknown champion: {}", champion))?;
+        let version_dir = self.dir.join(&patch.version);
+
+        let icon = self
+            .download(
+                &format!(
+                    "{}/{}/img/champion/{}.png",
+                    DDRAGON_CDN, patch.version, found.id
+                ),
+                &version_dir
+                    .join("champion")
+                    .join(format!("{}.png", found.id)),
+            )
+            .await?;
+        let splash = self
+            .download(
+                &format!("{}/img/champion/splash/{}_0.jpg", DDRAGON_CDN, found.id),
+                &version_dir
+                    .join("splash")
+                    .join(format!("{}_0.jpg", found.id)),
+            )
+            .await?;
+
+        Ok(ChampionAssets {
+            name: found.name.clone(),
+            id: found.id.clone(),
+            version: patch.version.clone(),
+            icon_path: icon.to_string_lossy().to_string(),
+            splash_path: splash.to_string_lossy().to_string(),
+        })
+    }
+
+    /// Icon of item `item_id` (64x64 PNG)
+    pub async fn item_icon(&self, item_id: u32) -> Result<PathBuf> {
+        let patch = self.patch().await?;
+        self.download(
+            &format!("{}/{}/img/item/{}.png", DDRAGON_CDN, patch.version, item_id),
+            &self
+                .dir
+                .join(&patch.version)
+                .join("item")
+                .join(format!("{}.png", item_id)),
+        )
+        .await
+    }
+
+    /// The current patch, or the newest cached one when offline
+    async fn patch(&self) -> Result<Arc<Patch>> {
+        let mut patch = self.patch.lock().await;
+        if let Some(patch) = patch.as_ref() {
+            return Ok(Arc::clone(patch));
+        }
+
+        let (version, online) = match latest_ddragon_version().await {
+            Ok(version) => (version, true),
+            Err(e) => {
+                let cached = newest_cached_version(&self.dir).with_context(|| {
+                    format!("Data Dragon unreachable and nothing cached: {:#}", e)
+                })?;
+                warn!(
+                    "Data Dragon unreachable, using cached patch {}: {:#}",
+                    cached, e
+                );
+                (cached, false)
+            }
+        };
+
+        let champions = self.champion_list(&version).await?;
+        if online {
+            prune_other_versions(&self.dir, &version);
+        }
+        info!(
+            "Data Dragon patch {} ({} champions)",
+            version,
+            champions.len()
+        );
+
+        let loaded = Arc::new(Patch { version, champions });
+        *patch = Some(Arc::clone(&loaded));
+        Ok(loaded)
+    }
+
+    /// Champions of `version`, downloading the list if it isn't cached
+    async fn champion_list(&self, version: &str) -> Result<Vec<Champion>> {
+        let path = self
+            .download(
+                &format!("{}/{}/data/en_US/champion.json", DDRAGON_CDN, version),
+                &self.dir.join(version).join(CHAMPION_LIST_FILE),
+            )
+            .await?;
+
+        let json = std::fs::read_to_string(&path)?;
+        let list: ChampionList =
+            serde_json::from_str(&json).context("Failed to parse Data Dragon champion list")?;
+        Ok(list.data.into_values().collect())
+    }
+
+    /// Download `url` to `path` unless it's already there
+    async fn download(&self, url: &str, path: &Path) -> Result<PathBuf> {
+        if path.is_file() {
+            return Ok(path.to_path_buf());
+        }
+
+        debug!("Downloading {}", url);
+        let response = self
+            .client
+            .get(url)
+            .timeout(DOWNLOAD_TIMEOUT)
+            .send()
+            .await
+            .with_context(|| format!("Failed to download {}", url))?;
+        if !response.status().is_success() {
+            bail!("Data Dragon returned {} for {}", response.status(), url);
+        }
+        let bytes = response.bytes().await?;
+
+        // Write next to the target first so an interrupted download is never
+        // taken for a cached file
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let partial = path.with_extension("partial");
+        std::fs::write(&partial, &bytes)?;
+        std::fs::rename(&partial, path)?;
+
+        Ok(path.to_path_buf())
+    }
+}
+
+/// Letters and digits, lowercased ("Kai'Sa" and "Kaisa" are both "kaisa")
+fn champion_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Champion called `name` (display name or id)
+fn find_champion<'a>(champions: &'a [Champion], name: &str) -> Option<&'a Champion> {
+    let key = champion_key(name);
+    if key.is_empty() {
+        return None;
+    }
+    champions
+        .iter()
+        .find(|champion| champion_key(&champion.name) == key || champion_key(&champion.id) == key)
+}
+
+/// "14.20.1" as numbers, for comparing versions
+fn version_key(version: &str) -> Option<Vec<u32>> {
+    version.split('.').map(|part| part.parse().ok()).collect()
+}
+
+/// Newest version with a cached champion list
+fn newest_cached_version(dir: &Path) -> Option<String> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join(CHAMPION_LIST_FILE).is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter_map(|version| version_key(&version).map(|key| (key, version)))
+        .max()
+        .map(|(_, version)| version)
+}
+
+/// Remove the art of every patch but `current`
+fn prune_other_versions(dir: &Path, current: &str) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        let is_old_version = entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name != current && version_key(name).is_some());
+        if !path.is_dir() || !is_old_version {
+            continue;
+        }
+
+        match std::fs::remove_dir_all(&path) {
+            Ok(()) => info!("Removed Data Dragon cache of {:?}", entry.file_name()),
+            Err(e) => warn!("Failed to remove Data Dragon cache {:?}: {}", path, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn champion(id: &str, name: &str) -> Champion {
+        Champion {
+            id: id.to_string(),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_find_champion_by_name_or_id() {
+        let champions = vec![
+            champion("Kaisa", "Kai'Sa"),
+            champion("MonkeyKing", "Wukong"),
+            champion("Nunu", "Nunu & Willump"),
+        ];
+
+        assert_eq!(find_champion(&champions, "Kai'Sa").unwrap().id, "Kaisa");
+        assert_eq!(find_champion(&champions, "kaisa").unwrap().id, "Kaisa");
+        assert_eq!(
+            find_champion(&champions, "Wukong").unwrap().id,
+            "MonkeyKing"
+        );
+        assert_eq!(
+            find_champion(&champions, "Nunu & Willump").unwrap().id,
+            "Nunu"
+        );
+        assert!(find_champion(&champions, "Unknown").is_none());
+        assert!(find_champion(&champions, "").is_none());
+    }
+
+    #[test]
+    fn test_newest_cached_version() {
+        let dir = tempfile::tempdir().unwrap();
+        for version in ["14.9.1", "14.20.1", "14.21.1"] {
+            std::fs::create_dir_all(dir.path().join(version)).unwrap();
+        }
+        for version in ["14.9.1", "14.20.1"] {
+            std::fs::write(dir.path().join(version).join(CHAMPION_LIST_FILE), "{}").unwrap();
+        }
+
+        // 14.21.1 has no champion list (download never finished)
+        assert_eq!(
+            newest_cached_version(dir.path()).as_deref(),
+            Some("14.20.1")
+        );
+
+        prune_other_versions(dir.path(), "14.20.1");
+        assert!(!dir.path().join("14.9.1").exists());
+        assert!(dir.path().join("14.20.1").exists());
+    }
+}
@@ -0,0 +1,158 @@
+/// Real-time events pushed to the frontend
+///
+/// Backend components hold an `Arc<EventBus>` and emit typed [`AppEvent`]s;
+/// the bus forwards them as Tauri events once the app handle is attached
+/// during setup. Events emitted before that (or in tests) are dropped.
+use serde::Serialize;
+use std::sync::OnceLock;
+use tauri::{AppHandle, Emitter};
+
+use crate::jobs::JobStatus;
+use crate::notifications::center::InAppNotification;
+use crate::recording::RecordingStatus;
+use crate::storage::batch::BatchProgress;
+use crate::storage::relocation::MigrationProgress;
+use crate::video::AutoEditProgress;
+
+pub const RECORDING_STATUS_CHANGED: &str = "recording:status_changed";
+pub const CLIP_SAVED: &str = "clip:saved";
+pub const AUTOCLIP_EVENT_DETECTED: &str = "autoclip:event_detected";
+pub const AUTOEDIT_PROGRESS: &str = "autoedit:progress";
+pub const CLIPS_BATCH_PROGRESS: &str = "clips:batch_progress";
+pub const JOB_STATUS_CHANGED: &str = "job:status_changed";
+pub const NOTIFICATION_ADDED: &str = "notification:added";
+pub const STORAGE_MIGRATION_PROGRESS: &str = "storage:migration_progress";
+pub const VIDEO_PROGRESS: &str = "video:progress";
+
+/// Payload of [`RECORDING_STATUS_CHANGED`]
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingStatusChanged {
+    pub status: RecordingStatus,
+}
+
+/// Payload of [`CLIP_SAVED`]
+#[derive(Debug, Clone, Serialize)]
+pub struct ClipSaved {
+    pub clip_path: String,
+    pub game_id: String,
+    pub priority: u8,
+    pub duration_secs: f64,
+}
+
+/// Payload of [`AUTOCLIP_EVENT_DETECTED`]
+#[derive(Debug, Clone, Serialize)]
+pub struct AutoClipEventDetected {
+    pub event_name: String,
+    /// In-game time in seconds
+    pub event_time: f64,
+    pub priority: u8,
+    pub game_id: Option<String>,
+}
+
+/// Payload of [`JOB_STATUS_CHANGED`] (sent when a job is deferred or released)
+#[derive(Debug, Clone, Serialize)]
+pub struct JobStatusChanged {
+    pub job_id: String,
+    pub job_type: String,
+    pub status: JobStatus,
+}
+
+/// Payload of [`VIDEO_PROGRESS`], sent while an FFmpeg operation runs
+#[derive(Debug, Clone, Serialize)]
+pub struct VideoProgress {
+    /// What's being processed ("extract_clip", "compose_shorts", ...)
+    pub operation: String,
+    /// 0-100
+    pub percent: f64,
+}
+
+/// Event sent to the frontend (serialized as the bare payload)
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum AppEvent {
+    RecordingStatusChanged(RecordingStatusChanged),
+    ClipSaved(ClipSaved),
+    AutoClipEventDetected(AutoClipEventDetected),
+    AutoEditProgress(AutoEditProgress),
+    ClipsBatchProgress(BatchProgress),
+    JobStatusChanged(JobStatusChanged),
+    NotificationAdded(InAppNotification),
+    StorageMigrationProgress(MigrationProgress),
+    VideoProgress(VideoProgress),
+}
+
+impl AppEvent {
+    /// Tauri event name
+    pub fn name(&self) -> &'static str {
+        match self {
+            AppEvent::RecordingStatusChanged(_) => RECORDING_STATUS_CHANGED,
+            AppEvent::ClipSaved(_) => CLIP_SAVED,
+            AppEvent::AutoClipEventDetected(_) => AUTOCLIP_EVENT_DETECTED,
+            AppEvent::AutoEditProgress(_) => AUTOEDIT_PROGRESS,
+            AppEvent::ClipsBatchProgress(_) => CLIPS_BATCH_PROGRESS,
+            AppEvent::JobStatusChanged(_) => JOB_STATUS_CHANGED,
+            AppEvent::NotificationAdded(_) => NOTIFICATION_ADDED,
+            AppEvent::StorageMigrationProgress(_) => STORAGE_MIGRATION_PROGRESS,
+            AppEvent::VideoProgress(_) => VIDEO_PROGRESS,
+        }
+    }
+}
+
+/// Forwards [`AppEvent`]s to the frontend
+#[derive(Default)]
+pub struct EventBus {
+    app: OnceLock<AppHandle>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start forwarding events to the app's windows
+    pub fn attach(&self, app: AppHandle) {
+        if self.app.set(app).is_err() {
+            tracing::warn!("Event bus already attached");
+        }
+    }
+
+    pub fn emit(&self, event: AppEvent) {
+        let Some(app) = self.app.get() else {
+            tracing::trace!("Event bus not attached, dropping {}", event.name());
+            return;
+        };
+
+        if let Err(e) = app.emit(event.name(), &event) {
+            tracing::warn!("Failed to emit {}: {}", event.name(), e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_serialized_without_wrapper() {
+        let event = AppEvent::RecordingStatusChanged(RecordingStatusChanged {
+            status: RecordingStatus::Buffering,
+        });
+
+        assert_eq!(event.name(), "recording:status_changed");
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({ "status": "buffering" })
+        );
+    }
+
+    #[test]
+    fn test_emit_without_app_is_noop() {
+        let bus = EventBus::new();
+        bus.emit(AppEvent::ClipSaved(ClipSaved {
+            clip_path: "clip.mp4".to_string(),
+            game_id: "game".to_string(),
+            priority: 3,
+            duration_secs: 30.0,
+        }));
+    }
+}
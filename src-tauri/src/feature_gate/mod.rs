@@ -1,5 +1,10 @@
-use crate::auth::{AuthManager, SubscriptionTier};
-use std::sync::Arc;
+pub mod remote;
+
+use crate::auth::{AuthManager, SubscriptionTier, User};
+use crate::supabase::SupabaseClient;
+use remote::FeatureMatrix;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -26,24 +31,168 @@ pub enum Feature {
     AutoUpload,
     HighQualityExport,
     UnlimitedStorage,
+    CloudBackup,
+    ExtendedShareLinks,
+}
+
+impl Feature {
+    /// Key used in the remote feature matrix
+    pub fn key(&self) -> &'static str {
+        match self {
+            Feature::BasicRecording => "basic_recording",
+            Feature::BasicClipExtraction => "basic_clip_extraction",
+            Feature::WatermarkedExport => "watermarked_export",
+            Feature::AdvancedEditing => "advanced_editing",
+            Feature::CustomTransitions => "custom_transitions",
+            Feature::NoWatermark => "no_watermark",
+            Feature::AutoUpload => "auto_upload",
+            Feature::HighQualityExport => "high_quality_export",
+            Feature::UnlimitedStorage => "unlimited_storage",
+            Feature::CloudBackup => "cloud_backup",
+            Feature::ExtendedShareLinks => "extended_share_links",
+        }
+    }
+}
+
+/// Feature matrix in effect
+struct ActiveMatrix {
+    matrix: FeatureMatrix,
+    /// Read back from the on-disk cache. The file is user-editable, so it may
+    /// take features away but never grants more than the built-in tier rules.
+    from_cache: bool,
 }
 
 pub struct FeatureGate {
     auth: Arc<AuthManager>,
+    /// Remote feature matrix (falls back to built-in tier rules when absent)
+    matrix: RwLock<Option<ActiveMatrix>>,
+    cache_path: Option<PathBuf>,
 }
 
 impl FeatureGate {
     pub fn new(auth: Arc<AuthManager>) -> Self {
-        Self { auth }
+        Self {
+            auth,
+            matrix: RwLock::new(None),
+            cache_path: None,
+        }
+    }
+
+    /// Create a gate backed by a cached remote feature matrix at `cache_path`
+    pub fn with_cache(auth: Arc<AuthManager>, cache_path: PathBuf) -> Self {
+        let matrix = FeatureMatrix::load(&cache_path);
+        if matrix.is_some() {
+            tracing::info!("Loaded cached feature flags from {:?}", cache_path);
+        }
+
+        Self {
+            auth,
+            matrix: RwLock::new(matrix.map(|matrix| ActiveMatrix {
+                matrix,
+                from_cache: true,
+            })),
+            cache_path: Some(cache_path),
+        }
+    }
+
+    /// Fetch the feature matrix for `user` and cache it
+    ///
+    /// On failure the previously cached matrix stays in effect.
+    pub async fn refresh(&self, client: &SupabaseClient, user: &User) {
+        let matrix = match FeatureMatrix::fetch(client, user).await {
+            Ok(matrix) => matrix,
+            Err(e) => {
+                tracing::warn!("Failed to fetch feature flags, using cached values: {}", e);
+                return;
+            }
+        };
+
+        if let Some(path) = &self.cache_path {
+            if let Err(e) = matrix.save(path) {
+                tracing::warn!("Failed to cache feature flags: {}", e);
+            }
+        }
+
+        tracing::info!(
+            "Feature flags refreshed ({} tiers, {} overrides)",
+            matrix.tiers.len(),
+            matrix.overrides.len()
+        );
+        self.set_matrix(Some(matrix));
+    }
+
+    /// Replace the active feature matrix with one fetched from the server
+    pub fn set_matrix(&self, matrix: Option<FeatureMatrix>) {
+        if let Ok(mut current) = self.matrix.write() {
+            *current = matrix.map(|matrix| ActiveMatrix {
+                matrix,
+                from_cache: false,
+            });
+        }
+    }
+
+    /// Drop the feature matrix and its cache (on logout)
+    pub fn clear(&self) {
+        self.set_matrix(None);
+
+        if let Some(path) = &self.cache_path {
+            if let Err(e) = std::fs::remove_file(path) {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    tracing::warn!("Failed to remove feature flag cache: {}", e);
+                }
+            }
+        }
     }
 
     /// Check if a feature is available for the current user
+    ///
+    /// Order: per-user override, remote tier flag, built-in tier rules.
+    /// A cached matrix can only disable features.
     pub fn is_available(&self, feature: Feature) -> bool {
         let tier = match self.auth.get_tier() {
             Ok(tier) => tier,
             Err(_) => return false,
         };
+        let default = Self::default_availability(feature, &tier);
+
+        match self.remote(|matrix, user_id| matrix.lookup(feature, &tier, Some(user_id))) {
+            Some((enabled, true)) => enabled && default,
+            Some((enabled, false)) => enabled,
+            None => default,
+        }
+    }
+
+    /// Monthly limit for a metered feature (`None` = unlimited)
+    ///
+    /// `default` is the built-in limit for the current tier. A cached matrix
+    /// can only lower it.
+    pub fn limit(&self, feature_key: &str, default: Option<u32>) -> Option<u32> {
+        let Ok(tier) = self.auth.get_tier() else {
+            return default;
+        };
+
+        match self.remote(|matrix, _| matrix.limit(feature_key, &tier)) {
+            Some((limit, true)) => stricter_limit(limit, default),
+            Some((limit, false)) => limit,
+            None => default,
+        }
+    }
+
+    /// Look `f` up in the active matrix if it belongs to the current user,
+    /// along with whether the matrix came from the cache
+    fn remote<T>(&self, f: impl FnOnce(&FeatureMatrix, &str) -> Option<T>) -> Option<(T, bool)> {
+        let user_id = self.auth.get_current_user().ok().flatten()?.id;
+        let active = self.matrix.read().ok()?;
+        let active = active.as_ref()?;
+        if !active.matrix.belongs_to(&user_id) {
+            return None;
+        }
 
+        f(&active.matrix, &user_id).map(|value| (value, active.from_cache))
+    }
+
+    /// Built-in tier rules used when the remote matrix has no entry
+    fn default_availability(feature: Feature, tier: &SubscriptionTier) -> bool {
         match feature {
             // FREE tier features
             Feature::BasicRecording | Feature::BasicClipExtraction | Feature::WatermarkedExport => {
@@ -56,7 +205,9 @@ impl FeatureGate {
             | Feature::NoWatermark
             | Feature::AutoUpload
             | Feature::HighQualityExport
-            | Feature::UnlimitedStorage => matches!(tier, SubscriptionTier::Pro),
+            | Feature::UnlimitedStorage
+            | Feature::CloudBackup
+            | Feature::ExtendedShareLinks => matches!(tier, SubscriptionTier::Pro),
         }
     }
 
@@ -70,10 +221,30 @@ impl FeatureGate {
     }
 }
 
+/// The lower of two monthly limits (`None` = unlimited)
+fn stricter_limit(a: Option<u32>, b: Option<u32>) -> Option<u32> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (limit, None) | (None, limit) => limit,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::auth::User;
+    use tempfile::TempDir;
+
+    fn user(id: &str, tier: SubscriptionTier) -> User {
+        User {
+            id: id.to_string(),
+            email: "test@example.com".to_string(),
+            tier,
+            access_token: "access_token".to_string(),
+            refresh_token: "refresh_token".to_string(),
+            expires_at: 9999999999,
+        }
+    }
 
     #[test]
     fn test_free_tier_features() {
@@ -85,6 +256,7 @@ mod tests {
         assert!(gate.is_available(Feature::WatermarkedExport));
         assert!(!gate.is_available(Feature::AdvancedEditing));
         assert!(!gate.is_available(Feature::NoWatermark));
+        assert!(!gate.is_available(Feature::CloudBackup));
     }
 
     #[test]
@@ -106,4 +278,72 @@ mod tests {
         assert!(gate.is_available(Feature::AdvancedEditing));
         assert!(gate.is_available(Feature::NoWatermark));
     }
+
+    #[test]
+    fn test_remote_matrix_overrides_defaults() {
+        let auth = Arc::new(AuthManager::new());
+        auth.login(User {
+            id: "user-1".to_string(),
+            email: "test@example.com".to_string(),
+            tier: SubscriptionTier::Free,
+            access_token: "access_token".to_string(),
+            refresh_token: "refresh_token".to_string(),
+            expires_at: 9999999999,
+        })
+        .unwrap();
+
+        let gate = FeatureGate::new(auth);
+        assert!(!gate.is_available(Feature::AdvancedEditing));
+
+        let mut matrix = FeatureMatrix::default();
+        matrix
+            .tiers
+            .entry("FREE".to_string())
+            .or_default()
+            .insert("advanced_editing".to_string(), true);
+        matrix.overrides.insert("cloud_backup".to_string(), true);
+        matrix.override_user_id = Some("user-1".to_string());
+        gate.set_matrix(Some(matrix));
+
+        assert!(gate.is_available(Feature::AdvancedEditing));
+        assert!(gate.is_available(Feature::CloudBackup));
+        // Not in the matrix: built-in rules still apply
+        assert!(!gate.is_available(Feature::NoWatermark));
+        assert!(gate.is_available(Feature::BasicRecording));
+    }
+
+    #[test]
+    fn test_cached_matrix_only_restricts() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_path = temp_dir.path().join("feature_flags.json");
+
+        let mut matrix = FeatureMatrix::default();
+        let free = matrix.tiers.entry("FREE".to_string()).or_default();
+        free.insert("advanced_editing".to_string(), true);
+        free.insert("watermarked_export".to_string(), false);
+        matrix.overrides.insert("cloud_backup".to_string(), true);
+        matrix.override_user_id = Some("user-1".to_string());
+        let limits = matrix.limits.entry("FREE".to_string()).or_default();
+        limits.insert("auto_edit".to_string(), None);
+        limits.insert("cloud_upload".to_string(), Some(2));
+        matrix.save(&cache_path).unwrap();
+
+        let auth = Arc::new(AuthManager::new());
+        auth.login(user("user-1", SubscriptionTier::Free)).unwrap();
+        let gate = FeatureGate::with_cache(auth.clone(), cache_path.clone());
+
+        assert!(!gate.is_available(Feature::AdvancedEditing));
+        assert!(!gate.is_available(Feature::CloudBackup));
+        assert!(!gate.is_available(Feature::WatermarkedExport));
+        assert_eq!(gate.limit("auto_edit", Some(5)), Some(5));
+        assert_eq!(gate.limit("cloud_upload", Some(20)), Some(2));
+
+        // Another user on the same machine
+        auth.login(user("user-2", SubscriptionTier::Free)).unwrap();
+        assert!(gate.is_available(Feature::WatermarkedExport));
+        assert_eq!(gate.limit("cloud_upload", Some(20)), Some(20));
+
+        gate.clear();
+        assert!(!cache_path.exists());
+    }
 }
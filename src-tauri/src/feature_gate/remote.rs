@@ -0,0 +1,257 @@
+use super::Feature;
+use crate::auth::{SubscriptionTier, User};
+use crate::supabase::{SupabaseClient, SupabaseError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Server-side feature flag matrix
+///
+/// Fetched from Supabase at login and cached on disk so flags still apply
+/// offline. Tables:
+/// - `feature_flags(tier, feature, enabled)`: per-tier availability
+/// - `feature_flag_overrides(user_id, feature, enabled)`: per-user overrides
+/// - `feature_limits(tier, feature, monthly_limit)`: quotas for metered
+///   features (`monthly_limit` NULL means unlimited)
+///
+/// Features missing from the matrix fall back to the built-in tier rules.
+/// The matrix only applies to the user it was fetched for.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeatureMatrix {
+    /// Tier ("FREE" / "PRO") -> feature key -> enabled
+    pub tiers: HashMap<String, HashMap<String, bool>>,
+    /// Overrides for `override_user_id` only
+    pub overrides: HashMap<String, bool>,
+    /// User the matrix was fetched for
+    pub override_user_id: Option<String>,
+    /// Tier -> metered feature key -> monthly limit (`None` = unlimited)
+    #[serde(default)]
+    pub limits: HashMap<String, HashMap<String, Option<u32>>>,
+    pub fetched_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FeatureFlagRow {
+    tier: String,
+    feature: String,
+    enabled: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct FeatureOverrideRow {
+    feature: String,
+    enabled: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct FeatureLimitRow {
+    tier: String,
+    feature: String,
+    monthly_limit: Option<u32>,
+}
+
+fn tier_key(tier: &SubscriptionTier) -> &'static str {
+    match tier {
+        SubscriptionTier::Free => "FREE",
+        SubscriptionTier::Pro => "PRO",
+    }
+}
+
+impl FeatureMatrix {
+    /// Fetch tier flags, quota limits and the user's overrides
+    pub async fn fetch(
+        client: &SupabaseClient,
+        user: &User,
+    ) -> std::result::Result<Self, SupabaseError> {
+        let flags: Vec<FeatureFlagRow> = client
+            .table("feature_flags")
+            .select("tier,feature,enabled")
+            .auth(&user.access_token)
+            .execute()
+            .await?;
+
+        let overrides: Vec<FeatureOverrideRow> = client
+            .table("feature_flag_overrides")
+            .select("feature,enabled")
+            .eq("user_id", &user.id)
+            .auth(&user.access_token)
+            .execute()
+            .await?;
+
+        let limit_rows: Vec<FeatureLimitRow> = client
+            .table("feature_limits")
+            .select("tier,feature,monthly_limit")
+            .auth(&user.access_token)
+            .execute()
+            .await?;
+
+        let mut limits: HashMap<String, HashMap<String, Option<u32>>> = HashMap::new();
+        for row in limit_rows {
+            limits
+                .entry(row.tier.to_uppercase())
+                .or_default()
+                .insert(row.feature, row.monthly_limit);
+        }
+
+        let mut tiers: HashMap<String, HashMap<String, bool>> = HashMap::new();
+        for row in flags {
+            tiers
+                .entry(row.tier.to_uppercase())
+                .or_default()
+                .insert(row.feature, row.enabled);
+        }
+
+        Ok(Self {
+            tiers,
+            overrides: overrides
+                .into_iter()
+                .map(|row| (row.feature, row.enabled))
+                .collect(),
+            override_user_id: Some(user.id.clone()),
+            limits,
+            fetched_at: Some(Utc::now()),
+        })
+    }
+
+    /// Remote decision for a feature, or `None` to use the built-in rules
+    pub fn lookup(
+        &self,
+        feature: Feature,
+        tier: &SubscriptionTier,
+        user_id: Option<&str>,
+    ) -> Option<bool> {
+        let key = feature.key();
+
+        if user_id.is_some() && user_id == self.override_user_id.as_deref() {
+            if let Some(enabled) = self.overrides.get(key) {
+                return Some(*enabled);
+            }
+        }
+
+        self.tiers.get(tier_key(tier))?.get(key).copied()
+    }
+
+    /// Whether the matrix was fetched for `user_id`
+    pub fn belongs_to(&self, user_id: &str) -> bool {
+        self.override_user_id.as_deref() == Some(user_id)
+    }
+
+    /// Remote monthly limit for a metered feature
+    ///
+    /// `None` means the matrix has no entry; `Some(None)` means unlimited.
+    pub fn limit(&self, feature_key: &str, tier: &SubscriptionTier) -> Option<Option<u32>> {
+        self.limits.get(tier_key(tier))?.get(feature_key).copied()
+    }
+
+    pub fn load(path: &Path) -> Option<Self> {
+        let json = std::fs::read_to_string(path).ok()?;
+        match serde_json::from_str(&json) {
+            Ok(matrix) => Some(matrix),
+            Err(e) => {
+                tracing::warn!("Ignoring corrupted feature flag cache: {}", e);
+                None
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn matrix() -> FeatureMatrix {
+        let mut free = HashMap::new();
+        free.insert("advanced_editing".to_string(), true);
+        free.insert("basic_recording".to_string(), false);
+
+        let mut overrides = HashMap::new();
+        overrides.insert("cloud_backup".to_string(), true);
+
+        let mut tiers = HashMap::new();
+        tiers.insert("FREE".to_string(), free);
+
+        let mut free_limits = HashMap::new();
+        free_limits.insert("auto_edit".to_string(), Some(10));
+        free_limits.insert("youtube_upload".to_string(), None);
+
+        let mut limits = HashMap::new();
+        limits.insert("FREE".to_string(), free_limits);
+
+        FeatureMatrix {
+            tiers,
+            overrides,
+            override_user_id: Some("user-1".to_string()),
+            limits,
+            fetched_at: Some(Utc::now()),
+        }
+    }
+
+    #[test]
+    fn test_lookup_tier_flags() {
+        let matrix = matrix();
+        let free = SubscriptionTier::Free;
+
+        assert_eq!(
+            matrix.lookup(Feature::AdvancedEditing, &free, None),
+            Some(true)
+        );
+        assert_eq!(
+            matrix.lookup(Feature::BasicRecording, &free, None),
+            Some(false)
+        );
+        // Not in the matrix: fall back to built-in rules
+        assert_eq!(matrix.lookup(Feature::NoWatermark, &free, None), None);
+        assert_eq!(
+            matrix.lookup(Feature::AdvancedEditing, &SubscriptionTier::Pro, None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_overrides_only_apply_to_their_user() {
+        let matrix = matrix();
+        let free = SubscriptionTier::Free;
+
+        assert_eq!(
+            matrix.lookup(Feature::CloudBackup, &free, Some("user-1")),
+            Some(true)
+        );
+        assert_eq!(
+            matrix.lookup(Feature::CloudBackup, &free, Some("user-2")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_limits() {
+        let matrix = matrix();
+        let free = SubscriptionTier::Free;
+
+        assert_eq!(matrix.limit("auto_edit", &free), Some(Some(10)));
+        assert_eq!(matrix.limit("youtube_upload", &free), Some(None));
+        assert_eq!(matrix.limit("cloud_upload", &free), None);
+        assert_eq!(matrix.limit("auto_edit", &SubscriptionTier::Pro), None);
+    }
+
+    #[test]
+    fn test_cache_roundtrip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("feature_flags.json");
+
+        matrix().save(&path).unwrap();
+        let loaded = FeatureMatrix::load(&path).unwrap();
+        assert_eq!(
+            loaded.lookup(Feature::AdvancedEditing, &SubscriptionTier::Free, None),
+            Some(true)
+        );
+    }
+}
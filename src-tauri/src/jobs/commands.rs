@@ -0,0 +1,65 @@
+use super::{Job, JobKind, JobPriority};
+use crate::auth::middleware::require_auth;
+use crate::quota::MeteredFeature;
+use crate::video::AutoEditConfig;
+use crate::AppState;
+use tauri::State;
+
+/// List queued, running and failed background jobs
+#[tauri::command]
+pub async fn list_jobs(state: State<'_, AppState>) -> Result<Vec<Job>, String> {
+    // FREE tier feature - no authentication required
+    Ok(state.jobs.list().await)
+}
+
+/// Cancel a pending or failed background job
+///
+/// Returns false if the job doesn't exist or is already running.
+#[tauri::command]
+pub async fn cancel_job(state: State<'_, AppState>, job_id: String) -> Result<bool, String> {
+    // FREE tier feature - no authentication required
+    state.jobs.cancel(&job_id).await.map_err(|e| e.to_string())
+}
+
+/// Start a job deferred by a running game without waiting for it to end
+///
+/// Also releases auto-edits started directly (`start_auto_edit`), which wait
+/// for the game outside the queue under their progress job ID. Returns false
+/// if the job doesn't exist.
+#[tauri::command]
+pub async fn run_job_anyway(state: State<'_, AppState>, job_id: String) -> Result<bool, String> {
+    // FREE tier feature - no authentication required
+    if state
+        .jobs
+        .run_anyway(&job_id)
+        .await
+        .map_err(|e| e.to_string())?
+    {
+        return Ok(true);
+    }
+    Ok(state.auto_composer.run_anyway(&job_id))
+}
+
+/// Queue an auto-edit to run in the background, returning the job ID
+///
+/// The quota is checked now and again when the job runs.
+#[tauri::command]
+pub async fn queue_auto_edit(
+    state: State<'_, AppState>,
+    mut config: AutoEditConfig,
+) -> Result<String, String> {
+    // Require authentication (both FREE and PRO can use auto-edit)
+    let user = require_auth(&state.auth).map_err(|e| e.to_string())?;
+    config.user_id = Some(user.id);
+
+    state
+        .quota
+        .check(MeteredFeature::AutoEdit)
+        .map_err(|e| format!("Quota check failed: {}", e))?;
+
+    state
+        .jobs
+        .enqueue(JobKind::AutoEdit { config }, JobPriority::Normal)
+        .await
+        .map_err(|e| e.to_string())
+}
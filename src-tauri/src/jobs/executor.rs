@@ -0,0 +1,184 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use super::JobKind;
+use crate::notifications::center::NotificationKind;
+use crate::notifications::{event_label, Notification};
+use crate::quota::MeteredFeature;
+use crate::setup::demo::DEMO_GAME_PREFIX;
+use crate::telemetry::TelemetryEvent;
+use crate::video::{thumbnail, AutoEditConfig};
+use crate::youtube::{FailedUpload, UploadError, VideoMetadata};
+use crate::AppState;
+
+/// Run one background job against the application state
+pub async fn run(state: &AppState, kind: JobKind) -> Result<()> {
+    match kind {
+        JobKind::GenerateThumbnail { game_id, clip_path } => {
+            generate_thumbnail(state, &game_id, &clip_path).await
+        }
+        JobKind::YoutubeUpload {
+            video_path,
+            metadata,
+            thumbnail_path,
+            channel_id,
+        } => {
+            youtube_upload(
+                state,
+                channel_id.as_deref(),
+                &video_path,
+                metadata,
+                thumbnail_path.as_deref(),
+            )
+            .await
+        }
+        JobKind::Cleanup => state.cleanup_manager.cleanup_on_startup().await,
+        JobKind::AutoEdit { config } => auto_edit(state, config).await,
+        JobKind::RefreshVideoStats => state.youtube_manager.refresh_history_stats().await,
+    }
+}
+
+/// Generate a midpoint thumbnail and store its path in the clip metadata
+async fn generate_thumbnail(state: &AppState, game_id: &str, clip_path: &Path) -> Result<()> {
+    if !clip_path.exists() {
+        // Clip was deleted before the job ran; nothing to do
+        tracing::debug!("Skipping thumbnail for missing clip: {:?}", clip_path);
+        return Ok(());
+    }
+
+    let output_dir = state.storage.game_path(game_id).join("thumbnails");
+    std::fs::create_dir_all(&output_dir)
+        .with_context(|| format!("Failed to create {:?}", output_dir))?;
+
+    let thumbnail_path = thumbnail::auto_generate_thumbnail(clip_path, &output_dir).await?;
+
+    let file_path = clip_path.to_string_lossy();
+    let clips = state.storage.load_clip_metadata(game_id)?;
+    if let Some(mut clip) = clips.into_iter().find(|c| c.file_path == file_path) {
+        clip.thumbnail_path = Some(thumbnail_path.to_string_lossy().to_string());
+        state.storage.save_clip_metadata(game_id, &clip)?;
+
+        let champion = state
+            .storage
+            .load_game_metadata(game_id)
+            .ok()
+            .map(|game| game.champion);
+        if !game_id.starts_with(DEMO_GAME_PREFIX) {
+            state.notification_center.push(
+                NotificationKind::ClipSaved,
+                format!("Clip saved: {}", event_label(&clip.event_type)),
+                champion.clone().unwrap_or_else(|| game_id.to_string()),
+            );
+        }
+        state
+            .notifications
+            .notify_in_background(Notification::ClipSaved {
+                clip_path: clip_path.to_path_buf(),
+                thumbnail_path: Some(thumbnail_path),
+                event_type: clip.event_type,
+                priority: clip.priority,
+                champion,
+            });
+    }
+
+    Ok(())
+}
+
+async fn youtube_upload(
+    state: &AppState,
+    channel_id: Option<&str>,
+    video_path: &Path,
+    metadata: VideoMetadata,
+    thumbnail_path: Option<&Path>,
+) -> Result<()> {
+    if !video_path.exists() {
+        anyhow::bail!("Video file not found: {:?}", video_path);
+    }
+
+    state.quota.check(MeteredFeature::YoutubeUpload)?;
+
+    let result = state
+        .youtube_manager
+        .upload_video(channel_id, video_path, metadata.clone(), thumbnail_path)
+        .await;
+
+    state
+        .telemetry
+        .record(TelemetryEvent::UploadCompleted {
+            success: result.is_ok(),
+        })
+        .await;
+
+    let video = match result {
+        Ok(video) => video,
+        Err(e) => {
+            let upload_error = UploadError::of(&e);
+            let failure = FailedUpload::new(
+                &upload_error,
+                video_path.to_path_buf(),
+                metadata,
+                thumbnail_path.map(Path::to_path_buf),
+                channel_id.map(str::to_string),
+            );
+            if let Err(history_error) = state.youtube_manager.record_failed_upload(failure).await {
+                tracing::error!("Failed to record failed upload: {}", history_error);
+            }
+
+            // Retrying won't help; it stays in the history for the user
+            if !upload_error.retryable {
+                tracing::warn!("Giving up on YouTube upload: {}", upload_error);
+                return Ok(());
+            }
+            return Err(e);
+        }
+    };
+    tracing::info!("Retried YouTube upload succeeded: {}", video.id);
+
+    if let Err(e) = state.youtube_manager.clear_failed_upload(video_path).await {
+        tracing::warn!("Failed to clear failed upload from history: {}", e);
+    }
+
+    state.notification_center.push(
+        NotificationKind::UploadCompleted,
+        "Uploaded to YouTube",
+        video.title.clone(),
+    );
+    state
+        .notifications
+        .notify_in_background(Notification::UploadCompleted {
+            video_id: video.id,
+            title: video.title,
+            thumbnail_url: video.thumbnail_url,
+        });
+
+    match state.quota.record(MeteredFeature::YoutubeUpload) {
+        Ok(status) => state.notification_center.quota_recorded(&status),
+        Err(e) => tracing::error!("Failed to record YouTube upload usage: {}", e),
+    }
+
+    Ok(())
+}
+
+async fn auto_edit(state: &AppState, config: AutoEditConfig) -> Result<()> {
+    state.quota.check(MeteredFeature::AutoEdit)?;
+
+    let job_id = format!("auto_edit_{}", chrono::Local::now().format("%Y%m%d_%H%M%S"));
+    let result = state.auto_composer.compose(config, job_id).await;
+
+    state
+        .telemetry
+        .record(TelemetryEvent::AutoEditCompleted {
+            success: result.is_ok(),
+        })
+        .await;
+
+    let result = result?;
+    tracing::info!("Queued auto-edit completed: {:?}", result.output_path);
+
+    match state.quota.record(MeteredFeature::AutoEdit) {
+        Ok(status) => state.notification_center.quota_recorded(&status),
+        Err(e) => tracing::error!("Failed to record auto-edit usage: {}", e),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,698 @@
+pub mod commands;
+pub mod executor;
+
+/// Background job scheduler for deferred heavy work
+///
+/// Jobs (thumbnail generation, upload retries, cleanup, auto-edit, video
+/// stats refresh) are kept
+/// in a persisted queue (`jobs.json`) and run one at a time by a single
+/// worker, highest priority first. Failed jobs are retried with exponential
+/// backoff until they run out of attempts. Jobs interrupted by an app exit
+/// are put back in the queue and resumed on the next launch.
+///
+/// Heavy FFmpeg jobs are deferred while a League match is running (it tanks
+/// in-game fps) unless the user chose to run them anyway.
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, Notify};
+use tracing::{debug, error, info, warn};
+
+use crate::events::{AppEvent, EventBus, JobStatusChanged};
+use crate::lcu::GameActivity;
+use crate::video::AutoEditConfig;
+use crate::youtube::VideoMetadata;
+
+/// Worker wakes up at least this often to pick up jobs whose backoff expired
+const POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Delay before the first retry (doubled on every further attempt)
+const RETRY_BASE_DELAY_SECS: i64 = 30;
+
+/// Upper bound for the retry delay
+const MAX_RETRY_DELAY_SECS: i64 = 60 * 60;
+
+/// Permanently failed jobs kept for display (oldest dropped first)
+const MAX_FAILED_JOBS: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobPriority {
+    Low,
+    Normal,
+    High,
+}
+
+/// Work to run in the background
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JobKind {
+    /// Generate a thumbnail for a saved clip and record it in the clip metadata
+    GenerateThumbnail { game_id: String, clip_path: PathBuf },
+    /// Retry a failed YouTube upload
+    YoutubeUpload {
+        video_path: PathBuf,
+        metadata: VideoMetadata,
+        thumbnail_path: Option<PathBuf>,
+        /// Linked channel to upload to (`None` = active channel)
+        #[serde(default)]
+        channel_id: Option<String>,
+    },
+    /// Remove old temp segments and enforce the log size limit
+    Cleanup,
+    /// Compose an auto-edit video
+    AutoEdit { config: AutoEditConfig },
+    /// Update YouTube upload history view counts
+    RefreshVideoStats,
+}
+
+impl JobKind {
+    /// Attempts before a job is marked as failed
+    pub fn max_attempts(&self) -> u32 {
+        match self {
+            JobKind::GenerateThumbnail { .. } => 3,
+            JobKind::YoutubeUpload { .. } => 5,
+            JobKind::Cleanup => 1,
+            JobKind::AutoEdit { .. } => 2,
+            JobKind::RefreshVideoStats => 2,
+        }
+    }
+
+    /// Heavy FFmpeg work that shouldn't run during a match
+    pub fn is_heavy(&self) -> bool {
+        matches!(self, JobKind::AutoEdit { .. })
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            JobKind::GenerateThumbnail { .. } => "generate_thumbnail",
+            JobKind::YoutubeUpload { .. } => "youtube_upload",
+            JobKind::Cleanup => "cleanup",
+            JobKind::AutoEdit { .. } => "auto_edit",
+            JobKind::RefreshVideoStats => "refresh_video_stats",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Pending,
+    /// Heavy job waiting for the current game to finish
+    Deferred,
+    Running,
+    /// Out of attempts (completed jobs are removed from the queue)
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: String,
+    pub kind: JobKind,
+    pub priority: JobPriority,
+    pub status: JobStatus,
+    pub attempts: u32,
+    pub max_attempts: u32,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Not started before this time (retry backoff)
+    pub run_after: DateTime<Utc>,
+    /// Run even while a game is in progress
+    #[serde(default)]
+    pub run_anyway: bool,
+}
+
+impl Job {
+    fn deferrable(&self) -> bool {
+        self.kind.is_heavy() && !self.run_anyway
+    }
+}
+
+/// Delay before retrying a job that has failed `attempts` times
+pub fn retry_delay(attempts: u32) -> chrono::Duration {
+    let exponent = attempts.saturating_sub(1).min(16);
+    let secs = (RETRY_BASE_DELAY_SECS << exponent).min(MAX_RETRY_DELAY_SECS);
+    chrono::Duration::seconds(secs)
+}
+
+/// In-memory job queue (persisted by [`JobScheduler`])
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JobQueue {
+    jobs: Vec<Job>,
+    /// A game is running, so heavy jobs are deferred
+    #[serde(skip)]
+    game_active: bool,
+}
+
+impl JobQueue {
+    pub fn push(&mut self, kind: JobKind, priority: JobPriority, now: DateTime<Utc>) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        let status = if self.game_active && kind.is_heavy() {
+            JobStatus::Deferred
+        } else {
+            JobStatus::Pending
+        };
+        self.jobs.push(Job {
+            id: id.clone(),
+            max_attempts: kind.max_attempts(),
+            kind,
+            priority,
+            status,
+            attempts: 0,
+            last_error: None,
+            created_at: now,
+            run_after: now,
+            run_anyway: false,
+        });
+        id
+    }
+
+    /// Whether a pending or running job of the same kind exists
+    pub fn has_active(&self, kind: &JobKind) -> bool {
+        self.jobs.iter().any(|job| {
+            job.status != JobStatus::Failed
+                && std::mem::discriminant(&job.kind) == std::mem::discriminant(kind)
+        })
+    }
+
+    /// Put jobs left running by a previous session back in the queue
+    pub fn resume_interrupted(&mut self) -> usize {
+        let mut resumed = 0;
+        for job in self.jobs.iter_mut() {
+            match job.status {
+                JobStatus::Running => {
+                    job.status = JobStatus::Pending;
+                    resumed += 1;
+                }
+                // Re-deferred once a game is seen again
+                JobStatus::Deferred => job.status = JobStatus::Pending,
+                _ => {}
+            }
+        }
+        resumed
+    }
+
+    /// Defer or release heavy jobs when a game starts or ends
+    ///
+    /// Returns the jobs whose status changed.
+    pub fn set_game_active(&mut self, active: bool) -> Vec<Job> {
+        self.game_active = active;
+
+        let (from, to) = if active {
+            (JobStatus::Pending, JobStatus::Deferred)
+        } else {
+            (JobStatus::Deferred, JobStatus::Pending)
+        };
+
+        self.jobs
+            .iter_mut()
+            .filter(|job| job.status == from && (!active || job.deferrable()))
+            .map(|job| {
+                job.status = to;
+                job.clone()
+            })
+            .collect()
+    }
+
+    /// Let a job run even during a game; returns the job if it exists
+    pub fn run_anyway(&mut self, id: &str) -> Option<Job> {
+        let job = self.jobs.iter_mut().find(|job| job.id == id)?;
+
+        job.run_anyway = true;
+        if job.status == JobStatus::Deferred {
+            job.status = JobStatus::Pending;
+        }
+        Some(job.clone())
+    }
+
+    /// Mark the next runnable job as running and return it
+    ///
+    /// Highest priority first, oldest first within a priority.
+    pub fn take_next(&mut self, now: DateTime<Utc>) -> Option<Job> {
+        let job = self
+            .jobs
+            .iter_mut()
+            .filter(|job| job.status == JobStatus::Pending && job.run_after <= now)
+            .max_by(|a, b| {
+                a.priority
+                    .cmp(&b.priority)
+                    .then_with(|| b.created_at.cmp(&a.created_at))
+            })?;
+
+        job.status = JobStatus::Running;
+        job.attempts += 1;
+        Some(job.clone())
+    }
+
+    pub fn complete(&mut self, id: &str) {
+        self.jobs.retain(|job| job.id != id);
+    }
+
+    /// Schedule a retry, or mark the job failed once it's out of attempts
+    pub fn fail(&mut self, id: &str, error: String, now: DateTime<Utc>) {
+        let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) else {
+            return;
+        };
+
+        job.last_error = Some(error);
+        if job.attempts >= job.max_attempts {
+            job.status = JobStatus::Failed;
+        } else {
+            job.status = if self.game_active && job.deferrable() {
+                JobStatus::Deferred
+            } else {
+                JobStatus::Pending
+            };
+            job.run_after = now + retry_delay(job.attempts);
+        }
+
+        self.prune_failed();
+    }
+
+    /// Remove a job that isn't running; returns whether it was removed
+    pub fn cancel(&mut self, id: &str) -> bool {
+        let before = self.jobs.len();
+        self.jobs
+            .retain(|job| job.id != id || job.status == JobStatus::Running);
+        self.jobs.len() != before
+    }
+
+    /// Time until the next pending job becomes runnable
+    pub fn next_wake(&self, now: DateTime<Utc>) -> Option<Duration> {
+        self.jobs
+            .iter()
+            .filter(|job| job.status == JobStatus::Pending)
+            .map(|job| (job.run_after - now).to_std().unwrap_or(Duration::ZERO))
+            .min()
+    }
+
+    pub fn jobs(&self) -> &[Job] {
+        &self.jobs
+    }
+
+    fn prune_failed(&mut self) {
+        let failed = self
+            .jobs
+            .iter()
+            .filter(|job| job.status == JobStatus::Failed)
+            .count();
+
+        let mut excess = failed.saturating_sub(MAX_FAILED_JOBS);
+        self.jobs.retain(|job| {
+            if excess > 0 && job.status == JobStatus::Failed {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+/// Persisted job queue with a single background worker
+pub struct JobScheduler {
+    path: PathBuf,
+    queue: Mutex<JobQueue>,
+    notify: Notify,
+    events: Arc<EventBus>,
+    game_activity: Option<Arc<GameActivity>>,
+}
+
+impl JobScheduler {
+    /// Load the queue from `path`, resuming jobs interrupted by the last exit
+    pub fn load(path: PathBuf) -> Result<Self> {
+        let mut queue: JobQueue = if path.exists() {
+            let json = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read job queue: {:?}", path))?;
+            serde_json::from_str(&json).unwrap_or_else(|e| {
+                warn!("Discarding corrupted job queue: {}", e);
+                JobQueue::default()
+            })
+        } else {
+            JobQueue::default()
+        };
+
+        let resumed = queue.resume_interrupted();
+        if resumed > 0 {
+            info!("Resuming {} interrupted background jobs", resumed);
+        }
+
+        Self::save(&path, &queue)?;
+
+        Ok(Self {
+            path,
+            queue: Mutex::new(queue),
+            notify: Notify::new(),
+            events: Arc::new(EventBus::new()),
+            game_activity: None,
+        })
+    }
+
+    /// Publish deferred/released job status changes on `events`
+    pub fn with_events(mut self, events: Arc<EventBus>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Defer heavy jobs while `activity` reports a running game
+    pub fn with_game_activity(mut self, activity: Arc<GameActivity>) -> Self {
+        self.game_activity = Some(activity);
+        self
+    }
+
+    /// Queue a job and wake the worker
+    pub async fn enqueue(&self, kind: JobKind, priority: JobPriority) -> Result<String> {
+        let mut queue = self.queue.lock().await;
+        let name = kind.name();
+        let id = queue.push(kind, priority, Utc::now());
+        Self::save(&self.path, &queue)?;
+        drop(queue);
+
+        debug!("Queued {} job {} ({:?})", name, id, priority);
+        self.notify.notify_one();
+        Ok(id)
+    }
+
+    /// Queue a job unless one of the same kind is already pending or running
+    pub async fn enqueue_unique(
+        &self,
+        kind: JobKind,
+        priority: JobPriority,
+    ) -> Result<Option<String>> {
+        if self.queue.lock().await.has_active(&kind) {
+            return Ok(None);
+        }
+        self.enqueue(kind, priority).await.map(Some)
+    }
+
+    pub async fn list(&self) -> Vec<Job> {
+        self.queue.lock().await.jobs().to_vec()
+    }
+
+    /// Start a deferred job now instead of waiting for the game to end
+    ///
+    /// Returns false if the job doesn't exist.
+    pub async fn run_anyway(&self, id: &str) -> Result<bool> {
+        let mut queue = self.queue.lock().await;
+        let Some(job) = queue.run_anyway(id) else {
+            return Ok(false);
+        };
+        Self::save(&self.path, &queue)?;
+        drop(queue);
+
+        info!(
+            "Running {} job {} despite game in progress",
+            job.kind.name(),
+            id
+        );
+        self.emit_status(&job);
+        self.notify.notify_one();
+        Ok(true)
+    }
+
+    /// Remove a pending or failed job (running jobs can't be cancelled)
+    pub async fn cancel(&self, id: &str) -> Result<bool> {
+        let mut queue = self.queue.lock().await;
+        let cancelled = queue.cancel(id);
+        if cancelled {
+            Self::save(&self.path, &queue)?;
+        }
+        Ok(cancelled)
+    }
+
+    /// Spawn the worker, running each job through `runner`
+    pub fn start<F, Fut>(self: &Arc<Self>, runner: F)
+    where
+        F: Fn(JobKind) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<()>> + Send,
+    {
+        let scheduler = Arc::clone(self);
+
+        if let Some(activity) = &self.game_activity {
+            let scheduler = Arc::clone(self);
+            let mut receiver = activity.subscribe();
+            tokio::spawn(async move {
+                loop {
+                    let in_game = *receiver.borrow_and_update() == Some(true);
+                    scheduler.set_game_active(in_game).await;
+                    if receiver.changed().await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        tokio::spawn(async move {
+            info!("Job scheduler started");
+
+            loop {
+                let job = scheduler.take_next().await;
+
+                let Some(job) = job else {
+                    let wait = scheduler
+                        .queue
+                        .lock()
+                        .await
+                        .next_wake(Utc::now())
+                        .map_or(POLL_INTERVAL, |wake| wake.min(POLL_INTERVAL));
+
+                    tokio::select! {
+                        _ = scheduler.notify.notified() => {}
+                        _ = tokio::time::sleep(wait) => {}
+                    }
+                    continue;
+                };
+
+                info!(
+                    "Running {} job {} (attempt {}/{})",
+                    job.kind.name(),
+                    job.id,
+                    job.attempts,
+                    job.max_attempts
+                );
+
+                let result = runner(job.kind.clone()).await;
+                scheduler.finish(&job, result).await;
+            }
+        });
+    }
+
+    async fn set_game_active(&self, active: bool) {
+        let mut queue = self.queue.lock().await;
+        let changed = queue.set_game_active(active);
+        if changed.is_empty() {
+            return;
+        }
+        if let Err(e) = Self::save(&self.path, &queue) {
+            error!("Failed to persist job queue: {}", e);
+        }
+        drop(queue);
+
+        if active {
+            info!("Game in progress, deferring {} heavy jobs", changed.len());
+        } else {
+            info!("Game finished, releasing {} deferred jobs", changed.len());
+            self.notify.notify_one();
+        }
+        for job in &changed {
+            self.emit_status(job);
+        }
+    }
+
+    fn emit_status(&self, job: &Job) {
+        self.events
+            .emit(AppEvent::JobStatusChanged(JobStatusChanged {
+                job_id: job.id.clone(),
+                job_type: job.kind.name().to_string(),
+                status: job.status,
+            }));
+    }
+
+    async fn take_next(&self) -> Option<Job> {
+        let mut queue = self.queue.lock().await;
+        let job = queue.take_next(Utc::now())?;
+        if let Err(e) = Self::save(&self.path, &queue) {
+            error!("Failed to persist job queue: {}", e);
+        }
+        Some(job)
+    }
+
+    async fn finish(&self, job: &Job, result: Result<()>) {
+        let mut queue = self.queue.lock().await;
+
+        match result {
+            Ok(()) => {
+                info!("{} job {} completed", job.kind.name(), job.id);
+                queue.complete(&job.id);
+            }
+            Err(e) => {
+                warn!(
+                    "{} job {} failed (attempt {}/{}): {:#}",
+                    job.kind.name(),
+                    job.id,
+                    job.attempts,
+                    job.max_attempts,
+                    e
+                );
+                queue.fail(&job.id, format!("{:#}", e), Utc::now());
+            }
+        }
+
+        if let Err(e) = Self::save(&self.path, &queue) {
+            error!("Failed to persist job queue: {}", e);
+        }
+    }
+
+    fn save(path: &Path, queue: &JobQueue) -> Result<()> {
+        let json = serde_json::to_string_pretty(queue)?;
+        std::fs::write(path, json).with_context(|| format!("Failed to write job queue: {:?}", path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn thumbnail_job(name: &str) -> JobKind {
+        JobKind::GenerateThumbnail {
+            game_id: "game".to_string(),
+            clip_path: PathBuf::from(name),
+        }
+    }
+
+    #[test]
+    fn test_take_next_by_priority_then_age() {
+        let now = Utc::now();
+        let mut queue = JobQueue::default();
+
+        let low = queue.push(JobKind::Cleanup, JobPriority::Low, now);
+        let first = queue.push(thumbnail_job("a.mp4"), JobPriority::Normal, now);
+        let second = queue.push(
+            thumbnail_job("b.mp4"),
+            JobPriority::Normal,
+            now + chrono::Duration::seconds(1),
+        );
+
+        let later = now + chrono::Duration::seconds(5);
+        assert_eq!(queue.take_next(later).unwrap().id, first);
+        assert_eq!(queue.take_next(later).unwrap().id, second);
+        assert_eq!(queue.take_next(later).unwrap().id, low);
+        assert!(queue.take_next(later).is_none());
+    }
+
+    #[test]
+    fn test_retry_with_backoff_until_failed() {
+        let now = Utc::now();
+        let mut queue = JobQueue::default();
+        let id = queue.push(thumbnail_job("a.mp4"), JobPriority::Normal, now);
+
+        let job = queue.take_next(now).unwrap();
+        queue.fail(&job.id, "ffmpeg crashed".to_string(), now);
+
+        // Backoff: not runnable immediately
+        assert!(queue.take_next(now).is_none());
+        assert_eq!(
+            queue.next_wake(now),
+            Some(Duration::from_secs(RETRY_BASE_DELAY_SECS as u64))
+        );
+
+        let mut at = now;
+        for _ in 1..3 {
+            at += chrono::Duration::hours(2);
+            let job = queue.take_next(at).unwrap();
+            queue.fail(&job.id, "ffmpeg crashed".to_string(), at);
+        }
+
+        let job = &queue.jobs()[0];
+        assert_eq!(job.id, id);
+        assert_eq!(job.status, JobStatus::Failed);
+        assert_eq!(job.attempts, 3);
+        assert!(queue.take_next(at + chrono::Duration::hours(2)).is_none());
+    }
+
+    #[test]
+    fn test_retry_delay_is_capped() {
+        assert_eq!(retry_delay(1), chrono::Duration::seconds(30));
+        assert_eq!(retry_delay(2), chrono::Duration::seconds(60));
+        assert_eq!(
+            retry_delay(30),
+            chrono::Duration::seconds(MAX_RETRY_DELAY_SECS)
+        );
+    }
+
+    #[test]
+    fn test_cancel_skips_running_jobs() {
+        let now = Utc::now();
+        let mut queue = JobQueue::default();
+        let running = queue.push(JobKind::Cleanup, JobPriority::High, now);
+        let pending = queue.push(thumbnail_job("a.mp4"), JobPriority::Low, now);
+        queue.take_next(now);
+
+        assert!(!queue.cancel(&running));
+        assert!(queue.cancel(&pending));
+        assert_eq!(queue.jobs().len(), 1);
+    }
+
+    #[test]
+    fn test_heavy_jobs_deferred_during_game() {
+        let now = Utc::now();
+        let mut queue = JobQueue::default();
+        let edit = JobKind::AutoEdit {
+            config: AutoEditConfig {
+                target_duration: 60,
+                game_ids: vec!["game".to_string()],
+                ..Default::default()
+            },
+        };
+
+        let first = queue.push(edit.clone(), JobPriority::High, now);
+        let deferred = queue.set_game_active(true);
+        assert_eq!(deferred.len(), 1);
+        assert_eq!(deferred[0].status, JobStatus::Deferred);
+
+        // Light jobs still run; heavy ones queued mid-game are deferred too
+        let second = queue.push(edit, JobPriority::High, now);
+        queue.push(JobKind::Cleanup, JobPriority::Low, now);
+        assert_eq!(queue.take_next(now).unwrap().kind.name(), "cleanup");
+        assert!(queue.take_next(now).is_none());
+
+        // "Run anyway" overrides the deferral
+        assert!(queue.run_anyway(&second).is_some());
+        assert_eq!(queue.take_next(now).unwrap().id, second);
+
+        assert_eq!(queue.set_game_active(false).len(), 1);
+        assert_eq!(queue.take_next(now).unwrap().id, first);
+    }
+
+    #[tokio::test]
+    async fn test_interrupted_jobs_resume_after_restart() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("jobs.json");
+
+        let scheduler = JobScheduler::load(path.clone()).unwrap();
+        scheduler
+            .enqueue(JobKind::Cleanup, JobPriority::Low)
+            .await
+            .unwrap();
+        assert!(scheduler
+            .enqueue_unique(JobKind::Cleanup, JobPriority::Low)
+            .await
+            .unwrap()
+            .is_none());
+
+        // Simulate an exit while the job is running
+        let job = scheduler.take_next().await.unwrap();
+        drop(scheduler);
+
+        let reloaded = JobScheduler::load(path).unwrap();
+        let jobs = reloaded.list().await;
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].id, job.id);
+        assert_eq!(jobs[0].status, JobStatus::Pending);
+    }
+}
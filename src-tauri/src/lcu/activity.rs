@@ -0,0 +1,212 @@
+/// Shared "is a game running" state
+///
+/// A single background task polls the LCU gameflow phase and publishes the
+/// result so the replay buffer and the job scheduler don't each need their
+/// own client. `None` means the League client isn't reachable (closed,
+/// restarting, or not polled yet). While it isn't, a running game process
+/// still counts as in game ([`GameDetection::Process`]). The raw gameflow
+/// phase is published too, for watchers that act before the game starts.
+use serde::Serialize;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::watch;
+
+use super::process::ProcessWatcher;
+use super::{GameFlowPhase, LcuClient};
+
+/// Interval between gameflow phase checks
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Where the current state comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameDetection {
+    /// Nothing detected
+    #[default]
+    None,
+    /// League client gameflow phase
+    Lcu,
+    /// Game process found while the client is unreachable (no game mode,
+    /// degraded auto-capture)
+    Process,
+}
+
+pub struct GameActivity {
+    state: watch::Sender<Option<bool>>,
+    /// Gameflow phase (`None` = client unavailable)
+    phase: watch::Sender<Option<GameFlowPhase>>,
+    /// LCU `gameMode` of the running match (e.g. "CLASSIC", "ARAM")
+    game_mode: RwLock<Option<String>>,
+    detection: RwLock<GameDetection>,
+}
+
+impl GameActivity {
+    pub fn new() -> Self {
+        let (state, _) = watch::channel(None);
+        let (phase, _) = watch::channel(None);
+        Self {
+            state,
+            phase,
+            game_mode: RwLock::new(None),
+            detection: RwLock::new(GameDetection::None),
+        }
+    }
+
+    /// Last observed state (`None` = client unavailable)
+    pub fn current(&self) -> Option<bool> {
+        *self.state.borrow()
+    }
+
+    /// Whether a match is running right now
+    pub fn is_in_game(&self) -> bool {
+        self.current() == Some(true)
+    }
+
+    /// Last observed gameflow phase
+    pub fn phase(&self) -> Option<GameFlowPhase> {
+        *self.phase.borrow()
+    }
+
+    /// Receiver notified whenever the gameflow phase changes
+    pub fn subscribe_phase(&self) -> watch::Receiver<Option<GameFlowPhase>> {
+        self.phase.subscribe()
+    }
+
+    fn set_phase(&self, phase: Option<GameFlowPhase>) {
+        self.phase.send_if_modified(|current| {
+            let changed = *current != phase;
+            *current = phase;
+            changed
+        });
+    }
+
+    /// Game mode of the running match, if any
+    pub fn game_mode(&self) -> Option<String> {
+        self.game_mode.read().ok()?.clone()
+    }
+
+    /// How the current state was detected
+    pub fn detection(&self) -> GameDetection {
+        self.detection
+            .read()
+            .map(|detection| *detection)
+            .unwrap_or_default()
+    }
+
+    fn set_detection(&self, detection: GameDetection) {
+        if let Ok(mut current) = self.detection.write() {
+            if *current != detection {
+                tracing::info!("Game detection: {:?}", detection);
+            }
+            *current = detection;
+        }
+    }
+
+    /// No client: fall back to the process list
+    fn set_from_process(&self, watcher: &mut ProcessWatcher) {
+        if let Ok(mut game_mode) = self.game_mode.write() {
+            *game_mode = None;
+        }
+        self.set_phase(None);
+        if watcher.is_game_running() {
+            self.set_detection(GameDetection::Process);
+            self.set(Some(true));
+        } else {
+            self.set_detection(GameDetection::None);
+            self.set(None);
+        }
+    }
+
+    /// Receiver notified whenever the state changes
+    pub fn subscribe(&self) -> watch::Receiver<Option<bool>> {
+        self.state.subscribe()
+    }
+
+    /// Publish a new observation (no-op if unchanged)
+    pub fn set(&self, in_game: Option<bool>) {
+        self.state.send_if_modified(|state| {
+            let changed = *state != in_game;
+            *state = in_game;
+            changed
+        });
+    }
+
+    /// Wait until no match is running
+    pub async fn wait_until_idle(&self) {
+        let mut receiver = self.subscribe();
+        // The sender lives as long as `self`, so this can't fail
+        let _ = receiver.wait_for(|state| *state != Some(true)).await;
+    }
+
+    /// Start polling the League client
+    pub fn start(self: &Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let activity = Arc::clone(self);
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(POLL_INTERVAL);
+            let mut lcu = LcuClient::new();
+            let mut processes = ProcessWatcher::new();
+
+            loop {
+                interval.tick().await;
+
+                if !lcu.is_connected() && lcu.connect().await.is_err() {
+                    activity.set_from_process(&mut processes);
+                    continue;
+                }
+
+                match lcu.get_game_session().await {
+                    Ok(session) => {
+                        let in_game = session.phase.is_in_game();
+                        // Updated before publishing so subscribers see the new mode
+                        if let Ok(mut game_mode) = activity.game_mode.write() {
+                            *game_mode = session
+                                .game_data
+                                .filter(|_| in_game)
+                                .map(|data| data.game_mode);
+                        }
+                        activity.set_detection(GameDetection::Lcu);
+                        activity.set_phase(Some(session.phase));
+                        activity.set(Some(in_game));
+                    }
+                    Err(e) => {
+                        // Client restarted or closed; reconnect on the next tick
+                        tracing::debug!("Game activity: LCU unavailable: {}", e);
+                        lcu = LcuClient::new();
+                        activity.set_from_process(&mut processes);
+                    }
+                }
+            }
+        })
+    }
+}
+
+impl Default for GameActivity {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_wait_until_idle_after_game_ends() {
+        let activity = Arc::new(GameActivity::new());
+        assert_eq!(activity.current(), None);
+
+        activity.set(Some(true));
+        assert!(activity.is_in_game());
+
+        let waiter = {
+            let activity = Arc::clone(&activity);
+            tokio::spawn(async move { activity.wait_until_idle().await })
+        };
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+
+        activity.set(Some(false));
+        waiter.await.unwrap();
+    }
+}
@@ -1,6 +1,7 @@
-use super::{GameInfo, LcuClient};
+use super::{install, GameInfo, LcuClient};
 use crate::AppState;
 use once_cell::sync::Lazy;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tauri::State;
 use tokio::sync::Mutex;
@@ -60,3 +61,40 @@ pub async fn is_in_game(state: State<'_, AppState>) -> Result<bool, String> {
 
     client.is_in_game().await.map_err(|e| e.to_string())
 }
+
+/// Detected League of Legends install directory (honours the override)
+#[tauri::command]
+pub async fn get_league_install_path() -> Result<Option<String>, String> {
+    // FREE tier feature - no authentication required
+    Ok(LcuClient::detect_install_dir().map(|dir| dir.to_string_lossy().to_string()))
+}
+
+/// Set the League of Legends install directory, or clear it with `None` to
+/// go back to auto-detection
+#[tauri::command]
+pub async fn set_league_install_path(
+    state: State<'_, AppState>,
+    path: Option<String>,
+) -> Result<(), String> {
+    // FREE tier feature - no authentication required
+    let dir = match path {
+        Some(path) => {
+            let dir = PathBuf::from(path.trim());
+            if !dir.is_absolute() || path.contains("..") {
+                return Err(format!("Invalid install path: {}", path));
+            }
+            if !install::is_league_install(&dir) {
+                return Err(format!("LeagueClient.exe not found in {}", dir.display()));
+            }
+            Some(dir)
+        }
+        None => None,
+    };
+
+    let mut settings = state.recording_settings.write().await;
+    settings.league_install_path = dir.as_ref().map(|dir| dir.to_string_lossy().to_string());
+    settings.save().map_err(|e| e.to_string())?;
+
+    install::set_override(dir);
+    Ok(())
+}
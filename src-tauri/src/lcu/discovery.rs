@@ -0,0 +1,192 @@
+//! Finding the LCU port and auth token
+//!
+//! The default is the `lockfile` the client writes to its install directory,
+//! which doesn't work for every client: Garena and some regional builds use
+//! another layout. Those users can point at the lockfile directly, read the
+//! port and token from the `LeagueClientUx` command line (through WMI on
+//! Windows), or enter them by hand (`RecordingSettings.lcu`).
+
+use std::path::PathBuf;
+use std::sync::RwLock;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+use super::{LcuClient, LcuError, LockfileData, Result};
+use crate::settings::models::{LcuConnectionSettings, LcuDiscoveryStrategy};
+
+/// Process the LCU API belongs to, lowercased
+const CLIENT_UX_PROCESS_NAMES: [&str; 2] = ["leagueclientux.exe", "leagueclientux"];
+
+/// Strategy from the advanced settings (defaults until [`set_settings`])
+static SETTINGS: RwLock<Option<LcuConnectionSettings>> = RwLock::new(None);
+
+/// Use `settings` for connections made from now on
+pub fn set_settings(settings: &LcuConnectionSettings) {
+    *SETTINGS.write().unwrap_or_else(|e| e.into_inner()) = Some(settings.clone());
+}
+
+fn settings() -> LcuConnectionSettings {
+    SETTINGS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+/// Connection details of the running client, using the configured strategy
+pub fn discover() -> Result<LockfileData> {
+    let settings = settings();
+    match settings.strategy {
+        LcuDiscoveryStrategy::Auto => from_lockfile(&settings)
+            .or_else(|_| from_process())
+            .or_else(|_| manual(&settings)),
+        LcuDiscoveryStrategy::Lockfile => from_lockfile(&settings),
+        LcuDiscoveryStrategy::ProcessCommandLine => from_process(),
+        LcuDiscoveryStrategy::Manual => manual(&settings),
+    }
+}
+
+/// Read the configured lockfile, or the one in the detected install
+fn from_lockfile(settings: &LcuConnectionSettings) -> Result<LockfileData> {
+    let path = match &settings.lockfile_path {
+        // The lockfile only exists while the client runs
+        Some(path) if PathBuf::from(path).exists() => PathBuf::from(path),
+        Some(_) => return Err(LcuError::ClientNotFound),
+        None => LcuClient::get_lockfile_path()?,
+    };
+
+    LockfileData::parse(&std::fs::read_to_string(path)?)
+}
+
+/// Port and token entered by the user
+fn manual(settings: &LcuConnectionSettings) -> Result<LockfileData> {
+    match (settings.port, settings.auth_token.as_deref()) {
+        (Some(port), Some(token)) if !token.trim().is_empty() => Ok(LockfileData {
+            process_name: "LeagueClientUx".to_string(),
+            pid: 0,
+            port,
+            password: token.trim().to_string(),
+            protocol: "https".to_string(),
+        }),
+        _ => Err(LcuError::ClientNotFound),
+    }
+}
+
+/// Scrape the `LeagueClientUx` command line
+fn from_process() -> Result<LockfileData> {
+    let mut system = System::new();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::All,
+        ProcessRefreshKind::new().with_cmd(UpdateKind::OnlyIfNotSet),
+    );
+
+    let process = system
+        .processes()
+        .values()
+        .find(|process| {
+            let name = process.name().to_string_lossy().to_lowercase();
+            CLIENT_UX_PROCESS_NAMES.contains(&name.as_str())
+        })
+        .ok_or(LcuError::ClientNotFound)?;
+
+    let command_line = process
+        .cmd()
+        .iter()
+        .map(|arg| arg.to_string_lossy())
+        .collect::<Vec<_>>()
+        .join(" ");
+    if let Some(data) = parse_command_line(&command_line) {
+        return Ok(data);
+    }
+
+    // The arguments of an elevated client can't be read directly
+    wmi_command_line()
+        .as_deref()
+        .and_then(parse_command_line)
+        .ok_or(LcuError::InvalidLockfile)
+}
+
+/// Connection details from `LeagueClientUx` arguments
+///
+/// Only `--app-port` and `--remoting-auth-token` are needed (not the
+/// similarly named `--riotclient-*` ones, which belong to the Riot Client).
+pub fn parse_command_line(command_line: &str) -> Option<LockfileData> {
+    let arg = |name: &str| {
+        command_line
+            .split(|c: char| c == '"' || c.is_whitespace())
+            .find_map(|arg| arg.strip_prefix(name))
+            .filter(|value| !value.is_empty())
+    };
+
+    Some(LockfileData {
+        process_name: "LeagueClientUx".to_string(),
+        pid: arg("--app-pid=")
+            .and_then(|pid| pid.parse().ok())
+            .unwrap_or(0),
+        port: arg("--app-port=")?.parse().ok()?,
+        password: arg("--remoting-auth-token=")?.to_string(),
+        protocol: "https".to_string(),
+    })
+}
+
+/// Command line of `LeagueClientUx.exe` queried through WMI
+#[cfg(target_os = "windows")]
+fn wmi_command_line() -> Option<String> {
+    use std::os::windows::process::CommandExt;
+
+    /// Don't flash a console window on every poll
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let output = std::process::Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "Get-CimInstance Win32_Process -Filter \"Name='LeagueClientUx.exe'\" | Select-Object -ExpandProperty CommandLine",
+        ])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| tracing::debug!("Failed to query WMI: {}", e))
+        .ok()?;
+
+    let command_line = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !command_line.is_empty()).then_some(command_line)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn wmi_command_line() -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_command_line() {
+        let command_line = r#""C:/Garena/Games/32775/LeagueClientUx.exe" "--riotclient-auth-token=riot" "--riotclient-app-port=50000" "--app-port=61234" "--remoting-auth-token=Xy_z-123" "--app-pid=4321""#;
+        let data = parse_command_line(command_line).unwrap();
+
+        assert_eq!(data.port, 61234);
+        assert_eq!(data.password, "Xy_z-123");
+        assert_eq!(data.pid, 4321);
+        assert_eq!(data.protocol, "https");
+
+        assert!(parse_command_line("LeagueClientUx.exe --app-port=61234").is_none());
+        assert!(parse_command_line("LeagueClientUx.exe --remoting-auth-token=abc").is_none());
+    }
+
+    #[test]
+    fn test_manual_needs_port_and_token() {
+        let mut settings = LcuConnectionSettings {
+            strategy: LcuDiscoveryStrategy::Manual,
+            port: Some(61234),
+            ..Default::default()
+        };
+        assert!(manual(&settings).is_err());
+
+        settings.auth_token = Some(" token ".to_string());
+        let data = manual(&settings).unwrap();
+        assert_eq!(data.port, 61234);
+        assert_eq!(data.password, "token");
+    }
+}
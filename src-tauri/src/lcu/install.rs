@@ -0,0 +1,153 @@
+/// League of Legends install path detection
+///
+/// Candidates are checked in order: the user override from settings, the
+/// Riot uninstall entries in the Windows registry, the Riot Client's
+/// `RiotClientInstalls.json`, and finally the usual install locations.
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// Riot Client's record of installed games
+const RIOT_CLIENT_INSTALLS: &str = "C:\\ProgramData\\Riot Games\\RiotClientInstalls.json";
+
+/// User-configured install directory (`RecordingSettings.league_install_path`)
+static INSTALL_DIR_OVERRIDE: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Set or clear the user-configured install directory
+pub fn set_override(dir: Option<PathBuf>) {
+    if let Ok(mut current) = INSTALL_DIR_OVERRIDE.write() {
+        *current = dir;
+    }
+}
+
+pub fn override_dir() -> Option<PathBuf> {
+    INSTALL_DIR_OVERRIDE.read().ok()?.clone()
+}
+
+/// Whether `dir` looks like a League of Legends install
+pub fn is_league_install(dir: &Path) -> bool {
+    dir.join("LeagueClient.exe").exists() || dir.join("lockfile").exists()
+}
+
+/// Possible install directories, most specific first (may not exist)
+pub fn candidates() -> Vec<PathBuf> {
+    candidates_with_override(override_dir())
+}
+
+fn candidates_with_override(override_dir: Option<PathBuf>) -> Vec<PathBuf> {
+    let mut candidates: Vec<PathBuf> = override_dir.into_iter().collect();
+
+    candidates.extend(registry_install_dirs());
+
+    if let Ok(json) = std::fs::read_to_string(RIOT_CLIENT_INSTALLS) {
+        candidates.extend(parse_riot_client_installs(&json));
+    }
+
+    candidates.extend([
+        // Standard installation in C:\Riot Games
+        PathBuf::from("C:\\Riot Games\\League of Legends"),
+        // Program Files locations
+        PathBuf::from("C:\\Program Files\\Riot Games\\League of Legends"),
+        PathBuf::from("C:\\Program Files (x86)\\Riot Games\\League of Legends"),
+    ]);
+
+    // Add LocalAppData location if environment variable exists
+    if let Ok(local_app_data) = std::env::var("LOCALAPPDATA") {
+        candidates.push(
+            PathBuf::from(local_app_data)
+                .join("Riot Games")
+                .join("League of Legends"),
+        );
+    }
+
+    let mut seen = std::collections::HashSet::new();
+    candidates.retain(|dir| seen.insert(dir.clone()));
+    candidates
+}
+
+/// League install directories listed in `RiotClientInstalls.json`
+///
+/// `associated_client` maps each game's install directory to the Riot
+/// Client that manages it.
+pub fn parse_riot_client_installs(json: &str) -> Vec<PathBuf> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(json) else {
+        return Vec::new();
+    };
+
+    value
+        .get("associated_client")
+        .and_then(|clients| clients.as_object())
+        .map(|clients| {
+            clients
+                .keys()
+                .map(|dir| dir.trim_end_matches(['/', '\\']))
+                .filter(|dir| dir.to_lowercase().ends_with("league of legends"))
+                .map(PathBuf::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `InstallLocation` of the Riot uninstall entries for League
+#[cfg(target_os = "windows")]
+fn registry_install_dirs() -> Vec<PathBuf> {
+    use winreg::enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
+    use winreg::RegKey;
+
+    const UNINSTALL_KEYS: [&str; 2] = [
+        "Software\\Microsoft\\Windows\\CurrentVersion\\Uninstall\\Riot Game league_of_legends.live",
+        "Software\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Uninstall\\Riot Game league_of_legends.live",
+    ];
+
+    let mut dirs = Vec::new();
+    for hive in [HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE] {
+        for key in UNINSTALL_KEYS {
+            let location: Option<String> = RegKey::predef(hive)
+                .open_subkey(key)
+                .and_then(|key| key.get_value("InstallLocation"))
+                .ok();
+
+            if let Some(location) = location.filter(|l| !l.is_empty()) {
+                dirs.push(PathBuf::from(location));
+            }
+        }
+    }
+    dirs
+}
+
+#[cfg(not(target_os = "windows"))]
+fn registry_install_dirs() -> Vec<PathBuf> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_riot_client_installs() {
+        let json = r#"{
+            "associated_client": {
+                "D:/Games/Riot Games/League of Legends/": "D:/Games/Riot Games/Riot Client/RiotClientServices.exe",
+                "D:/Games/Riot Games/VALORANT/live/": "D:/Games/Riot Games/Riot Client/RiotClientServices.exe"
+            },
+            "rc_default": "D:/Games/Riot Games/Riot Client/RiotClientServices.exe"
+        }"#;
+
+        assert_eq!(
+            parse_riot_client_installs(json),
+            vec![PathBuf::from("D:/Games/Riot Games/League of Legends")]
+        );
+        assert!(parse_riot_client_installs("not json").is_empty());
+        assert!(parse_riot_client_installs("{}").is_empty());
+    }
+
+    #[test]
+    fn test_override_comes_first() {
+        let dir = PathBuf::from("E:\\League of Legends");
+        assert_eq!(
+            candidates_with_override(Some(dir.clone())).first(),
+            Some(&dir)
+        );
+        assert_ne!(candidates_with_override(None).first(), Some(&dir));
+    }
+}
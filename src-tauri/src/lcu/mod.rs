@@ -1,7 +1,11 @@
+pub mod activity;
 pub mod commands;
+pub mod discovery;
+pub mod install;
+pub mod patch;
+pub mod process;
 
 use serde::{Deserialize, Serialize};
-use std::fs;
 use std::path::PathBuf;
 use thiserror::Error;
 
@@ -21,6 +25,8 @@ pub enum LcuError {
 
 pub type Result<T> = std::result::Result<T, LcuError>;
 
+pub use activity::{GameActivity, GameDetection};
+
 /// Lockfile data parsed from League client lockfile
 #[derive(Debug, Clone)]
 pub struct LockfileData {
@@ -59,7 +65,7 @@ pub struct GameInfo {
 }
 
 /// Game flow phase from LCU API
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
 pub enum GameFlowPhase {
     None,
@@ -78,6 +84,13 @@ pub enum GameFlowPhase {
     TerminatedInError,
 }
 
+impl GameFlowPhase {
+    /// Whether a match is running (including reconnecting to one)
+    pub fn is_in_game(&self) -> bool {
+        matches!(self, GameFlowPhase::InProgress | GameFlowPhase::Reconnect)
+    }
+}
+
 /// Game session response from /lol-gameflow/v1/session
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GameSession {
@@ -109,29 +122,21 @@ impl LcuClient {
         }
     }
 
+    /// Find the League of Legends install directory
+    ///
+    /// Unlike the lockfile, this works while the client isn't running.
+    pub fn detect_install_dir() -> Option<PathBuf> {
+        install::candidates()
+            .into_iter()
+            .find(|dir| install::is_league_install(dir))
+    }
+
     /// Get the lockfile path by checking multiple possible locations
     pub fn get_lockfile_path() -> Result<PathBuf> {
-        // List of possible lockfile locations
-        let mut possible_paths = vec![
-            // Standard installation in C:\Riot Games
-            PathBuf::from("C:\\Riot Games\\League of Legends\\lockfile"),
-            // Program Files locations
-            PathBuf::from("C:\\Program Files\\Riot Games\\League of Legends\\lockfile"),
-            PathBuf::from("C:\\Program Files (x86)\\Riot Games\\League of Legends\\lockfile"),
-        ];
-
-        // Add LocalAppData location if environment variable exists
-        if let Ok(local_app_data) = std::env::var("LOCALAPPDATA") {
-            possible_paths.push(
-                PathBuf::from(local_app_data)
-                    .join("Riot Games")
-                    .join("League of Legends")
-                    .join("lockfile"),
-            );
-        }
-
-        // Try each path
-        for path in possible_paths {
+        for path in install::candidates()
+            .into_iter()
+            .map(|dir| dir.join("lockfile"))
+        {
             if path.exists() {
                 tracing::info!("Found lockfile at: {}", path.display());
                 return Ok(path);
@@ -141,16 +146,12 @@ impl LcuClient {
         Err(LcuError::ClientNotFound)
     }
 
-    /// Read and parse the lockfile
-    pub fn read_lockfile() -> Result<LockfileData> {
-        let lockfile_path = Self::get_lockfile_path()?;
-        let content = fs::read_to_string(lockfile_path)?;
-        LockfileData::parse(&content)
-    }
-
-    /// Connect to the League client by reading lockfile
+    /// Connect to the League client
+    ///
+    /// Port and token come from the configured [`discovery`] strategy (the
+    /// lockfile by default).
     pub async fn connect(&mut self) -> Result<()> {
-        let lockfile = Self::read_lockfile()?;
+        let lockfile = discovery::discover()?;
 
         // Create HTTP client that accepts self-signed certificates
         let http_client = reqwest::Client::builder()
@@ -246,6 +247,91 @@ impl LcuClient {
         .or(Ok(false))
     }
 
+    /// Authenticated request to an LCU endpoint
+    fn request(&self, method: reqwest::Method, path: &str) -> Result<reqwest::RequestBuilder> {
+        let client = self
+            .http_client
+            .as_ref()
+            .ok_or(LcuError::Connection("Not connected".to_string()))?;
+        let lockfile = self
+            .lockfile_data
+            .as_ref()
+            .ok_or(LcuError::Connection("Not connected".to_string()))?;
+
+        let url = format!("{}{}", self.get_base_url()?, path);
+        Ok(client
+            .request(method, url)
+            .basic_auth("riot", Some(&lockfile.password)))
+    }
+
+    /// Send a request and parse the JSON response
+    async fn fetch_json<T: serde::de::DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<T> {
+        let response = request
+            .send()
+            .await
+            .map_err(|e| LcuError::Api(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(LcuError::Api(format!("HTTP {}", response.status())));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| LcuError::Api(e.to_string()))
+    }
+
+    /// Riot ID ("Name#TAG") of the logged-in player
+    pub async fn current_summoner_name(&self) -> Result<String> {
+        #[derive(Deserialize)]
+        struct Summoner {
+            #[serde(rename = "gameName")]
+            game_name: String,
+            #[serde(rename = "tagLine")]
+            tag_line: String,
+        }
+
+        let request = self.request(reqwest::Method::GET, "/lol-summoner/v1/current-summoner")?;
+        let summoner: Summoner = self.fetch_json(request).await?;
+        Ok(format!("{}#{}", summoner.game_name, summoner.tag_line))
+    }
+
+    /// Full game version of the client ("14.20.620.9461")
+    pub async fn game_version(&self) -> Result<String> {
+        let request = self.request(reqwest::Method::GET, "/lol-patch/v1/game-version")?;
+        self.fetch_json(request).await
+    }
+
+    /// Folder the client loads `.rofl` replays from
+    pub async fn replays_dir(&self) -> Result<PathBuf> {
+        let request = self.request(reqwest::Method::GET, "/lol-replays/v1/rofls/path")?;
+        let path: String = self.fetch_json(request).await?;
+        Ok(PathBuf::from(path))
+    }
+
+    /// Launch the replay of `game_id` from the replays folder
+    pub async fn watch_replay(&self, game_id: u64) -> Result<()> {
+        let response = self
+            .request(
+                reqwest::Method::POST,
+                &format!("/lol-replays/v1/rofls/{}/watch", game_id),
+            )?
+            .json(&serde_json::json!({ "componentType": "replay-button_match-history" }))
+            .send()
+            .await
+            .map_err(|e| LcuError::Api(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(LcuError::Api(format!("HTTP {}", response.status())));
+        }
+
+        tracing::info!("Launched replay for game {}", game_id);
+        Ok(())
+    }
+
     /// Check if client is connected
     pub fn is_connected(&self) -> bool {
         self.lockfile_data.is_some() && self.http_client.is_some()
@@ -0,0 +1,89 @@
+//! The League patch games are played on
+//!
+//! Asked from the client (`/lol-patch/v1/game-version`, e.g.
+//! "14.20.620.9461") and, when it isn't running, taken from the newest Data
+//! Dragon version ("14.20.1"). Both are shortened to the patch players talk
+//! about ("14.20").
+
+use anyhow::{Context, Result};
+use std::time::Duration;
+use tracing::debug;
+
+use super::LcuClient;
+
+/// Versions published on Data Dragon, newest first
+pub const DDRAGON_VERSIONS_URL: &str = "https://ddragon.leagueoflegends.com/api/versions.json";
+
+/// Clip tag recording the patch (`patch_14.20`)
+pub fn clip_tag(patch: &str) -> String {
+    format!("patch_{}", patch)
+}
+
+/// "14.20" for a client or Data Dragon version ("14.20.620.9461", "14.20.1")
+pub fn short_patch(version: &str) -> Option<String> {
+    let mut parts = version.trim().split('.');
+    let major = parts.next().filter(|part| is_number(part))?;
+    let minor = parts.next().filter(|part| is_number(part))?;
+    Some(format!("{}.{}", major, minor))
+}
+
+fn is_number(part: &str) -> bool {
+    !part.is_empty() && part.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Newest Data Dragon version ("14.20.1")
+pub async fn latest_ddragon_version() -> Result<String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let versions: Vec<String> = client
+        .get(DDRAGON_VERSIONS_URL)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await
+        .context("Failed to parse Data Dragon versions")?;
+
+    versions
+        .into_iter()
+        .next()
+        .context("Data Dragon returned no versions")
+}
+
+/// The current patch ("14.20"), if the client or Data Dragon can tell
+pub async fn current_patch() -> Option<String> {
+    let mut lcu = LcuClient::new();
+    let client_version = match lcu.connect().await {
+        Ok(()) => lcu.game_version().await.ok(),
+        Err(_) => None,
+    };
+
+    let version = match client_version {
+        Some(version) => version,
+        None => match latest_ddragon_version().await {
+            Ok(version) => version,
+            Err(e) => {
+                debug!("Failed to look up the current patch: {}", e);
+                return None;
+            }
+        },
+    };
+
+    short_patch(&version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_patch() {
+        assert_eq!(short_patch("14.20.620.9461").as_deref(), Some("14.20"));
+        assert_eq!(short_patch("14.3.1").as_deref(), Some("14.3"));
+        assert_eq!(short_patch("lolpatch_3.7"), None);
+        assert_eq!(short_patch(""), None);
+        assert_eq!(clip_tag("14.20"), "patch_14.20");
+    }
+}
@@ -0,0 +1,62 @@
+//! Game detection from the process list
+//!
+//! Fallback for when the League client can't be reached (lockfile
+//! unreadable because of permissions or an unusual install): a running game
+//! process still tells us a match is on, just without the game mode or id.
+
+use std::path::Path;
+use sysinfo::{ProcessesToUpdate, System};
+
+/// Executable of the game itself (not the client), lowercased
+const GAME_PROCESS_NAMES: [&str; 2] = ["league of legends.exe", "league of legends"];
+
+/// Whether `name` is the game executable
+pub fn is_game_process(name: &str) -> bool {
+    let name = Path::new(name)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    GAME_PROCESS_NAMES.contains(&name.as_str())
+}
+
+/// Reuses one process table between checks
+pub struct ProcessWatcher {
+    system: System,
+}
+
+impl ProcessWatcher {
+    pub fn new() -> Self {
+        Self {
+            system: System::new(),
+        }
+    }
+
+    /// Whether the game is running right now
+    pub fn is_game_running(&mut self) -> bool {
+        self.system.refresh_processes(ProcessesToUpdate::All);
+        self.system
+            .processes()
+            .values()
+            .any(|process| is_game_process(&process.name().to_string_lossy()))
+    }
+}
+
+impl Default for ProcessWatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_game_process() {
+        assert!(is_game_process("League of Legends.exe"));
+        assert!(is_game_process("LEAGUE OF LEGENDS.EXE"));
+        assert!(is_game_process("League of Legends"));
+        assert!(!is_game_process("LeagueClient.exe"));
+        assert!(!is_game_process("LeagueClientUx.exe"));
+    }
+}
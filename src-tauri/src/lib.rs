@@ -2,14 +2,27 @@
 // This file allows integration tests to access the application modules
 
 pub mod auth;
+pub mod cli;
+pub mod cloud;
+pub mod ddragon;
+pub mod events;
 pub mod feature_gate;
 pub mod hotkey;
+pub mod jobs;
 pub mod lcu;
+pub mod notifications;
+pub mod obs;
 pub mod payments;
+pub mod plugins;
+pub mod quota;
 pub mod recording;
 pub mod settings;
+pub mod setup;
 pub mod storage;
 pub mod supabase;
+pub mod telemetry;
+pub mod tray;
+pub mod uploads;
 pub mod utils;
 pub mod video;
 pub mod youtube;
@@ -24,11 +37,25 @@ pub struct AppState {
     pub auth: Arc<auth::AuthManager>,
     pub feature_gate: Arc<feature_gate::FeatureGate>,
     pub recording_manager: Arc<RwLock<recording::RecordingManager>>,
+    pub recorder: Arc<dyn recording::Recorder>,
     pub auto_clip_manager: Arc<recording::auto_clip_manager::AutoClipManager>,
     pub recording_settings: Arc<RwLock<settings::models::RecordingSettings>>,
     pub hotkey_manager: Arc<hotkey::HotkeyManager>,
     pub metrics_collector: Arc<utils::metrics::MetricsCollector>,
+    pub metrics_history: Arc<utils::metrics_history::MetricsHistory>,
     pub cleanup_manager: Arc<utils::cleanup::CleanupManager>,
     pub auto_composer: Arc<video::AutoComposer>,
     pub youtube_manager: Arc<youtube::YouTubeManager>,
+    pub log_handle: Arc<utils::logging::LogHandle>,
+    pub telemetry: Arc<telemetry::TelemetryManager>,
+    pub cloud_backup: Arc<cloud::CloudBackup>,
+    pub quota: Arc<quota::QuotaManager>,
+    pub jobs: Arc<jobs::JobScheduler>,
+    pub events: Arc<events::EventBus>,
+    pub notifications: Arc<notifications::DiscordNotifier>,
+    pub notification_center: Arc<notifications::center::NotificationCenter>,
+    pub plugins: Arc<plugins::PluginRegistry>,
+    pub obs: Arc<obs::ObsRecorder>,
+    pub circuit_breakers: Arc<utils::circuit_breaker::CircuitBreakerRegistry>,
+    pub ddragon: Arc<ddragon::DataDragon>,
 }
@@ -2,21 +2,34 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod auth;
+mod cli;
+mod cloud;
+mod ddragon;
+mod events;
 mod feature_gate;
 mod hotkey;
+mod jobs;
 mod lcu;
+mod notifications;
+mod obs;
 mod payments;
+mod plugins;
+mod quota;
 mod recording;
 mod settings;
+mod setup;
 mod storage;
 mod supabase;
+mod telemetry;
+mod tray;
+mod uploads;
 mod utils;
 mod video;
 mod youtube;
 
 use std::sync::Arc;
+use tauri::Manager;
 use tokio::sync::RwLock;
-use tracing_subscriber;
 
 /// Application state shared across all Tauri commands
 #[derive(Clone)]
@@ -25,13 +38,27 @@ pub struct AppState {
     pub auth: Arc<auth::AuthManager>,
     pub feature_gate: Arc<feature_gate::FeatureGate>,
     pub recording_manager: Arc<RwLock<recording::RecordingManager>>,
+    pub recorder: Arc<dyn recording::Recorder>,
     pub auto_clip_manager: Arc<recording::auto_clip_manager::AutoClipManager>,
     pub recording_settings: Arc<RwLock<settings::models::RecordingSettings>>,
     pub hotkey_manager: Arc<hotkey::HotkeyManager>,
     pub metrics_collector: Arc<utils::metrics::MetricsCollector>,
+    pub metrics_history: Arc<utils::metrics_history::MetricsHistory>,
     pub cleanup_manager: Arc<utils::cleanup::CleanupManager>,
     pub auto_composer: Arc<video::AutoComposer>,
     pub youtube_manager: Arc<youtube::YouTubeManager>,
+    pub log_handle: Arc<utils::logging::LogHandle>,
+    pub telemetry: Arc<telemetry::TelemetryManager>,
+    pub cloud_backup: Arc<cloud::CloudBackup>,
+    pub quota: Arc<quota::QuotaManager>,
+    pub jobs: Arc<jobs::JobScheduler>,
+    pub events: Arc<events::EventBus>,
+    pub notifications: Arc<notifications::DiscordNotifier>,
+    pub notification_center: Arc<notifications::center::NotificationCenter>,
+    pub plugins: Arc<plugins::PluginRegistry>,
+    pub obs: Arc<obs::ObsRecorder>,
+    pub circuit_breakers: Arc<utils::circuit_breaker::CircuitBreakerRegistry>,
+    pub ddragon: Arc<ddragon::DataDragon>,
 }
 
 #[tokio::main]
@@ -39,38 +66,124 @@ async fn main() {
     // Load environment variables from .env file (development)
     dotenvy::dotenv().ok();
 
-    // Initialize logging
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing::Level::INFO.into()),
-        )
-        .init();
-
-    tracing::info!("Starting LoLShorts application...");
-
     // Get application data directory
     let app_data_dir = dirs::data_dir()
         .expect("Failed to get data directory")
         .join("lolshorts");
 
+    // Headless CLI mode: run the command and exit without starting the app
+    if let Some(command) = cli::parse(std::env::args().skip(1)) {
+        #[cfg(windows)]
+        cli::attach_console();
+        std::process::exit(cli::run(command, &app_data_dir).await);
+    }
+
+    // Load recording settings (logging levels are persisted here too)
+    let loaded_settings = settings::models::RecordingSettings::load();
+
+    // Initialize logging
+    let log_dir = app_data_dir.join("logs");
+    let log_config = if cfg!(debug_assertions) {
+        utils::logging::LogConfig::development(log_dir)
+    } else {
+        utils::logging::LogConfig::production(log_dir)
+    };
+    let log_settings = loaded_settings
+        .as_ref()
+        .map(|s| s.logging.clone())
+        .unwrap_or_default();
+    let log_handle = Arc::new(
+        utils::logging::init_logging(log_config.with_settings(&log_settings))
+            .expect("Failed to initialize logging"),
+    );
+
+    tracing::info!("Starting LoLShorts application...");
+    if log_settings.level.trim().parse::<tracing::Level>().is_err() {
+        tracing::warn!(
+            "Ignoring invalid log level in settings: {}",
+            log_settings.level
+        );
+    }
+
     // Initialize storage
     let storage =
         Arc::new(storage::Storage::new(&app_data_dir).expect("Failed to initialize storage"));
 
+    // Kill FFmpeg processes orphaned by a crash, then track this session's.
+    // Another running instance keeps its processes and its PID file.
+    let ffmpeg_pid_file = app_data_dir.join("ffmpeg.pids");
+    match utils::cleanup::process::reap_stray_ffmpeg(&ffmpeg_pid_file) {
+        Some(reaped) => {
+            if reaped > 0 {
+                tracing::warn!("Killed {} FFmpeg processes left by the last session", reaped);
+            }
+            utils::cleanup::process::set_pid_file(ffmpeg_pid_file);
+        }
+        None => tracing::warn!(
+            "Another LoLShorts instance is running, not tracking FFmpeg processes"
+        ),
+    }
+
     // Initialize auth manager
     let auth = Arc::new(auth::AuthManager::new());
 
     // Initialize feature gate
-    let feature_gate = Arc::new(feature_gate::FeatureGate::new(auth.clone()));
+    let feature_gate = Arc::new(feature_gate::FeatureGate::with_cache(
+        auth.clone(),
+        app_data_dir.join("feature_flags.json"),
+    ));
+
+    // Initialize quota tracking for metered features
+    let quota_store =
+        quota::FileQuotaStore::new(&app_data_dir).expect("Failed to initialize quota store");
+    let quota = Arc::new(quota::QuotaManager::new(
+        Box::new(quota_store),
+        auth.clone(),
+        feature_gate.clone(),
+    ));
 
     // Initialize recording manager (platform-specific backend)
-    let recordings_dir = app_data_dir.join("recordings");
+    // Recordings can live on another drive; metadata always stays in app data
+    let mut storage_locations = loaded_settings
+        .as_ref()
+        .map(|s| s.storage.clone())
+        .unwrap_or_default();
+    if let Err(e) = std::fs::create_dir_all(storage_locations.recordings_dir(&app_data_dir)) {
+        // e.g. an external drive that isn't plugged in
+        tracing::error!("Recording folder unavailable, using the default: {}", e);
+        storage_locations = Default::default();
+    }
+    let recordings_dir = storage_locations.recordings_dir(&app_data_dir);
     std::fs::create_dir_all(&recordings_dir).expect("Failed to create recordings directory");
 
+    // Move segments left over from a crash out of temp_segments before the
+    // recorder reuses their file names or startup cleanup deletes them
+    let recovered_session = match recording::recovery::quarantine_orphaned_segments(
+        &recordings_dir,
+    ) {
+        Ok(Some(session)) => {
+            tracing::warn!(
+                "Found {} orphaned segments from previous session ({})",
+                session.segment_count,
+                session.session_id
+            );
+            Some(session)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            tracing::error!("Failed to recover orphaned segments: {}", e);
+            None
+        }
+    };
+
+    // Event bus for real-time frontend updates (attached to the app in setup)
+    let event_bus = Arc::new(events::EventBus::new());
+
     let recording_manager = Arc::new(RwLock::new(
-        recording::initialize_recording_backend(recordings_dir)
-            .expect("Failed to initialize recording backend"),
+        recording::initialize_recording_backend(recordings_dir.clone())
+            .expect("Failed to initialize recording backend")
+            .with_clips_dir(storage_locations.clips_dir(&app_data_dir))
+            .with_events(Arc::clone(&event_bus)),
     ));
 
     tracing::info!(
@@ -78,25 +191,105 @@ async fn main() {
         recording::Platform::current().name()
     );
 
-    // Load recording settings
-    let recording_settings = Arc::new(RwLock::new(
-        settings::models::RecordingSettings::load().unwrap_or_else(|e| {
-            tracing::warn!("Failed to load recording settings, using defaults: {}", e);
-            settings::models::RecordingSettings::default()
-        }),
-    ));
+    let recording_settings = Arc::new(RwLock::new(loaded_settings.unwrap_or_else(|e| {
+        tracing::warn!("Failed to load recording settings, using defaults: {}", e);
+        settings::models::RecordingSettings::default()
+    })));
 
     tracing::info!("Recording settings loaded");
 
-    // Initialize Auto Clip Manager
-    let auto_clip_manager = Arc::new(recording::auto_clip_manager::AutoClipManager::new(
-        Arc::clone(&recording_manager),
-        Arc::clone(&storage),
+    // Circuit breakers shown (and re-armed) in the health panel
+    let circuit_breakers = Arc::new(utils::circuit_breaker::CircuitBreakerRegistry::new());
+    circuit_breakers.register(recording_manager.read().await.circuit_breaker());
+
+    // Track whether a game is running (replay buffer pause, job deferral);
+    // polling starts once the LCU settings are applied
+    let game_activity = Arc::new(lcu::GameActivity::new());
+
+    // Load background job queue (jobs interrupted by the last exit are resumed)
+    let job_scheduler = Arc::new(
+        jobs::JobScheduler::load(app_data_dir.join("jobs.json"))
+            .expect("Failed to initialize job scheduler")
+            .with_events(Arc::clone(&event_bus))
+            .with_game_activity(Arc::clone(&game_activity)),
+    );
+
+    // Custom game event handlers (external hooks come from the settings)
+    let plugin_registry = Arc::new(plugins::PluginRegistry::new(Arc::clone(
+        &recording_settings,
+    )));
+
+    // OBS replay buffer, used instead of the built-in recorder when enabled in settings
+    let obs_recorder = Arc::new(
+        obs::ObsRecorder::new(Arc::clone(&recording_settings), app_data_dir.clone())
+            .with_events(Arc::clone(&event_bus)),
+    );
+
+    // Fake capture instead of the built-in recorder for tests and demos
+    let mock_recorder = recording::mock::MockRecorder::from_env(
+        recordings_dir.clone(),
+        storage_locations.clips_dir(&app_data_dir),
+    );
+    let builtin_recorder: Arc<dyn recording::Recorder> = match mock_recorder {
+        Some(mock) => {
+            tracing::warn!(
+                "{} is set, using the mock recorder",
+                recording::mock::MOCK_RECORDER_ENV
+            );
+            Arc::new(mock.with_events(Arc::clone(&event_bus)))
+        }
+        None => Arc::clone(&recording_manager),
+    };
+
+    // Recording backend chosen in settings (built-in recorder or OBS)
+    let recorder: Arc<dyn recording::Recorder> = Arc::new(recording::ActiveRecorder::new(
+        builtin_recorder,
+        Arc::clone(&obs_recorder),
         Arc::clone(&recording_settings),
     ));
 
+    // Windows toasts for clips saved while in game (attached to the app in setup)
+    let toasts = Arc::new(notifications::toast::ToastNotifier::new(Arc::clone(
+        &recording_settings,
+    )));
+
+    // Initialize Auto Clip Manager
+    let auto_clip_manager = Arc::new(
+        recording::auto_clip_manager::AutoClipManager::new(
+            Arc::clone(&recorder),
+            Arc::clone(&storage),
+            Arc::clone(&recording_settings),
+        )
+        .with_jobs(Arc::clone(&job_scheduler))
+        .with_events(Arc::clone(&event_bus))
+        .with_plugins(Arc::clone(&plugin_registry))
+        .with_toasts(Arc::clone(&toasts)),
+    );
+
     tracing::info!("Auto Clip Manager initialized");
 
+    // Probe the Live Client API so the UI can tell why no clips are saved
+    let live_client_health = auto_clip_manager.live_client_health();
+    tokio::spawn(async move {
+        let client = match reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .timeout(std::time::Duration::from_secs(2))
+            .build()
+        {
+            Ok(client) => client,
+            Err(e) => {
+                tracing::warn!("Live Client health probe disabled: {}", e);
+                return;
+            }
+        };
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(5));
+
+        loop {
+            interval.tick().await;
+            live_client_health.probe(&client).await;
+        }
+    });
+
     // Initialize Hotkey Manager
     let hotkey_manager = Arc::new(hotkey::HotkeyManager::new());
 
@@ -109,45 +302,167 @@ async fn main() {
 
     tracing::info!("Metrics Collector initialized");
 
+    // Initialize Metrics History (7-day rolling samples for health charts)
+    let metrics_history = Arc::new(
+        utils::metrics_history::MetricsHistory::new(
+            app_data_dir.join("metrics_history.jsonl"),
+            chrono::Duration::days(7),
+        )
+        .expect("Failed to initialize metrics history"),
+    );
+
+    // Sample metrics every 10 seconds, including recorder dropped-segment stats
+    let history_recording_manager = Arc::clone(&recording_manager);
+    let history_metrics_collector = Arc::clone(&metrics_collector);
+    let history = Arc::clone(&metrics_history);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(10));
+
+        loop {
+            interval.tick().await;
+
+            let stats = history_recording_manager.read().await.get_stats().await;
+            history_metrics_collector
+                .update_dropped_segments(stats.dropped_segments)
+                .await;
+
+            let sample =
+                utils::metrics_history::MetricsSample::capture(&history_metrics_collector).await;
+            if let Err(e) = history.record(sample).await {
+                tracing::warn!("Failed to record metrics sample: {}", e);
+            }
+        }
+    });
+
+    // Watch CPU usage and fall back to low-spec mode when it stays too high
+    recording::start_performance_watchdog(
+        Arc::clone(&recording_manager),
+        Arc::clone(&metrics_collector),
+        Arc::clone(&recording_settings),
+    );
+
+    // Pause the replay buffer between games
+    recording::start_buffer_auto_pause(
+        Arc::clone(&recording_manager),
+        Arc::clone(&recording_settings),
+        Arc::clone(&game_activity),
+    );
+
+    // Get the replay buffer going before the game loads
+    recording::start_buffer_warmup(
+        Arc::clone(&recording_manager),
+        Arc::clone(&recorder),
+        Arc::clone(&recording_settings),
+        Arc::clone(&game_activity),
+    );
+
+    // Keep the replay buffer running when the game is only found by process
+    recording::start_degraded_capture(Arc::clone(&recorder), Arc::clone(&game_activity));
+
+    // Initialize anonymous telemetry (opt-in via settings)
+    let telemetry_enabled = recording_settings.read().await.telemetry.enabled;
+    let telemetry = Arc::new(
+        telemetry::TelemetryManager::new(
+            app_data_dir.join("telemetry_queue.json"),
+            telemetry_enabled,
+            supabase::SupabaseClient::from_env().ok(),
+        )
+        .expect("Failed to initialize telemetry"),
+    );
+
+    // Sync recorder counters and send queued batches every 15 minutes
+    let telemetry_recording_manager = Arc::clone(&recording_manager);
+    let telemetry_flush = Arc::clone(&telemetry);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(15 * 60));
+
+        loop {
+            interval.tick().await;
+
+            {
+                let manager = telemetry_recording_manager.read().await;
+                let stats = manager.get_stats().await;
+                telemetry_flush
+                    .observe_recording(stats.clips_created, &manager.get_quality_info().encoder)
+                    .await;
+            }
+
+            if let Err(e) = telemetry_flush.flush().await {
+                tracing::warn!("Failed to flush telemetry: {}", e);
+            }
+        }
+    });
+
+    tracing::info!("Telemetry initialized (enabled: {})", telemetry_enabled);
+
+    // Initialize cloud backup (PRO), synced periodically once the app state exists
+    let settings_path = settings::models::RecordingSettings::get_settings_path()
+        .unwrap_or_else(|_| app_data_dir.join("settings.json"));
+    let cloud_backup = Arc::new(cloud::CloudBackup::new(&app_data_dir, settings_path));
+
+    tracing::info!("Cloud backup initialized");
+
     // Initialize Cleanup Manager
     let cleanup_config = utils::cleanup::CleanupConfig::default();
-    let cleanup_manager = Arc::new(utils::cleanup::CleanupManager::new(
-        app_data_dir.clone(),
-        cleanup_config,
-    ));
+    let cleanup_manager = Arc::new(
+        utils::cleanup::CleanupManager::new(app_data_dir.clone(), cleanup_config)
+            .with_recordings_dir(recordings_dir.clone()),
+    );
 
-    // Run startup cleanup
-    if let Err(e) = cleanup_manager.cleanup_on_startup().await {
-        tracing::error!("Startup cleanup failed: {}", e);
+    // Run startup cleanup in the background
+    if let Err(e) = job_scheduler
+        .enqueue_unique(jobs::JobKind::Cleanup, jobs::JobPriority::Low)
+        .await
+    {
+        tracing::error!("Failed to queue startup cleanup: {}", e);
     }
 
     tracing::info!("Cleanup Manager initialized");
 
+    // Champion and item art, cached per patch for offline use
+    let ddragon = Arc::new(ddragon::DataDragon::new(app_data_dir.join("ddragon")));
+
     // Initialize Auto Composer for auto-edit functionality
-    let video_processor = Arc::new(video::VideoProcessor::new());
-    let auto_composer = Arc::new(video::AutoComposer::new(
-        video_processor,
-        Arc::clone(&storage),
-    ));
+    let video_processor =
+        Arc::new(video::VideoProcessor::new().with_events(Arc::clone(&event_bus)));
+    let auto_composer = Arc::new(
+        video::AutoComposer::new(video_processor, Arc::clone(&storage))
+            .with_output_dir(storage_locations.auto_edits_dir(&app_data_dir))
+            .with_clip_cache(video::clip_cache::ClipCache::new(
+                app_data_dir.join("clip_cache"),
+                video::clip_cache::DEFAULT_MAX_BYTES,
+            ))
+            .with_events(Arc::clone(&event_bus))
+            .with_game_activity(Arc::clone(&game_activity))
+            .with_ddragon(Arc::clone(&ddragon)),
+    );
 
     tracing::info!("Auto Composer initialized");
 
-    // Initialize YouTube Manager
-    let youtube_client_id = std::env::var("YOUTUBE_CLIENT_ID")
-        .unwrap_or_else(|_| "your-client-id.apps.googleusercontent.com".to_string());
-    let youtube_client_secret =
-        std::env::var("YOUTUBE_CLIENT_SECRET").unwrap_or_else(|_| "your-client-secret".to_string());
-    let youtube_redirect_uri =
-        std::env::var("YOUTUBE_REDIRECT_URI").unwrap_or_else(|_| "http://localhost:8080/oauth2/callback".to_string());
+    // In-app notification history
+    let notification_center = Arc::new(
+        notifications::center::NotificationCenter::load(app_data_dir.join("notifications.json"))
+            .with_events(Arc::clone(&event_bus)),
+    );
+
+    // Tell the user a crashed session can be salvaged
+    if let Some(session) = &recovered_session {
+        notification_center.push(
+            notifications::center::NotificationKind::RecoveryAvailable,
+            "Recording recovered",
+            format!(
+                "About {:.0} seconds of footage were kept after the app closed unexpectedly. \
+                 Salvage them into a clip or discard them.",
+                session.estimated_duration_secs
+            ),
+        );
+    }
 
+    // Initialize YouTube Manager
     let youtube_manager = Arc::new(
-        youtube::YouTubeManager::new(
-            youtube_client_id,
-            youtube_client_secret,
-            youtube_redirect_uri,
-            Arc::clone(&storage),
-        )
-        .expect("Failed to initialize YouTube manager"),
+        youtube::YouTubeManager::from_env(Arc::clone(&storage))
+            .expect("Failed to initialize YouTube manager")
+            .with_notifications(Arc::clone(&notification_center)),
     );
 
     // Load stored YouTube credentials if available
@@ -157,28 +472,109 @@ async fn main() {
 
     tracing::info!("YouTube Manager initialized");
 
+    // Discord webhook notifications (configured in settings)
+    let notifications = Arc::new(notifications::DiscordNotifier::new(Arc::clone(
+        &recording_settings,
+    )));
+
+    // Warn once when the recordings disk runs low
+    let disk_notifications = Arc::clone(&notification_center);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(10 * 60));
+        let mut was_low = false;
+
+        loop {
+            interval.tick().await;
+
+            let Some(disk) = setup::disk_space(&recordings_dir) else {
+                continue;
+            };
+            if !disk.sufficient && !was_low {
+                disk_notifications.push(
+                    notifications::center::NotificationKind::DiskLow,
+                    "Disk space is running low",
+                    format!(
+                        "{:.1} GB left for recordings. Delete old clips to free up space.",
+                        disk.available_mb as f64 / 1024.0
+                    ),
+                );
+            }
+            was_low = !disk.sufficient;
+        }
+    });
+
     let app_state = AppState {
         storage,
         auth,
         feature_gate,
         recording_manager: Arc::clone(&recording_manager),
+        recorder: Arc::clone(&recorder),
         auto_clip_manager: Arc::clone(&auto_clip_manager),
         recording_settings,
         hotkey_manager: Arc::clone(&hotkey_manager),
         metrics_collector: Arc::clone(&metrics_collector),
+        metrics_history,
         cleanup_manager: Arc::clone(&cleanup_manager),
         auto_composer,
         youtube_manager,
+        log_handle,
+        telemetry,
+        cloud_backup,
+        quota,
+        jobs: Arc::clone(&job_scheduler),
+        events: Arc::clone(&event_bus),
+        notifications,
+        notification_center,
+        plugins: plugin_registry,
+        obs: obs_recorder,
+        circuit_breakers,
+        ddragon,
     };
 
+    // Apply persisted settings before anything records, uploads or polls the
+    // League client
+    let startup_settings = app_state.recording_settings.read().await.clone();
+    if let Err(e) = settings::apply::apply_settings(&app_state, &startup_settings).await {
+        tracing::warn!("Failed to apply settings: {:#}", e);
+    }
+    game_activity.start();
+    cloud::start_background_sync(app_state.clone());
+
+    // Start background job worker
+    let job_state = app_state.clone();
+    job_scheduler.start(move |kind| {
+        let state = job_state.clone();
+        async move { jobs::executor::run(&state, kind).await }
+    });
+
+    // Switch to the profile linked to each game's mode when it starts
+    settings::profiles::start_game_mode_profiles(app_state.clone(), Arc::clone(&game_activity));
+
+    // Refresh YouTube view counts for the upload history every 6 hours
+    let stats_jobs = Arc::clone(&job_scheduler);
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(6 * 60 * 60));
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = stats_jobs
+                .enqueue_unique(jobs::JobKind::RefreshVideoStats, jobs::JobPriority::Low)
+                .await
+            {
+                tracing::warn!("Failed to queue video stats refresh: {}", e);
+            }
+        }
+    });
+
     // Start hotkey system with callbacks
-    let recording_manager_hotkey = Arc::clone(&recording_manager);
+    let recorder_hotkey = Arc::clone(&recorder);
     let auto_clip_manager_hotkey = Arc::clone(&auto_clip_manager);
 
     tokio::spawn(async move {
         hotkey_manager
             .start(move |event| {
-                let rm = Arc::clone(&recording_manager_hotkey);
+                let recorder = Arc::clone(&recorder_hotkey);
                 let acm = Arc::clone(&auto_clip_manager_hotkey);
 
                 tokio::spawn(async move {
@@ -195,13 +591,13 @@ async fn main() {
                                 if let Err(e) = acm.stop_event_monitoring().await {
                                     tracing::error!("Failed to stop auto-capture: {}", e);
                                 }
-                                if let Err(e) = rm.write().await.stop_replay_buffer().await {
+                                if let Err(e) = recorder.stop_replay_buffer().await {
                                     tracing::error!("Failed to stop replay buffer: {}", e);
                                 }
                             } else {
                                 // Start auto-capture
                                 tracing::info!("Hotkey F8: Starting auto-capture");
-                                if let Err(e) = rm.write().await.start_replay_buffer().await {
+                                if let Err(e) = recorder.start_replay_buffer().await {
                                     tracing::error!("Failed to start replay buffer: {}", e);
                                 }
                                 if let Err(e) = acm.start_event_monitoring().await {
@@ -213,31 +609,7 @@ async fn main() {
                             // Save last 60 seconds
                             tracing::info!("Hotkey F9: Saving 60s replay");
 
-                            use crate::recording::GameEvent;
-                            use std::time::Instant;
-
-                            let manual_event = GameEvent {
-                                event_id: 0,
-                                event_name: "HotkeyReplay60".to_string(),
-                                event_time: 0.0,
-                                killer_name: None,
-                                victim_name: None,
-                                assisters: vec![],
-                                priority: 3,
-                                timestamp: Instant::now(),
-                            };
-
-                            match rm
-                                .read()
-                                .await
-                                .save_clip(
-                                    &manual_event,
-                                    format!("hotkey_60s_{}", Instant::now().elapsed().as_secs()),
-                                    3,
-                                    60.0,
-                                )
-                                .await
-                            {
+                            match acm.save_manual_clip("hotkey_60s", 60.0, 3).await {
                                 Ok(path) => tracing::info!("Saved 60s replay to: {:?}", path),
                                 Err(e) => tracing::error!("Failed to save 60s replay: {}", e),
                             }
@@ -246,31 +618,7 @@ async fn main() {
                             // Save last 30 seconds
                             tracing::info!("Hotkey F10: Saving 30s replay");
 
-                            use crate::recording::GameEvent;
-                            use std::time::Instant;
-
-                            let manual_event = GameEvent {
-                                event_id: 0,
-                                event_name: "HotkeyReplay30".to_string(),
-                                event_time: 0.0,
-                                killer_name: None,
-                                victim_name: None,
-                                assisters: vec![],
-                                priority: 2,
-                                timestamp: Instant::now(),
-                            };
-
-                            match rm
-                                .read()
-                                .await
-                                .save_clip(
-                                    &manual_event,
-                                    format!("hotkey_30s_{}", Instant::now().elapsed().as_secs()),
-                                    2,
-                                    30.0,
-                                )
-                                .await
-                            {
+                            match acm.save_manual_clip("hotkey_30s", 30.0, 2).await {
                                 Ok(path) => tracing::info!("Saved 30s replay to: {:?}", path),
                                 Err(e) => tracing::error!("Failed to save 30s replay: {}", e),
                             }
@@ -284,6 +632,21 @@ async fn main() {
 
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_notification::init())
+        .setup(move |app| {
+            event_bus.attach(app.handle().clone());
+            toasts.attach(app.handle().clone());
+            tray::init(app.handle())?;
+
+            // Launched at login with "start minimized": stay in the tray
+            if utils::autostart::launched_minimized() {
+                if let Some(window) = app.get_webview_window("main") {
+                    window.hide()?;
+                }
+                tracing::info!("Started minimized to tray");
+            }
+            Ok(())
+        })
         .manage(app_state)
         .invoke_handler(tauri::generate_handler![
             // Auth commands
@@ -298,20 +661,36 @@ async fn main() {
             recording::commands::start_recording,
             recording::commands::stop_recording,
             recording::commands::get_recording_status,
+            recording::commands::pause_replay_buffer,
+            recording::commands::resume_replay_buffer,
             recording::commands::start_auto_capture,
             recording::commands::stop_auto_capture,
+            recording::commands::get_live_client_status,
             recording::commands::save_replay,
             recording::commands::get_saved_clips,
             recording::commands::clear_saved_clips,
             recording::commands::list_audio_devices,
+            recording::commands::list_video_devices,
+            recording::commands::list_capture_displays,
             recording::commands::get_recording_quality_info,
+            recording::commands::get_recoverable_sessions,
+            recording::commands::recover_session,
+            recording::commands::discard_recoverable_session,
+            recording::commands::extract_replay_highlights,
             // Video commands
             video::commands::get_clips,
             video::commands::extract_clip,
             video::commands::compose_shorts,
             video::commands::generate_thumbnail,
+            video::commands::capture_frame,
             video::commands::get_video_duration,
+            video::commands::import_vod,
+            video::commands::export_markers,
             video::commands::delete_clip,
+            video::commands::export_reels,
+            video::commands::archive_game,
+            video::commands::extract_audio,
+            video::commands::export_gif,
             // Auto-edit commands
             video::commands::start_auto_edit,
             video::commands::get_auto_edit_progress,
@@ -320,11 +699,26 @@ async fn main() {
             video::commands::load_canvas_template,
             video::commands::list_canvas_templates,
             video::commands::delete_canvas_template,
+            video::commands::generate_upload_thumbnail,
+            // Clip selection feedback commands
+            video::commands::get_selection_weights,
+            video::commands::set_selection_weight,
+            video::commands::reset_selection_feedback,
+            // Music library commands
+            video::commands::list_music_tracks,
+            video::commands::import_music_track,
+            video::commands::tag_music_track,
+            video::commands::delete_music_track,
             // LCU commands
             lcu::commands::connect_lcu,
             lcu::commands::check_lcu_status,
             lcu::commands::get_current_game,
             lcu::commands::is_in_game,
+            lcu::commands::get_league_install_path,
+            lcu::commands::set_league_install_path,
+            // Data Dragon commands
+            ddragon::commands::get_champion_assets,
+            ddragon::commands::get_item_icon,
             // Payment commands
             payments::commands::create_subscription,
             payments::commands::confirm_payment,
@@ -337,12 +731,30 @@ async fn main() {
             storage::commands::get_game_metadata,
             storage::commands::save_game_metadata,
             storage::commands::get_game_events,
+            storage::commands::get_game_timeline,
             storage::commands::save_game_events,
             storage::commands::save_clip_metadata,
             storage::commands::delete_game,
             storage::commands::get_dashboard_stats,
+            storage::commands::get_storage_breakdown,
+            storage::commands::find_duplicate_clips,
             storage::commands::list_clips,
             storage::commands::get_auto_edit_quota,
+            storage::commands::verify_library,
+            storage::commands::repair_library,
+            storage::commands::list_trash,
+            storage::commands::restore_clip,
+            storage::commands::empty_trash,
+            storage::commands::batch_delete_clips,
+            storage::commands::batch_tag_clips,
+            storage::commands::batch_export_clips,
+            storage::commands::migrate_storage,
+            quota::commands::get_quota,
+            // Background job commands
+            jobs::commands::list_jobs,
+            jobs::commands::cancel_job,
+            jobs::commands::run_job_anyway,
+            jobs::commands::queue_auto_edit,
             storage::commands::get_auto_edit_results,
             storage::commands::get_auto_edit_result,
             storage::commands::delete_auto_edit_result,
@@ -351,21 +763,65 @@ async fn main() {
             settings::commands::get_recording_settings,
             settings::commands::save_recording_settings,
             settings::commands::reset_settings_to_default,
+            settings::commands::list_settings_profiles,
+            settings::commands::create_settings_profile,
+            settings::commands::apply_settings_profile,
+            settings::commands::delete_settings_profile,
+            settings::commands::set_game_mode_profile,
+            settings::commands::export_app_config,
+            settings::commands::import_app_config,
+            // Setup wizard commands
+            setup::commands::get_setup_report,
+            setup::commands::apply_recommended_settings,
+            setup::commands::complete_setup,
+            setup::commands::seed_demo_library,
             // Utils commands
             utils::commands::get_recording_metrics,
             utils::commands::get_system_metrics,
             utils::commands::get_health_status,
+            utils::commands::get_metrics_history,
+            utils::commands::get_circuit_breaker_status,
+            utils::commands::reset_circuit_breaker,
+            utils::commands::set_log_level,
+            // Telemetry commands
+            telemetry::commands::get_telemetry_status,
+            // Cloud backup commands (PRO)
+            cloud::commands::sync_now,
+            cloud::commands::get_last_sync,
+            cloud::commands::upload_clip_to_cloud,
+            cloud::commands::list_cloud_clips,
+            cloud::commands::download_cloud_clip,
+            cloud::commands::get_cloud_storage_usage,
+            cloud::commands::share_clip,
+            cloud::commands::list_clip_shares,
             utils::commands::get_app_version,
             utils::commands::force_cleanup,
             utils::commands::get_disk_space_info,
+            // Notification commands
+            notifications::commands::send_test_notification,
+            notifications::commands::list_notifications,
+            notifications::commands::mark_read,
+            // OBS commands
+            obs::commands::test_obs_connection,
+            // Upload queue commands
+            uploads::commands::pause_uploads,
+            uploads::commands::resume_uploads,
+            uploads::commands::get_upload_queue_status,
             // YouTube commands
             youtube::commands::youtube_start_auth,
             youtube::commands::youtube_start_auth_with_server,
             youtube::commands::youtube_complete_auth,
             youtube::commands::youtube_get_auth_status,
+            youtube::commands::youtube_list_channels,
+            youtube::commands::youtube_switch_channel,
             youtube::commands::youtube_upload_video,
+            youtube::commands::youtube_retry_upload,
+            youtube::commands::save_upload_draft,
+            youtube::commands::get_upload_draft,
+            youtube::commands::suggest_video_metadata,
             youtube::commands::youtube_get_upload_progress,
             youtube::commands::youtube_get_video_details,
+            youtube::commands::youtube_get_video_stats,
             youtube::commands::youtube_get_upload_history,
             youtube::commands::youtube_add_to_history,
             youtube::commands::youtube_get_quota_info,
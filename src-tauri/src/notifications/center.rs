@@ -0,0 +1,235 @@
+//! In-app notification center
+//!
+//! Keeps a history of things that happened while the user wasn't looking
+//! (clips saved, uploads finished, quotas running out, low disk space) with
+//! read/unread state. New notifications are pushed to the frontend as
+//! [`NOTIFICATION_ADDED`](crate::events::NOTIFICATION_ADDED) events and the
+//! list is saved to disk so it survives restarts.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::events::{AppEvent, EventBus};
+use crate::quota::QuotaStatus;
+use crate::storage;
+
+/// Oldest notifications are dropped beyond this
+const MAX_NOTIFICATIONS: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    ClipSaved,
+    UploadCompleted,
+    QuotaLow,
+    DiskLow,
+    /// Segments of a crashed session can be salvaged
+    RecoveryAvailable,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InAppNotification {
+    pub id: String,
+    pub kind: NotificationKind,
+    pub title: String,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+    pub read: bool,
+}
+
+/// Persistent list of [`InAppNotification`]s, newest first
+pub struct NotificationCenter {
+    path: PathBuf,
+    entries: Mutex<Vec<InAppNotification>>,
+    events: Arc<EventBus>,
+}
+
+impl NotificationCenter {
+    /// Load the notifications saved at `path`
+    ///
+    /// Starts with an empty history if there are none or they can't be read.
+    pub fn load(path: PathBuf) -> Self {
+        let entries = if path.exists() {
+            storage::read_json(&path).unwrap_or_else(|e| {
+                warn!("Discarding unreadable notifications {:?}: {}", path, e);
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            path,
+            entries: Mutex::new(entries),
+            events: Arc::new(EventBus::new()),
+        }
+    }
+
+    /// Publish new notifications on `events`
+    pub fn with_events(mut self, events: Arc<EventBus>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Add an unread notification
+    pub fn push(
+        &self,
+        kind: NotificationKind,
+        title: impl Into<String>,
+        message: impl Into<String>,
+    ) -> InAppNotification {
+        let notification = InAppNotification {
+            id: uuid::Uuid::new_v4().to_string(),
+            kind,
+            title: title.into(),
+            message: message.into(),
+            created_at: Utc::now(),
+            read: false,
+        };
+
+        {
+            let mut entries = self.entries.lock();
+            entries.insert(0, notification.clone());
+            entries.truncate(MAX_NOTIFICATIONS);
+            self.persist(&entries);
+        }
+
+        self.events
+            .emit(AppEvent::NotificationAdded(notification.clone()));
+        notification
+    }
+
+    /// Notifications, newest first
+    pub fn list(&self, unread_only: bool) -> Vec<InAppNotification> {
+        self.entries
+            .lock()
+            .iter()
+            .filter(|n| !unread_only || !n.read)
+            .cloned()
+            .collect()
+    }
+
+    pub fn unread_count(&self) -> usize {
+        self.entries.lock().iter().filter(|n| !n.read).count()
+    }
+
+    /// Mark the given notifications (all if `None`) as read
+    ///
+    /// Returns how many were unread.
+    pub fn mark_read(&self, ids: Option<&[String]>) -> usize {
+        let mut entries = self.entries.lock();
+        let mut marked = 0;
+        for notification in entries.iter_mut() {
+            let selected = ids.is_none_or(|ids| ids.contains(&notification.id));
+            if selected && !notification.read {
+                notification.read = true;
+                marked += 1;
+            }
+        }
+
+        if marked > 0 {
+            self.persist(&entries);
+        }
+        marked
+    }
+
+    /// Warn once a metered feature is nearly used up, after recording a use
+    pub fn quota_recorded(&self, status: &QuotaStatus) {
+        if let Some((title, message)) = quota_warning(status) {
+            self.push(NotificationKind::QuotaLow, title, message);
+        }
+    }
+
+    fn persist(&self, entries: &[InAppNotification]) {
+        if let Err(e) = save(&self.path, entries) {
+            warn!("Failed to save notifications: {}", e);
+        }
+    }
+}
+
+fn save(path: &Path, entries: &[InAppNotification]) -> Result<()> {
+    let json = serde_json::to_string_pretty(entries)?;
+    storage::write_atomic(path, json).with_context(|| format!("Failed to write {:?}", path))
+}
+
+/// Title and message when `status` just reached the low or used-up mark
+///
+/// "Low" is the last fifth of the monthly limit (at least one use left), so
+/// each mark is crossed once per period.
+fn quota_warning(status: &QuotaStatus) -> Option<(String, String)> {
+    let limit = status.limit?;
+    let remaining = status.remaining?;
+
+    if remaining == 0 {
+        Some((
+            format!("Monthly {} quota used up", status.feature),
+            format!(
+                "All {} used this month. Upgrade to PRO for unlimited usage.",
+                limit
+            ),
+        ))
+    } else if remaining == (limit / 5).max(1) {
+        Some((
+            format!("Monthly {} quota almost used up", status.feature),
+            format!("{} of {} left this month.", remaining, limit),
+        ))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quota::MeteredFeature;
+
+    #[test]
+    fn test_notifications_persist_with_read_state() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_notification_center");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+        let path = temp_dir.join("notifications.json");
+
+        let center = NotificationCenter::load(path.clone());
+        let first = center.push(NotificationKind::ClipSaved, "Clip saved", "Penta Kill");
+        center.push(NotificationKind::DiskLow, "Disk almost full", "2 GB left");
+        assert_eq!(center.unread_count(), 2);
+
+        assert_eq!(center.mark_read(Some(&[first.id.clone()])), 1);
+        assert_eq!(center.mark_read(Some(&[first.id.clone()])), 0);
+
+        let reloaded = NotificationCenter::load(path);
+        let all = reloaded.list(false);
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].kind, NotificationKind::DiskLow);
+        assert!(all[1].read);
+        assert_eq!(reloaded.list(true).len(), 1);
+
+        assert_eq!(reloaded.mark_read(None), 1);
+        assert_eq!(reloaded.unread_count(), 0);
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_quota_warning_marks() {
+        let status = |used: u32| QuotaStatus {
+            feature: MeteredFeature::YoutubeUpload,
+            tier: "Free".to_string(),
+            used,
+            limit: Some(10),
+            remaining: Some(10 - used),
+            period: "2024-01".to_string(),
+        };
+
+        assert!(quota_warning(&status(7)).is_none());
+        assert!(quota_warning(&status(8)).unwrap().0.contains("almost"));
+        assert!(quota_warning(&status(9)).is_none());
+        assert!(quota_warning(&status(10)).unwrap().0.contains("used up"));
+    }
+}
@@ -0,0 +1,38 @@
+use super::center::InAppNotification;
+use crate::AppState;
+use tauri::State;
+
+/// Send a test message to the configured Discord webhook
+///
+/// The webhook is configured through `RecordingSettings.notifications`.
+#[tauri::command]
+pub async fn send_test_notification(state: State<'_, AppState>) -> Result<(), String> {
+    // FREE tier feature - no authentication required
+    state
+        .notifications
+        .send_test()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// In-app notifications, newest first
+#[tauri::command]
+pub async fn list_notifications(
+    state: State<'_, AppState>,
+    unread_only: Option<bool>,
+) -> Result<Vec<InAppNotification>, String> {
+    // FREE tier feature - no authentication required
+    Ok(state.notification_center.list(unread_only.unwrap_or(false)))
+}
+
+/// Mark in-app notifications as read (all of them if `ids` is omitted)
+///
+/// Returns how many were unread.
+#[tauri::command]
+pub async fn mark_read(
+    state: State<'_, AppState>,
+    ids: Option<Vec<String>>,
+) -> Result<usize, String> {
+    // FREE tier feature - no authentication required
+    Ok(state.notification_center.mark_read(ids.as_deref()))
+}
@@ -0,0 +1,410 @@
+pub mod center;
+pub mod commands;
+pub mod toast;
+
+/// Discord webhook notifications
+///
+/// Posts an embed to the webhook configured in
+/// `RecordingSettings.notifications` when a high-priority clip is saved (once
+/// its thumbnail is ready, so the thumbnail can be attached) or a YouTube
+/// upload completes. Sends are rate limited so a teamfight full of clips
+/// can't flood the channel or trip Discord's own webhook limits.
+use anyhow::{Context, Result};
+use reqwest::{multipart, Client};
+use serde_json::json;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock};
+use tracing::{debug, info, warn};
+
+use crate::settings::models::RecordingSettings;
+use crate::storage::models::EventType;
+
+/// At most this many notifications per [`RATE_LIMIT_WINDOW`]
+const RATE_LIMIT_MAX: usize = 5;
+
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// Accepted webhook URL prefixes
+const DISCORD_WEBHOOK_PREFIXES: [&str; 2] = [
+    "https://discord.com/api/webhooks/",
+    "https://discordapp.com/api/webhooks/",
+];
+
+/// Embed accent colours
+const COLOR_CLIP: u32 = 0xC8AA6E; // League gold
+const COLOR_UPLOAD: u32 = 0xFF0000; // YouTube red
+const COLOR_TEST: u32 = 0x5865F2; // Discord blurple
+
+/// Something worth telling the user's Discord about
+#[derive(Debug, Clone)]
+pub enum Notification {
+    ClipSaved {
+        clip_path: PathBuf,
+        thumbnail_path: Option<PathBuf>,
+        event_type: EventType,
+        priority: u8,
+        champion: Option<String>,
+    },
+    UploadCompleted {
+        video_id: String,
+        title: String,
+        thumbnail_url: Option<String>,
+    },
+    Test,
+}
+
+impl Notification {
+    /// Webhook message body (embed only; attachments are added separately)
+    pub fn payload(&self) -> serde_json::Value {
+        let embed = match self {
+            Notification::ClipSaved {
+                clip_path,
+                thumbnail_path,
+                event_type,
+                priority,
+                champion,
+            } => {
+                let mut fields = vec![json!({
+                    "name": "Priority",
+                    "value": format!("{}/5", priority),
+                    "inline": true,
+                })];
+                if let Some(champion) = champion {
+                    fields.push(json!({ "name": "Champion", "value": champion, "inline": true }));
+                }
+                fields.push(json!({
+                    "name": "File",
+                    "value": format!("`{}`", clip_path.display()),
+                }));
+
+                let mut embed = json!({
+                    "title": format!("Clip saved: {}", event_label(event_type)),
+                    "color": COLOR_CLIP,
+                    "fields": fields,
+                });
+                if let Some(name) = thumbnail_path.as_deref().and_then(attachment_name) {
+                    embed["thumbnail"] = json!({ "url": format!("attachment://{}", name) });
+                }
+                embed
+            }
+            Notification::UploadCompleted {
+                video_id,
+                title,
+                thumbnail_url,
+            } => {
+                let mut embed = json!({
+                    "title": format!("Uploaded to YouTube: {}", title),
+                    "url": format!("https://youtu.be/{}", video_id),
+                    "color": COLOR_UPLOAD,
+                });
+                if let Some(url) = thumbnail_url {
+                    embed["thumbnail"] = json!({ "url": url });
+                }
+                embed
+            }
+            Notification::Test => json!({
+                "title": "LoLShorts notifications are working",
+                "description": "You'll get a message here for highlight clips and YouTube uploads.",
+                "color": COLOR_TEST,
+            }),
+        };
+
+        json!({ "username": "LoLShorts", "embeds": [embed] })
+    }
+
+    /// Local file to attach (only clip thumbnails that still exist)
+    fn attachment(&self) -> Option<&Path> {
+        match self {
+            Notification::ClipSaved {
+                thumbnail_path: Some(path),
+                ..
+            } if path.exists() => Some(path),
+            _ => None,
+        }
+    }
+}
+
+/// Human-readable event name for embed titles
+pub fn event_label(event_type: &EventType) -> String {
+    match event_type {
+        EventType::ChampionKill => "Kill".to_string(),
+        EventType::Multikill(2) => "Double Kill".to_string(),
+        EventType::Multikill(3) => "Triple Kill".to_string(),
+        EventType::Multikill(4) => "Quadra Kill".to_string(),
+        EventType::Multikill(5) => "Penta Kill".to_string(),
+        EventType::Multikill(n) => format!("{}x Multikill", n),
+        EventType::TurretKill => "Turret".to_string(),
+        EventType::InhibitorKill => "Inhibitor".to_string(),
+        EventType::DragonKill => "Dragon".to_string(),
+        EventType::BaronKill => "Baron".to_string(),
+        EventType::Ace => "Ace".to_string(),
+        EventType::FirstBlood => "First Blood".to_string(),
+        EventType::Death => "Death".to_string(),
+        EventType::Custom(name) => name.clone(),
+    }
+}
+
+pub fn is_valid_webhook_url(url: &str) -> bool {
+    DISCORD_WEBHOOK_PREFIXES
+        .iter()
+        .any(|prefix| url.starts_with(prefix) && url.len() > prefix.len())
+}
+
+fn attachment_name(path: &Path) -> Option<String> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+}
+
+/// Sliding-window rate limiter
+#[derive(Debug)]
+pub struct RateLimiter {
+    max: usize,
+    window: Duration,
+    sent: VecDeque<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(max: usize, window: Duration) -> Self {
+        Self {
+            max,
+            window,
+            sent: VecDeque::with_capacity(max),
+        }
+    }
+
+    /// Take a slot if one is free at `now`
+    pub fn try_acquire(&mut self, now: Instant) -> bool {
+        while let Some(&oldest) = self.sent.front() {
+            if now.duration_since(oldest) >= self.window {
+                self.sent.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if self.sent.len() >= self.max {
+            return false;
+        }
+
+        self.sent.push_back(now);
+        true
+    }
+}
+
+/// Sends [`Notification`]s to the configured Discord webhook
+pub struct DiscordNotifier {
+    http_client: Client,
+    settings: Arc<RwLock<RecordingSettings>>,
+    limiter: Mutex<RateLimiter>,
+}
+
+impl DiscordNotifier {
+    pub fn new(settings: Arc<RwLock<RecordingSettings>>) -> Self {
+        let http_client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            http_client,
+            settings,
+            limiter: Mutex::new(RateLimiter::new(RATE_LIMIT_MAX, RATE_LIMIT_WINDOW)),
+        }
+    }
+
+    /// Send a notification if it's enabled in the settings
+    ///
+    /// Returns false if it was filtered out or rate limited.
+    pub async fn notify(&self, notification: Notification) -> Result<bool> {
+        let (url, enabled) = {
+            let settings = self.settings.read().await;
+            let config = &settings.notifications;
+            let enabled = match &notification {
+                Notification::ClipSaved { priority, .. } => {
+                    config.notify_clip_saved && *priority >= config.min_clip_priority
+                }
+                Notification::UploadCompleted { .. } => config.notify_upload_completed,
+                Notification::Test => true,
+            };
+            (config.discord_webhook_url.clone(), enabled)
+        };
+
+        let Some(url) = url.filter(|url| !url.is_empty()) else {
+            return Ok(false);
+        };
+        if !enabled {
+            return Ok(false);
+        }
+
+        if !self.limiter.lock().await.try_acquire(Instant::now()) {
+            debug!("Discord notification rate limited, dropping");
+            return Ok(false);
+        }
+
+        self.send(&url, &notification).await?;
+        Ok(true)
+    }
+
+    /// Send a notification without waiting for it, logging failures
+    pub fn notify_in_background(self: &Arc<Self>, notification: Notification) {
+        let notifier = Arc::clone(self);
+        tokio::spawn(async move {
+            if let Err(e) = notifier.notify(notification).await {
+                warn!("Failed to send Discord notification: {}", e);
+            }
+        });
+    }
+
+    /// Send a test message to the configured webhook
+    pub async fn send_test(&self) -> Result<()> {
+        let url = self
+            .settings
+            .read()
+            .await
+            .notifications
+            .discord_webhook_url
+            .clone()
+            .filter(|url| !url.is_empty())
+            .context("No Discord webhook URL configured")?;
+
+        if !self.notify(Notification::Test).await? {
+            anyhow::bail!("Too many notifications sent recently, try again in a minute");
+        }
+
+        info!("Test notification sent to {}", redact(&url));
+        Ok(())
+    }
+
+    async fn send(&self, url: &str, notification: &Notification) -> Result<()> {
+        if !is_valid_webhook_url(url) {
+            anyhow::bail!("Invalid Discord webhook URL");
+        }
+
+        let payload = notification.payload();
+        let request = match notification.attachment() {
+            Some(path) => {
+                let data = tokio::fs::read(path)
+                    .await
+                    .with_context(|| format!("Failed to read {:?}", path))?;
+                let file_name = attachment_name(path).unwrap_or_default();
+                let form = multipart::Form::new()
+                    .text("payload_json", payload.to_string())
+                    .part(
+                        "files[0]",
+                        multipart::Part::bytes(data).file_name(file_name),
+                    );
+                self.http_client.post(url).multipart(form)
+            }
+            None => self.http_client.post(url).json(&payload),
+        };
+
+        let response = request
+            .send()
+            .await
+            .context("Failed to send Discord notification")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            warn!(
+                "Discord webhook rejected notification ({}): {}",
+                status, body
+            );
+            anyhow::bail!("Discord webhook returned {}", status);
+        }
+
+        Ok(())
+    }
+}
+
+/// Webhook URL without its secret token, for logging
+fn redact(url: &str) -> String {
+    match url.rsplit_once('/') {
+        Some((base, _token)) => format!("{}/***", base),
+        None => "***".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter_window() {
+        let mut limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let start = Instant::now();
+
+        assert!(limiter.try_acquire(start));
+        assert!(limiter.try_acquire(start + Duration::from_secs(1)));
+        assert!(!limiter.try_acquire(start + Duration::from_secs(2)));
+
+        // First slot frees up once the window has passed
+        assert!(limiter.try_acquire(start + Duration::from_secs(60)));
+        assert!(!limiter.try_acquire(start + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn test_webhook_url_validation() {
+        assert!(is_valid_webhook_url(
+            "https://discord.com/api/webhooks/123/abc"
+        ));
+        assert!(is_valid_webhook_url(
+            "https://discordapp.com/api/webhooks/123/abc"
+        ));
+        assert!(!is_valid_webhook_url("https://discord.com/api/webhooks/"));
+        assert!(!is_valid_webhook_url(
+            "http://discord.com/api/webhooks/123/abc"
+        ));
+        assert!(!is_valid_webhook_url("https://example.com/webhook"));
+    }
+
+    #[test]
+    fn test_clip_payload() {
+        let notification = Notification::ClipSaved {
+            clip_path: PathBuf::from("clips/penta.mp4"),
+            thumbnail_path: Some(PathBuf::from("thumbnails/penta.jpg")),
+            event_type: EventType::Multikill(5),
+            priority: 5,
+            champion: Some("Jinx".to_string()),
+        };
+
+        let payload = notification.payload();
+        let embed = &payload["embeds"][0];
+        assert_eq!(embed["title"], "Clip saved: Penta Kill");
+        assert_eq!(embed["thumbnail"]["url"], "attachment://penta.jpg");
+        assert_eq!(embed["fields"][1]["value"], "Jinx");
+    }
+
+    #[test]
+    fn test_upload_payload_links_video() {
+        let payload = Notification::UploadCompleted {
+            video_id: "abc123".to_string(),
+            title: "Pentakill".to_string(),
+            thumbnail_url: None,
+        }
+        .payload();
+
+        assert_eq!(payload["embeds"][0]["url"], "https://youtu.be/abc123");
+        assert!(payload["embeds"][0].get("thumbnail").is_none());
+    }
+
+    #[test]
+    fn test_redact() {
+        assert_eq!(
+            redact("https://discord.com/api/webhooks/123/secret"),
+            "https://discord.com/api/webhooks/123/***"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_notify_disabled_without_webhook() {
+        let settings = Arc::new(RwLock::new(RecordingSettings::default()));
+        let notifier = DiscordNotifier::new(settings);
+
+        assert!(!notifier.notify(Notification::Test).await.unwrap());
+        assert!(notifier.send_test().await.is_err());
+    }
+}
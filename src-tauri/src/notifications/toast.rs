@@ -0,0 +1,116 @@
+//! Native desktop notifications for clips saved in the background
+//!
+//! While the game has focus (usually fullscreen) the user can't see the
+//! app, so a high-priority clip (a pentakill by default) is announced with a
+//! Windows toast instead. Nothing is shown while LoLShorts itself is focused
+//! or during the quiet hours set in `RecordingSettings.notifications`.
+
+use chrono::Timelike;
+use std::sync::{Arc, OnceLock};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+
+use super::event_label;
+use crate::settings::models::{NotificationSettings, RecordingSettings};
+use crate::storage::models::EventType;
+
+/// Label of the main window (the default one from tauri.conf.json)
+const MAIN_WINDOW: &str = "main";
+
+/// Shows toasts once the app handle is attached during setup
+pub struct ToastNotifier {
+    settings: Arc<RwLock<RecordingSettings>>,
+    app: OnceLock<AppHandle>,
+}
+
+impl ToastNotifier {
+    pub fn new(settings: Arc<RwLock<RecordingSettings>>) -> Self {
+        Self {
+            settings,
+            app: OnceLock::new(),
+        }
+    }
+
+    /// Start showing toasts through the app's notification plugin
+    pub fn attach(&self, app: AppHandle) {
+        if self.app.set(app).is_err() {
+            warn!("Toast notifier already attached");
+        }
+    }
+
+    /// Announce a clip saved while the user is in game
+    ///
+    /// Returns false if the toast was filtered out.
+    pub async fn clip_saved(&self, event_type: &EventType, priority: u8) -> bool {
+        let Some(app) = self.app.get() else {
+            return false;
+        };
+
+        let allowed = {
+            let settings = self.settings.read().await;
+            should_show(&settings.notifications, priority, local_hour())
+        };
+        if !allowed || app_focused(app) {
+            return false;
+        }
+
+        let result = app
+            .notification()
+            .builder()
+            .title(format!("{} saved", event_label(event_type)))
+            .body("The clip is in your library.")
+            .show();
+
+        match result {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("Failed to show toast notification: {}", e);
+                false
+            }
+        }
+    }
+}
+
+/// Whether a clip toast is enabled for `priority` at `hour`
+fn should_show(config: &NotificationSettings, priority: u8, hour: u8) -> bool {
+    if !config.toast_clip_saved || priority < config.toast_min_priority {
+        return false;
+    }
+    if config.quiet_hours.contains(hour) {
+        debug!("Quiet hours, skipping toast");
+        return false;
+    }
+    true
+}
+
+fn local_hour() -> u8 {
+    chrono::Local::now().hour() as u8
+}
+
+/// The user is looking at LoLShorts, so the in-app UI is enough
+fn app_focused(app: &AppHandle) -> bool {
+    app.get_webview_window(MAIN_WINDOW)
+        .and_then(|window| window.is_focused().ok())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_show() {
+        let mut config = NotificationSettings::default();
+        assert!(should_show(&config, 5, 15));
+        assert!(!should_show(&config, 4, 15));
+
+        config.quiet_hours.enabled = true;
+        assert!(!should_show(&config, 5, 23));
+        assert!(should_show(&config, 5, 15));
+
+        config.toast_clip_saved = false;
+        assert!(!should_show(&config, 5, 15));
+    }
+}
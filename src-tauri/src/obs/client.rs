@@ -0,0 +1,230 @@
+//! Minimal obs-websocket (protocol v5) client
+//!
+//! Covers what the recorder needs: the Hello/Identify handshake with
+//! password authentication, requests, and waiting for output events.
+
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::VecDeque;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+/// Op codes from the obs-websocket v5 protocol
+mod op {
+    pub const HELLO: u64 = 0;
+    pub const IDENTIFY: u64 = 1;
+    pub const IDENTIFIED: u64 = 2;
+    pub const EVENT: u64 = 5;
+    pub const REQUEST: u64 = 6;
+    pub const REQUEST_RESPONSE: u64 = 7;
+}
+
+const RPC_VERSION: u64 = 1;
+
+/// Event subscription bit for output events (replay buffer saved, ...)
+const SUBSCRIBE_OUTPUTS: u64 = 1 << 6;
+
+/// How long OBS gets to answer a request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Events kept while waiting for a response (older ones are dropped)
+const MAX_PENDING_EVENTS: usize = 32;
+
+pub struct ObsClient {
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    next_request_id: u64,
+    /// Events received while waiting for something else
+    pending_events: VecDeque<Value>,
+    obs_websocket_version: String,
+}
+
+impl ObsClient {
+    /// Connect and identify, authenticating if OBS asks for a password
+    pub async fn connect(host: &str, port: u16, password: Option<&str>) -> Result<Self> {
+        let url = format!("ws://{}:{}", host, port);
+        let (socket, _) =
+            tokio::time::timeout(REQUEST_TIMEOUT, tokio_tungstenite::connect_async(&url))
+                .await
+                .context("Timed out connecting to OBS")?
+                .with_context(|| format!("Failed to connect to OBS at {}", url))?;
+
+        let mut client = Self {
+            socket,
+            next_request_id: 0,
+            pending_events: VecDeque::new(),
+            obs_websocket_version: String::new(),
+        };
+
+        let hello = client.receive_op(op::HELLO, REQUEST_TIMEOUT).await?;
+        client.obs_websocket_version = hello["obsWebSocketVersion"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+
+        let mut identify = json!({
+            "rpcVersion": RPC_VERSION,
+            "eventSubscriptions": SUBSCRIBE_OUTPUTS,
+        });
+        if let Some(auth) = hello.get("authentication") {
+            let Some(password) = password.filter(|p| !p.is_empty()) else {
+                bail!("OBS requires a WebSocket password");
+            };
+            identify["authentication"] = json!(auth_response(
+                password,
+                auth["salt"].as_str().unwrap_or_default(),
+                auth["challenge"].as_str().unwrap_or_default(),
+            ));
+        }
+        client.send(op::IDENTIFY, identify).await?;
+        client
+            .receive_op(op::IDENTIFIED, REQUEST_TIMEOUT)
+            .await
+            .context("OBS rejected the connection (wrong password?)")?;
+
+        tracing::info!(
+            "Connected to OBS at {} (obs-websocket {})",
+            url,
+            client.obs_websocket_version
+        );
+        Ok(client)
+    }
+
+    pub fn obs_websocket_version(&self) -> &str {
+        &self.obs_websocket_version
+    }
+
+    /// Send a request and return its response data
+    pub async fn request(&mut self, request_type: &str, data: Value) -> Result<Value> {
+        self.next_request_id += 1;
+        let request_id = self.next_request_id.to_string();
+        self.send(
+            op::REQUEST,
+            json!({
+                "requestType": request_type,
+                "requestId": request_id,
+                "requestData": data,
+            }),
+        )
+        .await?;
+
+        loop {
+            let response = self
+                .receive_op(op::REQUEST_RESPONSE, REQUEST_TIMEOUT)
+                .await?;
+            if response["requestId"] != request_id.as_str() {
+                continue;
+            }
+
+            let status = &response["requestStatus"];
+            if status["result"].as_bool() != Some(true) {
+                bail!(
+                    "OBS {} failed (code {}): {}",
+                    request_type,
+                    status["code"],
+                    status["comment"].as_str().unwrap_or("no details")
+                );
+            }
+            return Ok(response["responseData"].clone());
+        }
+    }
+
+    /// Wait for the next event of `event_type`, returning its data
+    pub async fn wait_for_event(&mut self, event_type: &str, timeout: Duration) -> Result<Value> {
+        if let Some(pos) = self
+            .pending_events
+            .iter()
+            .position(|event| event["eventType"] == event_type)
+        {
+            let event = self.pending_events.remove(pos).unwrap_or_default();
+            return Ok(event["eventData"].clone());
+        }
+
+        tokio::time::timeout(timeout, self.next_event(event_type, timeout))
+            .await
+            .with_context(|| format!("Timed out waiting for OBS {} event", event_type))?
+    }
+
+    /// Drop events received so far (so an old one can't answer a new wait)
+    pub fn clear_events(&mut self) {
+        self.pending_events.clear();
+    }
+
+    async fn next_event(&mut self, event_type: &str, timeout: Duration) -> Result<Value> {
+        loop {
+            let event = self.receive_op(op::EVENT, timeout).await?;
+            if event["eventType"] == event_type {
+                return Ok(event["eventData"].clone());
+            }
+        }
+    }
+
+    async fn send(&mut self, op: u64, data: Value) -> Result<()> {
+        let message = json!({ "op": op, "d": data }).to_string();
+        self.socket
+            .send(Message::Text(message))
+            .await
+            .context("Failed to send to OBS")
+    }
+
+    /// Read messages until one with `op` arrives, keeping events for later
+    ///
+    /// Fails if OBS sends nothing for `timeout`.
+    async fn receive_op(&mut self, expected: u64, timeout: Duration) -> Result<Value> {
+        loop {
+            let message = tokio::time::timeout(timeout, self.socket.next())
+                .await
+                .context("Timed out waiting for OBS")?
+                .context("OBS closed the connection")?
+                .context("Failed to read from OBS")?;
+
+            let text = match message {
+                Message::Text(text) => text,
+                Message::Close(frame) => bail!("OBS closed the connection: {:?}", frame),
+                _ => continue,
+            };
+            let mut message: Value =
+                serde_json::from_str(&text).context("Invalid message from OBS")?;
+
+            let op = message["op"].as_u64();
+            let data = message["d"].take();
+            if op == Some(expected) {
+                return Ok(data);
+            }
+            if op == Some(op::EVENT) {
+                if self.pending_events.len() >= MAX_PENDING_EVENTS {
+                    self.pending_events.pop_front();
+                }
+                self.pending_events.push_back(data);
+            }
+        }
+    }
+}
+
+/// Authentication string for Identify:
+/// `base64(sha256(base64(sha256(password + salt)) + challenge))`
+fn auth_response(password: &str, salt: &str, challenge: &str) -> String {
+    let secret = BASE64.encode(Sha256::digest(format!("{}{}", password, salt)));
+    BASE64.encode(Sha256::digest(format!("{}{}", secret, challenge)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auth_response() {
+        assert_eq!(
+            auth_response(
+                "supersecret",
+                "lM1GncleQOaCu9lT1yeUZhFYnqhsLLP1G5lAGo3ixaI=",
+                "+IxH4CnCiqpX1rM9scsNynZzbOe4KhDeYcTNS3PDaeY="
+            ),
+            "sQBlPUYd9mki/3XVFBp4Pt08FCMWdMVIqnFWdEitUME="
+        );
+    }
+}
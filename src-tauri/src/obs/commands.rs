@@ -0,0 +1,16 @@
+use super::ObsConnectionInfo;
+use crate::AppState;
+use tauri::State;
+
+/// Check that OBS can be reached with the saved connection settings
+///
+/// The connection is configured through `RecordingSettings.obs`.
+#[tauri::command]
+pub async fn test_obs_connection(state: State<'_, AppState>) -> Result<ObsConnectionInfo, String> {
+    // FREE tier feature - no authentication required
+    state
+        .obs
+        .test_connection()
+        .await
+        .map_err(|e| format!("{:#}", e))
+}
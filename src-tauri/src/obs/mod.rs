@@ -0,0 +1,351 @@
+pub mod client;
+pub mod commands;
+
+/// OBS as the recording backend
+///
+/// For users who already stream or record with OBS: instead of running our
+/// own FFmpeg replay buffer, clips are saved by asking OBS (through
+/// obs-websocket 5.x) to save its replay buffer, then the saved file is moved
+/// into the clips folder so the rest of the pipeline sees a normal clip.
+/// Enabled with `RecordingSettings.obs`.
+///
+/// Clip length is whatever the OBS replay buffer is set to; OBS can't save
+/// less, and the file is taken as is rather than trimmed with FFmpeg. The
+/// saved file is probed so clip metadata gets its real length.
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock as TokioRwLock};
+use tracing::{info, warn};
+
+use self::client::ObsClient;
+use crate::events::{AppEvent, ClipSaved, EventBus, RecordingStatusChanged};
+use crate::recording::{GameEvent, Recorder, RecordingStats, RecordingStatus};
+use crate::settings::models::RecordingSettings;
+use crate::storage::GameMetadata;
+use crate::video::VideoProcessor;
+
+/// How long OBS gets to write the replay buffer to disk
+const SAVE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// What a connection test found
+#[derive(Debug, Clone, Serialize)]
+pub struct ObsConnectionInfo {
+    pub obs_version: String,
+    pub obs_websocket_version: String,
+    /// False when the replay buffer isn't enabled in the OBS output settings
+    pub replay_buffer_available: bool,
+    pub replay_buffer_active: bool,
+}
+
+pub struct ObsRecorder {
+    settings: Arc<TokioRwLock<RecordingSettings>>,
+    /// Connected on first use and dropped after an error, so the next call
+    /// reconnects (e.g. after OBS was restarted)
+    client: Mutex<Option<ObsClient>>,
+    /// Resolves the clips folder from the storage settings
+    app_data_dir: PathBuf,
+    status: TokioRwLock<RecordingStatus>,
+    stats: RwLock<RecordingStats>,
+    current_game: TokioRwLock<Option<GameMetadata>>,
+    events: Arc<EventBus>,
+    /// Probed length of the last saved clip
+    saved_duration: parking_lot::Mutex<Option<(PathBuf, f64)>>,
+}
+
+impl ObsRecorder {
+    pub fn new(settings: Arc<TokioRwLock<RecordingSettings>>, app_data_dir: PathBuf) -> Self {
+        Self {
+            settings,
+            client: Mutex::new(None),
+            app_data_dir,
+            status: TokioRwLock::new(RecordingStatus::Idle),
+            stats: RwLock::new(RecordingStats::default()),
+            current_game: TokioRwLock::new(None),
+            events: Arc::new(EventBus::new()),
+            saved_duration: parking_lot::Mutex::new(None),
+        }
+    }
+
+    /// Publish status changes and saved clips on `events`
+    pub fn with_events(mut self, events: Arc<EventBus>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Whether OBS replaces the built-in recorder
+    pub async fn is_enabled(&self) -> bool {
+        self.settings.read().await.obs.enabled
+    }
+
+    /// Connect with the current settings and report what OBS offers
+    pub async fn test_connection(&self) -> Result<ObsConnectionInfo> {
+        let settings = self.settings.read().await.obs.clone();
+        let mut client =
+            ObsClient::connect(&settings.host, settings.port, settings.password.as_deref()).await?;
+
+        let version = client.request("GetVersion", json!({})).await?;
+        let replay_buffer = client.request("GetReplayBufferStatus", json!({})).await;
+
+        Ok(ObsConnectionInfo {
+            obs_version: version["obsVersion"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            obs_websocket_version: client.obs_websocket_version().to_string(),
+            replay_buffer_available: replay_buffer.is_ok(),
+            replay_buffer_active: replay_buffer
+                .map(|status| status["outputActive"] == true)
+                .unwrap_or(false),
+        })
+    }
+
+    /// Directory where clips are saved
+    pub async fn clips_dir(&self) -> PathBuf {
+        self.settings
+            .read()
+            .await
+            .storage
+            .clips_dir(&self.app_data_dir)
+    }
+
+    pub async fn set_current_game(&self, game: Option<GameMetadata>) {
+        *self.current_game.write().await = game;
+    }
+
+    /// Ask OBS to save its replay buffer, returning the written file
+    async fn save_replay_buffer(&self) -> Result<PathBuf> {
+        let mut guard = self.connect().await?;
+        let client = guard.as_mut().expect("connected above");
+
+        // A save the user triggered in OBS itself mustn't be taken for ours
+        client.clear_events();
+        let saved = match client.request("SaveReplayBuffer", json!({})).await {
+            Ok(_) => {
+                client
+                    .wait_for_event("ReplayBufferSaved", SAVE_TIMEOUT)
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        let saved = saved.inspect_err(|_| *guard = None)?;
+
+        saved["savedReplayPath"]
+            .as_str()
+            .map(PathBuf::from)
+            .context("OBS did not report where the replay was saved")
+    }
+
+    /// Send one request, reconnecting first if needed
+    async fn request(&self, request_type: &str, data: Value) -> Result<Value> {
+        let mut guard = self.connect().await?;
+        let client = guard.as_mut().expect("connected above");
+
+        client
+            .request(request_type, data)
+            .await
+            .inspect_err(|_| *guard = None)
+    }
+
+    async fn connect(&self) -> Result<tokio::sync::MutexGuard<'_, Option<ObsClient>>> {
+        let mut guard = self.client.lock().await;
+        if guard.is_none() {
+            let settings = self.settings.read().await.obs.clone();
+            let client =
+                ObsClient::connect(&settings.host, settings.port, settings.password.as_deref())
+                    .await
+                    .inspect_err(|e| warn!("OBS connection failed: {:#}", e))?;
+            *guard = Some(client);
+        }
+        Ok(guard)
+    }
+
+    async fn set_status(&self, status: RecordingStatus) {
+        *self.status.write().await = status;
+        self.events
+            .emit(AppEvent::RecordingStatusChanged(RecordingStatusChanged {
+                status,
+            }));
+    }
+}
+
+#[async_trait]
+impl Recorder for ObsRecorder {
+    /// Start the OBS replay buffer (left running if it already is)
+    async fn start_replay_buffer(&self) -> Result<()> {
+        let status = self.request("GetReplayBufferStatus", json!({})).await?;
+        if status["outputActive"] != true {
+            self.request("StartReplayBuffer", json!({})).await?;
+        }
+
+        self.set_status(RecordingStatus::Buffering).await;
+        info!("OBS replay buffer started");
+        Ok(())
+    }
+
+    /// Stop the OBS replay buffer
+    async fn stop_replay_buffer(&self) -> Result<()> {
+        if self.get_state().await == RecordingStatus::Idle {
+            return Ok(());
+        }
+
+        let status = self.request("GetReplayBufferStatus", json!({})).await?;
+        if status["outputActive"] == true {
+            self.request("StopReplayBuffer", json!({})).await?;
+        }
+
+        self.set_status(RecordingStatus::Idle).await;
+        info!("OBS replay buffer stopped");
+        Ok(())
+    }
+
+    /// Save the OBS replay buffer and move the file into the clips folder
+    ///
+    /// Named like clips from the built-in recorder, keeping the container
+    /// OBS recorded in. `duration_secs` is ignored in favor of the probed
+    /// length of the file; see the module docs.
+    async fn save_clip(
+        &self,
+        _event: &GameEvent,
+        clip_id: String,
+        priority: u8,
+        duration_secs: f64,
+    ) -> Result<PathBuf> {
+        if self.get_state().await == RecordingStatus::Idle {
+            anyhow::bail!("Cannot save clip: OBS replay buffer not active");
+        }
+
+        self.set_status(RecordingStatus::Processing).await;
+        let saved = self.save_replay_buffer().await;
+        self.set_status(RecordingStatus::Buffering).await;
+        let saved_path = saved?;
+
+        let game_id = self
+            .current_game
+            .read()
+            .await
+            .as_ref()
+            .map(|g| g.game_id.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+        let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string();
+        let extension = saved_path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("mp4");
+        let clip_filename = format!(
+            "{}_{}_p{}_{}.{}",
+            game_id, timestamp, priority, clip_id, extension
+        );
+
+        let clips_dir = self.clips_dir().await;
+        std::fs::create_dir_all(&clips_dir)?;
+        let output_path = clips_dir.join(&clip_filename);
+        move_file(&saved_path, &output_path)
+            .with_context(|| format!("Failed to import OBS replay {:?}", saved_path))?;
+
+        let duration_secs = match VideoProcessor::new().get_duration(&output_path).await {
+            Ok(duration) => {
+                *self.saved_duration.lock() = Some((output_path.clone(), duration));
+                duration
+            }
+            Err(e) => {
+                warn!("Failed to probe OBS clip {:?}: {}", output_path, e);
+                duration_secs
+            }
+        };
+
+        self.stats.write().clips_created += 1;
+        info!("OBS clip saved: {:?} ({:.1}s)", output_path, duration_secs);
+
+        self.events.emit(AppEvent::ClipSaved(ClipSaved {
+            clip_path: output_path.to_string_lossy().to_string(),
+            game_id,
+            priority,
+            duration_secs,
+        }));
+
+        Ok(output_path)
+    }
+
+    async fn get_state(&self) -> RecordingStatus {
+        *self.status.read().await
+    }
+
+    async fn get_stats(&self) -> RecordingStats {
+        self.stats.read().clone()
+    }
+
+    async fn take_clip_duration(&self, clip_path: &Path) -> Option<f64> {
+        let mut saved = self.saved_duration.lock();
+        match saved.take() {
+            Some((path, duration)) if path == clip_path => Some(duration),
+            other => {
+                *saved = other;
+                None
+            }
+        }
+    }
+}
+
+/// Move a file, copying when OBS records to a different drive
+fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(from, to)?;
+    std::fs::remove_file(from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn test_save_requires_active_buffer() {
+        let recorder = ObsRecorder::new(
+            Arc::new(TokioRwLock::new(RecordingSettings::default())),
+            std::env::temp_dir().join("lolshorts_test_obs"),
+        );
+        let event = GameEvent {
+            event_id: 0,
+            event_name: "manual".to_string(),
+            event_time: 0.0,
+            killer_name: None,
+            victim_name: None,
+            assisters: vec![],
+            priority: 3,
+            timestamp: Instant::now(),
+        };
+
+        // Fails before touching the network
+        let err = recorder
+            .save_clip(&event, "manual_1".to_string(), 3, 30.0)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("not active"));
+        assert_eq!(recorder.get_state().await, RecordingStatus::Idle);
+    }
+
+    #[test]
+    fn test_move_file() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_obs_move");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let from = temp_dir.join("Replay 2024-01-01 12-00-00.mkv");
+        let to = temp_dir.join("clip.mkv");
+        std::fs::write(&from, b"replay").unwrap();
+
+        move_file(&from, &to).unwrap();
+        assert!(!from.exists());
+        assert_eq!(std::fs::read(&to).unwrap(), b"replay");
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+}
@@ -1,8 +1,9 @@
 use crate::auth::middleware::require_auth;
 use crate::payments::toss::TossPaymentsClient;
+use crate::payments::{toss_key, PaymentError};
+use crate::utils::error::AppError;
 use crate::AppState;
 use chrono::Utc;
-use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use tauri::State;
 use uuid::Uuid;
@@ -24,22 +25,20 @@ pub struct SubscriptionResponse {
 pub async fn create_subscription(
     state: State<'_, AppState>,
     request: CreateSubscriptionRequest,
-) -> std::result::Result<SubscriptionResponse, String> {
+) -> Result<SubscriptionResponse, AppError> {
     // Require authentication
-    let user = require_auth(&state.auth).map_err(|e| e.to_string())?;
+    let user = require_auth(&state.auth)?;
 
     // Get Toss Payments secret key from environment
-    let _secret_key = std::env::var("TOSS_SECRET_KEY")
-        .map_err(|_| "TOSS_SECRET_KEY not configured".to_string())?;
+    let _secret_key = toss_key("TOSS_SECRET_KEY")?;
 
-    let client_key = std::env::var("TOSS_CLIENT_KEY")
-        .map_err(|_| "TOSS_CLIENT_KEY not configured".to_string())?;
+    let client_key = toss_key("TOSS_CLIENT_KEY")?;
 
     // Calculate amount based on period
     let amount = match request.period.as_str() {
         "MONTHLY" => 9900, // 9,900원/month
         "YEARLY" => 99000, // 99,000원/year (2 months free)
-        _ => return Err("Invalid subscription period".to_string()),
+        _ => return Err(PaymentError::InvalidPeriod(request.period.clone()).into()),
     };
 
     // Generate unique order ID
@@ -55,37 +54,24 @@ pub async fn create_subscription(
         _ => "LoLShorts PRO",
     };
 
-    // Create Supabase client
-    let supabase_url =
-        std::env::var("SUPABASE_URL").map_err(|_| "SUPABASE_URL not configured".to_string())?;
-    let supabase_key = std::env::var("SUPABASE_ANON_KEY")
-        .map_err(|_| "SUPABASE_ANON_KEY not configured".to_string())?;
+    // Get Supabase client
+    let supabase_client = state.auth.get_supabase_client()?;
 
-    let http_client = Client::new();
-
-    // Get user's license (using direct HTTP request)
-    let license_url = format!(
-        "{}/rest/v1/licenses?user_id=eq.{}&select=id",
-        supabase_url, user.id
-    );
-    let license_response = http_client
-        .get(&license_url)
-        .header("apikey", &supabase_key)
-        .header("Authorization", format!("Bearer {}", supabase_key))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to get license: {}", e))?;
-
-    let licenses: Vec<serde_json::Value> = license_response
-        .json()
+    // Get user's license
+    let license: Option<serde_json::Value> = supabase_client
+        .table("licenses")
+        .select("id")
+        .eq("user_id", &user.id)
+        .auth(&user.access_token)
+        .first()
         .await
-        .map_err(|e| format!("Failed to parse license response: {}", e))?;
+        .map_err(|e| PaymentError::Supabase(format!("Failed to get license: {}", e)))?;
 
-    let license_id = licenses
-        .first()
+    let license_id = license
+        .as_ref()
         .and_then(|l| l.get("id"))
         .and_then(|id| id.as_str())
-        .ok_or("License not found")?;
+        .ok_or_else(|| PaymentError::NotFound("License".to_string()))?;
 
     // Insert pending payment record into Supabase
     let payment_data = serde_json::json!({
@@ -101,17 +87,12 @@ pub async fn create_subscription(
         "requested_at": Utc::now().to_rfc3339(),
     });
 
-    let payments_url = format!("{}/rest/v1/toss_payments", supabase_url);
-    http_client
-        .post(&payments_url)
-        .header("apikey", &supabase_key)
-        .header("Authorization", format!("Bearer {}", supabase_key))
-        .header("Content-Type", "application/json")
-        .header("Prefer", "return=minimal")
-        .json(&payment_data)
-        .send()
+    supabase_client
+        .table("toss_payments")
+        .auth(&user.access_token)
+        .insert(&payment_data)
         .await
-        .map_err(|e| format!("Failed to create payment record: {}", e))?;
+        .map_err(|e| PaymentError::Supabase(format!("Failed to create payment record: {}", e)))?;
 
     // Generate Toss Payments checkout URL
     let success_url = "http://localhost:1420/payment/success";
@@ -140,41 +121,32 @@ pub async fn confirm_payment(
     payment_key: String,
     order_id: String,
     amount: i64,
-) -> std::result::Result<(), String> {
+) -> Result<(), AppError> {
     // Require authentication
-    let user = require_auth(&state.auth).map_err(|e| e.to_string())?;
+    let user = require_auth(&state.auth)?;
 
-    let secret_key = std::env::var("TOSS_SECRET_KEY")
-        .map_err(|_| "TOSS_SECRET_KEY not configured".to_string())?;
+    let secret_key = toss_key("TOSS_SECRET_KEY")?;
 
     let client = TossPaymentsClient::new(secret_key);
 
     // Get payment details from Toss
-    let payment = client
-        .get_payment(&payment_key)
-        .await
-        .map_err(|e| format!("Failed to get payment: {}", e))?;
+    let payment = client.get_payment(&payment_key).await?;
 
     // Verify payment
     if payment.status != "DONE" {
-        return Err(format!("Payment not completed. Status: {}", payment.status));
+        return Err(PaymentError::InvalidStatus(payment.status).into());
     }
 
     if payment.total_amount != amount {
-        return Err("Payment amount mismatch".to_string());
+        return Err(PaymentError::PaymentFailed("Payment amount mismatch".to_string()).into());
     }
 
     if payment.order_id != order_id {
-        return Err("Order ID mismatch".to_string());
+        return Err(PaymentError::PaymentFailed("Order ID mismatch".to_string()).into());
     }
 
     // Update payment record in Supabase (triggers will auto-upgrade license)
-    let supabase_url =
-        std::env::var("SUPABASE_URL").map_err(|_| "SUPABASE_URL not configured".to_string())?;
-    let supabase_key = std::env::var("SUPABASE_ANON_KEY")
-        .map_err(|_| "SUPABASE_ANON_KEY not configured".to_string())?;
-
-    let http_client = Client::new();
+    let supabase_client = state.auth.get_supabase_client()?;
 
     let update_data = serde_json::json!({
         "payment_key": payment_key,
@@ -186,20 +158,14 @@ pub async fn confirm_payment(
         "raw_webhook_data": serde_json::to_value(&payment).unwrap(),
     });
 
-    let payments_url = format!(
-        "{}/rest/v1/toss_payments?order_id=eq.{}",
-        supabase_url, order_id
-    );
-    http_client
-        .patch(&payments_url)
-        .header("apikey", &supabase_key)
-        .header("Authorization", format!("Bearer {}", supabase_key))
-        .header("Content-Type", "application/json")
-        .header("Prefer", "return=minimal")
-        .json(&update_data)
-        .send()
+    supabase_client
+        .table("toss_payments")
+        .eq("order_id", &order_id)
+        .eq("user_id", &user.id)
+        .auth(&user.access_token)
+        .update(&update_data)
         .await
-        .map_err(|e| format!("Failed to update payment: {}", e))?;
+        .map_err(|e| PaymentError::Supabase(format!("Failed to update payment: {}", e)))?;
 
     tracing::info!("Payment confirmed for user {}: {}", user.id, payment_key);
 
@@ -210,37 +176,22 @@ pub async fn confirm_payment(
 #[tauri::command]
 pub async fn get_subscription_status(
     state: State<'_, AppState>,
-) -> std::result::Result<SubscriptionStatus, String> {
+) -> Result<SubscriptionStatus, AppError> {
     // Require authentication
-    let user = require_auth(&state.auth).map_err(|e| e.to_string())?;
-
-    let supabase_url =
-        std::env::var("SUPABASE_URL").map_err(|_| "SUPABASE_URL not configured".to_string())?;
-    let supabase_key = std::env::var("SUPABASE_ANON_KEY")
-        .map_err(|_| "SUPABASE_ANON_KEY not configured".to_string())?;
+    let user = require_auth(&state.auth)?;
 
-    let http_client = Client::new();
+    let supabase_client = state.auth.get_supabase_client()?;
 
     // Get user's license
-    let license_url = format!(
-        "{}/rest/v1/licenses?user_id=eq.{}&select=tier,status,expires_at",
-        supabase_url, user.id
-    );
-
-    let license_response = http_client
-        .get(&license_url)
-        .header("apikey", &supabase_key)
-        .header("Authorization", format!("Bearer {}", supabase_key))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to get license: {}", e))?;
-
-    let licenses: Vec<serde_json::Value> = license_response
-        .json()
+    let license: serde_json::Value = supabase_client
+        .table("licenses")
+        .select("tier,status,expires_at")
+        .eq("user_id", &user.id)
+        .auth(&user.access_token)
+        .first()
         .await
-        .map_err(|e| format!("Failed to parse license response: {}", e))?;
-
-    let license = licenses.first().ok_or("License not found")?;
+        .map_err(|e| PaymentError::Supabase(format!("Failed to get license: {}", e)))?
+        .ok_or_else(|| PaymentError::NotFound("License".to_string()))?;
 
     let tier = license
         .get("tier")
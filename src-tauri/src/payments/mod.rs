@@ -19,6 +19,17 @@ pub enum PaymentError {
     WebhookVerificationFailed,
     #[error("Supabase error: {0}")]
     Supabase(String),
+    #[error("{0} not found")]
+    NotFound(String),
+    #[error("Invalid subscription period: {0}")]
+    InvalidPeriod(String),
+    #[error("{0} not configured")]
+    NotConfigured(&'static str),
 }
 
 pub type Result<T> = std::result::Result<T, PaymentError>;
+
+/// Toss Payments key `name` from the environment
+pub(crate) fn toss_key(name: &'static str) -> Result<String> {
+    std::env::var(name).map_err(|_| PaymentError::NotConfigured(name))
+}
@@ -1,4 +1,6 @@
 use crate::auth::middleware::require_auth;
+use crate::payments::{toss_key, PaymentError};
+use crate::utils::error::AppError;
 use crate::AppState;
 use serde::{Deserialize, Serialize};
 use tauri::State;
@@ -33,51 +35,36 @@ pub struct SubscriptionDetails {
 #[tauri::command]
 pub async fn get_subscription_details(
     state: State<'_, AppState>,
-) -> Result<SubscriptionDetails, String> {
+) -> Result<SubscriptionDetails, AppError> {
     // Require authentication
-    let user = require_auth(&state.auth).map_err(|e| e.to_string())?;
+    let user = require_auth(&state.auth)?;
 
     // Get Supabase client
-    let supabase_client = state
-        .auth
-        .get_supabase_client()
-        .map_err(|e| e.to_string())?;
+    let supabase_client = state.auth.get_supabase_client()?;
 
     // Query subscriptions table
-    let subscription_data = supabase_client
-        .query(
-            "subscriptions",
-            "id,user_id,billing_key,period,status,next_billing_date,created_at",
-            &[("user_id", &format!("eq.{}", user.id)), ("status", "eq.active")],
-            &user.access_token,
-        )
-        .await
-        .map_err(|e| format!("Failed to query subscription: {}", e))?;
-
-    // Parse subscription data
-    let subscriptions = subscription_data
-        .as_array()
-        .ok_or_else(|| "Invalid subscription data format".to_string())?;
-
-    if subscriptions.is_empty() {
-        return Err("No active subscription found".to_string());
-    }
-
-    let subscription = subscriptions
+    let subscription: serde_json::Value = supabase_client
+        .table("subscriptions")
+        .select("id,user_id,billing_key,period,status,next_billing_date,created_at")
+        .eq("user_id", &user.id)
+        .eq("status", "active")
+        .auth(&user.access_token)
         .first()
-        .ok_or_else(|| "No active subscription found".to_string())?;
+        .await
+        .map_err(|e| PaymentError::Supabase(format!("Failed to query subscription: {}", e)))?
+        .ok_or_else(|| PaymentError::NotFound("Active subscription".to_string()))?;
 
     // Extract fields
     let subscription_id = subscription
         .get("id")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| "Missing subscription ID".to_string())?
+        .ok_or_else(|| PaymentError::Supabase("Missing subscription ID".to_string()))?
         .to_string();
 
     let period_str = subscription
         .get("period")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| "Missing period".to_string())?;
+        .ok_or_else(|| PaymentError::Supabase("Missing period".to_string()))?;
 
     let period = match period_str {
         "MONTHLY" => SubscriptionPeriod::Monthly,
@@ -88,7 +75,7 @@ pub async fn get_subscription_details(
     let status_str = subscription
         .get("status")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| "Missing status".to_string())?;
+        .ok_or_else(|| PaymentError::Supabase("Missing status".to_string()))?;
 
     let status = match status_str {
         "active" => SubscriptionStatus::Active,
@@ -105,7 +92,7 @@ pub async fn get_subscription_details(
     let created_at = subscription
         .get("created_at")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| "Missing created_at".to_string())?
+        .ok_or_else(|| PaymentError::Supabase("Missing created_at".to_string()))?
         .to_string();
 
     // Determine amount based on period
@@ -115,19 +102,17 @@ pub async fn get_subscription_details(
     };
 
     // Get tier from user_licenses table
-    let license_data = supabase_client
-        .query(
-            "user_licenses",
-            "tier",
-            &[("user_id", &format!("eq.{}", user.id))],
-            &user.access_token,
-        )
+    let license_data: Option<serde_json::Value> = supabase_client
+        .table("user_licenses")
+        .select("tier")
+        .eq("user_id", &user.id)
+        .auth(&user.access_token)
+        .first()
         .await
-        .map_err(|e| format!("Failed to query license: {}", e))?;
+        .map_err(|e| PaymentError::Supabase(format!("Failed to query license: {}", e)))?;
 
     let tier = license_data
-        .as_array()
-        .and_then(|arr| arr.first())
+        .as_ref()
         .and_then(|obj| obj.get("tier"))
         .and_then(|v| v.as_str())
         .unwrap_or("PRO")
@@ -146,46 +131,32 @@ pub async fn get_subscription_details(
 
 /// Cancel subscription for the current user
 #[tauri::command]
-pub async fn cancel_subscription(state: State<'_, AppState>) -> Result<(), String> {
+pub async fn cancel_subscription(state: State<'_, AppState>) -> Result<(), AppError> {
     use crate::payments::toss::TossPaymentsClient;
 
     // Require authentication
-    let user = require_auth(&state.auth).map_err(|e| e.to_string())?;
+    let user = require_auth(&state.auth)?;
 
     // Get Supabase client
-    let supabase_client = state
-        .auth
-        .get_supabase_client()
-        .map_err(|e| e.to_string())?;
+    let supabase_client = state.auth.get_supabase_client()?;
 
     // Get user's active subscription from database
-    let subscription_data = supabase_client
-        .query(
-            "subscriptions",
-            "id,billing_key,next_billing_date",
-            &[("user_id", &format!("eq.{}", user.id)), ("status", "eq.active")],
-            &user.access_token,
-        )
-        .await
-        .map_err(|e| format!("Failed to query subscription: {}", e))?;
-
-    let subscriptions = subscription_data
-        .as_array()
-        .ok_or_else(|| "Invalid subscription data format".to_string())?;
-
-    if subscriptions.is_empty() {
-        return Err("No active subscription found".to_string());
-    }
-
-    let subscription = subscriptions
+    let subscription: serde_json::Value = supabase_client
+        .table("subscriptions")
+        .select("id,billing_key,next_billing_date")
+        .eq("user_id", &user.id)
+        .eq("status", "active")
+        .auth(&user.access_token)
         .first()
-        .ok_or_else(|| "No active subscription found".to_string())?;
+        .await
+        .map_err(|e| PaymentError::Supabase(format!("Failed to query subscription: {}", e)))?
+        .ok_or_else(|| PaymentError::NotFound("Active subscription".to_string()))?;
 
     // Get billing key and next billing date
     let billing_key = subscription
         .get("billing_key")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| "Missing billing key".to_string())?;
+        .ok_or_else(|| PaymentError::Supabase("Missing billing key".to_string()))?;
 
     let next_billing_date = subscription
         .get("next_billing_date")
@@ -194,8 +165,7 @@ pub async fn cancel_subscription(state: State<'_, AppState>) -> Result<(), Strin
         .to_string();
 
     // Get Toss Payments client
-    let secret_key = std::env::var("TOSS_SECRET_KEY")
-        .map_err(|_| "TOSS_SECRET_KEY not configured".to_string())?;
+    let secret_key = toss_key("TOSS_SECRET_KEY")?;
 
     let toss_client = TossPaymentsClient::new(secret_key);
 
@@ -205,8 +175,7 @@ pub async fn cancel_subscription(state: State<'_, AppState>) -> Result<(), Strin
 
     toss_client
         .delete_billing_key(billing_key, &customer_key)
-        .await
-        .map_err(|e| format!("Failed to delete billing key: {}", e))?;
+        .await?;
 
     // Note: The actual database updates will be handled by the webhook
     // when Toss Payments sends the BillingKeyDeleted event.
@@ -220,14 +189,13 @@ pub async fn cancel_subscription(state: State<'_, AppState>) -> Result<(), Strin
     });
 
     supabase_client
-        .update(
-            "subscriptions",
-            &subscription_update,
-            &[("user_id", &format!("eq.{}", user.id)), ("status", "eq.active")],
-            &user.access_token,
-        )
+        .table("subscriptions")
+        .eq("user_id", &user.id)
+        .eq("status", "active")
+        .auth(&user.access_token)
+        .update(&subscription_update)
         .await
-        .map_err(|e| format!("Failed to update subscription: {}", e))?;
+        .map_err(|e| PaymentError::Supabase(format!("Failed to update subscription: {}", e)))?;
 
     // Update user license to expire at end of current billing period
     let license_update = serde_json::json!({
@@ -238,14 +206,12 @@ pub async fn cancel_subscription(state: State<'_, AppState>) -> Result<(), Strin
     });
 
     supabase_client
-        .update(
-            "user_licenses",
-            &license_update,
-            &[("user_id", &format!("eq.{}", user.id))],
-            &user.access_token,
-        )
+        .table("user_licenses")
+        .eq("user_id", &user.id)
+        .auth(&user.access_token)
+        .update(&license_update)
         .await
-        .map_err(|e| format!("Failed to update license: {}", e))?;
+        .map_err(|e| PaymentError::Supabase(format!("Failed to update license: {}", e)))?;
 
     tracing::info!("Subscription cancelled successfully for user {}", user.id);
 
@@ -208,7 +208,10 @@ async fn process_successful_payment(
     });
 
     state.supabase
-        .update("payments", &payment_update, &[("order_id", &format!("eq.{}", payment.order_id))], &service_token)
+        .table("payments")
+        .eq("order_id", &payment.order_id)
+        .auth(&service_token)
+        .update(&payment_update)
         .await
         .map_err(|e| anyhow::anyhow!("Failed to update payment: {}", e))?;
 
@@ -222,7 +225,10 @@ async fn process_successful_payment(
     });
 
     state.supabase
-        .update("user_licenses", &license_update, &[("user_id", &format!("eq.{}", user_id))], &service_token)
+        .table("user_licenses")
+        .eq("user_id", &user_id)
+        .auth(&service_token)
+        .update(&license_update)
         .await
         .map_err(|e| anyhow::anyhow!("Failed to activate license: {}", e))?;
 
@@ -254,7 +260,10 @@ async fn process_failed_payment(
     });
 
     state.supabase
-        .update("payments", &payment_update, &[("order_id", &format!("eq.{}", payment.order_id))], &service_token)
+        .table("payments")
+        .eq("order_id", &payment.order_id)
+        .auth(&service_token)
+        .update(&payment_update)
         .await
         .map_err(|e| anyhow::anyhow!("Failed to update payment: {}", e))?;
 
@@ -330,7 +339,10 @@ async fn process_cancelled_payment(
     });
 
     state.supabase
-        .update("payments", &payment_update, &[("order_id", &format!("eq.{}", payment.order_id))], &service_token)
+        .table("payments")
+        .eq("order_id", &payment.order_id)
+        .auth(&service_token)
+        .update(&payment_update)
         .await
         .map_err(|e| anyhow::anyhow!("Failed to update payment: {}", e))?;
 
@@ -437,24 +449,29 @@ async fn process_billing_key_issued(
     });
 
     // Check if subscription exists
-    let existing = state.supabase
-        .query(
-            "subscriptions",
-            "id",
-            &[("user_id", &format!("eq.{}", user_id)), ("status", "eq.active")],
-            &service_token
-        )
+    let existing: Option<serde_json::Value> = state.supabase
+        .table("subscriptions")
+        .select("id")
+        .eq("user_id", &user_id)
+        .eq("status", "active")
+        .auth(&service_token)
+        .first()
         .await?;
 
-    if existing.as_array().map_or(0, |a| a.len()) > 0 {
+    if existing.is_some() {
         // Update existing subscription
         state.supabase
-            .update("subscriptions", &subscription_data, &[("user_id", &format!("eq.{}", user_id))], &service_token)
+            .table("subscriptions")
+            .eq("user_id", &user_id)
+            .auth(&service_token)
+            .update(&subscription_data)
             .await?;
     } else {
         // Create new subscription
         state.supabase
-            .insert("subscriptions", &subscription_data, &service_token)
+            .table("subscriptions")
+            .auth(&service_token)
+            .insert(&subscription_data)
             .await?;
     }
 
@@ -527,32 +544,29 @@ async fn process_billing_key_deleted(
     });
 
     state.supabase
-        .update("subscriptions", &subscription_update, &[("user_id", &format!("eq.{}", user_id)), ("status", "eq.active")], &service_token)
+        .table("subscriptions")
+        .eq("user_id", &user_id)
+        .eq("status", "active")
+        .auth(&service_token)
+        .update(&subscription_update)
         .await?;
 
     // Update user license to expire at end of current billing period
     // Query subscription to get next_billing_date
-    let sub_data = state.supabase
-        .query(
-            "subscriptions",
-            "next_billing_date",
-            &[("user_id", &format!("eq.{}", user_id))],
-            &service_token
-        )
+    let sub_data: Option<serde_json::Value> = state.supabase
+        .table("subscriptions")
+        .select("next_billing_date")
+        .eq("user_id", &user_id)
+        .auth(&service_token)
+        .first()
         .await?;
 
-    let expires_at = if let Some(sub_array) = sub_data.as_array() {
-        if let Some(sub) = sub_array.first() {
-            sub.get("next_billing_date")
-                .and_then(|v| v.as_str())
-                .unwrap_or(&chrono::Utc::now().format("%Y-%m-%d").to_string())
-                .to_string()
-        } else {
-            chrono::Utc::now().format("%Y-%m-%d").to_string()
-        }
-    } else {
-        chrono::Utc::now().format("%Y-%m-%d").to_string()
-    };
+    let expires_at = sub_data
+        .as_ref()
+        .and_then(|sub| sub.get("next_billing_date"))
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| chrono::Utc::now().format("%Y-%m-%d").to_string());
 
     let license_update = serde_json::json!({
         "status": "cancelled",
@@ -562,7 +576,10 @@ async fn process_billing_key_deleted(
     });
 
     state.supabase
-        .update("user_licenses", &license_update, &[("user_id", &format!("eq.{}", user_id))], &service_token)
+        .table("user_licenses")
+        .eq("user_id", &user_id)
+        .auth(&service_token)
+        .update(&license_update)
         .await?;
 
     info!("Subscription and license cancelled for user {}", user_id);
@@ -625,7 +642,10 @@ async fn process_refund(
     });
 
     state.supabase
-        .update("payments", &payment_update, &[("order_id", &format!("eq.{}", payment.order_id))], &service_token)
+        .table("payments")
+        .eq("order_id", &payment.order_id)
+        .auth(&service_token)
+        .update(&payment_update)
         .await?;
 
     // Deactivate PRO license (revert to FREE)
@@ -637,7 +657,10 @@ async fn process_refund(
     });
 
     state.supabase
-        .update("user_licenses", &license_update, &[("user_id", &format!("eq.{}", user_id))], &service_token)
+        .table("user_licenses")
+        .eq("user_id", &user_id)
+        .auth(&service_token)
+        .update(&license_update)
         .await?;
 
     info!("Refund processed and license downgraded for user {}", user_id);
@@ -0,0 +1,247 @@
+/// Custom handlers for game events
+///
+/// Every game event that passes the event filter is handed to the
+/// registered [`EventPlugin`]s, then to each enabled external hook in
+/// `RecordingSettings.plugins`. A hook is a program that gets the event as
+/// one line of JSON on stdin, so users can trigger things like Stream Deck
+/// actions without touching the app.
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::process::Stdio;
+use std::sync::{Arc, RwLock as StdRwLock};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::recording::GameEvent;
+use crate::settings::models::{EventHookSettings, RecordingSettings};
+
+/// Hooks still running after this are killed
+const HOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// What plugins and hooks receive for each game event
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginEvent {
+    pub game_id: Option<String>,
+    #[serde(flatten)]
+    pub event: GameEvent,
+}
+
+/// In-process event handler
+pub trait EventPlugin: Send + Sync {
+    /// Name used for logging and unregistering
+    fn name(&self) -> &str;
+
+    /// Called for each game event; runs on the event loop, so slow work
+    /// should be spawned
+    fn on_game_event(&self, event: &PluginEvent);
+}
+
+/// Registered plugins and the external hooks from the settings
+pub struct PluginRegistry {
+    plugins: StdRwLock<Vec<Arc<dyn EventPlugin>>>,
+    settings: Arc<RwLock<RecordingSettings>>,
+}
+
+impl PluginRegistry {
+    pub fn new(settings: Arc<RwLock<RecordingSettings>>) -> Self {
+        Self {
+            plugins: StdRwLock::new(Vec::new()),
+            settings,
+        }
+    }
+
+    /// Add a plugin, replacing any with the same name
+    pub fn register(&self, plugin: Arc<dyn EventPlugin>) {
+        let mut plugins = self.plugins.write().unwrap_or_else(|e| e.into_inner());
+        plugins.retain(|p| p.name() != plugin.name());
+        info!("Registered event plugin: {}", plugin.name());
+        plugins.push(plugin);
+    }
+
+    /// Remove a plugin, returning whether it was registered
+    pub fn unregister(&self, name: &str) -> bool {
+        let mut plugins = self.plugins.write().unwrap_or_else(|e| e.into_inner());
+        let before = plugins.len();
+        plugins.retain(|p| p.name() != name);
+        plugins.len() != before
+    }
+
+    pub fn plugin_names(&self) -> Vec<String> {
+        self.plugins
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|p| p.name().to_string())
+            .collect()
+    }
+
+    /// Hand an event to every plugin and start the enabled hooks
+    ///
+    /// Hooks run in the background; their failures are only logged.
+    pub async fn dispatch(&self, event: &PluginEvent) {
+        let plugins = self
+            .plugins
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        for plugin in plugins {
+            debug!(
+                "Event {} -> plugin {}",
+                event.event.event_name,
+                plugin.name()
+            );
+            plugin.on_game_event(event);
+        }
+
+        let hooks: Vec<EventHookSettings> = self
+            .settings
+            .read()
+            .await
+            .plugins
+            .event_hooks
+            .iter()
+            .filter(|hook| hook.enabled && !hook.command.is_empty())
+            .cloned()
+            .collect();
+        if hooks.is_empty() {
+            return;
+        }
+
+        let payload = match serde_json::to_string(event) {
+            Ok(json) => json,
+            Err(e) => {
+                warn!("Failed to serialize event for hooks: {}", e);
+                return;
+            }
+        };
+        for hook in hooks {
+            let payload = payload.clone();
+            tokio::spawn(async move {
+                if let Err(e) = run_hook(&hook, &payload).await {
+                    warn!("Event hook {} failed: {:#}", hook.name, e);
+                }
+            });
+        }
+    }
+}
+
+/// Run a hook with `payload` (plus a newline) on its stdin
+async fn run_hook(hook: &EventHookSettings, payload: &str) -> Result<()> {
+    let mut child = Command::new(&hook.command)
+        .args(&hook.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to start {}", hook.command))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(payload.as_bytes()).await?;
+        stdin.write_all(b"\n").await?;
+        // Dropping stdin closes it so the hook sees end of input
+    }
+
+    let status = tokio::time::timeout(HOOK_TIMEOUT, child.wait())
+        .await
+        .context("Timed out")??;
+    if !status.success() {
+        anyhow::bail!("Exited with {}", status);
+    }
+
+    debug!("Event hook {} finished", hook.name);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::Instant;
+
+    struct Recorder {
+        name: String,
+        events: Mutex<Vec<String>>,
+    }
+
+    impl EventPlugin for Recorder {
+        fn name(&self) -> &str {
+            &self.name
+        }
+
+        fn on_game_event(&self, event: &PluginEvent) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(event.event.event_name.clone());
+        }
+    }
+
+    fn event(event_name: &str) -> PluginEvent {
+        PluginEvent {
+            game_id: Some("game".to_string()),
+            event: GameEvent {
+                event_id: 1,
+                event_name: event_name.to_string(),
+                event_time: 300.0,
+                killer_name: Some("Me".to_string()),
+                victim_name: None,
+                assisters: vec![],
+                priority: 3,
+                timestamp: Instant::now(),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_to_plugins() {
+        let registry = PluginRegistry::new(Arc::new(RwLock::new(RecordingSettings::default())));
+        let plugin = Arc::new(Recorder {
+            name: "recorder".to_string(),
+            events: Mutex::new(Vec::new()),
+        });
+        registry.register(plugin.clone());
+        registry.register(plugin.clone());
+        assert_eq!(registry.plugin_names(), vec!["recorder"]);
+
+        registry.dispatch(&event("ChampionKill")).await;
+        assert_eq!(*plugin.events.lock().unwrap(), vec!["ChampionKill"]);
+
+        assert!(registry.unregister("recorder"));
+        registry.dispatch(&event("Ace")).await;
+        assert_eq!(plugin.events.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_payload_is_flat() {
+        let json = serde_json::to_value(event("Multikill")).unwrap();
+        assert_eq!(json["game_id"], "game");
+        assert_eq!(json["event_name"], "Multikill");
+        assert_eq!(json["priority"], 3);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_hook_receives_event_on_stdin() {
+        let out = std::env::temp_dir().join("lolshorts_test_event_hook.json");
+        let _ = std::fs::remove_file(&out);
+        let hook = EventHookSettings {
+            name: "capture".to_string(),
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), format!("cat > {}", out.display())],
+            enabled: true,
+        };
+
+        let payload = serde_json::to_string(&event("Ace")).unwrap();
+        run_hook(&hook, &payload).await.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&out).unwrap(),
+            format!("{}\n", payload)
+        );
+
+        let _ = std::fs::remove_file(&out);
+    }
+}
@@ -0,0 +1,16 @@
+use super::{MeteredFeature, QuotaStatus};
+use crate::AppState;
+use tauri::State;
+
+/// Get usage and remaining quota for a metered feature
+///
+/// `feature` is one of "auto_edit", "cloud_upload" or "youtube_upload".
+#[tauri::command]
+pub async fn get_quota(state: State<'_, AppState>, feature: String) -> Result<QuotaStatus, String> {
+    // FREE tier feature - no authentication required
+    let feature = feature
+        .parse::<MeteredFeature>()
+        .map_err(|e| e.to_string())?;
+
+    state.quota.status(feature).map_err(|e| e.to_string())
+}
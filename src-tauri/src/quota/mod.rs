@@ -0,0 +1,381 @@
+pub mod commands;
+pub mod store;
+
+/// Monthly quotas for metered features
+///
+/// Usage is counted per calendar month (UTC) and reset lazily on first
+/// access in a new month. Limits come from the remote feature matrix
+/// (`feature_limits` table) when present, otherwise from the built-in tier
+/// defaults in [`default_limit`].
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use thiserror::Error;
+
+use crate::auth::{AuthManager, SubscriptionTier};
+use crate::feature_gate::FeatureGate;
+
+pub use store::FileQuotaStore;
+
+#[derive(Debug, Error)]
+pub enum QuotaError {
+    #[error(
+        "Monthly {feature} quota exceeded ({used}/{limit}). Upgrade to PRO for unlimited usage."
+    )]
+    Exceeded {
+        feature: MeteredFeature,
+        used: u32,
+        limit: u32,
+    },
+    #[error("Unknown metered feature: {0}")]
+    UnknownFeature(String),
+    #[error("Authentication error: {0}")]
+    Auth(#[from] crate::auth::AuthError),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+pub type Result<T> = std::result::Result<T, QuotaError>;
+
+/// Features whose usage is counted against a monthly quota
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MeteredFeature {
+    AutoEdit,
+    CloudUpload,
+    YoutubeUpload,
+}
+
+impl MeteredFeature {
+    pub const ALL: [MeteredFeature; 3] = [
+        MeteredFeature::AutoEdit,
+        MeteredFeature::CloudUpload,
+        MeteredFeature::YoutubeUpload,
+    ];
+
+    /// Key used in the feature matrix and the usage file
+    pub fn key(&self) -> &'static str {
+        match self {
+            MeteredFeature::AutoEdit => "auto_edit",
+            MeteredFeature::CloudUpload => "cloud_upload",
+            MeteredFeature::YoutubeUpload => "youtube_upload",
+        }
+    }
+}
+
+impl fmt::Display for MeteredFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            MeteredFeature::AutoEdit => "auto-edit",
+            MeteredFeature::CloudUpload => "cloud upload",
+            MeteredFeature::YoutubeUpload => "YouTube upload",
+        };
+        f.write_str(label)
+    }
+}
+
+impl FromStr for MeteredFeature {
+    type Err = QuotaError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|feature| feature.key() == s)
+            .ok_or_else(|| QuotaError::UnknownFeature(s.to_string()))
+    }
+}
+
+/// Built-in monthly limits (`None` = unlimited)
+///
+/// FREE: 5 auto-edits, 20 cloud uploads, 10 YouTube uploads
+/// PRO: unlimited
+pub fn default_limit(feature: MeteredFeature, tier: &SubscriptionTier) -> Option<u32> {
+    match tier {
+        SubscriptionTier::Pro => None,
+        SubscriptionTier::Free => Some(match feature {
+            MeteredFeature::AutoEdit => 5,
+            MeteredFeature::CloudUpload => 20,
+            MeteredFeature::YoutubeUpload => 10,
+        }),
+    }
+}
+
+/// Usage of one metered feature in one period
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaUsage {
+    pub feature: MeteredFeature,
+    /// Period identifier (YYYY-MM)
+    pub period: String,
+    pub used: u32,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl QuotaUsage {
+    pub fn new(feature: MeteredFeature) -> Self {
+        Self {
+            feature,
+            period: current_period(),
+            used: 0,
+            updated_at: Utc::now(),
+        }
+    }
+
+    pub fn is_current(&self) -> bool {
+        self.period == current_period()
+    }
+}
+
+/// Current period identifier (YYYY-MM, UTC)
+pub fn current_period() -> String {
+    Utc::now().format("%Y-%m").to_string()
+}
+
+/// Quota state for frontend display
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaStatus {
+    pub feature: MeteredFeature,
+    /// User's subscription tier (FREE or PRO)
+    pub tier: String,
+    /// Uses this period
+    pub used: u32,
+    /// Monthly limit (`None` = unlimited)
+    pub limit: Option<u32>,
+    /// Remaining uses this period (`None` = unlimited)
+    pub remaining: Option<u32>,
+    /// Current period (YYYY-MM)
+    pub period: String,
+}
+
+/// Persistence backend for quota usage
+pub trait QuotaStore: Send + Sync {
+    /// Stored usage for a feature (may belong to a past period)
+    fn load(&self, feature: MeteredFeature) -> Result<Option<QuotaUsage>>;
+
+    fn save(&self, usage: &QuotaUsage) -> Result<()>;
+}
+
+/// Checks and records usage of metered features
+pub struct QuotaManager {
+    store: Box<dyn QuotaStore>,
+    auth: Arc<AuthManager>,
+    feature_gate: Arc<FeatureGate>,
+    /// Serializes read-modify-write cycles on the store
+    lock: Mutex<()>,
+}
+
+impl QuotaManager {
+    pub fn new(
+        store: Box<dyn QuotaStore>,
+        auth: Arc<AuthManager>,
+        feature_gate: Arc<FeatureGate>,
+    ) -> Self {
+        Self {
+            store,
+            auth,
+            feature_gate,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Monthly limit for the current user's tier (`None` = unlimited)
+    pub fn limit(&self, feature: MeteredFeature) -> Result<Option<u32>> {
+        let tier = self.auth.get_tier()?;
+        Ok(self
+            .feature_gate
+            .limit(feature.key(), default_limit(feature, &tier)))
+    }
+
+    /// Current usage and limit for a feature
+    pub fn status(&self, feature: MeteredFeature) -> Result<QuotaStatus> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let usage = self.current_usage(feature)?;
+        self.build_status(usage)
+    }
+
+    /// Fail with [`QuotaError::Exceeded`] if the feature's quota is used up
+    pub fn check(&self, feature: MeteredFeature) -> Result<QuotaStatus> {
+        let status = self.status(feature)?;
+
+        if let Some(limit) = status.limit {
+            if status.used >= limit {
+                return Err(QuotaError::Exceeded {
+                    feature,
+                    used: status.used,
+                    limit,
+                });
+            }
+        }
+
+        Ok(status)
+    }
+
+    /// Count one use of a feature
+    pub fn record(&self, feature: MeteredFeature) -> Result<QuotaStatus> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut usage = self.current_usage(feature)?;
+        usage.used += 1;
+        usage.updated_at = Utc::now();
+        self.store.save(&usage)?;
+
+        tracing::info!(
+            "Quota usage recorded: {} {} (period: {})",
+            feature.key(),
+            usage.used,
+            usage.period
+        );
+
+        self.build_status(usage)
+    }
+
+    /// Stored usage, reset if it belongs to a past period
+    fn current_usage(&self, feature: MeteredFeature) -> Result<QuotaUsage> {
+        match self.store.load(feature)? {
+            Some(usage) if usage.is_current() => Ok(usage),
+            Some(usage) => {
+                tracing::info!(
+                    "Resetting {} usage for new period: {} -> {}",
+                    feature.key(),
+                    usage.period,
+                    current_period()
+                );
+                let usage = QuotaUsage::new(feature);
+                self.store.save(&usage)?;
+                Ok(usage)
+            }
+            None => Ok(QuotaUsage::new(feature)),
+        }
+    }
+
+    fn build_status(&self, usage: QuotaUsage) -> Result<QuotaStatus> {
+        let tier = self.auth.get_tier()?;
+        let limit = self.limit(usage.feature)?;
+
+        Ok(QuotaStatus {
+            feature: usage.feature,
+            tier: format!("{:?}", tier),
+            used: usage.used,
+            limit,
+            remaining: limit.map(|limit| limit.saturating_sub(usage.used)),
+            period: usage.period,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::User;
+    use crate::feature_gate::remote::FeatureMatrix;
+    use tempfile::TempDir;
+
+    fn manager(temp_dir: &TempDir, tier: Option<SubscriptionTier>) -> QuotaManager {
+        let auth = Arc::new(AuthManager::new());
+        if let Some(tier) = tier {
+            auth.login(User {
+                id: "test".to_string(),
+                email: "test@example.com".to_string(),
+                tier,
+                access_token: "access_token".to_string(),
+                refresh_token: "refresh_token".to_string(),
+                expires_at: 9999999999,
+            })
+            .unwrap();
+        }
+
+        let feature_gate = Arc::new(FeatureGate::new(auth.clone()));
+        let store = FileQuotaStore::new(temp_dir.path()).unwrap();
+        QuotaManager::new(Box::new(store), auth, feature_gate)
+    }
+
+    #[test]
+    fn test_metered_feature_keys() {
+        for feature in MeteredFeature::ALL {
+            assert_eq!(feature.key().parse::<MeteredFeature>().unwrap(), feature);
+        }
+        assert!("unknown".parse::<MeteredFeature>().is_err());
+    }
+
+    #[test]
+    fn test_free_tier_quota_enforced() {
+        let temp_dir = TempDir::new().unwrap();
+        let quota = manager(&temp_dir, Some(SubscriptionTier::Free));
+
+        for _ in 0..5 {
+            quota.check(MeteredFeature::AutoEdit).unwrap();
+            quota.record(MeteredFeature::AutoEdit).unwrap();
+        }
+
+        let result = quota.check(MeteredFeature::AutoEdit);
+        assert!(matches!(
+            result,
+            Err(QuotaError::Exceeded {
+                used: 5,
+                limit: 5,
+                ..
+            })
+        ));
+
+        // Other features are counted separately
+        let status = quota.check(MeteredFeature::YoutubeUpload).unwrap();
+        assert_eq!(status.used, 0);
+        assert_eq!(status.remaining, Some(10));
+    }
+
+    #[test]
+    fn test_pro_tier_unlimited() {
+        let temp_dir = TempDir::new().unwrap();
+        let quota = manager(&temp_dir, Some(SubscriptionTier::Pro));
+
+        for _ in 0..10 {
+            quota.record(MeteredFeature::AutoEdit).unwrap();
+        }
+
+        let status = quota.check(MeteredFeature::AutoEdit).unwrap();
+        assert_eq!(status.used, 10);
+        assert_eq!(status.limit, None);
+        assert_eq!(status.remaining, None);
+    }
+
+    #[test]
+    fn test_remote_limit_takes_precedence() {
+        let temp_dir = TempDir::new().unwrap();
+        let quota = manager(&temp_dir, Some(SubscriptionTier::Free));
+
+        let mut matrix = FeatureMatrix::default();
+        matrix
+            .limits
+            .entry("FREE".to_string())
+            .or_default()
+            .insert("auto_edit".to_string(), Some(2));
+        matrix.override_user_id = Some("test".to_string());
+        quota.feature_gate.set_matrix(Some(matrix));
+
+        assert_eq!(quota.limit(MeteredFeature::AutoEdit).unwrap(), Some(2));
+        assert_eq!(quota.limit(MeteredFeature::CloudUpload).unwrap(), Some(20));
+    }
+
+    #[test]
+    fn test_usage_resets_for_new_period() {
+        let temp_dir = TempDir::new().unwrap();
+        let quota = manager(&temp_dir, None);
+
+        quota
+            .store
+            .save(&QuotaUsage {
+                feature: MeteredFeature::CloudUpload,
+                period: "2000-01".to_string(),
+                used: 20,
+                updated_at: Utc::now(),
+            })
+            .unwrap();
+
+        let status = quota.check(MeteredFeature::CloudUpload).unwrap();
+        assert_eq!(status.used, 0);
+        assert_eq!(status.period, current_period());
+    }
+}
@@ -0,0 +1,169 @@
+use super::{MeteredFeature, QuotaStore, QuotaUsage, Result};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+/// Usage file, keyed by metered feature key
+const USAGE_FILE: &str = "quota_usage.json";
+
+/// Auto-edit usage file written before the quota subsystem existed
+const LEGACY_AUTO_EDIT_FILE: &str = "auto_edit_usage.json";
+
+#[derive(Debug, Deserialize)]
+struct LegacyAutoEditUsage {
+    month: String,
+    usage_count: u32,
+    last_updated: DateTime<Utc>,
+}
+
+/// JSON file store for quota usage (`<base_path>/quota_usage.json`)
+pub struct FileQuotaStore {
+    path: PathBuf,
+    usage: RwLock<HashMap<String, QuotaUsage>>,
+}
+
+impl FileQuotaStore {
+    /// Open the store, importing legacy auto-edit usage on first run
+    pub fn new(base_path: &Path) -> Result<Self> {
+        fs::create_dir_all(base_path)?;
+        let path = base_path.join(USAGE_FILE);
+
+        let usage = if path.exists() {
+            Self::read_usage(&path)
+        } else {
+            let mut usage = HashMap::new();
+            if let Some(legacy) = Self::load_legacy(base_path) {
+                usage.insert(legacy.feature.key().to_string(), legacy);
+            }
+            usage
+        };
+
+        let store = Self {
+            path,
+            usage: RwLock::new(usage),
+        };
+        store.persist()?;
+        Ok(store)
+    }
+
+    /// Usage stored at `path`; a corrupt file is moved aside and counting
+    /// starts over
+    fn read_usage(path: &Path) -> HashMap<String, QuotaUsage> {
+        let parsed = fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()));
+
+        match parsed {
+            Ok(usage) => usage,
+            Err(e) => {
+                let quarantined = path.with_extension("json.corrupt");
+                tracing::warn!(
+                    "Discarding corrupted quota usage ({}), moved to {:?}",
+                    e,
+                    quarantined
+                );
+                if let Err(e) = fs::rename(path, &quarantined) {
+                    tracing::warn!("Failed to move corrupted quota usage aside: {}", e);
+                }
+                HashMap::new()
+            }
+        }
+    }
+
+    fn load_legacy(base_path: &Path) -> Option<QuotaUsage> {
+        let json = fs::read_to_string(base_path.join(LEGACY_AUTO_EDIT_FILE)).ok()?;
+        let legacy: LegacyAutoEditUsage = serde_json::from_str(&json).ok()?;
+
+        tracing::info!(
+            "Migrating legacy auto-edit usage: month={}, count={}",
+            legacy.month,
+            legacy.usage_count
+        );
+
+        Some(QuotaUsage {
+            feature: MeteredFeature::AutoEdit,
+            period: legacy.month,
+            used: legacy.usage_count,
+            updated_at: legacy.last_updated,
+        })
+    }
+
+    fn persist(&self) -> Result<()> {
+        let usage = self.usage.read().unwrap_or_else(|e| e.into_inner());
+        let json = serde_json::to_string_pretty(&*usage)?;
+        fs::write(&self.path, json)?;
+        Ok(())
+    }
+}
+
+impl QuotaStore for FileQuotaStore {
+    fn load(&self, feature: MeteredFeature) -> Result<Option<QuotaUsage>> {
+        let usage = self.usage.read().unwrap_or_else(|e| e.into_inner());
+        Ok(usage.get(feature.key()).cloned())
+    }
+
+    fn save(&self, usage: &QuotaUsage) -> Result<()> {
+        self.usage
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(usage.feature.key().to_string(), usage.clone());
+        self.persist()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_usage_persists() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let store = FileQuotaStore::new(temp_dir.path()).unwrap();
+        let mut usage = QuotaUsage::new(MeteredFeature::YoutubeUpload);
+        usage.used = 3;
+        store.save(&usage).unwrap();
+
+        let reopened = FileQuotaStore::new(temp_dir.path()).unwrap();
+        let loaded = reopened
+            .load(MeteredFeature::YoutubeUpload)
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.used, 3);
+        assert!(reopened.load(MeteredFeature::AutoEdit).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_legacy_auto_edit_usage_migrated() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join(LEGACY_AUTO_EDIT_FILE),
+            r#"{
+                "month": "2025-01",
+                "usage_count": 4,
+                "last_updated": "2025-01-15T10:00:00Z",
+                "period_start": "2025-01-01T00:00:00Z"
+            }"#,
+        )
+        .unwrap();
+
+        let store = FileQuotaStore::new(temp_dir.path()).unwrap();
+        let usage = store.load(MeteredFeature::AutoEdit).unwrap().unwrap();
+        assert_eq!(usage.period, "2025-01");
+        assert_eq!(usage.used, 4);
+    }
+
+    #[test]
+    fn test_corrupt_usage_is_quarantined() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join(USAGE_FILE), "{ not json").unwrap();
+
+        let store = FileQuotaStore::new(temp_dir.path()).unwrap();
+        assert!(store.load(MeteredFeature::AutoEdit).unwrap().is_none());
+        assert!(temp_dir.path().join("quota_usage.json.corrupt").exists());
+    }
+}
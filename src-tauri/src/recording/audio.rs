@@ -9,6 +9,9 @@ use anyhow::{Context as AnyhowContext, Result};
 use serde::{Deserialize, Serialize};
 use std::process::Command;
 
+/// Title of the microphone's own audio track in recordings
+pub const MIC_TRACK_TITLE: &str = "Microphone";
+
 /// Audio device information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioDevice {
@@ -69,7 +72,9 @@ impl AudioConfig {
     /// Build FFmpeg audio input arguments
     ///
     /// Returns (input_args, filter_args, map_args, codec_args)
-    /// where each component is a Vec of FFmpeg argument strings
+    /// where each component is a Vec of FFmpeg argument strings.
+    /// When both sources are recorded, the microphone is also kept on a
+    /// second track titled `MIC_TRACK_TITLE` next to the mix.
     pub fn build_ffmpeg_args(&self) -> (Vec<String>, Vec<String>, Vec<String>, Vec<String>) {
         if !self.is_enabled() {
             return (vec![], vec![], vec![], vec![]);
@@ -106,9 +111,16 @@ impl AudioConfig {
                 });
             input_args.push(mic_device);
 
-            // Apply volume to microphone
+            // Apply volume to microphone, splitting off its own track when mixed
             let volume = self.microphone_volume as f32 / 100.0;
-            filter_parts.push(format!("[{}:a]volume={}[mic]", audio_input_idx, volume));
+            if self.record_system_audio {
+                filter_parts.push(format!(
+                    "[{}:a]volume={},asplit=2[mic][mic_track]",
+                    audio_input_idx, volume
+                ));
+            } else {
+                filter_parts.push(format!("[{}:a]volume={}[mic]", audio_input_idx, volume));
+            }
             mix_inputs.push("[mic]".to_string());
             audio_input_idx += 1;
         }
@@ -166,10 +178,63 @@ impl AudioConfig {
             map_args.push("0:v".to_string());
             map_args.push("-map".to_string());
             map_args.push("[aout]".to_string());
+            map_args.push("-map".to_string());
+            map_args.push("[mic_track]".to_string());
+            map_args.push("-metadata:s:a:1".to_string());
+            map_args.push(format!("title={}", MIC_TRACK_TITLE));
+        } else if self.record_microphone {
+            map_args.push("-metadata:s:a:0".to_string());
+            map_args.push(format!("title={}", MIC_TRACK_TITLE));
         }
 
         (input_args, filter_args, map_args, codec_args)
     }
+
+    /// Build FFmpeg audio arguments without any filter graph (low-spec mode)
+    ///
+    /// Captures a single source (system audio preferred) and maps it directly,
+    /// skipping volume/amix filters to save CPU.
+    /// Returns (input_args, map_args, codec_args)
+    pub fn build_unfiltered_ffmpeg_args(&self) -> (Vec<String>, Vec<String>, Vec<String>) {
+        let device = if self.record_system_audio {
+            self.system_audio_device
+                .as_ref()
+                .map(|d| format!("audio={}", d))
+                .unwrap_or_else(|| "audio=Stereo Mix".to_string())
+        } else if self.record_microphone {
+            self.microphone_device
+                .as_ref()
+                .map(|d| format!("audio={}", d))
+                .unwrap_or_else(|| {
+                    "audio=@device_cm_{33D9A762-90C8-11D0-BD43-00A0C911CE86}\\wave_in".to_string()
+                })
+        } else {
+            return (vec![], vec![], vec![]);
+        };
+
+        let input_args = vec![
+            "-f".to_string(),
+            "dshow".to_string(),
+            "-i".to_string(),
+            device,
+        ];
+        let map_args = vec![
+            "-map".to_string(),
+            "0:v".to_string(),
+            "-map".to_string(),
+            "1:a".to_string(),
+        ];
+        let codec_args = vec![
+            "-c:a".to_string(),
+            "aac".to_string(),
+            "-b:a".to_string(),
+            format!("{}k", self.bitrate.min(128)),
+            "-ar".to_string(),
+            self.sample_rate.to_string(),
+        ];
+
+        (input_args, map_args, codec_args)
+    }
 }
 
 /// List available audio devices on Windows
@@ -226,6 +291,53 @@ pub fn list_audio_devices() -> Result<Vec<AudioDevice>> {
     Ok(devices)
 }
 
+/// List available video capture devices (webcams) on Windows
+pub fn list_video_devices() -> Result<Vec<String>> {
+    tracing::debug!("Listing DirectShow video devices...");
+
+    let output = Command::new("ffmpeg")
+        .args(["-list_devices", "true", "-f", "dshow", "-i", "dummy"])
+        .output()
+        .context("Failed to execute ffmpeg for device listing")?;
+
+    let devices = parse_video_devices(&String::from_utf8_lossy(&output.stderr));
+    tracing::info!("Found {} video devices", devices.len());
+    Ok(devices)
+}
+
+/// Extract device names from FFmpeg's DirectShow device list
+///
+/// Older FFmpeg builds list devices under section headers, newer ones tag
+/// each line with "(video)" or "(audio)".
+fn parse_video_devices(stderr: &str) -> Vec<String> {
+    let mut devices = Vec::new();
+    let mut in_video_section = false;
+
+    for line in stderr.lines() {
+        if line.contains("DirectShow video devices") {
+            in_video_section = true;
+            continue;
+        }
+        if line.contains("DirectShow audio devices") {
+            in_video_section = false;
+            continue;
+        }
+        if line.contains("Alternative name") || line.contains("(audio)") {
+            continue;
+        }
+
+        if in_video_section || line.contains("(video)") {
+            if let Some(start) = line.find('"') {
+                if let Some(end) = line[start + 1..].find('"') {
+                    devices.push(line[start + 1..start + 1 + end].to_string());
+                }
+            }
+        }
+    }
+
+    devices
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -296,5 +408,36 @@ mod tests {
         let filter_str = filter_args.join(" ");
         assert!(filter_str.contains("amix"));
         assert!(filter_str.contains("[aout]"));
+
+        // The microphone is kept on its own track as well
+        assert!(filter_str.contains("asplit=2[mic][mic_track]"));
+        let map_str = map_args.join(" ");
+        assert!(map_str.contains("-map [aout] -map [mic_track]"));
+        assert!(map_str.contains("-metadata:s:a:1 title=Microphone"));
+    }
+
+    #[test]
+    fn test_audio_config_unfiltered_prefers_system_audio() {
+        let config = AudioConfig::default();
+
+        let (input_args, map_args, codec_args) = config.build_unfiltered_ffmpeg_args();
+        assert_eq!(input_args.last().unwrap(), "audio=Stereo Mix");
+        assert_eq!(map_args.join(" "), "-map 0:v -map 1:a");
+        assert!(codec_args.contains(&"128k".to_string()));
+    }
+
+    #[test]
+    fn test_parse_video_devices() {
+        let old_format = r#"[dshow @ 0000] DirectShow video devices
+[dshow @ 0000]  "HD Webcam"
+[dshow @ 0000]     Alternative name "@device_pnp_usb#vid_046d"
+[dshow @ 0000] DirectShow audio devices
+[dshow @ 0000]  "Microphone (Realtek Audio)""#;
+        assert_eq!(parse_video_devices(old_format), vec!["HD Webcam"]);
+
+        let new_format = r#"[dshow @ 0000] "HD Webcam" (video)
+[dshow @ 0000]   Alternative name "@device_pnp_usb#vid_046d"
+[dshow @ 0000] "Microphone (Realtek Audio)" (audio)"#;
+        assert_eq!(parse_video_devices(new_format), vec!["HD Webcam"]);
     }
 }
@@ -1,27 +1,46 @@
 #![allow(clippy::unnecessary_cast)]
 use anyhow::{Context as AnyhowContext, Result};
 use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::{Mutex as TokioMutex, RwLock as TokioRwLock};
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
-use super::live_client::{EventTrigger, LiveClientMonitor};
-use super::windows_backend::WindowsRecorder;
+use super::backend::Recorder;
+use super::live_client::{
+    ClipFactors, EventTrigger, LiveClientHealth, LiveClientMonitor, LiveClientStatus,
+    PlayerInvolvement, PollIntervals,
+};
+use super::scoring;
 use super::GameEvent; // Use the recording module's GameEvent
-use crate::settings::models::RecordingSettings;
+use crate::events::{AppEvent, AutoClipEventDetected, EventBus};
+use crate::jobs::{JobKind, JobPriority, JobScheduler};
+use crate::lcu::patch as league_patch;
+use crate::notifications::toast::ToastNotifier;
+use crate::plugins::{PluginEvent, PluginRegistry};
+use crate::settings::models::{DuplicateClipHandling, EventFilterSettings, RecordingSettings};
 use crate::storage::{
     models::{ClipMetadata, EventData, EventType},
-    Storage,
+    models_v2::{EventInfo, EventWindow as ClipEventWindow, MergeStrategy},
+    ClipMetadataV2, Storage,
 };
 
+/// Clips overlapping an earlier clip by this much (fraction of the shorter
+/// one) are treated as duplicates
+const DUPLICATE_OVERLAP: f64 = 0.8;
+
+/// Wait before retrying a failed automatic save
+const SAVE_RETRY_DELAY: Duration = Duration::from_secs(2);
+
 /// Queued event with timestamp for merging logic
 #[derive(Debug, Clone)]
 struct QueuedEvent {
     trigger: EventTrigger,
     event: GameEvent,
+    factors: ClipFactors,
     received_at: Instant,
 }
 
@@ -29,16 +48,18 @@ struct QueuedEvent {
 #[derive(Debug, Clone)]
 struct EventWindow {
     primary_trigger: EventTrigger,
-    events: Vec<GameEvent>,
-    start_time: f32, // Game time in seconds
-    end_time: f32,   // Game time in seconds
-    priority: u8,    // Highest priority in window
+    primary_event: GameEvent,
+    events: Vec<QueuedEvent>, // Sorted by event time
+    start_time: f32,          // Game time in seconds
+    end_time: f32,            // Game time in seconds
+    priority: u8,             // Highest priority in window
+    factors: ClipFactors,
 }
 
 /// Auto Clip Manager - Bridges event detection with automatic clip saving
 ///
 /// Architecture:
-/// LiveClientMonitor → AutoClipManager → WindowsRecorder + Storage
+/// LiveClientMonitor → AutoClipManager → Recorder + Storage
 ///                           ↓
 ///                      Settings (filter)
 ///
@@ -47,11 +68,11 @@ struct EventWindow {
 /// 2. Event Filtering: Apply settings filters (event types, priority, game modes)
 /// 3. Event Merging: Combine consecutive events within threshold
 /// 4. Clip Window Calculation: Calculate pre/post durations from settings or defaults
-/// 5. Automatic Saving: Trigger WindowsRecorder.save_clip() for filtered events
+/// 5. Automatic Saving: Trigger Recorder.save_clip() for filtered events
 /// 6. Metadata Generation: Create rich metadata for each saved clip
 pub struct AutoClipManager {
     /// Recording backend reference
-    recorder: Arc<TokioRwLock<WindowsRecorder>>,
+    recorder: Arc<dyn Recorder>,
 
     /// Storage reference
     storage: Arc<Storage>,
@@ -65,7 +86,8 @@ pub struct AutoClipManager {
     /// Current game ID for clip organization
     current_game_id: Arc<TokioRwLock<Option<String>>>,
 
-    /// Processing lock to prevent concurrent clip saves
+    /// Clip save queue: saves run one at a time, in the order they were
+    /// requested (tokio's mutex is fair)
     processing_lock: Arc<TokioMutex<()>>,
 
     /// Event monitoring task handle
@@ -73,12 +95,27 @@ pub struct AutoClipManager {
 
     /// Cancellation token for stopping the monitoring task
     cancel_token: CancellationToken,
+
+    /// Background jobs (thumbnail generation for saved clips)
+    jobs: Option<Arc<JobScheduler>>,
+
+    /// Frontend notifications for detected events
+    events: Arc<EventBus>,
+
+    /// Custom handlers for detected events
+    plugins: Option<Arc<PluginRegistry>>,
+
+    /// Live Client API connection health
+    live_client: Arc<LiveClientHealth>,
+
+    /// Desktop notifications for clips saved in game
+    toasts: Option<Arc<ToastNotifier>>,
 }
 
 impl AutoClipManager {
     /// Create a new Auto Clip Manager
     pub fn new(
-        recorder: Arc<TokioRwLock<WindowsRecorder>>,
+        recorder: Arc<dyn Recorder>,
         storage: Arc<Storage>,
         settings: Arc<TokioRwLock<RecordingSettings>>,
     ) -> Self {
@@ -91,11 +128,47 @@ impl AutoClipManager {
             processing_lock: Arc::new(TokioMutex::new(())),
             monitor_task: Arc::new(TokioMutex::new(None)),
             cancel_token: CancellationToken::new(),
+            jobs: None,
+            events: Arc::new(EventBus::new()),
+            plugins: None,
+            live_client: Arc::new(LiveClientHealth::new()),
+            toasts: None,
         }
     }
 
+    /// Publish detected game events on `events`
+    pub fn with_events(mut self, events: Arc<EventBus>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Hand detected game events to `plugins`
+    pub fn with_plugins(mut self, plugins: Arc<PluginRegistry>) -> Self {
+        self.plugins = Some(plugins);
+        self
+    }
+
+    /// Queue thumbnail generation for saved clips on `jobs`
+    pub fn with_jobs(mut self, jobs: Arc<JobScheduler>) -> Self {
+        self.jobs = Some(jobs);
+        self
+    }
+
+    /// Announce clips saved in game with desktop notifications
+    pub fn with_toasts(mut self, toasts: Arc<ToastNotifier>) -> Self {
+        self.toasts = Some(toasts);
+        self
+    }
+
     /// Set the current game ID for clip organization
+    ///
+    /// A saved game without a patch gets the current one, so its clips can
+    /// be tagged without looking it up for each of them.
     pub async fn set_current_game(&self, game_id: Option<String>) {
+        if let Some(ref id) = game_id {
+            self.record_patch(id).await;
+        }
+
         let mut current = self.current_game_id.write().await;
         *current = game_id.clone();
 
@@ -109,6 +182,95 @@ impl AutoClipManager {
         }
     }
 
+    /// Record the current patch in the metadata of `game_id` if it has none
+    async fn record_patch(&self, game_id: &str) {
+        let Ok(mut game) = self.storage.load_game_metadata(game_id) else {
+            return;
+        };
+        if game.patch.is_some() {
+            return;
+        }
+
+        game.patch = league_patch::current_patch().await;
+        if game.patch.is_some() {
+            if let Err(e) = self.storage.save_game_metadata(game_id, &game) {
+                warn!("Failed to record patch of game {}: {}", game_id, e);
+            }
+        }
+    }
+
+    /// Save the last `duration` seconds on request (hotkey or UI)
+    ///
+    /// During a game the clip gets metadata with its game-time window, so it
+    /// is checked against auto-captured clips of the same play.
+    pub async fn save_manual_clip(
+        &self,
+        event_name: &str,
+        duration: f64,
+        priority: u8,
+    ) -> Result<PathBuf> {
+        let game_time = match LiveClientMonitor::new() {
+            Ok(monitor) => monitor.game_time().await.ok(),
+            Err(_) => None,
+        };
+
+        let event = GameEvent {
+            event_id: 0,
+            event_name: event_name.to_string(),
+            event_time: game_time.unwrap_or_default(),
+            killer_name: None,
+            victim_name: None,
+            assisters: vec![],
+            priority,
+            timestamp: Instant::now(),
+        };
+        let clip_id = format!("{}_{}", event_name, chrono::Utc::now().timestamp());
+
+        // Prevent concurrent saves
+        let _lock = self.processing_lock.lock().await;
+
+        let clip_path = self
+            .recorder
+            .save_clip(&event, clip_id.clone(), priority, duration)
+            .await
+            .context("Failed to save manual clip")?;
+
+        info!("Manual clip saved: {:?}", clip_path);
+
+        if game_time.is_some() {
+            let mut clip = ClipMetadataV2::from(ClipMetadata {
+                file_path: clip_path.to_string_lossy().to_string(),
+                thumbnail_path: None,
+                event_type: EventType::Custom(event_name.to_string()),
+                event_time: event.event_time - duration,
+                priority,
+                duration,
+                created_at: chrono::Utc::now(),
+            });
+            clip.primary_event.timestamp = event.event_time;
+            clip.primary_event.clip_timestamp = duration;
+            clip.event_window.merge_strategy = MergeStrategy::ManualSave;
+            clip.event_window.pre_duration = duration;
+            clip.event_window.post_duration = 0.0;
+            clip.add_tag("manual".to_string());
+            self.save_clip_metadata(&clip_id, clip).await?;
+        }
+
+        Ok(clip_path)
+    }
+
+    /// Live Client API health, shared with the background probe
+    pub fn live_client_health(&self) -> Arc<LiveClientHealth> {
+        Arc::clone(&self.live_client)
+    }
+
+    /// Why auto-capture may not be seeing events
+    pub async fn live_client_status(&self) -> LiveClientStatus {
+        let mut status = self.live_client.status();
+        status.monitoring = self.is_monitoring().await;
+        status
+    }
+
     /// Check if event monitoring is active
     pub async fn is_monitoring(&self) -> bool {
         let task_guard = self.monitor_task.lock().await;
@@ -130,7 +292,25 @@ impl AutoClipManager {
         info!("Starting event monitoring...");
 
         // Create a new LiveClientMonitor
-        let mut monitor = LiveClientMonitor::new().context("Failed to create LiveClientMonitor")?;
+        let (involvement, record_spectator_games, poll) = {
+            let settings = self.settings.read().await;
+            (
+                PlayerInvolvement {
+                    assists: settings.event_filter.record_assists,
+                    deaths: settings.event_filter.record_deaths,
+                    death_priority: settings.event_filter.death_priority,
+                },
+                settings.event_filter.record_spectator_games,
+                PollIntervals::from_settings(&settings.performance),
+            )
+        };
+        let mut monitor = LiveClientMonitor::new()
+            .context("Failed to create LiveClientMonitor")?
+            .with_involvement(involvement)
+            .with_spectator_games(record_spectator_games)
+            .with_poll_intervals(poll)
+            .with_health(Arc::clone(&self.live_client));
+        self.live_client.set_monitoring(true);
 
         // Clone Arc references for the monitoring task
         let event_queue = Arc::clone(&self.event_queue);
@@ -140,47 +320,64 @@ impl AutoClipManager {
         let current_game_id = Arc::clone(&self.current_game_id);
         let processing_lock = Arc::clone(&self.processing_lock);
         let cancel_token = self.cancel_token.clone();
+        let jobs = self.jobs.clone();
+        let events = Arc::clone(&self.events);
+        let plugins = self.plugins.clone();
+        let live_client = Arc::clone(&self.live_client);
+        let toasts = self.toasts.clone();
 
         // Spawn monitoring task
         let handle = tokio::spawn(async move {
             info!("Event monitoring task started");
+            let health = Arc::clone(&live_client);
 
             // Create callback closure that processes events
-            let callback =
-                move |trigger: EventTrigger, live_event: super::live_client::GameEvent| {
-                    // Convert live_client::GameEvent to recording::GameEvent
-                    let event = convert_live_event(live_event, &trigger);
-
-                    // Clone Arc references for the async block
-                    let event_queue = Arc::clone(&event_queue);
-                    let settings = Arc::clone(&settings);
-                    let recorder = Arc::clone(&recorder);
-                    let storage = Arc::clone(&storage);
-                    let current_game_id = Arc::clone(&current_game_id);
-                    let processing_lock = Arc::clone(&processing_lock);
-
-                    // Spawn a task to process the event asynchronously
-                    tokio::spawn(async move {
-                        // Create a temporary AutoClipManager instance for processing
-                        let temp_manager = AutoClipManager {
-                            recorder,
-                            storage,
-                            settings,
-                            event_queue,
-                            current_game_id,
-                            processing_lock,
-                            monitor_task: Arc::new(TokioMutex::new(None)),
-                            cancel_token: CancellationToken::new(),
-                        };
-
-                        if let Err(e) = temp_manager
-                            .process_event(trigger.clone(), event.clone())
-                            .await
-                        {
-                            error!("Failed to process event {:?}: {}", trigger, e);
-                        }
-                    });
-                };
+            let callback = move |trigger: EventTrigger,
+                                 live_event: super::live_client::GameEvent,
+                                 factors: ClipFactors| {
+                // Convert live_client::GameEvent to recording::GameEvent
+                let event = convert_live_event(live_event, &trigger, &factors);
+
+                // Clone Arc references for the async block
+                let event_queue = Arc::clone(&event_queue);
+                let settings = Arc::clone(&settings);
+                let recorder = Arc::clone(&recorder);
+                let storage = Arc::clone(&storage);
+                let current_game_id = Arc::clone(&current_game_id);
+                let processing_lock = Arc::clone(&processing_lock);
+                let jobs = jobs.clone();
+                let events = Arc::clone(&events);
+                let plugins = plugins.clone();
+                let live_client = Arc::clone(&live_client);
+                let toasts = toasts.clone();
+
+                // Spawn a task to process the event asynchronously
+                tokio::spawn(async move {
+                    // Create a temporary AutoClipManager instance for processing
+                    let temp_manager = AutoClipManager {
+                        recorder,
+                        storage,
+                        settings,
+                        event_queue,
+                        current_game_id,
+                        processing_lock,
+                        monitor_task: Arc::new(TokioMutex::new(None)),
+                        cancel_token: CancellationToken::new(),
+                        jobs,
+                        events,
+                        plugins,
+                        live_client,
+                        toasts,
+                    };
+
+                    if let Err(e) = temp_manager
+                        .process_event(trigger.clone(), event.clone(), factors)
+                        .await
+                    {
+                        error!("Failed to process event {:?}: {}", trigger, e);
+                    }
+                });
+            };
 
             // Run the monitor until cancelled
             let monitoring = monitor.start_monitoring(callback);
@@ -195,6 +392,7 @@ impl AutoClipManager {
                 }
             }
 
+            health.set_monitoring(false);
             info!("Event monitoring task stopped");
         });
 
@@ -229,27 +427,50 @@ impl AutoClipManager {
     ///
     /// This is the main entry point called by the event detection callback.
     /// Events are filtered, queued, merged, and automatically saved.
-    pub async fn process_event(&self, trigger: EventTrigger, event: GameEvent) -> Result<()> {
+    /// `event.priority` already includes the boost from `factors`.
+    pub async fn process_event(
+        &self,
+        trigger: EventTrigger,
+        event: GameEvent,
+        factors: ClipFactors,
+    ) -> Result<()> {
         debug!(
             "Auto Clip Manager: processing event {} (priority: {})",
-            event.event_name,
-            trigger.priority()
+            event.event_name, event.priority
         );
 
         // Check if we should record this event based on settings
         if !self.should_record_event(&trigger, &event).await? {
             debug!(
                 "Event filtered out by settings: {} (priority: {})",
-                event.event_name,
-                trigger.priority()
+                event.event_name, event.priority
             );
             return Ok(());
         }
 
+        let game_id = self.current_game_id.read().await.clone();
+        self.events
+            .emit(AppEvent::AutoClipEventDetected(AutoClipEventDetected {
+                event_name: event.event_name.clone(),
+                event_time: event.event_time,
+                priority: event.priority,
+                game_id: game_id.clone(),
+            }));
+
+        if let Some(plugins) = &self.plugins {
+            plugins
+                .dispatch(&PluginEvent {
+                    game_id,
+                    event: event.clone(),
+                })
+                .await;
+        }
+
         // Add event to queue
         let queued = QueuedEvent {
             trigger: trigger.clone(),
             event: event.clone(),
+            factors: factors.clone(),
             received_at: Instant::now(),
         };
 
@@ -262,66 +483,98 @@ impl AutoClipManager {
         let settings = self.settings.read().await;
 
         if settings.clip_timing.merge_consecutive_events {
+            let merge_threshold = settings.clip_timing.merge_time_threshold.max(0.0);
+            drop(settings);
+
             // Wait for merge window to close before processing
+            tokio::time::sleep(Duration::from_secs_f64(merge_threshold)).await;
             self.try_process_merged_events().await?;
         } else {
             // Save immediately without merging
-            self.save_single_event(trigger, event).await?;
+            self.save_single_event(trigger, event, factors).await?;
         }
 
         Ok(())
     }
 
     /// Check if event should be recorded based on settings
-    async fn should_record_event(&self, trigger: &EventTrigger, _event: &GameEvent) -> Result<bool> {
+    async fn should_record_event(&self, trigger: &EventTrigger, event: &GameEvent) -> Result<bool> {
         let settings = self.settings.read().await;
-
-        // Check priority threshold
-        let event_priority = trigger.priority();
-        if event_priority < settings.event_filter.min_priority {
-            return Ok(false);
-        }
-
-        // Check event type filters
-        let should_record = match trigger {
-            EventTrigger::ChampionKill => settings.event_filter.record_kills,
-            EventTrigger::Multikill(_) => settings.event_filter.record_multikills,
-            EventTrigger::DragonKill => settings.event_filter.record_dragon,
-            EventTrigger::BaronKill => settings.event_filter.record_baron,
-            EventTrigger::TurretKill => settings.event_filter.record_turret,
-            EventTrigger::InhibitorKill => settings.event_filter.record_inhibitor,
-            EventTrigger::Ace => settings.event_filter.record_ace,
-            EventTrigger::Steal => settings.event_filter.record_steal,
-            EventTrigger::ClutchPlay => true, // Always record clutch plays if detected
-        };
-
-        Ok(should_record)
+        Ok(is_event_enabled(
+            &settings.event_filter,
+            trigger,
+            event.priority,
+        ))
     }
 
     /// Try to process merged events if merge window has closed
+    ///
+    /// Queued events are grouped by overlapping clip windows; a group is
+    /// saved once no new event has joined it for the merge threshold.
     async fn try_process_merged_events(&self) -> Result<()> {
         let settings = self.settings.read().await;
-        let merge_threshold = settings.clip_timing.merge_time_threshold as u64;
-        drop(settings);
+        let merge_threshold = settings.clip_timing.merge_time_threshold;
 
         let mut queue = self.event_queue.lock().await;
+        let groups = self.group_overlapping_events(queue.drain(..).collect(), &settings);
+        drop(settings);
 
-        // Check if oldest event is outside merge window
-        if let Some(oldest) = queue.front() {
-            let age = oldest.received_at.elapsed().as_secs();
+        let mut ready = Vec::new();
+        for group in groups {
+            let newest = group.iter().map(|e| e.received_at).max();
+            if newest.is_some_and(|t| t.elapsed().as_secs_f64() >= merge_threshold) {
+                ready.push(group);
+            } else {
+                // Merge window still open - keep waiting
+                queue.extend(group);
+            }
+        }
+        drop(queue);
+
+        // A failed save mustn't drop the groups after it
+        let mut first_error = None;
+        for group in ready {
+            if let Err(e) = self.process_event_window(group).await {
+                match first_error {
+                    None => first_error = Some(e),
+                    Some(_) => error!("Failed to save merged clip: {:#}", e),
+                }
+            }
+        }
 
-            if age >= merge_threshold {
-                // Merge window closed - process events
-                let events_to_merge: Vec<QueuedEvent> = queue.drain(..).collect();
-                drop(queue);
+        first_error.map_or(Ok(()), Err)
+    }
 
-                if !events_to_merge.is_empty() {
-                    self.process_event_window(events_to_merge).await?;
-                }
+    /// Split events into groups whose clip windows overlap or that are
+    /// within the merge threshold of each other
+    fn group_overlapping_events(
+        &self,
+        mut events: Vec<QueuedEvent>,
+        settings: &RecordingSettings,
+    ) -> Vec<Vec<QueuedEvent>> {
+        events.sort_by(|a, b| a.event.event_time.total_cmp(&b.event.event_time));
+
+        let mut groups: Vec<Vec<QueuedEvent>> = Vec::new();
+        let mut group_end = f64::MIN; // Clip end of the current group (game time)
+        let mut last_time = f64::MIN; // Last event time in the current group
+
+        for queued in events {
+            let window = clip_window(&queued.trigger, settings);
+            let time = queued.event.event_time;
+            let clip_start = time - window.pre_duration as f64;
+
+            let overlaps = clip_start <= group_end
+                || time - last_time <= settings.clip_timing.merge_time_threshold;
+            match groups.last_mut() {
+                Some(group) if overlaps => group.push(queued),
+                _ => groups.push(vec![queued]),
             }
+
+            group_end = group_end.max(time + window.post_duration as f64);
+            last_time = time;
         }
 
-        Ok(())
+        groups
     }
 
     /// Process a window of merged events
@@ -350,9 +603,9 @@ impl AutoClipManager {
     /// Merge consecutive events into a single window
     fn merge_events(&self, events: &[QueuedEvent]) -> EventWindow {
         // Find highest priority event
-        let primary_event = events.iter().max_by_key(|e| e.trigger.priority()).unwrap();
+        let primary_event = events.iter().max_by_key(|e| e.event.priority).unwrap();
 
-        let priority = primary_event.trigger.priority();
+        let priority = primary_event.event.priority;
 
         // Calculate time range
         let start_time = events
@@ -367,72 +620,95 @@ impl AutoClipManager {
             .max_by(|a, b| a.partial_cmp(b).unwrap())
             .unwrap();
 
+        let mut sorted = events.to_vec();
+        sorted.sort_by(|a, b| a.event.event_time.total_cmp(&b.event.event_time));
+
         EventWindow {
             primary_trigger: primary_event.trigger.clone(),
-            events: events.iter().map(|e| e.event.clone()).collect(),
+            primary_event: primary_event.event.clone(),
+            events: sorted,
             start_time: start_time as f32,
             end_time: end_time as f32,
             priority,
+            factors: events
+                .iter()
+                .map(|e| e.factors.clone())
+                .reduce(|a, b| a.merge(&b))
+                .unwrap_or_default(),
         }
     }
 
     /// Save a single event without merging
-    async fn save_single_event(&self, trigger: EventTrigger, event: GameEvent) -> Result<()> {
+    async fn save_single_event(
+        &self,
+        trigger: EventTrigger,
+        event: GameEvent,
+        factors: ClipFactors,
+    ) -> Result<()> {
         // Prevent concurrent saves
         let _lock = self.processing_lock.lock().await;
 
         let settings = self.settings.read().await;
 
         // Calculate clip window duration
-        let clip_window = self.calculate_clip_window(&trigger, &settings);
+        let clip_window = clip_window(&trigger, &settings);
         drop(settings);
 
         let total_duration = clip_window.pre_duration as f64 + clip_window.post_duration as f64;
 
         info!(
             "Saving clip for event: {} (priority: {}, duration: {:.1}s)",
-            event.event_name,
-            trigger.priority(),
-            total_duration
+            event.event_name, event.priority, total_duration
         );
 
         // Generate clip ID
         let clip_id = format!("{}_{}", event.event_name, event.event_time as u32);
 
-        // Save clip via WindowsRecorder
+        // Save clip via the recording backend
         let clip_path = self
-            .recorder
-            .read()
-            .await
-            .save_clip(&event, clip_id.clone(), trigger.priority(), total_duration)
+            .save_with_retry(&event, &clip_id, event.priority, total_duration)
             .await
             .context("Failed to save clip via recorder")?;
 
         info!("Clip saved: {:?}", clip_path);
 
         // Save metadata to storage
-        self.save_clip_metadata(&clip_id, &event, trigger.priority(), &clip_path)
-            .await?;
+        let mut clip = clip_metadata(&event, &trigger, event.priority, &factors, &clip_path);
+        clip.game_time_start = event.event_time - clip_window.pre_duration as f64;
+        clip.game_time_end = clip.game_time_start + total_duration;
+        clip.clip_duration = total_duration;
+        clip.primary_event.clip_timestamp = clip_window.pre_duration as f64;
+        clip.score = Some(scoring::score(&clip, &factors));
+        self.save_clip_metadata(&clip_id, clip).await?;
+
+        self.save_events(&[event_data(&event, &trigger)]).await?;
 
         Ok(())
     }
 
     /// Save an event window (merged events)
+    ///
+    /// Windows with several events become one extended "teamfight" clip.
     async fn save_event_window(&self, window: EventWindow) -> Result<()> {
         // Prevent concurrent saves
         let _lock = self.processing_lock.lock().await;
 
         let settings = self.settings.read().await;
 
-        // Calculate clip window for primary event
-        let clip_window = self.calculate_clip_window(&window.primary_trigger, &settings);
+        // Lead-in of the first event and follow-up of the last one
+        let first = window.events.first().unwrap();
+        let last = window.events.last().unwrap();
+        let pre_duration = clip_window(&first.trigger, &settings).pre_duration as f64;
+        let post_duration = clip_window(&last.trigger, &settings).post_duration as f64;
+        let merge_threshold = settings.clip_timing.merge_time_threshold;
         drop(settings);
 
-        // Extend duration to cover the full event window
+        // Extend duration to cover the full event window. The buffer is saved
+        // up to now, which is at least the merge threshold after the last event.
         let event_window_duration = window.end_time - window.start_time;
-        let total_duration = clip_window.pre_duration as f64
-            + event_window_duration as f64
-            + clip_window.post_duration as f64;
+        let elapsed = last.received_at.elapsed().as_secs_f64();
+        let total_duration =
+            pre_duration + event_window_duration as f64 + post_duration.max(elapsed);
 
         info!(
             "Saving merged clip: {:?} ({} events, priority: {}, duration: {:.1}s)",
@@ -442,21 +718,22 @@ impl AutoClipManager {
             total_duration
         );
 
-        // Use primary event for clip generation
-        let primary_event = &window.events[0];
-        let clip_id = format!(
-            "merged_{}_{}",
-            window.start_time as u32, window.end_time as u32
-        );
+        let teamfight = window.events.len() > 1;
+        let clip_id = if teamfight {
+            format!(
+                "teamfight_{}_{}",
+                window.start_time as u32, window.end_time as u32
+            )
+        } else {
+            let event = &window.primary_event;
+            format!("{}_{}", event.event_name, event.event_time as u32)
+        };
 
-        // Save clip via WindowsRecorder
+        // Save clip via the recording backend
         let clip_path = self
-            .recorder
-            .read()
-            .await
-            .save_clip(
-                primary_event,
-                clip_id.clone(),
+            .save_with_retry(
+                &window.primary_event,
+                &clip_id,
                 window.priority,
                 total_duration,
             )
@@ -465,91 +742,181 @@ impl AutoClipManager {
 
         info!("Merged clip saved: {:?}", clip_path);
 
-        // Save metadata to storage
-        self.save_clip_metadata(&clip_id, primary_event, window.priority, &clip_path)
-            .await?;
+        // Save metadata to storage, listing every contributing event
+        let clip_start = window.start_time as f64 - pre_duration;
+        let mut clip = clip_metadata(
+            &window.primary_event,
+            &window.primary_trigger,
+            window.priority,
+            &window.factors,
+            &clip_path,
+        );
+        clip.game_time_start = clip_start;
+        clip.game_time_end = clip_start + total_duration;
+        clip.clip_duration = total_duration;
+        clip.primary_event.clip_timestamp = window.primary_event.event_time - clip_start;
+        clip.merged_events = window
+            .events
+            .iter()
+            .filter(|e| e.event.event_id != window.primary_event.event_id)
+            .map(|e| event_info(e, clip_start))
+            .collect();
+        clip.event_window = ClipEventWindow {
+            merge_strategy: if teamfight {
+                MergeStrategy::ConsecutiveEvents
+            } else {
+                MergeStrategy::SingleEvent
+            },
+            time_threshold_secs: merge_threshold,
+            events_merged: window.events.len(),
+            pre_duration,
+            post_duration,
+        };
+        if teamfight {
+            clip.add_tag("teamfight".to_string());
+        }
+        clip.score = Some(scoring::score(&clip, &window.factors));
+        self.save_clip_metadata(&clip_id, clip).await?;
 
-        // Save all events in the window to storage
-        let game_id = self.current_game_id.read().await;
-        if let Some(ref game_id) = *game_id {
-            let event_data: Vec<EventData> = window
-                .events
-                .iter()
-                .map(|e| {
-                    // Collect participants (killer + assisters)
-                    let mut participants = Vec::new();
-                    if let Some(ref killer) = e.killer_name {
-                        participants.push(killer.clone());
-                    }
-                    participants.extend_from_slice(&e.assisters);
-
-                    EventData {
-                        event_id: e.event_id,
-                        event_type: trigger_to_event_type(&window.primary_trigger),
-                        timestamp: e.event_time as f64,
-                        priority: window.priority,
-                        participants,
-                        details: None,
-                    }
-                })
-                .collect();
+        // Add the window's events to the game's event list
+        let events: Vec<EventData> = window
+            .events
+            .iter()
+            .map(|queued| event_data(&queued.event, &queued.trigger))
+            .collect();
+        self.save_events(&events).await?;
+
+        Ok(())
+    }
 
+    /// Add events to the current game's event list (kept by every clip save,
+    /// so markers, the timeline and VOD imports see the whole game)
+    async fn save_events(&self, events: &[EventData]) -> Result<()> {
+        if let Some(game_id) = self.current_game_id.read().await.as_deref() {
             self.storage
-                .save_events(game_id, &event_data)
+                .add_events(game_id, events)
                 .context("Failed to save event data")?;
         }
-
         Ok(())
     }
 
-    /// Calculate clip window (pre/post durations) based on settings and event type
-    fn calculate_clip_window(
+    /// Save through the recording backend, retrying once after a failure
+    ///
+    /// Callers hold the save queue, so later events wait for the retry
+    /// instead of overtaking it.
+    async fn save_with_retry(
         &self,
-        trigger: &EventTrigger,
-        settings: &RecordingSettings,
-    ) -> ClipWindow {
-        // Map EventTrigger to settings event type string
-        let event_type = match trigger {
-            EventTrigger::Multikill(_) => "multikill",
-            EventTrigger::Steal => "steal",
-            _ => "kill", // Default for other events
-        };
-
-        // Get event-specific timing or use defaults
-        let timing = settings.clip_timing.get_timing_for_event(event_type);
-
-        ClipWindow {
-            pre_duration: timing.pre_duration,
-            post_duration: timing.post_duration,
+        event: &GameEvent,
+        clip_id: &str,
+        priority: u8,
+        duration: f64,
+    ) -> Result<PathBuf> {
+        match self
+            .recorder
+            .save_clip(event, clip_id.to_string(), priority, duration)
+            .await
+        {
+            Ok(path) => Ok(path),
+            Err(e) => {
+                warn!("Saving clip {} failed, retrying: {:#}", clip_id, e);
+                tokio::time::sleep(SAVE_RETRY_DELAY).await;
+                self.recorder
+                    .save_clip(event, clip_id.to_string(), priority, duration)
+                    .await
+            }
         }
     }
 
     /// Save clip metadata to storage
-    async fn save_clip_metadata(
-        &self,
-        clip_id: &str,
-        event: &GameEvent,
-        priority: u8,
-        clip_path: &std::path::Path,
-    ) -> Result<()> {
+    ///
+    /// Written as V2 metadata so tags and merged events are kept; the
+    /// clips.json index is updated along with it.
+    async fn save_clip_metadata(&self, clip_id: &str, mut clip: ClipMetadataV2) -> Result<()> {
+        clip.dropped_segments = self
+            .recorder
+            .take_dropped_segments(Path::new(&clip.file_path))
+            .await
+            .iter()
+            .map(|segment| segment.to_string_lossy().to_string())
+            .collect();
+        if !clip.dropped_segments.is_empty() {
+            warn!(
+                "Clip {} is missing {} corrupt segment(s)",
+                clip_id,
+                clip.dropped_segments.len()
+            );
+        }
+        if let Some(duration) = self
+            .recorder
+            .take_clip_duration(Path::new(&clip.file_path))
+            .await
+        {
+            clip.fit_to_duration(duration);
+        }
+
         let game_id = self.current_game_id.read().await;
 
         if let Some(ref game_id) = *game_id {
-            let metadata = ClipMetadata {
-                file_path: clip_path.to_string_lossy().to_string(),
-                thumbnail_path: None,
-                event_type: EventType::Custom(event.event_name.clone()),
-                event_time: event.event_time as f64,
-                priority,
-                duration: 0.0, // Will be calculated by video processor
-                created_at: chrono::Utc::now(),
-            };
+            clip.game_id = game_id.clone();
+
+            // Recorded once per game by set_current_game
+            let patch = self
+                .storage
+                .load_game_metadata(game_id)
+                .ok()
+                .and_then(|game| game.patch);
+            if let Some(patch) = patch {
+                clip.add_tag(league_patch::clip_tag(&patch));
+            }
+
+            let handling = self.settings.read().await.clip_timing.duplicate_clips;
+            if handling != DuplicateClipHandling::Keep {
+                let original = self
+                    .storage
+                    .find_overlapping_clip(game_id, &clip, DUPLICATE_OVERLAP)
+                    .context("Failed to check for duplicate clips")?;
+
+                if let Some(original) = original {
+                    let original_id = ClipMetadataV2::generate_clip_id(&original.file_path);
+
+                    // A save the user asked for is never thrown away, only linked
+                    let manual =
+                        matches!(clip.event_window.merge_strategy, MergeStrategy::ManualSave);
+                    if handling == DuplicateClipHandling::Skip && !manual {
+                        info!("Skipping clip {}: duplicate of {}", clip_id, original_id);
+                        if let Err(e) = std::fs::remove_file(&clip.file_path) {
+                            warn!("Failed to remove duplicate clip: {}", e);
+                        }
+                        return Ok(());
+                    }
+
+                    info!("Clip {} is a duplicate of {}", clip_id, original_id);
+                    clip.duplicate_of = Some(original_id);
+                    clip.add_tag("duplicate".to_string());
+                }
+            }
 
             self.storage
-                .save_clip_metadata(game_id, &metadata)
+                .save_clip_metadata_v2(game_id, &clip)
                 .context("Failed to save clip metadata")?;
 
             info!("Clip metadata saved: {} (game: {})", clip_id, game_id);
+
+            if let Some(toasts) = &self.toasts {
+                toasts
+                    .clip_saved(&clip.primary_event.event_type, clip.priority)
+                    .await;
+            }
+
+            if let Some(jobs) = &self.jobs {
+                let job = JobKind::GenerateThumbnail {
+                    game_id: game_id.clone(),
+                    clip_path: PathBuf::from(&clip.file_path),
+                };
+                if let Err(e) = jobs.enqueue(job, JobPriority::Low).await {
+                    warn!("Failed to queue thumbnail generation: {}", e);
+                }
+            }
         } else {
             warn!("No current game ID set - clip metadata not saved");
         }
@@ -558,11 +925,109 @@ impl AutoClipManager {
     }
 }
 
+/// Whether the event filter settings allow clipping `trigger`
+pub(super) fn is_event_enabled(
+    filter: &EventFilterSettings,
+    trigger: &EventTrigger,
+    priority: u8,
+) -> bool {
+    // Check priority threshold
+    if priority < filter.min_priority {
+        return false;
+    }
+
+    // Check event type filters
+    match trigger {
+        EventTrigger::ChampionKill => filter.record_kills,
+        EventTrigger::Multikill(_) => filter.record_multikills,
+        EventTrigger::DragonKill => filter.record_dragon,
+        EventTrigger::BaronKill => filter.record_baron,
+        EventTrigger::TurretKill => filter.record_turret,
+        EventTrigger::InhibitorKill => filter.record_inhibitor,
+        EventTrigger::Ace => filter.record_ace,
+        EventTrigger::Steal => filter.record_steal,
+        EventTrigger::ClutchPlay => true, // Always record clutch plays if detected
+        EventTrigger::Death(_) => filter.record_deaths,
+    }
+}
+
+/// V2 metadata for a clip of `event`, tagged with its clip factors
+pub(super) fn clip_metadata(
+    event: &GameEvent,
+    trigger: &EventTrigger,
+    priority: u8,
+    factors: &ClipFactors,
+    clip_path: &Path,
+) -> ClipMetadataV2 {
+    // Deaths are tagged separately so they can go into a fails short
+    let event_type = match trigger {
+        EventTrigger::Death(_) => EventType::Death,
+        _ => EventType::Custom(event.event_name.clone()),
+    };
+
+    let metadata = ClipMetadata {
+        file_path: clip_path.to_string_lossy().to_string(),
+        thumbnail_path: None,
+        event_type,
+        event_time: event.event_time,
+        priority,
+        duration: 0.0, // Will be calculated by video processor
+        created_at: chrono::Utc::now(),
+    };
+
+    let mut clip = ClipMetadataV2::from(metadata);
+    clip.primary_event.event_id = event.event_id;
+    clip.primary_event.killer = event.killer_name.clone();
+    clip.primary_event.victim = event.victim_name.clone();
+    clip.primary_event.assisters = event.assisters.clone();
+    for tag in factors.tags() {
+        clip.add_tag(tag);
+    }
+    clip
+}
+
+/// Metadata entry for an event merged into a clip starting at `clip_start`
+fn event_info(queued: &QueuedEvent, clip_start: f64) -> EventInfo {
+    let event = &queued.event;
+    EventInfo {
+        event_id: event.event_id,
+        event_type: trigger_to_event_type(&queued.trigger),
+        timestamp: event.event_time,
+        clip_timestamp: event.event_time - clip_start,
+        priority: event.priority,
+        killer: event.killer_name.clone(),
+        victim: event.victim_name.clone(),
+        assisters: event.assisters.clone(),
+        gold_earned: None,
+        shutdown_bounty: None,
+        details: None,
+    }
+}
+
+/// Entry for the game's event list
+fn event_data(event: &GameEvent, trigger: &EventTrigger) -> EventData {
+    // Participants are the killer and the assisters
+    let mut participants = Vec::new();
+    if let Some(ref killer) = event.killer_name {
+        participants.push(killer.clone());
+    }
+    participants.extend_from_slice(&event.assisters);
+
+    EventData {
+        event_id: event.event_id,
+        event_type: trigger_to_event_type(trigger),
+        timestamp: event.event_time,
+        priority: event.priority,
+        participants,
+        details: None,
+    }
+}
+
 /// Clip window timing configuration
 #[derive(Debug, Clone)]
-struct ClipWindow {
-    pre_duration: u32,  // Seconds before event
-    post_duration: u32, // Seconds after event
+pub(super) struct ClipWindow {
+    pub(super) pre_duration: u32,  // Seconds before event
+    pub(super) post_duration: u32, // Seconds after event
 }
 
 /// Convert LiveClientMonitor's EventTrigger to storage's EventType
@@ -577,13 +1042,34 @@ fn trigger_to_event_type(trigger: &EventTrigger) -> EventType {
         EventTrigger::Ace => EventType::Ace,
         EventTrigger::Steal => EventType::Custom("Steal".to_string()),
         EventTrigger::ClutchPlay => EventType::Custom("ClutchPlay".to_string()),
+        EventTrigger::Death(_) => EventType::Death,
+    }
+}
+
+/// Calculate clip window (pre/post durations) based on settings and event type
+pub(super) fn clip_window(trigger: &EventTrigger, settings: &RecordingSettings) -> ClipWindow {
+    // Map EventTrigger to settings event type string
+    let event_type = match trigger {
+        EventTrigger::Multikill(_) => "multikill",
+        EventTrigger::Steal => "steal",
+        EventTrigger::Death(_) => "death",
+        _ => "kill", // Default for other events
+    };
+
+    // Get event-specific timing or use defaults
+    let timing = settings.clip_timing.get_timing_for_event(event_type);
+
+    ClipWindow {
+        pre_duration: timing.pre_duration,
+        post_duration: timing.post_duration,
     }
 }
 
 /// Convert live_client::GameEvent to recording::GameEvent
-fn convert_live_event(
+pub(super) fn convert_live_event(
     live_event: super::live_client::GameEvent,
     trigger: &EventTrigger,
+    factors: &ClipFactors,
 ) -> GameEvent {
     GameEvent {
         event_id: live_event.event_id as u64,
@@ -592,7 +1078,7 @@ fn convert_live_event(
         killer_name: live_event.killer_name,
         victim_name: live_event.victim_name,
         assisters: live_event.assisters.unwrap_or_default(),
-        priority: trigger.priority(),
+        priority: trigger.boosted_priority(factors),
         timestamp: Instant::now(), // Use current time as event timestamp
     }
 }
@@ -600,6 +1086,8 @@ fn convert_live_event(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::recording::mock::MockRecorder;
+    use crate::recording::RecordingManager;
     use crate::settings::models::RecordingSettings;
 
     fn create_test_event(event_name: &str, event_time: f64) -> GameEvent {
@@ -622,16 +1110,19 @@ mod tests {
             QueuedEvent {
                 trigger: EventTrigger::ChampionKill,
                 event: create_test_event("ChampionKill", 100.0),
+                factors: ClipFactors::default(),
                 received_at: Instant::now(),
             },
             QueuedEvent {
                 trigger: EventTrigger::Multikill(2),
                 event: create_test_event("ChampionKill", 105.0),
+                factors: ClipFactors::default(),
                 received_at: Instant::now(),
             },
             QueuedEvent {
                 trigger: EventTrigger::Multikill(3),
                 event: create_test_event("ChampionKill", 108.0),
+                factors: ClipFactors::default(),
                 received_at: Instant::now(),
             },
         ];
@@ -639,7 +1130,7 @@ mod tests {
         // Create manager (will need test doubles for dependencies)
         let temp_dir = std::env::temp_dir().join("lolshorts_test_acm");
         let recorder = Arc::new(TokioRwLock::new(
-            WindowsRecorder::new(temp_dir.clone()).unwrap(),
+            RecordingManager::new(temp_dir.clone()).unwrap(),
         ));
         let storage = Arc::new(Storage::new(&temp_dir).unwrap());
         let settings = Arc::new(TokioRwLock::new(RecordingSettings::default()));
@@ -658,11 +1149,59 @@ mod tests {
         let _ = std::fs::remove_dir_all(temp_dir);
     }
 
+    #[test]
+    fn test_group_overlapping_events() {
+        let queued = |trigger: EventTrigger, event_time: f64| QueuedEvent {
+            event: GameEvent {
+                priority: trigger.priority(),
+                ..create_test_event("Event", event_time)
+            },
+            trigger,
+            factors: ClipFactors::default(),
+            received_at: Instant::now(),
+        };
+        // Baron and Ace 12s apart, a lone kill later on
+        let events = vec![
+            queued(EventTrigger::ChampionKill, 300.0),
+            queued(EventTrigger::Ace, 112.0),
+            queued(EventTrigger::BaronKill, 100.0),
+        ];
+
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_group");
+        let recorder = Arc::new(TokioRwLock::new(
+            RecordingManager::new(temp_dir.clone()).unwrap(),
+        ));
+        let storage = Arc::new(Storage::new(&temp_dir).unwrap());
+        let mut settings = RecordingSettings::default();
+        // Only overlapping clip windows (10s before, 3s after) merge
+        settings.clip_timing.merge_time_threshold = 0.0;
+
+        let manager = AutoClipManager::new(
+            recorder,
+            storage,
+            Arc::new(TokioRwLock::new(settings.clone())),
+        );
+
+        let groups = manager.group_overlapping_events(events, &settings);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].len(), 2);
+        assert_eq!(groups[0][0].trigger, EventTrigger::BaronKill);
+        assert_eq!(groups[1][0].trigger, EventTrigger::ChampionKill);
+
+        let window = manager.merge_events(&groups[0]);
+        assert_eq!(window.primary_trigger, EventTrigger::Ace);
+        assert_eq!(window.start_time, 100.0);
+        assert_eq!(window.end_time, 112.0);
+
+        // Cleanup
+        let _ = std::fs::remove_dir_all(temp_dir);
+    }
+
     #[tokio::test]
     async fn test_event_filtering() {
         let temp_dir = std::env::temp_dir().join("lolshorts_test_filter");
         let recorder = Arc::new(TokioRwLock::new(
-            WindowsRecorder::new(temp_dir.clone()).unwrap(),
+            RecordingManager::new(temp_dir.clone()).unwrap(),
         ));
         let storage = Arc::new(Storage::new(&temp_dir).unwrap());
 
@@ -693,4 +1232,41 @@ mod tests {
         // Cleanup
         let _ = std::fs::remove_dir_all(temp_dir);
     }
+
+    #[tokio::test]
+    async fn test_event_saved_to_storage() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_auto_clip_pipeline");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let storage = Arc::new(Storage::new(&temp_dir).unwrap());
+        let recorder = Arc::new(MockRecorder::new(
+            temp_dir.join("recordings"),
+            temp_dir.join("clips"),
+        ));
+        recorder.start_replay_buffer().await.unwrap();
+
+        let mut settings = RecordingSettings::default();
+        settings.clip_timing.merge_consecutive_events = false;
+        let manager = AutoClipManager::new(
+            recorder.clone(),
+            Arc::clone(&storage),
+            Arc::new(TokioRwLock::new(settings)),
+        );
+        manager.set_current_game(Some("game_1".to_string())).await;
+
+        manager
+            .process_event(
+                EventTrigger::Multikill(3),
+                create_test_event("Multikill", 300.0),
+                ClipFactors::default(),
+            )
+            .await
+            .unwrap();
+
+        let clips = storage.load_clip_metadata("game_1").unwrap();
+        assert_eq!(clips.len(), 1);
+        assert!(Path::new(&clips[0].file_path).exists());
+
+        recorder.stop_replay_buffer().await.unwrap();
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
 }
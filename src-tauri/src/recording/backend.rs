@@ -0,0 +1,302 @@
+//! Recording backend interface
+//!
+//! [`AutoClipManager`] and the recording commands only need to start and
+//! stop a replay buffer and save clips from it, so they work through
+//! [`Recorder`] instead of a concrete backend. [`ActiveRecorder`] picks the
+//! built-in recorder or OBS per the settings when the replay buffer starts,
+//! so switching needs no app restart and a running buffer keeps its backend
+//! until it's stopped.
+//!
+//! Backend specific configuration (codec, audio, low-spec mode) still goes
+//! through the concrete type.
+//!
+//! [`AutoClipManager`]: super::auto_clip_manager::AutoClipManager
+
+use anyhow::Result;
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock as TokioRwLock;
+
+use super::{GameEvent, RecordingManager, RecordingStats, RecordingStatus};
+use crate::settings::models::RecordingSettings;
+
+#[async_trait]
+pub trait Recorder: Send + Sync {
+    /// Start keeping a replay buffer to save clips from
+    async fn start_replay_buffer(&self) -> Result<()>;
+
+    /// Stop the replay buffer, dropping anything not saved
+    async fn stop_replay_buffer(&self) -> Result<()>;
+
+    /// Save the last `duration_secs` of the buffer, returning the clip file
+    async fn save_clip(
+        &self,
+        event: &GameEvent,
+        clip_id: String,
+        priority: u8,
+        duration_secs: f64,
+    ) -> Result<PathBuf>;
+
+    async fn get_state(&self) -> RecordingStatus;
+
+    async fn get_stats(&self) -> RecordingStats;
+
+    /// Buffer segments that were left out of `clip_path` because they were
+    /// corrupt (asked once, right after saving it)
+    async fn take_dropped_segments(&self, _clip_path: &Path) -> Vec<PathBuf> {
+        Vec::new()
+    }
+
+    /// Length of `clip_path` when the backend couldn't save exactly the
+    /// requested duration (asked once, right after saving it)
+    async fn take_clip_duration(&self, _clip_path: &Path) -> Option<f64> {
+        None
+    }
+}
+
+#[async_trait]
+impl Recorder for RecordingManager {
+    async fn start_replay_buffer(&self) -> Result<()> {
+        RecordingManager::start_replay_buffer(self).await
+    }
+
+    async fn stop_replay_buffer(&self) -> Result<()> {
+        RecordingManager::stop_replay_buffer(self).await
+    }
+
+    async fn save_clip(
+        &self,
+        event: &GameEvent,
+        clip_id: String,
+        priority: u8,
+        duration_secs: f64,
+    ) -> Result<PathBuf> {
+        RecordingManager::save_clip(self, event, clip_id, priority, duration_secs).await
+    }
+
+    async fn get_state(&self) -> RecordingStatus {
+        RecordingManager::get_state(self).await
+    }
+
+    async fn get_stats(&self) -> RecordingStats {
+        RecordingManager::get_stats(self).await
+    }
+
+    async fn take_dropped_segments(&self, clip_path: &Path) -> Vec<PathBuf> {
+        RecordingManager::take_dropped_segments(self, clip_path)
+    }
+}
+
+/// A backend shared behind a lock so its settings can still be changed
+#[async_trait]
+impl<R: Recorder> Recorder for TokioRwLock<R> {
+    async fn start_replay_buffer(&self) -> Result<()> {
+        self.read().await.start_replay_buffer().await
+    }
+
+    async fn stop_replay_buffer(&self) -> Result<()> {
+        self.read().await.stop_replay_buffer().await
+    }
+
+    async fn save_clip(
+        &self,
+        event: &GameEvent,
+        clip_id: String,
+        priority: u8,
+        duration_secs: f64,
+    ) -> Result<PathBuf> {
+        self.read()
+            .await
+            .save_clip(event, clip_id, priority, duration_secs)
+            .await
+    }
+
+    async fn get_state(&self) -> RecordingStatus {
+        self.read().await.get_state().await
+    }
+
+    async fn get_stats(&self) -> RecordingStats {
+        self.read().await.get_stats().await
+    }
+
+    async fn take_dropped_segments(&self, clip_path: &Path) -> Vec<PathBuf> {
+        self.read().await.take_dropped_segments(clip_path).await
+    }
+
+    async fn take_clip_duration(&self, clip_path: &Path) -> Option<f64> {
+        self.read().await.take_clip_duration(clip_path).await
+    }
+}
+
+/// The built-in recorder, or OBS while `RecordingSettings.obs` is enabled
+pub struct ActiveRecorder {
+    builtin: Arc<dyn Recorder>,
+    obs: Arc<dyn Recorder>,
+    settings: Arc<TokioRwLock<RecordingSettings>>,
+    /// Whether the running replay buffer was started on OBS
+    session: TokioRwLock<Option<bool>>,
+}
+
+impl ActiveRecorder {
+    pub fn new(
+        builtin: Arc<dyn Recorder>,
+        obs: Arc<dyn Recorder>,
+        settings: Arc<TokioRwLock<RecordingSettings>>,
+    ) -> Self {
+        Self {
+            builtin,
+            obs,
+            settings,
+            session: TokioRwLock::new(None),
+        }
+    }
+
+    /// Backend of the running buffer, or the configured one when stopped
+    async fn uses_obs(&self) -> bool {
+        match *self.session.read().await {
+            Some(obs) => obs,
+            None => self.settings.read().await.obs.enabled,
+        }
+    }
+
+    async fn current(&self) -> &dyn Recorder {
+        if self.uses_obs().await {
+            self.obs.as_ref()
+        } else {
+            self.builtin.as_ref()
+        }
+    }
+}
+
+#[async_trait]
+impl Recorder for ActiveRecorder {
+    async fn start_replay_buffer(&self) -> Result<()> {
+        let obs = self.uses_obs().await;
+        self.current().await.start_replay_buffer().await?;
+        *self.session.write().await = Some(obs);
+        Ok(())
+    }
+
+    async fn stop_replay_buffer(&self) -> Result<()> {
+        let result = self.current().await.stop_replay_buffer().await;
+        *self.session.write().await = None;
+        result
+    }
+
+    async fn save_clip(
+        &self,
+        event: &GameEvent,
+        clip_id: String,
+        priority: u8,
+        duration_secs: f64,
+    ) -> Result<PathBuf> {
+        self.current()
+            .await
+            .save_clip(event, clip_id, priority, duration_secs)
+            .await
+    }
+
+    async fn get_state(&self) -> RecordingStatus {
+        self.current().await.get_state().await
+    }
+
+    async fn get_stats(&self) -> RecordingStats {
+        self.current().await.get_stats().await
+    }
+
+    async fn take_dropped_segments(&self, clip_path: &Path) -> Vec<PathBuf> {
+        self.current().await.take_dropped_segments(clip_path).await
+    }
+
+    async fn take_clip_duration(&self, clip_path: &Path) -> Option<f64> {
+        self.current().await.take_clip_duration(clip_path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    /// Backend that only remembers its name in the clip path
+    struct NamedRecorder(&'static str);
+
+    #[async_trait]
+    impl Recorder for NamedRecorder {
+        async fn start_replay_buffer(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn stop_replay_buffer(&self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn save_clip(
+            &self,
+            _event: &GameEvent,
+            clip_id: String,
+            _priority: u8,
+            _duration_secs: f64,
+        ) -> Result<PathBuf> {
+            Ok(PathBuf::from(format!("{}/{}.mp4", self.0, clip_id)))
+        }
+
+        async fn get_state(&self) -> RecordingStatus {
+            RecordingStatus::Buffering
+        }
+
+        async fn get_stats(&self) -> RecordingStats {
+            RecordingStats::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_active_recorder_follows_settings() {
+        let settings = Arc::new(TokioRwLock::new(RecordingSettings::default()));
+        let recorder = ActiveRecorder::new(
+            Arc::new(NamedRecorder("builtin")),
+            Arc::new(NamedRecorder("obs")),
+            Arc::clone(&settings),
+        );
+        let event = GameEvent {
+            event_id: 0,
+            event_name: "manual".to_string(),
+            event_time: 0.0,
+            killer_name: None,
+            victim_name: None,
+            assisters: vec![],
+            priority: 3,
+            timestamp: Instant::now(),
+        };
+
+        let path = recorder
+            .save_clip(&event, "a".to_string(), 3, 30.0)
+            .await
+            .unwrap();
+        assert_eq!(path, PathBuf::from("builtin/a.mp4"));
+
+        settings.write().await.obs.enabled = true;
+        let path = recorder
+            .save_clip(&event, "b".to_string(), 3, 30.0)
+            .await
+            .unwrap();
+        assert_eq!(path, PathBuf::from("obs/b.mp4"));
+
+        // A running buffer keeps its backend until it's stopped
+        recorder.start_replay_buffer().await.unwrap();
+        settings.write().await.obs.enabled = false;
+        let path = recorder
+            .save_clip(&event, "c".to_string(), 3, 30.0)
+            .await
+            .unwrap();
+        assert_eq!(path, PathBuf::from("obs/c.mp4"));
+
+        recorder.stop_replay_buffer().await.unwrap();
+        let path = recorder
+            .save_clip(&event, "d".to_string(), 3, 30.0)
+            .await
+            .unwrap();
+        assert_eq!(path, PathBuf::from("builtin/d.mp4"));
+    }
+}
@@ -1,17 +1,22 @@
-use super::{GameEvent, RecordingStatus};
+use super::live_client::LiveClientStatus;
+use super::recovery::{self, RecoverableSession};
+use super::replay::{self, ReplayExtraction, ReplayExtractor};
+use super::RecordingStatus;
 use crate::auth::middleware::require_auth;
+use crate::jobs::{JobKind, JobPriority};
+use crate::lcu::patch::short_patch;
+use crate::lcu::LcuClient;
+use crate::utils::security;
 use crate::AppState;
 use std::path::PathBuf;
-use std::time::Instant;
+use std::sync::Arc;
 use tauri::State;
 
 #[tauri::command]
 pub async fn start_recording(state: State<'_, AppState>) -> Result<(), String> {
     // FREE tier feature - no authentication required
     state
-        .recording_manager
-        .write()
-        .await
+        .recorder
         .start_replay_buffer()
         .await
         .map_err(|e| e.to_string())
@@ -21,9 +26,7 @@ pub async fn start_recording(state: State<'_, AppState>) -> Result<(), String> {
 pub async fn stop_recording(state: State<'_, AppState>) -> Result<(), String> {
     // FREE tier feature - no authentication required
     state
-        .recording_manager
-        .write()
-        .await
+        .recorder
         .stop_replay_buffer()
         .await
         .map_err(|e| e.to_string())
@@ -32,7 +35,7 @@ pub async fn stop_recording(state: State<'_, AppState>) -> Result<(), String> {
 #[tauri::command]
 pub async fn get_recording_status(state: State<'_, AppState>) -> Result<String, String> {
     // FREE tier feature - no authentication required
-    let status = state.recording_manager.read().await.get_state().await;
+    let status = state.recorder.get_state().await;
 
     // Convert RecordingStatus to string for frontend
     let status_str = match status {
@@ -47,15 +50,43 @@ pub async fn get_recording_status(state: State<'_, AppState>) -> Result<String,
     Ok(status_str.to_string())
 }
 
+/// Manually pause the replay buffer (stops recording new segments)
+///
+/// Returns false if the buffer wasn't running. Holds until resumed or the
+/// next game starts.
+#[tauri::command]
+pub async fn pause_replay_buffer(state: State<'_, AppState>) -> Result<bool, String> {
+    // FREE tier feature - no authentication required
+    Ok(state
+        .recording_manager
+        .read()
+        .await
+        .pause_replay_buffer()
+        .await)
+}
+
+/// Manually resume a paused replay buffer
+///
+/// Returns false if the buffer wasn't paused. Holds until paused or the
+/// current game ends.
+#[tauri::command]
+pub async fn resume_replay_buffer(state: State<'_, AppState>) -> Result<bool, String> {
+    // FREE tier feature - no authentication required
+    Ok(state
+        .recording_manager
+        .read()
+        .await
+        .resume_replay_buffer()
+        .await)
+}
+
 #[tauri::command]
 pub async fn start_auto_capture(state: State<'_, AppState>) -> Result<(), String> {
     // FREE tier feature - no authentication required
 
     // Start the replay buffer
     state
-        .recording_manager
-        .write()
-        .await
+        .recorder
         .start_replay_buffer()
         .await
         .map_err(|e| e.to_string())?;
@@ -83,9 +114,7 @@ pub async fn stop_auto_capture(state: State<'_, AppState>) -> Result<(), String>
 
     // Stop the replay buffer
     state
-        .recording_manager
-        .write()
-        .await
+        .recorder
         .stop_replay_buffer()
         .await
         .map_err(|e| e.to_string())?;
@@ -93,37 +122,26 @@ pub async fn stop_auto_capture(state: State<'_, AppState>) -> Result<(), String>
     Ok(())
 }
 
+/// Live Client API health, to show why auto-capture isn't saving clips
+#[tauri::command]
+pub async fn get_live_client_status(
+    state: State<'_, AppState>,
+) -> Result<LiveClientStatus, String> {
+    // FREE tier feature - no authentication required
+    Ok(state.auto_clip_manager.live_client_status().await)
+}
+
 #[tauri::command]
 pub async fn save_replay(state: State<'_, AppState>, seconds: u32) -> Result<PathBuf, String> {
     // Require authentication
     require_auth(&state.auth).map_err(|e| e.to_string())?;
-    // Create a dummy GameEvent for manual save
-    let manual_event = GameEvent {
-        event_id: 0,
-        event_name: "ManualSave".to_string(),
-        event_time: 0.0,
-        killer_name: None,
-        victim_name: None,
-        assisters: vec![],
-        priority: 3,
-        timestamp: Instant::now(),
-    };
 
-    // Save clip with new API (returns PathBuf directly)
-    let clip_path = state
-        .recording_manager
-        .read()
-        .await
-        .save_clip(
-            &manual_event,
-            format!("manual_{}", Instant::now().elapsed().as_secs()),
-            3, // priority = 3 (medium priority)
-            seconds as f64,
-        )
+    // Goes through the auto clip manager so the clip is checked for duplicates
+    state
+        .auto_clip_manager
+        .save_manual_clip("manual", seconds as f64, 3)
         .await
-        .map_err(|e| e.to_string())?;
-
-    Ok(clip_path)
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -173,6 +191,19 @@ pub async fn list_audio_devices() -> Result<Vec<crate::recording::audio::AudioDe
     crate::recording::audio::list_audio_devices().map_err(|e| e.to_string())
 }
 
+/// List available webcams for the facecam setting (Windows DirectShow)
+#[tauri::command]
+pub async fn list_video_devices() -> Result<Vec<String>, String> {
+    crate::recording::audio::list_video_devices().map_err(|e| e.to_string())
+}
+
+/// List the displays that can be recorded, primary first
+#[tauri::command]
+pub async fn list_capture_displays(
+) -> Result<Vec<crate::recording::display::CaptureDisplay>, String> {
+    crate::recording::display::list_displays().map_err(|e| e.to_string())
+}
+
 /// Get recording quality info (encoder, bitrate, resolution)
 #[tauri::command]
 pub async fn get_recording_quality_info(
@@ -193,7 +224,146 @@ pub async fn get_recording_quality_info(
         "fps": quality_info.fps,
         "bitrate_mbps": quality_info.bitrate_mbps,
         "audio_enabled": quality_info.audio_enabled,
+        "av1_supported": manager.supports_av1(),
     }))
 }
 
 // Screenshot capture moved to screenshot::commands module
+
+/// List replay buffer sessions left over from a crash that can be salvaged
+#[tauri::command]
+pub async fn get_recoverable_sessions(
+    state: State<'_, AppState>,
+) -> Result<Vec<RecoverableSession>, String> {
+    // FREE tier feature - no authentication required
+    let recordings_dir = state
+        .recording_manager
+        .read()
+        .await
+        .output_dir()
+        .to_path_buf();
+
+    recovery::list_recoverable_sessions(&recordings_dir).map_err(|e| e.to_string())
+}
+
+/// Salvage a crashed session into a clip under the "recovered" game
+#[tauri::command]
+pub async fn recover_session(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<PathBuf, String> {
+    // FREE tier feature - no authentication required
+    let recordings_dir = state
+        .recording_manager
+        .read()
+        .await
+        .output_dir()
+        .to_path_buf();
+
+    let session = recovery::list_recoverable_sessions(&recordings_dir)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|s| s.session_id == session_id)
+        .ok_or_else(|| format!("Recovery session not found: {}", session_id))?;
+
+    let clip_path = recovery::salvage_session(&recordings_dir, &session_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let clip = crate::storage::models::ClipMetadata {
+        file_path: clip_path.to_string_lossy().to_string(),
+        thumbnail_path: None,
+        event_type: crate::storage::models::EventType::Custom("Recovered".to_string()),
+        event_time: 0.0,
+        priority: 1,
+        duration: session.estimated_duration_secs,
+        created_at: session.last_modified,
+    };
+
+    state
+        .storage
+        .save_clip_metadata("recovered", &clip)
+        .map_err(|e| e.to_string())?;
+
+    let thumbnail_job = JobKind::GenerateThumbnail {
+        game_id: "recovered".to_string(),
+        clip_path: clip_path.clone(),
+    };
+    if let Err(e) = state.jobs.enqueue(thumbnail_job, JobPriority::Low).await {
+        tracing::warn!("Failed to queue thumbnail generation: {}", e);
+    }
+
+    Ok(clip_path)
+}
+
+/// Delete a crashed session without salvaging it
+#[tauri::command]
+pub async fn discard_recoverable_session(
+    state: State<'_, AppState>,
+    session_id: String,
+) -> Result<(), String> {
+    // FREE tier feature - no authentication required
+    let recordings_dir = state
+        .recording_manager
+        .read()
+        .await
+        .output_dir()
+        .to_path_buf();
+
+    recovery::discard_session(&recordings_dir, &session_id).map_err(|e| e.to_string())
+}
+
+/// Save highlights from a replay, for games that weren't recorded
+///
+/// With `rofl_path` the replay file is opened in the League client first;
+/// otherwise the replay currently being watched is used. Highlights are
+/// detected for `player_name` (default: the logged-in account). Returns once
+/// every highlight has been played back and saved.
+#[tauri::command]
+pub async fn extract_replay_highlights(
+    state: State<'_, AppState>,
+    rofl_path: Option<String>,
+    player_name: Option<String>,
+) -> Result<ReplayExtraction, String> {
+    // FREE tier feature - no authentication required
+    let metadata = match rofl_path {
+        Some(path) => {
+            let path = security::validate_replay_path(&path).map_err(|e| e.to_string())?;
+            Some(replay::open_rofl(&path).await.map_err(|e| e.to_string())?)
+        }
+        None => None,
+    };
+
+    let player_name = match player_name {
+        Some(name) => name,
+        None => {
+            let mut lcu = LcuClient::new();
+            lcu.connect().await.map_err(|e| e.to_string())?;
+            lcu.current_summoner_name()
+                .await
+                .map_err(|e| e.to_string())?
+        }
+    };
+
+    let game_id = match metadata.as_ref().and_then(|m| m.game_id) {
+        Some(id) => id.to_string(),
+        None => format!("replay_{}", chrono::Utc::now().format("%Y%m%d_%H%M%S")),
+    };
+    let champion = metadata
+        .as_ref()
+        .and_then(|m| m.player(&player_name))
+        .map(|p| p.champion.clone());
+    let patch = metadata.as_ref().and_then(|m| short_patch(&m.game_version));
+
+    let extractor = ReplayExtractor::new(
+        Arc::clone(&state.recording_manager),
+        Arc::clone(&state.storage),
+        Arc::clone(&state.recording_settings),
+    )
+    .map_err(|e| e.to_string())?;
+
+    extractor
+        .extract(&game_id, &player_name, champion, patch)
+        .await
+        .map_err(|e| e.to_string())
+}
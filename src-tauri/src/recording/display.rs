@@ -0,0 +1,242 @@
+//! Displays the replay buffer can capture
+//!
+//! gdigrab's `desktop` input spans the whole virtual screen, so on some
+//! multi-monitor setups every display ends up stitched into one recording.
+//! Capture is limited to a single display instead: the one picked in
+//! settings (`VideoSettings.capture_display`), or the primary one.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+/// A connected monitor, in virtual screen coordinates
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaptureDisplay {
+    /// GDI device name (`\\.\DISPLAY1`), stored in settings
+    pub id: String,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub primary: bool,
+    /// Windows HDR is on for this display
+    pub hdr: bool,
+}
+
+/// List the connected displays, primary first
+#[cfg(target_os = "windows")]
+pub fn list_displays() -> Result<Vec<CaptureDisplay>> {
+    use windows::Win32::Foundation::{BOOL, LPARAM, RECT};
+    use windows::Win32::Graphics::Gdi::{
+        EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR, MONITORINFO, MONITORINFOEXW,
+        MONITORINFOF_PRIMARY,
+    };
+
+    unsafe extern "system" fn collect(
+        monitor: HMONITOR,
+        _hdc: HDC,
+        _rect: *mut RECT,
+        data: LPARAM,
+    ) -> BOOL {
+        let displays = &mut *(data.0 as *mut Vec<CaptureDisplay>);
+
+        let mut info = MONITORINFOEXW::default();
+        info.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+        if GetMonitorInfoW(
+            monitor,
+            &mut info as *mut MONITORINFOEXW as *mut MONITORINFO,
+        )
+        .as_bool()
+        {
+            let rect = info.monitorInfo.rcMonitor;
+            let name_len = info
+                .szDevice
+                .iter()
+                .position(|&c| c == 0)
+                .unwrap_or(info.szDevice.len());
+            displays.push(CaptureDisplay {
+                id: String::from_utf16_lossy(&info.szDevice[..name_len]),
+                x: rect.left,
+                y: rect.top,
+                width: (rect.right - rect.left) as u32,
+                height: (rect.bottom - rect.top) as u32,
+                primary: info.monitorInfo.dwFlags & MONITORINFOF_PRIMARY != 0,
+                hdr: false,
+            });
+        }
+
+        // Keep enumerating
+        true.into()
+    }
+
+    let mut displays: Vec<CaptureDisplay> = Vec::new();
+    let enumerated = unsafe {
+        EnumDisplayMonitors(
+            HDC::default(),
+            None,
+            Some(collect),
+            LPARAM(&mut displays as *mut Vec<CaptureDisplay> as isize),
+        )
+    };
+    if !enumerated.as_bool() {
+        anyhow::bail!("Failed to enumerate displays");
+    }
+
+    let hdr_displays = hdr_display_ids();
+    for display in &mut displays {
+        display.hdr = hdr_displays.contains(&display.id);
+    }
+
+    displays.sort_by_key(|display| !display.primary);
+    tracing::info!("Found {} displays", displays.len());
+    Ok(displays)
+}
+
+/// Device names of the displays running in HDR (PQ / BT.2020 output)
+#[cfg(target_os = "windows")]
+fn hdr_display_ids() -> Vec<String> {
+    use windows::core::Interface;
+    use windows::Win32::Graphics::Dxgi::Common::DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020;
+    use windows::Win32::Graphics::Dxgi::{CreateDXGIFactory1, IDXGIFactory1, IDXGIOutput6};
+
+    let mut ids = Vec::new();
+    let factory = match unsafe { CreateDXGIFactory1::<IDXGIFactory1>() } {
+        Ok(factory) => factory,
+        Err(e) => {
+            tracing::warn!(
+                "Failed to create DXGI factory, assuming SDR displays: {}",
+                e
+            );
+            return ids;
+        }
+    };
+
+    for adapter_index in 0u32.. {
+        let Ok(adapter) = (unsafe { factory.EnumAdapters1(adapter_index) }) else {
+            break;
+        };
+        for output_index in 0u32.. {
+            let Ok(output) = (unsafe { adapter.EnumOutputs(output_index) }) else {
+                break;
+            };
+            // IDXGIOutput6 needs Windows 10 1703, which is also where HDR starts
+            let Ok(desc) = output
+                .cast::<IDXGIOutput6>()
+                .and_then(|output| unsafe { output.GetDesc1() })
+            else {
+                continue;
+            };
+
+            if desc.ColorSpace == DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020 {
+                let name_len = desc
+                    .DeviceName
+                    .iter()
+                    .position(|&c| c == 0)
+                    .unwrap_or(desc.DeviceName.len());
+                ids.push(String::from_utf16_lossy(&desc.DeviceName[..name_len]));
+            }
+        }
+    }
+
+    ids
+}
+
+/// List the connected displays (stub for non-Windows platforms)
+#[cfg(not(target_os = "windows"))]
+pub fn list_displays() -> Result<Vec<CaptureDisplay>> {
+    Ok(Vec::new())
+}
+
+/// The display `id` if it's connected, otherwise the primary display
+pub fn select_display<'a>(
+    displays: &'a [CaptureDisplay],
+    id: Option<&str>,
+) -> Option<&'a CaptureDisplay> {
+    if let Some(id) = id {
+        match displays.iter().find(|display| display.id == id) {
+            Some(display) => return Some(display),
+            None => tracing::warn!("Display {} not connected, capturing the primary one", id),
+        }
+    }
+
+    displays
+        .iter()
+        .find(|display| display.primary)
+        .or(displays.first())
+}
+
+/// gdigrab input capturing `display`, or the whole desktop without one
+pub fn gdigrab_input_args(
+    display: Option<&CaptureDisplay>,
+    fps: u32,
+    draw_mouse: bool,
+) -> Vec<String> {
+    let mut args = vec![
+        "-f".to_string(),
+        "gdigrab".to_string(), // Windows GDI screen capture
+        "-framerate".to_string(),
+        fps.to_string(),
+        "-draw_mouse".to_string(),
+        u8::from(draw_mouse).to_string(),
+    ];
+
+    if let Some(display) = display {
+        args.extend(vec![
+            "-offset_x".to_string(),
+            display.x.to_string(),
+            "-offset_y".to_string(),
+            display.y.to_string(),
+            "-video_size".to_string(),
+            format!("{}x{}", display.width, display.height),
+        ]);
+    }
+
+    args.extend(vec!["-i".to_string(), "desktop".to_string()]);
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn display(id: &str, x: i32, primary: bool) -> CaptureDisplay {
+        CaptureDisplay {
+            id: id.to_string(),
+            x,
+            y: 0,
+            width: 1920,
+            height: 1080,
+            primary,
+            hdr: false,
+        }
+    }
+
+    #[test]
+    fn test_select_display() {
+        let displays = vec![
+            display(r"\\.\DISPLAY2", 0, true),
+            display(r"\\.\DISPLAY1", -1920, false),
+        ];
+
+        let selected = select_display(&displays, Some(r"\\.\DISPLAY1")).unwrap();
+        assert_eq!(selected.x, -1920);
+
+        // Unplugged or never chosen: the primary display
+        let selected = select_display(&displays, Some(r"\\.\DISPLAY3")).unwrap();
+        assert!(selected.primary);
+        assert!(select_display(&displays, None).unwrap().primary);
+        assert!(select_display(&[], None).is_none());
+    }
+
+    #[test]
+    fn test_gdigrab_input_args() {
+        let secondary = display(r"\\.\DISPLAY1", -1920, false);
+        let args = gdigrab_input_args(Some(&secondary), 60, true).join(" ");
+        assert_eq!(
+            args,
+            "-f gdigrab -framerate 60 -draw_mouse 1 -offset_x -1920 -offset_y 0 -video_size 1920x1080 -i desktop"
+        );
+
+        let args = gdigrab_input_args(None, 30, false).join(" ");
+        assert_eq!(args, "-f gdigrab -framerate 30 -draw_mouse 0 -i desktop");
+    }
+}
@@ -1,13 +1,34 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::{Duration, SystemTime};
+use std::time::Duration;
+use tokio::net::TcpStream;
 use tokio::time;
 use tracing::{debug, info};
 
+use crate::settings::models::PerformanceSettings;
+
 /// Live Client Data API endpoint
-const LIVE_CLIENT_API: &str = "https://127.0.0.1:2999/liveclientdata";
+pub(super) const LIVE_CLIENT_API: &str = "https://127.0.0.1:2999/liveclientdata";
+
+/// Port the game serves the Live Client Data API on
+const LIVE_CLIENT_PORT: u16 = 2999;
+
+/// Shortest allowed polling interval, whatever the settings say
+const MIN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Kill streak at which the active player's clips get boosted (killing spree)
+const KILL_STREAK_BOOST: u32 = 3;
+
+/// Victim kill streak that makes a kill a shutdown
+const SHUTDOWN_STREAK: u32 = 3;
+
+/// Estimated team gold deficit that makes a play a comeback moment
+pub(super) const COMEBACK_GOLD_DEFICIT: i32 = 3000;
 
 /// Event types that trigger automatic recording
 #[derive(Debug, Clone, PartialEq)]
@@ -21,6 +42,7 @@ pub enum EventTrigger {
     Ace,
     Steal,      // Dragon/Baron steal
     ClutchPlay, // 1v2+, low HP survival
+    Death(u8),  // Active player's death, with its configured priority
 }
 
 impl EventTrigger {
@@ -39,10 +61,21 @@ impl EventTrigger {
             EventTrigger::Ace => 4,
             EventTrigger::Steal => 4,
             EventTrigger::ClutchPlay => 3,
+            EventTrigger::Death(priority) => (*priority).clamp(1, 5),
             _ => 1,
         }
     }
 
+    /// Priority including the game state around the event
+    ///
+    /// Deaths keep their configured priority.
+    pub fn boosted_priority(&self, factors: &ClipFactors) -> u8 {
+        match self {
+            EventTrigger::Death(_) => self.priority(),
+            _ => (self.priority() + factors.priority_boost()).min(5),
+        }
+    }
+
     /// Get recommended clip duration before event (seconds)
     pub fn pre_duration(&self) -> u32 {
         match self {
@@ -64,11 +97,198 @@ impl EventTrigger {
     }
 }
 
+/// Game state around an event that makes its clip more interesting
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClipFactors {
+    /// Active player's kills since their last death
+    pub kill_streak: u32,
+    /// Estimated gold lead of the active player's team (negative when behind)
+    pub gold_lead: i32,
+    /// The active player ended an enemy's kill streak
+    pub shutdown: bool,
+    /// Champion kills in the clip
+    pub kills: u32,
+    /// Of those, kills the active player got or assisted
+    pub kills_involved: u32,
+    /// Active player's health (0-100%) when last polled
+    pub health_percent: Option<u8>,
+}
+
+impl ClipFactors {
+    pub fn is_comeback(&self) -> bool {
+        self.gold_lead <= -COMEBACK_GOLD_DEFICIT
+    }
+
+    /// Priority added for streaks, shutdowns and comeback moments
+    pub fn priority_boost(&self) -> u8 {
+        (self.kill_streak >= KILL_STREAK_BOOST) as u8
+            + self.shutdown as u8
+            + self.is_comeback() as u8
+    }
+
+    /// Clip tags describing the factors ("kill_streak_5", "shutdown", "comeback")
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags = Vec::new();
+        if self.kill_streak >= KILL_STREAK_BOOST {
+            tags.push(format!("kill_streak_{}", self.kill_streak));
+        }
+        if self.shutdown {
+            tags.push("shutdown".to_string());
+        }
+        if self.is_comeback() {
+            tags.push("comeback".to_string());
+        }
+        tags
+    }
+
+    /// Factors of a clip covering both events
+    pub fn merge(&self, other: &Self) -> Self {
+        Self {
+            kill_streak: self.kill_streak.max(other.kill_streak),
+            gold_lead: self.gold_lead.min(other.gold_lead),
+            shutdown: self.shutdown || other.shutdown,
+            kills: self.kills + other.kills,
+            kills_involved: self.kills_involved + other.kills_involved,
+            health_percent: match (self.health_percent, other.health_percent) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            },
+        }
+    }
+}
+
+/// Per-game running stats behind [`ClipFactors`]
+#[derive(Debug, Default)]
+struct SessionTracker {
+    /// Kills since last death, by player game name
+    streaks: HashMap<String, u32>,
+    gold_lead: i32,
+    health_percent: Option<u8>,
+    last_game_time: f32,
+}
+
+impl SessionTracker {
+    /// Refresh team stats, starting over when a new game begins
+    fn update_game(&mut self, data: &AllGameData, player_name: &str) {
+        if data.game_data.game_time < self.last_game_time {
+            *self = Self::default();
+        }
+        self.last_game_time = data.game_data.game_time;
+        self.gold_lead = estimated_gold_lead(&data.all_players, player_name).unwrap_or(0);
+        self.health_percent = data
+            .active_player
+            .as_ref()
+            .and_then(|player| player.champion_stats.as_ref())
+            .and_then(ChampionStats::health_percent);
+    }
+
+    /// Update kill streaks with `event` and return its factors
+    fn record(&mut self, event: &GameEvent, player_name: &str) -> ClipFactors {
+        let mut shutdown = false;
+        let mut kills = 0;
+        let mut kills_involved = 0;
+
+        if event.event_name == "ChampionKill" {
+            let by_player = event
+                .killer_name
+                .as_deref()
+                .is_some_and(|killer| is_same_player(killer, player_name));
+            let assisted = event
+                .assisters
+                .iter()
+                .flatten()
+                .any(|assister| is_same_player(assister, player_name));
+            kills = 1;
+            kills_involved = (by_player || assisted) as u32;
+
+            if let Some(victim) = &event.victim_name {
+                let victim_streak = self.streaks.remove(&game_name(victim)).unwrap_or(0);
+                shutdown = by_player && victim_streak >= SHUTDOWN_STREAK;
+            }
+            if let Some(killer) = &event.killer_name {
+                *self.streaks.entry(game_name(killer)).or_default() += 1;
+            }
+        }
+
+        ClipFactors {
+            kill_streak: self
+                .streaks
+                .get(&game_name(player_name))
+                .copied()
+                .unwrap_or(0),
+            gold_lead: self.gold_lead,
+            shutdown,
+            kills,
+            kills_involved,
+            health_percent: self.health_percent,
+        }
+    }
+}
+
+/// Gold lead of the active player's team, estimated from scores
+///
+/// The Live Client API only reports the active player's own gold, so each
+/// player is valued by kills, assists and creep score.
+fn estimated_gold_lead(players: &[Player], player_name: &str) -> Option<i32> {
+    let team = &players
+        .iter()
+        .find(|p| is_same_player(&p.summoner_name, player_name))?
+        .team;
+
+    let lead = players
+        .iter()
+        .map(|p| {
+            let gold =
+                (p.scores.kills * 300 + p.scores.assists * 150 + p.scores.creep_score * 21) as i32;
+            if &p.team == team {
+                gold
+            } else {
+                -gold
+            }
+        })
+        .sum();
+
+    Some(lead)
+}
+
+/// Which champion kills involving the active player trigger clips
+///
+/// The player's own kills always do.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PlayerInvolvement {
+    pub assists: bool,
+    pub deaths: bool,
+    /// Priority given to [`EventTrigger::Death`] (1-5)
+    pub death_priority: u8,
+}
+
+/// Whether an event name refers to the active player
+///
+/// `activeplayername` returns the full Riot ID ("Name#TAG") while event
+/// names may omit the tag line.
+pub(super) fn is_same_player(name: &str, player_name: &str) -> bool {
+    !player_name.is_empty() && game_name(name) == game_name(player_name)
+}
+
+/// Riot ID without the tag line, lowercased
+fn game_name(name: &str) -> String {
+    name.split('#')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase()
+}
+
 /// Live Client API response structures
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct AllGameData {
-    #[serde(rename = "activePlayer")]
-    pub active_player: ActivePlayer,
+    /// Missing (or an error object) when spectating or watching a replay
+    #[serde(
+        rename = "activePlayer",
+        default,
+        deserialize_with = "lenient_active_player"
+    )]
+    pub active_player: Option<ActivePlayer>,
     #[serde(rename = "allPlayers")]
     pub all_players: Vec<Player>,
     pub events: Events,
@@ -76,6 +296,27 @@ pub struct AllGameData {
     pub game_data: GameData,
 }
 
+impl AllGameData {
+    /// Whether this is a spectated game or a replay rather than one the user
+    /// is playing
+    pub fn is_spectator(&self) -> bool {
+        self.active_player.is_none()
+    }
+}
+
+/// Spectator and replay sessions return
+/// `{"error": "Spectator mode doesn't currently support this feature"}`
+/// instead of the active player
+fn lenient_active_player<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<ActivePlayer>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(value.and_then(|value| serde_json::from_value(value).ok()))
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct ActivePlayer {
     #[serde(rename = "championName")]
@@ -85,6 +326,23 @@ pub struct ActivePlayer {
     pub level: u32,
     #[serde(rename = "currentGold")]
     pub current_gold: f32,
+    #[serde(rename = "championStats", default)]
+    pub champion_stats: Option<ChampionStats>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ChampionStats {
+    #[serde(rename = "currentHealth")]
+    pub current_health: f32,
+    #[serde(rename = "maxHealth")]
+    pub max_health: f32,
+}
+
+impl ChampionStats {
+    pub fn health_percent(&self) -> Option<u8> {
+        (self.max_health > 0.0)
+            .then(|| (self.current_health / self.max_health * 100.0).clamp(0.0, 100.0) as u8)
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -143,18 +401,158 @@ pub struct GameData {
     pub map_number: u32,
 }
 
+/// Live Client API health as shown in the UI
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LiveClientStatus {
+    /// Auto-capture is polling the API
+    pub monitoring: bool,
+    /// The last request got game data
+    pub connected: bool,
+    /// Something listens on port 2999 (false while no game is running)
+    pub port_reachable: bool,
+    /// When the last game event was received
+    pub last_event_at: Option<DateTime<Utc>>,
+    /// When game data was last received
+    pub last_connected_at: Option<DateTime<Utc>>,
+    pub last_checked_at: Option<DateTime<Utc>>,
+    /// Why the last request failed
+    pub last_error: Option<String>,
+}
+
+/// Shared Live Client API health, updated by the monitor and [`Self::probe`]
+#[derive(Default)]
+pub struct LiveClientHealth {
+    status: RwLock<LiveClientStatus>,
+}
+
+impl LiveClientHealth {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn status(&self) -> LiveClientStatus {
+        self.status.read().clone()
+    }
+
+    pub fn set_monitoring(&self, monitoring: bool) {
+        self.status.write().monitoring = monitoring;
+    }
+
+    /// Check port reachability, and the API itself when nothing else is
+    /// polling it
+    pub async fn probe(&self, client: &Client) {
+        let port_reachable = time::timeout(
+            Duration::from_secs(1),
+            TcpStream::connect(("127.0.0.1", LIVE_CLIENT_PORT)),
+        )
+        .await
+        .is_ok_and(|connected| connected.is_ok());
+
+        let monitoring = {
+            let mut status = self.status.write();
+            status.port_reachable = port_reachable;
+            status.last_checked_at = Some(Utc::now());
+            status.monitoring
+        };
+
+        if !port_reachable {
+            self.record_failure("Live Client API port is closed (no game running)");
+        } else if !monitoring {
+            let url = format!("{}/gamestats", LIVE_CLIENT_API);
+            match client.get(&url).send().await {
+                Ok(response) if response.status().is_success() => self.record_success(),
+                Ok(response) => {
+                    self.record_failure(format!("API returned status: {}", response.status()))
+                }
+                Err(e) => self.record_failure(format!("{:#}", e)),
+            }
+        }
+    }
+
+    fn record_success(&self) {
+        let now = Utc::now();
+        let mut status = self.status.write();
+        status.connected = true;
+        status.port_reachable = true;
+        status.last_connected_at = Some(now);
+        status.last_checked_at = Some(now);
+        status.last_error = None;
+    }
+
+    fn record_failure(&self, error: impl Into<String>) {
+        let mut status = self.status.write();
+        status.connected = false;
+        status.last_checked_at = Some(Utc::now());
+        status.last_error = Some(error.into());
+    }
+
+    fn record_event(&self) {
+        self.status.write().last_event_at = Some(Utc::now());
+    }
+}
+
+/// How often the Live Client API is polled
+///
+/// Fast while a game answers; once requests fail (no game running) the
+/// interval starts at `idle` and doubles with every further failure up to
+/// `max_backoff`, so the monitor costs next to nothing between games.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PollIntervals {
+    pub in_game: Duration,
+    pub idle: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for PollIntervals {
+    fn default() -> Self {
+        Self::from_settings(&PerformanceSettings::default())
+    }
+}
+
+impl PollIntervals {
+    pub fn from_settings(settings: &PerformanceSettings) -> Self {
+        let in_game =
+            Duration::from_millis(settings.live_client_poll_in_game_ms).max(MIN_POLL_INTERVAL);
+        let idle = Duration::from_millis(settings.live_client_poll_idle_ms).max(in_game);
+        let max_backoff = Duration::from_millis(settings.live_client_max_backoff_ms).max(idle);
+        Self {
+            in_game,
+            idle,
+            max_backoff,
+        }
+    }
+
+    /// Delay before the next request after `failures` failed ones in a row
+    pub fn next(&self, failures: u32) -> Duration {
+        match failures {
+            0 => self.in_game,
+            n => self
+                .idle
+                .saturating_mul(1 << (n - 1).min(16))
+                .min(self.max_backoff),
+        }
+    }
+}
+
 /// Monitor for Live Client events
 pub struct LiveClientMonitor {
     client: Client,
     last_event_id: Arc<tokio::sync::Mutex<u32>>,
     player_name: Option<String>,
     recent_kills: Arc<tokio::sync::Mutex<Vec<KillRecord>>>,
+    session: Arc<tokio::sync::Mutex<SessionTracker>>,
+    involvement: PlayerInvolvement,
+    /// Clip spectated games and replays too
+    record_spectator_games: bool,
+    spectating: bool,
+    health: Arc<LiveClientHealth>,
+    poll: PollIntervals,
 }
 
 #[derive(Debug, Clone)]
 struct KillRecord {
     killer: String,
-    timestamp: SystemTime,
+    game_time: f32,
 }
 
 impl LiveClientMonitor {
@@ -170,27 +568,97 @@ impl LiveClientMonitor {
             last_event_id: Arc::new(tokio::sync::Mutex::new(0)),
             player_name: None,
             recent_kills: Arc::new(tokio::sync::Mutex::new(Vec::new())),
+            session: Arc::new(tokio::sync::Mutex::new(SessionTracker::default())),
+            involvement: PlayerInvolvement::default(),
+            record_spectator_games: false,
+            spectating: false,
+            health: Arc::new(LiveClientHealth::new()),
+            poll: PollIntervals::default(),
         })
     }
 
+    /// Also report the active player's assists and/or deaths
+    pub fn with_involvement(mut self, involvement: PlayerInvolvement) -> Self {
+        self.involvement = involvement;
+        self
+    }
+
+    /// Detect events for `name` instead of asking the game who is playing
+    pub fn with_player_name(mut self, name: impl Into<String>) -> Self {
+        self.player_name = Some(name.into());
+        self
+    }
+
+    /// Also report events from spectated games and replays
+    ///
+    /// There is no active player there, so every kill and objective counts
+    /// as if the killer were the player.
+    pub fn with_spectator_games(mut self, record: bool) -> Self {
+        self.record_spectator_games = record;
+        self
+    }
+
+    /// Report connection health to `health`
+    pub fn with_health(mut self, health: Arc<LiveClientHealth>) -> Self {
+        self.health = health;
+        self
+    }
+
+    /// Poll at `poll` instead of the default intervals
+    pub fn with_poll_intervals(mut self, poll: PollIntervals) -> Self {
+        self.poll = poll;
+        self
+    }
+
     /// Start monitoring for events
     pub async fn start_monitoring<F>(&mut self, mut on_event: F) -> Result<()>
     where
-        F: FnMut(EventTrigger, GameEvent) + Send + 'static,
+        F: FnMut(EventTrigger, GameEvent, ClipFactors) + Send + 'static,
     {
         info!("Starting Live Client monitor...");
 
-        let mut interval = time::interval(Duration::from_millis(500)); // Check 2x per second
+        let mut failures: u32 = 0;
 
         loop {
-            interval.tick().await;
+            time::sleep(self.poll.next(failures)).await;
 
             match self.fetch_game_data().await {
                 Ok(data) => {
-                    // Store player name on first fetch
-                    if self.player_name.is_none() {
-                        self.player_name = Some(data.active_player.summoner_name.clone());
-                        info!("Monitoring player: {}", data.active_player.summoner_name);
+                    if failures > 0 {
+                        debug!(
+                            "Live Client available, polling every {:?}",
+                            self.poll.in_game
+                        );
+                    }
+                    failures = 0;
+                    self.health.record_success();
+                    if data.is_spectator() {
+                        if !self.spectating {
+                            self.spectating = true;
+                            if self.record_spectator_games {
+                                info!("Spectator or replay session detected, clipping all players");
+                            } else {
+                                info!(
+                                    "Spectator or replay session detected, skipping auto-clipping"
+                                );
+                            }
+                        }
+                        if !self.record_spectator_games {
+                            continue;
+                        }
+                    } else if self.player_name.is_none() {
+                        // Store player name on first fetch
+                        let player_name = match self.fetch_active_player_name().await {
+                            Ok(name) => Some(name),
+                            Err(e) => {
+                                debug!("Falling back to active player summoner name: {}", e);
+                                data.active_player.as_ref().map(|p| p.summoner_name.clone())
+                            }
+                        };
+                        if let Some(name) = player_name {
+                            info!("Monitoring player: {}", name);
+                            self.player_name = Some(name);
+                        }
                     }
 
                     // Process new events
@@ -199,11 +667,18 @@ impl LiveClientMonitor {
                 Err(e) => {
                     // Game might not be running, this is normal
                     debug!("Live Client not available: {}", e);
+                    self.health.record_failure(format!("{:#}", e));
+                    failures = failures.saturating_add(1);
                 }
             }
         }
     }
 
+    /// Current game time in seconds
+    pub async fn game_time(&self) -> Result<f64> {
+        Ok(self.fetch_game_data().await?.game_data.game_time as f64)
+    }
+
     /// Fetch current game data
     async fn fetch_game_data(&self) -> Result<AllGameData> {
         let url = format!("{}/allgamedata", LIVE_CLIENT_API);
@@ -230,13 +705,44 @@ impl LiveClientMonitor {
         Ok(data)
     }
 
+    /// Riot ID of the player whose client this is
+    async fn fetch_active_player_name(&self) -> Result<String> {
+        let url = format!("{}/activeplayername", LIVE_CLIENT_API);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .context("Failed to connect to Live Client API")?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "API returned status: {}",
+                response.status()
+            ));
+        }
+
+        let name = response
+            .json::<String>()
+            .await
+            .context("Failed to parse active player name")?;
+
+        if name.is_empty() {
+            return Err(anyhow::anyhow!("Active player name is empty"));
+        }
+
+        Ok(name)
+    }
+
     /// Process events and detect triggers
     async fn process_events<F>(&self, data: AllGameData, on_event: &mut F) -> Result<()>
     where
-        F: FnMut(EventTrigger, GameEvent),
+        F: FnMut(EventTrigger, GameEvent, ClipFactors),
     {
         let mut last_id = self.last_event_id.lock().await;
-        let player_name = self.player_name.as_ref().unwrap();
+        let mut session = self.session.lock().await;
+        session.update_game(&data, self.player_name.as_deref().unwrap_or_default());
 
         for event in &data.events.events {
             // Skip already processed events
@@ -246,17 +752,29 @@ impl LiveClientMonitor {
 
             debug!("New event: {} at {}s", event.event_name, event.event_time);
 
+            // Without an active player (spectating), each event is seen from
+            // its killer's side
+            let player_name = self
+                .player_name
+                .as_deref()
+                .or(event.killer_name.as_deref())
+                .unwrap_or_default();
+
+            let factors = session.record(event, player_name);
+
             // Detect event triggers
             if let Some(trigger) = self.detect_trigger(event, player_name).await {
                 info!(
-                    "Event trigger detected: {:?} (priority: {})",
+                    "Event trigger detected: {:?} (priority: {}, factors: {:?})",
                     trigger,
-                    trigger.priority()
+                    trigger.boosted_priority(&factors),
+                    factors
                 );
-                on_event(trigger, event.clone());
+                on_event(trigger, event.clone(), factors);
             }
 
             *last_id = event.event_id;
+            self.health.record_event();
         }
 
         Ok(())
@@ -264,93 +782,71 @@ impl LiveClientMonitor {
 
     /// Detect if an event should trigger recording
     async fn detect_trigger(&self, event: &GameEvent, player_name: &str) -> Option<EventTrigger> {
+        let by_player = event
+            .killer_name
+            .as_deref()
+            .is_some_and(|killer| is_same_player(killer, player_name));
+
         match event.event_name.as_str() {
             "ChampionKill" => {
-                if let Some(killer) = &event.killer_name {
-                    if killer == player_name {
-                        // Player got a kill
-                        let multikill = self.check_multikill(killer).await;
-
-                        if multikill >= 2 {
-                            Some(EventTrigger::Multikill(multikill))
-                        } else {
-                            Some(EventTrigger::ChampionKill)
-                        }
-                    } else if event.victim_name.as_deref() == Some(player_name) {
-                        // Player died - might want to save if it was a close fight
-                        None // TODO: Detect clutch plays
-                    } else if let Some(assisters) = &event.assisters {
-                        if assisters.contains(&player_name.to_string()) {
-                            // Player got an assist
-                            Some(EventTrigger::ChampionKill)
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            }
-            "DragonKill" => {
-                if event.killer_name.as_deref() == Some(player_name) {
-                    Some(EventTrigger::DragonKill)
-                } else {
-                    None
-                }
-            }
-            "BaronKill" => {
-                if event.killer_name.as_deref() == Some(player_name) {
-                    Some(EventTrigger::BaronKill)
-                } else {
-                    None
-                }
-            }
-            "TurretKilled" => {
-                if let Some(killer) = &event.killer_name {
-                    if killer == player_name {
-                        Some(EventTrigger::TurretKill)
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            }
-            "InhibKilled" => {
-                if let Some(killer) = &event.killer_name {
-                    if killer == player_name {
-                        Some(EventTrigger::InhibitorKill)
+                if by_player {
+                    // Player got a kill
+                    let killer = event.killer_name.as_deref().unwrap_or_default();
+                    let multikill = self.check_multikill(killer, event.event_time).await;
+
+                    if multikill >= 2 {
+                        Some(EventTrigger::Multikill(multikill))
                     } else {
-                        None
+                        Some(EventTrigger::ChampionKill)
                     }
+                } else if event
+                    .victim_name
+                    .as_deref()
+                    .is_some_and(|victim| is_same_player(victim, player_name))
+                {
+                    // Player died
+                    self.involvement
+                        .deaths
+                        .then_some(EventTrigger::Death(self.involvement.death_priority))
+                } else if event
+                    .assisters
+                    .iter()
+                    .flatten()
+                    .any(|assister| is_same_player(assister, player_name))
+                {
+                    // Player got an assist
+                    self.involvement
+                        .assists
+                        .then_some(EventTrigger::ChampionKill)
                 } else {
+                    // Kill between other players
                     None
                 }
             }
+            "DragonKill" => by_player.then_some(EventTrigger::DragonKill),
+            "BaronKill" => by_player.then_some(EventTrigger::BaronKill),
+            "TurretKilled" => by_player.then_some(EventTrigger::TurretKill),
+            "InhibKilled" => by_player.then_some(EventTrigger::InhibitorKill),
             "Ace" => Some(EventTrigger::Ace),
             _ => None,
         }
     }
 
     /// Check if recent kills form a multikill
-    async fn check_multikill(&self, killer: &str) -> u8 {
+    ///
+    /// Uses game time, so events fetched in a batch (first poll, replays)
+    /// aren't all counted as one multikill.
+    async fn check_multikill(&self, killer: &str, game_time: f32) -> u8 {
         let mut kills = self.recent_kills.lock().await;
-        let now = SystemTime::now();
 
         // Add new kill
         kills.push(KillRecord {
             killer: killer.to_string(),
-            timestamp: now,
+            game_time,
         });
 
         // Remove old kills (>10 seconds)
-        kills.retain(|k| {
-            now.duration_since(k.timestamp)
-                .unwrap_or(Duration::from_secs(100))
-                < Duration::from_secs(10)
-        });
+        kills.retain(|k| (game_time - k.game_time).abs() < 10.0);
 
         // Count kills by this player in the window
         let kill_count = kills.iter().filter(|k| k.killer == killer).count() as u8;
@@ -365,6 +861,27 @@ impl LiveClientMonitor {
         }
     }
 
+    /// Detect triggers in an already complete event list (e.g. a replay)
+    ///
+    /// Uses the player set with [`Self::with_player_name`].
+    pub async fn detect_events(
+        &self,
+        events: &[GameEvent],
+    ) -> Vec<(EventTrigger, GameEvent, ClipFactors)> {
+        let player_name = self.player_name.as_deref().unwrap_or_default();
+        let mut session = self.session.lock().await;
+        let mut detected = Vec::new();
+
+        for event in events {
+            let factors = session.record(event, player_name);
+            if let Some(trigger) = self.detect_trigger(event, player_name).await {
+                detected.push((trigger, event.clone(), factors));
+            }
+        }
+
+        detected
+    }
+
     /// Check if Live Client API is available
     pub async fn is_available(&self) -> bool {
         self.fetch_game_data().await.is_ok()
@@ -382,6 +899,8 @@ mod tests {
         assert_eq!(EventTrigger::Multikill(5).priority(), 5);
         assert_eq!(EventTrigger::BaronKill.priority(), 3);
         assert_eq!(EventTrigger::Ace.priority(), 4);
+        assert_eq!(EventTrigger::Death(3).priority(), 3);
+        assert_eq!(EventTrigger::Death(0).priority(), 1);
     }
 
     #[test]
@@ -395,6 +914,223 @@ mod tests {
         assert_eq!(trigger.post_duration(), 3);
     }
 
+    fn game_data_json(active_player: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "activePlayer": active_player,
+            "allPlayers": [],
+            "events": { "Events": [] },
+            "gameData": {
+                "gameMode": "CLASSIC",
+                "gameTime": 120.5,
+                "mapName": "Map11",
+                "mapNumber": 11
+            }
+        })
+    }
+
+    #[test]
+    fn test_spectator_detection() {
+        let playing: AllGameData = serde_json::from_value(game_data_json(serde_json::json!({
+            "championName": "Ahri",
+            "summonerName": "Player#KR1",
+            "level": 6,
+            "currentGold": 1250.0,
+            "championStats": { "currentHealth": 300.0, "maxHealth": 1200.0 }
+        })))
+        .unwrap();
+        assert!(!playing.is_spectator());
+        let stats = playing.active_player.unwrap().champion_stats.unwrap();
+        assert_eq!(stats.health_percent(), Some(25));
+
+        let spectating: AllGameData = serde_json::from_value(game_data_json(serde_json::json!({
+            "error": "Spectator mode doesn't currently support this feature"
+        })))
+        .unwrap();
+        assert!(spectating.is_spectator());
+
+        let mut replay = game_data_json(serde_json::Value::Null);
+        replay.as_object_mut().unwrap().remove("activePlayer");
+        let replay: AllGameData = serde_json::from_value(replay).unwrap();
+        assert!(replay.is_spectator());
+    }
+
+    #[test]
+    fn test_is_same_player() {
+        assert!(is_same_player("Faker", "Faker#KR1"));
+        assert!(is_same_player("faker#kr1", "Faker#KR1"));
+        assert!(!is_same_player("Faker2", "Faker#KR1"));
+        assert!(!is_same_player("", ""));
+    }
+
+    fn kill_event(killer: &str, victim: &str, assisters: &[&str]) -> GameEvent {
+        GameEvent {
+            event_id: 1,
+            event_name: "ChampionKill".to_string(),
+            event_time: 300.0,
+            killer_name: Some(killer.to_string()),
+            victim_name: Some(victim.to_string()),
+            assisters: Some(assisters.iter().map(|a| a.to_string()).collect()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_detect_trigger_player_involvement() {
+        let player = "Player#KR1";
+        let monitor = LiveClientMonitor::new().unwrap();
+
+        // Kills only by default
+        let kill = kill_event("Player", "Enemy", &[]);
+        assert_eq!(
+            monitor.detect_trigger(&kill, player).await,
+            Some(EventTrigger::ChampionKill)
+        );
+        let assist = kill_event("Ally", "Enemy", &["Player"]);
+        assert_eq!(monitor.detect_trigger(&assist, player).await, None);
+        let death = kill_event("Enemy", "Player", &[]);
+        assert_eq!(monitor.detect_trigger(&death, player).await, None);
+        let other = kill_event("Ally", "Enemy", &["Ally2"]);
+        assert_eq!(monitor.detect_trigger(&other, player).await, None);
+
+        let monitor = LiveClientMonitor::new()
+            .unwrap()
+            .with_involvement(PlayerInvolvement {
+                assists: true,
+                deaths: true,
+                death_priority: 3,
+            });
+        assert!(monitor.detect_trigger(&assist, player).await.is_some());
+        assert_eq!(
+            monitor.detect_trigger(&death, player).await,
+            Some(EventTrigger::Death(3))
+        );
+        assert_eq!(monitor.detect_trigger(&other, player).await, None);
+    }
+
+    fn player(name: &str, team: &str, kills: u32, creep_score: u32) -> Player {
+        Player {
+            champion_name: "Ahri".to_string(),
+            summoner_name: name.to_string(),
+            team: team.to_string(),
+            level: 11,
+            scores: Scores {
+                kills,
+                deaths: 0,
+                assists: 0,
+                creep_score,
+            },
+            is_dead: false,
+        }
+    }
+
+    #[test]
+    fn test_estimated_gold_lead() {
+        let players = vec![
+            player("Player#KR1", "ORDER", 1, 100),
+            player("Ally", "ORDER", 0, 50),
+            player("Enemy", "CHAOS", 10, 200),
+        ];
+
+        // Own team 300 + 3150, enemy 3000 + 4200
+        assert_eq!(estimated_gold_lead(&players, "Player#KR1"), Some(-3750));
+        assert_eq!(estimated_gold_lead(&players, "Spectator"), None);
+    }
+
+    #[test]
+    fn test_session_tracker_streaks_and_shutdowns() {
+        let mut session = SessionTracker::default();
+        let player = "Player#KR1";
+
+        for _ in 0..3 {
+            session.record(&kill_event("Enemy", "Ally", &[]), player);
+        }
+        let factors = session.record(&kill_event("Player", "Enemy", &[]), player);
+        assert!(factors.shutdown);
+        assert_eq!(factors.kill_streak, 1);
+
+        session.record(&kill_event("Player", "Enemy2", &[]), player);
+        let factors = session.record(&kill_event("Player", "Enemy3", &[]), player);
+        assert_eq!(factors.kill_streak, 3);
+        assert!(!factors.shutdown);
+        assert_eq!(factors.tags(), vec!["kill_streak_3"]);
+        assert_eq!(
+            EventTrigger::ChampionKill.boosted_priority(&factors),
+            EventTrigger::ChampionKill.priority() + 1
+        );
+
+        // Assists count towards kill participation
+        let factors = session.record(&kill_event("Ally", "Enemy4", &["Player"]), player);
+        assert_eq!((factors.kills, factors.kills_involved), (1, 1));
+        let factors = session.record(&kill_event("Ally", "Enemy5", &[]), player);
+        assert_eq!((factors.kills, factors.kills_involved), (1, 0));
+
+        // Dying resets the streak
+        let factors = session.record(&kill_event("Enemy", "Player", &[]), player);
+        assert_eq!(factors.kill_streak, 0);
+    }
+
+    #[test]
+    fn test_clip_factors_boost() {
+        let factors = ClipFactors {
+            kill_streak: 5,
+            gold_lead: -5000,
+            shutdown: true,
+            ..Default::default()
+        };
+        assert_eq!(factors.priority_boost(), 3);
+        assert_eq!(
+            factors.tags(),
+            vec!["kill_streak_5", "shutdown", "comeback"]
+        );
+        assert_eq!(EventTrigger::Multikill(4).boosted_priority(&factors), 5);
+        assert_eq!(EventTrigger::Death(2).boosted_priority(&factors), 2);
+    }
+
+    #[test]
+    fn test_live_client_health() {
+        let health = LiveClientHealth::new();
+        assert!(!health.status().connected);
+
+        health.record_success();
+        health.record_event();
+        let status = health.status();
+        assert!(status.connected && status.port_reachable);
+        assert!(status.last_event_at.is_some());
+
+        // A lost connection keeps the last event time for the UI
+        health.record_failure("Failed to connect to Live Client API");
+        let status = health.status();
+        assert!(!status.connected);
+        assert!(status.last_event_at.is_some());
+        assert!(status.last_error.unwrap().contains("Failed to connect"));
+    }
+
+    #[test]
+    fn test_poll_intervals_back_off() {
+        let poll = PollIntervals::from_settings(&PerformanceSettings {
+            live_client_poll_in_game_ms: 500,
+            live_client_poll_idle_ms: 2_000,
+            live_client_max_backoff_ms: 10_000,
+            ..PerformanceSettings::default()
+        });
+
+        assert_eq!(poll.next(0), Duration::from_millis(500));
+        assert_eq!(poll.next(1), Duration::from_secs(2));
+        assert_eq!(poll.next(2), Duration::from_secs(4));
+        assert_eq!(poll.next(3), Duration::from_secs(8));
+        assert_eq!(poll.next(4), Duration::from_secs(10));
+        assert_eq!(poll.next(u32::MAX), Duration::from_secs(10));
+
+        // Nonsense settings can't make it spin
+        let poll = PollIntervals::from_settings(&PerformanceSettings {
+            live_client_poll_in_game_ms: 0,
+            live_client_poll_idle_ms: 0,
+            live_client_max_backoff_ms: 0,
+            ..PerformanceSettings::default()
+        });
+        assert_eq!(poll.next(0), MIN_POLL_INTERVAL);
+        assert_eq!(poll.next(5), MIN_POLL_INTERVAL);
+    }
+
     #[tokio::test]
     async fn test_live_client_creation() {
         let monitor = LiveClientMonitor::new();
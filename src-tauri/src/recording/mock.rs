@@ -0,0 +1,289 @@
+//! Recorder that fakes capture, for tests and demos
+//!
+//! Writes placeholder segment files while "buffering" and produces clip
+//! files without screen capture or FFmpeg, so the AutoClipManager → Storage
+//! → AutoComposer pipeline can run on CI machines and the UI can be shown
+//! without playing a game.
+//!
+//! Selected in place of the built-in recorder by setting
+//! [`MOCK_RECORDER_ENV`]. If its value is the path of a video file, every
+//! clip is a copy of that video (needed for thumbnails and auto-edits);
+//! otherwise clips are small placeholder files.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use parking_lot::{Mutex, RwLock};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock as TokioRwLock;
+use tokio_util::sync::CancellationToken;
+
+use super::backend::Recorder;
+use super::{GameEvent, RecordingStats, RecordingStatus};
+use crate::events::{AppEvent, ClipSaved, EventBus, RecordingStatusChanged};
+
+/// Set (to anything but `0`) to record with [`MockRecorder`]
+pub const MOCK_RECORDER_ENV: &str = "LOLSHORTS_MOCK_RECORDER";
+
+/// Segments kept in the fake buffer (like the built-in 6 × 10s)
+const BUFFER_SEGMENTS: usize = 6;
+
+const DEFAULT_SEGMENT_INTERVAL: Duration = Duration::from_secs(10);
+
+pub struct MockRecorder {
+    status: Arc<TokioRwLock<RecordingStatus>>,
+    stats: Arc<RwLock<RecordingStats>>,
+    /// Segments are written to `output_dir/mock_segments`
+    output_dir: PathBuf,
+    clips_dir: PathBuf,
+    /// Copied as every clip when set
+    sample_clip: Option<PathBuf>,
+    segment_interval: Duration,
+    /// Stops the segment writer
+    buffer_task: Mutex<Option<CancellationToken>>,
+    events: Arc<EventBus>,
+}
+
+impl MockRecorder {
+    pub fn new(output_dir: PathBuf, clips_dir: PathBuf) -> Self {
+        Self {
+            status: Arc::new(TokioRwLock::new(RecordingStatus::Idle)),
+            stats: Arc::new(RwLock::new(RecordingStats::default())),
+            output_dir,
+            clips_dir,
+            sample_clip: None,
+            segment_interval: DEFAULT_SEGMENT_INTERVAL,
+            buffer_task: Mutex::new(None),
+            events: Arc::new(EventBus::new()),
+        }
+    }
+
+    /// A mock recorder if [`MOCK_RECORDER_ENV`] is set
+    pub fn from_env(output_dir: PathBuf, clips_dir: PathBuf) -> Option<Self> {
+        let value = std::env::var(MOCK_RECORDER_ENV).ok()?;
+        if value.is_empty() || value == "0" {
+            return None;
+        }
+
+        let recorder = Self::new(output_dir, clips_dir);
+        let sample = PathBuf::from(&value);
+        Some(if sample.is_file() {
+            recorder.with_sample_clip(sample)
+        } else {
+            recorder
+        })
+    }
+
+    /// Copy `path` as every saved clip
+    pub fn with_sample_clip(mut self, path: PathBuf) -> Self {
+        self.sample_clip = Some(path);
+        self
+    }
+
+    /// How often a fake segment is written
+    pub fn with_segment_interval(mut self, interval: Duration) -> Self {
+        self.segment_interval = interval;
+        self
+    }
+
+    /// Publish status changes and saved clips on `events`
+    pub fn with_events(mut self, events: Arc<EventBus>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Directory holding the fake replay buffer
+    pub fn segments_dir(&self) -> PathBuf {
+        self.output_dir.join("mock_segments")
+    }
+
+    async fn set_status(&self, status: RecordingStatus) {
+        *self.status.write().await = status;
+        self.events
+            .emit(AppEvent::RecordingStatusChanged(RecordingStatusChanged {
+                status,
+            }));
+    }
+}
+
+#[async_trait]
+impl Recorder for MockRecorder {
+    async fn start_replay_buffer(&self) -> Result<()> {
+        let segments_dir = self.segments_dir();
+        std::fs::create_dir_all(&segments_dir)?;
+
+        let cancel = CancellationToken::new();
+        if let Some(previous) = self.buffer_task.lock().replace(cancel.clone()) {
+            previous.cancel();
+        }
+
+        let stats = Arc::clone(&self.stats);
+        let interval = self.segment_interval;
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            let mut segments = VecDeque::new();
+            let mut index = 0u64;
+
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = cancel.cancelled() => break,
+                }
+
+                let segment = segments_dir.join(format!("segment_{:06}.mp4", index));
+                index += 1;
+                if let Err(e) = std::fs::write(&segment, placeholder("segment")) {
+                    tracing::warn!("Mock recorder failed to write {:?}: {}", segment, e);
+                    continue;
+                }
+                segments.push_back(segment);
+                if segments.len() > BUFFER_SEGMENTS {
+                    if let Some(old) = segments.pop_front() {
+                        let _ = std::fs::remove_file(old);
+                    }
+                }
+
+                stats.write().frames_captured += interval.as_secs() * 60;
+            }
+        });
+
+        self.set_status(RecordingStatus::Buffering).await;
+        tracing::info!("Mock replay buffer started");
+        Ok(())
+    }
+
+    async fn stop_replay_buffer(&self) -> Result<()> {
+        if let Some(cancel) = self.buffer_task.lock().take() {
+            cancel.cancel();
+        }
+        let _ = std::fs::remove_dir_all(self.segments_dir());
+
+        self.set_status(RecordingStatus::Idle).await;
+        tracing::info!("Mock replay buffer stopped");
+        Ok(())
+    }
+
+    async fn save_clip(
+        &self,
+        _event: &GameEvent,
+        clip_id: String,
+        priority: u8,
+        duration_secs: f64,
+    ) -> Result<PathBuf> {
+        if *self.status.read().await == RecordingStatus::Idle {
+            anyhow::bail!("Cannot save clip: replay buffer not active");
+        }
+
+        let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string();
+        let clip_filename = format!("mock_{}_p{}_{}.mp4", timestamp, priority, clip_id);
+        std::fs::create_dir_all(&self.clips_dir)?;
+        let output_path = self.clips_dir.join(&clip_filename);
+
+        match &self.sample_clip {
+            Some(sample) => {
+                std::fs::copy(sample, &output_path)
+                    .with_context(|| format!("Failed to copy sample clip {:?}", sample))?;
+            }
+            None => std::fs::write(&output_path, placeholder(&clip_id))?,
+        }
+
+        self.stats.write().clips_created += 1;
+        tracing::info!("Mock clip saved: {:?}", output_path);
+
+        self.events.emit(AppEvent::ClipSaved(ClipSaved {
+            clip_path: output_path.to_string_lossy().to_string(),
+            game_id: "unknown".to_string(),
+            priority,
+            duration_secs,
+        }));
+
+        Ok(output_path)
+    }
+
+    async fn get_state(&self) -> RecordingStatus {
+        *self.status.read().await
+    }
+
+    async fn get_stats(&self) -> RecordingStats {
+        self.stats.read().clone()
+    }
+}
+
+/// Contents of a fake video file
+fn placeholder(name: &str) -> Vec<u8> {
+    format!("LoLShorts mock recording: {}\n", name).into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn event() -> GameEvent {
+        GameEvent {
+            event_id: 1,
+            event_name: "ChampionKill".to_string(),
+            event_time: 300.0,
+            killer_name: None,
+            victim_name: None,
+            assisters: vec![],
+            priority: 2,
+            timestamp: Instant::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mock_recorder_saves_clips() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_mock_recorder");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let recorder = MockRecorder::new(temp_dir.join("recordings"), temp_dir.join("clips"))
+            .with_segment_interval(Duration::from_millis(10));
+
+        assert!(recorder
+            .save_clip(&event(), "kill".to_string(), 2, 20.0)
+            .await
+            .is_err());
+
+        recorder.start_replay_buffer().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let segments = std::fs::read_dir(recorder.segments_dir()).unwrap().count();
+        assert!((1..=BUFFER_SEGMENTS).contains(&segments));
+
+        let clip = recorder
+            .save_clip(&event(), "kill".to_string(), 2, 20.0)
+            .await
+            .unwrap();
+        assert!(clip.starts_with(temp_dir.join("clips")));
+        assert!(clip.exists());
+        assert_eq!(recorder.get_stats().await.clips_created, 1);
+
+        recorder.stop_replay_buffer().await.unwrap();
+        assert_eq!(recorder.get_state().await, RecordingStatus::Idle);
+        assert!(!recorder.segments_dir().exists());
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[tokio::test]
+    async fn test_mock_recorder_copies_sample() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_mock_sample");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        std::fs::create_dir_all(&temp_dir).unwrap();
+        let sample = temp_dir.join("sample.mp4");
+        std::fs::write(&sample, b"sample video").unwrap();
+
+        let recorder = MockRecorder::new(temp_dir.join("recordings"), temp_dir.join("clips"))
+            .with_sample_clip(sample);
+        recorder.start_replay_buffer().await.unwrap();
+        let clip = recorder
+            .save_clip(&event(), "kill".to_string(), 2, 20.0)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(&clip).unwrap(), b"sample video");
+
+        recorder.stop_replay_buffer().await.unwrap();
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+}
@@ -1,6 +1,8 @@
 #![allow(dead_code)]
 // Platform-specific recording implementations
 #[cfg(target_os = "windows")]
+mod wgc;
+#[cfg(target_os = "windows")]
 mod windows_backend;
 
 #[cfg(target_os = "macos")]
@@ -9,13 +11,20 @@ mod macos_backend; // Will be implemented in Wave 5
 // Common types and interfaces
 pub mod audio;
 pub mod auto_clip_manager;
+pub mod backend;
 pub mod commands;
+pub mod display;
 pub mod live_client;
+pub mod mock;
+pub mod recovery;
+pub mod replay;
+pub mod scoring;
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use std::time::Instant;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 // Re-export the platform-specific recorder as RecordingManager
 #[cfg(target_os = "windows")]
@@ -27,6 +36,14 @@ pub use macos_backend::MacOSRecorder as RecordingManager;
 #[cfg(not(any(target_os = "windows", target_os = "macos")))]
 compile_error!("LoLShorts only supports Windows and macOS");
 
+pub use backend::{ActiveRecorder, Recorder};
+
+/// Webcam recording stored next to a segment or clip
+/// (`123_clip.mp4` -> `123_clip.facecam.mp4`)
+pub fn facecam_path(video_path: &Path) -> PathBuf {
+    video_path.with_extension("facecam.mp4")
+}
+
 /// Recording status states
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -66,6 +83,9 @@ pub struct RecordingStats {
     pub cpu_usage: f64,
     /// Memory usage in MB
     pub memory_usage_mb: f64,
+    /// Segments discarded because FFmpeg produced no output
+    #[serde(default)]
+    pub dropped_segments: u64,
 }
 
 /// Game event types for clip creation
@@ -156,6 +176,263 @@ pub fn initialize_recording_backend(output_dir: PathBuf) -> Result<RecordingMana
     }
 }
 
+/// Interval between CPU checks for automatic low-spec mode
+const PERFORMANCE_WATCHDOG_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Start background watchdog that enables low-spec mode on sustained high CPU
+///
+/// Only acts while the replay buffer is running and
+/// `performance.auto_enable_on_high_cpu` is set. The switch is persisted to
+/// settings so the next session starts in low-spec mode as well.
+pub fn start_performance_watchdog(
+    recording_manager: Arc<tokio::sync::RwLock<RecordingManager>>,
+    metrics: Arc<crate::utils::metrics::MetricsCollector>,
+    settings: Arc<tokio::sync::RwLock<crate::settings::models::RecordingSettings>>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(PERFORMANCE_WATCHDOG_INTERVAL);
+
+        loop {
+            interval.tick().await;
+
+            metrics.update_system_metrics().await;
+
+            let performance = settings.read().await.performance.clone();
+            if !performance.auto_enable_on_high_cpu || performance.low_spec_mode {
+                continue;
+            }
+
+            let sustained = metrics
+                .track_sustained_cpu(performance.high_cpu_threshold)
+                .await;
+
+            if sustained < Duration::from_secs(performance.high_cpu_duration_secs) {
+                continue;
+            }
+
+            if recording_manager.read().await.get_state().await == RecordingStatus::Idle {
+                continue;
+            }
+
+            tracing::warn!(
+                "CPU above {:.0}% for {}s, enabling low-spec performance mode",
+                performance.high_cpu_threshold,
+                sustained.as_secs()
+            );
+
+            recording_manager.write().await.set_performance_mode(true);
+
+            let mut current = settings.write().await;
+            current.performance.low_spec_mode = true;
+            if let Err(e) = current.save() {
+                tracing::error!("Failed to persist performance mode: {}", e);
+            }
+        }
+    })
+}
+
+/// What the auto-pause watcher should do with the replay buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferAction {
+    Pause,
+    Resume,
+}
+
+/// Decide the buffer action for a newly observed in-game state
+///
+/// Only transitions act, so a manual pause/resume holds until the next game
+/// starts or ends. On the first observation the buffer is only resumed (if a
+/// game is already running); it is never paused before a game has been seen.
+pub fn buffer_action(previous: Option<bool>, in_game: bool) -> Option<BufferAction> {
+    match (previous, in_game) {
+        (Some(false) | None, true) => Some(BufferAction::Resume),
+        (Some(true), false) => Some(BufferAction::Pause),
+        _ => None,
+    }
+}
+
+/// Start background watcher that pauses the replay buffer outside games
+///
+/// Follows the LCU gameflow phase published by [`GameActivity`] and pauses
+/// segment recording once a game ends, resuming when the next one starts.
+/// Does nothing while `performance.pause_buffer_outside_game` is off or the
+/// client is closed.
+///
+/// [`GameActivity`]: crate::lcu::GameActivity
+pub fn start_buffer_auto_pause(
+    recording_manager: Arc<tokio::sync::RwLock<RecordingManager>>,
+    settings: Arc<tokio::sync::RwLock<crate::settings::models::RecordingSettings>>,
+    activity: Arc<crate::lcu::GameActivity>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut receiver = activity.subscribe();
+        let mut was_in_game: Option<bool> = None;
+
+        while receiver.changed().await.is_ok() {
+            let Some(in_game) = *receiver.borrow_and_update() else {
+                continue;
+            };
+
+            if !settings.read().await.performance.pause_buffer_outside_game {
+                was_in_game = None;
+                continue;
+            }
+
+            let action = buffer_action(was_in_game, in_game);
+            was_in_game = Some(in_game);
+
+            let manager = recording_manager.read().await;
+            match action {
+                Some(BufferAction::Pause) => {
+                    if manager.pause_replay_buffer().await {
+                        tracing::info!("Game ended, replay buffer paused");
+                    }
+                }
+                Some(BufferAction::Resume) => {
+                    if manager.resume_replay_buffer().await {
+                        tracing::info!("Game started, replay buffer resumed");
+                    }
+                }
+                None => {}
+            }
+        }
+    })
+}
+
+/// Whether the buffer should already be running in `phase`
+///
+/// Only covers the phases before the game itself; once it's running the
+/// auto-pause watcher takes over.
+pub fn warmup_active(
+    warmup: crate::settings::models::BufferWarmupPhase,
+    phase: crate::lcu::GameFlowPhase,
+) -> bool {
+    use crate::lcu::GameFlowPhase;
+    use crate::settings::models::BufferWarmupPhase;
+
+    match warmup {
+        BufferWarmupPhase::Off => false,
+        BufferWarmupPhase::ChampSelect => {
+            matches!(phase, GameFlowPhase::ChampSelect | GameFlowPhase::GameStart)
+        }
+        BufferWarmupPhase::GameStart => phase == GameFlowPhase::GameStart,
+    }
+}
+
+/// Start background watcher that warms the replay buffer up before a game
+///
+/// The first segment is still encoding for a few seconds after the buffer
+/// starts, so a buffer resumed only when the game loads misses its opening.
+/// From the configured `performance.buffer_warmup_phase` (champion select by
+/// default) the paused buffer is resumed, or started if it isn't running and
+/// `auto_start_with_league` is on. If the lobby is left without a game (a
+/// dodge), the buffer goes back to how it was.
+pub fn start_buffer_warmup(
+    recording_manager: Arc<tokio::sync::RwLock<RecordingManager>>,
+    recorder: Arc<dyn Recorder>,
+    settings: Arc<tokio::sync::RwLock<crate::settings::models::RecordingSettings>>,
+    activity: Arc<crate::lcu::GameActivity>,
+) -> tokio::task::JoinHandle<()> {
+    /// What the warm-up did to the buffer, to undo it after a dodge
+    #[derive(Clone, Copy)]
+    enum Warmup {
+        Resumed,
+        Started,
+    }
+
+    tokio::spawn(async move {
+        let mut receiver = activity.subscribe_phase();
+        let mut warmup: Option<Warmup> = None;
+
+        while receiver.changed().await.is_ok() {
+            let phase = *receiver.borrow_and_update();
+            let (warmup_phase, auto_start) = {
+                let settings = settings.read().await;
+                (
+                    settings.performance.buffer_warmup_phase,
+                    settings.auto_start_with_league,
+                )
+            };
+
+            match phase {
+                Some(phase) if phase.is_in_game() => {
+                    // The game started, the buffer is the auto-pause watcher's again
+                    warmup = None;
+                }
+                Some(phase) if warmup_active(warmup_phase, phase) => {
+                    if warmup.is_some() {
+                        continue;
+                    }
+                    if recording_manager.read().await.resume_replay_buffer().await {
+                        tracing::info!("{:?}: replay buffer warming up", phase);
+                        warmup = Some(Warmup::Resumed);
+                    } else if auto_start && recorder.get_state().await == RecordingStatus::Idle {
+                        match recorder.start_replay_buffer().await {
+                            Ok(()) => {
+                                tracing::info!("{:?}: replay buffer started early", phase);
+                                warmup = Some(Warmup::Started);
+                            }
+                            Err(e) => tracing::error!("Failed to start replay buffer: {}", e),
+                        }
+                    }
+                }
+                _ => match warmup.take() {
+                    Some(Warmup::Resumed) => {
+                        if recording_manager.read().await.pause_replay_buffer().await {
+                            tracing::info!("Left the lobby before the game, buffer paused");
+                        }
+                    }
+                    Some(Warmup::Started) => {
+                        tracing::info!("Left the lobby before the game, stopping buffer");
+                        if let Err(e) = recorder.stop_replay_buffer().await {
+                            tracing::error!("Failed to stop replay buffer: {}", e);
+                        }
+                    }
+                    None => {}
+                },
+            }
+        }
+    })
+}
+
+/// Start background watcher for degraded auto-capture
+///
+/// When the game is only detected from its process (the League client can't
+/// be reached, so there are no live events to clip automatically), the replay
+/// buffer is started anyway so manual clips and the hotkeys still work. The
+/// buffer is stopped again after the game only if this watcher started it.
+pub fn start_degraded_capture(
+    recorder: Arc<dyn Recorder>,
+    activity: Arc<crate::lcu::GameActivity>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut receiver = activity.subscribe();
+        let mut started_buffer = false;
+
+        while receiver.changed().await.is_ok() {
+            let in_game = *receiver.borrow_and_update() == Some(true);
+            let degraded = activity.detection() == crate::lcu::GameDetection::Process;
+
+            if in_game && degraded && !started_buffer {
+                if recorder.get_state().await != RecordingStatus::Idle {
+                    continue;
+                }
+                tracing::warn!("League client unreachable, starting degraded auto-capture");
+                match recorder.start_replay_buffer().await {
+                    Ok(()) => started_buffer = true,
+                    Err(e) => tracing::error!("Failed to start replay buffer: {}", e),
+                }
+            } else if !in_game && started_buffer {
+                started_buffer = false;
+                tracing::info!("Game ended, stopping degraded auto-capture");
+                if let Err(e) = recorder.stop_replay_buffer().await {
+                    tracing::error!("Failed to stop replay buffer: {}", e);
+                }
+            }
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,12 +457,36 @@ mod tests {
         assert_eq!(Platform::Unsupported.name(), "Unsupported");
     }
 
+    #[test]
+    fn test_warmup_active() {
+        use crate::lcu::GameFlowPhase;
+        use crate::settings::models::BufferWarmupPhase;
+
+        assert!(warmup_active(BufferWarmupPhase::ChampSelect, GameFlowPhase::ChampSelect));
+        assert!(warmup_active(BufferWarmupPhase::ChampSelect, GameFlowPhase::GameStart));
+        assert!(!warmup_active(BufferWarmupPhase::ChampSelect, GameFlowPhase::Lobby));
+        assert!(!warmup_active(BufferWarmupPhase::ChampSelect, GameFlowPhase::InProgress));
+        assert!(!warmup_active(BufferWarmupPhase::GameStart, GameFlowPhase::ChampSelect));
+        assert!(warmup_active(BufferWarmupPhase::GameStart, GameFlowPhase::GameStart));
+        assert!(!warmup_active(BufferWarmupPhase::Off, GameFlowPhase::GameStart));
+    }
+
     #[test]
     fn test_recording_status_default() {
         let status = RecordingStatus::default();
         assert_eq!(status, RecordingStatus::Idle);
     }
 
+    #[test]
+    fn test_buffer_action_transitions() {
+        assert_eq!(buffer_action(None, true), Some(BufferAction::Resume));
+        assert_eq!(buffer_action(None, false), None);
+        assert_eq!(buffer_action(Some(false), true), Some(BufferAction::Resume));
+        assert_eq!(buffer_action(Some(true), false), Some(BufferAction::Pause));
+        assert_eq!(buffer_action(Some(true), true), None);
+        assert_eq!(buffer_action(Some(false), false), None);
+    }
+
     #[test]
     fn test_recording_status_equality() {
         assert_eq!(RecordingStatus::Idle, RecordingStatus::Idle);
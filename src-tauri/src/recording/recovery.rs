@@ -0,0 +1,380 @@
+/// Crash recovery for replay buffer segments
+///
+/// If the app exits without stopping the replay buffer (crash, power loss,
+/// forced kill), the last segments are left in `temp_segments`. On startup
+/// they are moved into `recovery/<session_id>` so that neither the next
+/// recording session (which reuses segment file names) nor startup cleanup
+/// deletes them. The frontend can then salvage a session into a clip or
+/// discard it.
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::video::execute_ffmpeg_command;
+
+/// Directory (under the recordings dir) holding quarantined sessions
+const RECOVERY_DIR: &str = "recovery";
+const TEMP_SEGMENTS_DIR: &str = "temp_segments";
+
+/// Approximate length of one replay buffer segment
+const SEGMENT_DURATION_SECS: f64 = 10.0;
+
+/// Leftover segments from a previous session that can be salvaged
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecoverableSession {
+    pub session_id: String,
+    pub segment_count: usize,
+    pub total_size_bytes: u64,
+    /// Approximate duration (segment count × segment length)
+    pub estimated_duration_secs: f64,
+    /// When the last segment was written (approximate crash time)
+    pub last_modified: DateTime<Utc>,
+}
+
+/// Move leftover segments from `temp_segments` into a recovery session
+///
+/// Must run before the recording backend starts and before startup cleanup.
+/// Empty segments (FFmpeg killed before writing) are deleted.
+pub fn quarantine_orphaned_segments(recordings_dir: &Path) -> Result<Option<RecoverableSession>> {
+    let temp_dir = recordings_dir.join(TEMP_SEGMENTS_DIR);
+    let segments = list_segments(&temp_dir)?;
+
+    if segments.is_empty() {
+        return Ok(None);
+    }
+
+    let last_modified = segments
+        .last()
+        .map(|(_, modified)| DateTime::<Utc>::from(*modified))
+        .unwrap_or_else(Utc::now);
+    let session_id = unused_session_id(
+        &recordings_dir.join(RECOVERY_DIR),
+        &last_modified.format("%Y%m%d_%H%M%S").to_string(),
+    );
+
+    let session_dir = recordings_dir.join(RECOVERY_DIR).join(&session_id);
+    fs::create_dir_all(&session_dir)
+        .with_context(|| format!("Failed to create recovery directory: {:?}", session_dir))?;
+
+    for (idx, (path, _)) in segments.iter().enumerate() {
+        let target = session_dir.join(format!("segment_{:04}.mp4", idx));
+        fs::rename(path, &target)
+            .with_context(|| format!("Failed to move orphaned segment: {:?}", path))?;
+
+        let facecam = super::facecam_path(path);
+        if facecam.exists() {
+            fs::rename(&facecam, super::facecam_path(&target))
+                .with_context(|| format!("Failed to move orphaned segment: {:?}", facecam))?;
+        }
+    }
+
+    tracing::warn!(
+        "Recovered {} orphaned segments from previous session into {:?}",
+        segments.len(),
+        session_dir
+    );
+
+    load_session(recordings_dir, &session_id).map(Some)
+}
+
+/// List all quarantined sessions (most recent first)
+pub fn list_recoverable_sessions(recordings_dir: &Path) -> Result<Vec<RecoverableSession>> {
+    let recovery_dir = recordings_dir.join(RECOVERY_DIR);
+    if !recovery_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut sessions = Vec::new();
+    for entry in fs::read_dir(&recovery_dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+
+        if let Some(session_id) = entry.file_name().to_str() {
+            match load_session(recordings_dir, session_id) {
+                Ok(session) if session.segment_count > 0 => sessions.push(session),
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Skipping recovery session {}: {}", session_id, e),
+            }
+        }
+    }
+
+    sessions.sort_by(|a, b| b.last_modified.cmp(&a.last_modified));
+    Ok(sessions)
+}
+
+/// Concatenate a recovery session into a single clip and remove the session
+///
+/// The segment being written when the app died usually has no index and
+/// can't be read; it's left out so the rest can still be copied.
+///
+/// Returns the path of the salvaged clip in the recordings directory.
+pub async fn salvage_session(recordings_dir: &Path, session_id: &str) -> Result<PathBuf> {
+    let session_dir = session_path(recordings_dir, session_id)?;
+    let mut segments = list_segments(&session_dir)?;
+
+    if let Some((last, _)) = segments.last() {
+        if !is_readable(last).await {
+            tracing::warn!("Leaving out truncated segment {:?}", last);
+            segments.pop();
+        }
+    }
+
+    if segments.is_empty() {
+        anyhow::bail!("Recovery session {} has no readable segments", session_id);
+    }
+
+    let concat_file = session_dir.join("concat_list.txt");
+    let content: String = segments
+        .iter()
+        .map(|(path, _)| format!("file '{}'\n", path.display()))
+        .collect();
+    fs::write(&concat_file, content).context("Failed to write concat list")?;
+
+    let output_path = recordings_dir.join(format!("recovered_{}.mp4", session_id));
+
+    // Subject to the FFmpeg timeout, so a hung remux can't block recovery
+    let mut command = tokio::process::Command::new("ffmpeg");
+    command.args([
+        "-f",
+        "concat",
+        "-safe",
+        "0",
+        "-i",
+        concat_file.to_str().context("Invalid concat file path")?,
+        "-c",
+        "copy",
+        "-y",
+        output_path.to_str().context("Invalid output path")?,
+    ]);
+    execute_ffmpeg_command(&mut command)
+        .await
+        .context("FFmpeg concatenation failed")?;
+
+    fs::remove_dir_all(&session_dir)
+        .with_context(|| format!("Failed to remove recovery session: {:?}", session_dir))?;
+
+    tracing::info!(
+        "Salvaged recovery session {} into {:?}",
+        session_id,
+        output_path
+    );
+
+    Ok(output_path)
+}
+
+/// Delete a recovery session without salvaging it
+pub fn discard_session(recordings_dir: &Path, session_id: &str) -> Result<()> {
+    let session_dir = session_path(recordings_dir, session_id)?;
+    fs::remove_dir_all(&session_dir)
+        .with_context(|| format!("Failed to remove recovery session: {:?}", session_dir))?;
+
+    tracing::info!("Discarded recovery session {}", session_id);
+    Ok(())
+}
+
+/// Whether FFmpeg can read `segment` (a segment cut off by a crash can't)
+async fn is_readable(segment: &Path) -> bool {
+    tokio::process::Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-show_entries",
+            "format=duration",
+            "-of",
+            "csv=p=0",
+        ])
+        .arg(segment)
+        .output()
+        .await
+        .is_ok_and(|output| {
+            output.status.success() && !String::from_utf8_lossy(&output.stdout).trim().is_empty()
+        })
+}
+
+/// `base`, or `base_2`, `base_3`, ... if a session with that id exists
+///
+/// Two crashes within the same second would otherwise share a session.
+fn unused_session_id(recovery_dir: &Path, base: &str) -> String {
+    let mut session_id = base.to_string();
+    let mut suffix = 2;
+    while recovery_dir.join(&session_id).exists() {
+        session_id = format!("{}_{}", base, suffix);
+        suffix += 1;
+    }
+    session_id
+}
+
+/// Resolve and validate a session directory (session IDs are timestamps)
+fn session_path(recordings_dir: &Path, session_id: &str) -> Result<PathBuf> {
+    if session_id.is_empty()
+        || !session_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        anyhow::bail!("Invalid recovery session id: {}", session_id);
+    }
+
+    let path = recordings_dir.join(RECOVERY_DIR).join(session_id);
+    if !path.is_dir() {
+        anyhow::bail!("Recovery session not found: {}", session_id);
+    }
+
+    Ok(path)
+}
+
+fn load_session(recordings_dir: &Path, session_id: &str) -> Result<RecoverableSession> {
+    let session_dir = session_path(recordings_dir, session_id)?;
+    let segments = list_segments(&session_dir)?;
+
+    let mut total_size_bytes = 0;
+    for (path, _) in &segments {
+        total_size_bytes += fs::metadata(path)?.len();
+    }
+
+    let last_modified = segments
+        .last()
+        .map(|(_, modified)| DateTime::<Utc>::from(*modified))
+        .unwrap_or_else(Utc::now);
+
+    Ok(RecoverableSession {
+        session_id: session_id.to_string(),
+        segment_count: segments.len(),
+        total_size_bytes,
+        estimated_duration_secs: segments.len() as f64 * SEGMENT_DURATION_SECS,
+        last_modified,
+    })
+}
+
+/// List non-empty `segment_*.mp4` files in chronological order
+///
+/// Facecam tracks (`segment_*.facecam.mp4`) belong to the segment of the
+/// same number and aren't listed. Empty segments are removed since they
+/// can't be salvaged.
+fn list_segments(dir: &Path) -> Result<Vec<(PathBuf, SystemTime)>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut segments = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {:?}", dir))? {
+        let path = entry?.path();
+        let is_segment = path.file_name().and_then(|n| n.to_str()).is_some_and(|n| {
+            n.starts_with("segment_") && n.ends_with(".mp4") && !n.ends_with(".facecam.mp4")
+        });
+
+        if !is_segment || !path.is_file() {
+            continue;
+        }
+
+        let metadata = fs::metadata(&path)?;
+        if metadata.len() == 0 {
+            let _ = fs::remove_file(&path);
+            let _ = fs::remove_file(super::facecam_path(&path));
+            continue;
+        }
+
+        segments.push((path, metadata.modified()?));
+    }
+
+    segments.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_segment(dir: &Path, name: &str, size: usize) {
+        fs::create_dir_all(dir).unwrap();
+        fs::write(dir.join(name), vec![0u8; size]).unwrap();
+    }
+
+    #[test]
+    fn test_no_orphans() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = quarantine_orphaned_segments(temp_dir.path()).unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_quarantine_moves_segments() {
+        let temp_dir = TempDir::new().unwrap();
+        let segments_dir = temp_dir.path().join(TEMP_SEGMENTS_DIR);
+        write_segment(&segments_dir, "segment_0003.mp4", 1024);
+        write_segment(&segments_dir, "segment_0004.mp4", 2048);
+        write_segment(&segments_dir, "segment_0005.mp4", 0); // Empty, unsalvageable
+        write_segment(&segments_dir, "segment_0004.facecam.mp4", 512);
+
+        let session = quarantine_orphaned_segments(temp_dir.path())
+            .unwrap()
+            .expect("session should be created");
+
+        assert_eq!(session.segment_count, 2);
+        assert_eq!(session.total_size_bytes, 3072);
+        assert_eq!(session.estimated_duration_secs, 20.0);
+
+        // temp_segments is now empty so the next recording can't overwrite them
+        assert!(list_segments(&segments_dir).unwrap().is_empty());
+        assert!(!segments_dir.join("segment_0004.facecam.mp4").exists());
+        // The facecam track moved along with its segment
+        assert!(temp_dir
+            .path()
+            .join(RECOVERY_DIR)
+            .join(&session.session_id)
+            .join("segment_0001.facecam.mp4")
+            .exists());
+
+        let sessions = list_recoverable_sessions(temp_dir.path()).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].session_id, session.session_id);
+    }
+
+    #[test]
+    fn test_discard_session() {
+        let temp_dir = TempDir::new().unwrap();
+        write_segment(
+            &temp_dir.path().join(TEMP_SEGMENTS_DIR),
+            "segment_0000.mp4",
+            512,
+        );
+
+        let session = quarantine_orphaned_segments(temp_dir.path())
+            .unwrap()
+            .unwrap();
+        discard_session(temp_dir.path(), &session.session_id).unwrap();
+
+        assert!(list_recoverable_sessions(temp_dir.path())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_sessions_in_the_same_second() {
+        let temp_dir = TempDir::new().unwrap();
+        let recovery_dir = temp_dir.path().join(RECOVERY_DIR);
+        assert_eq!(
+            unused_session_id(&recovery_dir, "20240304_120000"),
+            "20240304_120000"
+        );
+
+        fs::create_dir_all(recovery_dir.join("20240304_120000")).unwrap();
+        fs::create_dir_all(recovery_dir.join("20240304_120000_2")).unwrap();
+        assert_eq!(
+            unused_session_id(&recovery_dir, "20240304_120000"),
+            "20240304_120000_3"
+        );
+    }
+
+    #[test]
+    fn test_invalid_session_id_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(discard_session(temp_dir.path(), "../clips").is_err());
+        assert!(discard_session(temp_dir.path(), "").is_err());
+    }
+}
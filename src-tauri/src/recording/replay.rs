@@ -0,0 +1,678 @@
+//! Highlight extraction from replays
+//!
+//! For games that weren't recorded live. The replay is opened in the League
+//! client, events are read from the game's Live Client API and each highlight
+//! is played back at normal speed through the Replay API (camera locked on the
+//! player) while the replay buffer records it, then saved like a live clip.
+//!
+//! The Replay API has to be enabled in the game's `game.cfg`
+//! (`EnableReplayApi=1`, see [`enable_replay_api`]).
+
+use anyhow::{bail, Context, Result};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use super::auto_clip_manager::{clip_metadata, clip_window, convert_live_event, is_event_enabled};
+use super::live_client::{
+    is_same_player, Events, GameEvent, LiveClientMonitor, PlayerInvolvement, LIVE_CLIENT_API,
+};
+use super::{RecordingManager, RecordingStatus};
+use crate::lcu::patch as league_patch;
+use crate::lcu::LcuClient;
+use crate::settings::models::RecordingSettings;
+use crate::storage::{models::GameMetadata, Storage};
+
+const REPLAY_API: &str = "https://127.0.0.1:2999/replay";
+
+/// How long to wait for a replay to load after launching it
+const REPLAY_LOAD_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Time the replay buffer needs after starting before segments are written
+const BUFFER_WARMUP: Duration = Duration::from_secs(2);
+
+/// Metadata embedded in a `.rofl` replay file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoflMetadata {
+    /// Parsed from the file name (`KR-7012345678.rofl`)
+    pub game_id: Option<u64>,
+    pub game_length_secs: f64,
+    pub game_version: String,
+    pub players: Vec<RoflPlayer>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoflPlayer {
+    /// Riot ID (`name#tag`) or summoner name on older replays
+    pub name: String,
+    pub champion: String,
+    /// "100" (blue) or "200" (red)
+    pub team: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawRoflMetadata {
+    #[serde(rename = "gameLength")]
+    game_length: u64, // Milliseconds
+    #[serde(rename = "gameVersion", default)]
+    game_version: String,
+    #[serde(rename = "statsJson", default)]
+    stats_json: String, // JSON array encoded as a string
+}
+
+impl RoflMetadata {
+    /// Read the metadata of a replay file
+    pub fn read(path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Failed to read replay file: {}", path.display()))?;
+
+        let mut metadata = Self::parse(&bytes)?;
+        metadata.game_id = game_id_from_file_name(path);
+
+        Ok(metadata)
+    }
+
+    /// Parse the metadata from the contents of a replay file
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        if !bytes.starts_with(b"RIOT") {
+            bail!("Not a League of Legends replay file");
+        }
+
+        let json = metadata_json(bytes).context("Replay file has no metadata")?;
+        let raw: RawRoflMetadata =
+            serde_json::from_slice(json).context("Invalid replay metadata")?;
+
+        let stats: Vec<HashMap<String, serde_json::Value>> =
+            serde_json::from_str(&raw.stats_json).unwrap_or_default();
+        let players = stats
+            .iter()
+            .map(|player| {
+                let field = |key: &str| {
+                    player
+                        .get(key)
+                        .and_then(|value| value.as_str())
+                        .unwrap_or_default()
+                        .to_string()
+                };
+
+                let game_name = field("RIOT_ID_GAME_NAME");
+                let name = match (game_name.is_empty(), field("RIOT_ID_TAG_LINE")) {
+                    (true, _) => field("NAME"),
+                    (false, tag) if tag.is_empty() => game_name,
+                    (false, tag) => format!("{}#{}", game_name, tag),
+                };
+
+                RoflPlayer {
+                    name,
+                    champion: field("SKIN"),
+                    team: field("TEAM"),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            game_id: None,
+            game_length_secs: raw.game_length as f64 / 1000.0,
+            game_version: raw.game_version,
+            players,
+        })
+    }
+
+    /// Find a player by Riot ID or game name
+    pub fn player(&self, name: &str) -> Option<&RoflPlayer> {
+        self.players
+            .iter()
+            .find(|player| is_same_player(&player.name, name))
+    }
+}
+
+/// Locate the metadata JSON of a replay file
+///
+/// Older replays point to it from a fixed header after the 256-byte
+/// signature; current ones append it to the end of the file, followed by its
+/// length.
+fn metadata_json(bytes: &[u8]) -> Option<&[u8]> {
+    // Magic (6) + signature (256) + header length (2) + file length (4)
+    // + metadata offset (4) + metadata length (4)
+    if bytes.len() >= 276 {
+        let offset = u32::from_le_bytes(bytes[268..272].try_into().ok()?) as usize;
+        let length = u32::from_le_bytes(bytes[272..276].try_into().ok()?) as usize;
+        let json = offset
+            .checked_add(length)
+            .and_then(|end| bytes.get(offset..end));
+        if let Some(json) = json.filter(|json| json.starts_with(b"{")) {
+            return Some(json);
+        }
+    }
+
+    let length_start = bytes.len().checked_sub(4)?;
+    let length = u32::from_le_bytes(bytes[length_start..].try_into().ok()?) as usize;
+    let json = bytes.get(length_start.checked_sub(length)?..length_start)?;
+    json.starts_with(b"{").then_some(json)
+}
+
+/// Game ID from a replay file name like `KR-7012345678.rofl`
+fn game_id_from_file_name(path: &Path) -> Option<u64> {
+    path.file_stem()?.to_str()?.rsplit('-').next()?.parse().ok()
+}
+
+/// Enable the Replay API in the game's `Config/game.cfg`
+///
+/// Returns false if it was already enabled. Takes effect the next time the
+/// game (or a replay) is started.
+pub fn enable_replay_api(install_dir: &Path) -> Result<bool> {
+    let cfg_path = install_dir.join("Config").join("game.cfg");
+    let content = std::fs::read_to_string(&cfg_path)
+        .with_context(|| format!("Failed to read {}", cfg_path.display()))?;
+
+    let updated = enable_replay_api_in_cfg(&content);
+    if updated == content {
+        return Ok(false);
+    }
+
+    std::fs::write(&cfg_path, updated)
+        .with_context(|| format!("Failed to write {}", cfg_path.display()))?;
+    info!("Enabled Replay API in {}", cfg_path.display());
+
+    Ok(true)
+}
+
+/// Set `EnableReplayApi=1` in the `[General]` section of `game.cfg` content
+fn enable_replay_api_in_cfg(content: &str) -> String {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let mut section = "";
+    let mut existing = None;
+    let mut insert_at = None;
+
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') && trimmed.ends_with(']') {
+            section = trimmed;
+            if section == "[General]" {
+                insert_at = Some(i + 1);
+            }
+            continue;
+        }
+        if section != "[General]" {
+            continue;
+        }
+        insert_at = Some(i + 1);
+
+        if let Some((key, _)) = trimmed.split_once('=') {
+            if key.trim().eq_ignore_ascii_case("EnableReplayApi") {
+                existing = Some(i);
+                break;
+            }
+        }
+    }
+
+    match (existing, insert_at) {
+        (Some(i), _) => lines[i] = "EnableReplayApi=1".to_string(),
+        (None, Some(i)) => lines.insert(i, "EnableReplayApi=1".to_string()),
+        (None, None) => {
+            lines.push("[General]".to_string());
+            lines.push("EnableReplayApi=1".to_string());
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// Open a replay file in the League client
+///
+/// The file is copied into the client's replays folder first, since the
+/// client only plays replays from there. Also enables the Replay API so the
+/// replay can be controlled.
+pub async fn open_rofl(path: &Path) -> Result<RoflMetadata> {
+    let metadata = RoflMetadata::read(path)?;
+    let game_id = metadata
+        .game_id
+        .context("Replay file name doesn't contain a game ID (e.g. KR-7012345678.rofl)")?;
+
+    if let Some(install_dir) = LcuClient::detect_install_dir() {
+        if let Err(e) = enable_replay_api(&install_dir) {
+            warn!("Failed to enable Replay API: {}", e);
+        }
+    }
+
+    let mut lcu = LcuClient::new();
+    lcu.connect().await?;
+
+    let replays_dir = lcu.replays_dir().await?;
+    let target = replays_dir.join(path.file_name().context("Invalid replay path")?);
+    if target != path {
+        tokio::fs::create_dir_all(&replays_dir).await?;
+        tokio::fs::copy(path, &target)
+            .await
+            .context("Failed to copy replay into the replays folder")?;
+    }
+
+    lcu.watch_replay(game_id).await?;
+    info!("Opened replay {} ({})", game_id, metadata.game_version);
+
+    Ok(metadata)
+}
+
+/// Replay playback state
+#[derive(Debug, Clone, Deserialize)]
+pub struct Playback {
+    pub length: f64,
+    pub time: f64,
+    pub paused: bool,
+    pub speed: f64,
+    #[serde(default)]
+    pub seeking: bool,
+}
+
+/// Client for the game's Replay API (only available while watching a replay)
+pub struct ReplayClient {
+    client: Client,
+}
+
+impl ReplayClient {
+    pub fn new() -> Result<Self> {
+        // Create HTTP client that accepts self-signed certificates
+        let client = Client::builder()
+            .danger_accept_invalid_certs(true)
+            .timeout(Duration::from_secs(5))
+            .build()?;
+
+        Ok(Self { client })
+    }
+
+    /// Current playback state
+    pub async fn playback(&self) -> Result<Playback> {
+        let response = self
+            .client
+            .get(format!("{}/playback", REPLAY_API))
+            .send()
+            .await
+            .context("Failed to connect to Replay API")?
+            .error_for_status()?;
+
+        Ok(response.json().await?)
+    }
+
+    async fn set_playback(&self, update: serde_json::Value) -> Result<Playback> {
+        let response = self
+            .client
+            .post(format!("{}/playback", REPLAY_API))
+            .json(&update)
+            .send()
+            .await
+            .context("Failed to connect to Replay API")?
+            .error_for_status()?;
+
+        Ok(response.json().await?)
+    }
+
+    /// Jump to `time` (game seconds) and wait for the seek to finish
+    pub async fn seek(&self, time: f64) -> Result<()> {
+        self.set_playback(serde_json::json!({ "time": time, "paused": true }))
+            .await?;
+
+        while self.playback().await?.seeking {
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+
+        Ok(())
+    }
+
+    /// Play at normal speed
+    pub async fn play(&self) -> Result<()> {
+        self.set_playback(serde_json::json!({ "paused": false, "speed": 1.0 }))
+            .await?;
+        Ok(())
+    }
+
+    pub async fn pause(&self) -> Result<()> {
+        self.set_playback(serde_json::json!({ "paused": true }))
+            .await?;
+        Ok(())
+    }
+
+    /// Lock the camera on a player
+    pub async fn focus_player(&self, name: &str) -> Result<()> {
+        // The replay selects players by game name, without the tag
+        let name = name.split('#').next().unwrap_or(name);
+
+        self.client
+            .post(format!("{}/render", REPLAY_API))
+            .json(&serde_json::json!({ "cameraAttached": true, "selectionName": name }))
+            .send()
+            .await
+            .context("Failed to connect to Replay API")?
+            .error_for_status()?;
+
+        Ok(())
+    }
+
+    /// Events up to the current playback position
+    pub async fn events(&self) -> Result<Vec<GameEvent>> {
+        let events: Events = self
+            .client
+            .get(format!("{}/eventdata", LIVE_CLIENT_API))
+            .send()
+            .await
+            .context("Failed to connect to Live Client API")?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(events.events)
+    }
+
+    /// Wait until a replay is loaded
+    pub async fn wait_until_ready(&self, timeout: Duration) -> Result<Playback> {
+        let started = Instant::now();
+
+        loop {
+            match self.playback().await {
+                Ok(playback) if playback.length > 0.0 && !playback.seeking => return Ok(playback),
+                Ok(_) => {}
+                Err(e) => debug!("Replay API not ready: {}", e),
+            }
+
+            if started.elapsed() >= timeout {
+                bail!("Replay did not load (is EnableReplayApi=1 set in game.cfg?)");
+            }
+
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    }
+}
+
+/// Result of [`ReplayExtractor::extract`]
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayExtraction {
+    pub game_id: String,
+    pub clips: Vec<PathBuf>,
+}
+
+/// Plays a replay's highlights back through the replay buffer
+pub struct ReplayExtractor {
+    recorder: Arc<RwLock<RecordingManager>>,
+    storage: Arc<Storage>,
+    settings: Arc<RwLock<RecordingSettings>>,
+    replay: ReplayClient,
+}
+
+impl ReplayExtractor {
+    pub fn new(
+        recorder: Arc<RwLock<RecordingManager>>,
+        storage: Arc<Storage>,
+        settings: Arc<RwLock<RecordingSettings>>,
+    ) -> Result<Self> {
+        Ok(Self {
+            recorder,
+            storage,
+            settings,
+            replay: ReplayClient::new()?,
+        })
+    }
+
+    /// Save `player_name`'s highlights from the replay that's being watched
+    ///
+    /// Uses the recording settings' event filter and clip timing, like live
+    /// auto-clipping. Takes as long as the highlights take to play back.
+    /// Without `patch` the current one is recorded (the client only plays
+    /// replays of the current patch).
+    pub async fn extract(
+        &self,
+        game_id: &str,
+        player_name: &str,
+        champion: Option<String>,
+        patch: Option<String>,
+    ) -> Result<ReplayExtraction> {
+        let playback = self.replay.wait_until_ready(REPLAY_LOAD_TIMEOUT).await?;
+
+        // Events are only reported up to the playback position
+        self.replay.seek(playback.length).await?;
+        let events = self.replay.events().await?;
+
+        let settings = self.settings.read().await.clone();
+        let monitor = LiveClientMonitor::new()?
+            .with_player_name(player_name)
+            .with_involvement(PlayerInvolvement {
+                assists: settings.event_filter.record_assists,
+                deaths: settings.event_filter.record_deaths,
+                death_priority: settings.event_filter.death_priority,
+            });
+        let highlights: Vec<_> = monitor
+            .detect_events(&events)
+            .await
+            .into_iter()
+            .filter(|(trigger, _, factors)| {
+                is_event_enabled(
+                    &settings.event_filter,
+                    trigger,
+                    trigger.boosted_priority(factors),
+                )
+            })
+            .collect();
+
+        info!(
+            "Found {} highlights for {} in replay {}",
+            highlights.len(),
+            player_name,
+            game_id
+        );
+
+        let game = self.save_game(game_id, champion, patch).await?;
+        // Clips are named after the game until it's unset again, whether
+        // or not the playback got through
+        let result = async {
+            self.ensure_buffering().await?;
+            self.replay.focus_player(player_name).await?;
+
+            let mut clips = Vec::new();
+            for (trigger, live_event, factors) in highlights {
+                let window = clip_window(&trigger, &settings);
+                let event_time = live_event.event_time as f64;
+                let start = (event_time - window.pre_duration as f64).max(0.0);
+                let duration = event_time - start + window.post_duration as f64;
+
+                self.replay.seek(start).await?;
+                self.replay.play().await?;
+                tokio::time::sleep(Duration::from_secs_f64(duration)).await;
+                self.replay.pause().await?;
+
+                let event = convert_live_event(live_event, &trigger, &factors);
+                let clip_id = format!("replay_{}_{}", event.event_name, event.event_time as u32);
+
+                let clip_path = match self
+                    .recorder
+                    .read()
+                    .await
+                    .save_clip(&event, clip_id.clone(), event.priority, duration)
+                    .await
+                {
+                    Ok(path) => path,
+                    Err(e) => {
+                        warn!("Failed to save replay clip {}: {}", clip_id, e);
+                        continue;
+                    }
+                };
+
+                let mut clip =
+                    clip_metadata(&event, &trigger, event.priority, &factors, &clip_path);
+                clip.game_id = game_id.to_string();
+                clip.add_tag("replay".to_string());
+                if let Some(patch) = &game.patch {
+                    clip.add_tag(league_patch::clip_tag(patch));
+                }
+                self.storage
+                    .save_clip_metadata_v2(game_id, &clip)
+                    .context("Failed to save clip metadata")?;
+
+                info!("Replay clip saved: {:?}", clip_path);
+                clips.push(clip_path);
+            }
+
+            Ok::<_, anyhow::Error>(clips)
+        }
+        .await;
+        self.recorder.read().await.set_current_game(None).await;
+
+        Ok(ReplayExtraction {
+            game_id: game_id.to_string(),
+            clips: result?,
+        })
+    }
+
+    /// Create the game in storage (if new) and name clips after it
+    async fn save_game(
+        &self,
+        game_id: &str,
+        champion: Option<String>,
+        patch: Option<String>,
+    ) -> Result<GameMetadata> {
+        let game = match self.storage.load_game_metadata(game_id) {
+            Ok(game) => game,
+            Err(_) => {
+                let patch = match patch {
+                    Some(patch) => Some(patch),
+                    None => league_patch::current_patch().await,
+                };
+                let game = GameMetadata {
+                    game_id: game_id.to_string(),
+                    champion: champion.unwrap_or_else(|| "Unknown".to_string()),
+                    game_mode: "Replay".to_string(),
+                    start_time: chrono::Utc::now(),
+                    end_time: None,
+                    result: None,
+                    kda: None,
+                    patch,
+                };
+                self.storage
+                    .save_game_metadata(game_id, &game)
+                    .context("Failed to save game metadata")?;
+                game
+            }
+        };
+
+        self.recorder
+            .read()
+            .await
+            .set_current_game(Some(game.clone()))
+            .await;
+        Ok(game)
+    }
+
+    /// Make sure the replay buffer is recording the screen
+    async fn ensure_buffering(&self) -> Result<()> {
+        let recorder = self.recorder.read().await;
+
+        match recorder.get_state().await {
+            RecordingStatus::Idle => {
+                recorder.start_replay_buffer().await?;
+                tokio::time::sleep(BUFFER_WARMUP).await;
+            }
+            RecordingStatus::Paused => {
+                recorder.resume_replay_buffer().await;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata_bytes() -> Vec<u8> {
+        let stats = serde_json::json!([
+            { "RIOT_ID_GAME_NAME": "Faker", "RIOT_ID_TAG_LINE": "KR1", "SKIN": "Ahri", "TEAM": "100" },
+            { "NAME": "OldName", "SKIN": "Zed", "TEAM": "200" }
+        ]);
+        serde_json::to_vec(&serde_json::json!({
+            "gameLength": 1_834_000,
+            "gameVersion": "14.20.620.9461",
+            "lastGameChunkId": 30,
+            "statsJson": stats.to_string()
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_trailing_metadata() {
+        let json = metadata_bytes();
+        let mut bytes = b"RIOT\x02\x00".to_vec();
+        bytes.extend(vec![0u8; 512]); // Chunk data
+        bytes.extend(&json);
+        bytes.extend((json.len() as u32).to_le_bytes());
+
+        let metadata = RoflMetadata::parse(&bytes).unwrap();
+        assert_eq!(metadata.game_length_secs, 1834.0);
+        assert_eq!(metadata.game_version, "14.20.620.9461");
+        assert_eq!(metadata.players.len(), 2);
+        assert_eq!(metadata.players[0].name, "Faker#KR1");
+        assert_eq!(metadata.players[1].name, "OldName");
+        assert_eq!(metadata.player("faker").unwrap().champion, "Ahri");
+        assert!(metadata.player("Chovy").is_none());
+    }
+
+    #[test]
+    fn test_parse_header_metadata() {
+        let json = metadata_bytes();
+        let mut bytes = b"RIOT\x00\x00".to_vec();
+        bytes.extend(vec![0u8; 256]); // Signature
+        bytes.extend(288u16.to_le_bytes()); // Header length
+        bytes.extend(0u32.to_le_bytes()); // File length
+        bytes.extend(288u32.to_le_bytes()); // Metadata offset
+        bytes.extend((json.len() as u32).to_le_bytes()); // Metadata length
+        bytes.resize(288, 0);
+        bytes.extend(&json);
+        bytes.extend(vec![0u8; 64]); // Payload
+
+        let metadata = RoflMetadata::parse(&bytes).unwrap();
+        assert_eq!(metadata.players[0].champion, "Ahri");
+        assert_eq!(metadata.players[1].team, "200");
+    }
+
+    #[test]
+    fn test_parse_rejects_other_files() {
+        assert!(RoflMetadata::parse(b"not a replay").is_err());
+        assert!(RoflMetadata::parse(b"RIOT\x00\x00").is_err());
+    }
+
+    #[test]
+    fn test_game_id_from_file_name() {
+        assert_eq!(
+            game_id_from_file_name(Path::new("C:\\Replays\\KR-7012345678.rofl")),
+            Some(7012345678)
+        );
+        assert_eq!(game_id_from_file_name(Path::new("highlights.rofl")), None);
+    }
+
+    #[test]
+    fn test_enable_replay_api_in_cfg() {
+        let cfg = "[General]\nWindowMode=2\nEnableReplayApi=0\n[HUD]\nScale=1\n";
+        assert_eq!(
+            enable_replay_api_in_cfg(cfg),
+            "[General]\nWindowMode=2\nEnableReplayApi=1\n[HUD]\nScale=1\n"
+        );
+
+        let cfg = "[General]\nWindowMode=2\n[HUD]\nScale=1\n";
+        assert_eq!(
+            enable_replay_api_in_cfg(cfg),
+            "[General]\nWindowMode=2\nEnableReplayApi=1\n[HUD]\nScale=1\n"
+        );
+
+        assert_eq!(
+            enable_replay_api_in_cfg("[HUD]\nScale=1\n"),
+            "[HUD]\nScale=1\n[General]\nEnableReplayApi=1\n"
+        );
+
+        // Already enabled: unchanged
+        let cfg = "[General]\nEnableReplayApi=1\n";
+        assert_eq!(enable_replay_api_in_cfg(cfg), cfg);
+    }
+}
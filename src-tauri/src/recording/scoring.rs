@@ -0,0 +1,146 @@
+//! Highlight score for clips
+//!
+//! The 1-5 priority only looks at the event type, so every solo kill ties
+//! with every other. The score (0-100) also weighs how involved the player
+//! was, the gold at stake, how close to death they were, how late in the
+//! game it happened and how much happened in the clip. Auto-edit picks
+//! clips by score when one is stored.
+
+use super::live_client::{ClipFactors, COMEBACK_GOLD_DEFICIT};
+use crate::storage::models::EventType;
+use crate::storage::models_v2::ClipMetadataV2;
+
+/// Points each factor contributes at most (they add up to 100)
+const EVENT_WEIGHT: f64 = 35.0;
+const PARTICIPATION_WEIGHT: f64 = 15.0;
+const GOLD_WEIGHT: f64 = 10.0;
+const HEALTH_WEIGHT: f64 = 15.0;
+const GAME_TIME_WEIGHT: f64 = 10.0;
+const DENSITY_WEIGHT: f64 = 15.0;
+
+/// Game time at which fights count fully (35 minutes)
+const LATE_GAME_SECS: f64 = 35.0 * 60.0;
+
+/// Events in a clip at which density counts fully
+const MAX_DENSITY_EVENTS: usize = 5;
+
+/// Score a clip from its events and the game state around them
+pub fn score(clip: &ClipMetadataV2, factors: &ClipFactors) -> u8 {
+    let events = clip.get_all_events();
+    let event = events
+        .iter()
+        .map(|event| event_value(&event.event_type))
+        .fold(0.0, f64::max);
+
+    // Objectives and deaths have no kill participation; count them as average
+    let participation = if factors.kills == 0 {
+        0.5
+    } else {
+        factors.kills_involved as f64 / factors.kills as f64
+    };
+
+    // Shutdowns and plays from behind swing the most gold
+    let gold = if factors.shutdown {
+        1.0
+    } else {
+        (-factors.gold_lead as f64 / COMEBACK_GOLD_DEFICIT as f64).clamp(0.0, 1.0)
+    };
+
+    // The less health left, the closer the play
+    let health = factors
+        .health_percent
+        .map_or(0.5, |percent| 1.0 - percent as f64 / 100.0);
+
+    let game_time = (clip.game_time_start / LATE_GAME_SECS).clamp(0.0, 1.0);
+    let density =
+        (events.len().saturating_sub(1) as f64 / (MAX_DENSITY_EVENTS - 1) as f64).min(1.0);
+
+    let total = event * EVENT_WEIGHT
+        + participation * PARTICIPATION_WEIGHT
+        + gold * GOLD_WEIGHT
+        + health * HEALTH_WEIGHT
+        + game_time * GAME_TIME_WEIGHT
+        + density * DENSITY_WEIGHT;
+    total.round().clamp(0.0, 100.0) as u8
+}
+
+/// How much an event type alone makes a highlight (0.0-1.0)
+fn event_value(event_type: &EventType) -> f64 {
+    match event_type {
+        EventType::Multikill(n) if *n >= 5 => 1.0,
+        EventType::Multikill(4) => 0.85,
+        EventType::Multikill(3) => 0.7,
+        EventType::Multikill(_) => 0.55,
+        EventType::Custom(name) if name == "Steal" => 0.8,
+        EventType::BaronKill => 0.7,
+        EventType::Ace => 0.6,
+        EventType::Custom(name) if name == "ClutchPlay" => 0.6,
+        EventType::DragonKill => 0.5,
+        EventType::FirstBlood => 0.45,
+        EventType::ChampionKill | EventType::InhibitorKill => 0.35,
+        EventType::TurretKill | EventType::Custom(_) => 0.25,
+        EventType::Death => 0.2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::models::ClipMetadata;
+
+    fn clip(event_type: EventType, game_time: f64) -> ClipMetadataV2 {
+        ClipMetadataV2::from(ClipMetadata {
+            file_path: "clip.mp4".to_string(),
+            thumbnail_path: None,
+            event_type,
+            event_time: game_time,
+            priority: 1,
+            duration: 20.0,
+            created_at: chrono::Utc::now(),
+        })
+    }
+
+    #[test]
+    fn test_score_ranks_clutch_kills_above_plain_ones() {
+        let kill = clip(EventType::ChampionKill, 600.0);
+        let plain = ClipFactors {
+            kills: 1,
+            kills_involved: 1,
+            health_percent: Some(90),
+            ..Default::default()
+        };
+        let clutch = ClipFactors {
+            shutdown: true,
+            health_percent: Some(5),
+            ..plain.clone()
+        };
+
+        assert!(score(&kill, &clutch) > score(&kill, &plain) + 20);
+        assert!(score(&clip(EventType::Multikill(5), 600.0), &plain) > score(&kill, &plain));
+        assert!(score(&clip(EventType::ChampionKill, 2400.0), &plain) > score(&kill, &plain));
+    }
+
+    #[test]
+    fn test_score_range() {
+        let mut best = clip(EventType::Multikill(5), 3000.0);
+        for _ in 0..4 {
+            best.merged_events.push(best.primary_event.clone());
+        }
+        let factors = ClipFactors {
+            kills: 5,
+            kills_involved: 5,
+            shutdown: true,
+            health_percent: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(score(&best, &factors), 100);
+
+        let worst = clip(EventType::Death, 0.0);
+        let factors = ClipFactors {
+            health_percent: Some(100),
+            gold_lead: 10_000,
+            ..Default::default()
+        };
+        assert_eq!(score(&worst, &factors), 15);
+    }
+}
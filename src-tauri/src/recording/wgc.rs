@@ -0,0 +1,240 @@
+//! Windows Graphics Capture input for the replay buffer
+//!
+//! gdigrab copies the screen through GDI, which is slow at high resolutions
+//! and misses frames of fullscreen-exclusive games. WGC gets the frames from
+//! the compositor instead; they're written to FFmpeg's stdin as raw BGRA
+//! video (`rawvideo_input_args`). Where WGC can't start (older Windows 10
+//! builds, remote sessions) the recorder falls back to gdigrab.
+//!
+//! 8-bit captures of an HDR display come out washed out, so HDR displays are
+//! captured as linear scRGB half floats and tone mapped to SDR by FFmpeg
+//! (`HDR_TONEMAP_FILTER`).
+
+use super::display::CaptureDisplay;
+use anyhow::Result;
+use std::io::Write;
+use std::process::{ChildStdin, Command, Stdio};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use windows_capture::capture::{CaptureControl, Context, GraphicsCaptureApiHandler};
+use windows_capture::frame::Frame;
+use windows_capture::graphics_capture_api::InternalCaptureControl;
+use windows_capture::monitor::Monitor;
+use windows_capture::settings::{ColorFormat, CursorCaptureSettings, DrawBorderSettings, Settings};
+
+type CaptureError = Box<dyn std::error::Error + Send + Sync>;
+
+/// FFmpeg's stdin; taking it out ends FFmpeg's input
+pub type FrameSink = Arc<parking_lot::Mutex<Option<ChildStdin>>>;
+
+/// Tone mapping from linear scRGB (HDR capture) to SDR BT.709 video
+pub const HDR_TONEMAP_FILTER: &str = "format=gbrpf32le,tonemap=tonemap=hable:desat=0,zscale=tin=linear:pin=bt709:t=bt709:p=bt709:m=bt709:r=tv,format=yuv420p";
+
+/// Whether FFmpeg can read half-float frames and has zscale/tonemap
+///
+/// Probed once; without them HDR displays are captured as 8-bit SDR.
+pub fn hdr_supported() -> bool {
+    static HDR_SUPPORT: OnceLock<bool> = OnceLock::new();
+    *HDR_SUPPORT.get_or_init(|| {
+        let has_pixel_format = Command::new("ffmpeg")
+            .args(["-hide_banner", "-pix_fmts"])
+            .stderr(Stdio::null())
+            .output()
+            .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains("rgbaf16le"));
+
+        let has_filters = Command::new("ffmpeg")
+            .args(["-f", "lavfi", "-i", "nullsrc=s=64x64:d=0.1"])
+            .args(["-vf", HDR_TONEMAP_FILTER, "-f", "null", "-"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success());
+
+        let supported = has_pixel_format && has_filters;
+        tracing::info!("HDR tone mapping available: {}", supported);
+        supported
+    })
+}
+
+/// FFmpeg input reading `display`'s frames from stdin
+///
+/// WGC only delivers a frame when the screen changes, so frames are
+/// timestamped on arrival and the segment output fills the gaps to a
+/// constant frame rate. `hdr` frames are linear scRGB half floats.
+pub fn rawvideo_input_args(display: &CaptureDisplay, fps: u32, hdr: bool) -> Vec<String> {
+    vec![
+        "-f".to_string(),
+        "rawvideo".to_string(),
+        "-pix_fmt".to_string(),
+        if hdr { "rgbaf16le" } else { "bgra" }.to_string(),
+        "-video_size".to_string(),
+        format!("{}x{}", display.width, display.height),
+        "-framerate".to_string(),
+        fps.to_string(),
+        "-use_wallclock_as_timestamps".to_string(),
+        "1".to_string(),
+        "-i".to_string(),
+        "-".to_string(), // Frames come from WgcCapture
+    ]
+}
+
+/// WGC capture of one display, writing into a `FrameSink`
+pub struct WgcCapture {
+    control: CaptureControl<FramePipe, CaptureError>,
+    sink: FrameSink,
+}
+
+impl WgcCapture {
+    /// Start capturing `display` at up to `fps`, as half floats if `hdr`
+    ///
+    /// Frames are dropped until FFmpeg's stdin is put into `sink`.
+    pub fn start(
+        display: &CaptureDisplay,
+        fps: u32,
+        capture_cursor: bool,
+        hdr: bool,
+        sink: FrameSink,
+    ) -> Result<Self> {
+        let find_monitor = || -> Result<Monitor> {
+            Monitor::enumerate()
+                .map_err(|e| anyhow::anyhow!("Failed to enumerate monitors: {}", e))?
+                .into_iter()
+                .find(|monitor| monitor.device_name().is_ok_and(|name| name == display.id))
+                .ok_or_else(|| anyhow::anyhow!("Display not found: {}", display.id))
+        };
+        let target = || PipeTarget {
+            sink: Arc::clone(&sink),
+            width: display.width,
+            height: display.height,
+            frame_interval: Duration::from_secs(1) / fps.max(1),
+        };
+        let cursor = if capture_cursor {
+            CursorCaptureSettings::WithCursor
+        } else {
+            CursorCaptureSettings::WithoutCursor
+        };
+        let color_format = if hdr {
+            ColorFormat::Rgba16F
+        } else {
+            ColorFormat::Bgra8
+        };
+
+        // Hiding the capture border needs Windows 11; Windows 10 always shows it
+        let settings = Settings::new(
+            find_monitor()?,
+            cursor,
+            DrawBorderSettings::WithoutBorder,
+            color_format,
+            target(),
+        );
+        let control = match FramePipe::start_free_threaded(settings) {
+            Ok(control) => control,
+            Err(_) => {
+                let settings = Settings::new(
+                    find_monitor()?,
+                    cursor,
+                    DrawBorderSettings::Default,
+                    color_format,
+                    target(),
+                );
+                FramePipe::start_free_threaded(settings).map_err(|e| {
+                    anyhow::anyhow!("Failed to start Windows Graphics Capture: {}", e)
+                })?
+            }
+        };
+
+        tracing::info!(
+            "Windows Graphics Capture started: {} (HDR: {})",
+            display.id,
+            hdr
+        );
+        Ok(Self { control, sink })
+    }
+
+    /// Whether capture ended on its own (display disconnected or resized)
+    pub fn is_finished(&self) -> bool {
+        self.control.is_finished()
+    }
+
+    /// Stop capturing and close FFmpeg's input
+    pub fn stop(self) {
+        if let Err(e) = self.control.stop() {
+            tracing::warn!("Failed to stop Windows Graphics Capture: {}", e);
+        }
+        self.sink.lock().take();
+    }
+}
+
+/// Where `FramePipe` writes, passed through the capture settings
+struct PipeTarget {
+    sink: FrameSink,
+    width: u32,
+    height: u32,
+    frame_interval: Duration,
+}
+
+/// Capture handler writing each frame to FFmpeg
+struct FramePipe {
+    target: PipeTarget,
+    last_frame: Option<Instant>,
+}
+
+impl GraphicsCaptureApiHandler for FramePipe {
+    type Flags = PipeTarget;
+    type Error = CaptureError;
+
+    fn new(ctx: Context<Self::Flags>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            target: ctx.flags,
+            last_frame: None,
+        })
+    }
+
+    fn on_frame_arrived(
+        &mut self,
+        frame: &mut Frame,
+        capture_control: InternalCaptureControl,
+    ) -> Result<(), Self::Error> {
+        // rawvideo can't change size mid-stream: end the input so the
+        // recorder restarts FFmpeg with the new size
+        if frame.width() != self.target.width || frame.height() != self.target.height {
+            tracing::warn!(
+                "Display resized to {}x{}, ending capture",
+                frame.width(),
+                frame.height()
+            );
+            self.target.sink.lock().take();
+            capture_control.stop();
+            return Ok(());
+        }
+
+        // High refresh rate displays deliver more frames than are recorded
+        if self
+            .last_frame
+            .is_some_and(|last| last.elapsed() < self.target.frame_interval)
+        {
+            return Ok(());
+        }
+
+        let mut sink = self.target.sink.lock();
+        let Some(stdin) = sink.as_mut() else {
+            return Ok(());
+        };
+
+        let mut buffer = frame.buffer()?;
+        if let Err(e) = stdin.write_all(buffer.as_nopadding_buffer()?) {
+            // FFmpeg exited
+            tracing::debug!("Failed to write frame to FFmpeg: {}", e);
+            sink.take();
+            capture_control.stop();
+        }
+        self.last_frame = Some(Instant::now());
+
+        Ok(())
+    }
+
+    fn on_closed(&mut self) -> Result<(), Self::Error> {
+        self.target.sink.lock().take();
+        Ok(())
+    }
+}
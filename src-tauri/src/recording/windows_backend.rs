@@ -1,28 +1,44 @@
 #![allow(clippy::upper_case_acronyms)]
 use super::audio::AudioConfig;
+use super::wgc::{rawvideo_input_args, FrameSink, WgcCapture, HDR_TONEMAP_FILTER};
 use super::{GameEvent, RecordingStats, RecordingStatus};
+use crate::events::{AppEvent, ClipSaved, EventBus, RecordingStatusChanged};
+use crate::settings::models::CaptureBackend;
 use crate::storage::GameMetadata;
 use crate::utils::circuit_breaker::{
     CircuitBreaker as ProductionCircuitBreaker, CircuitBreakerConfig,
 };
-use crate::utils::retry::{retry_with_backoff, RetryConfig};
+use crate::utils::retry::{self, retry_with_backoff, RetryConfig, RetryOperation};
+use crate::video::processor::{ConcatTarget, MediaInfo};
+use crate::video::VideoProcessor;
 use anyhow::{Context as AnyhowContext, Result};
 use parking_lot::RwLock;
 use std::collections::VecDeque;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock as TokioRwLock;
+use tokio::sync::{Mutex as TokioMutex, RwLock as TokioRwLock};
 
 // Configuration constants
 const SEGMENT_DURATION_SECS: u64 = 10;
 const BUFFER_SEGMENTS: usize = 6; // 60 seconds total (6 × 10s)
 const MAX_CLIP_DURATION_SECS: f64 = 60.0;
+const DEFAULT_CLIP_SAVE_TIMEOUT: Duration = Duration::from_secs(60);
+const SEGMENT_PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long FFmpeg gets to close the current segment before it's killed
+const FFMPEG_STOP_TIMEOUT: Duration = Duration::from_secs(5);
+/// Re-encoding a clip gets this many times the clip save timeout
+const REENCODE_TIMEOUT_FACTOR: u32 = 3;
 const DEFAULT_BITRATE: u32 = 20_000_000; // 20 Mbps for 1080p60
 const DEFAULT_FPS: u32 = 60;
 
-// Error recovery configuration
+// Low-spec (performance) mode preset
+const LOW_SPEC_RESOLUTION: (u32, u32) = (1280, 720);
+const LOW_SPEC_FPS: u32 = 30;
+const LOW_SPEC_MAX_BITRATE: u32 = 4_000_000; // 4 Mbps cap for 720p30 x264
+
+// Error recovery configuration (process startup uses the retry settings)
 const FFMPEG_RETRY_CONFIG: RetryConfig = RetryConfig {
     max_attempts: 3,
     initial_delay: Duration::from_millis(500),
@@ -55,10 +71,33 @@ pub struct WindowsRecorder {
     status: Arc<TokioRwLock<RecordingStatus>>,
     stats: Arc<RwLock<RecordingStats>>,
     output_dir: PathBuf,
+    /// Where saved clips go (defaults to `output_dir`)
+    clips_dir: PathBuf,
+    /// Limit for merging segments into a clip before FFmpeg is killed
+    clip_save_timeout: Duration,
     current_game: Arc<TokioRwLock<Option<GameMetadata>>>,
     segment_buffer: Arc<TokioRwLock<SegmentBuffer>>,
     config: RecordingConfig,
+    /// Standard config saved while low-spec mode is active (restored on disable)
+    standard_config: Option<RecordingConfig>,
+    /// Encoder found at startup, used when the settings leave it on auto
+    detected_encoder: HardwareEncoder,
+    /// Config the running segment recorder restarts FFmpeg with
+    config_update: Arc<parking_lot::Mutex<Option<RecordingConfig>>>,
     circuit_breaker: Arc<ProductionCircuitBreaker>,
+    events: Arc<EventBus>,
+    /// Corrupt segments skipped by the last save, with the clip they were
+    /// missing from
+    dropped_segments: Arc<parking_lot::Mutex<Option<(PathBuf, Vec<PathBuf>)>>>,
+    /// Saves waiting for the one in progress, served in call order
+    save_queue: Arc<TokioMutex<()>>,
+}
+
+/// Notify the frontend of a status change
+fn emit_status(events: &EventBus, status: RecordingStatus) {
+    events.emit(AppEvent::RecordingStatusChanged(RecordingStatusChanged {
+        status,
+    }));
 }
 
 #[derive(Clone)]
@@ -69,6 +108,18 @@ struct RecordingConfig {
     codec: VideoCodec,
     audio: AudioConfig,
     hardware_encoder: HardwareEncoder,
+    /// Low-spec mode: ultrafast software x264, scaled capture, no audio filters
+    low_spec: bool,
+    /// Webcam recorded next to each segment (DirectShow device name)
+    facecam_device: Option<String>,
+    /// Display to capture (`CaptureDisplay::id`, primary display if unset)
+    capture_display: Option<String>,
+    /// WGC falls back to gdigrab if it can't start
+    capture_backend: CaptureBackend,
+    /// Draw the mouse cursor into the recording
+    capture_cursor: bool,
+    /// Capture HDR displays as 8-bit SDR instead of tone mapping them
+    force_sdr: bool,
 }
 
 impl Default for RecordingConfig {
@@ -80,6 +131,12 @@ impl Default for RecordingConfig {
             codec: VideoCodec::HEVC,
             audio: AudioConfig::default(),
             hardware_encoder: HardwareEncoder::detect(),
+            low_spec: false,
+            facecam_device: None,
+            capture_display: None,
+            capture_backend: CaptureBackend::default(),
+            capture_cursor: true,
+            force_sdr: false,
         }
     }
 }
@@ -95,6 +152,7 @@ impl RecordingConfig {
         let base_bpp = match codec {
             VideoCodec::HEVC => 0.10, // H.265 is ~50% more efficient
             VideoCodec::H264 => 0.15, // H.264 baseline
+            VideoCodec::AV1 => 0.08,  // AV1 is ~30% more efficient than H.265
         };
 
         // FPS scaling factor
@@ -111,11 +169,38 @@ impl RecordingConfig {
         (bitrate / 1_000_000) * 1_000_000
     }
 
+    /// Derive the low-spec variant of this config
+    ///
+    /// 720p30, software H.264 with capped bitrate. Audio settings are kept but
+    /// captured without filters (see `AudioConfig::build_unfiltered_ffmpeg_args`).
+    fn to_low_spec(&self) -> Self {
+        Self {
+            resolution: LOW_SPEC_RESOLUTION,
+            fps: LOW_SPEC_FPS,
+            bitrate: Self::calculate_optimal_bitrate(
+                LOW_SPEC_RESOLUTION,
+                LOW_SPEC_FPS,
+                VideoCodec::H264,
+            )
+            .min(LOW_SPEC_MAX_BITRATE),
+            codec: VideoCodec::H264,
+            audio: self.audio.clone(),
+            hardware_encoder: HardwareEncoder::Software,
+            low_spec: true,
+            facecam_device: None,
+            capture_display: self.capture_display.clone(),
+            capture_backend: self.capture_backend,
+            capture_cursor: self.capture_cursor,
+            force_sdr: self.force_sdr,
+        }
+    }
+
     /// Get the appropriate encoder name for the current config
     fn get_encoder_name(&self) -> &'static str {
         match self.codec {
             VideoCodec::HEVC => self.hardware_encoder.hevc_encoder(),
             VideoCodec::H264 => self.hardware_encoder.h264_encoder(),
+            VideoCodec::AV1 => self.hardware_encoder.av1_encoder(),
         }
     }
 }
@@ -124,6 +209,7 @@ impl RecordingConfig {
 enum VideoCodec {
     HEVC, // H.265 (preferred for quality/size)
     H264, // H.264 (fallback for compatibility)
+    AV1,  // AV1 (RTX 40 / Arc / RX 7000 and newer)
 }
 
 /// Hardware encoder types
@@ -156,19 +242,30 @@ impl HardwareEncoder {
         }
     }
 
+    /// Get FFmpeg encoder name for AV1
+    fn av1_encoder(&self) -> &'static str {
+        match self {
+            Self::NVENC => "av1_nvenc",
+            Self::QSV => "av1_qsv",
+            Self::AMF => "av1_amf",
+            Self::Software => "libsvtav1",
+        }
+    }
+
     /// Get optimal encoding preset for this encoder
     /// Presets vary by encoder type
-    fn get_preset(&self) -> &'static str {
+    fn get_preset(&self, codec: VideoCodec) -> &'static str {
         match self {
             Self::NVENC => "p4",     // NVENC: p1 (fastest) to p7 (slowest), p4 is balanced
             Self::QSV => "balanced", // QSV: balanced quality/speed
             Self::AMF => "balanced", // AMF: balanced quality/speed
+            Self::Software if codec == VideoCodec::AV1 => "10", // SVT-AV1: 0-13, 10+ is real-time
             Self::Software => "medium", // x265: medium quality/speed
         }
     }
 
     /// Get additional encoder-specific options
-    fn get_encoder_options(&self) -> Vec<(&'static str, &'static str)> {
+    fn get_encoder_options(&self, codec: VideoCodec) -> Vec<(&'static str, &'static str)> {
         match self {
             Self::NVENC => vec![
                 ("-rc", "vbr"),          // Variable bitrate for better quality
@@ -184,12 +281,29 @@ impl HardwareEncoder {
                 ("-rc", "vbr_latency"),   // VBR for quality with low latency
                 ("-quality", "balanced"), // Balanced quality preset
             ],
+            Self::Software if codec == VideoCodec::AV1 => vec![],
             Self::Software => vec![
                 ("-x265-params", "aq-mode=3"), // Best adaptive quantization
             ],
         }
     }
 
+    /// Check whether this encoder can produce AV1
+    ///
+    /// Probed once per encoder.
+    fn supports_av1(&self) -> bool {
+        static AV1_SUPPORT: [OnceLock<bool>; 4] = [const { OnceLock::new() }; 4];
+        *AV1_SUPPORT[*self as usize].get_or_init(|| {
+            let supported = Self::test_encoder(self.av1_encoder());
+            tracing::info!(
+                "AV1 encoder {} available: {}",
+                self.av1_encoder(),
+                supported
+            );
+            supported
+        })
+    }
+
     /// Detect available hardware encoder
     /// Tests encoders in priority order and returns first working one
     fn detect() -> Self {
@@ -229,6 +343,86 @@ impl HardwareEncoder {
     }
 }
 
+/// Prefix of the folders segments are pinned in while a clip is saved
+const SNAPSHOT_DIR_PREFIX: &str = "snapshot_";
+
+/// Segments pinned for one clip save
+///
+/// Each segment (and its webcam recording) is hard-linked into a private
+/// folder, or copied where links aren't supported, so rotation can delete
+/// the buffer's file mid-concat without touching the clip's. The folder is
+/// removed on drop.
+struct SegmentSnapshot {
+    dir: PathBuf,
+    /// (buffer segment, pinned link), oldest first
+    segments: Vec<(PathBuf, PathBuf)>,
+}
+
+impl SegmentSnapshot {
+    fn pin<'a>(segments: impl IntoIterator<Item = &'a PathBuf>, dir: PathBuf) -> Result<Self> {
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create snapshot folder: {:?}", dir))?;
+        let mut snapshot = Self {
+            dir,
+            segments: Vec::new(),
+        };
+
+        for segment in segments {
+            // Already rotated out before the snapshot
+            if !segment.exists() {
+                continue;
+            }
+            let Some(name) = segment.file_name() else {
+                continue;
+            };
+
+            let pinned = snapshot.dir.join(name);
+            link_or_copy(segment, &pinned)?;
+            let facecam = super::facecam_path(segment);
+            if facecam.exists() {
+                link_or_copy(&facecam, &super::facecam_path(&pinned))?;
+            }
+            snapshot.segments.push((segment.clone(), pinned));
+        }
+
+        Ok(snapshot)
+    }
+
+    /// Pinned segments, oldest first
+    fn paths(&self) -> Vec<PathBuf> {
+        self.segments
+            .iter()
+            .map(|(_, pinned)| pinned.clone())
+            .collect()
+    }
+
+    /// Buffer segment a pinned path stands for
+    fn original(&self, pinned: &Path) -> PathBuf {
+        self.segments
+            .iter()
+            .find(|(_, path)| path == pinned)
+            .map(|(original, _)| original.clone())
+            .unwrap_or_else(|| pinned.to_path_buf())
+    }
+}
+
+impl Drop for SegmentSnapshot {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_dir_all(&self.dir) {
+            tracing::warn!("Failed to remove segment snapshot {:?}: {}", self.dir, e);
+        }
+    }
+}
+
+fn link_or_copy(from: &Path, to: &Path) -> Result<()> {
+    if std::fs::hard_link(from, to).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(from, to)
+        .map(|_| ())
+        .with_context(|| format!("Failed to pin segment: {:?}", from))
+}
+
 /// Manages circular buffer of video segments
 struct SegmentBuffer {
     segments: VecDeque<PathBuf>,
@@ -240,6 +434,7 @@ struct SegmentBuffer {
 impl SegmentBuffer {
     fn new(temp_dir: PathBuf) -> Result<Self> {
         std::fs::create_dir_all(&temp_dir)?;
+        remove_stale_snapshots(&temp_dir);
 
         Ok(Self {
             segments: VecDeque::with_capacity(BUFFER_SEGMENTS),
@@ -259,6 +454,7 @@ impl SegmentBuffer {
                         .with_context(|| format!("Failed to remove old segment: {:?}", old_path))?;
                     tracing::debug!("Removed old segment: {:?}", old_path);
                 }
+                let _ = std::fs::remove_file(super::facecam_path(&old_path));
             }
         }
 
@@ -275,15 +471,77 @@ impl SegmentBuffer {
         Ok(())
     }
 
-    /// Get all segments in chronological order
-    fn get_all_segments(&self) -> Vec<PathBuf> {
-        self.segments.iter().cloned().collect()
+    /// Pin the current segments for a clip save
+    ///
+    /// Holding the buffer lock while linking keeps rotation from removing a
+    /// segment halfway through.
+    fn snapshot(&self) -> Result<SegmentSnapshot> {
+        let dir = self
+            .temp_dir
+            .join(format!("{}{}", SNAPSHOT_DIR_PREFIX, uuid::Uuid::new_v4()));
+        SegmentSnapshot::pin(&self.segments, dir)
     }
 
     /// Generate path for next segment
     fn next_segment_path(&self) -> PathBuf {
-        self.temp_dir
-            .join(format!("segment_{:04}.mp4", self.current_segment))
+        self.segment_path(self.current_segment)
+    }
+
+    fn segment_path(&self, number: usize) -> PathBuf {
+        self.temp_dir.join(format!("segment_{:04}.mp4", number))
+    }
+
+    /// Output pattern for FFmpeg's segment muxer, numbered like `segment_path`
+    fn segment_pattern(&self) -> PathBuf {
+        self.temp_dir.join("segment_%04d.mp4")
+    }
+
+    /// Add the segments FFmpeg has finished writing, returning how many were empty
+    ///
+    /// The segment muxer only opens the next file once the previous one is
+    /// closed, so a segment is finished once its successor exists, or once
+    /// FFmpeg has exited (`writer_exited`).
+    fn add_finished_segments(&mut self, writer_exited: bool) -> Result<usize> {
+        let mut empty = 0;
+        loop {
+            let path = self.next_segment_path();
+            let finished = self.segment_path(self.current_segment + 1).exists()
+                || (writer_exited && path.exists());
+            if !finished {
+                return Ok(empty);
+            }
+
+            if std::fs::metadata(&path).map_or(0, |m| m.len()) > 0 {
+                self.add_segment(path)?;
+            } else {
+                tracing::warn!("Segment file is empty, not adding to buffer: {:?}", path);
+                let _ = std::fs::remove_file(&path);
+                let _ = std::fs::remove_file(super::facecam_path(&path));
+                self.current_segment += 1;
+                empty += 1;
+            }
+        }
+    }
+
+    /// Remove files numbered from the next segment on
+    ///
+    /// Left over from an earlier session, they'd look like finished segments
+    /// as soon as FFmpeg starts writing below them.
+    fn remove_unbuffered_segments(&self) {
+        let Ok(entries) = std::fs::read_dir(&self.temp_dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let number = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_prefix("segment_"))
+                .and_then(|rest| rest.split('.').next())
+                .and_then(|number| number.parse::<usize>().ok());
+            if number.is_some_and(|number| number >= self.current_segment) {
+                let _ = std::fs::remove_file(entry.path());
+            }
+        }
     }
 
     /// Clear all segments
@@ -292,21 +550,62 @@ impl SegmentBuffer {
             if segment.exists() {
                 std::fs::remove_file(&segment)?;
             }
+            let _ = std::fs::remove_file(super::facecam_path(&segment));
         }
         self.current_segment = 0;
         Ok(())
     }
 }
 
+/// Snapshots left behind by a crash mid-save
+fn remove_stale_snapshots(temp_dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(temp_dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let is_snapshot = entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name.starts_with(SNAPSHOT_DIR_PREFIX));
+        if is_snapshot && entry.path().is_dir() {
+            let _ = std::fs::remove_dir_all(entry.path());
+        }
+    }
+}
+
+/// Segment muxer output, cut every `SEGMENT_DURATION_SECS`
+///
+/// A keyframe is forced at each cut so segments start cleanly, and each one
+/// starts at timestamp zero for the concat demuxer.
+fn segment_output_args(start_number: usize, pattern: &Path) -> Vec<String> {
+    vec![
+        "-force_key_frames".to_string(),
+        format!("expr:gte(t,n_forced*{})", SEGMENT_DURATION_SECS),
+        "-f".to_string(),
+        "segment".to_string(),
+        "-segment_time".to_string(),
+        SEGMENT_DURATION_SECS.to_string(),
+        "-segment_format".to_string(),
+        "mp4".to_string(),
+        "-segment_start_number".to_string(),
+        start_number.to_string(),
+        "-reset_timestamps".to_string(),
+        "1".to_string(),
+        "-y".to_string(), // Overwrite output files
+        pattern.to_string_lossy().to_string(),
+    ]
+}
+
 /// FFmpeg-based recording handler for segment capture
 #[cfg(target_os = "windows")]
 struct SegmentRecorder {
     segment_buffer: Arc<TokioRwLock<SegmentBuffer>>,
     status: Arc<TokioRwLock<RecordingStatus>>,
+    stats: Arc<RwLock<RecordingStats>>,
     config: RecordingConfig,
     ffmpeg_process: Option<Child>,
-    current_segment_start: Instant,
-    current_segment_path: PathBuf,
+    /// Feeds FFmpeg when capturing through WGC
+    capture: Option<WgcCapture>,
     is_recording: Arc<parking_lot::Mutex<bool>>,
     circuit_breaker: Arc<ProductionCircuitBreaker>,
 }
@@ -316,26 +615,33 @@ impl SegmentRecorder {
     fn new(
         segment_buffer: Arc<TokioRwLock<SegmentBuffer>>,
         status: Arc<TokioRwLock<RecordingStatus>>,
+        stats: Arc<RwLock<RecordingStats>>,
         config: RecordingConfig,
         circuit_breaker: Arc<ProductionCircuitBreaker>,
     ) -> Self {
         Self {
             segment_buffer,
             status,
+            stats,
             config,
             ffmpeg_process: None,
-            current_segment_start: Instant::now(),
-            current_segment_path: PathBuf::new(),
+            capture: None,
             is_recording: Arc::new(parking_lot::Mutex::new(false)),
             circuit_breaker,
         }
     }
 
-    /// Start FFmpeg recording for a new segment
+    /// Start the FFmpeg process writing segments for the buffer
+    ///
+    /// One process runs for the whole session and the segment muxer cuts the
+    /// output every `SEGMENT_DURATION_SECS`, so there's no gap between
+    /// segments the way restarting FFmpeg for each one left.
     async fn start_segment_recording(&mut self) -> Result<()> {
-        // Get next segment path
+        // Continue numbering after the segments already in the buffer
         let buffer = self.segment_buffer.read().await;
-        self.current_segment_path = buffer.next_segment_path();
+        buffer.remove_unbuffered_segments();
+        let start_number = buffer.current_segment;
+        let segment_pattern = buffer.segment_pattern();
         drop(buffer);
 
         // Get encoder name based on detected hardware
@@ -345,36 +651,100 @@ impl SegmentRecorder {
         let bitrate = format!("{}k", self.config.bitrate / 1000);
 
         tracing::info!(
-            "Starting FFmpeg segment recording: {:?} (duration: {}s, bitrate: {}, encoder: {})",
-            self.current_segment_path,
+            "Starting FFmpeg segment recording: {:?} (segment: {}s, bitrate: {}, encoder: {})",
+            segment_pattern,
             SEGMENT_DURATION_SECS,
             bitrate,
             video_encoder
         );
 
-        // Build audio arguments from AudioConfig
-        let (audio_inputs, audio_filters, audio_maps, audio_codec) =
-            self.config.audio.build_ffmpeg_args();
+        // Build audio arguments from AudioConfig (no filter graph in low-spec mode)
+        let (audio_inputs, audio_filters, audio_maps, audio_codec) = if self.config.low_spec {
+            let (inputs, maps, codec) = self.config.audio.build_unfiltered_ffmpeg_args();
+            (inputs, vec![], maps, codec)
+        } else {
+            self.config.audio.build_ffmpeg_args()
+        };
+
+        let preset = if self.config.low_spec {
+            "ultrafast"
+        } else {
+            self.config.hardware_encoder.get_preset(self.config.codec)
+        };
+
+        // Capture one display (the desktop spans all of them)
+        let displays = super::display::list_displays().unwrap_or_else(|e| {
+            tracing::warn!(
+                "Failed to list displays, capturing the whole desktop: {}",
+                e
+            );
+            Vec::new()
+        });
+        let display =
+            super::display::select_display(&displays, self.config.capture_display.as_deref());
+        tracing::info!("Capturing display: {:?}", display);
+
+        // HDR displays are tone mapped to SDR, when FFmpeg can
+        let hdr_display = display.is_some_and(|display| display.hdr) && !self.config.force_sdr;
+        let hdr = hdr_display && super::wgc::hdr_supported();
+
+        // Prefer WGC, falling back to gdigrab if it can't start
+        let frame_sink = FrameSink::default();
+        let capture = match (self.config.capture_backend, display) {
+            (CaptureBackend::Wgc, Some(display)) => WgcCapture::start(
+                display,
+                self.config.fps,
+                self.config.capture_cursor,
+                hdr,
+                Arc::clone(&frame_sink),
+            )
+            .map_err(|e| tracing::warn!("WGC unavailable, using gdigrab: {}", e))
+            .ok(),
+            _ => None,
+        };
+
+        // Only the half-float WGC capture can be tone mapped
+        let tone_map = hdr && capture.is_some();
+        if hdr_display && !tone_map {
+            tracing::warn!(
+                "Recording an HDR display without tone mapping, colors will look washed out"
+            );
+        }
 
         // Build complete FFmpeg command
-        let mut ffmpeg_args = vec![
-            "-f".to_string(),
-            "gdigrab".to_string(), // Windows GDI screen capture
-            "-framerate".to_string(),
-            self.config.fps.to_string(),
-            "-i".to_string(),
-            "desktop".to_string(), // Capture entire desktop
-        ];
+        let mut ffmpeg_args = match (&capture, display) {
+            (Some(_), Some(display)) => rawvideo_input_args(display, self.config.fps, tone_map),
+            _ => super::display::gdigrab_input_args(
+                display,
+                self.config.fps,
+                self.config.capture_cursor,
+            ),
+        };
+
+        // Webcam input goes after the desktop and audio inputs
+        let facecam_input = self.config.facecam_device.as_ref().map(|device| {
+            let index = 1 + audio_inputs.iter().filter(|arg| *arg == "-i").count();
+            (index, device)
+        });
 
         // Add audio inputs (microphone and/or system audio)
         ffmpeg_args.extend(audio_inputs);
 
+        if let Some((_, device)) = &facecam_input {
+            ffmpeg_args.extend(vec![
+                "-f".to_string(),
+                "dshow".to_string(),
+                "-i".to_string(),
+                format!("video={}", device),
+            ]);
+        }
+
         // Video encoding args
         ffmpeg_args.extend(vec![
             "-c:v".to_string(),
             video_encoder.to_string(), // Hardware encoder
             "-preset".to_string(),
-            self.config.hardware_encoder.get_preset().to_string(), // Encoder-specific preset
+            preset.to_string(), // Encoder-specific preset
             "-b:v".to_string(),
             bitrate.clone(), // Bitrate
             "-maxrate".to_string(),
@@ -385,9 +755,36 @@ impl SegmentRecorder {
             "yuv420p".to_string(), // Pixel format
         ]);
 
-        // Add encoder-specific optimization options
-        for (key, value) in self.config.hardware_encoder.get_encoder_options() {
-            ffmpeg_args.extend(vec![key.to_string(), value.to_string()]);
+        let mut video_filters = Vec::new();
+        if tone_map {
+            video_filters.push(HDR_TONEMAP_FILTER.to_string());
+        }
+
+        if self.config.low_spec {
+            // Downscale the desktop capture and skip quality-oriented encoder options
+            video_filters.push(format!(
+                "scale={}:{}",
+                self.config.resolution.0, self.config.resolution.1
+            ));
+            ffmpeg_args.extend(vec!["-tune".to_string(), "zerolatency".to_string()]);
+        } else {
+            // Scale to the configured height, keeping the display's aspect ratio
+            if display.is_some_and(|display| display.height != self.config.resolution.1) {
+                video_filters.push(format!("scale=-2:{}", self.config.resolution.1));
+            }
+
+            // Add encoder-specific optimization options
+            let options = self
+                .config
+                .hardware_encoder
+                .get_encoder_options(self.config.codec);
+            for (key, value) in options {
+                ffmpeg_args.extend(vec![key.to_string(), value.to_string()]);
+            }
+        }
+
+        if !video_filters.is_empty() {
+            ffmpeg_args.extend(vec!["-vf".to_string(), video_filters.join(",")]);
         }
 
         // Add audio filter_complex if audio is enabled
@@ -409,122 +806,171 @@ impl SegmentRecorder {
             ffmpeg_args.extend(audio_codec);
         }
 
-        // Duration and output
-        ffmpeg_args.extend(vec![
-            "-t".to_string(),
-            SEGMENT_DURATION_SECS.to_string(), // Duration
-            "-y".to_string(),                  // Overwrite output file
-            self.current_segment_path.to_str().unwrap().to_string(),
-        ]);
+        // Segmented output
+        ffmpeg_args.extend(segment_output_args(start_number, &segment_pattern));
+
+        // Second output: the webcam as its own file, so it can be placed
+        // anywhere in the frame at compose time
+        if let Some((index, _)) = facecam_input {
+            ffmpeg_args.extend(vec![
+                "-map".to_string(),
+                format!("{}:v", index),
+                "-c:v".to_string(),
+                "libx264".to_string(),
+                "-preset".to_string(),
+                "ultrafast".to_string(),
+                "-crf".to_string(),
+                "28".to_string(),
+                "-pix_fmt".to_string(),
+                "yuv420p".to_string(),
+            ]);
+            ffmpeg_args.extend(segment_output_args(
+                start_number,
+                &super::facecam_path(&segment_pattern),
+            ));
+        }
 
         // Start FFmpeg process with retry logic and circuit breaker protection
         // Clone necessary data for closure
         let ffmpeg_args_clone = ffmpeg_args.clone();
         let circuit_breaker = Arc::clone(&self.circuit_breaker);
+        let retry_config = retry::configured(RetryOperation::FfmpegStart);
 
         let child = circuit_breaker
             .call(|| async {
-                retry_with_backoff(FFMPEG_RETRY_CONFIG, "FFmpeg process startup", || async {
+                retry_with_backoff(retry_config, "FFmpeg process startup", || async {
                     // Spawn FFmpeg process (sync operation wrapped in async)
+                    // stdin takes WGC frames or the quit command; stderr is never
+                    // read, and a long-running process would block once a pipe
+                    // filled up
                     Command::new("ffmpeg")
                         .args(&ffmpeg_args_clone)
+                        .stdin(Stdio::piped())
                         .stdout(Stdio::null())
-                        .stderr(Stdio::piped())
+                        .stderr(Stdio::null())
                         .spawn()
                         .context("Failed to start FFmpeg process")
                 })
                 .await
             })
-            .await?;
+            .await;
+
+        let mut child = match child {
+            Ok(child) => child,
+            Err(e) => {
+                if let Some(capture) = capture {
+                    capture.stop();
+                }
+                return Err(e);
+            }
+        };
 
+        if let Some(capture) = capture {
+            *frame_sink.lock() = child.stdin.take();
+            self.capture = Some(capture);
+        }
+
+        crate::utils::cleanup::process::track(child.id());
         self.ffmpeg_process = Some(child);
-        self.current_segment_start = Instant::now();
         *self.is_recording.lock() = true;
 
         tracing::info!(
-            "FFmpeg segment recording started successfully: {:?}",
-            self.current_segment_path
+            "FFmpeg segment recording started successfully from segment {}",
+            start_number
         );
 
         Ok(())
     }
 
-    /// Stop current segment recording and add to buffer
+    /// Stop FFmpeg and add the segments it finished to the buffer
+    ///
+    /// FFmpeg is asked to quit so it closes the segment it's writing; it's
+    /// only killed if it doesn't within `FFMPEG_STOP_TIMEOUT`.
     async fn stop_segment_recording(&mut self) -> Result<()> {
         if let Some(mut process) = self.ffmpeg_process.take() {
-            tracing::debug!("Stopping FFmpeg segment: {:?}", self.current_segment_path);
+            tracing::debug!("Stopping FFmpeg segment recording");
 
-            // Try graceful termination first
-            match process.try_wait() {
-                Ok(Some(status)) => {
-                    tracing::debug!("FFmpeg process already exited with status: {}", status);
+            // The end of WGC's input makes FFmpeg finish like "q" does
+            if let Some(capture) = self.capture.take() {
+                capture.stop();
+            }
+
+            if let Some(mut stdin) = process.stdin.take() {
+                use std::io::Write;
+                if let Err(e) = stdin.write_all(b"q") {
+                    tracing::debug!("Failed to send quit to FFmpeg: {}", e);
                 }
-                Ok(None) => {
-                    // Process still running, kill it
-                    tracing::debug!("Terminating FFmpeg process");
-                    if let Err(e) = process.kill() {
-                        tracing::warn!("Failed to kill FFmpeg process: {}", e);
+            }
+
+            let deadline = Instant::now() + FFMPEG_STOP_TIMEOUT;
+            loop {
+                match process.try_wait() {
+                    Ok(Some(status)) => {
+                        tracing::debug!("FFmpeg process exited with status: {}", status);
+                        break;
                     }
-                    // Wait for process to terminate
-                    if let Err(e) = process.wait() {
-                        tracing::warn!("Failed to wait for FFmpeg process: {}", e);
+                    Ok(None) if Instant::now() < deadline => {
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                    }
+                    Ok(None) => {
+                        // Process still running, kill it
+                        tracing::warn!("FFmpeg didn't quit in time, terminating it");
+                        if let Err(e) = process.kill() {
+                            tracing::warn!("Failed to kill FFmpeg process: {}", e);
+                        }
+                        // Wait for process to terminate
+                        if let Err(e) = process.wait() {
+                            tracing::warn!("Failed to wait for FFmpeg process: {}", e);
+                        }
+                        break;
+                    }
+                    Err(e) => {
+                        tracing::error!("Error checking FFmpeg process status: {}", e);
+                        break;
                     }
-                }
-                Err(e) => {
-                    tracing::error!("Error checking FFmpeg process status: {}", e);
                 }
             }
+            crate::utils::cleanup::process::untrack(process.id());
 
             *self.is_recording.lock() = false;
 
-            // Verify segment file was created and has content
-            if self.current_segment_path.exists() {
-                let file_size = std::fs::metadata(&self.current_segment_path)
-                    .context("Failed to get segment file metadata")?
-                    .len();
-
-                if file_size > 0 {
-                    // Add completed segment to buffer
-                    let segment_path = self.current_segment_path.clone();
-
-                    let mut buffer = self.segment_buffer.write().await;
-                    if let Err(e) = buffer.add_segment(segment_path.clone()) {
-                        tracing::error!("Failed to add segment to buffer: {}", e);
-                    } else {
-                        tracing::info!(
-                            "Segment added to buffer: {:?} (size: {} bytes)",
-                            segment_path,
-                            file_size
-                        );
-                    }
-                } else {
-                    tracing::warn!(
-                        "Segment file is empty, not adding to buffer: {:?}",
-                        self.current_segment_path
-                    );
-                }
-            } else {
-                tracing::warn!("Segment file not found: {:?}", self.current_segment_path);
-            }
+            self.collect_segments(true).await;
         }
 
         Ok(())
     }
 
-    /// Rotate to a new segment
-    async fn rotate_segment(&mut self) -> Result<()> {
-        // Stop current recording
-        self.stop_segment_recording().await?;
+    /// Add the segments FFmpeg has finished since the last call to the buffer
+    async fn collect_segments(&self, process_exited: bool) {
+        let mut buffer = self.segment_buffer.write().await;
+        match buffer.add_finished_segments(process_exited) {
+            Ok(0) => {}
+            Ok(empty) => self.stats.write().dropped_segments += empty as u64,
+            Err(e) => tracing::error!("Failed to add segment to buffer: {}", e),
+        }
+    }
 
-        // Start new segment
-        self.start_segment_recording().await?;
+    /// Drop every buffered segment, numbering restarts at zero
+    async fn discard_segments(&self) {
+        let mut buffer = self.segment_buffer.write().await;
+        if let Err(e) = buffer.clear() {
+            tracing::error!("Failed to clear segment buffer: {}", e);
+        }
+    }
 
-        Ok(())
+    /// Whether an FFmpeg segment process is running
+    fn is_active(&self) -> bool {
+        self.ffmpeg_process.is_some()
     }
 
-    /// Check if recording should rotate based on duration
-    fn should_rotate(&self) -> bool {
-        self.current_segment_start.elapsed() >= Duration::from_secs(SEGMENT_DURATION_SECS)
+    /// Whether the FFmpeg process (or the WGC capture feeding it) ended
+    /// without being stopped
+    fn has_exited(&mut self) -> bool {
+        self.capture.as_ref().is_some_and(WgcCapture::is_finished)
+            || self
+                .ffmpeg_process
+                .as_mut()
+                .is_some_and(|process| !matches!(process.try_wait(), Ok(None)))
     }
 }
 
@@ -539,23 +985,77 @@ impl WindowsRecorder {
             CircuitBreakerConfig::aggressive(), // Critical service requires aggressive failure detection
         ));
 
+        let config = RecordingConfig::default();
+
         Ok(Self {
             status: Arc::new(TokioRwLock::new(RecordingStatus::Idle)),
             stats: Arc::new(RwLock::new(RecordingStats::default())),
+            clips_dir: output_dir.clone(),
+            clip_save_timeout: DEFAULT_CLIP_SAVE_TIMEOUT,
             output_dir,
             current_game: Arc::new(TokioRwLock::new(None)),
             segment_buffer: Arc::new(TokioRwLock::new(SegmentBuffer::new(temp_dir)?)),
-            config: RecordingConfig::default(),
+            detected_encoder: config.hardware_encoder,
+            config,
+            standard_config: None,
+            config_update: Arc::new(parking_lot::Mutex::new(None)),
             circuit_breaker,
+            events: Arc::new(EventBus::new()),
+            dropped_segments: Arc::new(parking_lot::Mutex::new(None)),
+            save_queue: Arc::new(TokioMutex::new(())),
         })
     }
 
+    /// Publish status changes and saved clips on `events`
+    pub fn with_events(mut self, events: Arc<EventBus>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Save clips somewhere other than the replay buffer folder
+    pub fn with_clips_dir(mut self, clips_dir: PathBuf) -> Self {
+        self.clips_dir = clips_dir;
+        self
+    }
+
+    /// Move the replay buffer folder (temp segments, recovered sessions)
+    ///
+    /// Only while the buffer is stopped; segments already in the old folder
+    /// are left there.
+    pub async fn set_output_dir(&mut self, output_dir: PathBuf) -> Result<()> {
+        if *self.status.read().await != RecordingStatus::Idle {
+            anyhow::bail!("Stop the replay buffer before moving its folder");
+        }
+
+        let buffer = SegmentBuffer::new(output_dir.join("temp_segments"))?;
+        *self.segment_buffer.write().await = buffer;
+        tracing::info!("Replay buffer folder changed to {:?}", output_dir);
+        self.output_dir = output_dir;
+        Ok(())
+    }
+
+    /// Change where clips are saved (takes effect on the next clip)
+    pub fn set_clips_dir(&mut self, clips_dir: PathBuf) {
+        tracing::info!("Clip folder changed to {:?}", clips_dir);
+        self.clips_dir = clips_dir;
+    }
+
+    /// Change how long merging segments into a clip may take
+    pub fn set_clip_save_timeout(&mut self, secs: u64) {
+        self.clip_save_timeout = Duration::from_secs(secs.max(1));
+    }
+
+    /// Breaker that stops FFmpeg restarts after repeated failures
+    pub fn circuit_breaker(&self) -> Arc<ProductionCircuitBreaker> {
+        Arc::clone(&self.circuit_breaker)
+    }
+
     // Note: Circuit breaker state management is now handled automatically
     // via the ProductionCircuitBreaker::call() method in critical operations.
     // Manual success/failure tracking and state checks are no longer needed.
 
     /// Update audio configuration from settings
-    /// Note: Changes will take effect on next segment recording (after rotation)
+    /// Note: Changes take effect the next time the replay buffer starts
     pub fn update_audio_config(&mut self, audio_settings: &crate::settings::models::AudioSettings) {
         use crate::settings::models::{AudioBitrate, SampleRate};
 
@@ -592,6 +1092,157 @@ impl WindowsRecorder {
         );
     }
 
+    /// Enable or disable low-spec (performance) mode
+    ///
+    /// A running replay buffer restarts FFmpeg with the new config, keeping
+    /// the segments it already buffered.
+    pub fn set_performance_mode(&mut self, enabled: bool) {
+        if enabled == self.config.low_spec {
+            return;
+        }
+
+        if enabled {
+            let low_spec = self.config.to_low_spec();
+            self.standard_config = Some(std::mem::replace(&mut self.config, low_spec));
+        } else if let Some(mut standard) = self.standard_config.take() {
+            // Keep audio changes made while in low-spec mode
+            standard.audio = self.config.audio.clone();
+            self.config = standard;
+        }
+
+        tracing::info!(
+            "Performance mode {}: {}x{}@{}fps, {:.1} Mbps, encoder={}",
+            if enabled { "enabled" } else { "disabled" },
+            self.config.resolution.0,
+            self.config.resolution.1,
+            self.config.fps,
+            self.config.bitrate as f64 / 1_000_000.0,
+            self.config.get_encoder_name()
+        );
+
+        *self.config_update.lock() = Some(self.config.clone());
+    }
+
+    /// Set the resolution, frame rate, bitrate and encoder from settings
+    ///
+    /// In low-spec mode they're applied when the mode is turned off. Call
+    /// before [`Self::set_video_codec`], which checks the encoder for AV1.
+    /// Note: Changes take effect the next time the replay buffer starts
+    pub fn set_video_quality(&mut self, video: &crate::settings::models::VideoSettings) {
+        use crate::settings::models::{BitratePreset, EncoderPreference, FrameRate, Resolution};
+
+        let detected = self.detected_encoder;
+        let config = self.standard_config.as_mut().unwrap_or(&mut self.config);
+        config.resolution = match video.resolution {
+            Resolution::R1280x720 => (1280, 720),
+            Resolution::R1920x1080 => (1920, 1080),
+            Resolution::R2560x1440 => (2560, 1440),
+            Resolution::R3840x2160 => (3840, 2160),
+        };
+        config.fps = match video.frame_rate {
+            FrameRate::Fps30 => 30,
+            FrameRate::Fps60 => 60,
+            FrameRate::Fps120 => 120,
+            FrameRate::Fps144 => 144,
+        };
+        config.bitrate = match video.bitrate_preset {
+            BitratePreset::Low => 10_000_000,
+            BitratePreset::Medium => 20_000_000,
+            BitratePreset::High => 40_000_000,
+            BitratePreset::VeryHigh => 80_000_000,
+            BitratePreset::Custom(kbps) => kbps.max(1).saturating_mul(1000),
+        };
+        config.hardware_encoder = match video.encoder {
+            EncoderPreference::Auto => detected,
+            EncoderPreference::Nvenc => HardwareEncoder::NVENC,
+            EncoderPreference::Qsv => HardwareEncoder::QSV,
+            EncoderPreference::Amf => HardwareEncoder::AMF,
+            EncoderPreference::Software => HardwareEncoder::Software,
+        };
+
+        tracing::info!(
+            "Video quality set: {}x{}@{}fps, {:.1} Mbps, encoder={:?}",
+            config.resolution.0,
+            config.resolution.1,
+            config.fps,
+            config.bitrate as f64 / 1_000_000.0,
+            config.hardware_encoder
+        );
+    }
+
+    /// Set the video codec from settings
+    ///
+    /// AV1 falls back to H.265 if the detected encoder can't produce it. In
+    /// low-spec mode the codec is applied when the mode is turned off.
+    /// Note: Changes take effect the next time the replay buffer starts
+    pub fn set_video_codec(&mut self, codec: &crate::settings::models::VideoCodec) {
+        use crate::settings::models::VideoCodec as SettingsCodec;
+
+        let config = self.standard_config.as_mut().unwrap_or(&mut self.config);
+        config.codec = match codec {
+            SettingsCodec::H264 => VideoCodec::H264,
+            SettingsCodec::H265 => VideoCodec::HEVC,
+            SettingsCodec::Av1 if config.hardware_encoder.supports_av1() => VideoCodec::AV1,
+            SettingsCodec::Av1 => {
+                tracing::warn!(
+                    "AV1 not supported by {:?} encoder, falling back to H.265",
+                    config.hardware_encoder
+                );
+                VideoCodec::HEVC
+            }
+        };
+
+        tracing::info!("Video codec set: encoder={}", config.get_encoder_name());
+    }
+
+    /// Record the webcam from settings alongside the game (not in low-spec mode)
+    /// Note: Changes take effect the next time the replay buffer starts
+    pub fn set_facecam(&mut self, facecam: &crate::settings::models::FacecamSettings) {
+        let config = self.standard_config.as_mut().unwrap_or(&mut self.config);
+        config.facecam_device = match (&facecam.device, facecam.enabled) {
+            (Some(device), true) => Some(device.clone()),
+            (None, true) => {
+                tracing::warn!("Facecam enabled without a device, not recording it");
+                None
+            }
+            (_, false) => None,
+        };
+
+        tracing::info!("Facecam device set: {:?}", config.facecam_device);
+    }
+
+    /// Set the display, capture backend, cursor and HDR handling from settings
+    pub fn set_capture(&mut self, video: &crate::settings::models::VideoSettings) {
+        for config in std::iter::once(&mut self.config).chain(self.standard_config.as_mut()) {
+            config.capture_display = video.capture_display.clone();
+            config.capture_backend = video.capture_backend;
+            config.capture_cursor = video.capture_cursor;
+            config.force_sdr = video.force_sdr;
+        }
+
+        tracing::info!(
+            "Capture set: display={:?}, backend={:?}, cursor={}, force_sdr={}",
+            video.capture_display,
+            video.capture_backend,
+            video.capture_cursor,
+            video.force_sdr
+        );
+    }
+
+    /// Whether the detected encoder can record AV1 (runs an FFmpeg probe once)
+    pub fn supports_av1(&self) -> bool {
+        self.standard_config
+            .as_ref()
+            .unwrap_or(&self.config)
+            .hardware_encoder
+            .supports_av1()
+    }
+
+    /// Check if low-spec (performance) mode is active
+    pub fn is_performance_mode(&self) -> bool {
+        self.config.low_spec
+    }
+
     /// Start the replay buffer (continuous recording with FFmpeg)
     /// Circuit breaker protection is applied at FFmpeg spawn level
     #[cfg(target_os = "windows")]
@@ -604,16 +1255,20 @@ impl WindowsRecorder {
 
         *status = RecordingStatus::Buffering;
         drop(status);
+        emit_status(&self.events, RecordingStatus::Buffering);
 
         tracing::info!(
             "Starting FFmpeg-based replay buffer with {}s window",
             SEGMENT_DURATION_SECS * BUFFER_SEGMENTS as u64
         );
 
-        // Create segment recorder with circuit breaker
+        // Create segment recorder with circuit breaker (it starts with the
+        // current config, so earlier updates are already in it)
+        self.config_update.lock().take();
         let mut recorder = SegmentRecorder::new(
             Arc::clone(&self.segment_buffer),
             Arc::clone(&self.status),
+            Arc::clone(&self.stats),
             self.config.clone(),
             Arc::clone(&self.circuit_breaker),
         );
@@ -629,8 +1284,8 @@ impl WindowsRecorder {
                 tracing::error!("Failed to start initial segment: {}", e);
 
                 // Update status to error
-                let mut status = self.status.write().await;
-                *status = RecordingStatus::Error;
+                *self.status.write().await = RecordingStatus::Error;
+                emit_status(&self.events, RecordingStatus::Error);
 
                 return Err(anyhow::anyhow!("Failed to start recording: {}", e));
             }
@@ -638,10 +1293,14 @@ impl WindowsRecorder {
 
         // Spawn background task to handle segment rotation
         let is_recording = Arc::clone(&recorder.is_recording);
+        let config_update = Arc::clone(&self.config_update);
         let status_clone = Arc::clone(&self.status);
+        let events = Arc::clone(&self.events);
 
         tokio::spawn(async move {
             tracing::info!("Segment rotation task started");
+            // Segment recording was stopped for a pause
+            let mut paused = false;
 
             loop {
                 // Sleep for 1 second between checks
@@ -667,27 +1326,66 @@ impl WindowsRecorder {
                     break;
                 }
 
-                // Check if segment should rotate
-                if recorder.should_rotate() {
-                    tracing::info!("Rotating segment");
+                // Performance mode changed: stop FFmpeg so the check below
+                // restarts it with the new config
+                let update = config_update.lock().take();
+                if let Some(config) = update {
+                    recorder.config = config;
+                    if recorder.is_active() {
+                        tracing::info!("Recording config changed, restarting segment recording");
+                        if let Err(e) = recorder.stop_segment_recording().await {
+                            tracing::error!("Failed to stop segment recording: {}", e);
+                        }
+                    }
+                }
 
-                    match recorder.rotate_segment().await {
-                        Ok(_) => {
-                            tracing::debug!("Segment rotation successful");
+                // Paused: finish the current segment and spawn no new ones
+                if current_status == RecordingStatus::Paused {
+                    if recorder.is_active() {
+                        tracing::info!("Replay buffer paused, stopping segment recording");
+                        if let Err(e) = recorder.stop_segment_recording().await {
+                            tracing::error!("Failed to stop segment recording: {}", e);
                         }
-                        Err(e) => {
-                            tracing::error!("Segment rotation failed: {}", e);
+                        paused = true;
+                    }
+                    continue;
+                }
 
-                            // Update status to error
-                            let mut status = status_clone.write().await;
-                            *status = RecordingStatus::Error;
+                // Resumed after a pause or a config change (a clip saved
+                // while paused doesn't resume)
+                if !recorder.is_active() && current_status != RecordingStatus::Processing {
+                    tracing::info!("Replay buffer resumed, starting segment recording");
 
-                            // Stop recording
-                            *is_recording.lock() = false;
-                            break;
-                        }
+                    // What was buffered before the pause is usually the last
+                    // game; clips of the next one mustn't reach back into it
+                    if std::mem::take(&mut paused) {
+                        recorder.discard_segments().await;
+                    }
+
+                    if let Err(e) = recorder.start_segment_recording().await {
+                        tracing::error!("Failed to resume segment recording: {}", e);
+
+                        *status_clone.write().await = RecordingStatus::Error;
+                        emit_status(&events, RecordingStatus::Error);
+
+                        *is_recording.lock() = false;
+                        break;
+                    }
+                    continue;
+                }
+
+                // FFmpeg quit on its own (e.g. the capture device went away):
+                // keep what it wrote and restart it on the next check
+                if recorder.has_exited() {
+                    tracing::warn!("FFmpeg segment recording exited unexpectedly");
+                    if let Err(e) = recorder.stop_segment_recording().await {
+                        tracing::error!("Failed to stop segment recording: {}", e);
                     }
+                    continue;
                 }
+
+                // Add the segments the muxer has finished to the buffer
+                recorder.collect_segments(false).await;
             }
 
             tracing::info!("Segment rotation task ended");
@@ -712,6 +1410,7 @@ impl WindowsRecorder {
 
         *status = RecordingStatus::Idle;
         drop(status);
+        emit_status(&self.events, RecordingStatus::Idle);
 
         // Clear segment buffer
         let mut buffer = self.segment_buffer.write().await;
@@ -722,9 +1421,50 @@ impl WindowsRecorder {
         Ok(())
     }
 
+    /// Pause the replay buffer (stop spawning segments, keep buffered ones)
+    ///
+    /// Clips can still be saved from the buffered segments until it resumes.
+    ///
+    /// Returns false if the buffer isn't running or already paused.
+    pub async fn pause_replay_buffer(&self) -> bool {
+        let mut status = self.status.write().await;
+
+        if *status != RecordingStatus::Buffering && *status != RecordingStatus::Recording {
+            return false;
+        }
+
+        *status = RecordingStatus::Paused;
+        drop(status);
+        emit_status(&self.events, RecordingStatus::Paused);
+
+        tracing::info!("Replay buffer paused");
+        true
+    }
+
+    /// Resume a paused replay buffer
+    ///
+    /// The segments buffered before the pause are discarded when recording
+    /// restarts. Returns false if the buffer isn't paused.
+    pub async fn resume_replay_buffer(&self) -> bool {
+        let mut status = self.status.write().await;
+
+        if *status != RecordingStatus::Paused {
+            return false;
+        }
+
+        *status = RecordingStatus::Buffering;
+        drop(status);
+        emit_status(&self.events, RecordingStatus::Buffering);
+
+        tracing::info!("Replay buffer resumed");
+        true
+    }
+
     /// Save a clip from the replay buffer
     ///
-    /// This concatenates the available segments into a single output file
+    /// This concatenates the available segments into a single output file.
+    /// Saves requested while another one is processing wait for it, in the
+    /// order they were requested.
     pub async fn save_clip(
         &self,
         _event: &GameEvent,
@@ -734,8 +1474,18 @@ impl WindowsRecorder {
     ) -> Result<PathBuf> {
         let duration = duration_secs.min(MAX_CLIP_DURATION_SECS);
 
+        // Tokio's mutex is fair, so this is a FIFO queue. Holding it until
+        // the status is restored means the next save never sees Processing.
+        let _queue_slot = self.save_queue.lock().await;
+
         // Ensure we're buffering or recording
         let status = self.status.read().await;
+        // Paused stays paused after saving; everything else goes back to buffering
+        let restore_status = if *status == RecordingStatus::Paused {
+            RecordingStatus::Paused
+        } else {
+            RecordingStatus::Buffering
+        };
         match *status {
             RecordingStatus::Idle => {
                 anyhow::bail!("Cannot save clip: replay buffer not active");
@@ -743,7 +1493,7 @@ impl WindowsRecorder {
             RecordingStatus::Error => {
                 anyhow::bail!("Cannot save clip: recording in error state");
             }
-            _ => {} // Buffering, Recording, or Processing is OK
+            _ => {} // Buffering, Recording, Paused or Processing is OK
         }
         drop(status);
 
@@ -757,7 +1507,8 @@ impl WindowsRecorder {
 
         let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S").to_string();
         let clip_filename = format!("{}_{}_p{}_{}.mp4", game_id, timestamp, priority, clip_id);
-        let output_path = self.output_dir.join(&clip_filename);
+        std::fs::create_dir_all(&self.clips_dir)?;
+        let output_path = self.clips_dir.join(&clip_filename);
 
         tracing::info!(
             "Saving clip: {} (duration: {:.1}s, priority: {})",
@@ -768,45 +1519,132 @@ impl WindowsRecorder {
 
         // Set status to processing
         *self.status.write().await = RecordingStatus::Processing;
+        emit_status(&self.events, RecordingStatus::Processing);
 
-        // Get all available segments
-        let buffer = self.segment_buffer.read().await;
-        let segments = buffer.get_all_segments();
-        drop(buffer);
+        let result = self
+            .write_clip(&output_path, &clip_filename, duration)
+            .await;
+
+        // Restore status, also after a failure so recording carries on
+        *self.status.write().await = restore_status;
+        emit_status(&self.events, restore_status);
+        result?;
+
+        tracing::info!("Clip saved successfully: {:?}", output_path);
+
+        self.events.emit(AppEvent::ClipSaved(ClipSaved {
+            clip_path: output_path.to_string_lossy().to_string(),
+            game_id,
+            priority,
+            duration_secs: duration,
+        }));
+
+        Ok(output_path)
+    }
+
+    /// Concatenate the buffered segments (and webcam, if any) into a clip
+    async fn write_clip(
+        &self,
+        output_path: &PathBuf,
+        clip_filename: &str,
+        duration: f64,
+    ) -> Result<()> {
+        // Pin the available segments so rotation can't delete them mid-concat
+        let snapshot = self.segment_buffer.read().await.snapshot()?;
+        let segments = snapshot.paths();
 
         if segments.is_empty() {
             anyhow::bail!("No segments available to save");
         }
 
+        // A truncated segment (encoder crash) would break the whole clip
+        let (segments, dropped) = validate_segments(segments).await;
+        if segments.is_empty() {
+            anyhow::bail!("No valid segments available to save");
+        }
+        let dropped = dropped.iter().map(|path| snapshot.original(path)).collect();
+        *self.dropped_segments.lock() = Some((output_path.clone(), dropped));
+
         // Concatenate segments using FFmpeg
-        self.concat_segments(&segments, &output_path, duration)
+        self.concat_segments(&segments, output_path, duration)
             .await?;
 
+        // Webcam recording, if every segment has one
+        let facecam_segments: Vec<PathBuf> =
+            segments.iter().map(|s| super::facecam_path(s)).collect();
+        if facecam_segments.iter().all(|path| path.exists()) {
+            let facecam_output = super::facecam_path(output_path);
+            if let Err(e) = self
+                .concat_segments(&facecam_segments, &facecam_output, duration)
+                .await
+            {
+                tracing::warn!("Failed to save facecam for {}: {}", clip_filename, e);
+            }
+        }
+
         // Update stats
         {
             let mut stats = self.stats.write();
             stats.clips_created += 1;
         }
 
-        // Restore status
-        *self.status.write().await = RecordingStatus::Buffering;
-
-        tracing::info!("Clip saved successfully: {:?}", output_path);
-
-        Ok(output_path)
+        Ok(())
     }
 
     /// Concatenate video segments using FFmpeg
     ///
-    /// Uses FFmpeg's concat demuxer for fast, lossless concatenation
+    /// Uses FFmpeg's concat demuxer for fast, lossless concatenation. Segments
+    /// recorded with different parameters (the encoder fell back mid-session)
+    /// can't be stream-copied into one file, so those are re-encoded instead,
+    /// as is anything the copy fails on.
     async fn concat_segments(
         &self,
         segments: &[PathBuf],
         output_path: &PathBuf,
         duration_secs: f64,
     ) -> Result<()> {
-        use std::process::Command;
+        let Some(infos) = probe_segment_formats(segments).await else {
+            // Without ffprobe there's nothing to re-encode to
+            return self.concat_copy(segments, output_path, duration_secs).await;
+        };
+
+        if formats_match(&infos) {
+            match self.concat_copy(segments, output_path, duration_secs).await {
+                Ok(()) => return Ok(()),
+                Err(e) => tracing::warn!("Stream copy concat failed, re-encoding clip: {:#}", e),
+            }
+        } else {
+            tracing::warn!("Segments were recorded with different settings, re-encoding clip");
+        }
+
+        let target = ConcatTarget::for_clips(&infos).context("No segments to concatenate")?;
+        let args = reencode_concat_args(segments, &infos, target, duration_secs, output_path);
+        self.run_ffmpeg(
+            args,
+            output_path,
+            self.clip_save_timeout * REENCODE_TIMEOUT_FACTOR,
+        )
+        .await?;
+
+        tracing::info!(
+            "Re-encoded {} segments to {:?} ({}x{}@{}fps)",
+            segments.len(),
+            output_path,
+            target.width,
+            target.height,
+            target.fps
+        );
 
+        Ok(())
+    }
+
+    /// Concatenate segments with the concat demuxer, without re-encoding
+    async fn concat_copy(
+        &self,
+        segments: &[PathBuf],
+        output_path: &Path,
+        duration_secs: f64,
+    ) -> Result<()> {
         // Create concat file for FFmpeg
         let concat_file = self.output_dir.join("concat_list.txt");
         let mut content = String::new();
@@ -821,38 +1659,29 @@ impl WindowsRecorder {
 
         tracing::debug!("Concatenating {} segments", segments.len());
 
-        // Run FFmpeg concat with retry logic for transient failures
-        let concat_file_clone = concat_file.clone();
-        let output_path_clone = output_path.clone();
-        let duration_str = duration_secs.to_string();
-
-        let status = retry_with_backoff(FFMPEG_RETRY_CONFIG, "FFmpeg concatenation", || async {
-            Command::new("ffmpeg")
-                .args([
-                    "-f",
-                    "concat",
-                    "-safe",
-                    "0",
-                    "-i",
-                    concat_file_clone.to_str().unwrap(),
-                    "-t",
-                    &duration_str, // Limit duration
-                    "-c",
-                    "copy", // Copy without re-encoding
-                    "-y",   // Overwrite output
-                    output_path_clone.to_str().unwrap(),
-                ])
-                .status()
-                .context("Failed to execute FFmpeg")
-        })
-        .await?;
+        let args = vec![
+            "-f".to_string(),
+            "concat".to_string(),
+            "-safe".to_string(),
+            "0".to_string(),
+            "-i".to_string(),
+            concat_file.to_string_lossy().to_string(),
+            "-t".to_string(),
+            duration_secs.to_string(), // Limit duration
+            "-map".to_string(),
+            "0".to_string(), // Keep every track (the microphone has its own)
+            "-c".to_string(),
+            "copy".to_string(), // Copy without re-encoding
+            "-y".to_string(),   // Overwrite output
+            output_path.to_string_lossy().to_string(),
+        ];
+        let result = self
+            .run_ffmpeg(args, output_path, self.clip_save_timeout)
+            .await;
 
         // Cleanup concat file
         let _ = std::fs::remove_file(&concat_file);
-
-        if !status.success() {
-            anyhow::bail!("FFmpeg concatenation failed with status: {}", status);
-        }
+        result?;
 
         tracing::info!(
             "Successfully concatenated {} segments to {:?}",
@@ -863,6 +1692,60 @@ impl WindowsRecorder {
         Ok(())
     }
 
+    /// Run FFmpeg to write `output_path`, with retry logic for transient
+    /// failures
+    ///
+    /// The partial output is removed if FFmpeg fails or hangs.
+    async fn run_ffmpeg(
+        &self,
+        args: Vec<String>,
+        output_path: &Path,
+        timeout: Duration,
+    ) -> Result<()> {
+        use crate::utils::cleanup::process::TrackedPid;
+
+        let result = retry_with_backoff(FFMPEG_RETRY_CONFIG, "FFmpeg concatenation", || async {
+            let mut child = tokio::process::Command::new("ffmpeg")
+                .args(&args)
+                .kill_on_drop(true)
+                .spawn()
+                .context("Failed to execute FFmpeg")?;
+            let _tracked = TrackedPid::new(child.id());
+
+            match tokio::time::timeout(timeout, child.wait()).await {
+                Ok(status) => status.context("Failed to wait for FFmpeg"),
+                Err(_) => {
+                    let _ = child.kill().await;
+                    anyhow::bail!("FFmpeg concatenation timed out after {:?}", timeout)
+                }
+            }
+        })
+        .await;
+
+        let status = result.inspect_err(|_| {
+            let _ = std::fs::remove_file(output_path);
+        })?;
+
+        if !status.success() {
+            let _ = std::fs::remove_file(output_path);
+            anyhow::bail!("FFmpeg concatenation failed with status: {}", status);
+        }
+
+        Ok(())
+    }
+
+    /// Corrupt segments skipped while saving `clip_path`
+    pub fn take_dropped_segments(&self, clip_path: &Path) -> Vec<PathBuf> {
+        let mut dropped = self.dropped_segments.lock();
+        match dropped.take() {
+            Some((path, segments)) if path == clip_path => segments,
+            other => {
+                *dropped = other;
+                Vec::new()
+            }
+        }
+    }
+
     pub async fn get_state(&self) -> RecordingStatus {
         *self.status.read().await
     }
@@ -871,6 +1754,16 @@ impl WindowsRecorder {
         self.stats.read().clone()
     }
 
+    /// Directory where temp segments and recovered sessions are written
+    pub fn output_dir(&self) -> &std::path::Path {
+        &self.output_dir
+    }
+
+    /// Directory where clips are saved
+    pub fn clips_dir(&self) -> &std::path::Path {
+        &self.clips_dir
+    }
+
     pub async fn set_current_game(&self, game: Option<GameMetadata>) {
         let mut current = self.current_game.write().await;
         *current = game;
@@ -882,6 +1775,7 @@ impl WindowsRecorder {
         let codec_name = match self.config.codec {
             VideoCodec::HEVC => "H.265/HEVC",
             VideoCodec::H264 => "H.264/AVC",
+            VideoCodec::AV1 => "AV1",
         };
 
         QualityInfo {
@@ -895,6 +1789,155 @@ impl WindowsRecorder {
     }
 }
 
+/// Split `segments` into readable ones and corrupt ones, keeping the order
+async fn validate_segments(segments: Vec<PathBuf>) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let mut valid = Vec::with_capacity(segments.len());
+    let mut dropped = Vec::new();
+
+    for segment in segments {
+        if probe_segment(&segment).await {
+            valid.push(segment);
+        } else {
+            tracing::warn!("Skipping corrupt segment: {:?}", segment);
+            dropped.push(segment);
+        }
+    }
+
+    (valid, dropped)
+}
+
+/// Whether `segment` has a readable video stream
+///
+/// A segment cut short has no index (moov atom), so ffprobe fails on it
+/// right away. If ffprobe itself can't be run the segment is trusted.
+async fn probe_segment(segment: &Path) -> bool {
+    if !std::fs::metadata(segment).is_ok_and(|m| m.len() > 0) {
+        return false;
+    }
+
+    let probe = tokio::process::Command::new("ffprobe")
+        .args(["-v", "error", "-select_streams", "v:0"])
+        .args(["-show_entries", "stream=codec_name", "-of", "csv=p=0"])
+        .arg(segment)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .output();
+
+    match tokio::time::timeout(SEGMENT_PROBE_TIMEOUT, probe).await {
+        Ok(Ok(output)) => output.status.success() && !output.stdout.trim_ascii().is_empty(),
+        Ok(Err(e)) => {
+            tracing::debug!("Failed to run ffprobe, not validating segment: {}", e);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Format of every segment, or `None` if any can't be probed
+async fn probe_segment_formats(segments: &[PathBuf]) -> Option<Vec<MediaInfo>> {
+    let processor = VideoProcessor::new();
+    let mut infos = Vec::with_capacity(segments.len());
+    for segment in segments {
+        infos.push(processor.analyze(segment).await.ok()?);
+    }
+    Some(infos)
+}
+
+/// Whether all segments can be stream-copied into one file
+fn formats_match(infos: &[MediaInfo]) -> bool {
+    infos.windows(2).all(|pair| pair[0].matches(&pair[1]))
+}
+
+/// FFmpeg arguments re-encoding `segments` into one clip at `target`
+///
+/// Unlike the concat demuxer, the concat filter takes inputs with different
+/// codecs and resolutions; each one is letterboxed to `target` first. Every
+/// audio track all segments have is kept (game mix and microphone), with
+/// the titles of the first segment's tracks.
+fn reencode_concat_args(
+    segments: &[PathBuf],
+    infos: &[MediaInfo],
+    target: ConcatTarget,
+    duration_secs: f64,
+    output_path: &Path,
+) -> Vec<String> {
+    let audio_tracks = infos
+        .iter()
+        .map(|info| info.audio_tracks.len())
+        .min()
+        .unwrap_or(0);
+
+    let mut filter = String::new();
+    for idx in 0..segments.len() {
+        filter.push_str(&format!(
+            "[{idx}:v:0]scale={w}:{h}:force_original_aspect_ratio=decrease,pad={w}:{h}:(ow-iw)/2:(oh-ih)/2,setsar=1,fps={fps},format=yuv420p[v{idx}];",
+            w = target.width,
+            h = target.height,
+            fps = target.fps
+        ));
+    }
+    for idx in 0..segments.len() {
+        filter.push_str(&format!("[v{}]", idx));
+        for track in 0..audio_tracks {
+            filter.push_str(&format!("[{}:a:{}]", idx, track));
+        }
+    }
+    filter.push_str(&format!(
+        "concat=n={}:v=1:a={}[v]",
+        segments.len(),
+        audio_tracks
+    ));
+    for track in 0..audio_tracks {
+        filter.push_str(&format!("[a{}]", track));
+    }
+
+    let mut args = Vec::new();
+    for segment in segments {
+        args.extend(["-i".to_string(), segment.to_string_lossy().to_string()]);
+    }
+    args.extend([
+        "-filter_complex".to_string(),
+        filter,
+        "-map".to_string(),
+        "[v]".to_string(),
+    ]);
+    for track in 0..audio_tracks {
+        args.extend(["-map".to_string(), format!("[a{}]", track)]);
+        let title = &infos[0].audio_tracks[track];
+        if !title.is_empty() {
+            args.extend([
+                format!("-metadata:s:a:{}", track),
+                format!("title={}", title),
+            ]);
+        }
+    }
+    if audio_tracks > 0 {
+        args.extend(
+            ["-c:a", "aac", "-b:a", "192k"]
+                .into_iter()
+                .map(str::to_string),
+        );
+    }
+    args.extend(
+        [
+            "-t",
+            duration_secs.to_string().as_str(),
+            "-c:v",
+            "libx264",
+            "-preset",
+            "veryfast",
+            "-crf",
+            "20",
+            "-y",
+        ]
+        .into_iter()
+        .map(str::to_string),
+    );
+    args.push(output_path.to_string_lossy().to_string());
+    args
+}
+
 // Implement Clone manually (Arc types are Clone)
 impl Clone for WindowsRecorder {
     fn clone(&self) -> Self {
@@ -902,10 +1945,18 @@ impl Clone for WindowsRecorder {
             status: Arc::clone(&self.status),
             stats: Arc::clone(&self.stats),
             output_dir: self.output_dir.clone(),
+            clips_dir: self.clips_dir.clone(),
+            clip_save_timeout: self.clip_save_timeout,
             current_game: Arc::clone(&self.current_game),
             segment_buffer: Arc::clone(&self.segment_buffer),
             config: self.config.clone(),
+            standard_config: self.standard_config.clone(),
+            config_update: Arc::clone(&self.config_update),
+            detected_encoder: self.detected_encoder,
             circuit_breaker: Arc::clone(&self.circuit_breaker),
+            events: Arc::clone(&self.events),
+            dropped_segments: Arc::clone(&self.dropped_segments),
+            save_queue: Arc::clone(&self.save_queue),
         }
     }
 }
@@ -943,6 +1994,64 @@ mod tests {
         assert_eq!(recorder.get_state().await, RecordingStatus::Idle);
     }
 
+    #[tokio::test]
+    async fn test_performance_mode_toggle() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut recorder = WindowsRecorder::new(temp_dir.path().to_path_buf()).unwrap();
+        let original_fps = recorder.config.fps;
+
+        recorder.set_performance_mode(true);
+        assert!(recorder.is_performance_mode());
+        assert_eq!(recorder.config.resolution, LOW_SPEC_RESOLUTION);
+        assert_eq!(recorder.config.fps, LOW_SPEC_FPS);
+        assert!(recorder.config.bitrate <= LOW_SPEC_MAX_BITRATE);
+        assert_eq!(recorder.config.get_encoder_name(), "libx264");
+        let update = recorder.config_update.lock().take().unwrap();
+        assert!(update.low_spec);
+
+        recorder.set_performance_mode(false);
+        assert!(!recorder.is_performance_mode());
+        assert_eq!(recorder.config.fps, original_fps);
+    }
+
+    #[tokio::test]
+    async fn test_video_quality_waits_for_low_spec_mode() {
+        use crate::settings::models::{FrameRate, Resolution, VideoSettings};
+
+        let temp_dir = TempDir::new().unwrap();
+        let mut recorder = WindowsRecorder::new(temp_dir.path().to_path_buf()).unwrap();
+        let video = VideoSettings {
+            resolution: Resolution::R2560x1440,
+            frame_rate: FrameRate::Fps144,
+            ..VideoSettings::default()
+        };
+
+        recorder.set_performance_mode(true);
+        recorder.set_video_quality(&video);
+        assert_eq!(recorder.config.resolution, LOW_SPEC_RESOLUTION);
+
+        recorder.set_performance_mode(false);
+        assert_eq!(recorder.config.resolution, (2560, 1440));
+        assert_eq!(recorder.config.fps, 144);
+        assert_eq!(recorder.config.hardware_encoder, recorder.detected_encoder);
+    }
+
+    #[test]
+    fn test_av1_encoder_selection() {
+        let config = RecordingConfig {
+            codec: VideoCodec::AV1,
+            hardware_encoder: HardwareEncoder::NVENC,
+            ..RecordingConfig::default()
+        };
+        assert_eq!(config.get_encoder_name(), "av1_nvenc");
+
+        let software = HardwareEncoder::Software;
+        assert_eq!(software.av1_encoder(), "libsvtav1");
+        assert_eq!(software.get_preset(VideoCodec::AV1), "10");
+        assert!(software.get_encoder_options(VideoCodec::AV1).is_empty());
+        assert_eq!(software.get_preset(VideoCodec::HEVC), "medium");
+    }
+
     #[tokio::test]
     async fn test_segment_buffer() {
         let temp_dir = TempDir::new().unwrap();
@@ -971,6 +2080,78 @@ mod tests {
         assert_eq!(buffer.segments.len(), 0);
     }
 
+    #[test]
+    fn test_add_finished_segments() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut buffer = SegmentBuffer::new(temp_dir.path().to_path_buf()).unwrap();
+
+        // Left over from an earlier session
+        std::fs::write(buffer.segment_path(3), b"stale").unwrap();
+        buffer.remove_unbuffered_segments();
+        assert!(!buffer.segment_path(3).exists());
+
+        // The muxer is writing segment 1, so only segment 0 is finished
+        std::fs::write(buffer.segment_path(0), b"segment").unwrap();
+        std::fs::write(buffer.segment_path(1), b"segment").unwrap();
+        assert_eq!(buffer.add_finished_segments(false).unwrap(), 0);
+        assert_eq!(buffer.segments.len(), 1);
+
+        // Once FFmpeg exits its last segment is finished too
+        std::fs::write(buffer.segment_path(2), b"").unwrap();
+        assert_eq!(buffer.add_finished_segments(true).unwrap(), 1);
+        assert_eq!(buffer.segments.len(), 2);
+        assert_eq!(buffer.next_segment_path(), buffer.segment_path(3));
+        assert_eq!(buffer.add_finished_segments(true).unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_survives_rotation() {
+        let temp_dir = TempDir::new().unwrap();
+        let segment_dir = temp_dir.path().join("segments");
+        let mut buffer = SegmentBuffer::new(segment_dir.clone()).unwrap();
+
+        for _ in 0..BUFFER_SEGMENTS {
+            let path = buffer.next_segment_path();
+            std::fs::write(&path, b"segment").unwrap();
+            buffer.add_segment(path).unwrap();
+        }
+        let oldest = buffer.segments[0].clone();
+
+        let snapshot = buffer.snapshot().unwrap();
+        let pinned = snapshot.paths();
+        assert_eq!(pinned.len(), BUFFER_SEGMENTS);
+        assert_eq!(snapshot.original(&pinned[0]), oldest);
+
+        // Rotation deletes the oldest segment, the pinned one stays readable
+        let path = buffer.next_segment_path();
+        std::fs::write(&path, b"segment").unwrap();
+        buffer.add_segment(path).unwrap();
+        assert!(!oldest.exists());
+        assert_eq!(std::fs::read(&pinned[0]).unwrap(), b"segment");
+
+        let snapshot_dir = pinned[0].parent().unwrap().to_path_buf();
+        drop(snapshot);
+        assert!(!snapshot_dir.exists());
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_resume() {
+        let temp_dir = TempDir::new().unwrap();
+        let recorder = WindowsRecorder::new(temp_dir.path().to_path_buf()).unwrap();
+
+        // Nothing to pause while idle
+        assert!(!recorder.pause_replay_buffer().await);
+        assert!(!recorder.resume_replay_buffer().await);
+
+        *recorder.status.write().await = RecordingStatus::Buffering;
+        assert!(recorder.pause_replay_buffer().await);
+        assert_eq!(recorder.get_state().await, RecordingStatus::Paused);
+        assert!(!recorder.pause_replay_buffer().await);
+
+        assert!(recorder.resume_replay_buffer().await);
+        assert_eq!(recorder.get_state().await, RecordingStatus::Buffering);
+    }
+
     #[tokio::test]
     async fn test_save_clip_requires_active_buffer() {
         let temp_dir = TempDir::new().unwrap();
@@ -994,4 +2175,126 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not active"));
     }
+
+    #[tokio::test]
+    async fn test_failed_save_restores_status() {
+        let temp_dir = TempDir::new().unwrap();
+        let recorder = WindowsRecorder::new(temp_dir.path().to_path_buf()).unwrap();
+        let event = GameEvent {
+            event_id: 1,
+            event_name: "TestEvent".to_string(),
+            event_time: 0.0,
+            killer_name: None,
+            victim_name: None,
+            assisters: vec![],
+            priority: 3,
+            timestamp: Instant::now(),
+        };
+
+        for status in [RecordingStatus::Buffering, RecordingStatus::Paused] {
+            *recorder.status.write().await = status;
+
+            // No segments yet, but the next save mustn't find it Processing
+            let result = recorder
+                .save_clip(&event, "test".to_string(), 3, 30.0)
+                .await;
+            assert!(result.unwrap_err().to_string().contains("No segments"));
+            assert_eq!(recorder.get_state().await, status);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validate_segments_drops_empty_and_missing() {
+        let temp_dir = TempDir::new().unwrap();
+        let empty = temp_dir.path().join("segment_0001.mp4");
+        std::fs::write(&empty, b"").unwrap();
+        let missing = temp_dir.path().join("segment_0002.mp4");
+
+        let (valid, dropped) = validate_segments(vec![empty.clone(), missing.clone()]).await;
+        assert!(valid.is_empty());
+        assert_eq!(dropped, vec![empty, missing]);
+    }
+
+    fn media_info(codec: &str, width: u32, height: u32, has_audio: bool) -> MediaInfo {
+        MediaInfo {
+            codec: codec.to_string(),
+            width,
+            height,
+            fps: 60.0,
+            pixel_format: "yuv420p".to_string(),
+            variable_frame_rate: false,
+            has_audio,
+            audio_tracks: if has_audio {
+                vec![String::new()]
+            } else {
+                vec![]
+            },
+        }
+    }
+
+    #[test]
+    fn test_reencode_concat_args() {
+        let segments = vec![
+            PathBuf::from("segment_0001.mp4"),
+            PathBuf::from("segment_0002.mp4"),
+        ];
+        let infos = vec![
+            media_info("hevc", 1920, 1080, true),
+            media_info("h264", 1280, 720, true),
+        ];
+        assert!(!formats_match(&infos));
+        assert!(formats_match(&infos[..1]));
+
+        let target = ConcatTarget::for_clips(&infos).unwrap();
+        let args = reencode_concat_args(&segments, &infos, target, 30.0, Path::new("clip.mp4"));
+        let filter = &args[args.iter().position(|a| a == "-filter_complex").unwrap() + 1];
+
+        assert!(filter.contains("[1:v:0]scale=1920:1080"));
+        assert!(filter.ends_with("[v0][0:a:0][v1][1:a:0]concat=n=2:v=1:a=1[v][a0]"));
+        assert!(args.windows(2).any(|pair| pair == ["-map", "[a0]"]));
+        assert_eq!(args.last().unwrap(), "clip.mp4");
+
+        // The microphone track is kept with its title
+        let infos: Vec<MediaInfo> = infos
+            .into_iter()
+            .map(|info| MediaInfo {
+                audio_tracks: vec![String::new(), "Microphone".to_string()],
+                ..info
+            })
+            .collect();
+        let args = reencode_concat_args(&segments, &infos, target, 30.0, Path::new("clip.mp4"));
+        let filter = &args[args.iter().position(|a| a == "-filter_complex").unwrap() + 1];
+        assert!(
+            filter.ends_with("[v0][0:a:0][0:a:1][v1][1:a:0][1:a:1]concat=n=2:v=1:a=2[v][a0][a1]")
+        );
+        assert!(args.windows(2).any(|pair| pair == ["-map", "[a1]"]));
+        assert!(args
+            .windows(2)
+            .any(|pair| pair == ["-metadata:s:a:1", "title=Microphone"]));
+
+        // One silent segment drops audio from the whole clip
+        let infos = vec![
+            media_info("hevc", 1920, 1080, true),
+            media_info("hevc", 1920, 1080, false),
+        ];
+        let args = reencode_concat_args(&segments, &infos, target, 30.0, Path::new("clip.mp4"));
+        let filter = &args[args.iter().position(|a| a == "-filter_complex").unwrap() + 1];
+        assert!(filter.ends_with("[v0][v1]concat=n=2:v=1:a=0[v]"));
+        assert!(!args.iter().any(|a| a == "[a0]"));
+    }
+
+    #[test]
+    fn test_take_dropped_segments_matches_clip() {
+        let temp_dir = TempDir::new().unwrap();
+        let recorder = WindowsRecorder::new(temp_dir.path().to_path_buf()).unwrap();
+        let clip = temp_dir.path().join("clip.mp4");
+        let segment = temp_dir.path().join("segment_0001.mp4");
+        *recorder.dropped_segments.lock() = Some((clip.clone(), vec![segment.clone()]));
+
+        assert!(recorder
+            .take_dropped_segments(Path::new("other.mp4"))
+            .is_empty());
+        assert_eq!(recorder.take_dropped_segments(&clip), vec![segment]);
+        assert!(recorder.take_dropped_segments(&clip).is_empty());
+    }
 }
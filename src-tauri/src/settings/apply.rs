@@ -0,0 +1,71 @@
+/// Applying settings to the running app
+///
+/// Most of the app reads the shared `recording_settings`, but the recorder,
+/// composer, telemetry and a few process-wide knobs (FFmpeg timeout, retry
+/// policies, LCU discovery, upload limits) keep their own copy. Every path
+/// that replaces the settings (startup, save, reset, import, profiles,
+/// recommended settings, cloud restore) goes through [`apply_settings`] so
+/// none of them is missed.
+use anyhow::{Context, Result};
+
+use super::models::RecordingSettings;
+use crate::AppState;
+
+/// Push `settings` to every component and make them the shared settings
+///
+/// Saving them is up to the caller. Everything is applied even when
+/// telemetry or the login item can't be updated; that error is returned
+/// afterwards.
+pub async fn apply_settings(state: &AppState, settings: &RecordingSettings) -> Result<()> {
+    // Changes the running buffer can't pick up apply when it next starts
+    // (low-spec mode restarts FFmpeg right away)
+    {
+        let mut recording_manager = state.recording_manager.write().await;
+        recording_manager.update_audio_config(&settings.audio);
+        recording_manager.set_video_quality(&settings.video);
+        recording_manager.set_video_codec(&settings.video.codec);
+        recording_manager.set_facecam(&settings.video.facecam);
+        recording_manager.set_capture(&settings.video);
+        recording_manager.set_performance_mode(settings.performance.low_spec_mode);
+        recording_manager.set_clip_save_timeout(settings.performance.clip_save_timeout_secs);
+
+        // New clips go to the configured folders; existing ones stay until
+        // they're moved with migrate_storage
+        let data_dir = state.storage.base_path();
+        recording_manager.set_clips_dir(settings.storage.clips_dir(data_dir));
+        state
+            .auto_composer
+            .set_output_dir(settings.storage.auto_edits_dir(data_dir))
+            .await;
+    }
+
+    crate::video::set_ffmpeg_timeout(settings.performance.ffmpeg_timeout_secs);
+    crate::utils::retry::set_retry_settings(&settings.retry);
+    crate::lcu::discovery::set_settings(&settings.lcu);
+    crate::lcu::install::set_override(
+        settings
+            .league_install_path
+            .as_ref()
+            .map(std::path::PathBuf::from),
+    );
+    crate::uploads::set_settings(&settings.uploads);
+
+    *state.recording_settings.write().await = settings.clone();
+
+    let telemetry = state
+        .telemetry
+        .set_enabled(settings.telemetry.enabled)
+        .await
+        .context("Failed to update telemetry");
+
+    // Also re-registers the login item so it points at this executable
+    // after updates
+    let autostart = if settings.start_on_boot || crate::utils::autostart::is_enabled() {
+        crate::utils::autostart::set_enabled(settings.start_on_boot, settings.start_minimized)
+            .context("Failed to update start on boot")
+    } else {
+        Ok(())
+    };
+
+    telemetry.and(autostart)
+}
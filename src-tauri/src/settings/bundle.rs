@@ -0,0 +1,222 @@
+/// Full app configuration bundle for moving between machines
+///
+/// One JSON file holding the recording settings (including hotkeys, event
+/// filter and clip timing), settings profiles and canvas templates. Settings
+/// are kept as raw JSON so a bundle from an older version goes through the
+/// same schema migrations as `settings.json`.
+///
+/// Event hooks run programs on this machine, so they're left out of exported
+/// bundles and an import keeps the hooks already configured here.
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use super::models::{ProfileStore, RecordingSettings};
+use super::storage::{Result, SettingsError};
+use crate::storage::Storage;
+use crate::utils::security;
+use crate::video::CanvasTemplate;
+
+/// Current bundle format version
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigBundle {
+    pub format_version: u32,
+    pub app_version: String,
+    pub exported_at: DateTime<Utc>,
+    pub settings: serde_json::Value,
+    #[serde(default)]
+    pub profiles: ProfileStore,
+    #[serde(default)]
+    pub canvas_templates: Vec<CanvasTemplate>,
+}
+
+/// What an import changed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportSummary {
+    pub settings: RecordingSettings,
+    pub profiles_imported: usize,
+    pub templates_imported: usize,
+    /// Templates skipped because their ID isn't valid
+    pub templates_skipped: usize,
+}
+
+impl ConfigBundle {
+    /// Collect the current configuration
+    pub fn collect(settings: &RecordingSettings, storage: &Storage) -> Result<Self> {
+        let mut canvas_templates = Vec::new();
+        for info in storage
+            .list_canvas_templates()
+            .map_err(|e| SettingsError::Storage(e.to_string()))?
+        {
+            match storage.load_canvas_template(&info.id) {
+                Ok(template) => canvas_templates.push(template),
+                Err(e) => tracing::warn!("Skipping canvas template {}: {}", info.id, e),
+            }
+        }
+
+        let mut settings = settings.clone();
+        settings.plugins = Default::default();
+
+        Ok(Self {
+            format_version: BUNDLE_FORMAT_VERSION,
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            exported_at: Utc::now(),
+            settings: serde_json::to_value(&settings)?,
+            profiles: ProfileStore::load()?,
+            canvas_templates,
+        })
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        tracing::info!("Exported app configuration to: {:?}", path);
+        Ok(())
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        let bundle: Self = serde_json::from_str(&fs::read_to_string(path)?)?;
+        if bundle.format_version > BUNDLE_FORMAT_VERSION {
+            return Err(SettingsError::UnsupportedVersion(bundle.format_version));
+        }
+        Ok(bundle)
+    }
+
+    /// Settings from the bundle, migrated to the current schema, without
+    /// any event hooks
+    pub fn recording_settings(&self) -> Result<RecordingSettings> {
+        let mut settings = RecordingSettings::from_value_migrated(self.settings.clone())?.0;
+        settings.plugins = Default::default();
+        Ok(settings)
+    }
+
+    /// Save the bundle's profiles and templates, replacing the profile list
+    /// and overwriting templates with the same ID
+    ///
+    /// Settings are returned to the caller, which applies them to the running
+    /// app. The previous `settings.json` is backed up before it's replaced.
+    pub fn install(self, storage: &Storage) -> Result<ImportSummary> {
+        let mut settings = self.recording_settings()?;
+        settings.plugins = RecordingSettings::load()
+            .map(|current| current.plugins)
+            .unwrap_or_default();
+
+        let profiles_imported = self.profiles.profiles.len();
+        self.profiles.save()?;
+
+        let mut templates_imported = 0;
+        let mut templates_skipped = 0;
+        for template in &self.canvas_templates {
+            // The ID becomes a file name
+            if security::validate_template_id(&template.id).is_err() {
+                tracing::warn!("Skipping canvas template with invalid ID: {}", template.id);
+                templates_skipped += 1;
+                continue;
+            }
+            storage
+                .save_canvas_template(template)
+                .map_err(|e| SettingsError::Storage(e.to_string()))?;
+            templates_imported += 1;
+        }
+
+        RecordingSettings::backup_current("import")?;
+        settings.save()?;
+
+        tracing::info!(
+            "Imported app configuration ({} profiles, {} templates)",
+            profiles_imported,
+            templates_imported
+        );
+
+        Ok(ImportSummary {
+            settings,
+            profiles_imported,
+            templates_imported,
+            templates_skipped,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::models::EventHookSettings;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_bundle_round_trip_migrates_settings() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("lolshorts-config.json");
+
+        let mut settings = RecordingSettings::default();
+        settings.hotkeys.manual_save_clip = "F6".to_string();
+        let mut settings_json = serde_json::to_value(&settings).unwrap();
+        // Bundle exported before settings were versioned
+        settings_json
+            .as_object_mut()
+            .unwrap()
+            .remove("schema_version");
+
+        let bundle = ConfigBundle {
+            format_version: BUNDLE_FORMAT_VERSION,
+            app_version: "0.1.0".to_string(),
+            exported_at: Utc::now(),
+            settings: settings_json,
+            profiles: ProfileStore::default(),
+            canvas_templates: Vec::new(),
+        };
+        bundle.write(&path).unwrap();
+
+        let read = ConfigBundle::read(&path).unwrap();
+        assert_eq!(read.profiles.profiles.len(), 3);
+        let imported = read.recording_settings().unwrap();
+        assert_eq!(imported.hotkeys.manual_save_clip, "F6");
+    }
+
+    #[test]
+    fn test_recording_settings_drop_event_hooks() {
+        let mut settings = RecordingSettings::default();
+        settings.plugins.event_hooks.push(EventHookSettings {
+            name: "Stream Deck".to_string(),
+            command: "streamdeck.exe".to_string(),
+            args: Vec::new(),
+            enabled: true,
+        });
+
+        let bundle = ConfigBundle {
+            format_version: BUNDLE_FORMAT_VERSION,
+            app_version: "0.1.0".to_string(),
+            exported_at: Utc::now(),
+            settings: serde_json::to_value(&settings).unwrap(),
+            profiles: ProfileStore::default(),
+            canvas_templates: Vec::new(),
+        };
+
+        let imported = bundle.recording_settings().unwrap();
+        assert!(imported.plugins.event_hooks.is_empty());
+    }
+
+    #[test]
+    fn test_read_rejects_newer_format() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.json");
+        fs::write(
+            &path,
+            serde_json::json!({
+                "format_version": BUNDLE_FORMAT_VERSION + 1,
+                "app_version": "9.0.0",
+                "exported_at": Utc::now(),
+                "settings": {},
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        assert!(matches!(
+            ConfigBundle::read(&path),
+            Err(SettingsError::UnsupportedVersion(_))
+        ));
+    }
+}
@@ -1,4 +1,10 @@
-use super::models::RecordingSettings;
+use super::apply::apply_settings;
+use super::bundle::{ConfigBundle, ImportSummary};
+use super::models::{ProfileStore, RecordingSettings, SettingsProfile};
+use super::profiles;
+use super::storage::SettingsError;
+use crate::utils::error::AppError;
+use crate::utils::security;
 use crate::AppState;
 use tauri::State;
 
@@ -6,7 +12,7 @@ use tauri::State;
 #[tauri::command]
 pub async fn get_recording_settings(
     state: State<'_, AppState>,
-) -> Result<RecordingSettings, String> {
+) -> Result<RecordingSettings, AppError> {
     // Read from shared in-memory settings
     let settings = state.recording_settings.read().await;
     Ok(settings.clone())
@@ -17,45 +23,134 @@ pub async fn get_recording_settings(
 pub async fn save_recording_settings(
     state: State<'_, AppState>,
     settings: RecordingSettings,
-) -> Result<(), String> {
-    // Save to disk first
-    settings.save().map_err(|e| e.to_string())?;
-
-    // Update recording manager audio config
-    // Note: Changes take effect on next segment recording
-    state
-        .recording_manager
-        .write()
-        .await
-        .update_audio_config(&settings.audio);
+) -> Result<(), AppError> {
+    crate::uploads::validate_settings(&settings.uploads)?;
 
-    // Update shared in-memory settings
-    let mut current_settings = state.recording_settings.write().await;
-    *current_settings = settings;
+    // Save to disk
+    settings.save()?;
 
-    Ok(())
+    apply_settings(&state, &settings)
+        .await
+        .map_err(AppError::from)
 }
 
 /// Reset settings to default values
 #[tauri::command]
 pub async fn reset_settings_to_default(
     state: State<'_, AppState>,
-) -> Result<RecordingSettings, String> {
+) -> Result<RecordingSettings, AppError> {
     // Reset to defaults and save
-    let defaults = RecordingSettings::reset_to_default().map_err(|e| e.to_string())?;
+    let defaults = RecordingSettings::reset_to_default()?;
+
+    apply_settings(&state, &defaults).await?;
+
+    Ok(defaults)
+}
+
+/// List saved settings profiles and game mode links
+#[tauri::command]
+pub async fn list_settings_profiles() -> Result<ProfileStore, AppError> {
+    ProfileStore::load().map_err(AppError::from)
+}
 
-    // Update recording manager audio config with defaults
-    state
-        .recording_manager
-        .write()
+/// Save the current video/audio/performance settings as a named profile
+///
+/// An existing profile with the same name is overwritten.
+#[tauri::command]
+pub async fn create_settings_profile(
+    state: State<'_, AppState>,
+    name: String,
+) -> Result<SettingsProfile, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(SettingsError::InvalidProfileName.into());
+    }
+
+    let profile = SettingsProfile::from_settings(name, &*state.recording_settings.read().await);
+
+    let mut store = ProfileStore::load()?;
+    store.upsert(profile.clone());
+    store.save()?;
+
+    Ok(profile)
+}
+
+/// Apply a settings profile, returning the updated settings
+#[tauri::command]
+pub async fn apply_settings_profile(
+    state: State<'_, AppState>,
+    name: String,
+) -> Result<RecordingSettings, AppError> {
+    profiles::apply_profile(&state, &name)
         .await
-        .update_audio_config(&defaults.audio);
+        .map_err(AppError::from)
+}
 
-    // Update shared in-memory settings
-    let mut current_settings = state.recording_settings.write().await;
-    *current_settings = defaults.clone();
+/// Delete a settings profile
+///
+/// Returns false if no profile has that name.
+#[tauri::command]
+pub async fn delete_settings_profile(name: String) -> Result<bool, AppError> {
+    let mut store = ProfileStore::load()?;
+    let removed = store.remove(&name);
+    if removed {
+        store.save()?;
+    }
+    Ok(removed)
+}
 
-    Ok(defaults)
+/// Link a profile to a game mode (LCU `gameMode`, e.g. "ARAM"), or unlink it
+/// with `None`
+#[tauri::command]
+pub async fn set_game_mode_profile(
+    game_mode: String,
+    profile_name: Option<String>,
+) -> Result<(), AppError> {
+    let mut store = ProfileStore::load()?;
+    let game_mode = game_mode.trim().to_uppercase();
+
+    match profile_name {
+        Some(name) => {
+            if store.get(&name).is_none() {
+                return Err(SettingsError::ProfileNotFound(name).into());
+            }
+            store.game_mode_profiles.insert(game_mode, name);
+        }
+        None => {
+            store.game_mode_profiles.remove(&game_mode);
+        }
+    }
+
+    store.save().map_err(AppError::from)
+}
+
+/// Export settings, hotkeys, event filter, profiles and canvas templates to a
+/// single JSON file
+#[tauri::command]
+pub async fn export_app_config(state: State<'_, AppState>, path: String) -> Result<(), AppError> {
+    let path = security::validate_config_bundle_path(&path, false)?;
+
+    let settings = state.recording_settings.read().await.clone();
+    let bundle = ConfigBundle::collect(&settings, &state.storage)?;
+    bundle.write(&path).map_err(AppError::from)
+}
+
+/// Import a configuration bundle written by [`export_app_config`]
+///
+/// Replaces the current settings and profiles (the old settings file is
+/// backed up) and adds the bundle's canvas templates.
+#[tauri::command]
+pub async fn import_app_config(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<ImportSummary, AppError> {
+    let path = security::validate_config_bundle_path(&path, true)?;
+
+    let summary = ConfigBundle::read(&path).and_then(|bundle| bundle.install(&state.storage))?;
+
+    apply_settings(&state, &summary.settings).await?;
+
+    Ok(summary)
 }
 
 // TODO: These tests require Tauri State and should be integration tests
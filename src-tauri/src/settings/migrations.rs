@@ -0,0 +1,125 @@
+/// Versioned settings schema migrations
+///
+/// `settings.json` carries a `schema_version` (files written before versioning
+/// are version 1). On load the raw JSON is upgraded one version at a time
+/// before it is parsed, so a schema change never makes an old file
+/// unreadable. To change the schema, bump [`SETTINGS_SCHEMA_VERSION`] and
+/// append a migration to [`MIGRATIONS`].
+use serde_json::{Map, Value};
+
+use super::models::{RecordingSettings, SETTINGS_SCHEMA_VERSION};
+use super::storage::{Result, SettingsError};
+
+/// Upgrades a settings object from one version to the next
+type Migration = fn(&mut Map<String, Value>) -> Result<()>;
+
+/// `MIGRATIONS[i]` upgrades version `i + 1` to `i + 2`
+const MIGRATIONS: &[Migration] = &[v1_to_v2, v2_to_v3];
+
+/// Version of a raw settings file (1 if it predates versioning)
+pub fn schema_version(value: &Value) -> u32 {
+    value
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .map_or(1, |version| (version as u32).max(1))
+}
+
+/// Upgrade raw settings JSON to the current schema
+///
+/// Returns the version the file was at, so the caller can tell whether it
+/// needs to be backed up and rewritten.
+pub fn migrate(value: &mut Value) -> Result<u32> {
+    let from = schema_version(value);
+    if from > SETTINGS_SCHEMA_VERSION {
+        return Err(SettingsError::UnsupportedVersion(from));
+    }
+
+    let object = value.as_object_mut().ok_or(SettingsError::InvalidFormat)?;
+
+    for version in from..SETTINGS_SCHEMA_VERSION {
+        MIGRATIONS[(version - 1) as usize](object)?;
+        object.insert("schema_version".to_string(), Value::from(version + 1));
+        tracing::info!("Migrated settings from v{} to v{}", version, version + 1);
+    }
+
+    Ok(from)
+}
+
+/// v1 -> v2: fill in fields missing from older files with their defaults
+///
+/// Sections and fields added before versioning existed (without
+/// `#[serde(default)]`) made older files fail to parse entirely.
+fn v1_to_v2(object: &mut Map<String, Value>) -> Result<()> {
+    let defaults = serde_json::to_value(RecordingSettings::default())?;
+    if let Value::Object(defaults) = defaults {
+        fill_missing(object, &defaults);
+    }
+    Ok(())
+}
+
+/// v2 -> v3: users with an existing settings file skip the setup wizard
+fn v2_to_v3(object: &mut Map<String, Value>) -> Result<()> {
+    object.insert("setup_completed".to_string(), Value::Bool(true));
+    Ok(())
+}
+
+/// Recursively add keys from `defaults` that `target` doesn't have
+fn fill_missing(target: &mut Map<String, Value>, defaults: &Map<String, Value>) {
+    for (key, default) in defaults {
+        match (target.get_mut(key), default) {
+            (None, _) => {
+                target.insert(key.clone(), default.clone());
+            }
+            (Some(Value::Object(existing)), Value::Object(default)) => {
+                fill_missing(existing, default);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_migrate_unversioned_file() {
+        let mut value = serde_json::to_value(RecordingSettings::default()).unwrap();
+        let object = value.as_object_mut().unwrap();
+        object.remove("schema_version");
+        object.remove("hotkeys");
+        object["audio"]
+            .as_object_mut()
+            .unwrap()
+            .remove("sample_rate");
+        object["audio"]["microphone_volume"] = json!(150);
+
+        assert_eq!(migrate(&mut value).unwrap(), 1);
+        assert_eq!(schema_version(&value), SETTINGS_SCHEMA_VERSION);
+
+        // Missing fields are filled in, user values are kept
+        let settings: RecordingSettings = serde_json::from_value(value).unwrap();
+        assert_eq!(settings.audio.microphone_volume, 150);
+        assert_eq!(settings.hotkeys.manual_save_clip, "F8");
+        assert!(settings.setup_completed);
+    }
+
+    #[test]
+    fn test_migrate_current_version_is_noop() {
+        let mut value = serde_json::to_value(RecordingSettings::default()).unwrap();
+        let before = value.clone();
+
+        assert_eq!(migrate(&mut value).unwrap(), SETTINGS_SCHEMA_VERSION);
+        assert_eq!(value, before);
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer_version() {
+        let mut value = json!({ "schema_version": SETTINGS_SCHEMA_VERSION + 1 });
+        assert!(matches!(
+            migrate(&mut value),
+            Err(SettingsError::UnsupportedVersion(_))
+        ));
+    }
+}
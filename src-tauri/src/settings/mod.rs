@@ -1,5 +1,9 @@
+pub mod apply;
+pub mod bundle;
 pub mod commands;
+pub mod migrations;
 pub mod models;
+pub mod profiles;
 pub mod storage;
 
 // Re-export public types
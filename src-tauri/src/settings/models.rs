@@ -1,35 +1,99 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Current `settings.json` schema version (see `settings::migrations`)
+pub const SETTINGS_SCHEMA_VERSION: u32 = 3;
 
 /// Complete recording settings structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecordingSettings {
+    // 설정 파일 스키마 버전 (마이그레이션용, 파일에서는 migrations가 먼저 처리)
+    #[serde(default = "current_schema_version")]
+    pub schema_version: u32,
+
     pub event_filter: EventFilterSettings,
     pub game_mode: GameModeSettings,
     pub video: VideoSettings,
     pub audio: AudioSettings,
     pub clip_timing: ClipTimingSettings,
     pub hotkeys: HotkeySettings,
+    #[serde(default)]
+    pub performance: PerformanceSettings,
+    #[serde(default)]
+    pub logging: LoggingSettings,
+    #[serde(default)]
+    pub telemetry: TelemetrySettings,
+    #[serde(default)]
+    pub cloud_sync: CloudSyncSettings,
+    #[serde(default)]
+    pub notifications: NotificationSettings,
+    #[serde(default)]
+    pub plugins: PluginSettings,
+    #[serde(default)]
+    pub obs: ObsSettings,
+    #[serde(default)]
+    pub storage: StorageLocationSettings,
+    #[serde(default)]
+    pub retry: RetrySettings,
+    #[serde(default)]
+    pub lcu: LcuConnectionSettings,
+    #[serde(default)]
+    pub uploads: UploadSettings,
 
     // General settings
     pub auto_start_with_league: bool,
     pub minimize_to_tray: bool,
     pub show_notifications: bool,
+
+    // 로그인 시 자동 실행 (트레이로 최소화해서 시작 가능)
+    #[serde(default)]
+    pub start_on_boot: bool,
+    #[serde(default)]
+    pub start_minimized: bool,
+
+    // 첫 실행 설정 마법사 완료 여부
+    #[serde(default)]
+    pub setup_completed: bool,
+
+    // League of Legends 설치 경로 직접 지정 (없으면 자동 감지)
+    #[serde(default)]
+    pub league_install_path: Option<String>,
+}
+
+fn current_schema_version() -> u32 {
+    SETTINGS_SCHEMA_VERSION
 }
 
 impl Default for RecordingSettings {
     fn default() -> Self {
         Self {
+            schema_version: SETTINGS_SCHEMA_VERSION,
             event_filter: EventFilterSettings::default(),
             game_mode: GameModeSettings::default(),
             video: VideoSettings::default(),
             audio: AudioSettings::default(),
             clip_timing: ClipTimingSettings::default(),
             hotkeys: HotkeySettings::default(),
+            performance: PerformanceSettings::default(),
+            logging: LoggingSettings::default(),
+            telemetry: TelemetrySettings::default(),
+            cloud_sync: CloudSyncSettings::default(),
+            notifications: NotificationSettings::default(),
+            plugins: PluginSettings::default(),
+            obs: ObsSettings::default(),
+            storage: StorageLocationSettings::default(),
+            retry: RetrySettings::default(),
+            lcu: LcuConnectionSettings::default(),
+            uploads: UploadSettings::default(),
 
             auto_start_with_league: true,
             minimize_to_tray: true,
             show_notifications: true,
+            start_on_boot: false,
+            start_minimized: false,
+            setup_completed: false,
+            league_install_path: None,
         }
     }
 }
@@ -70,6 +134,14 @@ pub struct EventFilterSettings {
 
     // 우선순위 필터
     pub min_priority: u8, // 1-5
+
+    // 데스 클립 우선순위 (1-5, 페일 모음용)
+    #[serde(default = "default_death_priority")]
+    pub death_priority: u8,
+
+    // 관전/리플레이 게임도 자동 클립 (기본 OFF - 직접 플레이하지 않은 게임)
+    #[serde(default)]
+    pub record_spectator_games: bool,
 }
 
 impl Default for EventFilterSettings {
@@ -99,10 +171,17 @@ impl Default for EventFilterSettings {
             record_steal: true,
 
             min_priority: 1, // Allow all events including single kills
+            death_priority: default_death_priority(),
+
+            record_spectator_games: false,
         }
     }
 }
 
+fn default_death_priority() -> u8 {
+    1
+}
+
 // ============================================================================
 // Game Mode Settings
 // ============================================================================
@@ -147,11 +226,25 @@ pub struct VideoSettings {
     pub bitrate_preset: BitratePreset,
     pub codec: VideoCodec,
     pub encoder: EncoderPreference,
+    #[serde(default)]
+    pub facecam: FacecamSettings,
+    // 녹화할 모니터 (list_capture_displays의 id, 없으면 주 모니터)
+    #[serde(default)]
+    pub capture_display: Option<String>,
+    #[serde(default)]
+    pub capture_backend: CaptureBackend,
+    // 녹화에 마우스 커서 포함
+    #[serde(default = "default_true")]
+    pub capture_cursor: bool,
+    // HDR 모니터에서도 톤 매핑 없이 SDR로 캡처
+    #[serde(default)]
+    pub force_sdr: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Resolution {
+    R1280x720,  // 720p (저사양)
     R1920x1080, // 1080p (추천)
     R2560x1440, // 1440p
     R3840x2160, // 4K
@@ -194,6 +287,26 @@ pub enum EncoderPreference {
     Software, // CPU (느림, 호환성 높음)
 }
 
+/// 화면 캡처 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureBackend {
+    // Windows Graphics Capture (추천, 전체화면 게임도 캡처, 실패 시 gdigrab)
+    #[default]
+    Wgc,
+    // GDI 캡처 (느림, 호환성 높음)
+    Gdigrab,
+}
+
+/// 웹캠 녹화 설정 (게임 영상과 별도 파일로 저장, 숏츠 편집 시 오버레이)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FacecamSettings {
+    pub enabled: bool,
+    // DirectShow 비디오 장치 이름 (list_video_devices)
+    pub device: Option<String>,
+}
+
 impl Default for VideoSettings {
     fn default() -> Self {
         Self {
@@ -202,6 +315,11 @@ impl Default for VideoSettings {
             bitrate_preset: BitratePreset::Medium,
             codec: VideoCodec::H265,
             encoder: EncoderPreference::Auto,
+            facecam: FacecamSettings::default(),
+            capture_display: None,
+            capture_backend: CaptureBackend::default(),
+            capture_cursor: true,
+            force_sdr: false,
         }
     }
 }
@@ -276,6 +394,19 @@ pub struct ClipTimingSettings {
     // 이벤트 병합
     pub merge_consecutive_events: bool,
     pub merge_time_threshold: f64, // 15초 기본
+
+    // 중복 클립 (단축키와 자동 캡처가 같은 장면을 저장한 경우)
+    #[serde(default)]
+    pub duplicate_clips: DuplicateClipHandling,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateClipHandling {
+    Keep, // 그대로 저장
+    #[default]
+    Link, // 저장 후 원본 클립에 연결 (추천)
+    Skip, // 자동 캡처는 저장하지 않음 (단축키 저장은 Link처럼 연결)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -321,6 +452,7 @@ impl Default for ClipTimingSettings {
             event_timings,
             merge_consecutive_events: true,
             merge_time_threshold: 15.0,
+            duplicate_clips: DuplicateClipHandling::default(),
         }
     }
 }
@@ -359,6 +491,547 @@ impl Default for HotkeySettings {
     }
 }
 
+// ============================================================================
+// Performance Settings
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PerformanceSettings {
+    // 저사양 모드 (720p30, 소프트웨어 x264 ultrafast, 비트레이트 제한, 오디오 필터 OFF)
+    pub low_spec_mode: bool,
+
+    // 게임 중이 아닐 때 리플레이 버퍼 자동 일시정지 (다음 게임 시작 시 재개)
+    pub pause_buffer_outside_game: bool,
+
+    // 게임 시작 전 리플레이 버퍼를 미리 켜는 단계 (로딩이 끝났을 때 이미 프리롤 확보)
+    pub buffer_warmup_phase: BufferWarmupPhase,
+
+    // CPU 사용률이 계속 높으면 저사양 모드 자동 전환
+    pub auto_enable_on_high_cpu: bool,
+    pub high_cpu_threshold: f32,     // % (0-100)
+    pub high_cpu_duration_secs: u64, // 이 시간 이상 지속 시 전환
+
+    // 멈춘 FFmpeg 프로세스 강제 종료까지의 제한 시간
+    pub ffmpeg_timeout_secs: u64,    // 편집/인코딩 작업 (초)
+    pub clip_save_timeout_secs: u64, // 클립 저장 (세그먼트 병합, 초)
+
+    // Live Client API 폴링 간격 (게임 밖에서는 느리게, 연결 실패가 반복되면 점점 늘림)
+    pub live_client_poll_in_game_ms: u64, // 게임 중 (ms)
+    pub live_client_poll_idle_ms: u64,    // 게임 밖 첫 재시도 (ms)
+    pub live_client_max_backoff_ms: u64,  // 연결 실패 시 최대 간격 (ms)
+}
+
+impl Default for PerformanceSettings {
+    fn default() -> Self {
+        Self {
+            low_spec_mode: false,
+            pause_buffer_outside_game: true,
+            buffer_warmup_phase: BufferWarmupPhase::default(),
+            auto_enable_on_high_cpu: true,
+            high_cpu_threshold: 90.0,
+            high_cpu_duration_secs: 60,
+            ffmpeg_timeout_secs: 30 * 60,
+            clip_save_timeout_secs: 60,
+            live_client_poll_in_game_ms: 500,
+            live_client_poll_idle_ms: 2_000,
+            live_client_max_backoff_ms: 15_000,
+        }
+    }
+}
+
+/// 리플레이 버퍼 워밍업 시작 단계
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BufferWarmupPhase {
+    // 워밍업 없음 (게임 시작 시 버퍼 재개)
+    Off,
+    // 챔피언 선택부터
+    #[default]
+    ChampSelect,
+    // 로딩 화면부터
+    GameStart,
+}
+
+// ============================================================================
+// Logging Settings
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingSettings {
+    // 기본 로그 레벨 (trace, debug, info, warn, error)
+    pub level: String,
+
+    // 모듈별 레벨 오버라이드 (예: "lolshorts::recording" => "debug")
+    pub module_levels: HashMap<String, String>,
+
+    // 로그 파일을 JSON 형식으로 기록 (false면 일반 텍스트)
+    pub json_format: bool,
+}
+
+impl Default for LoggingSettings {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            module_levels: HashMap::new(),
+            json_format: true,
+        }
+    }
+}
+
+// ============================================================================
+// Telemetry Settings
+// ============================================================================
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetrySettings {
+    // 익명 사용 통계 전송 (옵트인, 기본값 OFF)
+    pub enabled: bool,
+}
+
+// ============================================================================
+// Cloud Sync Settings (PRO)
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CloudSyncSettings {
+    // 클립 메타데이터/템플릿/설정 자동 백업
+    pub auto_sync: bool,
+    pub interval_minutes: u32,
+}
+
+impl Default for CloudSyncSettings {
+    fn default() -> Self {
+        Self {
+            auto_sync: true,
+            interval_minutes: 30,
+        }
+    }
+}
+
+// ============================================================================
+// Notification Settings
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    // Discord 웹훅 URL (없으면 알림 끔)
+    pub discord_webhook_url: Option<String>,
+
+    // 하이라이트 클립 저장 알림 (min_clip_priority 이상만)
+    pub notify_clip_saved: bool,
+    pub min_clip_priority: u8,
+
+    // YouTube 업로드 완료 알림
+    pub notify_upload_completed: bool,
+
+    // 게임 중 클립 저장 시 Windows 토스트 알림 (toast_min_priority 이상만)
+    pub toast_clip_saved: bool,
+    pub toast_min_priority: u8,
+
+    // 방해 금지 시간 (토스트 알림 끔)
+    pub quiet_hours: QuietHours,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            discord_webhook_url: None,
+            notify_clip_saved: true,
+            min_clip_priority: 4,
+            notify_upload_completed: true,
+            toast_clip_saved: true,
+            toast_min_priority: 5,
+            quiet_hours: QuietHours::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuietHours {
+    pub enabled: bool,
+    // 로컬 시각 기준 (0-23), start > end 이면 자정을 넘김
+    pub start_hour: u8,
+    pub end_hour: u8,
+}
+
+impl Default for QuietHours {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start_hour: 23,
+            end_hour: 8,
+        }
+    }
+}
+
+impl QuietHours {
+    /// Whether `hour` (0-23, local time) falls in the quiet period
+    pub fn contains(&self, hour: u8) -> bool {
+        if !self.enabled || self.start_hour == self.end_hour {
+            return false;
+        }
+        if self.start_hour < self.end_hour {
+            (self.start_hour..self.end_hour).contains(&hour)
+        } else {
+            hour >= self.start_hour || hour < self.end_hour
+        }
+    }
+}
+
+// ============================================================================
+// Plugin Settings
+// ============================================================================
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PluginSettings {
+    // 게임 이벤트마다 실행할 외부 프로그램 (이벤트 JSON을 stdin으로 전달)
+    pub event_hooks: Vec<EventHookSettings>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventHookSettings {
+    pub name: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// ============================================================================
+// OBS Settings
+// ============================================================================
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ObsSettings {
+    // 내장 녹화 대신 OBS 리플레이 버퍼 사용 (obs-websocket 5.x 필요)
+    pub enabled: bool,
+
+    // obs-websocket 서버 주소
+    pub host: String,
+    pub port: u16,
+
+    // obs-websocket 인증 비밀번호 (인증 꺼져 있으면 없음)
+    pub password: Option<String>,
+}
+
+impl Default for ObsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "localhost".to_string(),
+            port: 4455,
+            password: None,
+        }
+    }
+}
+
+// ============================================================================
+// Storage Location Settings
+// ============================================================================
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StorageLocationSettings {
+    // 녹화 파일 루트 폴더 (없으면 앱 데이터 폴더, 메타데이터는 항상 앱 데이터 폴더)
+    pub recordings_root: Option<String>,
+
+    // 카테고리별 폴더 지정 (없으면 루트 아래 기본 폴더)
+    pub clips_dir: Option<String>,
+    pub auto_edits_dir: Option<String>,
+}
+
+impl StorageLocationSettings {
+    fn root(&self, data_dir: &Path) -> PathBuf {
+        self.recordings_root
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| data_dir.to_path_buf())
+    }
+
+    /// Replay buffer working folder (temp segments, crash recovery)
+    pub fn recordings_dir(&self, data_dir: &Path) -> PathBuf {
+        self.root(data_dir).join("recordings")
+    }
+
+    /// Where saved clips are written
+    pub fn clips_dir(&self, data_dir: &Path) -> PathBuf {
+        self.clips_dir
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| self.recordings_dir(data_dir))
+    }
+
+    /// Where finished auto-edit videos are written
+    pub fn auto_edits_dir(&self, data_dir: &Path) -> PathBuf {
+        self.auto_edits_dir
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| self.root(data_dir).join("auto_edits"))
+    }
+}
+
+// ============================================================================
+// Retry Settings (advanced)
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetryPolicy {
+    // 최대 시도 횟수 (첫 시도 포함)
+    pub max_attempts: u32,
+
+    // 재시도 간격 (지수 백오프, 밀리초)
+    pub initial_delay_ms: u64,
+    pub max_delay_ms: u64,
+    pub backoff_multiplier: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay_ms: 500,
+            max_delay_ms: 5_000,
+            backoff_multiplier: 2.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetrySettings {
+    // FFmpeg 녹화 프로세스 시작
+    pub ffmpeg_start: RetryPolicy,
+
+    // YouTube/클라우드 업로드
+    pub upload: RetryPolicy,
+
+    // Supabase API 요청
+    pub supabase: RetryPolicy,
+}
+
+impl Default for RetrySettings {
+    fn default() -> Self {
+        Self {
+            ffmpeg_start: RetryPolicy::default(),
+            upload: RetryPolicy {
+                max_attempts: 3,
+                initial_delay_ms: 2_000,
+                max_delay_ms: 30_000,
+                backoff_multiplier: 2.0,
+            },
+            supabase: RetryPolicy {
+                max_attempts: 3,
+                initial_delay_ms: 200,
+                max_delay_ms: 5_000,
+                backoff_multiplier: 2.0,
+            },
+        }
+    }
+}
+
+// ============================================================================
+// LCU Connection Settings (advanced)
+// ============================================================================
+
+/// League 클라이언트(LCU) 포트/인증 토큰을 찾는 방법
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LcuDiscoveryStrategy {
+    // lockfile → 프로세스 명령줄 → 수동 입력 순서로 시도
+    #[default]
+    Auto,
+    // lockfile만 사용
+    Lockfile,
+    // LeagueClientUx 프로세스 명령줄에서 추출 (Windows는 WMI)
+    ProcessCommandLine,
+    // 포트/토큰 직접 입력
+    Manual,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LcuConnectionSettings {
+    pub strategy: LcuDiscoveryStrategy,
+
+    // lockfile 경로 직접 지정 (Garena 등 설치 구조가 다른 클라이언트)
+    pub lockfile_path: Option<String>,
+
+    // 수동 연결용 포트와 인증 토큰
+    pub port: Option<u16>,
+    pub auth_token: Option<String>,
+}
+
+// ============================================================================
+// Upload Settings
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UploadSettings {
+    // 동시에 진행할 업로드 수 (최소 1)
+    pub max_concurrent: u32,
+
+    // 업로드 대역폭 제한 (KB/s, 0 = 무제한). 게임 중 랙 방지용
+    pub bandwidth_limit_kbps: u32,
+}
+
+impl Default for UploadSettings {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 1,
+            bandwidth_limit_kbps: 0,
+        }
+    }
+}
+
+// ============================================================================
+// Settings Profiles
+// ============================================================================
+
+/// 이름 붙은 녹화 설정 프로필 (비디오/오디오/성능만 포함)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsProfile {
+    pub name: String,
+    pub video: VideoSettings,
+    pub audio: AudioSettings,
+    #[serde(default)]
+    pub performance: PerformanceSettings,
+}
+
+impl SettingsProfile {
+    /// 현재 설정으로 프로필 생성
+    pub fn from_settings(name: &str, settings: &RecordingSettings) -> Self {
+        Self {
+            name: name.to_string(),
+            video: settings.video.clone(),
+            audio: settings.audio.clone(),
+            performance: settings.performance.clone(),
+        }
+    }
+
+    /// 프로필 내용을 설정에 덮어쓰기 (나머지 항목은 유지)
+    pub fn apply_to(&self, settings: &mut RecordingSettings) {
+        settings.video = self.video.clone();
+        settings.audio = self.audio.clone();
+        settings.performance = self.performance.clone();
+    }
+}
+
+/// 저장된 프로필 목록 (profiles.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileStore {
+    pub profiles: Vec<SettingsProfile>,
+
+    // 마지막으로 적용한 프로필
+    pub active_profile: Option<String>,
+
+    // 게임 모드별 자동 적용 프로필 (LCU gameMode => 프로필 이름, 예: "ARAM" => "Low spec")
+    pub game_mode_profiles: HashMap<String, String>,
+}
+
+impl ProfileStore {
+    pub fn get(&self, name: &str) -> Option<&SettingsProfile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// 프로필 추가 (같은 이름이면 교체)
+    pub fn upsert(&mut self, profile: SettingsProfile) {
+        match self.profiles.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => self.profiles.push(profile),
+        }
+    }
+
+    /// 프로필 삭제 (게임 모드 연결도 함께 제거)
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.profiles.len();
+        self.profiles.retain(|profile| profile.name != name);
+        if self.profiles.len() == before {
+            return false;
+        }
+
+        self.game_mode_profiles.retain(|_, profile| profile != name);
+        if self.active_profile.as_deref() == Some(name) {
+            self.active_profile = None;
+        }
+        true
+    }
+
+    /// 게임 모드에 자동 적용할 프로필
+    pub fn profile_for_game_mode(&self, game_mode: &str) -> Option<&SettingsProfile> {
+        self.game_mode_profiles
+            .get(&game_mode.to_uppercase())
+            .and_then(|name| self.get(name))
+    }
+}
+
+impl Default for ProfileStore {
+    fn default() -> Self {
+        let ranked = SettingsProfile {
+            name: "Ranked 1080p60".to_string(),
+            video: VideoSettings::default(),
+            audio: AudioSettings::default(),
+            performance: PerformanceSettings::default(),
+        };
+
+        let low_spec = SettingsProfile {
+            name: "Low spec".to_string(),
+            video: VideoSettings {
+                resolution: Resolution::R1280x720,
+                frame_rate: FrameRate::Fps30,
+                bitrate_preset: BitratePreset::Low,
+                codec: VideoCodec::H264,
+                encoder: EncoderPreference::Auto,
+                facecam: FacecamSettings::default(),
+                capture_display: None,
+                capture_backend: CaptureBackend::default(),
+                capture_cursor: true,
+                force_sdr: false,
+            },
+            audio: AudioSettings::default(),
+            performance: PerformanceSettings {
+                low_spec_mode: true,
+                ..PerformanceSettings::default()
+            },
+        };
+
+        // 방송 PC: 마이크는 방송 쪽에서 따로 잡으므로 녹화하지 않음
+        let streaming = SettingsProfile {
+            name: "Streaming PC".to_string(),
+            video: VideoSettings {
+                codec: VideoCodec::H264,
+                ..VideoSettings::default()
+            },
+            audio: AudioSettings {
+                record_microphone: false,
+                ..AudioSettings::default()
+            },
+            performance: PerformanceSettings::default(),
+        };
+
+        Self {
+            profiles: vec![ranked, low_spec, streaming],
+            active_profile: None,
+            game_mode_profiles: HashMap::new(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -412,6 +1085,59 @@ mod tests {
         assert_eq!(unknown_timing.post_duration, 3);
     }
 
+    #[test]
+    fn test_performance_settings_backward_compatible() {
+        let mut json = serde_json::to_value(RecordingSettings::default()).unwrap();
+        json.as_object_mut().unwrap().remove("performance");
+
+        // Settings saved before performance mode existed should still load
+        let settings: RecordingSettings = serde_json::from_value(json).unwrap();
+        assert!(!settings.performance.low_spec_mode);
+        assert!(settings.performance.auto_enable_on_high_cpu);
+    }
+
+    #[test]
+    fn test_profile_store() {
+        let mut store = ProfileStore::default();
+        assert!(store.get("Low spec").unwrap().performance.low_spec_mode);
+
+        let mut settings = RecordingSettings::default();
+        settings.audio.microphone_volume = 150;
+        store.upsert(SettingsProfile::from_settings("Low spec", &settings));
+        assert_eq!(store.profiles.len(), 3);
+        assert!(!store.get("Low spec").unwrap().performance.low_spec_mode);
+
+        store
+            .game_mode_profiles
+            .insert("ARAM".to_string(), "Low spec".to_string());
+        store.active_profile = Some("Low spec".to_string());
+        assert!(store.profile_for_game_mode("aram").is_some());
+        assert!(store.profile_for_game_mode("CLASSIC").is_none());
+
+        assert!(store.remove("Low spec"));
+        assert!(!store.remove("Low spec"));
+        assert!(store.game_mode_profiles.is_empty());
+        assert!(store.active_profile.is_none());
+    }
+
+    #[test]
+    fn test_quiet_hours() {
+        let mut quiet = QuietHours::default();
+        assert!(!quiet.contains(2));
+
+        // 23:00-08:00 wraps past midnight
+        quiet.enabled = true;
+        assert!(quiet.contains(23));
+        assert!(quiet.contains(2));
+        assert!(!quiet.contains(8));
+        assert!(!quiet.contains(15));
+
+        quiet.start_hour = 9;
+        quiet.end_hour = 17;
+        assert!(quiet.contains(9));
+        assert!(!quiet.contains(17));
+    }
+
     #[test]
     fn test_serialization() {
         let settings = RecordingSettings::default();
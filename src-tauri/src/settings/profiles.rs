@@ -0,0 +1,82 @@
+/// Applying settings profiles
+///
+/// A profile overwrites the video, audio and performance sections of the
+/// current settings. Profiles can also be linked to LCU game modes; the
+/// linked profile is applied automatically when a game of that mode starts.
+use std::sync::Arc;
+
+use super::apply::apply_settings;
+use super::models::{ProfileStore, RecordingSettings};
+use super::storage::{Result, SettingsError};
+use crate::lcu::GameActivity;
+use crate::AppState;
+
+/// Apply a saved profile to the running app and persist it
+pub async fn apply_profile(state: &AppState, name: &str) -> Result<RecordingSettings> {
+    let mut store = ProfileStore::load()?;
+    let profile = store
+        .get(name)
+        .cloned()
+        .ok_or_else(|| SettingsError::ProfileNotFound(name.to_string()))?;
+
+    let settings = {
+        let mut current = state.recording_settings.write().await;
+        profile.apply_to(&mut current);
+        current.save()?;
+        current.clone()
+    };
+
+    // A profile doesn't touch telemetry or start on boot, so a failure to
+    // update them isn't the profile's
+    if let Err(e) = apply_settings(state, &settings).await {
+        tracing::warn!("Failed to apply settings profile {}: {:#}", profile.name, e);
+    }
+
+    store.active_profile = Some(profile.name.clone());
+    store.save()?;
+
+    tracing::info!("Applied settings profile: {}", profile.name);
+    Ok(settings)
+}
+
+/// Start background task applying the profile linked to each game's mode
+pub fn start_game_mode_profiles(
+    state: AppState,
+    activity: Arc<GameActivity>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut receiver = activity.subscribe();
+
+        while receiver.changed().await.is_ok() {
+            if *receiver.borrow_and_update() != Some(true) {
+                continue;
+            }
+            let Some(game_mode) = activity.game_mode() else {
+                continue;
+            };
+
+            let store = match ProfileStore::load() {
+                Ok(store) => store,
+                Err(e) => {
+                    tracing::warn!("Failed to load settings profiles: {}", e);
+                    continue;
+                }
+            };
+            let Some(profile) = store.profile_for_game_mode(&game_mode) else {
+                continue;
+            };
+            if store.active_profile.as_deref() == Some(profile.name.as_str()) {
+                continue;
+            }
+
+            tracing::info!(
+                "{} game started, switching to profile {}",
+                game_mode,
+                profile.name
+            );
+            if let Err(e) = apply_profile(&state, &profile.name).await {
+                tracing::warn!("Failed to apply profile {}: {}", profile.name, e);
+            }
+        }
+    })
+}
@@ -1,6 +1,7 @@
-use super::models::RecordingSettings;
+use super::migrations;
+use super::models::{ProfileStore, RecordingSettings, SETTINGS_SCHEMA_VERSION};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -13,6 +14,21 @@ pub enum SettingsError {
 
     #[error("JSON serialization error: {0}")]
     Json(#[from] serde_json::Error),
+
+    #[error("Settings file is version {0}, newer than this app supports")]
+    UnsupportedVersion(u32),
+
+    #[error("Settings file is not a JSON object")]
+    InvalidFormat,
+
+    #[error("Settings profile not found: {0}")]
+    ProfileNotFound(String),
+
+    #[error("Invalid settings profile name")]
+    InvalidProfileName,
+
+    #[error("Storage error: {0}")]
+    Storage(String),
 }
 
 pub type Result<T> = std::result::Result<T, SettingsError>;
@@ -21,13 +37,35 @@ impl RecordingSettings {
     /// Load settings from file
     ///
     /// If the settings file doesn't exist, returns default settings.
+    /// Older schema versions are migrated and rewritten, keeping the previous
+    /// file as `settings.v<N>.bak.json`. A file that can't be read is backed
+    /// up as `settings.invalid.bak.json` before the error is returned, so the
+    /// defaults saved afterwards don't destroy it.
     /// Location: %APPDATA%/Roaming/LoLShorts/settings.json (Windows)
     pub fn load() -> Result<Self> {
         let settings_path = Self::get_settings_path()?;
 
         if settings_path.exists() {
             let json = fs::read_to_string(&settings_path)?;
-            let settings = serde_json::from_str(&json)?;
+
+            let (settings, from_version) = match Self::parse_and_migrate(&json) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    let backup = backup_path(&settings_path, "invalid");
+                    if let Err(backup_err) = fs::copy(&settings_path, &backup) {
+                        tracing::error!("Failed to back up unreadable settings: {}", backup_err);
+                    } else {
+                        tracing::warn!("Unreadable settings backed up to: {:?}", backup);
+                    }
+                    return Err(e);
+                }
+            };
+
+            if from_version < SETTINGS_SCHEMA_VERSION {
+                Self::backup_current(&format!("v{}", from_version))?;
+                settings.save()?;
+            }
+
             tracing::info!("Loaded settings from: {:?}", settings_path);
             Ok(settings)
         } else {
@@ -36,6 +74,32 @@ impl RecordingSettings {
         }
     }
 
+    /// Parse settings JSON, upgrading it to the current schema
+    ///
+    /// Returns the settings and the schema version the JSON was at.
+    pub(crate) fn parse_and_migrate(json: &str) -> Result<(Self, u32)> {
+        Self::from_value_migrated(serde_json::from_str(json)?)
+    }
+
+    /// Like [`Self::parse_and_migrate`], for already parsed JSON
+    pub(crate) fn from_value_migrated(mut value: serde_json::Value) -> Result<(Self, u32)> {
+        let from_version = migrations::migrate(&mut value)?;
+        Ok((serde_json::from_value(value)?, from_version))
+    }
+
+    /// Copy the current settings file to `settings.<tag>.bak.json`
+    ///
+    /// Does nothing if there is no settings file yet.
+    pub fn backup_current(tag: &str) -> Result<()> {
+        let settings_path = Self::get_settings_path()?;
+        if settings_path.exists() {
+            let backup = backup_path(&settings_path, tag);
+            fs::copy(&settings_path, &backup)?;
+            tracing::info!("Previous settings backed up to: {:?}", backup);
+        }
+        Ok(())
+    }
+
     /// Save settings to file
     ///
     /// Creates the config directory if it doesn't exist.
@@ -60,7 +124,7 @@ impl RecordingSettings {
     /// - Windows: %APPDATA%/Roaming/LoLShorts/settings.json
     /// - macOS: ~/Library/Application Support/LoLShorts/settings.json
     /// - Linux: ~/.config/LoLShorts/settings.json
-    fn get_settings_path() -> Result<PathBuf> {
+    pub(crate) fn get_settings_path() -> Result<PathBuf> {
         let config_dir = dirs::config_dir().ok_or(SettingsError::ConfigDirNotFound)?;
 
         let lolshorts_dir = config_dir.join("LoLShorts");
@@ -76,6 +140,51 @@ impl RecordingSettings {
     }
 }
 
+/// `settings.json` -> `settings.<tag>.bak.json`
+fn backup_path(path: &Path, tag: &str) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "settings".to_string());
+    path.with_file_name(format!("{}.{}.bak.json", stem, tag))
+}
+
+impl ProfileStore {
+    /// Load profiles from file
+    ///
+    /// If the profiles file doesn't exist, returns the built-in presets.
+    pub fn load() -> Result<Self> {
+        let profiles_path = Self::get_profiles_path()?;
+
+        if profiles_path.exists() {
+            let json = fs::read_to_string(&profiles_path)?;
+            Ok(serde_json::from_str(&json)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Save profiles next to settings.json
+    pub fn save(&self) -> Result<()> {
+        let profiles_path = Self::get_profiles_path()?;
+
+        if let Some(parent) = profiles_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(&profiles_path, json)?;
+
+        tracing::debug!("Saved settings profiles to: {:?}", profiles_path);
+        Ok(())
+    }
+
+    pub(crate) fn get_profiles_path() -> Result<PathBuf> {
+        let settings_path = RecordingSettings::get_settings_path()?;
+        Ok(settings_path.with_file_name("profiles.json"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,6 +197,35 @@ mod tests {
         assert!(path.to_string_lossy().ends_with("settings.json"));
     }
 
+    #[test]
+    fn test_backup_path() {
+        let path = PathBuf::from("LoLShorts").join("settings.json");
+        assert_eq!(
+            backup_path(&path, "v1"),
+            PathBuf::from("LoLShorts").join("settings.v1.bak.json")
+        );
+    }
+
+    #[test]
+    fn test_parse_legacy_settings() {
+        let mut value = serde_json::to_value(RecordingSettings::default()).unwrap();
+        value.as_object_mut().unwrap().remove("schema_version");
+        value["event_filter"]["min_priority"] = serde_json::json!(4);
+
+        let (settings, from_version) =
+            RecordingSettings::parse_and_migrate(&value.to_string()).unwrap();
+        assert_eq!(from_version, 1);
+        assert_eq!(settings.schema_version, SETTINGS_SCHEMA_VERSION);
+        assert_eq!(settings.event_filter.min_priority, 4);
+    }
+
+    #[test]
+    fn test_profiles_path() {
+        let path = ProfileStore::get_profiles_path().unwrap();
+        assert!(path.to_string_lossy().contains("LoLShorts"));
+        assert!(path.to_string_lossy().ends_with("profiles.json"));
+    }
+
     #[test]
     #[ignore] // Ignored due to race condition with test_reset_to_default (both use same settings file)
     fn test_save_and_load() {
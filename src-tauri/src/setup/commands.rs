@@ -0,0 +1,95 @@
+use super::demo::{self, DemoLibrary};
+use super::SetupReport;
+use crate::jobs::{JobKind, JobPriority};
+use crate::settings::apply::apply_settings;
+use crate::settings::models::RecordingSettings;
+use crate::AppState;
+use std::path::PathBuf;
+use tauri::State;
+
+/// Run the first-run checks for the setup wizard
+#[tauri::command]
+pub async fn get_setup_report(state: State<'_, AppState>) -> Result<SetupReport, String> {
+    // FREE tier feature - no authentication required
+    detect(&state).await
+}
+
+/// Apply settings recommended for this machine, returning them
+///
+/// Only video, audio and performance settings change.
+#[tauri::command]
+pub async fn apply_recommended_settings(
+    state: State<'_, AppState>,
+) -> Result<RecordingSettings, String> {
+    // FREE tier feature - no authentication required
+    let report = detect(&state).await?;
+
+    let recommended = report.recommended_settings(&*state.recording_settings.read().await);
+    recommended.save().map_err(|e| e.to_string())?;
+
+    apply_settings(&state, &recommended)
+        .await
+        .map_err(|e| e.to_string())?;
+    tracing::info!("Applied recommended settings from setup wizard");
+    Ok(recommended)
+}
+
+/// Mark the setup wizard as finished so it isn't shown again
+#[tauri::command]
+pub async fn complete_setup(state: State<'_, AppState>) -> Result<(), String> {
+    // FREE tier feature - no authentication required
+    let mut settings = state.recording_settings.write().await;
+    settings.setup_completed = true;
+    settings.save().map_err(|e| e.to_string())
+}
+
+/// Add sample games and clips so the library can be explored before playing
+///
+/// Fails if the demo library was already added. Thumbnails are generated in
+/// the background like for recorded clips.
+#[tauri::command]
+pub async fn seed_demo_library(state: State<'_, AppState>) -> Result<DemoLibrary, String> {
+    // FREE tier feature - no authentication required
+    let storage = state.storage.clone();
+    let clips_dir = state
+        .recording_manager
+        .read()
+        .await
+        .clips_dir()
+        .to_path_buf();
+
+    let library =
+        tokio::task::spawn_blocking(move || demo::seed_demo_library(&storage, &clips_dir))
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| e.to_string())?;
+
+    for clip in &library.clips {
+        let job = JobKind::GenerateThumbnail {
+            game_id: clip.game_id.clone(),
+            clip_path: PathBuf::from(&clip.file_path),
+        };
+        if let Err(e) = state.jobs.enqueue(job, JobPriority::Low).await {
+            tracing::warn!("Failed to queue demo thumbnail: {}", e);
+        }
+    }
+
+    Ok(library)
+}
+
+async fn detect(state: &AppState) -> Result<SetupReport, String> {
+    let (encoder, recordings_dir) = {
+        let manager = state.recording_manager.read().await;
+        (
+            manager.get_quality_info().encoder,
+            manager.output_dir().to_path_buf(),
+        )
+    };
+    let setup_completed = state.recording_settings.read().await.setup_completed;
+
+    tokio::task::spawn_blocking(move || {
+        SetupReport::detect(encoder, &recordings_dir, setup_completed)
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,319 @@
+//! Sample library for first-time users
+//!
+//! Seeds a couple of made-up games with events and placeholder clips (color
+//! bars rendered with FFmpeg) so the Library and Auto-edit screens can be
+//! explored before playing a game. Demo games are named `demo_*` and their
+//! clips tagged `demo`, so they're easy to find and delete later.
+
+use anyhow::{bail, Context, Result};
+use chrono::{Duration, Utc};
+use serde::Serialize;
+use std::path::Path;
+use std::process::Command;
+
+use crate::storage::batch::ClipRef;
+use crate::storage::models::{EventData, EventType, GameMetadata, GameResult, KDA};
+use crate::storage::{ClipMetadata, ClipMetadataV2, Storage};
+
+/// Game ids of the demo library start with this
+pub const DEMO_GAME_PREFIX: &str = "demo_";
+
+/// Length of each placeholder clip in seconds
+const CLIP_DURATION: f64 = 10.0;
+
+/// Seconds of each clip before its event
+const CLIP_PRE_ROLL: f64 = 7.0;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DemoLibrary {
+    pub game_ids: Vec<String>,
+    pub clips: Vec<ClipRef>,
+}
+
+struct DemoGame {
+    game_id: &'static str,
+    champion: &'static str,
+    game_mode: &'static str,
+    result: GameResult,
+    kda: KDA,
+    /// When the game was played, relative to now
+    days_ago: i64,
+    /// Event type and game time in seconds
+    events: Vec<(EventType, f64)>,
+}
+
+fn demo_games() -> Vec<DemoGame> {
+    vec![
+        DemoGame {
+            game_id: "demo_game_1",
+            champion: "Ahri",
+            game_mode: "CLASSIC",
+            result: GameResult::Win,
+            kda: KDA {
+                kills: 12,
+                deaths: 3,
+                assists: 8,
+            },
+            days_ago: 1,
+            events: vec![
+                (EventType::FirstBlood, 185.0),
+                (EventType::Multikill(2), 640.0),
+                (EventType::DragonKill, 910.0),
+                (EventType::Multikill(4), 1520.0),
+                (EventType::BaronKill, 1745.0),
+            ],
+        },
+        DemoGame {
+            game_id: "demo_game_2",
+            champion: "Jinx",
+            game_mode: "ARAM",
+            result: GameResult::Loss,
+            kda: KDA {
+                kills: 7,
+                deaths: 9,
+                assists: 21,
+            },
+            days_ago: 3,
+            events: vec![
+                (EventType::ChampionKill, 120.0),
+                (EventType::Multikill(3), 455.0),
+                (EventType::Death, 700.0),
+                (EventType::Ace, 980.0),
+            ],
+        },
+    ]
+}
+
+/// Add the demo games to `storage`, writing clips to `clips_dir`
+///
+/// Blocking (runs FFmpeg). Fails if a demo game already exists. If seeding
+/// fails partway, what was already added is removed again so it can be
+/// retried.
+pub fn seed_demo_library(storage: &Storage, clips_dir: &Path) -> Result<DemoLibrary> {
+    if storage
+        .list_games()?
+        .iter()
+        .any(|game_id| game_id.starts_with(DEMO_GAME_PREFIX))
+    {
+        bail!("The demo library has already been added");
+    }
+
+    std::fs::create_dir_all(clips_dir)?;
+    let source = clips_dir.join("demo_color_bars.mp4");
+    render_color_bars(&source)?;
+
+    let mut library = DemoLibrary {
+        game_ids: Vec::new(),
+        clips: Vec::new(),
+    };
+    let result = seed_games(storage, clips_dir, &source, &mut library);
+    let _ = std::fs::remove_file(&source);
+
+    if let Err(e) = result {
+        remove_partial_library(storage, &library);
+        return Err(e);
+    }
+
+    tracing::info!(
+        "Seeded demo library: {} games, {} clips",
+        library.game_ids.len(),
+        library.clips.len()
+    );
+    Ok(library)
+}
+
+/// Add the demo games to `library` as they're written, so a failed seed
+/// knows what to remove
+fn seed_games(
+    storage: &Storage,
+    clips_dir: &Path,
+    source: &Path,
+    library: &mut DemoLibrary,
+) -> Result<()> {
+    for game in demo_games() {
+        let game_id = game.game_id.to_string();
+        let last_event = game.events.last().map(|(_, time)| *time).unwrap_or(0.0);
+        let start_time = Utc::now() - Duration::days(game.days_ago);
+
+        storage.save_game_metadata(
+            &game_id,
+            &GameMetadata {
+                game_id: game_id.clone(),
+                champion: game.champion.to_string(),
+                game_mode: game.game_mode.to_string(),
+                start_time,
+                end_time: Some(start_time + Duration::seconds(last_event as i64 + 120)),
+                result: Some(game.result),
+                kda: Some(game.kda),
+                patch: None,
+            },
+        )?;
+        library.game_ids.push(game_id.clone());
+
+        let events: Vec<EventData> = game
+            .events
+            .iter()
+            .enumerate()
+            .map(|(i, (event_type, time))| EventData {
+                event_id: i as u64 + 1,
+                event_type: event_type.clone(),
+                timestamp: *time,
+                priority: event_type.default_priority(),
+                participants: vec![game.champion.to_string()],
+                details: None,
+            })
+            .collect();
+        storage.save_events(&game_id, &events)?;
+
+        for event in &events {
+            let file_path = clips_dir.join(format!(
+                "{}_{}_p{}.mp4",
+                game_id, event.event_id, event.priority
+            ));
+            std::fs::copy(source, &file_path)
+                .with_context(|| format!("Failed to write {:?}", file_path))?;
+
+            let file_path = file_path.to_string_lossy().to_string();
+            library.clips.push(ClipRef {
+                game_id: game_id.clone(),
+                file_path: file_path.clone(),
+            });
+
+            let mut clip = ClipMetadataV2::from(ClipMetadata {
+                file_path,
+                thumbnail_path: None,
+                event_type: event.event_type.clone(),
+                event_time: event.timestamp - CLIP_PRE_ROLL,
+                priority: event.priority,
+                duration: CLIP_DURATION,
+                created_at: start_time + Duration::seconds(event.timestamp as i64),
+            });
+            clip.game_id = game_id.clone();
+            clip.primary_event.event_id = event.event_id;
+            clip.primary_event.timestamp = event.timestamp;
+            clip.primary_event.clip_timestamp = CLIP_PRE_ROLL;
+            clip.event_window.pre_duration = CLIP_PRE_ROLL;
+            clip.event_window.post_duration = CLIP_DURATION - CLIP_PRE_ROLL;
+            clip.add_tag("demo".to_string());
+            storage.save_clip_metadata_v2(&game_id, &clip)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Remove the games and clip files of a demo library that failed to seed
+fn remove_partial_library(storage: &Storage, library: &DemoLibrary) {
+    for clip in &library.clips {
+        if let Err(e) = std::fs::remove_file(&clip.file_path) {
+            tracing::warn!("Failed to remove demo clip {}: {}", clip.file_path, e);
+        }
+    }
+    for game_id in &library.game_ids {
+        if let Err(e) = storage.delete_game(game_id) {
+            tracing::warn!("Failed to remove demo game {}: {}", game_id, e);
+        }
+    }
+}
+
+/// Render a 720p clip of SMPTE color bars with a test tone
+fn render_color_bars(output: &Path) -> Result<()> {
+    let duration = CLIP_DURATION.to_string();
+    let result = Command::new("ffmpeg")
+        .args(["-f", "lavfi", "-i"])
+        .arg(format!(
+            "smptehdbars=size=1280x720:rate=30:duration={}",
+            duration
+        ))
+        .args(["-f", "lavfi", "-i"])
+        .arg(format!("sine=frequency=1000:duration={}", duration))
+        .args([
+            "-c:v",
+            "libx264",
+            "-preset",
+            "ultrafast",
+            "-pix_fmt",
+            "yuv420p",
+            "-c:a",
+            "aac",
+            "-shortest",
+            "-y",
+        ])
+        .arg(output)
+        .output()
+        .context("FFmpeg is required to create the demo clips")?;
+
+    if !result.status.success() {
+        bail!(
+            "FFmpeg failed to render demo clip: {}",
+            String::from_utf8_lossy(&result.stderr)
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_demo_games_are_consistent() {
+        for game in demo_games() {
+            assert!(game.game_id.starts_with(DEMO_GAME_PREFIX));
+            assert!(!game.events.is_empty());
+            // Events are in game order and leave room for the pre-roll
+            assert!(game.events.windows(2).all(|w| w[0].1 < w[1].1));
+            assert!(game.events[0].1 >= CLIP_PRE_ROLL);
+        }
+    }
+
+    #[test]
+    fn test_refuses_to_seed_twice() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_demo_seed");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let storage = Storage::new(&temp_dir).unwrap();
+        storage
+            .save_game_metadata(
+                "demo_game_1",
+                &GameMetadata {
+                    game_id: "demo_game_1".to_string(),
+                    champion: "Ahri".to_string(),
+                    game_mode: "CLASSIC".to_string(),
+                    start_time: Utc::now(),
+                    end_time: None,
+                    result: None,
+                    kda: None,
+                    patch: None,
+                },
+            )
+            .unwrap();
+
+        let err = seed_demo_library(&storage, &temp_dir.join("clips")).unwrap_err();
+        assert!(err.to_string().contains("already"));
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_failed_seed_is_removed() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let storage = Storage::new(temp_dir.path()).unwrap();
+        storage.save_events("demo_game_1", &[]).unwrap();
+        let clip = temp_dir.path().join("demo_game_1_1_p3.mp4");
+        std::fs::write(&clip, b"clip").unwrap();
+
+        remove_partial_library(
+            &storage,
+            &DemoLibrary {
+                game_ids: vec!["demo_game_1".to_string()],
+                clips: vec![ClipRef {
+                    game_id: "demo_game_1".to_string(),
+                    file_path: clip.to_string_lossy().to_string(),
+                }],
+            },
+        );
+
+        assert!(!clip.exists());
+        assert!(storage.list_games().unwrap().is_empty());
+    }
+}
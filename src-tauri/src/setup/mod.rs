@@ -0,0 +1,288 @@
+pub mod commands;
+pub mod demo;
+
+/// First-run setup checks
+///
+/// Collects everything the onboarding wizard needs to show in one
+/// [`SetupReport`] (FFmpeg, hardware encoder, League install, audio devices,
+/// disk space, microphone permission) and derives recommended settings from
+/// it. Detection shells out to FFmpeg and reads system info, so it's blocking
+/// and should run on a blocking thread.
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+
+use crate::lcu::LcuClient;
+use crate::recording::audio::{self, AudioDevice, AudioDeviceType};
+use crate::settings::models::{
+    BitratePreset, EncoderPreference, FrameRate, RecordingSettings, Resolution,
+};
+
+/// Free space below which the wizard warns (replay buffer + a few games of clips)
+pub const MIN_FREE_DISK_MB: u64 = 10 * 1024;
+
+/// Machines at or below these get low-spec defaults
+const LOW_SPEC_CPU_CORES: usize = 4;
+const LOW_SPEC_MEMORY_MB: u64 = 8 * 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FfmpegStatus {
+    pub installed: bool,
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskSpace {
+    /// Directory the space was measured for (recordings folder)
+    pub path: String,
+    pub available_mb: u64,
+    pub total_mb: u64,
+    pub sufficient: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MicrophonePermission {
+    Granted,
+    Denied,
+    /// Couldn't be determined (non-Windows, or no consent entry yet)
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemSpecs {
+    pub cpu_cores: usize,
+    pub total_memory_mb: u64,
+}
+
+impl SystemSpecs {
+    pub fn is_low_spec(&self) -> bool {
+        self.cpu_cores <= LOW_SPEC_CPU_CORES || self.total_memory_mb <= LOW_SPEC_MEMORY_MB
+    }
+}
+
+/// Everything the setup wizard shows
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetupReport {
+    pub setup_completed: bool,
+    pub ffmpeg: FfmpegStatus,
+    /// Encoder picked by the recorder ("NVENC", "QSV", "AMF" or "Software")
+    pub encoder: String,
+    pub hardware_encoder_available: bool,
+    pub league_install_path: Option<String>,
+    pub audio_devices: Vec<AudioDevice>,
+    pub disk: Option<DiskSpace>,
+    pub microphone_permission: MicrophonePermission,
+    pub system: SystemSpecs,
+}
+
+impl SetupReport {
+    /// Run all checks (blocking)
+    pub fn detect(encoder: String, recordings_dir: &Path, setup_completed: bool) -> Self {
+        let ffmpeg = detect_ffmpeg();
+
+        // Device listing needs FFmpeg
+        let audio_devices = if ffmpeg.installed {
+            audio::list_audio_devices().unwrap_or_else(|e| {
+                tracing::warn!("Failed to list audio devices: {}", e);
+                Vec::new()
+            })
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            setup_completed,
+            ffmpeg,
+            hardware_encoder_available: encoder != "Software",
+            encoder,
+            league_install_path: LcuClient::detect_install_dir()
+                .map(|dir| dir.to_string_lossy().to_string()),
+            audio_devices,
+            disk: disk_space(recordings_dir),
+            microphone_permission: microphone_permission(),
+            system: system_specs(),
+        }
+    }
+
+    /// Settings recommended for this machine, based on `current`
+    ///
+    /// Only the video, audio and performance sections are touched.
+    pub fn recommended_settings(&self, current: &RecordingSettings) -> RecordingSettings {
+        let mut settings = current.clone();
+
+        settings.video.encoder = match self.encoder.as_str() {
+            "NVENC" => EncoderPreference::Nvenc,
+            "QSV" => EncoderPreference::Qsv,
+            "AMF" => EncoderPreference::Amf,
+            _ => EncoderPreference::Software,
+        };
+
+        let low_spec = self.system.is_low_spec() || !self.hardware_encoder_available;
+        settings.performance.low_spec_mode = low_spec;
+        if low_spec {
+            settings.video.resolution = Resolution::R1280x720;
+            settings.video.frame_rate = FrameRate::Fps30;
+            settings.video.bitrate_preset = BitratePreset::Low;
+        } else {
+            settings.video.resolution = Resolution::R1920x1080;
+            settings.video.frame_rate = FrameRate::Fps60;
+            settings.video.bitrate_preset = BitratePreset::Medium;
+        }
+
+        let microphone = self
+            .audio_devices
+            .iter()
+            .find(|device| device.device_type == AudioDeviceType::Microphone);
+        settings.audio.record_microphone =
+            microphone.is_some() && self.microphone_permission != MicrophonePermission::Denied;
+        settings.audio.microphone_device = microphone.map(|device| device.name.clone());
+
+        settings
+    }
+}
+
+fn detect_ffmpeg() -> FfmpegStatus {
+    match Command::new("ffmpeg").arg("-version").output() {
+        Ok(output) if output.status.success() => FfmpegStatus {
+            installed: true,
+            version: parse_ffmpeg_version(&String::from_utf8_lossy(&output.stdout)),
+        },
+        _ => FfmpegStatus {
+            installed: false,
+            version: None,
+        },
+    }
+}
+
+/// Version from the first line of `ffmpeg -version`
+/// ("ffmpeg version 6.1.1-full_build-www.gyan.dev Copyright ...")
+pub fn parse_ffmpeg_version(output: &str) -> Option<String> {
+    output
+        .lines()
+        .next()?
+        .strip_prefix("ffmpeg version ")?
+        .split_whitespace()
+        .next()
+        .map(str::to_string)
+}
+
+/// Space on the disk holding `dir` (the deepest matching mount point)
+pub fn disk_space(dir: &Path) -> Option<DiskSpace> {
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+
+    let disk = disks
+        .iter()
+        .filter(|disk| dir.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())?;
+
+    let available_mb = disk.available_space() / 1024 / 1024;
+    Some(DiskSpace {
+        path: dir.to_string_lossy().to_string(),
+        available_mb,
+        total_mb: disk.total_space() / 1024 / 1024,
+        sufficient: available_mb >= MIN_FREE_DISK_MB,
+    })
+}
+
+fn system_specs() -> SystemSpecs {
+    let mut sys = sysinfo::System::new();
+    sys.refresh_memory();
+
+    SystemSpecs {
+        cpu_cores: num_cpus::get(),
+        total_memory_mb: sys.total_memory() / 1024 / 1024,
+    }
+}
+
+/// Windows privacy setting for desktop apps' microphone access
+#[cfg(target_os = "windows")]
+fn microphone_permission() -> MicrophonePermission {
+    use winreg::enums::HKEY_CURRENT_USER;
+    use winreg::RegKey;
+
+    const CONSENT_KEY: &str =
+        "Software\\Microsoft\\Windows\\CurrentVersion\\CapabilityAccessManager\\ConsentStore\\microphone";
+
+    let value: Option<String> = RegKey::predef(HKEY_CURRENT_USER)
+        .open_subkey(CONSENT_KEY)
+        .and_then(|key| key.get_value("Value"))
+        .ok();
+
+    match value.as_deref() {
+        Some("Allow") => MicrophonePermission::Granted,
+        Some("Deny") => MicrophonePermission::Denied,
+        _ => MicrophonePermission::Unknown,
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn microphone_permission() -> MicrophonePermission {
+    MicrophonePermission::Unknown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(encoder: &str, cpu_cores: usize, devices: Vec<AudioDevice>) -> SetupReport {
+        SetupReport {
+            setup_completed: false,
+            ffmpeg: FfmpegStatus {
+                installed: true,
+                version: Some("6.1.1".to_string()),
+            },
+            hardware_encoder_available: encoder != "Software",
+            encoder: encoder.to_string(),
+            league_install_path: None,
+            audio_devices: devices,
+            disk: None,
+            microphone_permission: MicrophonePermission::Granted,
+            system: SystemSpecs {
+                cpu_cores,
+                total_memory_mb: 16 * 1024,
+            },
+        }
+    }
+
+    #[test]
+    fn test_parse_ffmpeg_version() {
+        assert_eq!(
+            parse_ffmpeg_version(
+                "ffmpeg version 6.1.1-full_build-www.gyan.dev Copyright (c) 2000-2023\nbuilt with gcc"
+            ),
+            Some("6.1.1-full_build-www.gyan.dev".to_string())
+        );
+        assert_eq!(parse_ffmpeg_version("not ffmpeg"), None);
+    }
+
+    #[test]
+    fn test_recommended_settings_hardware() {
+        let mic = AudioDevice {
+            name: "Microphone (USB Audio)".to_string(),
+            device_type: AudioDeviceType::Microphone,
+        };
+        let settings =
+            report("NVENC", 8, vec![mic]).recommended_settings(&RecordingSettings::default());
+
+        assert!(matches!(settings.video.encoder, EncoderPreference::Nvenc));
+        assert!(matches!(settings.video.frame_rate, FrameRate::Fps60));
+        assert!(!settings.performance.low_spec_mode);
+        assert!(settings.audio.record_microphone);
+        assert_eq!(
+            settings.audio.microphone_device.as_deref(),
+            Some("Microphone (USB Audio)")
+        );
+    }
+
+    #[test]
+    fn test_recommended_settings_low_spec() {
+        let settings =
+            report("Software", 4, Vec::new()).recommended_settings(&RecordingSettings::default());
+
+        assert!(settings.performance.low_spec_mode);
+        assert!(matches!(settings.video.resolution, Resolution::R1280x720));
+        assert!(!settings.audio.record_microphone);
+    }
+}
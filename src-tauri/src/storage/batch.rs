@@ -0,0 +1,265 @@
+//! Operations on many clips at once
+//!
+//! Each batch is all-or-nothing: if one clip fails, the clips already
+//! handled are put back the way they were and the error is returned.
+//! Progress is reported after each clip.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::models_v2::ClipMetadataV2;
+use super::{Result, Storage, StorageError};
+
+/// A clip picked in the library
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClipRef {
+    pub game_id: String,
+    pub file_path: String,
+}
+
+/// Progress of a batch operation, reported after each clip
+#[derive(Debug, Clone, Serialize)]
+pub struct BatchProgress {
+    /// "delete", "tag" or "export"
+    pub operation: String,
+    pub clips_done: usize,
+    pub clips_total: usize,
+    pub current_clip: String,
+}
+
+impl BatchProgress {
+    fn new(operation: &str, clips_total: usize) -> Self {
+        Self {
+            operation: operation.to_string(),
+            clips_done: 0,
+            clips_total,
+            current_clip: String::new(),
+        }
+    }
+
+    fn advance(&mut self, clip: &ClipRef, on_progress: &mut impl FnMut(&BatchProgress)) {
+        self.clips_done += 1;
+        self.current_clip = clip.file_path.clone();
+        on_progress(self);
+    }
+}
+
+impl Storage {
+    /// Move clips to the recycle bin
+    ///
+    /// Returns the trash entry ids. On failure the clips already trashed
+    /// are restored.
+    pub fn batch_delete_clips(
+        &self,
+        clips: &[ClipRef],
+        mut on_progress: impl FnMut(&BatchProgress),
+    ) -> Result<Vec<String>> {
+        let mut progress = BatchProgress::new("delete", clips.len());
+        let mut trashed = Vec::new();
+
+        for clip in clips {
+            match self.trash_clip(&clip.game_id, &clip.file_path) {
+                Ok(entry) => trashed.push(entry.id),
+                Err(e) => {
+                    tracing::warn!("Batch delete failed at {}: {}", clip.file_path, e);
+                    for id in trashed.iter().rev() {
+                        if let Err(e) = self.restore_clip(id) {
+                            tracing::error!("Failed to restore trashed clip {}: {}", id, e);
+                        }
+                    }
+                    return Err(e);
+                }
+            }
+            progress.advance(clip, &mut on_progress);
+        }
+
+        tracing::info!("Batch deleted {} clips", trashed.len());
+        Ok(trashed)
+    }
+
+    /// Add and remove tags on clips
+    ///
+    /// Every clip's metadata is loaded before anything is written, so a
+    /// missing clip fails the batch without changes.
+    pub fn batch_tag_clips(
+        &self,
+        clips: &[ClipRef],
+        add: &[String],
+        remove: &[String],
+        mut on_progress: impl FnMut(&BatchProgress),
+    ) -> Result<()> {
+        let originals = clips
+            .iter()
+            .map(|clip| self.load_clip_metadata_v2(&clip.file_path))
+            .collect::<Result<Vec<ClipMetadataV2>>>()?;
+
+        let mut progress = BatchProgress::new("tag", clips.len());
+        for (i, (clip, original)) in clips.iter().zip(&originals).enumerate() {
+            let mut tagged = original.clone();
+            tagged.tags.retain(|tag| !remove.contains(tag));
+            for tag in add {
+                tagged.add_tag(tag.clone());
+            }
+
+            if let Err(e) = self.write_clip_v2(&tagged) {
+                tracing::warn!("Batch tag failed at {}: {}", clip.file_path, e);
+                for original in &originals[..i] {
+                    if let Err(e) = self.write_clip_v2(original) {
+                        tracing::error!("Failed to restore tags of {}: {}", original.file_path, e);
+                    }
+                }
+                return Err(e);
+            }
+            progress.advance(clip, &mut on_progress);
+        }
+
+        tracing::info!("Batch tagged {} clips", clips.len());
+        Ok(())
+    }
+
+    /// Copy clip videos into `dest_dir`
+    ///
+    /// Returns the exported paths. On failure the copies made so far are
+    /// removed.
+    pub fn batch_export_clips(
+        &self,
+        clips: &[ClipRef],
+        dest_dir: &Path,
+        mut on_progress: impl FnMut(&BatchProgress),
+    ) -> Result<Vec<PathBuf>> {
+        fs::create_dir_all(dest_dir)?;
+
+        let mut progress = BatchProgress::new("export", clips.len());
+        let mut exported = Vec::new();
+        for clip in clips {
+            match export_clip(&clip.file_path, dest_dir) {
+                Ok(path) => exported.push(path),
+                Err(e) => {
+                    tracing::warn!("Batch export failed at {}: {}", clip.file_path, e);
+                    for path in &exported {
+                        let _ = fs::remove_file(path);
+                    }
+                    return Err(e);
+                }
+            }
+            progress.advance(clip, &mut on_progress);
+        }
+
+        tracing::info!("Exported {} clips to {:?}", exported.len(), dest_dir);
+        Ok(exported)
+    }
+}
+
+/// Copy a clip into `dest_dir` without overwriting anything there
+fn export_clip(clip_path: &str, dest_dir: &Path) -> Result<PathBuf> {
+    let source = Path::new(clip_path);
+    let name = source.file_name().ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("Not a clip file: {}", clip_path),
+        )
+    })?;
+
+    let target = dest_dir.join(name);
+    if target.exists() {
+        return Err(StorageError::AlreadyExists(
+            target.to_string_lossy().to_string(),
+        ));
+    }
+    fs::copy(source, &target)?;
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::test_support::save_clip;
+    use chrono::Duration;
+
+    #[test]
+    fn test_batch_delete() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_batch_delete");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let storage = Storage::new(&temp_dir).unwrap();
+
+        let clips = vec![
+            save_clip(&storage, "game", "a.mp4", b"video", Duration::zero()),
+            save_clip(&storage, "game", "b.mp4", b"video", Duration::zero()),
+        ];
+
+        let mut reported = Vec::new();
+        let ids = storage
+            .batch_delete_clips(&clips, |p| reported.push(p.clips_done))
+            .unwrap();
+        assert_eq!(ids.len(), 2);
+        assert_eq!(reported, vec![1, 2]);
+        assert!(clips.iter().all(|c| !Path::new(&c.file_path).exists()));
+        assert!(storage.load_clip_metadata("game").unwrap().is_empty());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_batch_tag_fails_without_changes() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_batch_tag");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let storage = Storage::new(&temp_dir).unwrap();
+
+        let a = save_clip(&storage, "game", "a.mp4", b"video", Duration::zero());
+        let b = save_clip(&storage, "game", "b.mp4", b"video", Duration::zero());
+        let missing = ClipRef {
+            game_id: "game".to_string(),
+            file_path: temp_dir.join("missing.mp4").to_string_lossy().to_string(),
+        };
+        let add = vec!["ranked".to_string()];
+
+        let result = storage.batch_tag_clips(&[a.clone(), missing], &add, &[], |_| {});
+        assert!(result.is_err());
+        assert!(storage
+            .load_clip_metadata_v2(&a.file_path)
+            .unwrap()
+            .tags
+            .is_empty());
+
+        storage
+            .batch_tag_clips(&[a.clone(), b.clone()], &add, &[], |_| {})
+            .unwrap();
+        storage
+            .batch_tag_clips(&[b.clone()], &[], &add, |_| {})
+            .unwrap();
+        assert_eq!(
+            storage.load_clip_metadata_v2(&a.file_path).unwrap().tags,
+            add
+        );
+        assert!(storage
+            .load_clip_metadata_v2(&b.file_path)
+            .unwrap()
+            .tags
+            .is_empty());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_batch_export_rolls_back() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_batch_export");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let storage = Storage::new(&temp_dir).unwrap();
+        let dest_dir = temp_dir.join("export");
+
+        let a = save_clip(&storage, "game", "a.mp4", b"video", Duration::zero());
+        let b = save_clip(&storage, "game", "b.mp4", b"video", Duration::zero());
+        fs::remove_file(&b.file_path).unwrap();
+
+        let result = storage.batch_export_clips(&[a.clone(), b], &dest_dir, |_| {});
+        assert!(result.is_err());
+        assert!(!dest_dir.join("a.mp4").exists());
+
+        let exported = storage.batch_export_clips(&[a], &dest_dir, |_| {}).unwrap();
+        assert_eq!(exported, vec![dest_dir.join("a.mp4")]);
+        assert!(exported[0].exists());
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+}
@@ -1,15 +1,28 @@
 use crate::auth::middleware::require_auth;
 use crate::auth::SubscriptionTier;
-use crate::storage::{AutoEditUsage, ClipMetadata, EventData, GameMetadata, StorageStats};
+use crate::events::AppEvent;
+use crate::quota::MeteredFeature;
+use crate::recording::RecordingStatus;
+use crate::settings::models::StorageLocationSettings;
+use crate::storage::batch::ClipRef;
+use crate::storage::duplicates::DuplicateScan;
+use crate::storage::integrity::{LibraryReport, RepairAction};
+use crate::storage::relocation::{MigrationSummary, StorageDirs};
+use crate::storage::timeline::GameTimeline;
+use crate::storage::trash::TrashEntry;
+use crate::storage::usage::StorageBreakdown;
+use crate::storage::{ClipMetadata, EventData, GameMetadata, StorageStats};
+use crate::utils::error::AppError;
+use crate::utils::security;
 use crate::AppState;
 use serde::{Deserialize, Serialize};
 use tauri::State;
 
 /// List all games (sorted by most recent)
 #[tauri::command]
-pub async fn list_games(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+pub async fn list_games(state: State<'_, AppState>) -> Result<Vec<String>, AppError> {
     // FREE tier feature - no authentication required
-    state.storage.list_games().map_err(|e| e.to_string())
+    state.storage.list_games().map_err(AppError::from)
 }
 
 /// Get metadata for a specific game
@@ -17,26 +30,32 @@ pub async fn list_games(state: State<'_, AppState>) -> Result<Vec<String>, Strin
 pub async fn get_game_metadata(
     state: State<'_, AppState>,
     game_id: String,
-) -> Result<GameMetadata, String> {
+) -> Result<GameMetadata, AppError> {
     // FREE tier feature - no authentication required
     state
         .storage
         .load_game_metadata(&game_id)
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 /// Save game metadata
+///
+/// The current patch is recorded when `metadata` doesn't name one.
 #[tauri::command]
 pub async fn save_game_metadata(
     state: State<'_, AppState>,
     game_id: String,
-    metadata: GameMetadata,
-) -> Result<(), String> {
+    mut metadata: GameMetadata,
+) -> Result<(), AppError> {
     // FREE tier feature - no authentication required
+    if metadata.patch.is_none() {
+        metadata.patch = crate::lcu::patch::current_patch().await;
+    }
+
     state
         .storage
         .save_game_metadata(&game_id, &metadata)
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 /// Load events for a game
@@ -44,12 +63,25 @@ pub async fn save_game_metadata(
 pub async fn get_game_events(
     state: State<'_, AppState>,
     game_id: String,
-) -> Result<Vec<EventData>, String> {
+) -> Result<Vec<EventData>, AppError> {
     // FREE tier feature - no authentication required
+    state.storage.load_events(&game_id).map_err(AppError::from)
+}
+
+/// Events and clips of a game in one chronological timeline, with the
+/// stretches no clip covers and the ones covered twice
+#[tauri::command]
+pub async fn get_game_timeline(
+    state: State<'_, AppState>,
+    game_id: String,
+) -> Result<GameTimeline, AppError> {
+    // FREE tier feature - no authentication required
+    let game_id = security::validate_game_id(&game_id)?;
+
     state
         .storage
-        .load_events(&game_id)
-        .map_err(|e| e.to_string())
+        .game_timeline(&game_id)
+        .map_err(AppError::from)
 }
 
 /// Save events for a game
@@ -58,12 +90,12 @@ pub async fn save_game_events(
     state: State<'_, AppState>,
     game_id: String,
     events: Vec<EventData>,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     // FREE tier feature - no authentication required
     state
         .storage
         .save_events(&game_id, &events)
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 /// Save clip metadata
@@ -72,29 +104,26 @@ pub async fn save_clip_metadata(
     state: State<'_, AppState>,
     game_id: String,
     clip: ClipMetadata,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     // FREE tier feature - no authentication required
     state
         .storage
         .save_clip_metadata(&game_id, &clip)
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 /// Delete a game and all its data
 #[tauri::command]
-pub async fn delete_game(state: State<'_, AppState>, game_id: String) -> Result<(), String> {
+pub async fn delete_game(state: State<'_, AppState>, game_id: String) -> Result<(), AppError> {
     // FREE tier feature - no authentication required
-    state
-        .storage
-        .delete_game(&game_id)
-        .map_err(|e| e.to_string())
+    state.storage.delete_game(&game_id).map_err(AppError::from)
 }
 
 /// Get storage statistics
 #[tauri::command]
-pub async fn get_storage_stats(state: State<'_, AppState>) -> Result<StorageStats, String> {
+pub async fn get_storage_stats(state: State<'_, AppState>) -> Result<StorageStats, AppError> {
     // FREE tier feature - no authentication required
-    state.storage.get_stats().map_err(|e| e.to_string())
+    state.storage.get_stats().map_err(AppError::from)
 }
 
 /// List all clips for a specific game
@@ -102,12 +131,12 @@ pub async fn get_storage_stats(state: State<'_, AppState>) -> Result<StorageStat
 pub async fn list_clips(
     state: State<'_, AppState>,
     game_id: String,
-) -> Result<Vec<ClipMetadata>, String> {
+) -> Result<Vec<ClipMetadata>, AppError> {
     // FREE tier feature - no authentication required
     state
         .storage
         .load_clip_metadata(&game_id)
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 // ============================================================================
@@ -118,34 +147,25 @@ pub async fn list_clips(
 ///
 /// Returns current month's usage and remaining quota based on user tier.
 #[tauri::command]
-pub async fn get_auto_edit_quota(state: State<'_, AppState>) -> Result<AutoEditQuotaInfo, String> {
+pub async fn get_auto_edit_quota(
+    state: State<'_, AppState>,
+) -> Result<AutoEditQuotaInfo, AppError> {
     // Require authentication to check tier
-    require_auth(&state.auth).map_err(|e| e.to_string())?;
+    require_auth(&state.auth)?;
 
-    let tier = state.auth.get_tier().map_err(|e| e.to_string())?;
+    let tier = state.auth.get_tier()?;
     let is_pro = matches!(tier, SubscriptionTier::Pro);
 
-    // Load current usage
-    let usage = state
-        .storage
-        .load_auto_edit_usage()
-        .map_err(|e| e.to_string())?;
-
-    // Calculate remaining quota
-    let limit = if is_pro { u32::MAX } else { 5 };
-    let remaining = if is_pro {
-        u32::MAX
-    } else {
-        limit.saturating_sub(usage.usage_count)
-    };
+    let status = state.quota.status(MeteredFeature::AutoEdit)?;
 
+    // Unlimited quotas are reported as u32::MAX
     Ok(AutoEditQuotaInfo {
-        tier: format!("{:?}", tier),
+        tier: status.tier,
         is_pro,
-        usage: usage.usage_count,
-        limit,
-        remaining,
-        month: usage.month,
+        usage: status.used,
+        limit: status.limit.unwrap_or(u32::MAX),
+        remaining: status.remaining.unwrap_or(u32::MAX),
+        month: status.period,
     })
 }
 
@@ -161,7 +181,7 @@ pub struct AutoEditQuotaInfo {
     /// Number of auto-edits used this month
     pub usage: u32,
 
-    /// Monthly limit (5 for FREE, u32::MAX for PRO)
+    /// Monthly limit (u32::MAX when unlimited)
     pub limit: u32,
 
     /// Remaining auto-edits this month
@@ -179,11 +199,11 @@ pub struct AutoEditQuotaInfo {
 #[tauri::command]
 pub async fn get_auto_edit_results(
     state: State<'_, AppState>,
-) -> Result<Vec<crate::storage::AutoEditResultMetadata>, String> {
+) -> Result<Vec<crate::storage::AutoEditResultMetadata>, AppError> {
     state
         .storage
         .load_auto_edit_results()
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 /// Get a specific auto-edit result by ID
@@ -191,11 +211,11 @@ pub async fn get_auto_edit_results(
 pub async fn get_auto_edit_result(
     state: State<'_, AppState>,
     result_id: String,
-) -> Result<crate::storage::AutoEditResultMetadata, String> {
+) -> Result<crate::storage::AutoEditResultMetadata, AppError> {
     state
         .storage
         .load_auto_edit_result(&result_id)
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 /// Delete an auto-edit result
@@ -206,14 +226,14 @@ pub async fn delete_auto_edit_result(
     state: State<'_, AppState>,
     result_id: String,
     delete_file: bool,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     // Require authentication
-    require_auth(&state.auth).map_err(|e| e.to_string())?;
+    require_auth(&state.auth)?;
 
     state
         .storage
         .delete_auto_edit_result(&result_id, delete_file)
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 /// Update YouTube upload status for an auto-edit result
@@ -222,19 +242,268 @@ pub async fn update_auto_edit_youtube_status(
     state: State<'_, AppState>,
     result_id: String,
     status: crate::storage::YouTubeUploadStatus,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     // Require authentication
-    require_auth(&state.auth).map_err(|e| e.to_string())?;
+    require_auth(&state.auth)?;
 
     state
         .storage
         .update_auto_edit_youtube_status(&result_id, status)
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 /// Get dashboard statistics (total games, clips, storage used)
 #[tauri::command]
-pub async fn get_dashboard_stats(state: State<'_, AppState>) -> Result<StorageStats, String> {
+pub async fn get_dashboard_stats(state: State<'_, AppState>) -> Result<StorageStats, AppError> {
+    // FREE tier feature - no authentication required
+    state.storage.get_stats().map_err(AppError::from)
+}
+
+/// Disk usage by game, category (clips, auto-edits, temp, trash) and age
+#[tauri::command]
+pub async fn get_storage_breakdown(
+    state: State<'_, AppState>,
+) -> Result<StorageBreakdown, AppError> {
+    // FREE tier feature - no authentication required
+    let recordings_dir = state
+        .recording_settings
+        .read()
+        .await
+        .storage
+        .recordings_dir(state.storage.base_path());
+    let temp_dirs = [
+        recordings_dir.join("temp_segments"),
+        recordings_dir.join("recovery"),
+        std::env::temp_dir().join("lolshorts_auto_edit"),
+    ];
+
+    let storage = state.storage.clone();
+    tokio::task::spawn_blocking(move || storage.storage_breakdown(&temp_dirs))
+        .await?
+        .map_err(AppError::from)
+}
+
+/// Find clips whose videos are identical, with the space deleting them frees
+#[tauri::command]
+pub async fn find_duplicate_clips(state: State<'_, AppState>) -> Result<DuplicateScan, AppError> {
     // FREE tier feature - no authentication required
-    state.storage.get_stats().map_err(|e| e.to_string())
+    let storage = state.storage.clone();
+    tokio::task::spawn_blocking(move || storage.find_duplicate_clips())
+        .await?
+        .map_err(AppError::from)
+}
+
+/// Check the library for missing videos, broken metadata and orphaned files
+#[tauri::command]
+pub async fn verify_library(state: State<'_, AppState>) -> Result<LibraryReport, AppError> {
+    // FREE tier feature - no authentication required
+    let recording_dirs = {
+        let recording_manager = state.recording_manager.read().await;
+        vec![
+            recording_manager.output_dir().to_path_buf(),
+            recording_manager.clips_dir().to_path_buf(),
+        ]
+    };
+
+    state
+        .storage
+        .verify_library(&recording_dirs)
+        .map_err(AppError::from)
+}
+
+/// Apply repairs returned by `verify_library`
+///
+/// Also takes `DeleteFile` for an orphaned video the user confirmed deleting.
+#[tauri::command]
+pub async fn repair_library(
+    state: State<'_, AppState>,
+    actions: Vec<RepairAction>,
+) -> Result<(), AppError> {
+    // FREE tier feature - no authentication required
+    for action in &actions {
+        state.storage.apply_repair(action)?;
+    }
+
+    Ok(())
+}
+
+/// List clips in the recycle bin
+#[tauri::command]
+pub async fn list_trash(state: State<'_, AppState>) -> Result<Vec<TrashEntry>, AppError> {
+    // FREE tier feature - no authentication required
+    state.storage.list_trash().map_err(AppError::from)
+}
+
+/// Restore a clip from the recycle bin
+#[tauri::command]
+pub async fn restore_clip(
+    state: State<'_, AppState>,
+    entry_id: String,
+) -> Result<TrashEntry, AppError> {
+    // FREE tier feature - no authentication required
+    state
+        .storage
+        .restore_clip(&entry_id)
+        .map_err(AppError::from)
+}
+
+/// Permanently delete everything in the recycle bin
+///
+/// Returns the number of bytes freed.
+#[tauri::command]
+pub async fn empty_trash(state: State<'_, AppState>) -> Result<u64, AppError> {
+    // FREE tier feature - no authentication required
+    state.storage.empty_trash().map_err(AppError::from)
+}
+
+/// Move several clips to the recycle bin at once
+///
+/// Either every clip is trashed or none are. Progress is sent as
+/// `clips:batch_progress` events. Returns the trash entry ids.
+#[tauri::command]
+pub async fn batch_delete_clips(
+    state: State<'_, AppState>,
+    clips: Vec<ClipRef>,
+) -> Result<Vec<String>, AppError> {
+    // Require authentication
+    require_auth(&state.auth)?;
+    let clips = validate_clip_refs(clips)?;
+
+    let storage = state.storage.clone();
+    let events = state.events.clone();
+    tokio::task::spawn_blocking(move || {
+        storage.batch_delete_clips(&clips, |progress| {
+            events.emit(AppEvent::ClipsBatchProgress(progress.clone()))
+        })
+    })
+    .await?
+    .map_err(AppError::from)
+}
+
+/// Add and remove tags on several clips at once
+///
+/// Either every clip is tagged or none are. Progress is sent as
+/// `clips:batch_progress` events.
+#[tauri::command]
+pub async fn batch_tag_clips(
+    state: State<'_, AppState>,
+    clips: Vec<ClipRef>,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<(), AppError> {
+    // Require authentication
+    require_auth(&state.auth)?;
+    let clips = validate_clip_refs(clips)?;
+    for tag in add.iter().chain(&remove) {
+        security::validate_id(tag, 100)?;
+    }
+
+    let storage = state.storage.clone();
+    let events = state.events.clone();
+    tokio::task::spawn_blocking(move || {
+        storage.batch_tag_clips(&clips, &add, &remove, |progress| {
+            events.emit(AppEvent::ClipsBatchProgress(progress.clone()))
+        })
+    })
+    .await?
+    .map_err(AppError::from)
+}
+
+/// Copy several clips into a new folder under the export directory
+///
+/// Either every clip is exported or none are. Progress is sent as
+/// `clips:batch_progress` events. Returns the exported file paths.
+#[tauri::command]
+pub async fn batch_export_clips(
+    state: State<'_, AppState>,
+    clips: Vec<ClipRef>,
+) -> Result<Vec<String>, AppError> {
+    // Require authentication
+    require_auth(&state.auth)?;
+    let clips = validate_clip_refs(clips)?;
+
+    let dest_dir = crate::video::export::export_dir(state.storage.base_path()).join(format!(
+        "clips_{}",
+        chrono::Local::now().format("%Y%m%d_%H%M%S")
+    ));
+
+    let storage = state.storage.clone();
+    let events = state.events.clone();
+    let exported = tokio::task::spawn_blocking(move || {
+        storage.batch_export_clips(&clips, &dest_dir, |progress| {
+            events.emit(AppEvent::ClipsBatchProgress(progress.clone()))
+        })
+    })
+    .await??;
+
+    Ok(exported
+        .iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect())
+}
+
+fn validate_clip_refs(clips: Vec<ClipRef>) -> Result<Vec<ClipRef>, AppError> {
+    for clip in &clips {
+        security::validate_video_input_path(&clip.file_path)?;
+        security::validate_game_id(&clip.game_id)?;
+    }
+    Ok(clips)
+}
+
+/// Move recordings to a new root folder (e.g. another drive)
+///
+/// Clips and auto-edit videos are moved and their metadata rewritten, with
+/// progress sent as `storage:migration_progress` events. Afterwards the new
+/// folder is saved as the recordings root and the replay buffer works there;
+/// clip and auto-edit folders the user picked stay as they are.
+#[tauri::command]
+pub async fn migrate_storage(
+    state: State<'_, AppState>,
+    new_path: String,
+) -> Result<MigrationSummary, AppError> {
+    // FREE tier feature - no authentication required
+    let new_root = security::validate_path(&new_path, None, false)?;
+
+    if state.recorder.get_state().await != RecordingStatus::Idle {
+        return Err(AppError::Recording(
+            "Stop the replay buffer before moving recordings".to_string(),
+        ));
+    }
+
+    let data_dir = state.storage.base_path().to_path_buf();
+    let mut settings = state.recording_settings.read().await.clone();
+    let from = storage_dirs(&settings.storage, &data_dir);
+    settings.storage.recordings_root = Some(new_root.to_string_lossy().to_string());
+    let to = storage_dirs(&settings.storage, &data_dir);
+
+    std::fs::create_dir_all(&to.recordings)?;
+
+    let storage = state.storage.clone();
+    let events = state.events.clone();
+    let migration_to = to.clone();
+    let summary = tokio::task::spawn_blocking(move || {
+        storage.migrate_storage(&from, &migration_to, |progress| {
+            events.emit(AppEvent::StorageMigrationProgress(progress.clone()))
+        })
+    })
+    .await??;
+
+    settings.save()?;
+    {
+        let mut recording_manager = state.recording_manager.write().await;
+        recording_manager.set_output_dir(to.recordings).await?;
+        recording_manager.set_clips_dir(to.clips);
+    }
+    state.auto_composer.set_output_dir(to.auto_edits).await;
+    *state.recording_settings.write().await = settings;
+
+    Ok(summary)
+}
+
+fn storage_dirs(locations: &StorageLocationSettings, data_dir: &std::path::Path) -> StorageDirs {
+    StorageDirs {
+        recordings: locations.recordings_dir(data_dir),
+        clips: locations.clips_dir(data_dir),
+        auto_edits: locations.auto_edits_dir(data_dir),
+    }
 }
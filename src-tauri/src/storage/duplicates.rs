@@ -0,0 +1,198 @@
+//! Finding clips saved more than once
+//!
+//! Two videos count as duplicates when they have the same size and the same
+//! first and last megabyte. Reading only the ends keeps a scan of a large
+//! library fast, and re-encoded copies differ in size anyway. Only files
+//! sharing a size with another file are read at all.
+
+use serde::Serialize;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::hash::Hasher;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use super::batch::ClipRef;
+use super::models::ClipMetadata;
+use super::{Result, Storage};
+
+/// Bytes hashed at each end of a file
+const SAMPLE_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DuplicateScan {
+    pub groups: Vec<DuplicateGroup>,
+    /// Space freed by deleting every duplicate
+    pub reclaimable_bytes: u64,
+}
+
+/// Copies of the same video
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateGroup {
+    pub size_bytes: u64,
+    /// The earliest saved copy
+    pub keep: ClipRef,
+    /// The other copies, which can go to `batch_delete_clips`
+    pub duplicates: Vec<ClipRef>,
+}
+
+impl Storage {
+    /// Group clips across all games whose videos are identical
+    ///
+    /// A file indexed more than once is only counted the first time, so it
+    /// isn't reported as a copy of itself.
+    pub fn find_duplicate_clips(&self) -> Result<DuplicateScan> {
+        let mut by_size: HashMap<u64, Vec<(String, ClipMetadata)>> = HashMap::new();
+        let mut seen = HashSet::new();
+        for game_id in self.list_games()? {
+            for clip in self.load_clip_metadata(&game_id).unwrap_or_default() {
+                let Ok(metadata) = fs::metadata(&clip.file_path) else {
+                    continue;
+                };
+                let path = fs::canonicalize(&clip.file_path)
+                    .unwrap_or_else(|_| PathBuf::from(&clip.file_path));
+                if !seen.insert(path) {
+                    continue;
+                }
+                by_size
+                    .entry(metadata.len())
+                    .or_default()
+                    .push((game_id.clone(), clip));
+            }
+        }
+
+        let mut scan = DuplicateScan::default();
+        for (size_bytes, clips) in by_size {
+            if clips.len() < 2 {
+                continue;
+            }
+
+            let mut by_hash: HashMap<u64, Vec<(String, ClipMetadata)>> = HashMap::new();
+            for (game_id, clip) in clips {
+                match sample_hash(Path::new(&clip.file_path), size_bytes) {
+                    Ok(hash) => by_hash.entry(hash).or_default().push((game_id, clip)),
+                    Err(e) => tracing::warn!("Failed to hash {}: {}", clip.file_path, e),
+                }
+            }
+
+            for mut copies in by_hash.into_values().filter(|c| c.len() > 1) {
+                copies.sort_by_key(|(_, clip)| clip.created_at);
+                let mut refs = copies.into_iter().map(|(game_id, clip)| ClipRef {
+                    game_id,
+                    file_path: clip.file_path,
+                });
+                let keep = refs.next().expect("group has at least two clips");
+                let duplicates: Vec<ClipRef> = refs.collect();
+
+                scan.reclaimable_bytes += size_bytes * duplicates.len() as u64;
+                scan.groups.push(DuplicateGroup {
+                    size_bytes,
+                    keep,
+                    duplicates,
+                });
+            }
+        }
+        scan.groups.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes));
+
+        tracing::info!(
+            "Found {} duplicate groups ({} bytes reclaimable)",
+            scan.groups.len(),
+            scan.reclaimable_bytes
+        );
+        Ok(scan)
+    }
+}
+
+/// Hash of the first and last [`SAMPLE_BYTES`] of a file of `size` bytes
+fn sample_hash(path: &Path, size: u64) -> std::io::Result<u64> {
+    let mut file = File::open(path)?;
+    let mut hasher = DefaultHasher::new();
+    let mut buf = Vec::with_capacity(SAMPLE_BYTES as usize);
+
+    file.by_ref().take(SAMPLE_BYTES).read_to_end(&mut buf)?;
+    hasher.write(&buf);
+
+    if size > SAMPLE_BYTES {
+        buf.clear();
+        file.seek(SeekFrom::Start(size - SAMPLE_BYTES))?;
+        file.take(SAMPLE_BYTES).read_to_end(&mut buf)?;
+        hasher.write(&buf);
+    }
+
+    Ok(hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::test_support::save_clip;
+    use chrono::Duration;
+
+    #[test]
+    fn test_find_duplicate_clips() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_duplicates");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let storage = Storage::new(&temp_dir).unwrap();
+
+        for (game_id, name, contents, age_secs) in [
+            ("game_a", "copy.mp4", "same video", 10),
+            ("game_a", "original.mp4", "same video", 60),
+            ("game_b", "other_game.mp4", "same video", 30),
+            // Same size, different content
+            ("game_b", "lookalike.mp4", "diff video", 5),
+            ("game_b", "unique.mp4", "unique", 5),
+            // Indexed twice, still one file
+            ("game_c", "unique.mp4", "unique", 5),
+        ] {
+            save_clip(
+                &storage,
+                game_id,
+                name,
+                contents.as_bytes(),
+                Duration::seconds(age_secs),
+            );
+        }
+
+        let scan = storage.find_duplicate_clips().unwrap();
+        assert_eq!(scan.groups.len(), 1);
+        assert_eq!(scan.reclaimable_bytes, 20);
+
+        let group = &scan.groups[0];
+        assert!(group.keep.file_path.ends_with("original.mp4"));
+        let duplicates: Vec<&str> = group
+            .duplicates
+            .iter()
+            .map(|c| c.game_id.as_str())
+            .collect();
+        assert_eq!(duplicates, vec!["game_b", "game_a"]);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+
+    #[test]
+    fn test_sample_hash_reads_both_ends() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_sample_hash");
+        let _ = fs::remove_dir_all(&temp_dir);
+        fs::create_dir_all(&temp_dir).unwrap();
+
+        let size = 3 * SAMPLE_BYTES as usize;
+        let a = vec![0u8; size];
+        let mut b = a.clone();
+        *b.last_mut().unwrap() = 1;
+        let mut c = a.clone();
+        c[size / 2] = 1;
+
+        let hash = |name: &str, content: &[u8]| {
+            let path = temp_dir.join(name);
+            fs::write(&path, content).unwrap();
+            sample_hash(&path, content.len() as u64).unwrap()
+        };
+        let hash_a = hash("a.mp4", &a);
+        assert_ne!(hash_a, hash("b.mp4", &b));
+        // The middle isn't sampled
+        assert_eq!(hash_a, hash("c.mp4", &c));
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+}
@@ -0,0 +1,194 @@
+//! What each user keeps and drops when picking auto-edit clips by hand
+//!
+//! Every manual selection counts, per event type, how many clips were kept
+//! and how many were left out. Auto-selection multiplies clip scores by the
+//! resulting weight, so a user who always removes turret kills stops getting
+//! them. Users can also pin a weight for an event type themselves.
+//!
+//! Selections are recorded per set of games, so rerunning an auto-edit on
+//! the same games replaces its earlier decisions instead of counting twice.
+//!
+//! Stored in `<base_path>/feedback/<user_id>.json`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use super::{read_json, write_atomic, Result, Storage};
+
+/// Weights range from `1 - WEIGHT_RANGE` (always dropped) to
+/// `1 + WEIGHT_RANGE` (always kept)
+const WEIGHT_RANGE: f64 = 0.5;
+
+/// Decisions needed before the learned weight moves halfway to its limit
+const PRIOR_DECISIONS: f64 = 4.0;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Decisions {
+    pub included: u32,
+    pub excluded: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SelectionFeedback {
+    /// Manual selection decisions: selection key -> event type -> decisions
+    pub selections: HashMap<String, HashMap<String, Decisions>>,
+    /// Weights set by the user, replacing the learned ones
+    pub overrides: HashMap<String, f64>,
+}
+
+impl SelectionFeedback {
+    /// Record which clips of one manual selection were kept, as
+    /// `(event_type, included)`
+    ///
+    /// Replaces what was recorded earlier under the same `selection` key.
+    pub fn record<'a>(
+        &mut self,
+        selection: &str,
+        clips: impl IntoIterator<Item = (&'a str, bool)>,
+    ) {
+        let mut decisions: HashMap<String, Decisions> = HashMap::new();
+        for (event_type, included) in clips {
+            let decisions = decisions.entry(event_type.to_string()).or_default();
+            if included {
+                decisions.included += 1;
+            } else {
+                decisions.excluded += 1;
+            }
+        }
+        self.selections.insert(selection.to_string(), decisions);
+    }
+
+    /// Decisions for `event_type` across all selections
+    fn decisions(&self, event_type: &str) -> Option<Decisions> {
+        let mut total: Option<Decisions> = None;
+        for decisions in self.selections.values() {
+            if let Some(decisions) = decisions.get(event_type) {
+                let total = total.get_or_insert_with(Decisions::default);
+                total.included += decisions.included;
+                total.excluded += decisions.excluded;
+            }
+        }
+        total
+    }
+
+    /// Multiplier for clip scores of `event_type` (1.0 = no preference)
+    pub fn weight(&self, event_type: &str) -> f64 {
+        if let Some(weight) = self.overrides.get(event_type) {
+            return *weight;
+        }
+        let Some(decisions) = self.decisions(event_type) else {
+            return 1.0;
+        };
+
+        // Smoothed keep rate in -1..1, so a couple of decisions don't swing it
+        let kept = decisions.included as f64 - decisions.excluded as f64;
+        let total = (decisions.included + decisions.excluded) as f64;
+        1.0 + WEIGHT_RANGE * kept / (total + PRIOR_DECISIONS)
+    }
+
+    /// Current weight of every event type with decisions or an override
+    pub fn weights(&self) -> HashMap<String, f64> {
+        self.selections
+            .values()
+            .flat_map(|decisions| decisions.keys())
+            .chain(self.overrides.keys())
+            .map(|event_type| (event_type.clone(), self.weight(event_type)))
+            .collect()
+    }
+}
+
+impl Storage {
+    fn feedback_path(&self, user_id: &str) -> PathBuf {
+        let file_name: String = user_id
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.base_path
+            .join("feedback")
+            .join(format!("{}.json", file_name))
+    }
+
+    /// Selection feedback for `user_id` (empty if none was recorded)
+    pub fn load_selection_feedback(&self, user_id: &str) -> Result<SelectionFeedback> {
+        let path = self.feedback_path(user_id);
+        if !path.exists() {
+            return Ok(SelectionFeedback::default());
+        }
+        read_json(&path)
+    }
+
+    pub fn save_selection_feedback(
+        &self,
+        user_id: &str,
+        feedback: &SelectionFeedback,
+    ) -> Result<()> {
+        let path = self.feedback_path(user_id);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        write_atomic(&path, serde_json::to_string_pretty(feedback)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feedback_weights() {
+        let mut feedback = SelectionFeedback::default();
+        assert_eq!(feedback.weight("TurretKill"), 1.0);
+
+        let mut selection = vec![("TurretKill", false); 4];
+        selection.extend([("PentaKill", true); 4]);
+        selection.push(("ChampionKill", true));
+        feedback.record("game-1", selection.clone());
+        feedback.record("game-2", [("ChampionKill", false)]);
+
+        assert!((feedback.weight("TurretKill") - 0.75).abs() < 1e-9);
+        assert!((feedback.weight("PentaKill") - 1.25).abs() < 1e-9);
+        assert_eq!(feedback.weight("ChampionKill"), 1.0);
+
+        // Rerunning the same selection doesn't count it twice
+        feedback.record("game-1", selection);
+        assert!((feedback.weight("TurretKill") - 0.75).abs() < 1e-9);
+
+        feedback.overrides.insert("TurretKill".to_string(), 1.2);
+        assert_eq!(feedback.weight("TurretKill"), 1.2);
+        assert_eq!(feedback.weights().len(), 3);
+    }
+
+    #[test]
+    fn test_feedback_storage() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_feedback");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let storage = Storage::new(&temp_dir).unwrap();
+
+        assert_eq!(
+            storage.load_selection_feedback("user-1").unwrap(),
+            SelectionFeedback::default()
+        );
+
+        let mut feedback = SelectionFeedback::default();
+        feedback.record("game-1", [("TurretKill", false)]);
+        storage
+            .save_selection_feedback("user-1", &feedback)
+            .unwrap();
+        assert_eq!(storage.load_selection_feedback("user-1").unwrap(), feedback);
+        assert_eq!(
+            storage.load_selection_feedback("user-2").unwrap(),
+            SelectionFeedback::default()
+        );
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+}
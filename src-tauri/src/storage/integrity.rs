@@ -0,0 +1,438 @@
+//! Library integrity check and repair
+//!
+//! [`Storage::verify_library`] scans every game for clips whose video is gone,
+//! metadata that can't be parsed, videos no metadata points to and
+//! thumbnails left behind by deleted clips. Most problems come with a repair
+//! the UI can apply through [`Storage::apply_repair`]; videos that may be the
+//! user's own are only reported.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::models::{ClipMetadata, EventType};
+use super::{read_json, ClipMetadataV2, EventData, GameMetadata, Result, Storage};
+
+/// A problem found in the library
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum LibraryIssue {
+    /// clips.json lists a clip whose video file doesn't exist
+    MissingVideo { game_id: String, file_path: String },
+    /// A metadata file that can't be parsed (and has no usable backup)
+    CorruptMetadata { game_id: String, path: String },
+    /// A video that no clips.json refers to
+    OrphanedVideo { path: String },
+    /// A thumbnail whose clip is gone
+    OrphanedThumbnail { game_id: String, path: String },
+}
+
+/// A fix for a [`LibraryIssue`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum RepairAction {
+    /// Drop the clip from clips.json (along with its V2 metadata)
+    RemoveClip {
+        game_id: String,
+        file_path: String,
+    },
+    /// Rebuild the clip's metadata from clips.json or its file name
+    RegenerateMetadata {
+        game_id: String,
+        file_path: String,
+    },
+    DeleteFile {
+        path: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepairItem {
+    pub issue: LibraryIssue,
+    /// None if the issue can't be fixed without asking the user
+    pub repair: Option<RepairAction>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LibraryReport {
+    pub games_checked: usize,
+    pub clips_checked: usize,
+    pub items: Vec<RepairItem>,
+}
+
+impl Storage {
+    /// Check every game in the library
+    ///
+    /// `recordings_dirs` are where the recorder saves clips; videos there (and
+    /// in the game folders) that no game refers to are reported as orphans.
+    pub fn verify_library(&self, recordings_dirs: &[PathBuf]) -> Result<LibraryReport> {
+        let mut report = LibraryReport::default();
+        let mut known_videos = HashSet::new();
+        let mut known_thumbnails = HashSet::new();
+        let games = self.list_games()?;
+
+        for game_id in &games {
+            report.games_checked += 1;
+            let game_path = self.game_path(game_id);
+
+            for (file, parsed) in [
+                (
+                    "metadata.json",
+                    parses::<GameMetadata>(&game_path, "metadata.json"),
+                ),
+                (
+                    "events.json",
+                    parses::<Vec<EventData>>(&game_path, "events.json"),
+                ),
+            ] {
+                if !parsed {
+                    report.items.push(RepairItem {
+                        issue: LibraryIssue::CorruptMetadata {
+                            game_id: game_id.clone(),
+                            path: game_path.join(file).to_string_lossy().to_string(),
+                        },
+                        repair: None,
+                    });
+                }
+            }
+
+            let clips = match self.load_clip_metadata(game_id) {
+                Ok(clips) => clips,
+                Err(_) => {
+                    report.items.push(RepairItem {
+                        issue: LibraryIssue::CorruptMetadata {
+                            game_id: game_id.clone(),
+                            path: game_path.join("clips.json").to_string_lossy().to_string(),
+                        },
+                        repair: None,
+                    });
+                    continue;
+                }
+            };
+
+            for clip in clips {
+                report.clips_checked += 1;
+                known_videos.insert(PathBuf::from(&clip.file_path));
+
+                if !Path::new(&clip.file_path).exists() {
+                    report.items.push(RepairItem {
+                        issue: LibraryIssue::MissingVideo {
+                            game_id: game_id.clone(),
+                            file_path: clip.file_path.clone(),
+                        },
+                        repair: Some(RepairAction::RemoveClip {
+                            game_id: game_id.clone(),
+                            file_path: clip.file_path,
+                        }),
+                    });
+                    continue;
+                }
+
+                if let Some(thumbnail) = &clip.thumbnail_path {
+                    known_thumbnails.insert(PathBuf::from(thumbnail));
+                }
+
+                let json_path = Path::new(&clip.file_path).with_extension("json");
+                if json_path.exists() && read_json::<ClipMetadataV2>(&json_path).is_err() {
+                    report.items.push(RepairItem {
+                        issue: LibraryIssue::CorruptMetadata {
+                            game_id: game_id.clone(),
+                            path: json_path.to_string_lossy().to_string(),
+                        },
+                        repair: Some(RepairAction::RegenerateMetadata {
+                            game_id: game_id.clone(),
+                            file_path: clip.file_path,
+                        }),
+                    });
+                }
+            }
+
+            for thumbnail in files_with_extension(&game_path.join("thumbnails"), "jpg") {
+                if !known_thumbnails.contains(&thumbnail) {
+                    report.items.push(RepairItem {
+                        issue: LibraryIssue::OrphanedThumbnail {
+                            game_id: game_id.clone(),
+                            path: thumbnail.to_string_lossy().to_string(),
+                        },
+                        repair: Some(RepairAction::DeleteFile {
+                            path: thumbnail.to_string_lossy().to_string(),
+                        }),
+                    });
+                }
+            }
+        }
+
+        let mut video_dirs = recordings_dirs.to_vec();
+        video_dirs.extend(games.iter().map(|game_id| self.game_path(game_id)));
+        video_dirs.dedup();
+        for video in video_dirs
+            .iter()
+            .flat_map(|dir| files_with_extension(dir, "mp4"))
+        {
+            if known_videos.contains(&video) {
+                continue;
+            }
+
+            let path = video.to_string_lossy().to_string();
+            // Clips of a game that still exists get their metadata back.
+            // Anything else may be a video the user keeps there on purpose,
+            // so it's only reported; the UI sends `DeleteFile` once they
+            // confirm.
+            let repair = match parse_clip_file_name(&video) {
+                Some(name) if games.contains(&name.game_id) => {
+                    Some(RepairAction::RegenerateMetadata {
+                        game_id: name.game_id,
+                        file_path: path.clone(),
+                    })
+                }
+                _ => None,
+            };
+
+            report.items.push(RepairItem {
+                issue: LibraryIssue::OrphanedVideo { path },
+                repair,
+            });
+        }
+
+        tracing::info!(
+            "Verified library: {} games, {} clips, {} issues",
+            report.games_checked,
+            report.clips_checked,
+            report.items.len()
+        );
+
+        Ok(report)
+    }
+
+    /// Apply one repair from [`Storage::verify_library`]
+    pub fn apply_repair(&self, action: &RepairAction) -> Result<()> {
+        match action {
+            RepairAction::RemoveClip { game_id, file_path } => {
+                let json_path = Path::new(file_path).with_extension("json");
+                if json_path.exists() {
+                    fs::remove_file(json_path)?;
+                }
+                self.delete_clip_metadata(game_id, file_path)?;
+            }
+            RepairAction::RegenerateMetadata { game_id, file_path } => {
+                let indexed = self
+                    .load_clip_metadata(game_id)?
+                    .into_iter()
+                    .find(|clip| &clip.file_path == file_path);
+                let clip = match indexed {
+                    Some(clip) => clip,
+                    None => clip_from_file(Path::new(file_path)),
+                };
+
+                let mut clip = ClipMetadataV2::from(clip);
+                clip.game_id = game_id.clone();
+                self.save_clip_metadata_v2(game_id, &clip)?;
+            }
+            RepairAction::DeleteFile { path } => {
+                let path = Path::new(path);
+                if path.exists() {
+                    fs::remove_file(path)?;
+                }
+            }
+        }
+
+        tracing::info!("Applied library repair: {:?}", action);
+        Ok(())
+    }
+}
+
+/// Whether `file` in `dir` is missing or parses as `T`
+fn parses<T: serde::de::DeserializeOwned>(dir: &Path, file: &str) -> bool {
+    let path = dir.join(file);
+    !path.exists() || read_json::<T>(&path).is_ok()
+}
+
+fn files_with_extension(dir: &Path, extension: &str) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+        })
+        .collect()
+}
+
+/// Parts of a recorder clip file name
+#[derive(Debug, PartialEq)]
+struct ClipFileName {
+    game_id: String,
+    priority: u8,
+    clip_id: String,
+}
+
+/// Parse `<game_id>_<YYYYmmdd>_<HHMMSS>_p<priority>_<clip_id>.mp4`
+fn parse_clip_file_name(path: &Path) -> Option<ClipFileName> {
+    let stem = path.file_stem()?.to_str()?;
+
+    // The game ID may contain underscores itself, so find the timestamp
+    let parts: Vec<&str> = stem.split('_').collect();
+    (1..parts.len().saturating_sub(3)).find_map(|i| {
+        let (date, time, priority) = (parts[i], parts[i + 1], parts[i + 2]);
+        let is_digits =
+            |s: &str, len: usize| s.len() == len && s.bytes().all(|b| b.is_ascii_digit());
+
+        if !is_digits(date, 8) || !is_digits(time, 6) {
+            return None;
+        }
+
+        Some(ClipFileName {
+            game_id: parts[..i].join("_"),
+            priority: priority.strip_prefix('p')?.parse().ok()?,
+            clip_id: parts[i + 3..].join("_"),
+        })
+    })
+}
+
+/// Best-effort clips.json entry for a video without metadata
+fn clip_from_file(path: &Path) -> ClipMetadata {
+    let name = parse_clip_file_name(path);
+    let created_at = fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map(chrono::DateTime::<chrono::Utc>::from)
+        .unwrap_or_else(|_| chrono::Utc::now());
+
+    ClipMetadata {
+        file_path: path.to_string_lossy().to_string(),
+        thumbnail_path: None,
+        event_type: EventType::Custom(
+            name.as_ref()
+                .map(|n| n.clip_id.clone())
+                .unwrap_or_else(|| "Recovered".to_string()),
+        ),
+        event_time: 0.0,
+        priority: name.as_ref().map(|n| n.priority).unwrap_or(1),
+        duration: 0.0, // Unknown without probing the video
+        created_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_clip_file_name() {
+        let name = parse_clip_file_name(Path::new(
+            "/rec/replay_20261018_101500_20261018_102030_p3_ChampionKill_754.mp4",
+        ))
+        .unwrap();
+        assert_eq!(name.game_id, "replay_20261018_101500");
+        assert_eq!(name.priority, 3);
+        assert_eq!(name.clip_id, "ChampionKill_754");
+
+        let name = parse_clip_file_name(Path::new("7012345678_20261018_102030_p5_x.mp4")).unwrap();
+        assert_eq!(name.game_id, "7012345678");
+
+        assert!(parse_clip_file_name(Path::new("holiday_video.mp4")).is_none());
+    }
+
+    #[test]
+    fn test_verify_and_repair_library() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_integrity");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let storage = Storage::new(&temp_dir).unwrap();
+        let recordings = temp_dir.join("recordings");
+        fs::create_dir_all(&recordings).unwrap();
+
+        let video = |name: &str| {
+            let path = recordings.join(name);
+            fs::write(&path, b"video").unwrap();
+            path.to_string_lossy().to_string()
+        };
+        let clip = |file_path: String| ClipMetadata {
+            file_path,
+            thumbnail_path: None,
+            event_type: EventType::ChampionKill,
+            event_time: 100.0,
+            priority: 1,
+            duration: 13.0,
+            created_at: chrono::Utc::now(),
+        };
+
+        let good = video("123_20261018_102030_p1_kill_100.mp4");
+        storage
+            .save_clip_metadata("123", &clip(good.clone()))
+            .unwrap();
+        let missing = recordings.join("gone.mp4").to_string_lossy().to_string();
+        storage
+            .save_clip_metadata("123", &clip(missing.clone()))
+            .unwrap();
+        let orphan = video("123_20261018_103000_p4_hotkey_60s_1.mp4");
+        let stray = video("999_20261018_103000_p1_kill_1.mp4");
+        let thumbnails = storage.game_path("123").join("thumbnails");
+        fs::create_dir_all(&thumbnails).unwrap();
+        fs::write(thumbnails.join("gone_thumbnail.jpg"), b"jpg").unwrap();
+
+        let report = storage.verify_library(&[recordings.clone()]).unwrap();
+        assert_eq!(report.games_checked, 1);
+        assert_eq!(report.clips_checked, 2);
+        assert_eq!(report.items.len(), 4);
+
+        let repair = |issue: &LibraryIssue| {
+            report
+                .items
+                .iter()
+                .find(|item| &item.issue == issue)
+                .map(|item| item.repair.clone())
+                .unwrap()
+        };
+        assert_eq!(
+            repair(&LibraryIssue::MissingVideo {
+                game_id: "123".to_string(),
+                file_path: missing.clone(),
+            }),
+            Some(RepairAction::RemoveClip {
+                game_id: "123".to_string(),
+                file_path: missing,
+            })
+        );
+        assert_eq!(
+            repair(&LibraryIssue::OrphanedVideo {
+                path: orphan.clone()
+            }),
+            Some(RepairAction::RegenerateMetadata {
+                game_id: "123".to_string(),
+                file_path: orphan.clone(),
+            })
+        );
+        assert_eq!(
+            repair(&LibraryIssue::OrphanedVideo {
+                path: stray.clone()
+            }),
+            None
+        );
+
+        for repair in report.items.iter().filter_map(|item| item.repair.as_ref()) {
+            storage.apply_repair(repair).unwrap();
+        }
+
+        // The stray video stays until the user deletes it
+        let report = storage.verify_library(&[recordings.clone()]).unwrap();
+        assert_eq!(report.items.len(), 1);
+        storage
+            .apply_repair(&RepairAction::DeleteFile { path: stray })
+            .unwrap();
+        let report = storage.verify_library(&[recordings.clone()]).unwrap();
+        assert!(report.items.is_empty());
+        let clips = storage.load_clip_metadata("123").unwrap();
+        let restored = clips.iter().find(|c| c.file_path == orphan).unwrap();
+        assert_eq!(restored.priority, 4);
+
+        // Cleanup
+        let _ = fs::remove_dir_all(temp_dir);
+    }
+}
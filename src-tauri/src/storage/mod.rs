@@ -1,15 +1,25 @@
+pub mod batch;
 pub mod commands;
+pub mod duplicates;
+pub mod feedback;
+pub mod integrity;
 pub mod models;
 pub mod models_v2;
+pub mod relocation;
+pub mod timeline;
+pub mod trash;
+pub mod usage;
 
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 // Re-export public types
 pub use models::{
-    AutoEditResultMetadata, AutoEditUsage, ClipMetadata, EventData, GameMetadata, StorageStats,
+    AutoEditResultMetadata, ClipMetadata, EventData, GameMetadata, StorageStats, UploadDraft,
     UploadStatus, YouTubeUploadStatus,
 };
 
@@ -24,11 +34,26 @@ pub enum StorageError {
     Json(#[from] serde_json::Error),
     #[error("Game not found: {0}")]
     GameNotFound(String),
+    #[error("Trash entry not found: {0}")]
+    TrashEntryNotFound(String),
+    #[error("File already exists: {0}")]
+    AlreadyExists(String),
 }
 
 pub type Result<T> = std::result::Result<T, StorageError>;
 
+/// Library format flags (kept apart from settings.json, which may be the
+/// recording settings file on Windows)
+const STORAGE_INFO_FILE: &str = "storage.json";
+
+/// Set in [`STORAGE_INFO_FILE`] once clip paths have been made relative
+const RELATIVE_PATHS_KEY: &str = "relative_clip_paths";
+
 /// JSON-based file storage for clips and metadata
+///
+/// Clip paths inside the storage root are saved relative to it, so the
+/// library keeps working if the whole folder is moved; they're returned
+/// as absolute paths.
 pub struct Storage {
     base_path: PathBuf,
 }
@@ -46,7 +71,12 @@ impl Storage {
 
         tracing::info!("Storage initialized at: {}", base_path.display());
 
-        Ok(Self { base_path })
+        let storage = Self { base_path };
+        if let Err(e) = storage.migrate_to_relative_paths() {
+            tracing::error!("Failed to convert clip paths to relative: {}", e);
+        }
+
+        Ok(storage)
     }
 
     /// Get the base storage path
@@ -67,7 +97,7 @@ impl Storage {
         // Save metadata
         let metadata_path = game_path.join("metadata.json");
         let json = serde_json::to_string_pretty(metadata)?;
-        fs::write(metadata_path, json)?;
+        write_atomic(&metadata_path, json)?;
 
         tracing::info!("Created game directory: {}", game_id);
         Ok(())
@@ -83,7 +113,7 @@ impl Storage {
 
         let metadata_path = game_path.join("metadata.json");
         let json = serde_json::to_string_pretty(metadata)?;
-        fs::write(metadata_path, json)?;
+        write_atomic(&metadata_path, json)?;
 
         Ok(())
     }
@@ -96,8 +126,7 @@ impl Storage {
             return Err(StorageError::GameNotFound(game_id.to_string()));
         }
 
-        let json = fs::read_to_string(metadata_path)?;
-        let metadata = serde_json::from_str(&json)?;
+        let metadata = read_json(&metadata_path)?;
 
         Ok(metadata)
     }
@@ -112,12 +141,23 @@ impl Storage {
 
         let events_path = game_path.join("events.json");
         let json = serde_json::to_string_pretty(events)?;
-        fs::write(events_path, json)?;
+        write_atomic(&events_path, json)?;
 
         tracing::debug!("Saved {} events for game {}", events.len(), game_id);
         Ok(())
     }
 
+    /// Add events to a game's list, replacing any with the same ID
+    ///
+    /// The list stays sorted by game time.
+    pub fn add_events(&self, game_id: &str, events: &[EventData]) -> Result<()> {
+        let mut all = self.load_events(game_id)?;
+        all.retain(|existing| events.iter().all(|e| e.event_id != existing.event_id));
+        all.extend_from_slice(events);
+        all.sort_by(|a, b| a.timestamp.total_cmp(&b.timestamp));
+        self.save_events(game_id, &all)
+    }
+
     /// Load events for a game
     pub fn load_events(&self, game_id: &str) -> Result<Vec<EventData>> {
         let events_path = self.game_path(game_id).join("events.json");
@@ -126,8 +166,7 @@ impl Storage {
             return Ok(Vec::new());
         }
 
-        let json = fs::read_to_string(events_path)?;
-        let events = serde_json::from_str(&json)?;
+        let events = read_json(&events_path)?;
 
         Ok(events)
     }
@@ -151,11 +190,7 @@ impl Storage {
         }
 
         // Save clips
-        let clips_path = game_path.join("clips.json");
-        let json = serde_json::to_string_pretty(&clips)?;
-        fs::write(clips_path, json)?;
-
-        Ok(())
+        self.write_clip_index(game_id, &clips)
     }
 
     /// Load all clip metadata for a game
@@ -166,8 +201,11 @@ impl Storage {
             return Ok(Vec::new());
         }
 
-        let json = fs::read_to_string(clips_path)?;
-        let clips = serde_json::from_str(&json)?;
+        let mut clips: Vec<ClipMetadata> = read_json(&clips_path)?;
+        for clip in &mut clips {
+            clip.file_path = self.resolve_path(&clip.file_path);
+            clip.thumbnail_path = clip.thumbnail_path.as_deref().map(|p| self.resolve_path(p));
+        }
 
         Ok(clips)
     }
@@ -232,11 +270,7 @@ impl Storage {
         }
 
         // Save updated clips list
-        let clips_path = self.game_path(game_id).join("clips.json");
-        let json = serde_json::to_string_pretty(&clips)?;
-        fs::write(clips_path, json)?;
-
-        Ok(())
+        self.write_clip_index(game_id, &clips)
     }
 
     /// Get storage statistics
@@ -292,8 +326,7 @@ impl Storage {
         let json_path = video_path.with_extension("json");
 
         // Save individual clip JSON
-        let json = serde_json::to_string_pretty(clip)?;
-        fs::write(&json_path, json)?;
+        self.write_clip_v2(clip)?;
 
         tracing::debug!("Saved V2 metadata: {:?}", json_path);
 
@@ -316,10 +349,29 @@ impl Storage {
             )));
         }
 
-        let json = fs::read_to_string(&json_path)?;
-        let clip = serde_json::from_str(&json)?;
+        self.read_clip_v2(&json_path)
+    }
 
-        Ok(clip)
+    /// Find an earlier clip of the game covering mostly the same game time
+    ///
+    /// Looks through the game's clips.json index for a clip (other than `clip`
+    /// itself) whose game-time window overlaps `clip`'s by at least
+    /// `min_overlap`, as a fraction of the shorter window.
+    pub fn find_overlapping_clip(
+        &self,
+        game_id: &str,
+        clip: &ClipMetadataV2,
+        min_overlap: f64,
+    ) -> Result<Option<ClipMetadata>> {
+        let clips = self.load_clip_metadata(game_id)?;
+
+        Ok(clips.into_iter().find(|existing| {
+            existing.file_path != clip.file_path
+                && window_overlap(
+                    (existing.event_time, existing.event_time + existing.duration),
+                    (clip.game_time_start, clip.game_time_end),
+                ) >= min_overlap
+        }))
     }
 
     /// Load all V2 clip metadata for a game
@@ -340,10 +392,8 @@ impl Storage {
 
             // Only load JSON files
             if path.extension().and_then(|s| s.to_str()) == Some("json") {
-                if let Ok(json) = fs::read_to_string(&path) {
-                    if let Ok(clip) = serde_json::from_str::<ClipMetadataV2>(&json) {
-                        clips.push(clip);
-                    }
+                if let Ok(clip) = self.read_clip_v2(&path) {
+                    clips.push(clip);
                 }
             }
         }
@@ -358,8 +408,6 @@ impl Storage {
     ///
     /// This maintains backward compatibility with V1 while adding V2 support.
     fn update_clips_index_v2(&self, game_id: &str, clip: &ClipMetadataV2) -> Result<()> {
-        let game_path = self.game_path(game_id);
-
         // Load existing V1 clips
         let mut v1_clips = self.load_clip_metadata(game_id).unwrap_or_default();
 
@@ -385,40 +433,116 @@ impl Storage {
         }
 
         // Save index
-        let clips_path = game_path.join("clips.json");
-        let json = serde_json::to_string_pretty(&v1_clips)?;
-        fs::write(clips_path, json)?;
+        self.write_clip_index(game_id, &v1_clips)
+    }
 
-        Ok(())
+    /// Write a game's clips.json, storing paths relative to the storage root
+    fn write_clip_index(&self, game_id: &str, clips: &[ClipMetadata]) -> Result<()> {
+        let stored: Vec<ClipMetadata> = clips
+            .iter()
+            .cloned()
+            .map(|mut clip| {
+                clip.file_path = self.stored_path(&clip.file_path);
+                clip.thumbnail_path = clip.thumbnail_path.as_deref().map(|p| self.stored_path(p));
+                clip
+            })
+            .collect();
+
+        let clips_path = self.game_path(game_id).join("clips.json");
+        let json = serde_json::to_string_pretty(&stored)?;
+        write_atomic(&clips_path, json)
     }
 
-    /// Delete V2 clip metadata (both video and JSON)
-    pub fn delete_clip_v2(&self, game_id: &str, clip_path: &str) -> Result<()> {
-        let video_path = Path::new(clip_path);
-        let json_path = video_path.with_extension("json");
-        let jpg_path = video_path.with_extension("jpg");
+    /// Write a clip's V2 JSON next to its video, with relative paths
+    fn write_clip_v2(&self, clip: &ClipMetadataV2) -> Result<()> {
+        let json_path = Path::new(&self.resolve_path(&clip.file_path)).with_extension("json");
+
+        let mut stored = clip.clone();
+        stored.file_path = self.stored_path(&clip.file_path);
+        stored.thumbnail_path = clip.thumbnail_path.as_deref().map(|p| self.stored_path(p));
+
+        let json = serde_json::to_string_pretty(&stored)?;
+        write_atomic(&json_path, json)
+    }
+
+    /// Read a V2 JSON file, resolving its paths
+    fn read_clip_v2(&self, json_path: &Path) -> Result<ClipMetadataV2> {
+        let mut clip: ClipMetadataV2 = read_json(json_path)?;
+        clip.file_path = self.resolve_path(&clip.file_path);
+        clip.thumbnail_path = clip.thumbnail_path.as_deref().map(|p| self.resolve_path(p));
+        Ok(clip)
+    }
 
-        // Delete video file
-        if video_path.exists() {
-            fs::remove_file(video_path)?;
-            tracing::info!("Deleted video: {:?}", video_path);
+    /// Path as written to metadata: relative if it's inside the storage root
+    ///
+    /// Files elsewhere (e.g. recordings on another drive) keep absolute paths.
+    fn stored_path(&self, path: &str) -> String {
+        Path::new(path)
+            .strip_prefix(&self.base_path)
+            .map(|relative| relative.to_string_lossy().to_string())
+            .unwrap_or_else(|_| path.to_string())
+    }
+
+    /// Absolute path for a path read from metadata
+    pub fn resolve_path(&self, path: &str) -> String {
+        if Path::new(path).is_relative() {
+            self.base_path.join(path).to_string_lossy().to_string()
+        } else {
+            path.to_string()
         }
+    }
 
-        // Delete JSON metadata
-        if json_path.exists() {
-            fs::remove_file(&json_path)?;
-            tracing::info!("Deleted metadata: {:?}", json_path);
+    /// Rewrite metadata saved with absolute paths (before paths were stored
+    /// relative to the storage root); runs once per library
+    fn migrate_to_relative_paths(&self) -> Result<()> {
+        let info_path = self.base_path.join(STORAGE_INFO_FILE);
+        let mut info: serde_json::Map<String, serde_json::Value> = if info_path.exists() {
+            read_json(&info_path)?
+        } else {
+            serde_json::Map::new()
+        };
+        if info.contains_key(RELATIVE_PATHS_KEY) {
+            return Ok(());
         }
 
-        // Delete thumbnail
-        if jpg_path.exists() {
-            fs::remove_file(&jpg_path)?;
-            tracing::info!("Deleted thumbnail: {:?}", jpg_path);
+        let mut converted = 0;
+        for game_id in self.list_games()? {
+            let clips = match self.load_clip_metadata(&game_id) {
+                Ok(clips) => clips,
+                Err(e) => {
+                    tracing::warn!("Skipping path migration for game {}: {}", game_id, e);
+                    continue;
+                }
+            };
+            if clips.is_empty() {
+                continue;
+            }
+            self.write_clip_index(&game_id, &clips)?;
+
+            for clip in &clips {
+                let json_path = Path::new(&clip.file_path).with_extension("json");
+                if let Ok(clip) = self.read_clip_v2(&json_path) {
+                    self.write_clip_v2(&clip)?;
+                }
+            }
+            converted += clips.len();
         }
 
-        // Update clips.json index
-        self.delete_clip_metadata(game_id, clip_path)?;
+        info.insert(
+            RELATIVE_PATHS_KEY.to_string(),
+            serde_json::Value::Bool(true),
+        );
+        write_atomic(&info_path, serde_json::to_string_pretty(&info)?)?;
+
+        tracing::info!("Converted {} clip paths to relative paths", converted);
+        Ok(())
+    }
 
+    /// Delete a V2 clip (video, JSON and thumbnail)
+    ///
+    /// The files go to the recycle bin, see [`Storage::trash_clip`].
+    pub fn delete_clip_v2(&self, game_id: &str, clip_path: &str) -> Result<()> {
+        self.trash_clip(game_id, clip_path)?;
         Ok(())
     }
 
@@ -487,7 +611,7 @@ impl Storage {
 
         let template_path = templates_dir.join(format!("{}.json", template.id));
         let json = serde_json::to_string_pretty(template)?;
-        fs::write(template_path, json)?;
+        write_atomic(&template_path, json)?;
 
         tracing::info!("Saved canvas template: {} ({})", template.name, template.id);
         Ok(())
@@ -507,8 +631,7 @@ impl Storage {
             )));
         }
 
-        let json = fs::read_to_string(template_path)?;
-        let template = serde_json::from_str(&json)?;
+        let template = read_json(&template_path)?;
 
         Ok(template)
     }
@@ -530,16 +653,12 @@ impl Storage {
             let path = entry.path();
 
             if path.extension().and_then(|s| s.to_str()) == Some("json") {
-                if let Ok(json) = fs::read_to_string(&path) {
-                    if let Ok(template) =
-                        serde_json::from_str::<crate::video::CanvasTemplate>(&json)
-                    {
-                        templates.push(CanvasTemplateInfo {
-                            id: template.id.clone(),
-                            name: template.name.clone(),
-                            element_count: template.elements.len(),
-                        });
-                    }
+                if let Ok(template) = read_json::<crate::video::CanvasTemplate>(&path) {
+                    templates.push(CanvasTemplateInfo {
+                        id: template.id.clone(),
+                        name: template.name.clone(),
+                        element_count: template.elements.len(),
+                    });
                 }
             }
         }
@@ -580,8 +699,7 @@ impl Storage {
             )));
         }
 
-        let json = fs::read_to_string(settings_path)?;
-        let settings: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&json)?;
+        let settings: serde_json::Map<String, serde_json::Value> = read_json(&settings_path)?;
 
         settings
             .get(key)
@@ -601,8 +719,7 @@ impl Storage {
 
         // Load existing settings
         let mut settings: serde_json::Map<String, serde_json::Value> = if settings_path.exists() {
-            let json = fs::read_to_string(&settings_path)?;
-            serde_json::from_str(&json)?
+            read_json(&settings_path)?
         } else {
             serde_json::Map::new()
         };
@@ -612,7 +729,7 @@ impl Storage {
 
         // Save settings
         let json = serde_json::to_string_pretty(&settings)?;
-        fs::write(settings_path, json)?;
+        write_atomic(&settings_path, json)?;
 
         Ok(())
     }
@@ -626,109 +743,18 @@ impl Storage {
         }
 
         // Load existing settings
-        let json = fs::read_to_string(&settings_path)?;
-        let mut settings: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&json)?;
+        let mut settings: serde_json::Map<String, serde_json::Value> = read_json(&settings_path)?;
 
         // Remove setting
         settings.remove(key);
 
         // Save settings
         let json = serde_json::to_string_pretty(&settings)?;
-        fs::write(settings_path, json)?;
+        write_atomic(&settings_path, json)?;
 
         Ok(())
     }
 
-    // ========================================================================
-    // Auto-Edit Usage Tracking (Quota System)
-    // ========================================================================
-
-    /// Load auto-edit usage for current month
-    ///
-    /// Returns existing usage or creates new tracking for current month.
-    pub fn load_auto_edit_usage(&self) -> Result<AutoEditUsage> {
-        let usage_path = self.base_path.join("auto_edit_usage.json");
-
-        if !usage_path.exists() {
-            // No usage file exists, create new
-            return Ok(AutoEditUsage::new());
-        }
-
-        let json = fs::read_to_string(&usage_path)?;
-        let mut usage: AutoEditUsage = serde_json::from_str(&json)?;
-
-        // Check if we need to reset for new month
-        if !usage.is_current_month() {
-            tracing::info!(
-                "Resetting auto-edit usage for new month: {} -> {}",
-                usage.month,
-                AutoEditUsage::current_month()
-            );
-            usage = AutoEditUsage::reset_for_month(AutoEditUsage::current_month());
-            self.save_auto_edit_usage(&usage)?;
-        }
-
-        Ok(usage)
-    }
-
-    /// Save auto-edit usage
-    fn save_auto_edit_usage(&self, usage: &AutoEditUsage) -> Result<()> {
-        let usage_path = self.base_path.join("auto_edit_usage.json");
-        let json = serde_json::to_string_pretty(usage)?;
-        fs::write(usage_path, json)?;
-
-        tracing::debug!("Saved auto-edit usage: month={}, count={}", usage.month, usage.usage_count);
-        Ok(())
-    }
-
-    /// Increment auto-edit usage counter
-    ///
-    /// Returns the new usage count.
-    pub fn increment_auto_edit_usage(&self) -> Result<u32> {
-        let mut usage = self.load_auto_edit_usage()?;
-
-        usage.usage_count += 1;
-        usage.last_updated = chrono::Utc::now();
-
-        self.save_auto_edit_usage(&usage)?;
-
-        tracing::info!("Auto-edit usage incremented: {}/{} (month: {})",
-            usage.usage_count, "∞", usage.month);
-
-        Ok(usage.usage_count)
-    }
-
-    /// Check if user can perform auto-edit based on quota
-    ///
-    /// FREE tier: 5 per month
-    /// PRO tier: Unlimited
-    ///
-    /// Returns Ok(remaining) if allowed, Err if quota exceeded.
-    pub fn check_auto_edit_quota(&self, is_pro: bool) -> Result<u32> {
-        if is_pro {
-            // PRO tier has unlimited usage
-            return Ok(u32::MAX);
-        }
-
-        // FREE tier: check quota
-        const FREE_TIER_LIMIT: u32 = 5;
-
-        let usage = self.load_auto_edit_usage()?;
-
-        if usage.usage_count >= FREE_TIER_LIMIT {
-            return Err(StorageError::Io(std::io::Error::new(
-                std::io::ErrorKind::PermissionDenied,
-                format!(
-                    "Monthly auto-edit quota exceeded ({}/{}). Upgrade to PRO for unlimited usage.",
-                    usage.usage_count, FREE_TIER_LIMIT
-                ),
-            )));
-        }
-
-        let remaining = FREE_TIER_LIMIT - usage.usage_count;
-        Ok(remaining)
-    }
-
     // ========================================================================
     // Auto-Edit Result Storage
     // ========================================================================
@@ -741,8 +767,7 @@ impl Storage {
 
         // Load existing results or create new list
         let mut results: Vec<models::AutoEditResultMetadata> = if results_path.exists() {
-            let json = fs::read_to_string(&results_path)?;
-            serde_json::from_str(&json).unwrap_or_else(|_| Vec::new())
+            read_json(&results_path).unwrap_or_default()
         } else {
             Vec::new()
         };
@@ -752,7 +777,7 @@ impl Storage {
 
         // Save updated results
         let json = serde_json::to_string_pretty(&results)?;
-        fs::write(results_path, json)?;
+        write_atomic(&results_path, json)?;
 
         tracing::info!(
             "Saved auto-edit result: {} (duration: {:.1}s, clips: {})",
@@ -772,8 +797,7 @@ impl Storage {
             return Ok(Vec::new());
         }
 
-        let json = fs::read_to_string(results_path)?;
-        let results: Vec<models::AutoEditResultMetadata> = serde_json::from_str(&json)?;
+        let results: Vec<models::AutoEditResultMetadata> = read_json(&results_path)?;
 
         tracing::debug!("Loaded {} auto-edit results", results.len());
 
@@ -809,8 +833,7 @@ impl Storage {
         }
 
         // Load existing results
-        let json = fs::read_to_string(&results_path)?;
-        let mut results: Vec<models::AutoEditResultMetadata> = serde_json::from_str(&json)?;
+        let mut results: Vec<models::AutoEditResultMetadata> = read_json(&results_path)?;
 
         // Find and remove the result
         let original_len = results.len();
@@ -842,9 +865,10 @@ impl Storage {
                 tracing::info!("Deleted auto-edit video file: {:?}", path);
             }
 
-            // Also delete thumbnail if it exists
+            // Also delete thumbnails if they exist
             if let Ok(result) = self.load_auto_edit_result(result_id) {
-                if let Some(thumb_path) = result.thumbnail_path {
+                let thumbnails = [result.thumbnail_path, result.upload_thumbnail_path];
+                for thumb_path in thumbnails.into_iter().flatten() {
                     let thumb = PathBuf::from(&thumb_path);
                     if thumb.exists() {
                         fs::remove_file(&thumb)?;
@@ -856,7 +880,11 @@ impl Storage {
 
         // Save updated results
         let json = serde_json::to_string_pretty(&results)?;
-        fs::write(results_path, json)?;
+        write_atomic(&results_path, json)?;
+
+        if let Err(e) = self.delete_upload_draft(result_id) {
+            tracing::warn!("Failed to delete upload draft of {}: {}", result_id, e);
+        }
 
         tracing::info!("Deleted auto-edit result: {}", result_id);
 
@@ -881,8 +909,7 @@ impl Storage {
         }
 
         // Load existing results
-        let json = fs::read_to_string(&results_path)?;
-        let mut results: Vec<models::AutoEditResultMetadata> = serde_json::from_str(&json)?;
+        let mut results: Vec<models::AutoEditResultMetadata> = read_json(&results_path)?;
 
         // Find and update the result
         let mut found = false;
@@ -903,7 +930,7 @@ impl Storage {
 
         // Save updated results
         let json = serde_json::to_string_pretty(&results)?;
-        fs::write(results_path, json)?;
+        write_atomic(&results_path, json)?;
 
         tracing::info!(
             "Updated YouTube status for result {}: {:?}",
@@ -913,6 +940,82 @@ impl Storage {
 
         Ok(())
     }
+
+    /// Set the thumbnail YouTube uploads of an auto-edit result use
+    pub fn set_auto_edit_upload_thumbnail(&self, result_id: &str, path: &Path) -> Result<()> {
+        let results_path = self.base_path.join("auto_edit_results.json");
+
+        let mut results: Vec<models::AutoEditResultMetadata> = if results_path.exists() {
+            read_json(&results_path)?
+        } else {
+            Vec::new()
+        };
+
+        let result = results
+            .iter_mut()
+            .find(|r| r.result_id == result_id)
+            .ok_or_else(|| {
+                StorageError::Io(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("Auto-edit result not found: {}", result_id),
+                ))
+            })?;
+        result.upload_thumbnail_path = Some(path.to_string_lossy().to_string());
+
+        let json = serde_json::to_string_pretty(&results)?;
+        write_atomic(&results_path, json)?;
+
+        tracing::info!("Set upload thumbnail for result {}: {:?}", result_id, path);
+
+        Ok(())
+    }
+
+    // ========================================================================
+    // Upload Drafts
+    // ========================================================================
+
+    /// Save the upload draft of an auto-edit result, replacing the previous one
+    pub fn save_upload_draft(&self, result_id: &str, draft: &models::UploadDraft) -> Result<()> {
+        let mut drafts = self.load_upload_drafts()?;
+        drafts.insert(result_id.to_string(), draft.clone());
+        self.save_upload_drafts(&drafts)?;
+
+        tracing::info!("Saved upload draft for result {}", result_id);
+
+        Ok(())
+    }
+
+    /// Load the upload draft of an auto-edit result (if one was saved)
+    pub fn load_upload_draft(&self, result_id: &str) -> Result<Option<models::UploadDraft>> {
+        Ok(self.load_upload_drafts()?.remove(result_id))
+    }
+
+    /// Delete the upload draft of an auto-edit result (if there is one)
+    pub fn delete_upload_draft(&self, result_id: &str) -> Result<()> {
+        let mut drafts = self.load_upload_drafts()?;
+        if drafts.remove(result_id).is_some() {
+            self.save_upload_drafts(&drafts)?;
+            tracing::info!("Deleted upload draft for result {}", result_id);
+        }
+
+        Ok(())
+    }
+
+    /// Upload drafts by result ID
+    fn load_upload_drafts(&self) -> Result<BTreeMap<String, models::UploadDraft>> {
+        let drafts_path = self.base_path.join("upload_drafts.json");
+
+        if !drafts_path.exists() {
+            return Ok(BTreeMap::new());
+        }
+
+        read_json(&drafts_path)
+    }
+
+    fn save_upload_drafts(&self, drafts: &BTreeMap<String, models::UploadDraft>) -> Result<()> {
+        let json = serde_json::to_string_pretty(drafts)?;
+        write_atomic(&self.base_path.join("upload_drafts.json"), json)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -922,6 +1025,85 @@ pub struct StorageStats {
     pub total_size_bytes: u64,
 }
 
+/// Write a file without leaving it half-written if the app crashes
+///
+/// The contents go to a temp file that is synced and then renamed over
+/// `path`. The previous version is kept as a `.bak` copy (if it was valid
+/// JSON) for [`read_json`] to fall back to.
+pub(crate) fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> Result<()> {
+    let tmp_path = suffixed_path(path, "tmp");
+    {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(contents.as_ref())?;
+        file.sync_all()?;
+    }
+
+    // Only keep backups that can actually be restored
+    if let Ok(current) = fs::read(path) {
+        if serde_json::from_slice::<serde_json::Value>(&current).is_ok() {
+            fs::write(backup_path(path), current)?;
+        }
+    }
+
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// Read a JSON file, restoring it from its `.bak` copy if it's corrupt
+pub(crate) fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let json = fs::read_to_string(path)?;
+
+    match serde_json::from_str(&json) {
+        Ok(value) => Ok(value),
+        Err(e) => {
+            let backup = fs::read_to_string(backup_path(path))
+                .ok()
+                .and_then(|json| Some((serde_json::from_str(&json).ok()?, json)));
+            let Some((value, backup_json)) = backup else {
+                return Err(e.into());
+            };
+
+            tracing::warn!("Corrupt metadata {:?} ({}), restoring from backup", path, e);
+            write_atomic(path, backup_json)?;
+            Ok(value)
+        }
+    }
+}
+
+/// Rename, or copy and delete when the destination is on another drive
+fn move_file(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).is_err() {
+        fs::copy(from, to)?;
+        fs::remove_file(from)?;
+    }
+    Ok(())
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+    suffixed_path(path, "bak")
+}
+
+/// `path` with `.suffix` appended (`clips.json` -> `clips.json.bak`)
+fn suffixed_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Overlap of two time windows as a fraction of the shorter one
+///
+/// Empty windows never overlap.
+pub fn window_overlap(a: (f64, f64), b: (f64, f64)) -> f64 {
+    let shorter = (a.1 - a.0).min(b.1 - b.0);
+    if shorter <= 0.0 {
+        return 0.0;
+    }
+
+    let overlap = a.1.min(b.1) - a.0.max(b.0);
+    (overlap / shorter).clamp(0.0, 1.0)
+}
+
 /// Canvas template metadata for listing
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CanvasTemplateInfo {
@@ -930,10 +1112,51 @@ pub struct CanvasTemplateInfo {
     pub element_count: usize,
 }
 
+/// Fixtures shared by the storage tests
+#[cfg(test)]
+mod test_support {
+    use super::batch::ClipRef;
+    use super::models::{ClipMetadata, EventType};
+    use super::{ClipMetadataV2, Storage};
+    use std::fs;
+
+    /// Write `contents` to `clips/<name>` and save it as a kill clip of
+    /// `game_id` created `age` ago
+    pub fn save_clip(
+        storage: &Storage,
+        game_id: &str,
+        name: &str,
+        contents: &[u8],
+        age: chrono::Duration,
+    ) -> ClipRef {
+        let clips_dir = storage.base_path().join("clips");
+        fs::create_dir_all(&clips_dir).unwrap();
+        let file_path = clips_dir.join(name).to_string_lossy().to_string();
+        fs::write(&file_path, contents).unwrap();
+
+        let clip = ClipMetadataV2::from(ClipMetadata {
+            file_path: file_path.clone(),
+            thumbnail_path: None,
+            event_type: EventType::ChampionKill,
+            event_time: 100.0,
+            priority: 1,
+            duration: 20.0,
+            created_at: chrono::Utc::now() - age,
+        });
+        storage.save_clip_metadata_v2(game_id, &clip).unwrap();
+
+        ClipRef {
+            game_id: game_id.to_string(),
+            file_path,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use chrono::Utc;
+    use models::EventType;
 
     #[test]
     fn test_storage_creation() {
@@ -958,6 +1181,7 @@ mod tests {
             end_time: None,
             result: None,
             kda: None,
+            patch: Some("14.20".to_string()),
         };
 
         storage.save_game_metadata("12345", &metadata).unwrap();
@@ -965,6 +1189,175 @@ mod tests {
 
         assert_eq!(loaded.game_id, "12345");
         assert_eq!(loaded.champion, "Yasuo");
+        assert_eq!(loaded.patch.as_deref(), Some("14.20"));
+
+        // Cleanup
+        let _ = fs::remove_dir_all(temp_dir);
+    }
+
+    #[test]
+    fn test_corrupt_metadata_is_restored_from_backup() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_atomic");
+        let storage = Storage::new(&temp_dir).unwrap();
+
+        let events = |id| {
+            vec![EventData {
+                event_id: id,
+                event_type: EventType::ChampionKill,
+                timestamp: 100.0,
+                priority: 1,
+                participants: vec![],
+                details: None,
+            }]
+        };
+        storage.save_events("game", &events(1)).unwrap();
+        storage.save_events("game", &events(2)).unwrap();
+
+        let events_path = storage.game_path("game").join("events.json");
+        assert!(!suffixed_path(&events_path, "tmp").exists());
+        assert!(backup_path(&events_path).exists());
+
+        // Simulate a crash mid-write
+        fs::write(&events_path, "[{\"event_id\": 2, \"eve").unwrap();
+
+        let loaded = storage.load_events("game").unwrap();
+        assert_eq!(loaded[0].event_id, 1);
+        // The restored file is valid again
+        assert_eq!(storage.load_events("game").unwrap()[0].event_id, 1);
+
+        // Cleanup
+        let _ = fs::remove_dir_all(temp_dir);
+    }
+
+    #[test]
+    fn test_add_events_keeps_earlier_events() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_add_events");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let storage = Storage::new(&temp_dir).unwrap();
+
+        let event = |id, timestamp| EventData {
+            event_id: id,
+            event_type: EventType::ChampionKill,
+            timestamp,
+            priority: 1,
+            participants: vec![],
+            details: None,
+        };
+        storage
+            .add_events("game", &[event(2, 200.0), event(3, 300.0)])
+            .unwrap();
+        storage
+            .add_events("game", &[event(1, 100.0), event(3, 300.0)])
+            .unwrap();
+
+        let ids: Vec<u64> = storage
+            .load_events("game")
+            .unwrap()
+            .iter()
+            .map(|e| e.event_id)
+            .collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+
+        // Cleanup
+        let _ = fs::remove_dir_all(temp_dir);
+    }
+
+    #[test]
+    fn test_clip_paths_are_stored_relative() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_relative_paths");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let storage = Storage::new(&temp_dir).unwrap();
+
+        let inside = temp_dir.join("recordings").join("clip.mp4");
+        let outside = std::env::temp_dir().join("elsewhere").join("clip.mp4");
+        let clip = |path: &Path| ClipMetadata {
+            file_path: path.to_string_lossy().to_string(),
+            thumbnail_path: None,
+            event_type: EventType::ChampionKill,
+            event_time: 100.0,
+            priority: 1,
+            duration: 13.0,
+            created_at: chrono::Utc::now(),
+        };
+
+        // Metadata written before paths were relative
+        fs::remove_file(temp_dir.join(STORAGE_INFO_FILE)).unwrap();
+        let clips_path = storage.game_path("game").join("clips.json");
+        fs::create_dir_all(storage.game_path("game")).unwrap();
+        fs::write(
+            &clips_path,
+            serde_json::to_string(&vec![clip(&inside), clip(&outside)]).unwrap(),
+        )
+        .unwrap();
+
+        let storage = Storage::new(&temp_dir).unwrap();
+        let raw: Vec<ClipMetadata> = read_json(&clips_path).unwrap();
+        assert_eq!(
+            Path::new(&raw[0].file_path),
+            Path::new("recordings").join("clip.mp4")
+        );
+        assert_eq!(raw[1].file_path, outside.to_string_lossy());
+
+        // Moving the library keeps clips resolvable
+        let moved_dir = std::env::temp_dir().join("lolshorts_test_relative_paths_moved");
+        let _ = fs::remove_dir_all(&moved_dir);
+        fs::rename(&temp_dir, &moved_dir).unwrap();
+        let storage = Storage::new(&moved_dir).unwrap();
+        let clips = storage.load_clip_metadata("game").unwrap();
+        assert_eq!(
+            Path::new(&clips[0].file_path),
+            moved_dir.join("recordings").join("clip.mp4")
+        );
+
+        // Cleanup
+        let _ = fs::remove_dir_all(moved_dir);
+    }
+
+    #[test]
+    fn test_window_overlap() {
+        assert_eq!(window_overlap((100.0, 113.0), (100.0, 113.0)), 1.0);
+        // 30s hotkey clip containing a 13s auto clip
+        assert_eq!(window_overlap((70.0, 100.0), (85.0, 98.0)), 1.0);
+        assert_eq!(window_overlap((100.0, 110.0), (105.0, 125.0)), 0.5);
+        assert_eq!(window_overlap((100.0, 110.0), (120.0, 130.0)), 0.0);
+        assert_eq!(window_overlap((100.0, 100.0), (90.0, 110.0)), 0.0);
+    }
+
+    #[test]
+    fn test_find_overlapping_clip() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_overlap");
+        let storage = Storage::new(&temp_dir).unwrap();
+
+        let clip = |path: &str, start: f64, duration: f64| {
+            ClipMetadataV2::from(ClipMetadata {
+                file_path: path.to_string(),
+                thumbnail_path: None,
+                event_type: EventType::ChampionKill,
+                event_time: start,
+                priority: 1,
+                duration,
+                created_at: Utc::now(),
+            })
+        };
+        let clips_dir = temp_dir.join("videos");
+        fs::create_dir_all(&clips_dir).unwrap();
+        let auto = clip(&clips_dir.join("kill.mp4").to_string_lossy(), 90.0, 13.0);
+        storage.save_clip_metadata_v2("game", &auto).unwrap();
+
+        let hotkey = clip(&clips_dir.join("hotkey.mp4").to_string_lossy(), 75.0, 30.0);
+        let found = storage.find_overlapping_clip("game", &hotkey, 0.8).unwrap();
+        assert_eq!(found.unwrap().file_path, auto.file_path);
+
+        // A clip doesn't duplicate itself, and later plays aren't duplicates
+        assert!(storage
+            .find_overlapping_clip("game", &auto, 0.8)
+            .unwrap()
+            .is_none());
+        let later = clip(&clips_dir.join("later.mp4").to_string_lossy(), 200.0, 13.0);
+        assert!(storage
+            .find_overlapping_clip("game", &later, 0.8)
+            .unwrap()
+            .is_none());
 
         // Cleanup
         let _ = fs::remove_dir_all(temp_dir);
@@ -12,6 +12,8 @@ pub struct GameMetadata {
     pub end_time: Option<DateTime<Utc>>,
     pub result: Option<GameResult>,
     pub kda: Option<KDA>,
+    #[serde(default)]
+    pub patch: Option<String>, // "14.20"
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,6 +62,7 @@ pub enum EventType {
     BaronKill,
     Ace,
     FirstBlood,
+    Death, // Active player's death, for fails compilations
     Custom(String),
 }
 
@@ -78,6 +81,7 @@ impl EventType {
             EventType::BaronKill => 4,
             EventType::Ace => 4,
             EventType::FirstBlood => 3,
+            EventType::Death => 1,
             EventType::Custom(_) => 2,
         }
     }
@@ -95,70 +99,6 @@ pub struct ClipMetadata {
     pub created_at: DateTime<Utc>,
 }
 
-// ============================================================================
-// Auto-Edit Usage Tracking (Quota System)
-// ============================================================================
-
-/// Auto-edit usage tracking for quota enforcement
-///
-/// Tracks monthly usage to enforce:
-/// - FREE tier: 5 auto-edits per month
-/// - PRO tier: Unlimited auto-edits
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AutoEditUsage {
-    /// Month identifier (YYYY-MM format, e.g., "2025-01")
-    pub month: String,
-
-    /// Number of auto-edits used this month
-    pub usage_count: u32,
-
-    /// Last time the usage was updated
-    pub last_updated: DateTime<Utc>,
-
-    /// When this month's tracking period started
-    pub period_start: DateTime<Utc>,
-}
-
-impl Default for AutoEditUsage {
-    fn default() -> Self {
-        let now = Utc::now();
-        Self {
-            month: now.format("%Y-%m").to_string(),
-            usage_count: 0,
-            last_updated: now,
-            period_start: now,
-        }
-    }
-}
-
-impl AutoEditUsage {
-    /// Create new usage tracking for current month
-    pub fn new() -> Self {
-        Self::default()
-    }
-
-    /// Get current month identifier
-    pub fn current_month() -> String {
-        Utc::now().format("%Y-%m").to_string()
-    }
-
-    /// Check if this usage record is for the current month
-    pub fn is_current_month(&self) -> bool {
-        self.month == Self::current_month()
-    }
-
-    /// Reset usage for new month
-    pub fn reset_for_month(month: String) -> Self {
-        let now = Utc::now();
-        Self {
-            month,
-            usage_count: 0,
-            last_updated: now,
-            period_start: now,
-        }
-    }
-}
-
 // ============================================================================
 // Auto-Edit Result Storage
 // ============================================================================
@@ -183,6 +123,10 @@ pub struct AutoEditResultMetadata {
     /// Path to thumbnail (generated from video)
     pub thumbnail_path: Option<String>,
 
+    /// 1280x720 thumbnail set on YouTube uploads of this result
+    #[serde(default)]
+    pub upload_thumbnail_path: Option<String>,
+
     /// When this auto-edit was created
     pub created_at: DateTime<Utc>,
 
@@ -244,6 +188,32 @@ pub enum UploadStatus {
     Failed,
 }
 
+// ============================================================================
+// Upload Drafts
+// ============================================================================
+
+/// YouTube metadata prepared for an auto-edit result before it's uploaded
+///
+/// Text may contain placeholders such as `{champion}` or `{kda}`, filled in
+/// from the result's games when the upload starts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadDraft {
+    pub title: String,
+
+    #[serde(default)]
+    pub description: String,
+
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// public, unlisted or private
+    pub privacy_status: String,
+
+    /// When the draft was last saved
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
 // ============================================================================
 // Dashboard Statistics
 // ============================================================================
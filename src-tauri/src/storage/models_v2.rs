@@ -40,9 +40,17 @@ pub struct ClipMetadataV2 {
     // === Priority & Filtering ===
     pub priority: u8,      // 1-5 (5=pentakill)
     pub tags: Vec<String>, // ["pentakill", "yasuo", "ranked"]
+    #[serde(default)]
+    pub duplicate_of: Option<String>, // clip_id of an earlier clip of the same play
+    #[serde(default)]
+    pub score: Option<u8>, // 0-100 highlight score (see recording::scoring)
 
     // === Video Technical Details ===
     pub video_info: VideoInfo,
+    #[serde(default)]
+    pub archived_at: Option<DateTime<Utc>>, // Re-encoded for long-term storage
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dropped_segments: Vec<String>, // Corrupt buffer segments left out of the clip
 
     // === Audio Information ===
     pub audio_info: AudioInfo,
@@ -473,6 +481,25 @@ impl ClipMetadataV2 {
         }
     }
 
+    /// Match the clip to the `duration` of its file
+    ///
+    /// The clip still ends where it was saved, so a longer or shorter file
+    /// moves its start and every time within the clip.
+    pub fn fit_to_duration(&mut self, duration: f64) {
+        let shift = duration - self.clip_duration;
+        self.clip_duration = duration;
+        self.game_time_start = self.game_time_end - duration;
+        self.event_window.pre_duration = (self.event_window.pre_duration + shift).max(0.0);
+
+        self.primary_event.clip_timestamp += shift;
+        for event in &mut self.merged_events {
+            event.clip_timestamp += shift;
+        }
+        for marker in &mut self.timeline.markers {
+            marker.timestamp += shift;
+        }
+    }
+
     /// Get all events (primary + merged) sorted by timestamp
     pub fn get_all_events(&self) -> Vec<&EventInfo> {
         let mut events = vec![&self.primary_event];
@@ -500,6 +527,10 @@ impl From<super::models::ClipMetadata> for ClipMetadataV2 {
     fn from(old: super::models::ClipMetadata) -> Self {
         let clip_id = Self::generate_clip_id(&old.file_path);
         let game_id = Self::extract_game_id(&old.file_path);
+        let tags = match old.event_type {
+            super::models::EventType::Death => vec!["death".to_string()],
+            _ => vec![],
+        };
 
         ClipMetadataV2 {
             clip_id,
@@ -536,10 +567,14 @@ impl From<super::models::ClipMetadata> for ClipMetadataV2 {
             },
 
             priority: old.priority,
-            tags: vec![],
+            score: None,
+            tags,
+            duplicate_of: None,
 
             // Fill with defaults for missing data
             video_info: VideoInfo::default(),
+            archived_at: None,
+            dropped_segments: vec![],
             audio_info: AudioInfo::default(),
             timeline: ClipTimeline::default(),
             game_context: GameContext::default(),
@@ -593,6 +628,20 @@ mod tests {
         assert_eq!(clip.annotations.as_ref().unwrap().rating, Some(5));
     }
 
+    #[test]
+    fn test_fit_to_duration() {
+        let mut clip = create_test_clip();
+
+        // The file is 10s longer than asked for
+        clip.fit_to_duration(40.0);
+
+        assert_eq!(clip.clip_duration, 40.0);
+        assert_eq!(clip.game_time_start, 90.0);
+        assert_eq!(clip.game_time_end, 130.0);
+        assert_eq!(clip.event_window.pre_duration, 25.0);
+        assert_eq!(clip.primary_event.clip_timestamp, 25.0);
+    }
+
     fn create_test_clip() -> ClipMetadataV2 {
         ClipMetadataV2 {
             clip_id: "test_clip".to_string(),
@@ -625,8 +674,12 @@ mod tests {
                 post_duration: 5.0,
             },
             priority: 5,
+            score: None,
             tags: vec!["pentakill".to_string()],
+            duplicate_of: None,
             video_info: VideoInfo::default(),
+            archived_at: None,
+            dropped_segments: vec![],
             audio_info: AudioInfo::default(),
             timeline: ClipTimeline::default(),
             game_context: GameContext::default(),
@@ -0,0 +1,311 @@
+//! Moving recordings to a different folder or drive
+//!
+//! Only video files (and the sidecar files next to them) move; the JSON
+//! metadata stays under the app data folder and has its paths rewritten.
+//! Files keep their place below the category folder, so `<game_id>/`
+//! subfolders are recreated at the target. Temp segments and unrecovered
+//! crash sessions are left where they are.
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::models::AutoEditResultMetadata;
+use super::{move_file, read_json, write_atomic, Result, Storage};
+
+/// Where each category of recording lives
+#[derive(Debug, Clone, PartialEq)]
+pub struct StorageDirs {
+    /// Replay buffer folder (recovered sessions are saved here)
+    pub recordings: PathBuf,
+    pub clips: PathBuf,
+    pub auto_edits: PathBuf,
+}
+
+/// Progress of [`Storage::migrate_storage`], reported after each file
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationProgress {
+    pub files_done: usize,
+    pub files_total: usize,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+    pub current_file: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MigrationSummary {
+    pub files_moved: usize,
+    pub bytes_moved: u64,
+    /// Files left in place because something already exists at the target
+    pub skipped: Vec<String>,
+}
+
+/// A file to move and the metadata that points to it
+#[derive(Debug)]
+struct PlannedMove {
+    owner: Owner,
+    /// Video first, then sidecar files
+    files: Vec<(PathBuf, PathBuf)>,
+}
+
+#[derive(Debug)]
+enum Owner {
+    Clip { game_id: String, file_path: String },
+    AutoEdit { result_id: String },
+}
+
+impl Storage {
+    /// Move clips and auto-edit videos from `from` to `to`
+    ///
+    /// Metadata is rewritten after each clip, so an interrupted migration
+    /// leaves a consistent library split across both folders.
+    pub fn migrate_storage(
+        &self,
+        from: &StorageDirs,
+        to: &StorageDirs,
+        mut on_progress: impl FnMut(&MigrationProgress),
+    ) -> Result<MigrationSummary> {
+        let plan = self.plan_migration(from, to)?;
+        let mut summary = MigrationSummary::default();
+        let mut progress = MigrationProgress {
+            files_done: 0,
+            files_total: plan.iter().map(|m| m.files.len()).sum(),
+            bytes_done: 0,
+            bytes_total: plan
+                .iter()
+                .flat_map(|m| &m.files)
+                .filter_map(|(source, _)| fs::metadata(source).ok())
+                .map(|metadata| metadata.len())
+                .sum(),
+            current_file: String::new(),
+        };
+
+        tracing::info!(
+            "Migrating {} files ({} bytes) from {:?} to {:?}",
+            progress.files_total,
+            progress.bytes_total,
+            from,
+            to
+        );
+
+        for planned in plan {
+            let mut moved = Vec::new();
+            for (source, target) in &planned.files {
+                let size = fs::metadata(source).map(|m| m.len()).unwrap_or(0);
+                progress.current_file = source.to_string_lossy().to_string();
+
+                if target.exists() {
+                    tracing::warn!("Not migrating {:?}: {:?} already exists", source, target);
+                    summary.skipped.push(progress.current_file.clone());
+                } else {
+                    if let Some(parent) = target.parent() {
+                        fs::create_dir_all(parent)?;
+                    }
+                    move_file(source, target)?;
+                    moved.push((source.clone(), target.clone()));
+                    summary.files_moved += 1;
+                    summary.bytes_moved += size;
+                }
+
+                progress.files_done += 1;
+                progress.bytes_done += size;
+                on_progress(&progress);
+            }
+
+            if !moved.is_empty() {
+                self.rewrite_paths(&planned.owner, &moved)?;
+            }
+        }
+
+        tracing::info!(
+            "Storage migration complete: {} files moved, {} skipped",
+            summary.files_moved,
+            summary.skipped.len()
+        );
+
+        Ok(summary)
+    }
+
+    fn plan_migration(&self, from: &StorageDirs, to: &StorageDirs) -> Result<Vec<PlannedMove>> {
+        let mut plan = Vec::new();
+
+        for game_id in self.list_games()? {
+            for clip in self.load_clip_metadata(&game_id)? {
+                let video = PathBuf::from(&clip.file_path);
+                let (source_dir, target_dir) = if video.starts_with(&from.clips) {
+                    (&from.clips, &to.clips)
+                } else if video.starts_with(&from.recordings) {
+                    (&from.recordings, &to.recordings)
+                } else {
+                    continue;
+                };
+                if !video.exists() || video.starts_with(target_dir) {
+                    continue;
+                }
+
+                let files = [
+                    video.clone(),
+                    video.with_extension("json"),
+                    video.with_extension("jpg"),
+                    crate::recording::facecam_path(&video),
+                ]
+                .into_iter()
+                .filter(|path| path.exists())
+                .filter_map(|path| {
+                    let target = target_dir.join(path.strip_prefix(source_dir).ok()?);
+                    Some((path, target))
+                })
+                .collect();
+
+                plan.push(PlannedMove {
+                    owner: Owner::Clip {
+                        game_id: game_id.clone(),
+                        file_path: clip.file_path,
+                    },
+                    files,
+                });
+            }
+        }
+
+        for result in self.load_auto_edit_results()? {
+            let video = PathBuf::from(&result.output_path);
+            if !video.exists() || video.starts_with(&to.auto_edits) {
+                continue;
+            }
+            let target = match video.strip_prefix(&from.auto_edits) {
+                Ok(relative) => to.auto_edits.join(relative),
+                Err(_) => match video.file_name() {
+                    Some(name) => to.auto_edits.join(name),
+                    None => continue,
+                },
+            };
+
+            plan.push(PlannedMove {
+                files: vec![(video.clone(), target)],
+                owner: Owner::AutoEdit {
+                    result_id: result.result_id,
+                },
+            });
+        }
+
+        Ok(plan)
+    }
+
+    /// Point metadata at the files' new locations
+    fn rewrite_paths(&self, owner: &Owner, moved: &[(PathBuf, PathBuf)]) -> Result<()> {
+        let relocate = |path: &str| {
+            moved
+                .iter()
+                .find(|(source, _)| source == Path::new(path))
+                .map(|(_, target)| target.to_string_lossy().to_string())
+        };
+
+        match owner {
+            Owner::Clip { game_id, file_path } => {
+                let Some(new_path) = relocate(file_path) else {
+                    return Ok(()); // The video itself was skipped
+                };
+
+                let mut clips = self.load_clip_metadata(game_id)?;
+                for clip in clips.iter_mut().filter(|c| &c.file_path == file_path) {
+                    clip.file_path = new_path.clone();
+                    if let Some(thumbnail) = clip.thumbnail_path.as_deref().and_then(relocate) {
+                        clip.thumbnail_path = Some(thumbnail);
+                    }
+                }
+                self.write_clip_index(game_id, &clips)?;
+
+                let json_path = Path::new(&new_path).with_extension("json");
+                if json_path.exists() {
+                    let mut clip = self.read_clip_v2(&json_path)?;
+                    clip.file_path = new_path.clone();
+                    if let Some(thumbnail) = clip.thumbnail_path.as_deref().and_then(relocate) {
+                        clip.thumbnail_path = Some(thumbnail);
+                    }
+                    self.write_clip_v2(&clip)?;
+                }
+            }
+            Owner::AutoEdit { result_id } => {
+                let results_path = self.base_path.join("auto_edit_results.json");
+                let mut results: Vec<AutoEditResultMetadata> = read_json(&results_path)?;
+                for result in results.iter_mut().filter(|r| &r.result_id == result_id) {
+                    if let Some(new_path) = relocate(&result.output_path) {
+                        result.output_path = new_path;
+                    }
+                }
+                write_atomic(&results_path, serde_json::to_string_pretty(&results)?)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::models::{ClipMetadata, EventType};
+    use crate::storage::ClipMetadataV2;
+
+    #[test]
+    fn test_migrate_storage() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_relocation");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let storage = Storage::new(temp_dir.join("data")).unwrap();
+        let from = StorageDirs {
+            recordings: temp_dir.join("data").join("recordings"),
+            clips: temp_dir.join("data").join("recordings"),
+            auto_edits: temp_dir.join("data").join("auto_edits"),
+        };
+        let to = StorageDirs {
+            recordings: temp_dir.join("d").join("recordings"),
+            clips: temp_dir.join("d").join("recordings"),
+            auto_edits: temp_dir.join("d").join("auto_edits"),
+        };
+
+        let video = from.clips.join("123").join("123_clip.mp4");
+        fs::create_dir_all(video.parent().unwrap()).unwrap();
+        fs::write(&video, b"video").unwrap();
+        fs::write(video.with_extension("jpg"), b"jpg").unwrap();
+        let clip = ClipMetadata {
+            file_path: video.to_string_lossy().to_string(),
+            thumbnail_path: Some(video.with_extension("jpg").to_string_lossy().to_string()),
+            event_type: EventType::ChampionKill,
+            event_time: 100.0,
+            priority: 1,
+            duration: 13.0,
+            created_at: chrono::Utc::now(),
+        };
+        storage
+            .save_clip_metadata_v2("123", &ClipMetadataV2::from(clip))
+            .unwrap();
+
+        let mut reports = Vec::new();
+        let summary = storage
+            .migrate_storage(&from, &to, |p| reports.push(p.files_done))
+            .unwrap();
+        assert_eq!(summary.files_moved, 3); // Video, JSON and thumbnail
+        assert_eq!(reports, vec![1, 2, 3]);
+        assert!(!video.exists());
+
+        // The game's subfolder is kept
+        let new_video = to.clips.join("123").join("123_clip.mp4");
+        let clips = storage.load_clip_metadata("123").unwrap();
+        assert_eq!(clips[0].file_path, new_video.to_string_lossy());
+        assert_eq!(
+            clips[0].thumbnail_path.as_deref(),
+            Some(new_video.with_extension("jpg").to_string_lossy().as_ref())
+        );
+        let v2 = storage
+            .load_clip_metadata_v2(&new_video.to_string_lossy())
+            .unwrap();
+        assert_eq!(v2.file_path, new_video.to_string_lossy());
+
+        // Nothing left to move
+        let summary = storage.migrate_storage(&from, &to, |_| {}).unwrap();
+        assert_eq!(summary.files_moved, 0);
+
+        // Cleanup
+        let _ = fs::remove_dir_all(temp_dir);
+    }
+}
@@ -0,0 +1,318 @@
+//! Chronological view of a game for the editor timeline
+//!
+//! Combines the game's events (events.json) with its saved clips, and works
+//! out which stretches of the game no clip covers and where clips overlap.
+//! All times are game time in seconds.
+
+use serde::{Deserialize, Serialize};
+
+use super::models::{ClipMetadata, EventData, EventType};
+use super::{Result, Storage};
+
+/// Uncovered stretches shorter than this aren't reported as gaps
+const MIN_GAP_SECS: f64 = 1.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameTimeline {
+    pub game_id: String,
+    /// Game length, or the end of the last event or clip if it isn't known
+    pub duration: f64,
+    /// Events and clips ordered by start time
+    pub entries: Vec<TimelineEntry>,
+    /// Stretches of the game not covered by any clip
+    pub gaps: Vec<TimeRange>,
+    /// Stretches covered by more than one clip
+    pub overlaps: Vec<ClipOverlap>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TimelineEntry {
+    Event {
+        event_id: u64,
+        event_type: EventType,
+        time: f64,
+        priority: u8,
+        participants: Vec<String>,
+        /// Clips the event appears in
+        clip_paths: Vec<String>,
+    },
+    Clip {
+        file_path: String,
+        thumbnail_path: Option<String>,
+        event_type: EventType,
+        start: f64,
+        end: f64,
+        priority: u8,
+        score: Option<u8>,
+        tags: Vec<String>,
+    },
+}
+
+impl TimelineEntry {
+    fn start(&self) -> f64 {
+        match self {
+            TimelineEntry::Event { time, .. } => *time,
+            TimelineEntry::Clip { start, .. } => *start,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TimeRange {
+    pub start: f64,
+    pub end: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClipOverlap {
+    pub start: f64,
+    pub end: f64,
+    /// File paths of the two overlapping clips
+    pub clips: (String, String),
+}
+
+impl Storage {
+    /// Timeline of a game's events and clips
+    pub fn game_timeline(&self, game_id: &str) -> Result<GameTimeline> {
+        let events = self.load_events(game_id).unwrap_or_default();
+
+        // Prefer the V2 metadata for its tags and score
+        let clips = self
+            .load_clip_metadata(game_id)?
+            .into_iter()
+            .map(|clip| match self.load_clip_metadata_v2(&clip.file_path) {
+                Ok(v2) => TimelineEntry::Clip {
+                    file_path: clip.file_path,
+                    thumbnail_path: v2.thumbnail_path.or(clip.thumbnail_path),
+                    event_type: clip.event_type,
+                    start: v2.game_time_start,
+                    end: v2.game_time_end,
+                    priority: v2.priority,
+                    score: v2.score,
+                    tags: v2.tags,
+                },
+                Err(_) => clip_entry(clip),
+            })
+            .collect();
+
+        let game_length = self.load_game_metadata(game_id).ok().and_then(|game| {
+            let end = game.end_time?;
+            Some((end - game.start_time).num_milliseconds() as f64 / 1000.0)
+        });
+
+        Ok(build_timeline(game_id, events, clips, game_length))
+    }
+}
+
+fn clip_entry(clip: ClipMetadata) -> TimelineEntry {
+    TimelineEntry::Clip {
+        file_path: clip.file_path,
+        thumbnail_path: clip.thumbnail_path,
+        event_type: clip.event_type,
+        start: clip.event_time,
+        end: clip.event_time + clip.duration,
+        priority: clip.priority,
+        score: None,
+        tags: Vec::new(),
+    }
+}
+
+/// Merge events and clip entries into a timeline
+fn build_timeline(
+    game_id: &str,
+    events: Vec<EventData>,
+    clips: Vec<TimelineEntry>,
+    game_length: Option<f64>,
+) -> GameTimeline {
+    let mut windows: Vec<(f64, f64, String)> = clips
+        .iter()
+        .filter_map(|entry| match entry {
+            TimelineEntry::Clip {
+                start,
+                end,
+                file_path,
+                ..
+            } => Some((*start, *end, file_path.clone())),
+            TimelineEntry::Event { .. } => None,
+        })
+        .collect();
+    windows.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut entries: Vec<TimelineEntry> = events
+        .into_iter()
+        .map(|event| TimelineEntry::Event {
+            clip_paths: windows
+                .iter()
+                .filter(|(start, end, _)| (*start..=*end).contains(&event.timestamp))
+                .map(|(_, _, path)| path.clone())
+                .collect(),
+            event_id: event.event_id,
+            event_type: event.event_type,
+            time: event.timestamp,
+            priority: event.priority,
+            participants: event.participants,
+        })
+        .chain(clips)
+        .collect();
+    entries.sort_by(|a, b| a.start().total_cmp(&b.start()));
+
+    let last_end = entries
+        .iter()
+        .map(|entry| match entry {
+            TimelineEntry::Event { time, .. } => *time,
+            TimelineEntry::Clip { end, .. } => *end,
+        })
+        .fold(0.0, f64::max);
+    let duration = game_length.unwrap_or(last_end).max(last_end);
+
+    let mut overlaps = Vec::new();
+    for (i, a) in windows.iter().enumerate() {
+        for b in &windows[i + 1..] {
+            // Sorted by start, so no later clip can overlap `a` either
+            if b.0 >= a.1 {
+                break;
+            }
+            overlaps.push(ClipOverlap {
+                start: b.0,
+                end: a.1.min(b.1),
+                clips: (a.2.clone(), b.2.clone()),
+            });
+        }
+    }
+
+    let mut gaps = Vec::new();
+    let mut covered_until = 0.0;
+    for (start, end, _) in &windows {
+        if start - covered_until >= MIN_GAP_SECS {
+            gaps.push(TimeRange {
+                start: covered_until,
+                end: *start,
+            });
+        }
+        covered_until = f64::max(covered_until, *end);
+    }
+    if duration - covered_until >= MIN_GAP_SECS {
+        gaps.push(TimeRange {
+            start: covered_until,
+            end: duration,
+        });
+    }
+
+    GameTimeline {
+        game_id: game_id.to_string(),
+        duration,
+        entries,
+        gaps,
+        overlaps,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(event_id: u64, timestamp: f64) -> EventData {
+        EventData {
+            event_id,
+            event_type: EventType::ChampionKill,
+            timestamp,
+            priority: 1,
+            participants: vec![],
+            details: None,
+        }
+    }
+
+    fn clip(file_path: &str, start: f64, duration: f64) -> TimelineEntry {
+        clip_entry(ClipMetadata {
+            file_path: file_path.to_string(),
+            thumbnail_path: None,
+            event_type: EventType::ChampionKill,
+            event_time: start,
+            priority: 1,
+            duration,
+            created_at: chrono::Utc::now(),
+        })
+    }
+
+    #[test]
+    fn test_build_timeline() {
+        let timeline = build_timeline(
+            "game",
+            vec![event(1, 110.0), event(2, 125.0), event(3, 400.0)],
+            vec![
+                clip("b.mp4", 120.0, 20.0),
+                clip("a.mp4", 100.0, 30.0),
+                clip("c.mp4", 300.0, 10.0),
+            ],
+            Some(600.0),
+        );
+
+        let starts: Vec<f64> = timeline.entries.iter().map(|e| e.start()).collect();
+        assert_eq!(starts, vec![100.0, 110.0, 120.0, 125.0, 300.0, 400.0]);
+        match &timeline.entries[3] {
+            TimelineEntry::Event { clip_paths, .. } => assert_eq!(clip_paths, &["a.mp4", "b.mp4"]),
+            other => panic!("Expected event, got {:?}", other),
+        }
+
+        assert_eq!(
+            timeline.overlaps,
+            vec![ClipOverlap {
+                start: 120.0,
+                end: 130.0,
+                clips: ("a.mp4".to_string(), "b.mp4".to_string()),
+            }]
+        );
+        assert_eq!(
+            timeline.gaps,
+            vec![
+                TimeRange {
+                    start: 0.0,
+                    end: 100.0
+                },
+                TimeRange {
+                    start: 140.0,
+                    end: 300.0
+                },
+                TimeRange {
+                    start: 310.0,
+                    end: 600.0
+                },
+            ]
+        );
+        assert_eq!(timeline.duration, 600.0);
+    }
+
+    #[test]
+    fn test_timeline_without_game_length() {
+        let timeline = build_timeline("game", vec![event(1, 50.0)], vec![], None);
+        assert_eq!(timeline.duration, 50.0);
+        assert_eq!(
+            timeline.gaps,
+            vec![TimeRange {
+                start: 0.0,
+                end: 50.0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_game_timeline_has_every_saved_window() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_timeline_windows");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let storage = Storage::new(&temp_dir).unwrap();
+
+        // Two clip saves, each adding its own window's events
+        storage
+            .add_events("game", &[event(1, 110.0), event(2, 125.0)])
+            .unwrap();
+        storage.add_events("game", &[event(3, 400.0)]).unwrap();
+
+        let timeline = storage.game_timeline("game").unwrap();
+        let times: Vec<f64> = timeline.entries.iter().map(|e| e.start()).collect();
+        assert_eq!(times, vec![110.0, 125.0, 400.0]);
+
+        // Cleanup
+        let _ = std::fs::remove_dir_all(temp_dir);
+    }
+}
@@ -0,0 +1,298 @@
+//! Recycle bin for deleted clips
+//!
+//! Deleting a clip moves its video, metadata and thumbnails into
+//! `<base_path>/trash/<entry_id>/` along with an `entry.json` recording where
+//! everything came from, so the clip can be restored until
+//! [`purge_expired`] (run by the cleanup manager) or `empty_trash` removes it.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::models::ClipMetadata;
+use super::{backup_path, move_file, read_json, write_atomic, Result, Storage, StorageError};
+
+const ENTRY_FILE: &str = "entry.json";
+
+/// A deleted clip waiting in the recycle bin
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub id: String,
+    pub game_id: String,
+    /// The clip's clips.json entry, if it had one
+    pub clip: Option<ClipMetadata>,
+    pub original_path: String,
+    pub trashed_at: DateTime<Utc>,
+    pub files: Vec<TrashedFile>,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedFile {
+    pub original_path: String,
+    /// File name inside the entry folder
+    pub trash_name: String,
+}
+
+impl Storage {
+    pub fn trash_dir(&self) -> PathBuf {
+        self.base_path.join("trash")
+    }
+
+    /// Move a clip and its sidecar files to the recycle bin
+    pub fn trash_clip(&self, game_id: &str, clip_path: &str) -> Result<TrashEntry> {
+        let video_path = Path::new(clip_path);
+        let clip = self
+            .load_clip_metadata(game_id)
+            .unwrap_or_default()
+            .into_iter()
+            .find(|clip| clip.file_path == clip_path);
+
+        let mut candidates = vec![
+            video_path.to_path_buf(),
+            video_path.with_extension("json"),
+            video_path.with_extension("jpg"),
+            crate::recording::facecam_path(video_path),
+        ];
+        if let Some(thumbnail) = clip.as_ref().and_then(|c| c.thumbnail_path.as_ref()) {
+            candidates.push(PathBuf::from(thumbnail));
+        }
+        candidates.dedup();
+
+        let stem = video_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("clip");
+        let trashed_at = Utc::now();
+        let id = format!("{}_{}", trashed_at.format("%Y%m%d_%H%M%S%3f"), stem);
+        let entry_dir = self.trash_dir().join(&id);
+        fs::create_dir_all(&entry_dir)?;
+
+        let mut files = Vec::new();
+        let mut size_bytes = 0;
+        for (i, path) in candidates.iter().enumerate() {
+            if !path.is_file() {
+                continue;
+            }
+
+            // Prefix with the index so a video and thumbnail can't collide
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("file");
+            let trash_name = format!("{}_{}", i, name);
+            size_bytes += fs::metadata(path)?.len();
+            move_file(path, &entry_dir.join(&trash_name))?;
+
+            files.push(TrashedFile {
+                original_path: path.to_string_lossy().to_string(),
+                trash_name,
+            });
+        }
+        let _ = fs::remove_file(backup_path(&video_path.with_extension("json")));
+
+        let entry = TrashEntry {
+            id,
+            game_id: game_id.to_string(),
+            clip,
+            original_path: clip_path.to_string(),
+            trashed_at,
+            files,
+            size_bytes,
+        };
+        write_atomic(
+            &entry_dir.join(ENTRY_FILE),
+            serde_json::to_string_pretty(&entry)?,
+        )?;
+
+        self.delete_clip_metadata(game_id, clip_path)?;
+
+        tracing::info!("Moved clip to trash: {} ({})", clip_path, entry.id);
+        Ok(entry)
+    }
+
+    /// List the recycle bin, most recently deleted first
+    pub fn list_trash(&self) -> Result<Vec<TrashEntry>> {
+        let mut entries: Vec<TrashEntry> = entry_dirs(&self.trash_dir())
+            .iter()
+            .filter_map(|dir| read_json(&dir.join(ENTRY_FILE)).ok())
+            .collect();
+
+        entries.sort_by(|a, b| b.trashed_at.cmp(&a.trashed_at));
+        Ok(entries)
+    }
+
+    /// Move a trashed clip back to where it was
+    ///
+    /// Fails without touching anything if a file now exists at one of the
+    /// original paths.
+    pub fn restore_clip(&self, entry_id: &str) -> Result<TrashEntry> {
+        let entry_dir = self.trash_dir().join(entry_id);
+        let entry_path = entry_dir.join(ENTRY_FILE);
+        if entry_id.contains(['/', '\\']) || !entry_path.exists() {
+            return Err(StorageError::TrashEntryNotFound(entry_id.to_string()));
+        }
+        let entry: TrashEntry = read_json(&entry_path)?;
+
+        if let Some(file) = entry
+            .files
+            .iter()
+            .find(|f| Path::new(&f.original_path).exists())
+        {
+            return Err(StorageError::AlreadyExists(file.original_path.clone()));
+        }
+
+        for file in &entry.files {
+            let original = Path::new(&file.original_path);
+            if let Some(parent) = original.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            move_file(&entry_dir.join(&file.trash_name), original)?;
+        }
+
+        if let Some(clip) = &entry.clip {
+            self.save_clip_metadata(&entry.game_id, clip)?;
+        }
+
+        fs::remove_dir_all(&entry_dir)?;
+
+        tracing::info!("Restored clip from trash: {}", entry.original_path);
+        Ok(entry)
+    }
+
+    /// Permanently delete everything in the recycle bin
+    ///
+    /// Returns the number of bytes freed.
+    pub fn empty_trash(&self) -> Result<u64> {
+        let mut freed = 0;
+        for dir in entry_dirs(&self.trash_dir()) {
+            freed += remove_entry(&dir)?;
+        }
+
+        tracing::info!("Emptied trash: freed {} bytes", freed);
+        Ok(freed)
+    }
+}
+
+/// Delete recycle bin entries older than `retention`
+///
+/// Returns the number of bytes freed.
+pub fn purge_expired(trash_dir: &Path, retention: Duration) -> Result<u64> {
+    let now = Utc::now();
+    let mut freed = 0;
+
+    for dir in entry_dirs(trash_dir) {
+        // Fall back to the folder time for entries without readable metadata
+        let trashed_at = read_json::<TrashEntry>(&dir.join(ENTRY_FILE))
+            .map(|entry| entry.trashed_at)
+            .or_else(|_| {
+                fs::metadata(&dir)
+                    .and_then(|m| m.modified())
+                    .map(DateTime::<Utc>::from)
+            });
+
+        let expired = trashed_at
+            .ok()
+            .and_then(|time| (now - time).to_std().ok())
+            .is_some_and(|age| age >= retention);
+        if expired {
+            freed += remove_entry(&dir)?;
+        }
+    }
+
+    Ok(freed)
+}
+
+fn entry_dirs(trash_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(trash_dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect()
+}
+
+/// Remove an entry folder, returning the bytes freed
+fn remove_entry(dir: &Path) -> Result<u64> {
+    let size = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok()?.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum();
+
+    fs::remove_dir_all(dir)?;
+    tracing::debug!("Removed trash entry: {:?}", dir);
+    Ok(size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::models::EventType;
+
+    #[test]
+    fn test_trash_and_restore_clip() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_trash");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let storage = Storage::new(&temp_dir).unwrap();
+
+        let video = temp_dir.join("recordings").join("123_clip.mp4");
+        fs::write(&video, b"video").unwrap();
+        fs::write(video.with_extension("jpg"), b"jpg").unwrap();
+        let clip = ClipMetadata {
+            file_path: video.to_string_lossy().to_string(),
+            thumbnail_path: None,
+            event_type: EventType::ChampionKill,
+            event_time: 100.0,
+            priority: 1,
+            duration: 13.0,
+            created_at: Utc::now(),
+        };
+        storage.save_clip_metadata("123", &clip).unwrap();
+
+        let entry = storage.trash_clip("123", &clip.file_path).unwrap();
+        assert_eq!(entry.files.len(), 2);
+        assert!(!video.exists());
+        assert!(storage.load_clip_metadata("123").unwrap().is_empty());
+        assert_eq!(storage.list_trash().unwrap().len(), 1);
+
+        // Something else took the clip's place
+        fs::write(&video, b"other").unwrap();
+        assert!(matches!(
+            storage.restore_clip(&entry.id),
+            Err(StorageError::AlreadyExists(_))
+        ));
+        fs::remove_file(&video).unwrap();
+
+        storage.restore_clip(&entry.id).unwrap();
+        assert_eq!(fs::read(&video).unwrap(), b"video");
+        assert!(video.with_extension("jpg").exists());
+        assert_eq!(storage.load_clip_metadata("123").unwrap().len(), 1);
+        assert!(storage.list_trash().unwrap().is_empty());
+
+        // Cleanup
+        let _ = fs::remove_dir_all(temp_dir);
+    }
+
+    #[test]
+    fn test_purge_expired() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_trash_purge");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let storage = Storage::new(&temp_dir).unwrap();
+
+        let video = temp_dir.join("recordings").join("old.mp4");
+        fs::write(&video, b"video").unwrap();
+        storage.trash_clip("123", &video.to_string_lossy()).unwrap();
+
+        let one_day = Duration::from_secs(24 * 60 * 60);
+        assert_eq!(purge_expired(&storage.trash_dir(), one_day).unwrap(), 0);
+        assert_eq!(storage.list_trash().unwrap().len(), 1);
+
+        assert!(purge_expired(&storage.trash_dir(), Duration::ZERO).unwrap() > 0);
+        assert!(storage.list_trash().unwrap().is_empty());
+
+        // Cleanup
+        let _ = fs::remove_dir_all(temp_dir);
+    }
+}
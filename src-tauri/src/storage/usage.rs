@@ -0,0 +1,175 @@
+//! Disk usage broken down by game, category and age
+//!
+//! Sizes come from the files on disk, so clips whose video is missing count
+//! as zero. Age is measured from when a clip or auto-edit was created.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{Result, Storage};
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StorageBreakdown {
+    /// Games by clip size, largest first
+    pub games: Vec<GameUsage>,
+    pub categories: CategoryUsage,
+    /// Clips and auto-edits older than 30 days (includes the 90-day bucket)
+    pub older_than_30_days: AgeBucket,
+    pub older_than_90_days: AgeBucket,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct GameUsage {
+    pub game_id: String,
+    pub clip_count: usize,
+    pub size_bytes: u64,
+}
+
+/// Bytes used by each kind of file
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CategoryUsage {
+    pub clips_bytes: u64,
+    pub auto_edits_bytes: u64,
+    /// Replay buffer segments and intermediate auto-edit files
+    pub temp_bytes: u64,
+    pub trash_bytes: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AgeBucket {
+    pub file_count: usize,
+    pub size_bytes: u64,
+}
+
+impl AgeBucket {
+    fn add(&mut self, size_bytes: u64) {
+        self.file_count += 1;
+        self.size_bytes += size_bytes;
+    }
+}
+
+impl Storage {
+    /// Break down disk usage of the library
+    ///
+    /// `temp_dirs` are the folders holding temporary files, which live
+    /// outside the storage root.
+    pub fn storage_breakdown(&self, temp_dirs: &[PathBuf]) -> Result<StorageBreakdown> {
+        let now = Utc::now();
+        let mut breakdown = StorageBreakdown::default();
+
+        for game_id in self.list_games()? {
+            let clips = self.load_clip_metadata(&game_id).unwrap_or_default();
+            let mut game = GameUsage {
+                game_id,
+                clip_count: clips.len(),
+                size_bytes: 0,
+            };
+            for clip in &clips {
+                let size = file_size(Path::new(&clip.file_path));
+                game.size_bytes += size;
+                breakdown.add_aged(now, clip.created_at, size);
+            }
+            breakdown.categories.clips_bytes += game.size_bytes;
+            breakdown.games.push(game);
+        }
+        breakdown
+            .games
+            .sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes));
+
+        for result in self.load_auto_edit_results().unwrap_or_default() {
+            let size = file_size(Path::new(&result.output_path));
+            breakdown.categories.auto_edits_bytes += size;
+            breakdown.add_aged(now, result.created_at, size);
+        }
+
+        breakdown.categories.temp_bytes = temp_dirs.iter().map(|dir| dir_size(dir)).sum();
+        breakdown.categories.trash_bytes = dir_size(&self.trash_dir());
+
+        Ok(breakdown)
+    }
+}
+
+impl StorageBreakdown {
+    fn add_aged(&mut self, now: DateTime<Utc>, created_at: DateTime<Utc>, size_bytes: u64) {
+        let age = now - created_at;
+        if age > Duration::days(30) {
+            self.older_than_30_days.add(size_bytes);
+        }
+        if age > Duration::days(90) {
+            self.older_than_90_days.add(size_bytes);
+        }
+    }
+}
+
+fn file_size(path: &Path) -> u64 {
+    fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+}
+
+/// Total size of the files under `dir` (0 if it doesn't exist)
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => dir_size(&entry.path()),
+            Ok(_) => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::test_support::save_clip;
+
+    #[test]
+    fn test_storage_breakdown() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_usage");
+        let _ = fs::remove_dir_all(&temp_dir);
+        let storage = Storage::new(&temp_dir).unwrap();
+
+        for (game_id, name, size, age_days) in [
+            ("game_a", "a1.mp4", 100, 1),
+            ("game_b", "b1.mp4", 300, 40),
+            ("game_b", "b2.mp4", 200, 120),
+        ] {
+            save_clip(
+                &storage,
+                game_id,
+                name,
+                &vec![0u8; size],
+                Duration::days(age_days),
+            );
+        }
+
+        let segments = temp_dir.join("recordings").join("temp_segments");
+        fs::create_dir_all(segments.join("nested")).unwrap();
+        fs::write(segments.join("seg_0.mp4"), vec![0u8; 50]).unwrap();
+        fs::write(segments.join("nested").join("seg_1.mp4"), vec![0u8; 25]).unwrap();
+
+        let breakdown = storage
+            .storage_breakdown(&[segments, temp_dir.join("missing")])
+            .unwrap();
+
+        let games: Vec<(&str, usize, u64)> = breakdown
+            .games
+            .iter()
+            .map(|g| (g.game_id.as_str(), g.clip_count, g.size_bytes))
+            .collect();
+        assert_eq!(games, vec![("game_b", 2, 500), ("game_a", 1, 100)]);
+        assert_eq!(breakdown.categories.clips_bytes, 600);
+        assert_eq!(breakdown.categories.temp_bytes, 75);
+        assert_eq!(breakdown.categories.trash_bytes, 0);
+        assert_eq!(breakdown.older_than_30_days.file_count, 2);
+        assert_eq!(breakdown.older_than_30_days.size_bytes, 500);
+        assert_eq!(breakdown.older_than_90_days.size_bytes, 200);
+
+        let _ = fs::remove_dir_all(&temp_dir);
+    }
+}
@@ -1,8 +1,9 @@
+use super::query::QueryBuilder;
 use super::{
     License, RefreshTokenRequest, Result, Session, SignInRequest, SignUpRequest, SupabaseError,
     SupabaseErrorResponse, SupabaseUser,
 };
-use reqwest::Client;
+use reqwest::{Client, Method, RequestBuilder};
 use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::{debug, error, info};
 
@@ -37,6 +38,9 @@ impl SupabaseConfig {
 #[derive(Debug, Clone)]
 pub struct SupabaseClient {
     client: Client,
+    /// Client for uploads, which go through the upload queue and have no
+    /// total timeout (see `crate::uploads`)
+    upload_client: Client,
     config: SupabaseConfig,
 }
 
@@ -46,8 +50,16 @@ impl SupabaseClient {
             .timeout(std::time::Duration::from_secs(30))
             .build()
             .expect("Failed to create HTTP client");
+        let upload_client = Client::builder()
+            .connect_timeout(std::time::Duration::from_secs(30))
+            .build()
+            .expect("Failed to create HTTP client");
 
-        Self { client, config }
+        Self {
+            client,
+            upload_client,
+            config,
+        }
     }
 
     pub fn from_env() -> Result<Self> {
@@ -294,206 +306,79 @@ impl SupabaseClient {
         &self.config.anon_key
     }
 
-    /// Get user's license from database
-    pub async fn get_user_license(
-        &self,
-        user_id: &str,
-        access_token: &str,
-    ) -> Result<Option<License>> {
-        let url = format!("{}/rest/v1/licenses", self.config.project_url);
-
-        let response = self
-            .client
-            .get(&url)
-            .header("apikey", &self.config.anon_key)
-            .header("Authorization", format!("Bearer {}", access_token))
-            .header("Content-Type", "application/json")
-            .query(&[("user_id", format!("eq.{}", user_id))])
-            .query(&[("select", "*")])
-            .send()
-            .await?;
-
-        if response.status().is_success() {
-            let licenses: Vec<License> = response.json().await.map_err(|e| {
-                SupabaseError::InvalidResponse(format!("Failed to parse license: {}", e))
-            })?;
-
-            Ok(licenses.into_iter().next())
-        } else {
-            let status = response.status();
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            tracing::error!("Failed to fetch license: {} - {}", status, error_text);
-            Err(SupabaseError::ApiError(format!(
-                "Failed to fetch license: {}",
-                status
-            )))
-        }
+    /// Start a typed query against a PostgREST table
+    pub fn table(&self, table: &str) -> QueryBuilder<'_> {
+        QueryBuilder::new(self, table)
     }
 
-    /// Generic database query method
-    ///
-    /// # Arguments
-    /// * `table` - The table name to query
-    /// * `select` - Columns to select (e.g., "*" or "id,name,email")
-    /// * `filters` - Query filters as (column, value) tuples (e.g., [("status", "eq.active")])
-    /// * `access_token` - User's access token for authentication
+    /// Build a REST request with `apikey` and `Authorization` headers
     ///
-    /// # Returns
-    /// JSON value containing the query results
-    pub async fn query(
+    /// Falls back to the anon key when no access token is given.
+    pub(super) fn rest_request(
         &self,
+        method: Method,
         table: &str,
-        select: &str,
-        filters: &[(&str, &str)],
-        access_token: &str,
-    ) -> Result<serde_json::Value> {
+        access_token: Option<&str>,
+    ) -> RequestBuilder {
         let url = format!("{}/rest/v1/{}", self.config.project_url, table);
+        let bearer = access_token.unwrap_or(&self.config.anon_key);
 
-        let mut request = self
-            .client
-            .get(&url)
+        self.client
+            .request(method, &url)
             .header("apikey", &self.config.anon_key)
-            .header("Authorization", format!("Bearer {}", access_token))
+            .header("Authorization", format!("Bearer {}", bearer))
             .header("Content-Type", "application/json")
-            .query(&[("select", select)]);
-
-        // Add filters
-        for (key, value) in filters {
-            request = request.query(&[(key, value)]);
-        }
-
-        let response = request.send().await?;
-
-        if response.status().is_success() {
-            let data: serde_json::Value = response.json().await.map_err(|e| {
-                error!("Failed to parse query response: {}", e);
-                SupabaseError::InvalidResponse(e.to_string())
-            })?;
-
-            debug!("Query successful on table: {}", table);
-            Ok(data)
-        } else {
-            let status = response.status();
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-
-            error!("Query failed on {}: {} - {}", table, status, error_text);
-            Err(SupabaseError::ApiError(error_text))
-        }
     }
 
-    /// Generic database insert method
-    ///
-    /// # Arguments
-    /// * `table` - The table name to insert into
-    /// * `data` - The data to insert as a JSON-serializable value
-    /// * `access_token` - User's access token for authentication
-    ///
-    /// # Returns
-    /// JSON value containing the inserted record(s)
-    pub async fn insert<T: serde::Serialize>(
+    /// Build a Storage API request (`path` is relative to `/storage/v1/`)
+    pub(super) fn storage_request(
         &self,
-        table: &str,
-        data: &T,
+        method: Method,
+        path: &str,
         access_token: &str,
-    ) -> Result<serde_json::Value> {
-        let url = format!("{}/rest/v1/{}", self.config.project_url, table);
+    ) -> RequestBuilder {
+        let url = format!("{}/storage/v1/{}", self.config.project_url, path);
 
-        let response = self
-            .client
-            .post(&url)
+        self.client
+            .request(method, &url)
             .header("apikey", &self.config.anon_key)
             .header("Authorization", format!("Bearer {}", access_token))
-            .header("Content-Type", "application/json")
-            .header("Prefer", "return=representation") // Return inserted data
-            .json(data)
-            .send()
-            .await?;
+    }
 
-        if response.status().is_success() {
-            let result: serde_json::Value = response.json().await.map_err(|e| {
-                error!("Failed to parse insert response: {}", e);
-                SupabaseError::InvalidResponse(e.to_string())
-            })?;
+    /// Build a Storage API upload request (`path` is relative to
+    /// `/storage/v1/`), without the total timeout of other requests
+    pub(super) fn storage_upload_request(&self, path: &str, access_token: &str) -> RequestBuilder {
+        let url = format!("{}/storage/v1/{}", self.config.project_url, path);
 
-            info!("Insert successful on table: {}", table);
-            Ok(result)
-        } else {
-            let status = response.status();
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
+        self.upload_client
+            .post(&url)
+            .header("apikey", &self.config.anon_key)
+            .header("Authorization", format!("Bearer {}", access_token))
+    }
 
-            error!("Insert failed on {}: {} - {}", table, status, error_text);
-            Err(SupabaseError::ApiError(format!(
-                "Insert failed: {}",
-                error_text
-            )))
-        }
+    /// Get user's license from database
+    pub async fn get_user_license(
+        &self,
+        user_id: &str,
+        access_token: &str,
+    ) -> Result<Option<License>> {
+        self.table("licenses")
+            .eq("user_id", user_id)
+            .auth(access_token)
+            .first()
+            .await
     }
 
-    /// Generic database update method
-    ///
-    /// # Arguments
-    /// * `table` - The table name to update
-    /// * `data` - The data to update as a JSON-serializable value
-    /// * `filters` - Query filters to identify which rows to update (e.g., [("id", "eq.123")])
-    /// * `access_token` - User's access token for authentication
+    /// Insert a row into a table
     ///
-    /// # Returns
-    /// JSON value containing the updated record(s)
-    pub async fn update<T: serde::Serialize>(
+    /// Shorthand for `table(table).auth(access_token).insert(data)`.
+    pub async fn insert<T: serde::Serialize>(
         &self,
         table: &str,
         data: &T,
-        filters: &[(&str, &str)],
         access_token: &str,
-    ) -> Result<serde_json::Value> {
-        let url = format!("{}/rest/v1/{}", self.config.project_url, table);
-
-        let mut request = self
-            .client
-            .patch(&url)
-            .header("apikey", &self.config.anon_key)
-            .header("Authorization", format!("Bearer {}", access_token))
-            .header("Content-Type", "application/json")
-            .header("Prefer", "return=representation") // Return updated data
-            .json(data);
-
-        // Add filters
-        for (key, value) in filters {
-            request = request.query(&[(key, value)]);
-        }
-
-        let response = request.send().await?;
-
-        if response.status().is_success() {
-            let result: serde_json::Value = response.json().await.map_err(|e| {
-                error!("Failed to parse update response: {}", e);
-                SupabaseError::InvalidResponse(e.to_string())
-            })?;
-
-            info!("Update successful on table: {}", table);
-            Ok(result)
-        } else {
-            let status = response.status();
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-
-            error!("Update failed on {}: {} - {}", table, status, error_text);
-            Err(SupabaseError::ApiError(format!(
-                "Update failed: {}",
-                error_text
-            )))
-        }
+    ) -> Result<Vec<serde_json::Value>> {
+        self.table(table).auth(access_token).insert(data).await
     }
 }
 
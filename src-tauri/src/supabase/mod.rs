@@ -1,6 +1,9 @@
 pub mod client;
+pub mod query;
+pub mod storage;
 
 pub use client::{SupabaseClient, SupabaseConfig};
+pub use query::QueryBuilder;
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -24,6 +27,9 @@ pub enum SupabaseError {
 
     #[error("Configuration error: {0}")]
     ConfigError(String),
+
+    #[error("Invalid query: {0}")]
+    InvalidQuery(String),
 }
 
 pub type Result<T> = std::result::Result<T, SupabaseError>;
@@ -0,0 +1,367 @@
+use super::{Result, SupabaseClient, SupabaseError};
+use crate::utils::retry::{self, retry_with_backoff, RetryOperation};
+use reqwest::{Method, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::Display;
+use std::future::Future;
+use tracing::{debug, error, info};
+
+/// Typed PostgREST query builder
+///
+/// Built from [`SupabaseClient::table`]. Filters, ordering and limits are
+/// encoded as PostgREST query parameters; the `apikey`/`Authorization`
+/// headers are added automatically (the anon key is used when no access
+/// token is set).
+///
+/// ```ignore
+/// let subscriptions: Vec<Subscription> = client
+///     .table("subscriptions")
+///     .select("id,status")
+///     .eq("user_id", &user.id)
+///     .eq("status", "active")
+///     .order("created_at", false)
+///     .auth(&user.access_token)
+///     .execute()
+///     .await?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct QueryBuilder<'a> {
+    client: &'a SupabaseClient,
+    table: String,
+    columns: String,
+    filters: Vec<(String, String)>,
+    order: Vec<String>,
+    limit: Option<usize>,
+    access_token: Option<String>,
+}
+
+impl<'a> QueryBuilder<'a> {
+    pub(super) fn new(client: &'a SupabaseClient, table: &str) -> Self {
+        Self {
+            client,
+            table: table.to_string(),
+            columns: "*".to_string(),
+            filters: Vec::new(),
+            order: Vec::new(),
+            limit: None,
+            access_token: None,
+        }
+    }
+
+    /// Columns to return (e.g. "id,name,email"), defaults to "*"
+    pub fn select(mut self, columns: &str) -> Self {
+        self.columns = columns.to_string();
+        self
+    }
+
+    /// `column = value`
+    pub fn eq(self, column: &str, value: impl Display) -> Self {
+        self.filter(column, "eq", value)
+    }
+
+    /// `column <> value`
+    pub fn neq(self, column: &str, value: impl Display) -> Self {
+        self.filter(column, "neq", value)
+    }
+
+    /// `column > value`
+    pub fn gt(self, column: &str, value: impl Display) -> Self {
+        self.filter(column, "gt", value)
+    }
+
+    /// `column >= value`
+    pub fn gte(self, column: &str, value: impl Display) -> Self {
+        self.filter(column, "gte", value)
+    }
+
+    /// `column < value`
+    pub fn lt(self, column: &str, value: impl Display) -> Self {
+        self.filter(column, "lt", value)
+    }
+
+    /// `column <= value`
+    pub fn lte(self, column: &str, value: impl Display) -> Self {
+        self.filter(column, "lte", value)
+    }
+
+    /// `column IN (values...)`
+    pub fn in_list<I>(mut self, column: &str, values: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        // Quote every value so commas and parentheses inside values are safe
+        let quoted: Vec<String> = values
+            .into_iter()
+            .map(|v| {
+                format!(
+                    "\"{}\"",
+                    v.to_string().replace('\\', "\\\\").replace('"', "\\\"")
+                )
+            })
+            .collect();
+
+        self.filters
+            .push((column.to_string(), format!("in.({})", quoted.join(","))));
+        self
+    }
+
+    /// Sort by `column` (may be called repeatedly for secondary sort keys)
+    pub fn order(mut self, column: &str, ascending: bool) -> Self {
+        let direction = if ascending { "asc" } else { "desc" };
+        self.order.push(format!("{}.{}", column, direction));
+        self
+    }
+
+    /// Maximum number of rows to return
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Authenticate as a user (or with a service token) instead of the anon key
+    pub fn auth(mut self, access_token: &str) -> Self {
+        self.access_token = Some(access_token.to_string());
+        self
+    }
+
+    /// Run the query and deserialize all returned rows
+    pub async fn execute<T: DeserializeOwned>(self) -> Result<Vec<T>> {
+        let request = self
+            .client
+            .rest_request(Method::GET, &self.table, self.access_token.as_deref())
+            .query(&self.query_params());
+
+        let rows = send_and_parse(request, "Query", &self.table).await?;
+        debug!("Query successful on table: {}", self.table);
+        Ok(rows)
+    }
+
+    /// Run the query and return the first row, if any
+    pub async fn first<T: DeserializeOwned>(self) -> Result<Option<T>> {
+        Ok(self.limit(1).execute().await?.into_iter().next())
+    }
+
+    /// Update all rows matching the filters, returning the updated rows
+    ///
+    /// Refuses to run without filters so a missing `.eq()` can't rewrite
+    /// the whole table.
+    pub async fn update<B: Serialize>(self, data: &B) -> Result<Vec<serde_json::Value>> {
+        if self.filters.is_empty() {
+            return Err(SupabaseError::InvalidQuery(format!(
+                "Refusing to update {} without filters",
+                self.table
+            )));
+        }
+
+        let request = self
+            .client
+            .rest_request(Method::PATCH, &self.table, self.access_token.as_deref())
+            .header("Prefer", "return=representation") // Return updated data
+            .query(&self.filter_params())
+            .json(data);
+
+        let rows = send_and_parse(request, "Update", &self.table).await?;
+        info!("Update successful on table: {}", self.table);
+        Ok(rows)
+    }
+
+    /// Insert one row (or an array of rows), returning the inserted rows
+    pub async fn insert<B: Serialize>(self, data: &B) -> Result<Vec<serde_json::Value>> {
+        let request = self
+            .client
+            .rest_request(Method::POST, &self.table, self.access_token.as_deref())
+            .header("Prefer", "return=representation") // Return inserted data
+            .json(data);
+
+        let rows = send_and_parse(request, "Insert", &self.table).await?;
+        info!("Insert successful on table: {}", self.table);
+        Ok(rows)
+    }
+
+    fn filter(mut self, column: &str, operator: &str, value: impl Display) -> Self {
+        self.filters
+            .push((column.to_string(), format!("{}.{}", operator, value)));
+        self
+    }
+
+    fn filter_params(&self) -> Vec<(String, String)> {
+        self.filters.clone()
+    }
+
+    /// All query parameters for a read (select, filters, order, limit)
+    fn query_params(&self) -> Vec<(String, String)> {
+        let mut params = vec![("select".to_string(), self.columns.clone())];
+        params.extend(self.filter_params());
+
+        if !self.order.is_empty() {
+            params.push(("order".to_string(), self.order.join(",")));
+        }
+        if let Some(limit) = self.limit {
+            params.push(("limit".to_string(), limit.to_string()));
+        }
+
+        params
+    }
+}
+
+/// Send a PostgREST request and map the response into rows or a typed error
+async fn send_and_parse<T: DeserializeOwned>(
+    request: reqwest::RequestBuilder,
+    operation: &str,
+    table: &str,
+) -> Result<Vec<T>> {
+    let response = send_with_retry(RetryOperation::Supabase, operation, || {
+        let request = request.try_clone();
+        async move {
+            let request = request.ok_or_else(|| {
+                SupabaseError::InvalidQuery("Request body can't be resent".to_string())
+            })?;
+            Ok(request.send().await?)
+        }
+    })
+    .await?;
+    let status = response.status();
+
+    if status.is_success() {
+        return response.json().await.map_err(|e| {
+            error!("Failed to parse {} response on {}: {}", operation, table, e);
+            SupabaseError::InvalidResponse(e.to_string())
+        });
+    }
+
+    let error_text = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "Unknown error".to_string());
+    error!(
+        "{} failed on {}: {} - {}",
+        operation, table, status, error_text
+    );
+
+    Err(map_error(status, operation, &error_text))
+}
+
+/// Run `send` with the retry settings for `operation`
+///
+/// Network errors, 5xx and 429 responses are retried; any other response is
+/// returned for the caller to handle.
+pub(super) async fn send_with_retry<F, Fut>(
+    operation: RetryOperation,
+    operation_name: &str,
+    mut send: F,
+) -> Result<Response>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Response>>,
+{
+    retry_with_backoff(retry::configured(operation), operation_name, || {
+        let response = send();
+        async move {
+            let response = response.await?;
+            let status = response.status();
+            if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+                let body = response.text().await.unwrap_or_default();
+                return Err(map_error(status, operation_name, &body));
+            }
+            Ok(response)
+        }
+    })
+    .await
+    .map_err(|e| SupabaseError::ApiError(format!("{:#}", e)))
+}
+
+/// Map a failed PostgREST response to a `SupabaseError`
+///
+/// PostgREST returns `{ "message": ..., "code": ..., "hint": ... }`; the
+/// message is surfaced when present, otherwise the raw body.
+pub(super) fn map_error(status: reqwest::StatusCode, operation: &str, body: &str) -> SupabaseError {
+    let message = serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| v.get("message").and_then(|m| m.as_str()).map(String::from))
+        .unwrap_or_else(|| body.to_string());
+
+    match status {
+        reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => {
+            SupabaseError::Unauthorized(message)
+        }
+        _ => SupabaseError::ApiError(format!("{} failed ({}): {}", operation, status, message)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::supabase::SupabaseConfig;
+
+    fn client() -> SupabaseClient {
+        SupabaseClient::new(SupabaseConfig::new(
+            "https://example.supabase.co".to_string(),
+            "test-anon-key".to_string(),
+        ))
+    }
+
+    #[test]
+    fn test_query_params() {
+        let client = client();
+        let query = client
+            .table("subscriptions")
+            .select("id,status")
+            .eq("user_id", "abc")
+            .gt("amount", 1000)
+            .order("created_at", false)
+            .limit(5);
+
+        assert_eq!(
+            query.query_params(),
+            vec![
+                ("select".to_string(), "id,status".to_string()),
+                ("user_id".to_string(), "eq.abc".to_string()),
+                ("amount".to_string(), "gt.1000".to_string()),
+                ("order".to_string(), "created_at.desc".to_string()),
+                ("limit".to_string(), "5".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_in_list_quotes_values() {
+        let client = client();
+        let query = client
+            .table("payments")
+            .in_list("status", ["completed", "a,b"]);
+
+        assert_eq!(
+            query.filter_params(),
+            vec![(
+                "status".to_string(),
+                "in.(\"completed\",\"a,b\")".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_map_error() {
+        let error = map_error(
+            reqwest::StatusCode::UNAUTHORIZED,
+            "Query",
+            r#"{"message":"JWT expired","code":"PGRST301"}"#,
+        );
+        assert!(matches!(error, SupabaseError::Unauthorized(ref m) if m == "JWT expired"));
+
+        let error = map_error(reqwest::StatusCode::BAD_REQUEST, "Update", "bad request");
+        assert!(matches!(error, SupabaseError::ApiError(ref m) if m.contains("bad request")));
+    }
+
+    #[tokio::test]
+    async fn test_update_requires_filters() {
+        let client = client();
+        let result = client
+            .table("user_licenses")
+            .update(&serde_json::json!({ "tier": "PRO" }))
+            .await;
+
+        assert!(matches!(result, Err(SupabaseError::InvalidQuery(_))));
+    }
+}
@@ -0,0 +1,368 @@
+use super::query::{map_error, send_with_retry};
+use super::{Result, SupabaseClient, SupabaseError};
+use crate::utils::retry::RetryOperation;
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use reqwest::{Body, Method, StatusCode};
+use serde::Deserialize;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tracing::{debug, error};
+
+/// Object entry returned by the Storage list endpoint
+#[derive(Debug, Clone, Deserialize)]
+pub struct StorageObject {
+    pub name: String,
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Size, MIME type, etc. (`None` for folders)
+    pub metadata: Option<serde_json::Value>,
+}
+
+impl StorageObject {
+    pub fn size_bytes(&self) -> u64 {
+        self.metadata
+            .as_ref()
+            .and_then(|m| m.get("size"))
+            .and_then(|s| s.as_u64())
+            .unwrap_or(0)
+    }
+
+    /// Folders are returned as entries without metadata
+    pub fn is_folder(&self) -> bool {
+        self.metadata.is_none()
+    }
+}
+
+/// Response of the Storage sign endpoint (URL relative to `/storage/v1`)
+#[derive(Debug, Deserialize)]
+struct SignedUrlResponse {
+    #[serde(rename = "signedURL")]
+    signed_url: String,
+}
+
+/// Supabase Storage (object store) operations
+///
+/// Object paths are relative to the bucket, e.g. `<user_id>/manifest.json`.
+/// Access is governed by bucket policies, so requests always carry the
+/// user's access token.
+impl SupabaseClient {
+    /// Upload (or overwrite) an object
+    pub async fn upload_object(
+        &self,
+        bucket: &str,
+        path: &str,
+        bytes: Vec<u8>,
+        content_type: &str,
+        access_token: &str,
+    ) -> Result<()> {
+        let response = send_with_retry(RetryOperation::Upload, "Upload", || {
+            let request = self
+                .storage_request(
+                    Method::POST,
+                    &format!("object/{}/{}", bucket, path),
+                    access_token,
+                )
+                .header("Content-Type", content_type)
+                .header("x-upsert", "true")
+                .body(bytes.clone());
+            async move { Ok(request.send().await?) }
+        })
+        .await?;
+
+        let status = response.status();
+        if status.is_success() {
+            debug!("Uploaded storage object: {}/{}", bucket, path);
+            return Ok(());
+        }
+
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        error!(
+            "Upload failed for {}/{}: {} - {}",
+            bucket, path, status, error_text
+        );
+        Err(map_error(status, "Upload", &error_text))
+    }
+
+    /// Download an object, returning `None` if it doesn't exist
+    pub async fn download_object(
+        &self,
+        bucket: &str,
+        path: &str,
+        access_token: &str,
+    ) -> Result<Option<Vec<u8>>> {
+        let response = self
+            .storage_request(
+                Method::GET,
+                &format!("object/authenticated/{}/{}", bucket, path),
+                access_token,
+            )
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(Some(response.bytes().await?.to_vec()));
+        }
+
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+
+        if is_not_found(status, &error_text) {
+            return Ok(None);
+        }
+
+        error!(
+            "Download failed for {}/{}: {} - {}",
+            bucket, path, status, error_text
+        );
+        Err(map_error(status, "Download", &error_text))
+    }
+
+    /// List objects under `prefix` (a folder path without trailing slash)
+    pub async fn list_objects(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        access_token: &str,
+    ) -> Result<Vec<StorageObject>> {
+        let response = self
+            .storage_request(
+                Method::POST,
+                &format!("object/list/{}", bucket),
+                access_token,
+            )
+            .json(&serde_json::json!({
+                "prefix": prefix,
+                "limit": 1000,
+                "offset": 0,
+                "sortBy": { "column": "updated_at", "order": "desc" },
+            }))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status.is_success() {
+            return response
+                .json()
+                .await
+                .map_err(|e| SupabaseError::InvalidResponse(e.to_string()));
+        }
+
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        error!(
+            "List failed for {}/{}: {} - {}",
+            bucket, prefix, status, error_text
+        );
+        Err(map_error(status, "List", &error_text))
+    }
+
+    /// Create a signed download URL valid for `expires_in_secs`
+    ///
+    /// Returns an absolute URL that can be opened without authentication.
+    pub async fn create_signed_url(
+        &self,
+        bucket: &str,
+        path: &str,
+        expires_in_secs: u64,
+        access_token: &str,
+    ) -> Result<String> {
+        let response = self
+            .storage_request(
+                Method::POST,
+                &format!("object/sign/{}/{}", bucket, path),
+                access_token,
+            )
+            .json(&serde_json::json!({ "expiresIn": expires_in_secs }))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status.is_success() {
+            let body: SignedUrlResponse = response
+                .json()
+                .await
+                .map_err(|e| SupabaseError::InvalidResponse(e.to_string()))?;
+            return Ok(format!(
+                "{}/storage/v1{}",
+                self.project_url(),
+                body.signed_url
+            ));
+        }
+
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        error!(
+            "Signing failed for {}/{}: {} - {}",
+            bucket, path, status, error_text
+        );
+        Err(map_error(status, "Sign", &error_text))
+    }
+
+    /// Upload a file to an object, reporting `(bytes_sent, total_bytes)`
+    ///
+    /// Goes through the app's upload queue like YouTube uploads: it waits
+    /// for a slot and is sent at the queue's pace (bandwidth cap, pause). A
+    /// retried upload starts over, so progress restarts from 0.
+    pub async fn upload_file(
+        &self,
+        bucket: &str,
+        path: &str,
+        file_path: &Path,
+        content_type: &str,
+        access_token: &str,
+        on_progress: impl Fn(u64, u64) + Send + Sync + 'static,
+    ) -> Result<()> {
+        let data = bytes::Bytes::from(
+            tokio::fs::read(file_path)
+                .await
+                .map_err(|e| SupabaseError::ApiError(format!("Failed to read file: {}", e)))?,
+        );
+        let total_bytes = data.len() as u64;
+        let on_progress = Arc::new(on_progress);
+
+        crate::uploads::queue()
+            .check_bandwidth(total_bytes)
+            .map_err(SupabaseError::ApiError)?;
+        let _slot = crate::uploads::queue().acquire().await;
+
+        let response = send_with_retry(RetryOperation::Upload, "Upload", || {
+            let on_progress = Arc::clone(&on_progress);
+            let mut sent = 0u64;
+            let stream = crate::uploads::throttled_body(data.clone()).inspect(move |chunk| {
+                if let Ok(bytes) = chunk {
+                    sent += bytes.len() as u64;
+                    on_progress(sent, total_bytes);
+                }
+            });
+
+            let request = self
+                .storage_upload_request(&format!("object/{}/{}", bucket, path), access_token)
+                .header("Content-Type", content_type)
+                .header("Content-Length", total_bytes)
+                .header("x-upsert", "true")
+                .body(Body::wrap_stream(stream));
+
+            async move { Ok(request.send().await?) }
+        })
+        .await?;
+
+        let status = response.status();
+        if status.is_success() {
+            debug!("Uploaded {:?} to {}/{}", file_path, bucket, path);
+            return Ok(());
+        }
+
+        let error_text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        error!(
+            "Upload failed for {}/{}: {} - {}",
+            bucket, path, status, error_text
+        );
+        Err(map_error(status, "Upload", &error_text))
+    }
+
+    /// Stream an object to `dest`, reporting `(bytes_received, total_bytes)`
+    ///
+    /// `total_bytes` is 0 when the server doesn't send a content length.
+    pub async fn download_file(
+        &self,
+        bucket: &str,
+        path: &str,
+        dest: &Path,
+        access_token: &str,
+        on_progress: impl Fn(u64, u64),
+    ) -> Result<()> {
+        let mut response = self
+            .storage_request(
+                Method::GET,
+                &format!("object/authenticated/{}/{}", bucket, path),
+                access_token,
+            )
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error_text = response
+                .text()
+                .await
+                .unwrap_or_else(|_| "Unknown error".to_string());
+            error!(
+                "Download failed for {}/{}: {} - {}",
+                bucket, path, status, error_text
+            );
+            return Err(map_error(status, "Download", &error_text));
+        }
+
+        // Write to a temp file so a failed download never leaves a truncated clip
+        let partial = dest.with_extension("part");
+        if let Err(e) = write_download(&mut response, &partial, dest, on_progress).await {
+            let _ = tokio::fs::remove_file(&partial).await;
+            return Err(e);
+        }
+
+        debug!("Downloaded {}/{} to {:?}", bucket, path, dest);
+        Ok(())
+    }
+}
+
+/// Stream the body of `response` into `partial`, then move it to `dest`
+async fn write_download(
+    response: &mut reqwest::Response,
+    partial: &Path,
+    dest: &Path,
+    on_progress: impl Fn(u64, u64),
+) -> Result<()> {
+    let total_bytes = response.content_length().unwrap_or(0);
+    let io_error =
+        |e: std::io::Error| SupabaseError::ApiError(format!("Failed to write file: {}", e));
+
+    let mut file = tokio::fs::File::create(partial).await.map_err(io_error)?;
+
+    let mut received = 0u64;
+    while let Some(chunk) = response.chunk().await? {
+        file.write_all(&chunk).await.map_err(io_error)?;
+        received += chunk.len() as u64;
+        on_progress(received, total_bytes);
+    }
+
+    file.flush().await.map_err(io_error)?;
+    drop(file);
+    tokio::fs::rename(partial, dest).await.map_err(io_error)
+}
+
+/// Storage reports missing objects as 404, or as 400 with a "not_found" body
+fn is_not_found(status: StatusCode, body: &str) -> bool {
+    status == StatusCode::NOT_FOUND
+        || (status == StatusCode::BAD_REQUEST
+            && (body.contains("not_found") || body.contains("Object not found")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_not_found() {
+        assert!(is_not_found(StatusCode::NOT_FOUND, ""));
+        assert!(is_not_found(
+            StatusCode::BAD_REQUEST,
+            r#"{"statusCode":"404","error":"not_found","message":"Object not found"}"#
+        ));
+        assert!(!is_not_found(StatusCode::BAD_REQUEST, "invalid path"));
+        assert!(!is_not_found(StatusCode::UNAUTHORIZED, "not_found"));
+    }
+}
@@ -0,0 +1,12 @@
+use super::TelemetryStatus;
+use crate::AppState;
+use tauri::State;
+
+/// Get telemetry opt-in state, pending counters and queue health
+///
+/// Telemetry is toggled through `RecordingSettings.telemetry.enabled`.
+#[tauri::command]
+pub async fn get_telemetry_status(state: State<'_, AppState>) -> Result<TelemetryStatus, String> {
+    // FREE tier feature - no authentication required
+    Ok(state.telemetry.status().await)
+}
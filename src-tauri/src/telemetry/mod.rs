@@ -0,0 +1,457 @@
+pub mod commands;
+
+/// Anonymous usage telemetry (opt-in)
+///
+/// Counts a handful of coarse usage events (clips saved, auto-edits run,
+/// upload outcomes, active encoder) and periodically sends them to Supabase
+/// as batches. Nothing user-identifying is collected: batches carry a random
+/// installation ID that is regenerated whenever telemetry is turned off.
+///
+/// Batches are queued on disk so they survive restarts and are retried on the
+/// next flush if Supabase is unreachable.
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::supabase::SupabaseClient;
+
+/// Supabase table receiving telemetry batches
+const TELEMETRY_TABLE: &str = "telemetry_batches";
+
+/// Batches kept locally while Supabase is unreachable (oldest dropped first)
+const MAX_QUEUED_BATCHES: usize = 50;
+
+/// Give up on a batch after this many failed sends
+const MAX_SEND_ATTEMPTS: u32 = 5;
+
+/// Usage event to count
+#[derive(Debug, Clone, Copy)]
+pub enum TelemetryEvent {
+    ClipsSaved(u64),
+    AutoEditCompleted { success: bool },
+    UploadCompleted { success: bool },
+}
+
+/// Aggregated counters for one reporting period
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TelemetryCounters {
+    pub clips_saved: u64,
+    pub auto_edits_run: u64,
+    pub auto_edits_failed: u64,
+    pub uploads_attempted: u64,
+    pub uploads_succeeded: u64,
+}
+
+impl TelemetryCounters {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Fraction of uploads that succeeded (None if nothing was uploaded)
+    pub fn upload_success_rate(&self) -> Option<f64> {
+        if self.uploads_attempted == 0 {
+            None
+        } else {
+            Some(self.uploads_succeeded as f64 / self.uploads_attempted as f64)
+        }
+    }
+}
+
+/// A batch row as stored in Supabase
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryBatch {
+    pub batch_id: String,
+    pub installation_id: String,
+    pub app_version: String,
+    pub platform: String,
+    pub encoder: Option<String>,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    #[serde(flatten)]
+    pub counters: TelemetryCounters,
+    pub upload_success_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct QueuedBatch {
+    batch: TelemetryBatch,
+    attempts: u32,
+}
+
+/// On-disk telemetry state (pending counters and unsent batches)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TelemetryState {
+    installation_id: String,
+    period_start: DateTime<Utc>,
+    pending: TelemetryCounters,
+    encoder: Option<String>,
+    queue: Vec<QueuedBatch>,
+    last_flush_at: Option<DateTime<Utc>>,
+    last_error: Option<String>,
+}
+
+impl TelemetryState {
+    fn fresh() -> Self {
+        Self {
+            installation_id: uuid::Uuid::new_v4().to_string(),
+            period_start: Utc::now(),
+            pending: TelemetryCounters::default(),
+            encoder: None,
+            queue: Vec::new(),
+            last_flush_at: None,
+            last_error: None,
+        }
+    }
+
+    /// Move pending counters into a queued batch
+    fn seal_batch(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+
+        let now = Utc::now();
+        let counters = std::mem::take(&mut self.pending);
+        let batch = TelemetryBatch {
+            batch_id: uuid::Uuid::new_v4().to_string(),
+            installation_id: self.installation_id.clone(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            platform: std::env::consts::OS.to_string(),
+            encoder: self.encoder.clone(),
+            period_start: self.period_start,
+            period_end: now,
+            upload_success_rate: counters.upload_success_rate(),
+            counters,
+        };
+
+        self.period_start = now;
+        self.queue.push(QueuedBatch { batch, attempts: 0 });
+
+        if self.queue.len() > MAX_QUEUED_BATCHES {
+            let overflow = self.queue.len() - MAX_QUEUED_BATCHES;
+            self.queue.drain(..overflow);
+            warn!("Telemetry queue full, dropped {} oldest batches", overflow);
+        }
+    }
+}
+
+/// Telemetry status for the settings UI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryStatus {
+    pub enabled: bool,
+    /// Whether a Supabase endpoint is configured to receive batches
+    pub endpoint_configured: bool,
+    pub installation_id: Option<String>,
+    pub pending: TelemetryCounters,
+    pub encoder: Option<String>,
+    pub queued_batches: usize,
+    pub last_flush_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+/// Collects, queues and sends anonymous usage counters
+pub struct TelemetryManager {
+    path: PathBuf,
+    client: Option<SupabaseClient>,
+    enabled: RwLock<bool>,
+    state: RwLock<TelemetryState>,
+    /// Last recorder `clips_created` value seen (resets with the recorder)
+    last_clips_created: RwLock<u64>,
+}
+
+impl TelemetryManager {
+    /// Load the telemetry queue from `path`
+    ///
+    /// When telemetry is disabled any leftover data on disk is discarded.
+    pub fn new(
+        path: impl AsRef<Path>,
+        enabled: bool,
+        client: Option<SupabaseClient>,
+    ) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+
+        let state = if enabled && path.exists() {
+            let json = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read telemetry queue: {:?}", path))?;
+            serde_json::from_str(&json).unwrap_or_else(|e| {
+                warn!("Discarding corrupted telemetry queue: {}", e);
+                TelemetryState::fresh()
+            })
+        } else {
+            TelemetryState::fresh()
+        };
+
+        let manager = Self {
+            path,
+            client,
+            enabled: RwLock::new(enabled),
+            state: RwLock::new(state),
+            last_clips_created: RwLock::new(0),
+        };
+
+        if !enabled {
+            manager.remove_queue_file()?;
+        }
+
+        Ok(manager)
+    }
+
+    pub async fn is_enabled(&self) -> bool {
+        *self.enabled.read().await
+    }
+
+    /// Turn telemetry on or off
+    ///
+    /// Opting out drops all pending counters and unsent batches and rotates
+    /// the installation ID so later opt-ins can't be linked to earlier data.
+    pub async fn set_enabled(&self, enabled: bool) -> Result<()> {
+        let mut current = self.enabled.write().await;
+        if *current == enabled {
+            return Ok(());
+        }
+        *current = enabled;
+
+        let mut state = self.state.write().await;
+        *state = TelemetryState::fresh();
+
+        if enabled {
+            info!("Telemetry enabled");
+            self.persist(&state)
+        } else {
+            info!("Telemetry disabled, pending data discarded");
+            self.remove_queue_file()
+        }
+    }
+
+    /// Count a usage event (no-op while telemetry is disabled)
+    pub async fn record(&self, event: TelemetryEvent) {
+        if !self.is_enabled().await {
+            return;
+        }
+
+        let mut state = self.state.write().await;
+        match event {
+            TelemetryEvent::ClipsSaved(count) => state.pending.clips_saved += count,
+            TelemetryEvent::AutoEditCompleted { success } => {
+                state.pending.auto_edits_run += 1;
+                if !success {
+                    state.pending.auto_edits_failed += 1;
+                }
+            }
+            TelemetryEvent::UploadCompleted { success } => {
+                state.pending.uploads_attempted += 1;
+                if success {
+                    state.pending.uploads_succeeded += 1;
+                }
+            }
+        }
+
+        if let Err(e) = self.persist(&state) {
+            debug!("Failed to persist telemetry counters: {}", e);
+        }
+    }
+
+    /// Sync clip count and encoder from the recorder's running stats
+    pub async fn observe_recording(&self, clips_created: u64, encoder: &str) {
+        let mut last = self.last_clips_created.write().await;
+        // The counter restarts when the recorder does
+        let new_clips = if clips_created >= *last {
+            clips_created - *last
+        } else {
+            clips_created
+        };
+        *last = clips_created;
+        drop(last);
+
+        if !self.is_enabled().await {
+            return;
+        }
+
+        if new_clips > 0 {
+            self.record(TelemetryEvent::ClipsSaved(new_clips)).await;
+        }
+        self.state.write().await.encoder = Some(encoder.to_string());
+    }
+
+    /// Seal pending counters into a batch and send all queued batches
+    ///
+    /// Returns the number of batches sent. Batches that fail stay queued and
+    /// are retried on the next flush.
+    pub async fn flush(&self) -> Result<usize> {
+        if !self.is_enabled().await {
+            return Ok(0);
+        }
+
+        let mut state = self.state.write().await;
+        state.seal_batch();
+
+        let Some(client) = &self.client else {
+            state.last_error = Some("Supabase is not configured".to_string());
+            self.persist(&state)?;
+            return Ok(0);
+        };
+
+        let mut sent = 0;
+        let mut remaining = Vec::new();
+        let mut unreachable = false;
+
+        for mut queued in std::mem::take(&mut state.queue) {
+            // Don't hammer an unreachable endpoint; keep the rest for later
+            if unreachable {
+                remaining.push(queued);
+                continue;
+            }
+
+            match client
+                .insert(TELEMETRY_TABLE, &queued.batch, client.anon_key())
+                .await
+            {
+                Ok(_) => sent += 1,
+                Err(e) => {
+                    unreachable = true;
+                    queued.attempts += 1;
+                    state.last_error = Some(e.to_string());
+
+                    if queued.attempts < MAX_SEND_ATTEMPTS {
+                        remaining.push(queued);
+                    } else {
+                        warn!(
+                            "Dropping telemetry batch {} after {} attempts",
+                            queued.batch.batch_id, queued.attempts
+                        );
+                    }
+                }
+            }
+        }
+
+        state.queue = remaining;
+        state.last_flush_at = Some(Utc::now());
+        if !unreachable {
+            state.last_error = None;
+        }
+        self.persist(&state)?;
+
+        if sent > 0 {
+            debug!("Sent {} telemetry batches", sent);
+        }
+        Ok(sent)
+    }
+
+    pub async fn status(&self) -> TelemetryStatus {
+        let enabled = self.is_enabled().await;
+        let state = self.state.read().await;
+
+        TelemetryStatus {
+            enabled,
+            endpoint_configured: self.client.is_some(),
+            installation_id: enabled.then(|| state.installation_id.clone()),
+            pending: state.pending.clone(),
+            encoder: state.encoder.clone(),
+            queued_batches: state.queue.len(),
+            last_flush_at: state.last_flush_at,
+            last_error: state.last_error.clone(),
+        }
+    }
+
+    fn persist(&self, state: &TelemetryState) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let json = serde_json::to_string_pretty(state)?;
+        std::fs::write(&self.path, json)
+            .with_context(|| format!("Failed to write telemetry queue: {:?}", self.path))
+    }
+
+    fn remove_queue_file(&self) -> Result<()> {
+        if self.path.exists() {
+            std::fs::remove_file(&self.path)
+                .with_context(|| format!("Failed to remove telemetry queue: {:?}", self.path))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_disabled_records_nothing() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("telemetry.json");
+        let telemetry = TelemetryManager::new(&path, false, None).unwrap();
+
+        telemetry
+            .record(TelemetryEvent::AutoEditCompleted { success: true })
+            .await;
+
+        let status = telemetry.status().await;
+        assert!(!status.enabled);
+        assert!(status.pending.is_empty());
+        assert!(status.installation_id.is_none());
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_counters_and_success_rate() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("telemetry.json");
+        let telemetry = TelemetryManager::new(&path, true, None).unwrap();
+
+        telemetry.observe_recording(3, "hevc_nvenc").await;
+        telemetry.observe_recording(5, "hevc_nvenc").await;
+        telemetry
+            .record(TelemetryEvent::UploadCompleted { success: true })
+            .await;
+        telemetry
+            .record(TelemetryEvent::UploadCompleted { success: false })
+            .await;
+
+        let status = telemetry.status().await;
+        assert_eq!(status.pending.clips_saved, 5);
+        assert_eq!(status.pending.upload_success_rate(), Some(0.5));
+        assert_eq!(status.encoder.as_deref(), Some("hevc_nvenc"));
+    }
+
+    #[tokio::test]
+    async fn test_unsent_batches_survive_restart() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("telemetry.json");
+
+        {
+            let telemetry = TelemetryManager::new(&path, true, None).unwrap();
+            telemetry.record(TelemetryEvent::ClipsSaved(2)).await;
+
+            // No endpoint configured: the batch is sealed and kept
+            assert_eq!(telemetry.flush().await.unwrap(), 0);
+            let status = telemetry.status().await;
+            assert_eq!(status.queued_batches, 1);
+            assert!(status.pending.is_empty());
+        }
+
+        let reloaded = TelemetryManager::new(&path, true, None).unwrap();
+        assert_eq!(reloaded.status().await.queued_batches, 1);
+    }
+
+    #[tokio::test]
+    async fn test_opt_out_discards_data() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("telemetry.json");
+        let telemetry = TelemetryManager::new(&path, true, None).unwrap();
+
+        telemetry.record(TelemetryEvent::ClipsSaved(1)).await;
+        let first_id = telemetry.status().await.installation_id;
+
+        telemetry.set_enabled(false).await.unwrap();
+        assert!(!path.exists());
+
+        telemetry.set_enabled(true).await.unwrap();
+        let status = telemetry.status().await;
+        assert!(status.pending.is_empty());
+        assert_ne!(status.installation_id, first_id);
+    }
+}
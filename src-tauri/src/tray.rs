@@ -0,0 +1,287 @@
+//! System tray icon with quick actions
+//!
+//! The icon shows the recording state as a coloured dot over the app icon,
+//! and the menu offers the same actions as the hotkeys, so the app can stay
+//! in the tray while a game runs. Auto-capture can be toggled from the UI,
+//! the hotkeys or the tray, so the icon and menu are kept in sync by polling
+//! the recorder rather than by the tray's own clicks.
+
+use image::{Rgba, RgbaImage};
+use std::time::Duration;
+use tauri::image::Image;
+use tauri::menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::{TrayIcon, TrayIconBuilder};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_shell::ShellExt;
+
+use crate::recording::RecordingStatus;
+use crate::AppState;
+
+const TRAY_ID: &str = "main";
+
+const MENU_TOGGLE_AUTO_CAPTURE: &str = "toggle_auto_capture";
+const MENU_SAVE_30: &str = "save_30";
+const MENU_SAVE_60: &str = "save_60";
+const MENU_OPEN_CLIPS: &str = "open_clips";
+const MENU_SHOW: &str = "show";
+const MENU_QUIT: &str = "quit";
+
+/// How often the icon is brought in line with the recorder
+const SYNC_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What the tray icon shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayState {
+    Idle,
+    Buffering,
+    Recording,
+    Error,
+}
+
+impl From<RecordingStatus> for TrayState {
+    fn from(status: RecordingStatus) -> Self {
+        match status {
+            RecordingStatus::Idle | RecordingStatus::Paused => TrayState::Idle,
+            RecordingStatus::Buffering => TrayState::Buffering,
+            RecordingStatus::Recording | RecordingStatus::Processing => TrayState::Recording,
+            RecordingStatus::Error => TrayState::Error,
+        }
+    }
+}
+
+impl TrayState {
+    fn tooltip(&self) -> &'static str {
+        match self {
+            TrayState::Idle => "LoLShorts - Idle",
+            TrayState::Buffering => "LoLShorts - Replay buffer running",
+            TrayState::Recording => "LoLShorts - Recording",
+            TrayState::Error => "LoLShorts - Recording error",
+        }
+    }
+
+    /// Colour of the status dot (`None` = plain app icon)
+    fn color(&self) -> Option<Rgba<u8>> {
+        match self {
+            TrayState::Idle => None,
+            TrayState::Buffering => Some(Rgba([0x3B, 0x82, 0xF6, 0xFF])),
+            TrayState::Recording => Some(Rgba([0xEF, 0x44, 0x44, 0xFF])),
+            TrayState::Error => Some(Rgba([0xF5, 0x9E, 0x0B, 0xFF])),
+        }
+    }
+}
+
+/// Create the tray icon and keep it in sync with the recorder
+///
+/// Needs [`AppState`] to be managed already.
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let auto_capture = CheckMenuItem::with_id(
+        app,
+        MENU_TOGGLE_AUTO_CAPTURE,
+        "Auto-capture",
+        true,
+        false,
+        None::<&str>,
+    )?;
+    let menu = Menu::with_items(
+        app,
+        &[
+            &auto_capture,
+            &MenuItem::with_id(
+                app,
+                MENU_SAVE_30,
+                "Save last 30 seconds",
+                true,
+                None::<&str>,
+            )?,
+            &MenuItem::with_id(
+                app,
+                MENU_SAVE_60,
+                "Save last 60 seconds",
+                true,
+                None::<&str>,
+            )?,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(
+                app,
+                MENU_OPEN_CLIPS,
+                "Open clips folder",
+                true,
+                None::<&str>,
+            )?,
+            &MenuItem::with_id(app, MENU_SHOW, "Show LoLShorts", true, None::<&str>)?,
+            &PredefinedMenuItem::separator(app)?,
+            &MenuItem::with_id(app, MENU_QUIT, "Quit", true, None::<&str>)?,
+        ],
+    )?;
+
+    let base_icon = app.default_window_icon().cloned().map(Image::to_owned);
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip(TrayState::Idle.tooltip())
+        .menu(&menu)
+        .on_menu_event(handle_menu_event);
+    if let Some(icon) = &base_icon {
+        builder = builder.icon(icon.clone());
+    }
+    let tray = builder.build(app)?;
+
+    let state = app.state::<AppState>().inner().clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(SYNC_INTERVAL);
+        let mut shown = None;
+
+        loop {
+            interval.tick().await;
+
+            let tray_state = TrayState::from(state.recorder.get_state().await);
+            let monitoring = state.auto_clip_manager.is_monitoring().await;
+            if shown == Some((tray_state, monitoring)) {
+                continue;
+            }
+            shown = Some((tray_state, monitoring));
+
+            if let Err(e) = auto_capture.set_checked(monitoring) {
+                tracing::warn!("Failed to update tray menu: {}", e);
+            }
+            update_icon(&tray, base_icon.as_ref(), tray_state);
+        }
+    });
+
+    tracing::info!("System tray initialized");
+    Ok(())
+}
+
+fn update_icon(tray: &TrayIcon, base_icon: Option<&Image<'static>>, state: TrayState) {
+    if let Err(e) = tray.set_tooltip(Some(state.tooltip())) {
+        tracing::warn!("Failed to update tray tooltip: {}", e);
+    }
+
+    let Some(base_icon) = base_icon else {
+        return;
+    };
+    let icon = match state.color() {
+        Some(color) => status_icon(base_icon, color),
+        None => base_icon.clone(),
+    };
+    if let Err(e) = tray.set_icon(Some(icon)) {
+        tracing::warn!("Failed to update tray icon: {}", e);
+    }
+}
+
+/// App icon with a status dot in the bottom-right corner
+fn status_icon(base: &Image<'_>, color: Rgba<u8>) -> Image<'static> {
+    let (width, height) = (base.width(), base.height());
+    let Some(mut icon) = RgbaImage::from_raw(width, height, base.rgba().to_vec()) else {
+        return base.clone().to_owned();
+    };
+
+    let radius = (width.min(height) / 4) as i32;
+    let center = (width as i32 - radius - 1, height as i32 - radius - 1);
+    imageproc::drawing::draw_filled_circle_mut(
+        &mut icon,
+        center,
+        radius + 1,
+        Rgba([0x00, 0x00, 0x00, 0xFF]),
+    );
+    imageproc::drawing::draw_filled_circle_mut(&mut icon, center, radius, color);
+
+    Image::new_owned(icon.into_raw(), width, height)
+}
+
+fn handle_menu_event(app: &AppHandle, event: MenuEvent) {
+    let state = app.state::<AppState>().inner().clone();
+
+    match event.id().as_ref() {
+        MENU_TOGGLE_AUTO_CAPTURE => {
+            tokio::spawn(async move { toggle_auto_capture(&state).await });
+        }
+        MENU_SAVE_30 => {
+            tokio::spawn(async move { save_replay(&state, 30.0, 2).await });
+        }
+        MENU_SAVE_60 => {
+            tokio::spawn(async move { save_replay(&state, 60.0, 3).await });
+        }
+        MENU_OPEN_CLIPS => {
+            let app = app.clone();
+            tokio::spawn(async move {
+                let clips_dir = state
+                    .recording_manager
+                    .read()
+                    .await
+                    .clips_dir()
+                    .to_path_buf();
+                #[allow(deprecated)] // Superseded by the opener plugin
+                let result = app
+                    .shell()
+                    .open(clips_dir.to_string_lossy().to_string(), None);
+                if let Err(e) = result {
+                    tracing::error!("Failed to open clips folder: {}", e);
+                }
+            });
+        }
+        MENU_SHOW => show_main_window(app),
+        MENU_QUIT => app.exit(0),
+        _ => {}
+    }
+}
+
+/// Bring the main window back from the tray
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+/// Start or stop auto-capture, like the F8 hotkey
+async fn toggle_auto_capture(state: &AppState) {
+    if state.auto_clip_manager.is_monitoring().await {
+        tracing::info!("Tray: Stopping auto-capture");
+        if let Err(e) = state.auto_clip_manager.stop_event_monitoring().await {
+            tracing::error!("Failed to stop auto-capture: {}", e);
+        }
+        if let Err(e) = state.recorder.stop_replay_buffer().await {
+            tracing::error!("Failed to stop replay buffer: {}", e);
+        }
+    } else {
+        tracing::info!("Tray: Starting auto-capture");
+        if let Err(e) = state.recorder.start_replay_buffer().await {
+            tracing::error!("Failed to start replay buffer: {}", e);
+        }
+        if let Err(e) = state.auto_clip_manager.start_event_monitoring().await {
+            tracing::error!("Failed to start event monitoring: {}", e);
+        }
+    }
+}
+
+async fn save_replay(state: &AppState, seconds: f64, priority: u8) {
+    let clip_id = format!("tray_{}s", seconds as u32);
+    match state
+        .auto_clip_manager
+        .save_manual_clip(&clip_id, seconds, priority)
+        .await
+    {
+        Ok(path) => tracing::info!("Saved {}s replay to: {:?}", seconds, path),
+        Err(e) => tracing::error!("Failed to save {}s replay: {}", seconds, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_icon_keeps_size() {
+        let base = Image::new_owned(vec![0; 32 * 32 * 4], 32, 32);
+        let icon = status_icon(&base, TrayState::Recording.color().unwrap());
+
+        assert_eq!((icon.width(), icon.height()), (32, 32));
+        // Bottom-right corner is now the dot colour, top-left untouched
+        let pixel = |x: u32, y: u32| {
+            let i = ((y * 32 + x) * 4) as usize;
+            icon.rgba()[i..i + 4].to_vec()
+        };
+        assert_eq!(pixel(24, 24), vec![0xEF, 0x44, 0x44, 0xFF]);
+        assert_eq!(pixel(0, 0), vec![0, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,27 @@
+use super::UploadQueueStatus;
+use crate::utils::error::AppError;
+
+/// Hold queued uploads and stall running ones until resumed
+#[tauri::command]
+pub async fn pause_uploads() -> Result<UploadQueueStatus, AppError> {
+    // FREE tier feature - no authentication required
+    super::queue().set_paused(true);
+    Ok(super::queue().status())
+}
+
+/// Continue paused uploads
+#[tauri::command]
+pub async fn resume_uploads() -> Result<UploadQueueStatus, AppError> {
+    // FREE tier feature - no authentication required
+    super::queue().set_paused(false);
+    Ok(super::queue().status())
+}
+
+/// Running and waiting uploads, and the limits they run under
+///
+/// Limits are configured through `RecordingSettings.uploads`.
+#[tauri::command]
+pub async fn get_upload_queue_status() -> Result<UploadQueueStatus, AppError> {
+    // FREE tier feature - no authentication required
+    Ok(super::queue().status())
+}
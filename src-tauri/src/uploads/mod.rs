@@ -0,0 +1,386 @@
+pub mod commands;
+
+/// Upload queue shared by every upload platform
+///
+/// Uploading a short during a match saturates the connection and causes lag
+/// spikes, so uploads go through one queue: at most `max_concurrent` run at
+/// once, and the bytes they send share one bandwidth budget
+/// (`UploadSettings`). Pausing holds queued uploads and stalls running ones
+/// between chunks.
+///
+/// Upload requests have no total timeout, since a capped or paused upload can
+/// take any amount of time. Instead a chunk the connection doesn't take
+/// within [`CHUNK_TIMEOUT`] fails the upload (time spent throttled or paused
+/// doesn't count), and caps too low to send a video within
+/// [`MAX_THROTTLED_UPLOAD`] are rejected up front.
+use bytes::Bytes;
+use futures_util::Stream;
+use serde::Serialize;
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+use tracing::info;
+
+use crate::settings::models::UploadSettings;
+
+/// Size of the pieces an upload body is sent in (and throttled by)
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// How long the connection may take to accept one chunk
+pub const CHUNK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Longest an upload may take at the bandwidth cap
+pub const MAX_THROTTLED_UPLOAD: Duration = Duration::from_secs(12 * 60 * 60);
+
+/// Lowest bandwidth cap accepted in settings (one chunk per second)
+pub const MIN_BANDWIDTH_LIMIT_KBPS: u32 = (CHUNK_SIZE / 1024) as u32;
+
+/// Snapshot of the queue for the UI
+#[derive(Debug, Clone, Serialize)]
+pub struct UploadQueueStatus {
+    pub paused: bool,
+    pub running: usize,
+    pub waiting: usize,
+    pub max_concurrent: u32,
+    /// 0 = unlimited
+    pub bandwidth_limit_kbps: u32,
+}
+
+/// Token bucket for the bandwidth cap, holding at most one second of budget
+#[derive(Debug)]
+struct Bandwidth {
+    bytes_per_sec: u64,
+    budget: f64,
+    refilled_at: Instant,
+}
+
+impl Bandwidth {
+    fn new(limit_kbps: u32, now: Instant) -> Self {
+        let bytes_per_sec = limit_kbps as u64 * 1024;
+        Self {
+            bytes_per_sec,
+            budget: bytes_per_sec as f64,
+            refilled_at: now,
+        }
+    }
+
+    /// Spend `bytes`, returning how long to wait before sending them
+    fn take(&mut self, bytes: usize, now: Instant) -> Duration {
+        if self.bytes_per_sec == 0 {
+            return Duration::ZERO;
+        }
+
+        let rate = self.bytes_per_sec as f64;
+        let elapsed = now
+            .saturating_duration_since(self.refilled_at)
+            .as_secs_f64();
+        self.budget = (self.budget + elapsed * rate).min(rate);
+        self.refilled_at = now;
+
+        // Going into debt makes later callers wait as well
+        self.budget -= bytes as f64;
+        if self.budget < 0.0 {
+            Duration::from_secs_f64(-self.budget / rate)
+        } else {
+            Duration::ZERO
+        }
+    }
+}
+
+#[derive(Debug)]
+struct QueueState {
+    settings: UploadSettings,
+    paused: bool,
+    running: usize,
+    waiting: usize,
+    bandwidth: Bandwidth,
+}
+
+/// Concurrency slots, bandwidth budget and pause switch
+pub struct UploadQueue {
+    state: Mutex<QueueState>,
+    /// Woken on pause/resume, settings changes and finished uploads
+    changed: Notify,
+}
+
+/// Counts a caller of [`UploadQueue::acquire`] as waiting until it returns
+struct Waiting(&'static UploadQueue);
+
+impl Drop for Waiting {
+    fn drop(&mut self) {
+        self.0.lock().waiting -= 1;
+    }
+}
+
+/// A running upload's place in the queue, released on drop
+pub struct UploadSlot {
+    queue: &'static UploadQueue,
+}
+
+impl Drop for UploadSlot {
+    fn drop(&mut self) {
+        self.queue.lock().running -= 1;
+        self.queue.changed.notify_waiters();
+    }
+}
+
+impl UploadQueue {
+    fn new(settings: UploadSettings) -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                bandwidth: Bandwidth::new(settings.bandwidth_limit_kbps, Instant::now()),
+                settings,
+                paused: false,
+                running: 0,
+                waiting: 0,
+            }),
+            changed: Notify::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Wait for a free slot (and the queue to be resumed)
+    pub async fn acquire(&'static self) -> UploadSlot {
+        self.lock().waiting += 1;
+        let _waiting = Waiting(self);
+
+        loop {
+            let changed = self.changed.notified();
+            tokio::pin!(changed);
+            changed.as_mut().enable();
+
+            {
+                let mut state = self.lock();
+                let max_concurrent = state.settings.max_concurrent.max(1) as usize;
+                if !state.paused && state.running < max_concurrent {
+                    state.running += 1;
+                    return UploadSlot { queue: self };
+                }
+            }
+
+            changed.await;
+        }
+    }
+
+    /// Wait until `bytes` more can be sent under the bandwidth cap
+    pub async fn throttle(&self, bytes: usize) {
+        loop {
+            let changed = self.changed.notified();
+            tokio::pin!(changed);
+            changed.as_mut().enable();
+
+            let delay = {
+                let mut state = self.lock();
+                if state.paused {
+                    None
+                } else {
+                    Some(state.bandwidth.take(bytes, Instant::now()))
+                }
+            };
+
+            match delay {
+                Some(delay) => {
+                    if !delay.is_zero() {
+                        tokio::time::sleep(delay).await;
+                    }
+                    return;
+                }
+                None => changed.await,
+            }
+        }
+    }
+
+    pub fn set_settings(&self, settings: &UploadSettings) {
+        let mut state = self.lock();
+        if state.settings.bandwidth_limit_kbps != settings.bandwidth_limit_kbps {
+            state.bandwidth = Bandwidth::new(settings.bandwidth_limit_kbps, Instant::now());
+        }
+        state.settings = settings.clone();
+        drop(state);
+
+        self.changed.notify_waiters();
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.lock().paused = paused;
+        self.changed.notify_waiters();
+        info!("Uploads {}", if paused { "paused" } else { "resumed" });
+    }
+
+    /// Fail if `bytes` can't be sent within [`MAX_THROTTLED_UPLOAD`] at the
+    /// bandwidth cap
+    pub fn check_bandwidth(&self, bytes: u64) -> Result<(), String> {
+        let limit_kbps = self.lock().settings.bandwidth_limit_kbps;
+        if limit_kbps == 0 {
+            return Ok(());
+        }
+
+        let secs = bytes / (limit_kbps as u64 * 1024);
+        if secs > MAX_THROTTLED_UPLOAD.as_secs() {
+            return Err(format!(
+                "The upload bandwidth limit of {} KB/s is too low to upload {} MB within {} hours. Raise or remove it in the upload settings.",
+                limit_kbps,
+                bytes / (1024 * 1024),
+                MAX_THROTTLED_UPLOAD.as_secs() / 3600
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn status(&self) -> UploadQueueStatus {
+        let state = self.lock();
+        UploadQueueStatus {
+            paused: state.paused,
+            running: state.running,
+            waiting: state.waiting,
+            max_concurrent: state.settings.max_concurrent.max(1),
+            bandwidth_limit_kbps: state.settings.bandwidth_limit_kbps,
+        }
+    }
+}
+
+/// The app-wide upload queue (default settings until [`set_settings`])
+pub fn queue() -> &'static UploadQueue {
+    static QUEUE: OnceLock<UploadQueue> = OnceLock::new();
+    QUEUE.get_or_init(|| UploadQueue::new(UploadSettings::default()))
+}
+
+/// Use `settings` for the upload queue from now on
+pub fn set_settings(settings: &UploadSettings) {
+    queue().set_settings(settings);
+}
+
+/// Reject a bandwidth cap below [`MIN_BANDWIDTH_LIMIT_KBPS`]
+pub fn validate_settings(settings: &UploadSettings) -> Result<(), String> {
+    let limit = settings.bandwidth_limit_kbps;
+    if limit != 0 && limit < MIN_BANDWIDTH_LIMIT_KBPS {
+        return Err(format!(
+            "Upload bandwidth limit must be 0 (unlimited) or at least {} KB/s",
+            MIN_BANDWIDTH_LIMIT_KBPS
+        ));
+    }
+    Ok(())
+}
+
+/// `data` as a request body stream sent at the queue's pace
+pub fn throttled_body(data: Bytes) -> impl Stream<Item = std::io::Result<Bytes>> + Send {
+    paced_body(data, queue(), CHUNK_TIMEOUT)
+}
+
+/// `data` in chunks paced by `queue`, failing once a chunk waits longer than
+/// `chunk_timeout` to be taken
+///
+/// The next chunk is asked for once the previous one was written, so the
+/// time in between (less throttling and pauses) is how long it took.
+fn paced_body(
+    data: Bytes,
+    queue: &'static UploadQueue,
+    chunk_timeout: Duration,
+) -> impl Stream<Item = std::io::Result<Bytes>> + Send {
+    futures_util::stream::unfold(
+        (data, None::<Instant>),
+        move |(mut rest, sent_at)| async move {
+            if rest.is_empty() {
+                return None;
+            }
+            if sent_at.is_some_and(|sent_at| sent_at.elapsed() > chunk_timeout) {
+                let error = std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "Upload stalled: the connection stopped taking data",
+                );
+                return Some((Err(error), (Bytes::new(), None)));
+            }
+
+            let chunk = rest.split_to(rest.len().min(CHUNK_SIZE));
+            queue.throttle(chunk.len()).await;
+            Some((Ok(chunk), (rest, Some(Instant::now()))))
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bandwidth_limit() {
+        let start = Instant::now();
+        let mut bandwidth = Bandwidth::new(100, start);
+
+        // One second of burst, then wait for the debt to be paid off
+        assert_eq!(bandwidth.take(100 * 1024, start), Duration::ZERO);
+        assert_eq!(bandwidth.take(50 * 1024, start), Duration::from_millis(500));
+
+        // Budget refills with time, up to one second's worth
+        let later = start + Duration::from_secs(10);
+        assert_eq!(bandwidth.take(100 * 1024, later), Duration::ZERO);
+
+        let mut unlimited = Bandwidth::new(0, start);
+        assert_eq!(unlimited.take(usize::MAX, start), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_bandwidth_too_low_for_video() {
+        let queue = UploadQueue::new(UploadSettings {
+            bandwidth_limit_kbps: 64,
+            ..UploadSettings::default()
+        });
+
+        // 12 hours at 64 KB/s is about 2.6 GB
+        assert!(queue.check_bandwidth(100 * 1024 * 1024).is_ok());
+        assert!(queue.check_bandwidth(4 * 1024 * 1024 * 1024).is_err());
+
+        queue.set_settings(&UploadSettings::default());
+        assert!(queue.check_bandwidth(u64::MAX).is_ok());
+
+        assert!(validate_settings(&UploadSettings {
+            bandwidth_limit_kbps: 8,
+            ..UploadSettings::default()
+        })
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stalled_chunk_fails_body() {
+        use futures_util::StreamExt;
+
+        let queue: &'static UploadQueue =
+            Box::leak(Box::new(UploadQueue::new(UploadSettings::default())));
+        let data = Bytes::from(vec![0u8; CHUNK_SIZE * 2]);
+        let mut body = Box::pin(paced_body(data, queue, Duration::from_millis(10)));
+
+        assert!(body.next().await.unwrap().is_ok());
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let error = body.next().await.unwrap().unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::TimedOut);
+        assert!(body.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pause_holds_new_uploads() {
+        let queue: &'static UploadQueue =
+            Box::leak(Box::new(UploadQueue::new(UploadSettings::default())));
+
+        let slot = queue.acquire().await;
+        assert_eq!(queue.status().running, 1);
+
+        // Only one upload at a time by default
+        let second = tokio::spawn(queue.acquire());
+        tokio::task::yield_now().await;
+        assert_eq!(queue.status().waiting, 1);
+
+        queue.set_paused(true);
+        drop(slot);
+        tokio::task::yield_now().await;
+        assert_eq!(queue.status().running, 0);
+        assert!(!second.is_finished());
+
+        queue.set_paused(false);
+        let _second = second.await.unwrap();
+        assert_eq!(queue.status().running, 1);
+        assert_eq!(queue.status().waiting, 0);
+    }
+}
@@ -0,0 +1,219 @@
+//! Launch LoLShorts when the user logs in
+//!
+//! So the recorder is already running when a game starts. On Windows the
+//! app is added to the `HKCU\...\Run` registry key, on macOS a LaunchAgent
+//! is written to `~/Library/LaunchAgents`. With "start minimized" the app is
+//! launched with [`MINIMIZED_ARG`] and only shows its tray icon.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Passed by the login item when the app should start in the tray
+pub const MINIMIZED_ARG: &str = "--minimized";
+
+/// Name of the Run key value
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+const APP_NAME: &str = "LoLShorts";
+
+/// LaunchAgent label (the bundle identifier)
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+const LAUNCH_AGENT_LABEL: &str = "com.lolshorts.app";
+
+/// Add or remove the login item for the running executable
+pub fn set_enabled(enabled: bool, minimized: bool) -> Result<()> {
+    if !enabled {
+        return platform::disable();
+    }
+
+    let exe = std::env::current_exe().context("Failed to find the app executable")?;
+    platform::enable(&exe, minimized)?;
+    tracing::info!("Start on boot enabled (minimized: {})", minimized);
+    Ok(())
+}
+
+/// Whether a login item is registered
+pub fn is_enabled() -> bool {
+    platform::is_enabled()
+}
+
+/// Whether this process was started by the login item in minimized mode
+pub fn launched_minimized() -> bool {
+    std::env::args().skip(1).any(|arg| arg == MINIMIZED_ARG)
+}
+
+fn launch_args(minimized: bool) -> Vec<&'static str> {
+    if minimized {
+        vec![MINIMIZED_ARG]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Run key value: the quoted executable path followed by the arguments
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+fn run_command(exe: &Path, minimized: bool) -> String {
+    std::iter::once(format!("\"{}\"", exe.display()))
+        .chain(launch_args(minimized).into_iter().map(str::to_string))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn launch_agent_plist(exe: &Path, minimized: bool) -> String {
+    let arguments: String = std::iter::once(exe.display().to_string())
+        .chain(launch_args(minimized).into_iter().map(str::to_string))
+        .map(|arg| format!("        <string>{}</string>\n", xml_escape(&arg)))
+        .collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{}</string>
+    <key>ProgramArguments</key>
+    <array>
+{}    </array>
+    <key>RunAtLoad</key>
+    <true/>
+</dict>
+</plist>
+"#,
+        LAUNCH_AGENT_LABEL, arguments
+    )
+}
+
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::*;
+    use winreg::enums::{HKEY_CURRENT_USER, KEY_SET_VALUE};
+    use winreg::RegKey;
+
+    const RUN_KEY: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Run";
+
+    pub fn enable(exe: &Path, minimized: bool) -> Result<()> {
+        let (key, _) = RegKey::predef(HKEY_CURRENT_USER)
+            .create_subkey(RUN_KEY)
+            .context("Failed to open the Run registry key")?;
+        key.set_value(APP_NAME, &run_command(exe, minimized))
+            .context("Failed to add LoLShorts to the Run registry key")
+    }
+
+    pub fn disable() -> Result<()> {
+        let Ok(key) =
+            RegKey::predef(HKEY_CURRENT_USER).open_subkey_with_flags(RUN_KEY, KEY_SET_VALUE)
+        else {
+            return Ok(());
+        };
+        match key.delete_value(APP_NAME) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).context("Failed to remove LoLShorts from the Run registry key")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn is_enabled() -> bool {
+        RegKey::predef(HKEY_CURRENT_USER)
+            .open_subkey(RUN_KEY)
+            .and_then(|key| key.get_value::<String, _>(APP_NAME))
+            .is_ok()
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn plist_path() -> Result<PathBuf> {
+        let home = dirs::home_dir().context("Failed to find the home directory")?;
+        Ok(home
+            .join("Library/LaunchAgents")
+            .join(format!("{}.plist", LAUNCH_AGENT_LABEL)))
+    }
+
+    pub fn enable(exe: &Path, minimized: bool) -> Result<()> {
+        let path = plist_path()?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, launch_agent_plist(exe, minimized))
+            .with_context(|| format!("Failed to write {:?}", path))
+    }
+
+    pub fn disable() -> Result<()> {
+        let path = plist_path()?;
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("Failed to remove {:?}", path))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn is_enabled() -> bool {
+        plist_path().map(|path| path.exists()).unwrap_or(false)
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod platform {
+    use super::*;
+
+    pub fn enable(_exe: &Path, _minimized: bool) -> Result<()> {
+        anyhow::bail!("Start on boot isn't supported on this platform")
+    }
+
+    pub fn disable() -> Result<()> {
+        Ok(())
+    }
+
+    pub fn is_enabled() -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_run_command_quotes_path() {
+        let exe = PathBuf::from("C:\\Program Files\\LoLShorts\\lolshorts.exe");
+
+        assert_eq!(
+            run_command(&exe, false),
+            "\"C:\\Program Files\\LoLShorts\\lolshorts.exe\""
+        );
+        assert_eq!(
+            run_command(&exe, true),
+            "\"C:\\Program Files\\LoLShorts\\lolshorts.exe\" --minimized"
+        );
+    }
+
+    #[test]
+    fn test_launch_agent_plist() {
+        let plist = launch_agent_plist(
+            Path::new("/Applications/LoLShorts & Co.app/Contents/MacOS/lolshorts"),
+            true,
+        );
+
+        assert!(plist.contains("<string>com.lolshorts.app</string>"));
+        assert!(plist.contains(
+            "<string>/Applications/LoLShorts &amp; Co.app/Contents/MacOS/lolshorts</string>"
+        ));
+        assert!(plist.contains("<string>--minimized</string>"));
+        assert!(plist.contains("<key>RunAtLoad</key>"));
+    }
+}
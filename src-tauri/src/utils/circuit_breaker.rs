@@ -9,13 +9,17 @@ use anyhow::{anyhow, Result};
 /// - Closed: Normal operation, requests pass through
 /// - Open: Failure threshold exceeded, requests fail fast
 /// - HalfOpen: Testing recovery, limited requests allowed
+use parking_lot::RwLock as SyncRwLock;
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tracing::{debug, info, warn};
 
 /// Circuit breaker state
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum CircuitState {
     /// Normal operation - requests pass through
     Closed,
@@ -83,6 +87,17 @@ pub struct CircuitBreaker {
     name: String,
 }
 
+/// Snapshot of a circuit breaker for the UI
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitBreakerStatus {
+    pub name: String,
+    pub state: CircuitState,
+    pub failure_count: u32,
+    pub failure_threshold: u32,
+    /// Seconds until an open circuit lets a test request through
+    pub retry_in_secs: Option<u64>,
+}
+
 #[derive(Debug)]
 struct CircuitBreakerState {
     state: CircuitState,
@@ -170,6 +185,29 @@ impl CircuitBreaker {
         }
     }
 
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get current state, failure count and time until retry
+    pub async fn status(&self) -> CircuitBreakerStatus {
+        let state = self.state.read().await;
+        let retry_in_secs = (state.state == CircuitState::Open).then(|| {
+            self.config
+                .timeout
+                .saturating_sub(state.last_state_change.elapsed())
+                .as_secs()
+        });
+
+        CircuitBreakerStatus {
+            name: self.name.clone(),
+            state: state.state,
+            failure_count: state.failure_count,
+            failure_threshold: self.config.failure_threshold,
+            retry_in_secs,
+        }
+    }
+
     /// Get current circuit state
     pub async fn get_state(&self) -> CircuitState {
         self.state.read().await.state
@@ -284,6 +322,48 @@ impl CircuitBreaker {
     }
 }
 
+/// Circuit breakers by name, so their state can be shown and reset
+#[derive(Default)]
+pub struct CircuitBreakerRegistry {
+    breakers: SyncRwLock<BTreeMap<String, Arc<CircuitBreaker>>>,
+}
+
+impl CircuitBreakerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Track `breaker`, replacing one with the same name
+    pub fn register(&self, breaker: Arc<CircuitBreaker>) {
+        self.breakers
+            .write()
+            .insert(breaker.name().to_string(), breaker);
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<CircuitBreaker>> {
+        self.breakers.read().get(name).cloned()
+    }
+
+    /// Status of every breaker, sorted by name
+    pub async fn statuses(&self) -> Vec<CircuitBreakerStatus> {
+        let breakers: Vec<_> = self.breakers.read().values().cloned().collect();
+        let mut statuses = Vec::with_capacity(breakers.len());
+        for breaker in breakers {
+            statuses.push(breaker.status().await);
+        }
+        statuses
+    }
+
+    /// Close the named breaker so requests go through again
+    pub async fn reset(&self, name: &str) -> Result<()> {
+        let breaker = self
+            .get(name)
+            .ok_or_else(|| anyhow!("Unknown circuit breaker: {}", name))?;
+        breaker.reset().await;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -375,4 +455,27 @@ mod tests {
         assert_eq!(breaker.get_state().await, CircuitState::Closed);
         assert_eq!(breaker.get_failure_count().await, 0);
     }
+
+    #[tokio::test]
+    async fn test_registry_status_and_reset() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            ..CircuitBreakerConfig::default()
+        };
+        let registry = CircuitBreakerRegistry::new();
+        let breaker = Arc::new(CircuitBreaker::new("FFmpeg Recording", config));
+        registry.register(Arc::clone(&breaker));
+
+        let _ = breaker
+            .call(|| async { Err::<(), _>(anyhow!("Fail")) })
+            .await;
+        let statuses = registry.statuses().await;
+        assert_eq!(statuses.len(), 1);
+        assert_eq!(statuses[0].state, CircuitState::Open);
+        assert!(statuses[0].retry_in_secs.is_some());
+
+        registry.reset("FFmpeg Recording").await.unwrap();
+        assert_eq!(breaker.get_state().await, CircuitState::Closed);
+        assert!(registry.reset("Unknown").await.is_err());
+    }
 }
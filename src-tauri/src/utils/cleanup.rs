@@ -21,6 +21,9 @@ pub struct CleanupConfig {
     /// Maximum size of temp segments in MB (default: 10GB)
     pub max_temp_segments_mb: u64,
 
+    /// How long deleted clips stay in the recycle bin (default: 30 days)
+    pub trash_retention: Duration,
+
     /// Enable automatic cleanup on startup (default: true)
     pub cleanup_on_startup: bool,
 
@@ -34,6 +37,7 @@ impl Default for CleanupConfig {
             temp_file_max_age: Duration::from_secs(24 * 60 * 60), // 24 hours
             max_log_size_mb: 500,
             max_temp_segments_mb: 10 * 1024, // 10 GB
+            trash_retention: Duration::from_secs(30 * 24 * 60 * 60), // 30 days
             cleanup_on_startup: true,
             cleanup_on_shutdown: true,
         }
@@ -44,16 +48,24 @@ impl Default for CleanupConfig {
 pub struct CleanupManager {
     config: CleanupConfig,
     app_data_dir: PathBuf,
+    recordings_dir: PathBuf,
 }
 
 impl CleanupManager {
     pub fn new(app_data_dir: PathBuf, config: CleanupConfig) -> Self {
         Self {
             config,
+            recordings_dir: app_data_dir.join("recordings"),
             app_data_dir,
         }
     }
 
+    /// Replay buffer folder, if it's not the default one under the app data
+    pub fn with_recordings_dir(mut self, recordings_dir: PathBuf) -> Self {
+        self.recordings_dir = recordings_dir;
+        self
+    }
+
     /// Run startup cleanup
     ///
     /// Cleans up orphaned files from previous session crashes
@@ -67,7 +79,7 @@ impl CleanupManager {
         let mut total_freed_mb = 0;
 
         // Clean old temporary segments
-        let temp_segments_dir = self.app_data_dir.join("recordings/temp_segments");
+        let temp_segments_dir = self.recordings_dir.join("temp_segments");
         if temp_segments_dir.exists() {
             total_freed_mb += self
                 .cleanup_old_files(&temp_segments_dir, self.config.temp_file_max_age)
@@ -80,6 +92,15 @@ impl CleanupManager {
             total_freed_mb += self.enforce_log_size_limit(&logs_dir).await?;
         }
 
+        // Permanently delete clips past the recycle bin retention
+        let trash_dir = self.app_data_dir.join("trash");
+        if trash_dir.exists() {
+            total_freed_mb +=
+                crate::storage::trash::purge_expired(&trash_dir, self.config.trash_retention)?
+                    / 1024
+                    / 1024;
+        }
+
         info!("Startup cleanup complete: freed {} MB", total_freed_mb);
 
         Ok(())
@@ -96,7 +117,7 @@ impl CleanupManager {
         info!("Running shutdown cleanup...");
 
         // Clean all temporary segments (fresh start on next launch)
-        let temp_segments_dir = self.app_data_dir.join("recordings/temp_segments");
+        let temp_segments_dir = self.recordings_dir.join("temp_segments");
         if temp_segments_dir.exists() {
             self.clear_directory(&temp_segments_dir).await?;
         }
@@ -296,8 +317,144 @@ impl Drop for TempFileGuard {
 
 /// Process cleanup utilities
 pub mod process {
+    use std::collections::BTreeSet;
+    use std::fs;
+    use std::path::{Path, PathBuf};
     use std::process::Child;
-    use tracing::{debug, warn};
+    use std::sync::Mutex;
+    use tracing::{debug, info, warn};
+
+    /// FFmpeg processes started by this session, mirrored to a file so the
+    /// next session can kill any left behind by a crash
+    ///
+    /// The first line names the app process that owns the file
+    /// (`app <pid>`), so a second instance can tell it's still running.
+    struct PidFile {
+        path: PathBuf,
+        pids: BTreeSet<u32>,
+    }
+
+    static PID_FILE: Mutex<Option<PidFile>> = Mutex::new(None);
+
+    /// Marks the line holding the owning app's PID
+    const OWNER_PREFIX: &str = "app";
+
+    /// Start recording FFmpeg PIDs in `path` (replaces its contents)
+    ///
+    /// Call [`reap_stray_ffmpeg`] on the same path first, and skip this when
+    /// it reports the owner is still running.
+    pub fn set_pid_file(path: PathBuf) {
+        let file = PidFile {
+            path,
+            pids: BTreeSet::new(),
+        };
+        write_pid_file(&file);
+        *PID_FILE.lock().unwrap_or_else(|e| e.into_inner()) = Some(file);
+    }
+
+    /// Remember a spawned FFmpeg process (no-op before [`set_pid_file`])
+    pub fn track(pid: u32) {
+        update_pids(|pids| pids.insert(pid));
+    }
+
+    /// Forget an FFmpeg process once it has exited or been killed
+    pub fn untrack(pid: u32) {
+        update_pids(|pids| pids.remove(&pid));
+    }
+
+    /// Tracks a process for as long as it's alive
+    pub struct TrackedPid(Option<u32>);
+
+    impl TrackedPid {
+        pub fn new(pid: Option<u32>) -> Self {
+            if let Some(pid) = pid {
+                track(pid);
+            }
+            Self(pid)
+        }
+    }
+
+    impl Drop for TrackedPid {
+        fn drop(&mut self) {
+            if let Some(pid) = self.0 {
+                untrack(pid);
+            }
+        }
+    }
+
+    fn update_pids(update: impl FnOnce(&mut BTreeSet<u32>) -> bool) {
+        let mut guard = PID_FILE.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(file) = guard.as_mut() {
+            if update(&mut file.pids) {
+                write_pid_file(file);
+            }
+        }
+    }
+
+    fn write_pid_file(file: &PidFile) {
+        let mut contents = format!("{} {}\n", OWNER_PREFIX, std::process::id());
+        contents.extend(file.pids.iter().map(|pid| format!("{}\n", pid)));
+        if let Err(e) = fs::write(&file.path, contents) {
+            warn!("Failed to write FFmpeg PID file {:?}: {}", file.path, e);
+        }
+    }
+
+    /// Kill FFmpeg processes listed in a previous session's PID file
+    ///
+    /// PIDs that now belong to something other than FFmpeg are left alone.
+    /// Returns the number of processes killed, or None when the app that
+    /// wrote the file is still running (another instance), in which case
+    /// nothing is killed and the file is kept.
+    pub fn reap_stray_ffmpeg(pid_file: &Path) -> Option<usize> {
+        let Ok(contents) = fs::read_to_string(pid_file) else {
+            return Some(0);
+        };
+        let owner: Option<u32> = contents.lines().find_map(|line| {
+            line.trim()
+                .strip_prefix(OWNER_PREFIX)
+                .and_then(|pid| pid.trim().parse().ok())
+        });
+        let pids: Vec<u32> = contents
+            .lines()
+            .filter_map(|line| line.trim().parse().ok())
+            .collect();
+
+        let system = sysinfo::System::new_all();
+        if owner.is_some_and(|owner| is_this_app(&system, owner)) {
+            return None;
+        }
+
+        let mut killed = 0;
+        for pid in pids {
+            let Some(process) = system.process(sysinfo::Pid::from_u32(pid)) else {
+                continue;
+            };
+            let name = Path::new(process.name()).to_string_lossy().to_lowercase();
+            if !name.starts_with("ffmpeg") {
+                continue;
+            }
+
+            if process.kill() {
+                info!("Killed stray FFmpeg process (PID {})", pid);
+                killed += 1;
+            } else {
+                warn!("Failed to kill stray FFmpeg process (PID {})", pid);
+            }
+        }
+
+        let _ = fs::remove_file(pid_file);
+        Some(killed)
+    }
+
+    /// Whether `pid` is a running instance of this executable
+    fn is_this_app(system: &sysinfo::System, pid: u32) -> bool {
+        let this = system.process(sysinfo::Pid::from_u32(std::process::id()));
+        let other = system.process(sysinfo::Pid::from_u32(pid));
+        match (this, other) {
+            (Some(this), Some(other)) => this.name() == other.name(),
+            _ => false,
+        }
+    }
 
     /// Ensure FFmpeg process is terminated
     pub fn terminate_ffmpeg(mut child: Child) {
@@ -391,6 +548,28 @@ mod tests {
         assert!(freed > 0);
     }
 
+    #[test]
+    fn test_reap_skips_non_ffmpeg_processes() {
+        let temp_dir = tempdir().unwrap();
+        let pid_file = temp_dir.path().join("ffmpeg.pids");
+        fs::write(&pid_file, format!("{}\nnot-a-pid\n", std::process::id())).unwrap();
+
+        // The test runner isn't FFmpeg, so nothing is killed
+        assert_eq!(process::reap_stray_ffmpeg(&pid_file), Some(0));
+        assert!(!pid_file.exists());
+    }
+
+    #[test]
+    fn test_reap_leaves_running_instance_alone() {
+        let temp_dir = tempdir().unwrap();
+        let pid_file = temp_dir.path().join("ffmpeg.pids");
+        fs::write(&pid_file, format!("app {}\n", std::process::id())).unwrap();
+
+        // The owner (this test process) is running, so the file is kept
+        assert_eq!(process::reap_stray_ffmpeg(&pid_file), None);
+        assert!(pid_file.exists());
+    }
+
     #[test]
     fn test_temp_file_guard_cleanup() {
         let temp_dir = tempdir().unwrap();
@@ -1,4 +1,7 @@
+use crate::settings::models::LoggingSettings;
+use crate::utils::circuit_breaker::CircuitBreakerStatus;
 use crate::utils::metrics::{HealthStatus, RecordingMetrics, SystemMetrics};
+use crate::utils::metrics_history::{MetricsRange, MetricsSample};
 /// Tauri commands for production utilities
 ///
 /// Exposes metrics, health status, and system info to frontend
@@ -23,6 +26,73 @@ pub async fn get_health_status(state: State<'_, AppState>) -> Result<HealthStatu
     Ok(state.metrics_collector.check_health().await)
 }
 
+/// Get historical metrics samples for charting recording health
+///
+/// `max_points` downsamples long ranges (e.g. 300 points for a chart)
+#[tauri::command]
+pub async fn get_metrics_history(
+    state: State<'_, AppState>,
+    range: MetricsRange,
+    max_points: Option<usize>,
+) -> Result<Vec<MetricsSample>, String> {
+    Ok(state.metrics_history.query(&range, max_points).await)
+}
+
+/// Get the state of every circuit breaker (e.g. recording disabled after
+/// repeated FFmpeg failures)
+#[tauri::command]
+pub async fn get_circuit_breaker_status(
+    state: State<'_, AppState>,
+) -> Result<Vec<CircuitBreakerStatus>, String> {
+    Ok(state.circuit_breakers.statuses().await)
+}
+
+/// Close a circuit breaker so its operations are tried again right away
+#[tauri::command]
+pub async fn reset_circuit_breaker(state: State<'_, AppState>, name: String) -> Result<(), String> {
+    state
+        .circuit_breakers
+        .reset(&name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Change log verbosity at runtime and persist it to settings
+///
+/// With `module` (e.g. `lolshorts::recording`) only that target's level is
+/// overridden; passing `"default"` as the level removes the override.
+/// Without `module` the global level is changed.
+#[tauri::command]
+pub async fn set_log_level(
+    state: State<'_, AppState>,
+    level: String,
+    module: Option<String>,
+) -> Result<LoggingSettings, String> {
+    let mut settings = state.recording_settings.write().await;
+    let mut logging = settings.logging.clone();
+
+    match module {
+        Some(module) if level.eq_ignore_ascii_case("default") => {
+            logging.module_levels.remove(&module);
+        }
+        Some(module) => {
+            logging.module_levels.insert(module, level);
+        }
+        None => logging.level = level,
+    }
+
+    // Apply first so invalid levels are rejected before being persisted
+    state
+        .log_handle
+        .set_levels(&logging.level, &logging.module_levels)
+        .map_err(|e| e.to_string())?;
+
+    settings.logging = logging.clone();
+    settings.save().map_err(|e| e.to_string())?;
+
+    Ok(logging)
+}
+
 /// Get application version info
 #[tauri::command]
 pub fn get_app_version() -> Result<String, String> {
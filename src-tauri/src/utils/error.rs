@@ -2,9 +2,24 @@
 
 /// Production-grade error types with context
 ///
-/// Provides rich error information for debugging and monitoring
+/// Provides rich error information for debugging and monitoring. Commands
+/// return [`AppError`], which reaches the frontend as
+/// `{ code, category, message, suggestions }` (`AppError` in
+/// src/types/error.ts): `code` is stable across releases (safe to branch on),
+/// `message` is meant for the user.
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
 use thiserror::Error;
 
+use crate::auth::AuthError;
+use crate::payments::PaymentError;
+use crate::quota::QuotaError;
+use crate::settings::storage::SettingsError;
+use crate::storage::StorageError;
+use crate::supabase::SupabaseError;
+use crate::utils::security::SecurityError;
+use crate::video::VideoError;
+
 /// Application-wide error types
 #[derive(Debug, Error)]
 pub enum AppError {
@@ -15,7 +30,7 @@ pub enum AppError {
     VideoProcessing(String),
 
     #[error("Database error: {0}")]
-    Database(#[from] anyhow::Error),
+    Database(anyhow::Error),
 
     #[error("HTTP request error: {0}")]
     Http(String),
@@ -37,6 +52,277 @@ pub enum AppError {
 
     #[error("Retry exhausted: {0}")]
     RetryExhausted(String),
+
+    #[error(transparent)]
+    Video(#[from] VideoError),
+
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    #[error(transparent)]
+    Settings(#[from] SettingsError),
+
+    #[error(transparent)]
+    Auth(#[from] AuthError),
+
+    #[error(transparent)]
+    Payment(#[from] PaymentError),
+
+    #[error(transparent)]
+    Quota(#[from] QuotaError),
+
+    #[error(transparent)]
+    Supabase(#[from] SupabaseError),
+
+    #[error(transparent)]
+    InvalidInput(#[from] SecurityError),
+
+    #[error("Background task failed: {0}")]
+    Task(#[from] tokio::task::JoinError),
+
+    /// Errors not classified yet (commands that still build message strings)
+    #[error("{0}")]
+    Other(String),
+}
+
+/// Broad area an [`AppError`] comes from, for grouping in the UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    Recording,
+    Video,
+    Storage,
+    Network,
+    Auth,
+    Validation,
+    Internal,
+}
+
+impl AppError {
+    /// Stable numeric code: 1xxx recording, 2xxx video, 3xxx storage,
+    /// 4xxx network, 5xxx account, 6xxx validation, 9xxx internal
+    pub fn code(&self) -> u32 {
+        match self {
+            Self::Recording(_) => 1000,
+            Self::CircuitBreakerOpen(_) => 1001,
+            Self::RetryExhausted(_) => 1002,
+
+            Self::VideoProcessing(_) => 2000,
+            Self::Video(e) => video_code(e),
+
+            Self::Storage(e) => match e {
+                StorageError::Io(_) => 3001,
+                StorageError::Json(_) => 3002,
+                StorageError::GameNotFound(_) => 3003,
+                StorageError::TrashEntryNotFound(_) => 3004,
+                StorageError::AlreadyExists(_) => 3005,
+            },
+            Self::Io(_) => 3006,
+            Self::Settings(e) => match e {
+                SettingsError::ConfigDirNotFound => 3010,
+                SettingsError::Io(_) => 3011,
+                SettingsError::Json(_) => 3012,
+                SettingsError::UnsupportedVersion(_) => 3013,
+                SettingsError::InvalidFormat => 3014,
+                SettingsError::Storage(_) => 3015,
+                SettingsError::ProfileNotFound(_) => 6002,
+                SettingsError::InvalidProfileName => 6003,
+            },
+
+            Self::Http(_) => 4000,
+            Self::ServiceUnavailable { .. } => 4001,
+            Self::Supabase(e) => match e {
+                SupabaseError::HttpError(_) => 4002,
+                SupabaseError::Unauthorized(_) => 5004,
+                SupabaseError::ConfigError(_) => 9002,
+                _ => 4003,
+            },
+            Self::Payment(e) => match e {
+                PaymentError::Http(_) => 4004,
+                PaymentError::Supabase(_) => 4005,
+                PaymentError::PaymentFailed(_) => 5006,
+                PaymentError::InvalidStatus(_) => 5007,
+                PaymentError::WebhookVerificationFailed => 5008,
+                PaymentError::NotFound(_) => 5009,
+                PaymentError::InvalidPeriod(_) => 6001,
+                PaymentError::NotConfigured(_) => 9004,
+            },
+
+            Self::FeatureGated => 5000,
+            Self::LicenseExpired => 5001,
+            Self::Auth(e) => match e {
+                AuthError::NotAuthenticated => 5002,
+                AuthError::InvalidToken => 5003,
+                AuthError::Failed(_) | AuthError::Supabase(_) => 5004,
+            },
+            Self::Quota(e) => match e {
+                QuotaError::Exceeded { .. } => 5005,
+                QuotaError::Auth(_) => 5002,
+                _ => 9003,
+            },
+
+            Self::InvalidInput(_) => 6000,
+
+            Self::Other(_) => 9000,
+            Self::Database(_) => 9001,
+            Self::Task(_) => 9005,
+        }
+    }
+
+    pub fn category(&self) -> ErrorCategory {
+        match self.code() / 1000 {
+            1 => ErrorCategory::Recording,
+            2 => ErrorCategory::Video,
+            3 => ErrorCategory::Storage,
+            4 => ErrorCategory::Network,
+            5 => ErrorCategory::Auth,
+            6 => ErrorCategory::Validation,
+            _ => ErrorCategory::Internal,
+        }
+    }
+
+    /// Message shown to the user
+    pub fn user_message(&self) -> String {
+        self.to_string()
+    }
+
+    /// What the user can do about it
+    pub fn recovery_suggestions(&self) -> Vec<String> {
+        let suggestions: &[&str] = match self {
+            Self::Video(e) => return e.recovery_suggestions(),
+            Self::CircuitBreakerOpen(_) | Self::RetryExhausted(_) => &[
+                "Recording was paused after repeated failures",
+                "Re-arm it from the diagnostics panel or restart the application",
+            ],
+            Self::Storage(StorageError::Io(_))
+            | Self::Settings(SettingsError::Io(_))
+            | Self::Io(_) => &[
+                "Check that the disk isn't full or read-only",
+                "Check the storage folders in settings",
+            ],
+            Self::Storage(StorageError::GameNotFound(_))
+            | Self::Storage(StorageError::TrashEntryNotFound(_)) => {
+                &["Refresh the library, it may have changed"]
+            }
+            Self::Http(_)
+            | Self::ServiceUnavailable { .. }
+            | Self::Supabase(_)
+            | Self::Payment(PaymentError::Http(_) | PaymentError::Supabase(_)) => &[
+                "Check your internet connection",
+                "Try again in a few minutes",
+            ],
+            Self::Auth(_) | Self::Quota(QuotaError::Auth(_)) => &["Sign in again"],
+            Self::FeatureGated | Self::Quota(QuotaError::Exceeded { .. }) => {
+                &["Upgrade to PRO to remove this limit"]
+            }
+            Self::LicenseExpired => &["Renew your subscription in account settings"],
+            Self::Settings(SettingsError::ProfileNotFound(_)) => {
+                &["Refresh the profile list, it may have changed"]
+            }
+            Self::InvalidInput(_) => &["Choose a different file or value"],
+            _ => &["Try again", "Contact support if issue persists"],
+        };
+        suggestions.iter().map(|s| s.to_string()).collect()
+    }
+}
+
+fn video_code(error: &VideoError) -> u32 {
+    match error {
+        VideoError::FileNotFound { .. } => 2001,
+        VideoError::FileAccessError { .. } => 2002,
+        VideoError::InsufficientDiskSpace { .. } => 2003,
+        VideoError::OutputDirectoryNotFound { .. } => 2004,
+        VideoError::FfmpegNotFound => 2005,
+        VideoError::FfmpegProcessError { .. } => 2006,
+        VideoError::UnsupportedCodec { .. } => 2007,
+        VideoError::CorruptedVideo => 2008,
+        VideoError::CanvasApplicationError { .. } => 2009,
+        VideoError::BackgroundMusicNotFound { .. } => 2010,
+        VideoError::AudioMixingError { .. } => 2011,
+        VideoError::NoClipsFound => 2012,
+        VideoError::InsufficientClips { .. } => 2013,
+        VideoError::ConcatenationError { .. } => 2014,
+        VideoError::ResourceExhaustion => 2015,
+        VideoError::Timeout { .. } => 2016,
+        VideoError::ProcessingError { .. } => 2017,
+        VideoError::AnyhowError(_) => 2018,
+    }
+}
+
+/// Keeps the type of errors that went through `anyhow`
+impl From<anyhow::Error> for AppError {
+    fn from(error: anyhow::Error) -> Self {
+        let error = match error.downcast::<VideoError>() {
+            Ok(e) => return Self::Video(e),
+            Err(error) => error,
+        };
+        let error = match error.downcast::<StorageError>() {
+            Ok(e) => return Self::Storage(e),
+            Err(error) => error,
+        };
+        match error.downcast::<SupabaseError>() {
+            Ok(e) => Self::Supabase(e),
+            Err(error) => Self::Other(format!("{:#}", error)),
+        }
+    }
+}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        Self::Other(message)
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 4)?;
+        state.serialize_field("code", &self.code())?;
+        state.serialize_field("category", &self.category())?;
+        state.serialize_field("message", &self.user_message())?;
+        state.serialize_field("suggestions", &self.recovery_suggestions())?;
+        state.end()
+    }
 }
 
 pub type Result<T> = std::result::Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialized_shape() {
+        let error = AppError::from(VideoError::FfmpegNotFound);
+        let json = serde_json::to_value(&error).unwrap();
+
+        assert_eq!(json["code"], 2005);
+        assert_eq!(json["category"], "video");
+        assert!(json["message"].as_str().unwrap().contains("FFmpeg"));
+        assert_eq!(
+            json["suggestions"].as_array().unwrap().len(),
+            VideoError::FfmpegNotFound.recovery_suggestions().len()
+        );
+    }
+
+    #[test]
+    fn test_anyhow_keeps_error_type() {
+        let error = AppError::from(anyhow::Error::new(StorageError::GameNotFound(
+            "game_1".to_string(),
+        )));
+        assert_eq!(error.code(), 3003);
+        assert_eq!(error.category(), ErrorCategory::Storage);
+
+        let error = AppError::from(anyhow::anyhow!("something else"));
+        assert_eq!(error.category(), ErrorCategory::Internal);
+    }
+
+    #[test]
+    fn test_settings_and_payment_categories() {
+        let error = AppError::from(SettingsError::ProfileNotFound("ARAM".to_string()));
+        assert_eq!(error.category(), ErrorCategory::Validation);
+
+        let error = AppError::from(PaymentError::NotFound("License".to_string()));
+        assert_eq!(error.code(), 5009);
+        assert_eq!(error.category(), ErrorCategory::Auth);
+    }
+}
@@ -1,13 +1,33 @@
 #![allow(dead_code)]
 
+use std::collections::HashMap;
 use std::fs;
+use std::io::{self, Write};
 /// Production-grade structured logging system
 ///
 /// Provides context-rich logging with file rotation, performance tracking,
 /// and integration with external monitoring systems.
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, bail};
+use chrono::{NaiveDate, Utc};
+use tracing::level_filters::LevelFilter;
 use tracing::Level;
-use tracing_subscriber::{fmt, EnvFilter};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt::{self, MakeWriter};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+use crate::settings::models::LoggingSettings;
+
+/// Log file name prefix/suffix (files are named `lolshorts.YYYY-MM-DD.log`)
+const LOG_FILE_PREFIX: &str = "lolshorts";
+const LOG_FILE_SUFFIX: &str = "log";
+
+/// Written once when a day's log file reaches its size cap
+const CAP_NOTICE: &str = "Log file size cap reached, dropping entries until rotation";
 
 /// Logging configuration
 #[derive(Debug, Clone)]
@@ -15,13 +35,21 @@ pub struct LogConfig {
     /// Minimum log level (default: INFO)
     pub level: Level,
 
+    /// Per-module level overrides, e.g. `lolshorts::recording` => `debug`
+    pub module_levels: HashMap<String, String>,
+
     /// Enable file logging (default: true)
     pub file_enabled: bool,
 
+    /// Write log files as JSON lines instead of plain text (default: true)
+    pub json_format: bool,
+
     /// Log file path (default: app_data/logs/)
     pub log_dir: PathBuf,
 
-    /// Maximum log file size in MB before rotation (default: 10)
+    /// Maximum size of a single day's log file in MB (default: 10)
+    ///
+    /// Once reached, further entries are dropped until the next rotation.
     pub max_file_size_mb: u64,
 
     /// Number of rotated log files to keep (default: 5)
@@ -39,7 +67,9 @@ impl LogConfig {
     pub fn production(log_dir: PathBuf) -> Self {
         Self {
             level: Level::INFO,
+            module_levels: HashMap::new(),
             file_enabled: true,
+            json_format: true,
             log_dir,
             max_file_size_mb: 10,
             max_files: 5,
@@ -52,7 +82,9 @@ impl LogConfig {
     pub fn development(log_dir: PathBuf) -> Self {
         Self {
             level: Level::DEBUG,
+            module_levels: HashMap::new(),
             file_enabled: true,
+            json_format: true,
             log_dir,
             max_file_size_mb: 50,
             max_files: 3,
@@ -60,6 +92,18 @@ impl LogConfig {
             console_pretty: true,
         }
     }
+
+    /// Apply persisted logging settings (level, module overrides, format)
+    ///
+    /// An invalid persisted level keeps the configuration's default level.
+    pub fn with_settings(mut self, settings: &LoggingSettings) -> Self {
+        if let Ok(level) = settings.level.trim().parse::<Level>() {
+            self.level = level;
+        }
+        self.module_levels = settings.module_levels.clone();
+        self.json_format = settings.json_format;
+        self
+    }
 }
 
 impl Default for LogConfig {
@@ -67,7 +111,9 @@ impl Default for LogConfig {
         // Default to production-like settings
         Self {
             level: Level::INFO,
+            module_levels: HashMap::new(),
             file_enabled: true,
+            json_format: true,
             log_dir: PathBuf::from("logs"),
             max_file_size_mb: 10,
             max_files: 5,
@@ -77,8 +123,67 @@ impl Default for LogConfig {
     }
 }
 
+/// Handle for changing log verbosity after initialization
+#[derive(Debug, Clone)]
+pub struct LogHandle {
+    filter: reload::Handle<EnvFilter, Registry>,
+}
+
+impl LogHandle {
+    /// Replace the active filter with a new default level and module overrides
+    pub fn set_levels(
+        &self,
+        level: &str,
+        module_levels: &HashMap<String, String>,
+    ) -> anyhow::Result<()> {
+        let filter = build_filter(level, module_levels)?;
+        self.filter
+            .reload(filter)
+            .map_err(|e| anyhow!("Failed to update log filter: {}", e))?;
+
+        tracing::info!(level, ?module_levels, "Log level updated");
+        Ok(())
+    }
+}
+
+/// Build an `EnvFilter` from a default level and per-module overrides
+///
+/// # Errors
+/// Returns error if a level or module target is invalid
+pub fn build_filter(
+    level: &str,
+    module_levels: &HashMap<String, String>,
+) -> anyhow::Result<EnvFilter> {
+    let mut directives = vec![parse_level(level)?.to_string()];
+
+    // Sorted so the resulting directive string is deterministic
+    let mut modules: Vec<_> = module_levels.iter().collect();
+    modules.sort();
+
+    for (module, module_level) in modules {
+        let module = module.trim();
+        if module.is_empty() || module.contains(['=', ',', ' ']) {
+            bail!("Invalid log target: {:?}", module);
+        }
+        directives.push(format!("{}={}", module, parse_level(module_level)?));
+    }
+
+    Ok(EnvFilter::try_new(directives.join(","))?)
+}
+
+fn parse_level(level: &str) -> anyhow::Result<LevelFilter> {
+    level
+        .trim()
+        .parse::<LevelFilter>()
+        .map_err(|_| anyhow!("Invalid log level: {}", level))
+}
+
 /// Initialize the logging system
 ///
+/// File logs rotate daily, keeping `max_files` files of at most
+/// `max_file_size_mb` each. `RUST_LOG`, when set, takes precedence over the
+/// configured levels until changed at runtime through the returned handle.
+///
 /// # Arguments
 /// * `config` - Logging configuration
 ///
@@ -91,64 +196,202 @@ impl Default for LogConfig {
 /// use lolshorts::utils::logging::{LogConfig, init_logging};
 ///
 /// let config = LogConfig::production(PathBuf::from("C:/logs"));
-/// init_logging(config).expect("Failed to initialize logging");
+/// let handle = init_logging(config).expect("Failed to initialize logging");
 /// ```
-pub fn init_logging(config: LogConfig) -> anyhow::Result<()> {
-    // Create log directory if it doesn't exist
-    if config.file_enabled {
-        fs::create_dir_all(&config.log_dir)?;
+pub fn init_logging(config: LogConfig) -> anyhow::Result<LogHandle> {
+    if !config.file_enabled && !config.console_enabled {
+        return Err(anyhow!("No logging output configured"));
     }
 
     // Build environment filter
-    let env_filter = EnvFilter::from_default_env().add_directive(config.level.into());
-
-    // Simplified implementation: Choose one primary output
-    if config.file_enabled {
-        // File logging with daily rotation
-        let file_appender =
-            tracing_appender::rolling::daily(config.log_dir.clone(), "lolshorts.log");
-
-        let subscriber = fmt()
-            .with_env_filter(env_filter)
-            .with_writer(file_appender)
-            .with_ansi(false)
-            .with_target(true)
-            .with_thread_ids(true)
-            .with_line_number(true)
-            .with_file(true)
-            .json()
-            .finish();
-
-        tracing::subscriber::set_global_default(subscriber)?;
-    } else if config.console_enabled {
-        // Console logging only
-        if config.console_pretty {
-            let subscriber = fmt()
-                .with_env_filter(env_filter)
-                .pretty()
-                .with_thread_ids(true)
-                .with_thread_names(true)
-                .with_line_number(true)
-                .with_file(true)
-                .finish();
-
-            tracing::subscriber::set_global_default(subscriber)?;
-        } else {
-            let subscriber = fmt()
-                .with_env_filter(env_filter)
-                .compact()
-                .with_target(true)
-                .with_thread_ids(true)
-                .finish();
-
-            tracing::subscriber::set_global_default(subscriber)?;
-        }
+    let env_filter = match std::env::var(EnvFilter::DEFAULT_ENV) {
+        Ok(directives) if !directives.trim().is_empty() => EnvFilter::try_new(directives)?,
+        _ => build_filter(config.level.as_str(), &config.module_levels)?,
+    };
+    let (filter_layer, filter_handle) = reload::Layer::new(env_filter);
+
+    let file_writer = if config.file_enabled {
+        // Create log directory if it doesn't exist
+        fs::create_dir_all(&config.log_dir)?;
+        Some(SizeCappedWriter::new(&config)?)
     } else {
-        // No logging configured
-        return Err(anyhow::anyhow!("No logging output configured"));
+        None
+    };
+
+    let (json_file_layer, text_file_layer) = match file_writer {
+        Some(writer) if config.json_format => (
+            Some(
+                fmt::layer()
+                    .json()
+                    .with_writer(writer)
+                    .with_ansi(false)
+                    .with_target(true)
+                    .with_thread_ids(true)
+                    .with_line_number(true)
+                    .with_file(true),
+            ),
+            None,
+        ),
+        Some(writer) => (
+            None,
+            Some(
+                fmt::layer()
+                    .with_writer(writer)
+                    .with_ansi(false)
+                    .with_target(true)
+                    .with_thread_ids(true)
+                    .with_line_number(true),
+            ),
+        ),
+        None => (None, None),
+    };
+
+    let (pretty_console_layer, compact_console_layer) = match config.console_enabled {
+        true if config.console_pretty => (
+            Some(
+                fmt::layer()
+                    .pretty()
+                    .with_thread_ids(true)
+                    .with_thread_names(true)
+                    .with_line_number(true)
+                    .with_file(true),
+            ),
+            None,
+        ),
+        true => (
+            None,
+            Some(
+                fmt::layer()
+                    .compact()
+                    .with_target(true)
+                    .with_thread_ids(true),
+            ),
+        ),
+        false => (None, None),
+    };
+
+    tracing_subscriber::registry()
+        .with(filter_layer)
+        .with(json_file_layer)
+        .with(text_file_layer)
+        .with(pretty_console_layer)
+        .with(compact_console_layer)
+        .try_init()?;
+
+    Ok(LogHandle {
+        filter: filter_handle,
+    })
+}
+
+/// Daily rolling file writer that stops writing once a day's file hits its cap
+#[derive(Clone)]
+struct SizeCappedWriter {
+    inner: Arc<Mutex<CappedFile>>,
+}
+
+struct CappedFile {
+    appender: RollingFileAppender,
+    max_bytes: u64,
+    day: NaiveDate,
+    written: u64,
+    cap_reported: bool,
+    /// Write the cap notice as a JSON line rather than plain text
+    json_format: bool,
+}
+
+impl SizeCappedWriter {
+    fn new(config: &LogConfig) -> anyhow::Result<Self> {
+        let appender = RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .filename_prefix(LOG_FILE_PREFIX)
+            .filename_suffix(LOG_FILE_SUFFIX)
+            .max_log_files(config.max_files.max(1))
+            .build(&config.log_dir)?;
+
+        // tracing-appender rotates on UTC dates
+        let day = Utc::now().date_naive();
+
+        Ok(Self {
+            inner: Arc::new(Mutex::new(CappedFile {
+                appender,
+                max_bytes: config.max_file_size_mb.max(1) * 1024 * 1024,
+                day,
+                written: current_log_size(&config.log_dir, day),
+                cap_reported: false,
+                json_format: config.json_format,
+            })),
+        })
+    }
+}
+
+/// Size of the log file for `day`, so restarts count toward the same cap
+fn current_log_size(log_dir: &Path, day: NaiveDate) -> u64 {
+    let file_name = format!(
+        "{}.{}.{}",
+        LOG_FILE_PREFIX,
+        day.format("%Y-%m-%d"),
+        LOG_FILE_SUFFIX
+    );
+    fs::metadata(log_dir.join(file_name))
+        .map(|m| m.len())
+        .unwrap_or(0)
+}
+
+impl CappedFile {
+    fn write_capped(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let today = Utc::now().date_naive();
+        if today != self.day {
+            self.day = today;
+            self.written = 0;
+            self.cap_reported = false;
+        }
+
+        if self.written + buf.len() as u64 > self.max_bytes {
+            if !self.cap_reported {
+                self.cap_reported = true;
+                let notice = if self.json_format {
+                    format!(
+                        "{{\"level\":\"WARN\",\"fields\":{{\"message\":\"{}\"}}}}\n",
+                        CAP_NOTICE
+                    )
+                } else {
+                    format!("{}  WARN {}\n", Utc::now().to_rfc3339(), CAP_NOTICE)
+                };
+                self.appender.write_all(notice.as_bytes())?;
+            }
+            // Report success so the subscriber doesn't surface write errors
+            return Ok(buf.len());
+        }
+
+        let written = self.appender.write(buf)?;
+        self.written += written as u64;
+        Ok(written)
+    }
+}
+
+impl Write for SizeCappedWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .write_capped(buf)
     }
 
-    Ok(())
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .appender
+            .flush()
+    }
+}
+
+impl<'a> MakeWriter<'a> for SizeCappedWriter {
+    type Writer = SizeCappedWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
 }
 
 /// Logging macros with context
@@ -230,4 +473,94 @@ mod tests {
         init_logging(config).expect("Failed to init logging");
         assert!(log_dir.exists());
     }
+
+    #[test]
+    fn test_build_filter_with_module_overrides() {
+        let mut module_levels = HashMap::new();
+        module_levels.insert("lolshorts::recording".to_string(), "debug".to_string());
+        module_levels.insert("lolshorts::lcu".to_string(), "WARN".to_string());
+
+        let filter = build_filter("info", &module_levels).unwrap();
+        let directives = filter.to_string().to_lowercase();
+        assert!(directives.contains("lolshorts::recording=debug"));
+        assert!(directives.contains("lolshorts::lcu=warn"));
+    }
+
+    #[test]
+    fn test_build_filter_rejects_invalid_levels() {
+        assert!(build_filter("verbose", &HashMap::new()).is_err());
+
+        let mut module_levels = HashMap::new();
+        module_levels.insert("lolshorts::video".to_string(), "loud".to_string());
+        assert!(build_filter("info", &module_levels).is_err());
+
+        let mut module_levels = HashMap::new();
+        module_levels.insert("a=b".to_string(), "info".to_string());
+        assert!(build_filter("info", &module_levels).is_err());
+    }
+
+    #[test]
+    fn test_size_capped_writer_drops_after_cap() {
+        let temp_dir = tempdir().unwrap();
+        let config = LogConfig {
+            log_dir: temp_dir.path().to_path_buf(),
+            max_file_size_mb: 1,
+            ..Default::default()
+        };
+
+        let mut writer = SizeCappedWriter::new(&config).unwrap();
+        let line = vec![b'x'; 256 * 1024];
+        for _ in 0..8 {
+            assert_eq!(writer.write(&line).unwrap(), line.len());
+        }
+        writer.flush().unwrap();
+
+        let size = current_log_size(temp_dir.path(), Utc::now().date_naive());
+        assert!(size <= 1024 * 1024 + 256);
+        assert!(size >= 1024 * 1024);
+    }
+
+    #[test]
+    fn test_cap_notice_follows_format() {
+        let temp_dir = tempdir().unwrap();
+        let config = LogConfig {
+            log_dir: temp_dir.path().to_path_buf(),
+            max_file_size_mb: 1,
+            json_format: false,
+            ..Default::default()
+        };
+
+        let mut writer = SizeCappedWriter::new(&config).unwrap();
+        writer.write_all(&vec![b'x'; 1024 * 1024]).unwrap();
+        writer.write_all(b"dropped\n").unwrap();
+        writer.flush().unwrap();
+
+        let file_name = format!(
+            "{}.{}.{}",
+            LOG_FILE_PREFIX,
+            Utc::now().date_naive().format("%Y-%m-%d"),
+            LOG_FILE_SUFFIX
+        );
+        let log = fs::read_to_string(temp_dir.path().join(file_name)).unwrap();
+        let notice = log.trim_start_matches('x').trim_end();
+        assert!(notice.ends_with(&format!("WARN {}", CAP_NOTICE)));
+        assert!(!notice.contains('{'));
+    }
+
+    #[test]
+    fn test_log_config_with_settings() {
+        let mut settings = LoggingSettings {
+            level: "debug".to_string(),
+            json_format: false,
+            ..Default::default()
+        };
+        settings
+            .module_levels
+            .insert("lolshorts::video".to_string(), "trace".to_string());
+
+        let config = LogConfig::default().with_settings(&settings);
+        assert_eq!(config.level, Level::DEBUG);
+        assert!(!config.json_format);
+        assert_eq!(config.module_levels.len(), 1);
+    }
 }
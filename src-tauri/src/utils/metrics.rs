@@ -32,6 +32,10 @@ pub struct RecordingMetrics {
     /// Total disk space used by buffer in MB
     pub buffer_size_mb: f32,
 
+    /// Segments discarded since recording started (empty or missing output)
+    #[serde(default)]
+    pub dropped_segments: u64,
+
     /// Timestamp of last update (excluded from serialization)
     #[serde(skip, default = "Instant::now")]
     pub last_updated: Instant,
@@ -47,6 +51,7 @@ impl Default for RecordingMetrics {
             memory_mb: 0.0,
             buffer_segments: 0,
             buffer_size_mb: 0.0,
+            dropped_segments: 0,
             last_updated: Instant::now(),
         }
     }
@@ -137,6 +142,8 @@ pub struct MetricsCollector {
     system_metrics: Arc<RwLock<SystemMetrics>>,
     thresholds: HealthThresholds,
     sysinfo: Arc<RwLock<sysinfo::System>>,
+    /// When total CPU usage first exceeded the tracked threshold (None = below)
+    high_cpu_since: Arc<RwLock<Option<Instant>>>,
 }
 
 impl MetricsCollector {
@@ -146,6 +153,7 @@ impl MetricsCollector {
             system_metrics: Arc::new(RwLock::new(SystemMetrics::default())),
             thresholds,
             sysinfo: Arc::new(RwLock::new(sysinfo::System::new_all())),
+            high_cpu_since: Arc::new(RwLock::new(None)),
         }
     }
 
@@ -173,6 +181,13 @@ impl MetricsCollector {
         metrics.last_updated = Instant::now();
     }
 
+    /// Update dropped segment count (from recorder stats)
+    pub async fn update_dropped_segments(&self, dropped_segments: u64) {
+        let mut metrics = self.recording_metrics.write().await;
+        metrics.dropped_segments = dropped_segments;
+        metrics.last_updated = Instant::now();
+    }
+
     /// Update system metrics from sysinfo
     pub async fn update_system_metrics(&self) {
         let mut sys = self.sysinfo.write().await;
@@ -195,6 +210,22 @@ impl MetricsCollector {
         // TODO: Add GPU metrics if available
     }
 
+    /// Track sustained high CPU usage
+    ///
+    /// Call after `update_system_metrics`. Returns how long total CPU usage has
+    /// stayed above `threshold_percent` (zero if currently below).
+    pub async fn track_sustained_cpu(&self, threshold_percent: f32) -> Duration {
+        let cpu = self.system_metrics.read().await.total_cpu_percent;
+        let mut since = self.high_cpu_since.write().await;
+
+        if cpu > threshold_percent {
+            since.get_or_insert_with(Instant::now).elapsed()
+        } else {
+            *since = None;
+            Duration::ZERO
+        }
+    }
+
     /// Check health status against thresholds
     pub async fn check_health(&self) -> HealthStatus {
         let rec_metrics = self.recording_metrics.read().await;
@@ -373,6 +404,31 @@ mod tests {
         assert_eq!(health, HealthStatus::Warning);
     }
 
+    #[tokio::test]
+    async fn test_sustained_cpu_tracking() {
+        let collector = MetricsCollector::new(HealthThresholds::default());
+
+        collector
+            .set_system_metrics_for_test(SystemMetrics {
+                total_cpu_percent: 97.0,
+                ..Default::default()
+            })
+            .await;
+
+        collector.track_sustained_cpu(90.0).await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(collector.track_sustained_cpu(90.0).await >= Duration::from_millis(20));
+
+        // Dropping below the threshold resets the timer
+        collector
+            .set_system_metrics_for_test(SystemMetrics {
+                total_cpu_percent: 40.0,
+                ..Default::default()
+            })
+            .await;
+        assert_eq!(collector.track_sustained_cpu(90.0).await, Duration::ZERO);
+    }
+
     #[tokio::test]
     async fn test_health_check_critical() {
         let collector = MetricsCollector::new(HealthThresholds::default());
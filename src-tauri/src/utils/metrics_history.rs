@@ -0,0 +1,279 @@
+#![allow(dead_code)]
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::Write;
+/// Rolling metrics history for recording health charts
+///
+/// Samples of CPU, memory, FPS and dropped segments are kept in memory and
+/// appended to a JSON Lines file so a session can be plotted after the fact
+/// (or after a crash). Samples older than the retention window are pruned.
+use std::path::{Path, PathBuf};
+use tokio::sync::RwLock;
+use tracing::debug;
+
+use super::metrics::MetricsCollector;
+
+/// Rewrite the history file after this many appends to drop expired lines
+const COMPACT_EVERY_N_SAMPLES: usize = 360;
+
+/// A single point-in-time metrics sample
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsSample {
+    pub timestamp: DateTime<Utc>,
+
+    /// Recording FPS
+    pub fps: f32,
+
+    /// FFmpeg process CPU usage (0.0-100.0)
+    pub cpu_percent: f32,
+
+    /// Overall system CPU usage (0.0-100.0)
+    pub system_cpu_percent: f32,
+
+    /// FFmpeg process memory usage in MB
+    pub memory_mb: f32,
+
+    /// Available system RAM in GB
+    pub available_ram_gb: f32,
+
+    /// Frame drops in current segment
+    pub frame_drops: u64,
+
+    /// Segments discarded since recording started (empty or missing output)
+    pub dropped_segments: u64,
+}
+
+impl MetricsSample {
+    /// Capture the collector's current values
+    pub async fn capture(collector: &MetricsCollector) -> Self {
+        let rec = collector.get_recording_metrics().await;
+        let sys = collector.get_system_metrics().await;
+
+        Self {
+            timestamp: Utc::now(),
+            fps: rec.fps,
+            cpu_percent: rec.cpu_percent,
+            system_cpu_percent: sys.total_cpu_percent,
+            memory_mb: rec.memory_mb,
+            available_ram_gb: sys.available_ram_gb,
+            frame_drops: rec.frame_drops,
+            dropped_segments: rec.dropped_segments,
+        }
+    }
+}
+
+/// Time range for history queries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MetricsRange {
+    /// Samples recorded since the app started
+    Session,
+    /// Samples from the last N minutes
+    LastMinutes { minutes: u32 },
+    /// Samples between two timestamps (inclusive)
+    Between {
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    },
+}
+
+/// Persistent rolling metrics history
+pub struct MetricsHistory {
+    path: PathBuf,
+    retention: chrono::Duration,
+    session_start: DateTime<Utc>,
+    samples: RwLock<VecDeque<MetricsSample>>,
+    appends_since_compact: RwLock<usize>,
+}
+
+impl MetricsHistory {
+    /// Open (or create) the history file, dropping samples older than `retention`
+    pub fn new(path: impl AsRef<Path>, retention: chrono::Duration) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let cutoff = Utc::now() - retention;
+
+        let mut samples = VecDeque::new();
+        if path.exists() {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read metrics history: {:?}", path))?;
+
+            for line in content.lines().filter(|l| !l.trim().is_empty()) {
+                match serde_json::from_str::<MetricsSample>(line) {
+                    Ok(sample) if sample.timestamp >= cutoff => samples.push_back(sample),
+                    Ok(_) => {}
+                    Err(e) => debug!("Skipping malformed metrics sample: {}", e),
+                }
+            }
+        }
+
+        // Persist the pruned set so the file doesn't grow across sessions
+        Self::write_samples(&path, &samples)?;
+
+        Ok(Self {
+            path,
+            retention,
+            session_start: Utc::now(),
+            samples: RwLock::new(samples),
+            appends_since_compact: RwLock::new(0),
+        })
+    }
+
+    /// Record a sample, appending it to disk and pruning expired samples
+    pub async fn record(&self, sample: MetricsSample) -> Result<()> {
+        let cutoff = Utc::now() - self.retention;
+
+        let mut samples = self.samples.write().await;
+        while samples.front().is_some_and(|s| s.timestamp < cutoff) {
+            samples.pop_front();
+        }
+        samples.push_back(sample.clone());
+
+        let mut appends = self.appends_since_compact.write().await;
+        *appends += 1;
+
+        if *appends >= COMPACT_EVERY_N_SAMPLES {
+            *appends = 0;
+            Self::write_samples(&self.path, &samples)
+        } else {
+            self.append(&sample)
+        }
+    }
+
+    /// Query samples in a range, downsampled to at most `max_points` for charting
+    pub async fn query(
+        &self,
+        range: &MetricsRange,
+        max_points: Option<usize>,
+    ) -> Vec<MetricsSample> {
+        let (from, to) = match range {
+            MetricsRange::Session => (self.session_start, Utc::now()),
+            MetricsRange::LastMinutes { minutes } => (
+                Utc::now() - chrono::Duration::minutes(*minutes as i64),
+                Utc::now(),
+            ),
+            MetricsRange::Between { from, to } => (*from, *to),
+        };
+
+        let samples = self.samples.read().await;
+        let selected: Vec<MetricsSample> = samples
+            .iter()
+            .filter(|s| s.timestamp >= from && s.timestamp <= to)
+            .cloned()
+            .collect();
+
+        match max_points {
+            Some(max) if max > 0 && selected.len() > max => {
+                let stride = selected.len().div_ceil(max);
+                selected.into_iter().step_by(stride).collect()
+            }
+            _ => selected,
+        }
+    }
+
+    /// Number of samples currently retained
+    pub async fn sample_count(&self) -> usize {
+        self.samples.read().await.len()
+    }
+
+    fn append(&self, sample: &MetricsSample) -> Result<()> {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open metrics history: {:?}", self.path))?;
+
+        writeln!(file, "{}", serde_json::to_string(sample)?)?;
+        Ok(())
+    }
+
+    fn write_samples(path: &Path, samples: &VecDeque<MetricsSample>) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut content = String::new();
+        for sample in samples {
+            content.push_str(&serde_json::to_string(sample)?);
+            content.push('\n');
+        }
+
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write metrics history: {:?}", path))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sample_at(timestamp: DateTime<Utc>, fps: f32) -> MetricsSample {
+        MetricsSample {
+            timestamp,
+            fps,
+            cpu_percent: 20.0,
+            system_cpu_percent: 40.0,
+            memory_mb: 300.0,
+            available_ram_gb: 8.0,
+            frame_drops: 0,
+            dropped_segments: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_history_persists_and_reloads() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("metrics_history.jsonl");
+
+        let history = MetricsHistory::new(&path, chrono::Duration::days(7)).unwrap();
+        history.record(sample_at(Utc::now(), 60.0)).await.unwrap();
+        history.record(sample_at(Utc::now(), 58.0)).await.unwrap();
+
+        let reloaded = MetricsHistory::new(&path, chrono::Duration::days(7)).unwrap();
+        assert_eq!(reloaded.sample_count().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_history_retention_prunes_old_samples() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("metrics_history.jsonl");
+
+        let history = MetricsHistory::new(&path, chrono::Duration::hours(1)).unwrap();
+        history
+            .record(sample_at(Utc::now() - chrono::Duration::hours(2), 60.0))
+            .await
+            .unwrap();
+        history.record(sample_at(Utc::now(), 60.0)).await.unwrap();
+
+        assert_eq!(history.sample_count().await, 1);
+
+        // Expired lines on disk are dropped on reload as well
+        let reloaded = MetricsHistory::new(&path, chrono::Duration::hours(1)).unwrap();
+        assert_eq!(reloaded.sample_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_history_query_downsamples() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("metrics_history.jsonl");
+
+        let history = MetricsHistory::new(&path, chrono::Duration::days(1)).unwrap();
+        for i in 0..100 {
+            history
+                .record(sample_at(Utc::now(), i as f32))
+                .await
+                .unwrap();
+        }
+
+        let all = history.query(&MetricsRange::Session, None).await;
+        assert_eq!(all.len(), 100);
+
+        let charted = history.query(&MetricsRange::Session, Some(10)).await;
+        assert!(charted.len() <= 10);
+        assert_eq!(charted[0].fps, 0.0);
+    }
+}
@@ -1,8 +1,10 @@
+pub mod autostart;
 pub mod circuit_breaker;
 pub mod cleanup;
 pub mod commands;
 pub mod error;
 pub mod logging;
 pub mod metrics;
+pub mod metrics_history;
 pub mod retry;
 pub mod security;
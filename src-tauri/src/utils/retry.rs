@@ -6,10 +6,41 @@ use anyhow::{anyhow, Result};
 /// - Exponential backoff with jitter to prevent thundering herd
 /// - Maximum retry attempts with timeout
 /// - Customizable retry conditions
+use std::sync::RwLock;
 use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{debug, warn};
 
+use crate::settings::models::{RetryPolicy, RetrySettings};
+
+/// Policies from the advanced settings (defaults until [`set_retry_settings`])
+static RETRY_SETTINGS: RwLock<Option<RetrySettings>> = RwLock::new(None);
+
+/// Operations whose retries are configured in settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryOperation {
+    FfmpegStart,
+    Upload,
+    Supabase,
+}
+
+/// Use `settings` for retries started from now on
+pub fn set_retry_settings(settings: &RetrySettings) {
+    *RETRY_SETTINGS.write().unwrap_or_else(|e| e.into_inner()) = Some(settings.clone());
+}
+
+/// Retry config for `operation` from the current settings
+pub fn configured(operation: RetryOperation) -> RetryConfig {
+    let settings = RETRY_SETTINGS.read().unwrap_or_else(|e| e.into_inner());
+    let settings = settings.clone().unwrap_or_default();
+    let policy = match operation {
+        RetryOperation::FfmpegStart => &settings.ffmpeg_start,
+        RetryOperation::Upload => &settings.upload,
+        RetryOperation::Supabase => &settings.supabase,
+    };
+    RetryConfig::from(policy)
+}
+
 /// Retry strategy configuration
 #[derive(Debug, Clone)]
 pub struct RetryConfig {
@@ -41,6 +72,19 @@ impl Default for RetryConfig {
     }
 }
 
+impl From<&RetryPolicy> for RetryConfig {
+    fn from(policy: &RetryPolicy) -> Self {
+        let initial_delay = Duration::from_millis(policy.initial_delay_ms);
+        Self {
+            max_attempts: policy.max_attempts.max(1),
+            initial_delay,
+            max_delay: Duration::from_millis(policy.max_delay_ms).max(initial_delay),
+            backoff_multiplier: policy.backoff_multiplier.max(1.0),
+            jitter_factor: 0.1,
+        }
+    }
+}
+
 impl RetryConfig {
     /// Create aggressive retry config for critical operations
     pub fn aggressive() -> Self {
@@ -336,4 +380,19 @@ mod tests {
         assert_eq!(delay1.as_millis(), 200); // 100 * 2^1
         assert_eq!(delay2.as_millis(), 400); // 100 * 2^2
     }
+
+    #[test]
+    fn test_config_from_settings_policy() {
+        let config = RetryConfig::from(&RetryPolicy {
+            max_attempts: 0,
+            initial_delay_ms: 2_000,
+            max_delay_ms: 100,
+            backoff_multiplier: 0.5,
+        });
+
+        // Always tries once, and delays never shrink
+        assert_eq!(config.max_attempts, 1);
+        assert_eq!(config.max_delay, Duration::from_secs(2));
+        assert_eq!(config.backoff_multiplier, 1.0);
+    }
 }
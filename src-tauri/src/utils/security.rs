@@ -151,6 +151,26 @@ pub fn validate_thumbnail_path(path: &str) -> Result<PathBuf> {
     validate_path(path, Some(&["png", "jpg", "jpeg"]), false)
 }
 
+/// Validate captured frame output path
+pub fn validate_frame_path(path: &str) -> Result<PathBuf> {
+    validate_path(path, Some(&["png"]), false)
+}
+
+/// Validate exported marker file path (EDL, CSV or chapter list)
+pub fn validate_markers_path(path: &str) -> Result<PathBuf> {
+    validate_path(path, Some(&["edl", "csv", "txt"]), false)
+}
+
+/// Validate League replay file path
+pub fn validate_replay_path(path: &str) -> Result<PathBuf> {
+    validate_path(path, Some(&["rofl"]), true)
+}
+
+/// Validate app configuration bundle path
+pub fn validate_config_bundle_path(path: &str, must_exist: bool) -> Result<PathBuf> {
+    validate_path(path, Some(&["json"]), must_exist)
+}
+
 // ========================================================================
 // String Validation
 // ========================================================================
@@ -0,0 +1,143 @@
+//! Short animated exports for chats
+//!
+//! A few seconds of a clip as a looping GIF or animated WebP, small enough
+//! to drop into Discord. GIFs get a palette generated from the excerpt
+//! itself (`palettegen`), which looks far better than the default one.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::process::Command as TokioCommand;
+use tracing::info;
+
+use super::{execute_ffmpeg_command, Result};
+
+/// Longest excerpt that can be exported; GIFs grow quickly past this
+pub const MAX_DURATION_SECS: f64 = 15.0;
+pub const MIN_FPS: u32 = 5;
+pub const MAX_FPS: u32 = 30;
+pub const MIN_WIDTH: u32 = 120;
+pub const MAX_WIDTH: u32 = 720;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnimatedFormat {
+    /// Plays everywhere, limited to 256 colours
+    #[default]
+    Gif,
+    /// Full colour and smaller, but not every app animates it
+    Webp,
+}
+
+impl AnimatedFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            AnimatedFormat::Gif => "gif",
+            AnimatedFormat::Webp => "webp",
+        }
+    }
+
+    /// `-filter_complex` graph scaling to `width` (height keeps the aspect)
+    fn filter(self, fps: u32, width: u32) -> String {
+        let scale = format!("fps={},scale={}:-2:flags=lanczos", fps, width);
+        match self {
+            AnimatedFormat::Gif => format!(
+                "{},split[a][b];[a]palettegen=stats_mode=diff[p];\
+                 [b][p]paletteuse=dither=bayer:bayer_scale=5:diff_mode=rectangle",
+                scale
+            ),
+            AnimatedFormat::Webp => scale,
+        }
+    }
+
+    fn codec_args(self) -> &'static [&'static str] {
+        match self {
+            AnimatedFormat::Gif => &["-loop", "0"],
+            AnimatedFormat::Webp => &[
+                "-c:v",
+                "libwebp",
+                "-lossless",
+                "0",
+                "-q:v",
+                "70",
+                "-compression_level",
+                "6",
+                "-loop",
+                "0",
+            ],
+        }
+    }
+}
+
+/// Export `duration` seconds of `clip_path` from `start` next to the clip
+///
+/// `fps`, `width` and `duration` are expected within the limits above.
+/// Returns the path of the animation.
+pub async fn export_animated(
+    clip_path: &Path,
+    start: f64,
+    duration: f64,
+    fps: u32,
+    width: u32,
+    format: AnimatedFormat,
+) -> Result<PathBuf> {
+    let output_path = output_path(clip_path, start, format);
+
+    let mut command = TokioCommand::new("ffmpeg");
+    command
+        .args(["-ss", &start.to_string(), "-t", &duration.to_string()])
+        .arg("-i")
+        .arg(clip_path)
+        .args(["-filter_complex", &format.filter(fps, width), "-an"])
+        .args(format.codec_args())
+        .arg("-y")
+        .arg(&output_path);
+
+    execute_ffmpeg_command(&mut command).await?;
+
+    info!(
+        "Exported {:.1}s of {:?} to {:?}",
+        duration, clip_path, output_path
+    );
+    Ok(output_path)
+}
+
+/// `clip_12.5s.gif` for an excerpt starting at 12.5s
+fn output_path(clip_path: &Path, start: f64, format: AnimatedFormat) -> PathBuf {
+    let stem = clip_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("clip");
+
+    clip_path.with_file_name(format!("{}_{:.1}s.{}", stem, start, format.extension()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter() {
+        let gif = AnimatedFormat::Gif.filter(15, 480);
+        assert!(gif.starts_with("fps=15,scale=480:-2:flags=lanczos,split[a][b]"));
+        assert!(gif.contains("palettegen"));
+        assert!(gif.contains("[b][p]paletteuse"));
+
+        assert_eq!(
+            AnimatedFormat::Webp.filter(20, 360),
+            "fps=20,scale=360:-2:flags=lanczos"
+        );
+    }
+
+    #[test]
+    fn test_output_path() {
+        let clip = Path::new("clips/game_1/baron_steal.mp4");
+        assert_eq!(
+            output_path(clip, 12.5, AnimatedFormat::Gif),
+            Path::new("clips/game_1/baron_steal_12.5s.gif")
+        );
+        assert_eq!(
+            output_path(clip, 0.0, AnimatedFormat::Webp),
+            Path::new("clips/game_1/baron_steal_0.0s.webp")
+        );
+    }
+}
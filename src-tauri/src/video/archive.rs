@@ -0,0 +1,258 @@
+//! Archiving old games for long-term storage
+//!
+//! Re-encodes a game's clips with a slow, high-compression preset and
+//! replaces the originals. Clips are marked as archived in their metadata so
+//! they're not re-encoded again.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::process::Command as TokioCommand;
+use tracing::{info, warn};
+
+use super::{execute_ffmpeg_command, Result, VideoError, VideoProcessor};
+use crate::storage::models_v2::VideoCodec;
+use crate::storage::{ClipMetadataV2, Storage};
+
+/// Archived clips may be this much shorter or longer than the original
+const DURATION_TOLERANCE_SECS: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArchiveCodec {
+    /// libx265, plays everywhere HEVC does
+    #[default]
+    Hevc,
+    /// SVT-AV1, smaller but slower to encode and decode
+    Av1,
+}
+
+impl ArchiveCodec {
+    fn encoder(self) -> &'static str {
+        match self {
+            ArchiveCodec::Hevc => "libx265",
+            ArchiveCodec::Av1 => "libsvtav1",
+        }
+    }
+
+    fn crf(self) -> u8 {
+        match self {
+            ArchiveCodec::Hevc => 28,
+            ArchiveCodec::Av1 => 35,
+        }
+    }
+
+    fn preset(self) -> &'static str {
+        match self {
+            ArchiveCodec::Hevc => "slow",
+            ArchiveCodec::Av1 => "6",
+        }
+    }
+
+    fn video_codec(self) -> VideoCodec {
+        match self {
+            ArchiveCodec::Hevc => VideoCodec::H265,
+            ArchiveCodec::Av1 => VideoCodec::Av1,
+        }
+    }
+}
+
+/// Result of archiving a game
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ArchiveReport {
+    pub game_id: String,
+    pub clips_archived: usize,
+    /// Missing, already archived, or not smaller after re-encoding
+    pub clips_skipped: usize,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
+    pub bytes_saved: u64,
+}
+
+/// Re-encode every clip of `game_id` with `codec`, replacing the originals
+pub async fn archive_game(
+    storage: &Storage,
+    game_id: &str,
+    codec: ArchiveCodec,
+) -> Result<ArchiveReport> {
+    let clips = storage
+        .load_clip_metadata(game_id)
+        .map_err(|e| VideoError::ProcessingError {
+            message: format!("Failed to load clips: {}", e),
+        })?;
+    if clips.is_empty() {
+        return Err(VideoError::NoClipsFound);
+    }
+
+    let processor = VideoProcessor::new();
+    let mut report = ArchiveReport {
+        game_id: game_id.to_string(),
+        ..Default::default()
+    };
+
+    for clip in clips {
+        let video_path = PathBuf::from(&clip.file_path);
+        let mut metadata = storage
+            .load_clip_metadata_v2(&clip.file_path)
+            .unwrap_or_else(|_| ClipMetadataV2::from(clip.clone()));
+        metadata.game_id = game_id.to_string();
+
+        if !video_path.exists() || metadata.archived_at.is_some() {
+            report.clips_skipped += 1;
+            continue;
+        }
+
+        let size_before = file_size(&video_path).await?;
+        let Some(size_after) = archive_clip(&processor, &video_path, codec).await? else {
+            report.clips_skipped += 1;
+            continue;
+        };
+
+        metadata.archived_at = Some(chrono::Utc::now());
+        metadata.video_info.codec = codec.video_codec();
+        metadata.video_info.encoder = codec.encoder().to_string();
+        metadata.video_info.crf = Some(codec.crf());
+        metadata.video_info.preset = Some(codec.preset().to_string());
+        metadata.video_info.file_size_bytes = size_after;
+        metadata.add_tag("archived".to_string());
+        storage
+            .save_clip_metadata_v2(game_id, &metadata)
+            .map_err(|e| VideoError::ProcessingError {
+                message: format!("Failed to save clip metadata: {}", e),
+            })?;
+
+        report.clips_archived += 1;
+        report.bytes_before += size_before;
+        report.bytes_after += size_after;
+    }
+
+    report.bytes_saved = report.bytes_before - report.bytes_after;
+    info!(
+        "Archived game {}: {} clips, saved {} MB",
+        game_id,
+        report.clips_archived,
+        report.bytes_saved / 1024 / 1024
+    );
+
+    Ok(report)
+}
+
+/// Re-encode one clip in place, returning its new size
+///
+/// The original is kept (and None returned) if the archived copy isn't
+/// smaller.
+async fn archive_clip(
+    processor: &VideoProcessor,
+    video_path: &Path,
+    codec: ArchiveCodec,
+) -> Result<Option<u64>> {
+    let temp_path = video_path.with_extension("archive.mp4");
+    let mut command = TokioCommand::new("ffmpeg");
+    command.args(archive_args(video_path, &temp_path, codec));
+
+    if let Err(e) = execute_ffmpeg_command(&mut command).await {
+        let _ = tokio::fs::remove_file(&temp_path).await;
+        return Err(e);
+    }
+
+    // Don't replace a clip with a truncated encode
+    let original_duration = processor.get_duration(video_path).await?;
+    let archived_duration = processor.get_duration(&temp_path).await?;
+    if (original_duration - archived_duration).abs() > DURATION_TOLERANCE_SECS {
+        let _ = tokio::fs::remove_file(&temp_path).await;
+        return Err(VideoError::ProcessingError {
+            message: format!(
+                "Archived clip is {:.1}s long, expected {:.1}s",
+                archived_duration, original_duration
+            ),
+        });
+    }
+
+    let size_before = file_size(video_path).await?;
+    let size_after = file_size(&temp_path).await?;
+    if size_after >= size_before {
+        warn!(
+            "Archiving {:?} wouldn't save space, keeping original",
+            video_path
+        );
+        let _ = tokio::fs::remove_file(&temp_path).await;
+        return Ok(None);
+    }
+
+    tokio::fs::rename(&temp_path, video_path)
+        .await
+        .map_err(|_| VideoError::FileAccessError {
+            path: video_path.display().to_string(),
+        })?;
+
+    Ok(Some(size_after))
+}
+
+fn archive_args(input: &Path, output: &Path, codec: ArchiveCodec) -> Vec<String> {
+    let mut args: Vec<String> = vec![
+        "-i".into(),
+        input.to_string_lossy().into(),
+        "-map".into(),
+        "0".into(),
+        "-c:v".into(),
+        codec.encoder().into(),
+        "-crf".into(),
+        codec.crf().to_string(),
+        "-preset".into(),
+        codec.preset().into(),
+    ];
+    if codec == ArchiveCodec::Hevc {
+        // Lets QuickTime/Safari play the file
+        args.extend(["-tag:v".into(), "hvc1".into()]);
+    }
+    args.extend([
+        "-c:a".into(),
+        "aac".into(),
+        "-b:a".into(),
+        "96k".into(),
+        "-map_metadata".into(),
+        "0".into(),
+        "-movflags".into(),
+        "+faststart".into(),
+        "-y".into(),
+        output.to_string_lossy().into(),
+    ]);
+    args
+}
+
+async fn file_size(path: &Path) -> Result<u64> {
+    tokio::fs::metadata(path)
+        .await
+        .map(|m| m.len())
+        .map_err(|_| VideoError::FileAccessError {
+            path: path.display().to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_args() {
+        let args = archive_args(
+            Path::new("in.mp4"),
+            Path::new("out.mp4"),
+            ArchiveCodec::Hevc,
+        );
+        assert_eq!(args.first().map(String::as_str), Some("-i"));
+        assert!(args.windows(2).any(|w| w == ["-c:v", "libx265"]));
+        assert!(args.windows(2).any(|w| w == ["-crf", "28"]));
+        assert!(args.windows(2).any(|w| w == ["-tag:v", "hvc1"]));
+        assert_eq!(args.last().map(String::as_str), Some("out.mp4"));
+
+        let args = archive_args(Path::new("in.mp4"), Path::new("out.mp4"), ArchiveCodec::Av1);
+        assert!(args.windows(2).any(|w| w == ["-c:v", "libsvtav1"]));
+        assert!(!args.iter().any(|a| a == "hvc1"));
+    }
+
+    #[test]
+    fn test_codec_deserializes_lowercase() {
+        let codec: ArchiveCodec = serde_json::from_str("\"av1\"").unwrap();
+        assert_eq!(codec, ArchiveCodec::Av1);
+    }
+}
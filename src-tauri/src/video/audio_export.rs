@@ -0,0 +1,125 @@
+//! Exporting a clip's audio on its own
+//!
+//! For voice-comm highlight reels and podcast snippets: the clip's audio is
+//! written next to it as MP3 or WAV. Recordings with both microphone and
+//! system audio carry the microphone on a second track as well
+//! (`MIC_TRACK_TITLE`), which can be exported alone.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::process::Command as TokioCommand;
+use tracing::info;
+
+use super::{execute_ffmpeg_command, Result, VideoError, VideoProcessor};
+use crate::recording::audio::MIC_TRACK_TITLE;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AudioExportFormat {
+    /// LAME VBR, small enough to share
+    #[default]
+    Mp3,
+    /// 16-bit PCM, for further editing
+    Wav,
+}
+
+impl AudioExportFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            AudioExportFormat::Mp3 => "mp3",
+            AudioExportFormat::Wav => "wav",
+        }
+    }
+
+    fn codec_args(self) -> &'static [&'static str] {
+        match self {
+            AudioExportFormat::Mp3 => &["-c:a", "libmp3lame", "-q:a", "2"],
+            AudioExportFormat::Wav => &["-c:a", "pcm_s16le"],
+        }
+    }
+}
+
+/// Write the audio of `clip_path` next to it, returning the new file
+///
+/// The first track is the full mix; with `mic_only` the microphone track is
+/// exported instead, which older recordings and clips without a microphone
+/// don't have.
+pub async fn extract_audio(
+    clip_path: &Path,
+    format: AudioExportFormat,
+    mic_only: bool,
+) -> Result<PathBuf> {
+    let titles = VideoProcessor::new().audio_track_titles(clip_path).await?;
+    let track = select_track(&titles, mic_only).ok_or_else(|| VideoError::ProcessingError {
+        message: if mic_only {
+            "This clip has no separate microphone track".to_string()
+        } else {
+            "This clip has no audio".to_string()
+        },
+    })?;
+
+    let output_path = output_path(clip_path, format, mic_only);
+    let mut command = TokioCommand::new("ffmpeg");
+    command
+        .arg("-i")
+        .arg(clip_path)
+        .args(["-map", &format!("0:a:{}", track), "-vn"])
+        .args(format.codec_args())
+        .arg("-y")
+        .arg(&output_path);
+
+    execute_ffmpeg_command(&mut command).await?;
+
+    info!("Exported audio of {:?} to {:?}", clip_path, output_path);
+    Ok(output_path)
+}
+
+/// Index among the audio tracks to export
+fn select_track(titles: &[String], mic_only: bool) -> Option<usize> {
+    if mic_only {
+        titles.iter().position(|title| title == MIC_TRACK_TITLE)
+    } else {
+        (!titles.is_empty()).then_some(0)
+    }
+}
+
+/// `clip.mp3`, or `clip_mic.mp3` for the microphone track
+fn output_path(clip_path: &Path, format: AudioExportFormat, mic_only: bool) -> PathBuf {
+    let stem = clip_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("clip");
+    let suffix = if mic_only { "_mic" } else { "" };
+
+    clip_path.with_file_name(format!("{}{}.{}", stem, suffix, format.extension()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_track() {
+        let mixed = vec![String::new(), MIC_TRACK_TITLE.to_string()];
+        assert_eq!(select_track(&mixed, false), Some(0));
+        assert_eq!(select_track(&mixed, true), Some(1));
+
+        // System audio only, or recorded before the separate track
+        let single = vec![String::new()];
+        assert_eq!(select_track(&single, true), None);
+        assert_eq!(select_track(&[], false), None);
+    }
+
+    #[test]
+    fn test_output_path() {
+        let clip = Path::new("clips/game_1/pentakill.mp4");
+        assert_eq!(
+            output_path(clip, AudioExportFormat::Mp3, false),
+            Path::new("clips/game_1/pentakill.mp3")
+        );
+        assert_eq!(
+            output_path(clip, AudioExportFormat::Wav, true),
+            Path::new("clips/game_1/pentakill_mic.wav")
+        );
+    }
+}
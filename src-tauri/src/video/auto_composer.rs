@@ -1,11 +1,22 @@
 #![allow(dead_code)]
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Notify, RwLock};
 use tracing::{info, warn};
 
-use super::{execute_ffmpeg_command, ClipInfo, Result, VideoError, VideoProcessor};
+use super::clip_cache::ClipCache;
+use super::framing::{Framing, KillFeedOverlay, ShortsLayout};
+use super::music::{MusicLibrary, MusicMood};
+use super::speed_ramp::{self, SpeedSegment};
+use super::{execute_ffmpeg_command_with_progress, ClipInfo, Result, VideoError, VideoProcessor};
+use crate::ddragon::{ChampionAssets, DataDragon};
+use crate::events::{AppEvent, EventBus};
+use crate::lcu::GameActivity;
+use crate::recording::facecam_path;
 use crate::storage::Storage;
 
 /// Configuration for auto-edit composition
@@ -28,6 +39,165 @@ pub struct AutoEditConfig {
 
     /// Audio mixing levels
     pub audio_levels: AudioLevels,
+
+    /// Per-clip loudness normalization target in LUFS (None = disabled)
+    #[serde(default)]
+    pub loudness_target: Option<f64>,
+
+    /// Compose the active player's death clips into a fails short instead
+    /// of highlights
+    #[serde(default)]
+    pub fails_only: bool,
+
+    /// How clips are fitted into the 9:16 frame
+    #[serde(default)]
+    pub framing: Framing,
+
+    /// Show the kill feed (or another part of the source frame) enlarged at
+    /// the top of the short
+    #[serde(default)]
+    pub kill_feed: Option<KillFeedOverlay>,
+
+    /// Branded clip played before the highlights
+    #[serde(default)]
+    pub intro_path: Option<String>,
+
+    /// Branded clip played after the highlights
+    #[serde(default)]
+    pub outro_path: Option<String>,
+
+    /// Subscribe card shown at the very end
+    #[serde(default)]
+    pub end_card: Option<EndCard>,
+
+    /// Slow motion around events and faster travel time, 0.0-1.0
+    /// (None = disabled)
+    #[serde(default)]
+    pub speed_ramp: Option<f64>,
+
+    /// 3D LUT (.cube) color grading the gameplay, overriding the canvas
+    /// template's
+    #[serde(default)]
+    pub color_lut: Option<String>,
+
+    /// User whose clip selection feedback is recorded and applied (set by
+    /// `start_auto_edit`)
+    #[serde(default)]
+    pub user_id: Option<String>,
+}
+
+/// A 60 second highlights short with no options enabled
+impl Default for AutoEditConfig {
+    fn default() -> Self {
+        Self {
+            target_duration: 60,
+            game_ids: Vec::new(),
+            selected_clip_ids: None,
+            canvas_template: None,
+            background_music: None,
+            audio_levels: AudioLevels::default(),
+            loudness_target: None,
+            fails_only: false,
+            framing: Framing::default(),
+            kill_feed: None,
+            intro_path: None,
+            outro_path: None,
+            end_card: None,
+            speed_ramp: None,
+            color_lut: None,
+            user_id: None,
+        }
+    }
+}
+
+/// Generated end card asking viewers to subscribe
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndCard {
+    pub channel_name: String,
+    #[serde(default = "default_call_to_action")]
+    pub call_to_action: String,
+    /// Channel logo shown above the text
+    #[serde(default)]
+    pub logo_path: Option<String>,
+    /// FFmpeg color name or hex code
+    #[serde(default = "default_end_card_background")]
+    pub background: String,
+    #[serde(default = "default_end_card_font")]
+    pub font: String,
+    /// Seconds
+    #[serde(default = "default_end_card_duration")]
+    pub duration: f64,
+}
+
+fn default_call_to_action() -> String {
+    "Subscribe for more!".to_string()
+}
+
+fn default_end_card_background() -> String {
+    "black".to_string()
+}
+
+fn default_end_card_font() -> String {
+    "Arial".to_string()
+}
+
+fn default_end_card_duration() -> f64 {
+    3.0
+}
+
+impl EndCard {
+    /// Canvas drawn over the plain background
+    fn template(&self) -> CanvasTemplate {
+        let fade_in = |start: f64| ElementAnimation {
+            start,
+            fade_in: Some(0.5),
+            ..Default::default()
+        };
+
+        let mut elements = vec![
+            CanvasElement::Text {
+                id: "end_card_cta".to_string(),
+                content: self.call_to_action.clone(),
+                font: self.font.clone(),
+                size: 80,
+                color: "white".to_string(),
+                outline: Some("black".to_string()),
+                position: Position { x: 8.0, y: 45.0 },
+                animation: Some(fade_in(0.0)),
+            },
+            CanvasElement::Text {
+                id: "end_card_channel".to_string(),
+                content: self.channel_name.clone(),
+                font: self.font.clone(),
+                size: 56,
+                color: "#FFD700".to_string(),
+                outline: Some("black".to_string()),
+                position: Position { x: 8.0, y: 53.0 },
+                animation: Some(fade_in(0.3)),
+            },
+        ];
+        if let Some(logo) = &self.logo_path {
+            // 360px wide, centered
+            elements.push(CanvasElement::Image {
+                id: "end_card_logo".to_string(),
+                path: logo.clone(),
+                width: 360,
+                height: 360,
+                position: Position { x: 33.3, y: 22.0 },
+                animation: None,
+            });
+        }
+
+        CanvasTemplate {
+            id: "end_card".to_string(),
+            name: "End card".to_string(),
+            background: BackgroundLayer::Color {
+                value: self.background.clone(),
+            },
+            elements,
+            color_lut: None,
+        }
+    }
 }
 
 /// Canvas template for overlays
@@ -37,14 +207,140 @@ pub struct CanvasTemplate {
     pub name: String,
     pub background: BackgroundLayer,
     pub elements: Vec<CanvasElement>,
+    /// 3D LUT (.cube) giving every short made with the template the same look
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color_lut: Option<String>,
+}
+
+impl CanvasTemplate {
+    /// Whether the template has a slot for the webcam recording
+    pub fn has_facecam(&self) -> bool {
+        self.elements
+            .iter()
+            .any(|element| matches!(element, CanvasElement::Facecam { .. }))
+    }
+
+    /// Whether the template has slots for the champion's art
+    pub fn has_champion_slots(&self) -> bool {
+        matches!(self.background, BackgroundLayer::ChampionSplash)
+            || self
+                .elements
+                .iter()
+                .any(|element| matches!(element, CanvasElement::ChampionIcon { .. }))
+    }
+
+    /// The template with its champion slots showing `assets`
+    pub fn with_champion(&self, assets: &ChampionAssets) -> CanvasTemplate {
+        let mut template = self.clone();
+
+        if matches!(template.background, BackgroundLayer::ChampionSplash) {
+            template.background = BackgroundLayer::Image {
+                path: assets.splash_path.clone(),
+            };
+        }
+        for element in &mut template.elements {
+            if let CanvasElement::ChampionIcon {
+                id,
+                width,
+                height,
+                position,
+                animation,
+            } = element
+            {
+                *element = CanvasElement::Image {
+                    id: id.clone(),
+                    path: assets.icon_path.clone(),
+                    width: *width,
+                    height: *height,
+                    position: position.clone(),
+                    animation: animation.clone(),
+                };
+            }
+        }
+
+        template
+    }
+}
+
+/// `template` with its champion slots filled for the champion of `game_ids`
+///
+/// Slots stay empty (and are skipped when drawing) if no game names a
+/// champion or its art can't be downloaded.
+pub async fn fill_champion_slots(
+    template: &CanvasTemplate,
+    storage: &Storage,
+    ddragon: &DataDragon,
+    game_ids: &[String],
+) -> CanvasTemplate {
+    if !template.has_champion_slots() {
+        return template.clone();
+    }
+
+    let Some(champion) = main_champion(storage, game_ids) else {
+        warn!("No champion recorded for the games, leaving champion slots empty");
+        return template.clone();
+    };
+
+    match ddragon.champion_assets(&champion).await {
+        Ok(assets) => {
+            info!(
+                "Canvas champion slots: {} ({})",
+                assets.name, assets.version
+            );
+            template.with_champion(&assets)
+        }
+        Err(e) => {
+            warn!(
+                "No art for {}, leaving champion slots empty: {:#}",
+                champion, e
+            );
+            template.clone()
+        }
+    }
+}
+
+/// Champion played in most of `game_ids` (the earliest one on a tie)
+fn main_champion(storage: &Storage, game_ids: &[String]) -> Option<String> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for game in game_ids
+        .iter()
+        .filter_map(|game_id| storage.load_game_metadata(game_id).ok())
+    {
+        if game.champion.is_empty() || game.champion == "Unknown" {
+            continue;
+        }
+        match counts
+            .iter_mut()
+            .find(|(champion, _)| *champion == game.champion)
+        {
+            Some((_, count)) => *count += 1,
+            None => counts.push((game.champion, 1)),
+        }
+    }
+
+    // max_by_key keeps the last maximum, so search from the end
+    counts
+        .into_iter()
+        .rev()
+        .max_by_key(|(_, count)| *count)
+        .map(|(champion, _)| champion)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "lowercase")]
 pub enum BackgroundLayer {
-    Color { value: String },
-    Gradient { value: String },
-    Image { path: String },
+    Color {
+        value: String,
+    },
+    Gradient {
+        value: String,
+    },
+    Image {
+        path: String,
+    },
+    /// Splash art of the champion played, filled in at compose time
+    #[serde(rename = "champion_splash")]
+    ChampionSplash,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -58,6 +354,9 @@ pub enum CanvasElement {
         color: String,
         outline: Option<String>,
         position: Position,
+        /// Optional animation (static for the whole video when absent)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        animation: Option<ElementAnimation>,
     },
     Image {
         id: String,
@@ -65,9 +364,176 @@ pub enum CanvasElement {
         width: u32,
         height: u32,
         position: Position,
+        /// Optional animation (static for the whole video when absent)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        animation: Option<ElementAnimation>,
+    },
+    /// Icon of the champion played, filled in at compose time
+    #[serde(rename = "champion_icon")]
+    ChampionIcon {
+        id: String,
+        width: u32,
+        height: u32,
+        position: Position,
+        /// Optional animation (static for the whole video when absent)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        animation: Option<ElementAnimation>,
+    },
+    /// Webcam recorded alongside the clips, skipped if any clip has none
+    Facecam {
+        id: String,
+        width: u32,
+        height: u32,
+        position: Position,
     },
 }
 
+/// Animation descriptor for a canvas element
+///
+/// All times are in seconds relative to the start of the composed video.
+/// The descriptor is translated into FFmpeg `enable`/expression arguments
+/// by `apply_canvas_overlay`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ElementAnimation {
+    /// Time at which the element appears
+    #[serde(default)]
+    pub start: f64,
+    /// How long the element stays visible (until the end of the video if None)
+    #[serde(default)]
+    pub duration: Option<f64>,
+    /// Fade-in duration
+    #[serde(default)]
+    pub fade_in: Option<f64>,
+    /// Fade-out duration (requires `duration`)
+    #[serde(default)]
+    pub fade_out: Option<f64>,
+    /// Slide in from a frame edge
+    #[serde(default)]
+    pub slide: Option<SlideAnimation>,
+    /// Scale from a factor up to the element's natural size
+    #[serde(default)]
+    pub scale: Option<ScaleAnimation>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlideAnimation {
+    /// Edge the element enters from
+    pub from: SlideDirection,
+    /// Slide duration in seconds
+    pub duration: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SlideDirection {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScaleAnimation {
+    /// Initial scale factor (e.g. 0.5 = half size)
+    pub from: f32,
+    /// Scale duration in seconds
+    pub duration: f64,
+}
+
+impl ElementAnimation {
+    /// End time of the element, if bounded
+    pub fn end_time(&self) -> Option<f64> {
+        self.duration.map(|d| self.start + d.max(0.0))
+    }
+
+    /// FFmpeg `enable` expression limiting when the element is drawn
+    pub fn enable_expr(&self) -> String {
+        match self.end_time() {
+            Some(end) => format!("between(t,{:.3},{:.3})", self.start, end),
+            None => format!("gte(t,{:.3})", self.start),
+        }
+    }
+
+    /// FFmpeg alpha expression (0.0-1.0) for fade in/out, None if no fades
+    pub fn alpha_expr(&self) -> Option<String> {
+        let mut terms = Vec::new();
+
+        if let Some(fade_in) = self.fade_in.filter(|d| *d > 0.0) {
+            terms.push(format!("(t-{:.3})/{:.3}", self.start, fade_in));
+        }
+
+        if let (Some(fade_out), Some(end)) = (self.fade_out.filter(|d| *d > 0.0), self.end_time()) {
+            terms.push(format!("({:.3}-t)/{:.3}", end, fade_out));
+        }
+
+        match terms.len() {
+            0 => None,
+            1 => Some(format!("clip({},0,1)", terms[0])),
+            _ => Some(format!("clip(min({},{}),0,1)", terms[0], terms[1])),
+        }
+    }
+
+    /// Linear 0→1 progress expression over `duration` seconds from start
+    fn progress_expr(&self, duration: f64) -> String {
+        if duration <= 0.0 {
+            return "1".to_string();
+        }
+        format!("clip((t-{:.3})/{:.3},0,1)", self.start, duration)
+    }
+
+    /// FFmpeg X coordinate expression for a base pixel position
+    pub fn x_expr(&self, base: u32, frame_width: u32) -> String {
+        match &self.slide {
+            Some(slide) if slide.from == SlideDirection::Left => format!(
+                "{}-{}*(1-{})",
+                base,
+                frame_width,
+                self.progress_expr(slide.duration)
+            ),
+            Some(slide) if slide.from == SlideDirection::Right => format!(
+                "{}+{}*(1-{})",
+                base,
+                frame_width,
+                self.progress_expr(slide.duration)
+            ),
+            _ => base.to_string(),
+        }
+    }
+
+    /// FFmpeg Y coordinate expression for a base pixel position
+    pub fn y_expr(&self, base: u32, frame_height: u32) -> String {
+        match &self.slide {
+            Some(slide) if slide.from == SlideDirection::Top => format!(
+                "{}-{}*(1-{})",
+                base,
+                frame_height,
+                self.progress_expr(slide.duration)
+            ),
+            Some(slide) if slide.from == SlideDirection::Bottom => format!(
+                "{}+{}*(1-{})",
+                base,
+                frame_height,
+                self.progress_expr(slide.duration)
+            ),
+            _ => base.to_string(),
+        }
+    }
+
+    /// FFmpeg expression for `value` scaled by the scale animation, None if not scaling
+    pub fn scale_expr(&self, value: u32) -> Option<String> {
+        self.scale.as_ref().map(|scale| {
+            let from = scale.from.max(0.0);
+            format!(
+                "{}*({:.3}+{:.3}*{})",
+                value,
+                from,
+                1.0 - from,
+                self.progress_expr(scale.duration)
+            )
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Position {
     /// X position as percentage (0-100)
@@ -78,10 +544,17 @@ pub struct Position {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BackgroundMusic {
-    /// Path to MP3 file
+    /// Path to MP3 file (ignored with `surprise_me`)
+    #[serde(default)]
     pub file_path: String,
     /// Whether to loop music if shorter than video
     pub loop_music: bool,
+    /// Pick a track from the music library instead of `file_path`
+    #[serde(default)]
+    pub surprise_me: bool,
+    /// Mood to pick with `surprise_me` (None = any)
+    #[serde(default)]
+    pub mood: Option<MusicMood>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -90,6 +563,9 @@ pub struct AudioLevels {
     pub game_audio: u32,
     /// Background music volume (0-100)
     pub background_music: u32,
+    /// Dip the music while game audio is loud (voice lines, kill sounds)
+    #[serde(default)]
+    pub ducking: bool,
 }
 
 impl Default for AudioLevels {
@@ -97,6 +573,7 @@ impl Default for AudioLevels {
         Self {
             game_audio: 60,
             background_music: 80,
+            ducking: false,
         }
     }
 }
@@ -159,6 +636,16 @@ pub struct AutoComposer {
     video_processor: Arc<VideoProcessor>,
     storage: Arc<Storage>,
     progress: Arc<RwLock<Option<AutoEditProgress>>>,
+    events: Arc<EventBus>,
+    game_activity: Option<Arc<GameActivity>>,
+    /// Jobs held by [`Self::wait_for_game_end`], released by [`Self::run_anyway`]
+    waiting: Mutex<HashMap<String, Arc<Notify>>>,
+    /// Where finished videos are moved (None = leave them in the temp folder)
+    output_dir: RwLock<Option<PathBuf>>,
+    /// Trimmed and normalized clips reused across jobs
+    clip_cache: Option<ClipCache>,
+    /// Champion art for canvas templates with champion slots
+    ddragon: Option<Arc<DataDragon>>,
 }
 
 impl AutoComposer {
@@ -168,6 +655,97 @@ impl AutoComposer {
             video_processor,
             storage,
             progress: Arc::new(RwLock::new(None)),
+            events: Arc::new(EventBus::new()),
+            game_activity: None,
+            waiting: Mutex::new(HashMap::new()),
+            output_dir: RwLock::new(None),
+            clip_cache: None,
+            ddragon: None,
+        }
+    }
+
+    /// Publish progress updates on `events`
+    pub fn with_events(mut self, events: Arc<EventBus>) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Keep finished videos in `output_dir`
+    pub fn with_output_dir(mut self, output_dir: PathBuf) -> Self {
+        self.output_dir = RwLock::new(Some(output_dir));
+        self
+    }
+
+    /// Change where finished videos are kept (takes effect on the next job)
+    pub async fn set_output_dir(&self, output_dir: PathBuf) {
+        *self.output_dir.write().await = Some(output_dir);
+    }
+
+    /// Reuse trimmed and normalized clips from `cache`
+    pub fn with_clip_cache(mut self, cache: ClipCache) -> Self {
+        self.clip_cache = Some(cache);
+        self
+    }
+
+    /// Fill the champion slots of canvas templates with art from `ddragon`
+    pub fn with_ddragon(mut self, ddragon: Arc<DataDragon>) -> Self {
+        self.ddragon = Some(ddragon);
+        self
+    }
+
+    /// Let [`Self::wait_for_game_end`] see whether a match is running
+    pub fn with_game_activity(mut self, activity: Arc<GameActivity>) -> Self {
+        self.game_activity = Some(activity);
+        self
+    }
+
+    /// Hold a job in the queued state until the current game ends, or until
+    /// [`Self::run_anyway`] releases it
+    ///
+    /// Returns immediately if no game is running.
+    pub async fn wait_for_game_end(&self, job_id: &str) {
+        let Some(activity) = &self.game_activity else {
+            return;
+        };
+        if !activity.is_in_game() {
+            return;
+        }
+
+        info!("Game in progress, deferring auto-edit job {}", job_id);
+        self.update_progress(
+            job_id,
+            AutoEditStatus::Queued,
+            0.0,
+            "Waiting for the current game to finish...".to_string(),
+        )
+        .await;
+
+        let release = Arc::new(Notify::new());
+        self.waiting
+            .lock()
+            .insert(job_id.to_string(), Arc::clone(&release));
+
+        tokio::select! {
+            _ = activity.wait_until_idle() => {}
+            _ = release.notified() => {
+                info!("Running auto-edit job {} despite game in progress", job_id);
+            }
+        }
+
+        self.waiting.lock().remove(job_id);
+    }
+
+    /// Let a job held by [`Self::wait_for_game_end`] run during the game
+    ///
+    /// Returns false if no job with that ID is waiting.
+    pub fn run_anyway(&self, job_id: &str) -> bool {
+        match self.waiting.lock().get(job_id) {
+            Some(release) => {
+                // Stores a permit if the job hasn't started waiting yet
+                release.notify_one();
+                true
+            }
+            None => false,
         }
     }
 
@@ -238,6 +816,42 @@ impl AutoComposer {
             .prepare_clips(&selected_clips, config.target_duration)
             .await?;
 
+        // Step 3.2: Slow down kills and speed up travel time
+        let prepared_clips = if let Some(intensity) = config.speed_ramp {
+            self.update_progress(
+                &job_id,
+                AutoEditStatus::Processing,
+                45.0,
+                "Applying speed ramps...".to_string(),
+            )
+            .await;
+
+            self.ramp_clips(
+                &selected_clips,
+                &prepared_clips,
+                config.target_duration,
+                intensity,
+            )
+            .await?
+        } else {
+            prepared_clips
+        };
+
+        // Step 3.5: Normalize clip loudness so volume doesn't jump between clips
+        let prepared_clips = if let Some(target_lufs) = config.loudness_target {
+            self.update_progress(
+                &job_id,
+                AutoEditStatus::Processing,
+                50.0,
+                format!("Normalizing audio to {} LUFS...", target_lufs),
+            )
+            .await;
+
+            self.normalize_clips(&prepared_clips, target_lufs).await?
+        } else {
+            prepared_clips
+        };
+
         // Step 4: Concatenate clips (60% progress)
         self.update_progress(
             &job_id,
@@ -247,7 +861,17 @@ impl AutoComposer {
         )
         .await;
 
-        let concatenated_path = self.concatenate_clips(&prepared_clips).await?;
+        let concatenated_path = self
+            .concatenate_clips(
+                &prepared_clips,
+                ShortsLayout {
+                    framing: config.framing,
+                    kill_feed: config.kill_feed,
+                    color_lut: color_lut(&config),
+                },
+                self.step_progress(&job_id, 60.0, 75.0, "Concatenating clips"),
+            )
+            .await?;
 
         // Step 5: Apply canvas overlay (75% progress)
         self.update_progress(
@@ -259,8 +883,31 @@ impl AutoComposer {
         .await;
 
         let with_overlay = if let Some(canvas) = &config.canvas_template {
-            self.apply_canvas_overlay(&concatenated_path, canvas)
-                .await?
+            let canvas = match &self.ddragon {
+                Some(ddragon) => {
+                    fill_champion_slots(canvas, &self.storage, ddragon, &config.game_ids).await
+                }
+                None => canvas.clone(),
+            };
+
+            let facecam = if canvas.has_facecam() {
+                self.build_facecam_track(&selected_clips, config.target_duration, config.speed_ramp)
+                    .await
+                    .unwrap_or_else(|e| {
+                        warn!("Skipping facecam overlay: {}", e);
+                        None
+                    })
+            } else {
+                None
+            };
+
+            self.apply_canvas_overlay(
+                &concatenated_path,
+                &canvas,
+                facecam.as_deref(),
+                self.step_progress(&job_id, 75.0, 90.0, "Applying canvas overlay"),
+            )
+            .await?
         } else {
             concatenated_path
         };
@@ -275,12 +922,51 @@ impl AutoComposer {
         .await;
 
         let final_path = if let Some(music) = &config.background_music {
-            self.mix_audio(&with_overlay, music, &config.audio_levels)
-                .await?
+            self.mix_audio(
+                &with_overlay,
+                music,
+                &config.audio_levels,
+                self.step_progress(&job_id, 90.0, 97.0, "Mixing audio"),
+            )
+            .await?
         } else {
             with_overlay
         };
 
+        // Step 6.5: Add intro, outro and end card (97% progress)
+        let has_bumpers =
+            config.intro_path.is_some() || config.outro_path.is_some() || config.end_card.is_some();
+        let final_path = if has_bumpers {
+            self.update_progress(
+                &job_id,
+                AutoEditStatus::Processing,
+                97.0,
+                "Adding intro and outro...".to_string(),
+            )
+            .await;
+
+            let end_card = match &config.end_card {
+                Some(card) => Some(self.render_end_card(card).await?),
+                None => None,
+            };
+            let outros: Vec<&Path> = [config.outro_path.as_deref().map(Path::new)]
+                .into_iter()
+                .chain([end_card.as_deref()])
+                .flatten()
+                .collect();
+
+            self.stitch_bumpers(
+                &final_path,
+                config.intro_path.as_deref().map(Path::new),
+                &outros,
+                self.step_progress(&job_id, 97.0, 99.0, "Adding intro and outro"),
+            )
+            .await?
+        } else {
+            final_path
+        };
+        let final_path = self.move_to_output_dir(&final_path, &job_id).await?;
+
         // Step 7: Get final duration
         let total_duration = self.video_processor.get_duration(&final_path).await?;
 
@@ -306,6 +992,7 @@ impl AutoComposer {
             job_id: job_id.clone(),
             output_path: final_path.to_string_lossy().to_string(),
             thumbnail_path: None, // TODO: Generate thumbnail
+            upload_thumbnail_path: None,
             created_at: chrono::Utc::now(),
             duration: total_duration,
             clip_count: prepared_clips.len(),
@@ -365,12 +1052,31 @@ impl AutoComposer {
                 return Err(VideoError::NoClipsFound);
             }
 
+            if let Some(user_id) = &config.user_id {
+                let mut games = config.game_ids.clone();
+                games.sort();
+                self.record_selection_feedback(user_id, &games.join(","), all_clips, selected_ids);
+            }
+
             return Ok(selected);
         }
 
-        // Auto-selection based on priority
-        let mut sorted_clips = all_clips.to_vec();
-        sorted_clips.sort_by(|a, b| b.priority.cmp(&a.priority)); // Descending priority
+        // Auto-selection based on highlight score, weighted by what the user
+        // usually keeps, keeping deaths and highlights apart
+        let feedback = config
+            .user_id
+            .as_deref()
+            .and_then(|user_id| self.storage.load_selection_feedback(user_id).ok())
+            .unwrap_or_default();
+        let weighted_score =
+            |c: &ClipInfo| c.highlight_score() as f64 * feedback.weight(&c.event_type);
+
+        let mut sorted_clips: Vec<ClipInfo> = all_clips
+            .iter()
+            .filter(|c| (c.event_type == "Death") == config.fails_only)
+            .cloned()
+            .collect();
+        sorted_clips.sort_by(|a, b| weighted_score(b).total_cmp(&weighted_score(a)));
 
         let target_duration = config.target_duration as f64;
         let buffer_duration = target_duration * 0.9; // Reserve 10% for transitions/padding
@@ -407,6 +1113,33 @@ impl AutoComposer {
         Ok(selected)
     }
 
+    /// Count which event types the user kept and left out of a manual
+    /// selection from `games`
+    fn record_selection_feedback(
+        &self,
+        user_id: &str,
+        games: &str,
+        all_clips: &[ClipInfo],
+        selected_ids: &[i64],
+    ) {
+        let result = self
+            .storage
+            .load_selection_feedback(user_id)
+            .and_then(|mut feedback| {
+                feedback.record(
+                    games,
+                    all_clips
+                        .iter()
+                        .map(|clip| (clip.event_type.as_str(), selected_ids.contains(&clip.id))),
+                );
+                self.storage.save_selection_feedback(user_id, &feedback)
+            });
+
+        if let Err(e) = result {
+            warn!("Failed to record clip selection feedback: {}", e);
+        }
+    }
+
     /// Prepare clips by trimming to fit target duration
     ///
     /// This function intelligently trims clips if the total duration exceeds
@@ -478,30 +1211,32 @@ impl AutoComposer {
             }
 
             let clip_duration = clip.duration.unwrap_or(10.0);
-            let trimmed_duration = (clip_duration * trim_factor).max(3.0); // Minimum 3 seconds
-
-            // If trimming saves less than 0.5 seconds, use original
-            if (clip_duration - trimmed_duration).abs() < 0.5 {
+            let Some((start_time, trimmed_duration)) = trim_window(clip_duration, trim_factor)
+            else {
                 info!(
                     "Clip {} ({:.1}s): using original (trimming saves <0.5s)",
                     idx, clip_duration
                 );
                 prepared_paths.push(input_path);
                 continue;
-            }
+            };
 
-            // Trim the clip from the center to preserve important moments
-            let start_time = (clip_duration - trimmed_duration) / 2.0;
             let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-            let output_path = output_dir.join(format!("trimmed_{}_{}.mp4", idx, timestamp));
+            let temp_path = output_dir.join(format!("trimmed_{}_{}.mp4", idx, timestamp));
 
             info!(
                 "Clip {}: trimming from {:.1}s to {:.1}s (start={:.1}s)",
                 idx, clip_duration, trimmed_duration, start_time
             );
 
-            self.video_processor
-                .extract_clip(&input_path, &output_path, start_time, trimmed_duration)
+            let input = input_path.as_path();
+            let params = format!("trim:{:.3}:{:.3}", start_time, trimmed_duration);
+            let output_path = self
+                .cached(input, &params, temp_path, |output| async move {
+                    self.video_processor
+                        .extract_clip(input, &output, start_time, trimmed_duration)
+                        .await
+                })
                 .await
                 .map_err(|e| VideoError::ProcessingError {
                     message: format!("Failed to trim clip {}: {}", idx, e),
@@ -519,66 +1254,365 @@ impl AutoComposer {
         Ok(prepared_paths)
     }
 
-    /// Concatenate multiple clips
-    async fn concatenate_clips(&self, clip_paths: &[PathBuf]) -> Result<PathBuf> {
+    /// Trim, speed ramp and join the clips' webcam recordings to match the
+    /// composed video
+    ///
+    /// Returns None if any clip was recorded without a facecam.
+    async fn build_facecam_track(
+        &self,
+        clips: &[ClipInfo],
+        target_duration: u32,
+        speed_ramp: Option<f64>,
+    ) -> Result<Option<PathBuf>> {
+        let sources: Vec<PathBuf> = clips
+            .iter()
+            .map(|clip| facecam_path(Path::new(&clip.file_path)))
+            .collect();
+        if let Some(missing) = sources.iter().find(|path| !path.exists()) {
+            info!("No facecam recording at {:?}, skipping overlay", missing);
+            return Ok(None);
+        }
+
         let output_dir = std::env::temp_dir().join("lolshorts_auto_edit");
         tokio::fs::create_dir_all(&output_dir)
             .await
             .map_err(|e| VideoError::ProcessingError {
                 message: format!("Failed to create temp directory: {}", e),
             })?;
-
         let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-        let output_path = output_dir.join(format!("concatenated_{}.mp4", timestamp));
 
-        // Use VideoProcessor to compose clips into 9:16 format
-        self.video_processor
-            .compose_shorts(clip_paths, &output_path, 1080, 1920)
+        let plan = trim_plan(clips, target_duration);
+        let mut pieces = Vec::with_capacity(sources.len());
+        for (idx, ((clip, source), window)) in clips.iter().zip(&sources).zip(plan).enumerate() {
+            let piece = match window {
+                Some((start_time, duration)) => {
+                    let temp_path =
+                        output_dir.join(format!("facecam_trimmed_{}_{}.mp4", idx, timestamp));
+                    let params = format!("trim:{:.3}:{:.3}", start_time, duration);
+                    self.cached(source, &params, temp_path, |output| async move {
+                        self.video_processor
+                            .extract_clip(source, &output, start_time, duration)
+                            .await
+                    })
+                    .await?
+                }
+                None => source.clone(),
+            };
+
+            // Ramped like the gameplay so the two stay in sync
+            let segments = match speed_ramp {
+                Some(intensity) => self.speed_segments(clip, window, intensity),
+                None => Vec::new(),
+            };
+            if segments.is_empty() {
+                pieces.push(piece);
+                continue;
+            }
+
+            let temp_path = output_dir.join(format!("facecam_ramped_{}_{}.mp4", idx, timestamp));
+            pieces.push(self.apply_ramp(&piece, &segments, temp_path).await?);
+        }
+
+        let concat_file = output_dir.join(format!("facecam_list_{}.txt", timestamp));
+        let concat_content: String = pieces
+            .iter()
+            .map(|p| format!("file '{}'\n", p.to_string_lossy()))
+            .collect();
+        tokio::fs::write(&concat_file, concat_content)
             .await
+            .map_err(|e| VideoError::ProcessingError {
+                message: format!("Failed to write concat file: {}", e),
+            })?;
+
+        let output_path = output_dir.join(format!("facecam_{}.mp4", timestamp));
+        let mut command = tokio::process::Command::new("ffmpeg");
+        command
+            .args(["-f", "concat", "-safe", "0", "-i"])
+            .arg(&concat_file)
+            .args(["-an", "-c:v", "libx264", "-preset", "veryfast"])
+            .args(["-crf", "23", "-y"])
+            .arg(&output_path);
+
+        let result = execute_ffmpeg_command_with_progress(
+            &mut command,
+            "facecam_track",
+            0.0,
+            &self.events,
+            |_| {},
+        )
+        .await;
+        let _ = tokio::fs::remove_file(&concat_file).await;
+        result?;
+
+        Ok(Some(output_path))
     }
 
-    /// Apply canvas overlay (background + text + images)
-    ///
-    /// Creates a complex FFmpeg filter chain to apply:
-    /// 1. Background layer (color, gradient, or image)
-    /// 2. Text overlays with positioning
-    /// 3. Image overlays with positioning
+    /// Speed ramp each prepared clip around the events in its metadata
     ///
-    /// All positions are percentage-based (0-100) and converted to 1080x1920 pixels.
-    async fn apply_canvas_overlay(
+    /// Clips without v2 metadata or events are left as they are.
+    async fn ramp_clips(
         &self,
-        video_path: &Path,
-        canvas: &CanvasTemplate,
-    ) -> Result<PathBuf> {
+        clips: &[ClipInfo],
+        clip_paths: &[PathBuf],
+        target_duration: u32,
+        intensity: f64,
+    ) -> Result<Vec<PathBuf>> {
         let output_dir = std::env::temp_dir().join("lolshorts_auto_edit");
-        tokio::fs::create_dir_all(&output_dir).await.map_err(|e| {
-            VideoError::CanvasApplicationError {
-                reason: format!("Failed to create temp directory: {}", e),
-            }
-        })?;
+        tokio::fs::create_dir_all(&output_dir)
+            .await
+            .map_err(|e| VideoError::ProcessingError {
+                message: format!("Failed to create temp directory: {}", e),
+            })?;
 
         let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
-        let output_path = output_dir.join(format!("with_canvas_{}.mp4", timestamp));
+        let plan = trim_plan(clips, target_duration);
+        let mut ramped = Vec::with_capacity(clip_paths.len());
 
-        info!("Applying canvas template: {}", canvas.name);
+        for (idx, ((clip, path), window)) in clips.iter().zip(clip_paths).zip(plan).enumerate() {
+            let segments = self.speed_segments(clip, window, intensity);
+            if segments.is_empty() {
+                ramped.push(path.clone());
+                continue;
+            }
 
-        // YouTube Shorts dimensions
-        const WIDTH: u32 = 1080;
-        const HEIGHT: u32 = 1920;
+            let temp_path = output_dir.join(format!("ramped_{}_{}.mp4", idx, timestamp));
+            ramped.push(self.apply_ramp(path, &segments, temp_path).await?);
+        }
 
-        // Build FFmpeg filter chain
-        let mut filter_parts = Vec::new();
+        Ok(ramped)
+    }
 
-        // Step 1: Apply background layer
-        match &canvas.background {
-            BackgroundLayer::Color { value } => {
-                // Create solid color background
-                info!("Canvas background: solid color {}", value);
-                filter_parts.push(format!("color=c={}:s={}x{}:d=1[bg]", value, WIDTH, HEIGHT));
-                filter_parts.push("[0:v][bg]overlay=shortest=1".to_string());
-            }
-            BackgroundLayer::Gradient { value } => {
-                // For gradient, we'll use a simple vertical gradient
+    /// Speed ramp around the events of `clip`, trimmed to `window`
+    ///
+    /// Empty if the clip has no v2 metadata or no events to ramp around.
+    fn speed_segments(
+        &self,
+        clip: &ClipInfo,
+        window: Option<(f64, f64)>,
+        intensity: f64,
+    ) -> Vec<SpeedSegment> {
+        let Ok(metadata) = self.storage.load_clip_metadata_v2(&clip.file_path) else {
+            return Vec::new();
+        };
+
+        // Event times are relative to the untrimmed clip
+        let (offset, duration) =
+            window.unwrap_or((0.0, clip.duration.unwrap_or(metadata.clip_duration)));
+        let events: Vec<f64> = metadata
+            .get_all_events()
+            .iter()
+            .map(|event| event.clip_timestamp - offset)
+            .collect();
+
+        speed_ramp::plan(duration, &events, intensity)
+    }
+
+    /// Speed ramp `path` into `temp_path` (or the clip cache)
+    async fn apply_ramp(
+        &self,
+        path: &Path,
+        segments: &[SpeedSegment],
+        temp_path: PathBuf,
+    ) -> Result<PathBuf> {
+        let params = segments
+            .iter()
+            .map(|s| format!("{:.3}-{:.3}x{:.3}", s.start, s.end, s.speed))
+            .collect::<Vec<_>>()
+            .join(",");
+        let params = format!("speed:{}", params);
+        self.cached(path, &params, temp_path, |output| async move {
+            self.video_processor
+                .apply_speed_ramp(path, &output, segments)
+                .await
+        })
+        .await
+    }
+
+    /// Normalize the loudness of each prepared clip (EBU R128)
+    ///
+    /// Clips are normalized individually before concatenation so that quiet and
+    /// loud moments end up at a consistent level in the final short.
+    async fn normalize_clips(
+        &self,
+        clip_paths: &[PathBuf],
+        target_lufs: f64,
+    ) -> Result<Vec<PathBuf>> {
+        let output_dir = std::env::temp_dir().join("lolshorts_auto_edit");
+        tokio::fs::create_dir_all(&output_dir)
+            .await
+            .map_err(|e| VideoError::ProcessingError {
+                message: format!("Failed to create temp directory: {}", e),
+            })?;
+
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let mut normalized = Vec::with_capacity(clip_paths.len());
+
+        for (idx, path) in clip_paths.iter().enumerate() {
+            let temp_path = output_dir.join(format!("normalized_{}_{}.mp4", idx, timestamp));
+
+            let params = format!("loudnorm:{:.1}", target_lufs);
+            let output_path = self
+                .cached(path, &params, temp_path, |output| async move {
+                    self.video_processor
+                        .normalize_audio(path, &output, target_lufs)
+                        .await
+                })
+                .await?;
+
+            normalized.push(output_path);
+        }
+
+        info!(
+            "Normalized {} clips to {} LUFS",
+            normalized.len(),
+            target_lufs
+        );
+
+        Ok(normalized)
+    }
+
+    /// Run `produce` on `source` unless its output is already cached
+    ///
+    /// `produce` is given the path to write to: a cache entry when caching is
+    /// enabled, otherwise `temp_path`.
+    async fn cached<F, Fut>(
+        &self,
+        source: &Path,
+        params: &str,
+        temp_path: PathBuf,
+        produce: F,
+    ) -> Result<PathBuf>
+    where
+        F: FnOnce(PathBuf) -> Fut,
+        Fut: Future<Output = Result<PathBuf>>,
+    {
+        let Some((cache, key)) = self
+            .clip_cache
+            .as_ref()
+            .and_then(|cache| Some((cache, ClipCache::key(source, params)?)))
+        else {
+            return produce(temp_path).await;
+        };
+
+        if let Some(path) = cache.get(&key) {
+            info!("Reusing cached clip for {:?} ({})", source, params);
+            return Ok(path);
+        }
+
+        let Ok(pending) = cache.pending_path(&key) else {
+            return produce(temp_path).await;
+        };
+        if let Err(e) = produce(pending.clone()).await {
+            let _ = tokio::fs::remove_file(&pending).await;
+            return Err(e);
+        }
+
+        cache
+            .insert(&key, &pending)
+            .map_err(|e| VideoError::ProcessingError {
+                message: format!("Failed to cache clip: {}", e),
+            })
+    }
+
+    /// Move the finished video out of the temp folder into the output folder
+    async fn move_to_output_dir(&self, path: &Path, job_id: &str) -> Result<PathBuf> {
+        let Some(output_dir) = self.output_dir.read().await.clone() else {
+            return Ok(path.to_path_buf());
+        };
+
+        tokio::fs::create_dir_all(&output_dir).await.map_err(|_| {
+            VideoError::OutputDirectoryNotFound {
+                path: output_dir.display().to_string(),
+            }
+        })?;
+
+        let output_path = output_dir.join(format!("auto_edit_{}.mp4", job_id));
+        // Rename fails across drives, so fall back to copying
+        if tokio::fs::rename(path, &output_path).await.is_err() {
+            tokio::fs::copy(path, &output_path)
+                .await
+                .map_err(|e| VideoError::ProcessingError {
+                    message: format!("Failed to move result to {:?}: {}", output_path, e),
+                })?;
+            let _ = tokio::fs::remove_file(path).await;
+        }
+
+        Ok(output_path)
+    }
+
+    /// Concatenate multiple clips
+    async fn concatenate_clips(
+        &self,
+        clip_paths: &[PathBuf],
+        layout: ShortsLayout,
+        on_progress: impl FnMut(f64) + Send,
+    ) -> Result<PathBuf> {
+        let output_dir = std::env::temp_dir().join("lolshorts_auto_edit");
+        tokio::fs::create_dir_all(&output_dir)
+            .await
+            .map_err(|e| VideoError::ProcessingError {
+                message: format!("Failed to create temp directory: {}", e),
+            })?;
+
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let output_path = output_dir.join(format!("concatenated_{}.mp4", timestamp));
+
+        // Use VideoProcessor to compose clips into 9:16 format
+        self.video_processor
+            .compose_shorts_with_progress(clip_paths, &output_path, 1080, 1920, layout, on_progress)
+            .await
+    }
+
+    /// Apply canvas overlay (background + text + images)
+    ///
+    /// Creates a complex FFmpeg filter chain to apply:
+    /// 1. Background layer (color, gradient, or image)
+    /// 2. Text overlays with positioning
+    /// 3. Image overlays with positioning
+    ///
+    /// All positions are percentage-based (0-100) and converted to 1080x1920 pixels.
+    async fn apply_canvas_overlay(
+        &self,
+        video_path: &Path,
+        canvas: &CanvasTemplate,
+        facecam: Option<&Path>,
+        on_progress: impl FnMut(f64) + Send,
+    ) -> Result<PathBuf> {
+        let output_dir = std::env::temp_dir().join("lolshorts_auto_edit");
+        tokio::fs::create_dir_all(&output_dir).await.map_err(|e| {
+            VideoError::CanvasApplicationError {
+                reason: format!("Failed to create temp directory: {}", e),
+            }
+        })?;
+
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let output_path = output_dir.join(format!("with_canvas_{}.mp4", timestamp));
+
+        info!("Applying canvas template: {}", canvas.name);
+
+        // YouTube Shorts dimensions
+        const WIDTH: u32 = 1080;
+        const HEIGHT: u32 = 1920;
+
+        // Looped overlay images are timed at the frame rate of the short
+        let fps = match self.video_processor.analyze(video_path).await {
+            Ok(info) if info.fps > 0.0 => info.fps,
+            _ => 30.0,
+        };
+
+        // Build FFmpeg filter chain
+        let mut filter_parts = Vec::new();
+
+        // Step 1: Apply background layer
+        match &canvas.background {
+            BackgroundLayer::Color { value } => {
+                // Create solid color background
+                info!("Canvas background: solid color {}", value);
+                filter_parts.push(format!("color=c={}:s={}x{}:d=1[bg]", value, WIDTH, HEIGHT));
+                filter_parts.push("[0:v][bg]overlay=shortest=1".to_string());
+            }
+            BackgroundLayer::Gradient { value } => {
+                // For gradient, we'll use a simple vertical gradient
                 // Format: "color1:color2" (e.g., "blue:purple")
                 info!("Canvas background: gradient {}", value);
                 let colors: Vec<&str> = value.split(':').collect();
@@ -611,6 +1645,9 @@ impl AutoComposer {
                     warn!("Background image not found: {}", path);
                 }
             }
+            BackgroundLayer::ChampionSplash => {
+                warn!("No champion splash art, skipping background");
+            }
         }
 
         // Step 2: Apply text overlays
@@ -622,6 +1659,7 @@ impl AutoComposer {
                 color,
                 outline,
                 position,
+                animation,
                 ..
             } = element
             {
@@ -631,15 +1669,27 @@ impl AutoComposer {
 
                 info!("Text overlay {}: '{}' at ({}, {})", idx, content, x, y);
 
+                // Positions and size become time-based expressions when animated
+                let (x_arg, y_arg, size_arg) = match animation {
+                    Some(anim) => (
+                        format!("'{}'", anim.x_expr(x, WIDTH)),
+                        format!("'{}'", anim.y_expr(y, HEIGHT)),
+                        anim.scale_expr(*size)
+                            .map(|expr| format!("'{}'", expr))
+                            .unwrap_or_else(|| size.to_string()),
+                    ),
+                    None => (x.to_string(), y.to_string(), size.to_string()),
+                };
+
                 // Build drawtext filter
                 let mut drawtext = format!(
                     "drawtext=text='{}':fontfile={}:fontsize={}:fontcolor={}:x={}:y={}",
                     content.replace("'", "\\'"),
                     font,
-                    size,
+                    size_arg,
                     color,
-                    x,
-                    y
+                    x_arg,
+                    y_arg
                 );
 
                 // Add outline if specified
@@ -647,6 +1697,14 @@ impl AutoComposer {
                     drawtext.push_str(&format!(":borderw=2:bordercolor={}", outline_color));
                 }
 
+                // Add timing and fade if animated
+                if let Some(anim) = animation {
+                    drawtext.push_str(&format!(":enable='{}'", anim.enable_expr()));
+                    if let Some(alpha) = anim.alpha_expr() {
+                        drawtext.push_str(&format!(":alpha='{}'", alpha));
+                    }
+                }
+
                 filter_parts.push(drawtext);
             }
         }
@@ -658,6 +1716,7 @@ impl AutoComposer {
                 width,
                 height,
                 position,
+                animation,
                 ..
             } = element
             {
@@ -676,13 +1735,93 @@ impl AutoComposer {
                     idx, path, x, y, width, height
                 );
 
-                // Add movie input and overlay
+                match animation {
+                    Some(anim) => {
+                        // Loop the still image so time-based scale/fade can be evaluated
+                        let scale = match (anim.scale_expr(*width), anim.scale_expr(*height)) {
+                            (Some(w), Some(h)) => format!("scale=w='{}':h='{}':eval=frame", w, h),
+                            _ => format!("scale={}:{}", width, height),
+                        };
+
+                        let mut image_chain = format!(
+                            "movie={}:loop=0,setpts=N/({:.3}*TB)[img{}];\
+                             [img{}]format=rgba,{}",
+                            path, fps, idx, idx, scale
+                        );
+
+                        if let Some(fade_in) = anim.fade_in.filter(|d| *d > 0.0) {
+                            image_chain.push_str(&format!(
+                                ",fade=t=in:st={:.3}:d={:.3}:alpha=1",
+                                anim.start, fade_in
+                            ));
+                        }
+                        if let (Some(fade_out), Some(end)) =
+                            (anim.fade_out.filter(|d| *d > 0.0), anim.end_time())
+                        {
+                            image_chain.push_str(&format!(
+                                ",fade=t=out:st={:.3}:d={:.3}:alpha=1",
+                                (end - fade_out).max(anim.start),
+                                fade_out
+                            ));
+                        }
+                        image_chain.push_str(&format!("[scaled_img{}]", idx));
+
+                        filter_parts.push(image_chain);
+                        filter_parts.push(format!(
+                            "overlay=x='{}':y='{}':enable='{}'[out{}]",
+                            anim.x_expr(x, WIDTH),
+                            anim.y_expr(y, HEIGHT),
+                            anim.enable_expr(),
+                            idx
+                        ));
+                    }
+                    None => {
+                        // Add movie input and overlay
+                        filter_parts.push(format!(
+                            "movie={}[img{}];\
+                             [img{}]scale={}:{}[scaled_img{}]",
+                            path, idx, idx, width, height, idx
+                        ));
+                        filter_parts.push(format!("overlay={}:{}[out{}]", x, y, idx));
+                    }
+                }
+            }
+        }
+
+        // Step 4: Place the webcam recording
+        for (idx, element) in canvas.elements.iter().enumerate() {
+            if let CanvasElement::Facecam {
+                width,
+                height,
+                position,
+                ..
+            } = element
+            {
+                let Some(facecam) = facecam else {
+                    continue;
+                };
+
+                // Convert percentage position to pixels
+                let x = (position.x * WIDTH as f32 / 100.0) as u32;
+                let y = (position.y * HEIGHT as f32 / 100.0) as u32;
+
+                info!(
+                    "Facecam overlay {}: at ({}, {}) size {}x{}",
+                    idx, x, y, width, height
+                );
+
                 filter_parts.push(format!(
-                    "movie={}[img{}];\
-                     [img{}]scale={}:{}[scaled_img{}]",
-                    path, idx, idx, width, height, idx
+                    "movie={}[facecam{}];\
+                     [facecam{}]scale={}:{}[scaled_facecam{}]",
+                    facecam.display(),
+                    idx,
+                    idx,
+                    width,
+                    height,
+                    idx
                 ));
-                filter_parts.push(format!("overlay={}:{}[out{}]", x, y, idx));
+                // Keep the gameplay going if the facecam ends early
+                filter_parts.push(format!("overlay={}:{}:eof_action=pass[out{}]", x, y, idx));
             }
         }
 
@@ -724,10 +1863,21 @@ impl AutoComposer {
                 })?,
         ]);
 
-        execute_ffmpeg_command(&mut command).await.map_err(|e| {
-            VideoError::CanvasApplicationError {
-                reason: e.to_string(),
-            }
+        let total_secs = self
+            .video_processor
+            .get_duration(video_path)
+            .await
+            .unwrap_or(0.0);
+        execute_ffmpeg_command_with_progress(
+            &mut command,
+            "canvas_overlay",
+            total_secs,
+            &self.events,
+            on_progress,
+        )
+        .await
+        .map_err(|e| VideoError::CanvasApplicationError {
+            reason: e.to_string(),
         })?;
 
         info!("Successfully applied canvas overlay");
@@ -749,6 +1899,7 @@ impl AutoComposer {
         video_path: &Path,
         music: &BackgroundMusic,
         levels: &AudioLevels,
+        on_progress: impl FnMut(f64) + Send,
     ) -> Result<PathBuf> {
         let output_dir = std::env::temp_dir().join("lolshorts_auto_edit");
         tokio::fs::create_dir_all(&output_dir)
@@ -760,23 +1911,6 @@ impl AutoComposer {
         let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
         let output_path = output_dir.join(format!("with_audio_{}.mp4", timestamp));
 
-        let music_path = PathBuf::from(&music.file_path);
-        if !music_path.exists() {
-            return Err(VideoError::BackgroundMusicNotFound {
-                path: music.file_path.clone(),
-            });
-        }
-
-        info!(
-            "Mixing audio: game={}%, music={}%",
-            levels.game_audio, levels.background_music
-        );
-
-        // Convert 0-100 volume to FFmpeg volume (0.0-2.0)
-        // 100% = 1.0, 50% = 0.5, 200% = 2.0
-        let game_volume = levels.game_audio as f64 / 100.0;
-        let music_volume = levels.background_music as f64 / 100.0;
-
         // Get video duration for fade-out timing
         let video_duration = self
             .video_processor
@@ -788,39 +1922,27 @@ impl AutoComposer {
 
         info!("Video duration: {:.1}s", video_duration);
 
-        // Build audio filter chain
-        let mut audio_filter = String::new();
-
-        // [0:a] = game audio with volume adjustment
-        audio_filter.push_str(&format!("[0:a]volume={}[game_audio];", game_volume));
-
-        // [1:a] = background music with volume, fade-in, fade-out
-        let fade_duration = 3.0; // 3 seconds fade
-        let fade_out_start = (video_duration - fade_duration).max(0.0);
-
-        if music.loop_music {
-            // Loop music if shorter than video
-            audio_filter.push_str(&format!(
-                "[1:a]aloop=loop=-1:size=2e+09,\
-                 atrim=0:{},\
-                 volume={},\
-                 afade=t=in:st=0:d={},\
-                 afade=t=out:st={}:d={}[bg_music];",
-                video_duration, music_volume, fade_duration, fade_out_start, fade_duration
-            ));
+        let music_path = if music.surprise_me {
+            MusicLibrary::open(self.storage.base_path())
+                .pick_music(music.mood, video_duration)?
+                .ok_or_else(|| VideoError::BackgroundMusicNotFound {
+                    path: "music library (no tracks imported)".to_string(),
+                })?
         } else {
-            // No looping - music plays once
-            audio_filter.push_str(&format!(
-                "[1:a]volume={},\
-                 afade=t=in:st=0:d={},\
-                 afade=t=out:st={}:d={}[bg_music];",
-                music_volume, fade_duration, fade_out_start, fade_duration
-            ));
+            PathBuf::from(&music.file_path)
+        };
+        if !music_path.exists() {
+            return Err(VideoError::BackgroundMusicNotFound {
+                path: music_path.display().to_string(),
+            });
         }
 
-        // Mix the two audio streams
-        audio_filter.push_str("[game_audio][bg_music]amix=inputs=2:duration=first[audio_out]");
+        info!(
+            "Mixing audio: music={:?}, game={}%, music={}%",
+            music_path, levels.game_audio, levels.background_music
+        );
 
+        let audio_filter = mix_filter(levels, music.loop_music, video_duration);
         info!("Audio filter chain: {}", audio_filter);
 
         // Execute FFmpeg command
@@ -859,16 +1981,135 @@ impl AutoComposer {
                 })?,
         ]);
 
-        execute_ffmpeg_command(&mut command)
+        execute_ffmpeg_command_with_progress(
+            &mut command,
+            "mix_audio",
+            video_duration,
+            &self.events,
+            on_progress,
+        )
+        .await
+        .map_err(|e| VideoError::AudioMixingError {
+            reason: e.to_string(),
+        })?;
+
+        info!("Successfully mixed audio");
+        Ok(output_path)
+    }
+
+    /// Play `intro` before and `outros` after the composed video
+    ///
+    /// Done last so the branded clips aren't reframed or covered by the
+    /// canvas. They're scaled and padded to 1080x1920 with their audio faded
+    /// in and out; clips without audio get silence so the streams line up.
+    async fn stitch_bumpers(
+        &self,
+        video_path: &Path,
+        intro: Option<&Path>,
+        outros: &[&Path],
+        on_progress: impl FnMut(f64) + Send,
+    ) -> Result<PathBuf> {
+        let output_dir = std::env::temp_dir().join("lolshorts_auto_edit");
+        tokio::fs::create_dir_all(&output_dir)
             .await
-            .map_err(|e| VideoError::AudioMixingError {
-                reason: e.to_string(),
+            .map_err(|e| VideoError::ProcessingError {
+                message: format!("Failed to create temp directory: {}", e),
             })?;
 
-        info!("Successfully mixed audio");
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let output_path = output_dir.join(format!("with_bumpers_{}.mp4", timestamp));
+
+        let main_info = self.video_processor.analyze(video_path).await?;
+        let fps = Some(main_info.fps).filter(|fps| *fps > 0.0).unwrap_or(30.0);
+
+        let order = intro
+            .map(|path| (path, true))
+            .into_iter()
+            .chain([(video_path, false)])
+            .chain(outros.iter().map(|path| (*path, true)));
+
+        let mut inputs = Vec::new();
+        for (path, is_bumper) in order {
+            if !path.exists() {
+                return Err(VideoError::FileNotFound {
+                    path: path.display().to_string(),
+                });
+            }
+
+            let info = self.video_processor.analyze(path).await?;
+            inputs.push((
+                path,
+                StitchInput {
+                    is_bumper,
+                    duration: self.video_processor.get_duration(path).await?,
+                    has_audio: info.has_audio,
+                },
+            ));
+        }
+
+        let stitch_inputs: Vec<StitchInput> = inputs.iter().map(|(_, input)| *input).collect();
+        let mut command = tokio::process::Command::new("ffmpeg");
+        for (path, _) in &inputs {
+            command.arg("-i").arg(path);
+        }
+        command
+            .arg("-filter_complex")
+            .arg(stitch_filter(&stitch_inputs, fps))
+            .args(["-map", "[v]", "-map", "[a]"])
+            .args(["-c:v", "libx264", "-preset", "medium", "-crf", "23"])
+            .args(["-c:a", "aac", "-b:a", "192k", "-y"])
+            .arg(&output_path);
+
+        let total_secs = stitch_inputs.iter().map(|input| input.duration).sum();
+        execute_ffmpeg_command_with_progress(
+            &mut command,
+            "stitch_bumpers",
+            total_secs,
+            &self.events,
+            on_progress,
+        )
+        .await
+        .map_err(|e| VideoError::ConcatenationError {
+            reason: format!("Failed to add intro/outro: {}", e),
+        })?;
+
         Ok(output_path)
     }
 
+    /// Render `card` over a plain background with silent audio
+    async fn render_end_card(&self, card: &EndCard) -> Result<PathBuf> {
+        let output_dir = std::env::temp_dir().join("lolshorts_auto_edit");
+        tokio::fs::create_dir_all(&output_dir)
+            .await
+            .map_err(|e| VideoError::ProcessingError {
+                message: format!("Failed to create temp directory: {}", e),
+            })?;
+
+        let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+        let base_path = output_dir.join(format!("end_card_base_{}.mp4", timestamp));
+        let duration = format!("{:.3}", card.duration.clamp(1.0, 10.0));
+
+        let mut command = tokio::process::Command::new("ffmpeg");
+        command
+            .args(["-f", "lavfi", "-i"])
+            .arg(format!("color=c={}:s=1080x1920:r=30", card.background))
+            .args(["-f", "lavfi", "-i", "anullsrc=r=48000:cl=stereo"])
+            .args(["-t", &duration])
+            .args(["-c:v", "libx264", "-pix_fmt", "yuv420p"])
+            .args(["-c:a", "aac", "-y"])
+            .arg(&base_path);
+        execute_ffmpeg_command_with_progress(&mut command, "end_card", 0.0, &self.events, |_| {})
+            .await?;
+
+        let rendered = self
+            .apply_canvas_overlay(&base_path, &card.template(), None, |_| {})
+            .await;
+        if !matches!(&rendered, Ok(path) if path == &base_path) {
+            let _ = tokio::fs::remove_file(&base_path).await;
+        }
+        rendered
+    }
+
     /// Load clips from database for given game IDs
     async fn load_clips_from_games(&self, game_ids: &[String]) -> Result<Vec<ClipInfo>> {
         let mut all_clips = Vec::new();
@@ -902,9 +2143,17 @@ impl AutoComposer {
                     crate::storage::models::EventType::BaronKill => "BaronKill".to_string(),
                     crate::storage::models::EventType::Ace => "Ace".to_string(),
                     crate::storage::models::EventType::FirstBlood => "FirstBlood".to_string(),
+                    crate::storage::models::EventType::Death => "Death".to_string(),
                     crate::storage::models::EventType::Custom(s) => s.clone(),
                 };
 
+                // Clips saved before scoring existed have no score
+                let score = self
+                    .storage
+                    .load_clip_metadata_v2(&clip.file_path)
+                    .ok()
+                    .and_then(|metadata| metadata.score);
+
                 all_clips.push(ClipInfo {
                     id: clip_id_counter,
                     event_type,
@@ -913,6 +2162,7 @@ impl AutoComposer {
                     file_path: clip.file_path,
                     thumbnail_path: clip.thumbnail_path,
                     duration: Some(clip.duration),
+                    score,
                 });
 
                 clip_id_counter += 1;
@@ -936,8 +2186,7 @@ impl AutoComposer {
         progress: f64,
         current_step: String,
     ) {
-        let mut progress_guard = self.progress.write().await;
-        *progress_guard = Some(AutoEditProgress {
+        self.set_progress(AutoEditProgress {
             job_id: job_id.to_string(),
             status,
             progress,
@@ -946,13 +2195,13 @@ impl AutoComposer {
             estimated_seconds: 120.0, // Default estimate: 2 minutes
             output_path: None,
             error: None,
-        });
+        })
+        .await;
     }
 
     /// Update progress to completed
     async fn update_progress_complete(&self, job_id: &str, output_path: String, elapsed: f64) {
-        let mut progress_guard = self.progress.write().await;
-        *progress_guard = Some(AutoEditProgress {
+        self.set_progress(AutoEditProgress {
             job_id: job_id.to_string(),
             status: AutoEditStatus::Completed,
             progress: 100.0,
@@ -961,13 +2210,13 @@ impl AutoComposer {
             estimated_seconds: elapsed,
             output_path: Some(output_path),
             error: None,
-        });
+        })
+        .await;
     }
 
     /// Update progress to failed
     async fn update_progress_failed(&self, job_id: &str, error: String, elapsed: f64) {
-        let mut progress_guard = self.progress.write().await;
-        *progress_guard = Some(AutoEditProgress {
+        self.set_progress(AutoEditProgress {
             job_id: job_id.to_string(),
             status: AutoEditStatus::Failed,
             progress: 0.0,
@@ -976,7 +2225,44 @@ impl AutoComposer {
             estimated_seconds: elapsed,
             output_path: None,
             error: Some(error),
-        });
+        })
+        .await;
+    }
+
+    /// Progress callback mapping an FFmpeg step's 0-100% onto `from`..`to` of
+    /// the whole job
+    fn step_progress<'a>(
+        &'a self,
+        job_id: &'a str,
+        from: f64,
+        to: f64,
+        step: &'a str,
+    ) -> impl FnMut(f64) + Send + 'a {
+        move |percent| {
+            let progress = AutoEditProgress {
+                job_id: job_id.to_string(),
+                status: AutoEditStatus::Processing,
+                progress: from + (to - from) * percent / 100.0,
+                current_step: format!("{}... {:.0}%", step, percent),
+                elapsed_seconds: 0.0,
+                estimated_seconds: 120.0,
+                output_path: None,
+                error: None,
+            };
+
+            // Called from FFmpeg's output loop; if a poll holds the lock, the
+            // next update will be stored instead
+            if let Ok(mut current) = self.progress.try_write() {
+                *current = Some(progress.clone());
+            }
+            self.events.emit(AppEvent::AutoEditProgress(progress));
+        }
+    }
+
+    /// Store progress for polling and push it to the frontend
+    async fn set_progress(&self, progress: AutoEditProgress) {
+        *self.progress.write().await = Some(progress.clone());
+        self.events.emit(AppEvent::AutoEditProgress(progress));
     }
 
     /// Get current progress
@@ -985,6 +2271,183 @@ impl AutoComposer {
     }
 }
 
+/// `sidechaincompress` settings for music ducking: react within 20ms once
+/// game audio passes about -26 dB, recover over 400ms
+const DUCKING_COMPRESSOR: &str = "threshold=0.05:ratio=8:attack=20:release=400";
+
+/// Filter graph mixing the video's audio (`[0:a]`) with background music
+/// (`[1:a]`) into `[audio_out]`
+fn mix_filter(levels: &AudioLevels, loop_music: bool, video_duration: f64) -> String {
+    // Convert 0-100 volume to FFmpeg volume (0.0-2.0)
+    // 100% = 1.0, 50% = 0.5, 200% = 2.0
+    let game_volume = levels.game_audio as f64 / 100.0;
+    let music_volume = levels.background_music as f64 / 100.0;
+
+    // Build audio filter chain
+    let mut audio_filter = String::new();
+
+    // [0:a] = game audio with volume adjustment, split off as the
+    // sidechain when ducking
+    if levels.ducking {
+        audio_filter.push_str(&format!(
+            "[0:a]volume={},asplit=2[game_audio][sidechain];",
+            game_volume
+        ));
+    } else {
+        audio_filter.push_str(&format!("[0:a]volume={}[game_audio];", game_volume));
+    }
+
+    // [1:a] = background music with volume, fade-in, fade-out
+    let fade_duration = 3.0; // 3 seconds fade
+    let fade_out_start = (video_duration - fade_duration).max(0.0);
+
+    if loop_music {
+        // Loop music if shorter than video
+        audio_filter.push_str(&format!(
+            "[1:a]aloop=loop=-1:size=2e+09,\
+             atrim=0:{},\
+             volume={},\
+             afade=t=in:st=0:d={},\
+             afade=t=out:st={}:d={}[bg_music];",
+            video_duration, music_volume, fade_duration, fade_out_start, fade_duration
+        ));
+    } else {
+        // No looping - music plays once
+        audio_filter.push_str(&format!(
+            "[1:a]volume={},\
+             afade=t=in:st=0:d={},\
+             afade=t=out:st={}:d={}[bg_music];",
+            music_volume, fade_duration, fade_out_start, fade_duration
+        ));
+    }
+
+    // Compress the music whenever game audio crosses the threshold
+    let music_label = if levels.ducking {
+        audio_filter.push_str(&format!(
+            "[bg_music][sidechain]sidechaincompress={}[ducked_music];",
+            DUCKING_COMPRESSOR
+        ));
+        "ducked_music"
+    } else {
+        "bg_music"
+    };
+
+    // Mix the two audio streams
+    audio_filter.push_str(&format!(
+        "[game_audio][{}]amix=inputs=2:duration=first[audio_out]",
+        music_label
+    ));
+
+    audio_filter
+}
+
+/// Audio fade applied to each end of an intro or outro
+const BUMPER_AUDIO_FADE_SECS: f64 = 0.5;
+
+/// One video joined by [`AutoComposer::stitch_bumpers`]
+#[derive(Debug, Clone, Copy)]
+struct StitchInput {
+    /// Intro or outro (as opposed to the composed video)
+    is_bumper: bool,
+    duration: f64,
+    has_audio: bool,
+}
+
+/// LUT from the config, else from its canvas template, if the file is usable
+fn color_lut(config: &AutoEditConfig) -> Option<PathBuf> {
+    let path = config
+        .color_lut
+        .as_deref()
+        .or_else(|| config.canvas_template.as_ref()?.color_lut.as_deref())
+        .map(PathBuf::from)?;
+
+    let is_cube = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("cube"));
+    if !is_cube {
+        warn!("Skipping color LUT, not a .cube file: {:?}", path);
+        return None;
+    }
+    if !path.exists() {
+        warn!("Skipping color LUT, file not found: {:?}", path);
+        return None;
+    }
+    Some(path)
+}
+
+/// Filter graph joining `inputs` (in order) into `[v]` and `[a]`
+fn stitch_filter(inputs: &[StitchInput], fps: f64) -> String {
+    let mut parts = Vec::new();
+    let mut concat_inputs = String::new();
+
+    for (idx, input) in inputs.iter().enumerate() {
+        let video = if input.is_bumper {
+            "scale=1080:1920:force_original_aspect_ratio=decrease,\
+             pad=1080:1920:(ow-iw)/2:(oh-ih)/2,"
+        } else {
+            ""
+        };
+        parts.push(format!(
+            "[{idx}:v]{video}setsar=1,fps={fps:.3},format=yuv420p[v{idx}]"
+        ));
+
+        let mut audio = if input.has_audio {
+            format!("[{idx}:a]aformat=sample_rates=48000:channel_layouts=stereo")
+        } else {
+            format!(
+                "anullsrc=r=48000:cl=stereo,atrim=duration={:.3}",
+                input.duration
+            )
+        };
+        if input.is_bumper && input.has_audio {
+            let fade = BUMPER_AUDIO_FADE_SECS.min(input.duration / 2.0);
+            audio.push_str(&format!(
+                ",afade=t=in:d={:.3},afade=t=out:st={:.3}:d={:.3}",
+                fade,
+                input.duration - fade,
+                fade
+            ));
+        }
+        parts.push(format!("{}[a{}]", audio, idx));
+
+        concat_inputs.push_str(&format!("[v{idx}][a{idx}]"));
+    }
+
+    parts.push(format!(
+        "{}concat=n={}:v=1:a=1[v][a]",
+        concat_inputs,
+        inputs.len()
+    ));
+    parts.join(";")
+}
+
+/// How [`AutoComposer::prepare_clips`] trims each clip to fit
+/// `target_duration`: `(start, duration)`, or None to use the whole clip
+fn trim_plan(clips: &[ClipInfo], target_duration: u32) -> Vec<Option<(f64, f64)>> {
+    let total_duration: f64 = clips.iter().map(|c| c.duration.unwrap_or(10.0)).sum();
+    let buffer_target = target_duration as f64 * 0.9; // Leave 10% buffer for transitions
+    if total_duration <= buffer_target {
+        return vec![None; clips.len()];
+    }
+
+    let trim_factor = buffer_target / total_duration;
+    clips
+        .iter()
+        .map(|clip| trim_window(clip.duration.unwrap_or(10.0), trim_factor))
+        .collect()
+}
+
+/// Trim a clip from the center to preserve important moments
+///
+/// Clips keep at least 3 seconds; None if trimming would save less than 0.5s.
+fn trim_window(clip_duration: f64, trim_factor: f64) -> Option<(f64, f64)> {
+    let trimmed_duration = (clip_duration * trim_factor).max(3.0);
+    if (clip_duration - trimmed_duration).abs() < 0.5 {
+        return None;
+    }
+    Some(((clip_duration - trimmed_duration) / 2.0, trimmed_duration))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1003,6 +2466,7 @@ mod tests {
             file_path: format!("/tmp/clip_{}.mp4", id),
             thumbnail_path: None,
             duration: Some(duration),
+            score: None,
         }
     }
 
@@ -1023,10 +2487,7 @@ mod tests {
         let config = AutoEditConfig {
             target_duration: 60,
             game_ids: vec!["game1".to_string()],
-            selected_clip_ids: None,
-            canvas_template: None,
-            background_music: None,
-            audio_levels: AudioLevels::default(),
+            ..Default::default()
         };
 
         let selected = composer.select_clips(&clips, &config).await.unwrap();
@@ -1041,6 +2502,27 @@ mod tests {
         assert!(total_duration <= 54.0);
     }
 
+    #[tokio::test]
+    async fn test_clip_selection_prefers_score() {
+        let processor = Arc::new(VideoProcessor::new());
+        let composer = AutoComposer::new(processor, create_test_storage());
+
+        // A clutch solo kill outscores an unscored quadra (priority 4 = 80)
+        let mut clutch = create_test_clip(1, 1, 30.0, "ChampionKill");
+        clutch.score = Some(92);
+        let clips = vec![create_test_clip(2, 4, 30.0, "QuadraKill"), clutch];
+
+        let config = AutoEditConfig {
+            target_duration: 60,
+            game_ids: vec!["game1".to_string()],
+            ..Default::default()
+        };
+
+        let selected = composer.select_clips(&clips, &config).await.unwrap();
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].id, 1);
+    }
+
     #[tokio::test]
     async fn test_clip_selection_fits_duration() {
         let processor = Arc::new(VideoProcessor::new());
@@ -1056,10 +2538,7 @@ mod tests {
         let config = AutoEditConfig {
             target_duration: 60,
             game_ids: vec!["game1".to_string()],
-            selected_clip_ids: None,
-            canvas_template: None,
-            background_music: None,
-            audio_levels: AudioLevels::default(),
+            ..Default::default()
         };
 
         let selected = composer.select_clips(&clips, &config).await.unwrap();
@@ -1070,6 +2549,32 @@ mod tests {
         assert_eq!(selected.len(), 2); // Should select 2 clips (20 + 25 = 45s)
     }
 
+    #[tokio::test]
+    async fn test_clip_selection_separates_deaths() {
+        let processor = Arc::new(VideoProcessor::new());
+        let storage = create_test_storage();
+        let composer = AutoComposer::new(processor, storage);
+
+        let clips = vec![
+            create_test_clip(1, 5, 10.0, "Pentakill"),
+            create_test_clip(2, 1, 10.0, "Death"),
+            create_test_clip(3, 3, 10.0, "Death"),
+        ];
+
+        let mut config = AutoEditConfig {
+            target_duration: 60,
+            game_ids: vec!["game1".to_string()],
+            ..Default::default()
+        };
+
+        let highlights = composer.select_clips(&clips, &config).await.unwrap();
+        assert_eq!(highlights.iter().map(|c| c.id).collect::<Vec<_>>(), [1]);
+
+        config.fails_only = true;
+        let fails = composer.select_clips(&clips, &config).await.unwrap();
+        assert_eq!(fails.iter().map(|c| c.id).collect::<Vec<_>>(), [3, 2]);
+    }
+
     #[tokio::test]
     async fn test_manual_clip_selection() {
         let processor = Arc::new(VideoProcessor::new());
@@ -1087,8 +2592,7 @@ mod tests {
             game_ids: vec!["game1".to_string()],
             selected_clip_ids: Some(vec![1, 3]), // Manually select clips 1 and 3
             canvas_template: None,
-            background_music: None,
-            audio_levels: AudioLevels::default(),
+            ..Default::default()
         };
 
         let selected = composer.select_clips(&clips, &config).await.unwrap();
@@ -1116,10 +2620,306 @@ mod tests {
             color: "#FFD700".to_string(),
             outline: Some("#000000".to_string()),
             position: Position { x: 50.0, y: 10.0 },
+            animation: None,
         };
 
         let json = serde_json::to_string(&text_element).unwrap();
         assert!(json.contains("\"type\":\"text\""));
         assert!(json.contains("PENTAKILL"));
+        assert!(!json.contains("animation"));
+    }
+
+    #[test]
+    fn test_element_animation_expressions() {
+        let anim = ElementAnimation {
+            start: 2.0,
+            duration: Some(3.0),
+            fade_in: Some(0.5),
+            fade_out: Some(1.0),
+            slide: Some(SlideAnimation {
+                from: SlideDirection::Left,
+                duration: 0.5,
+            }),
+            scale: None,
+        };
+
+        assert_eq!(anim.end_time(), Some(5.0));
+        assert_eq!(anim.enable_expr(), "between(t,2.000,5.000)");
+        assert_eq!(
+            anim.alpha_expr().unwrap(),
+            "clip(min((t-2.000)/0.500,(5.000-t)/1.000),0,1)"
+        );
+        assert_eq!(
+            anim.x_expr(540, 1080),
+            "540-1080*(1-clip((t-2.000)/0.500,0,1))"
+        );
+        assert_eq!(anim.y_expr(192, 1920), "192");
+        assert!(anim.scale_expr(48).is_none());
+    }
+
+    #[test]
+    fn test_element_animation_open_ended() {
+        let anim = ElementAnimation {
+            start: 1.0,
+            fade_out: Some(1.0), // Ignored without a duration
+            scale: Some(ScaleAnimation {
+                from: 0.5,
+                duration: 1.0,
+            }),
+            ..Default::default()
+        };
+
+        assert_eq!(anim.enable_expr(), "gte(t,1.000)");
+        assert!(anim.alpha_expr().is_none());
+        assert_eq!(
+            anim.scale_expr(100).unwrap(),
+            "100*(0.500+0.500*clip((t-1.000)/1.000,0,1))"
+        );
+    }
+
+    #[test]
+    fn test_canvas_element_animation_deserialization() {
+        let json = r#"{
+            "type": "text",
+            "id": "title",
+            "content": "ACE",
+            "font": "Arial",
+            "size": 64,
+            "color": "white",
+            "outline": null,
+            "position": {"x": 50.0, "y": 20.0},
+            "animation": {"start": 1.5, "duration": 2.0, "slide": {"from": "bottom", "duration": 0.3}}
+        }"#;
+
+        let element: CanvasElement = serde_json::from_str(json).unwrap();
+        match element {
+            CanvasElement::Text { animation, .. } => {
+                let anim = animation.expect("animation should be parsed");
+                assert_eq!(anim.start, 1.5);
+                assert_eq!(anim.slide.unwrap().from, SlideDirection::Bottom);
+            }
+            _ => panic!("Expected Text element"),
+        }
+    }
+
+    #[test]
+    fn test_stitch_filter() {
+        let inputs = [
+            StitchInput {
+                is_bumper: true,
+                duration: 3.0,
+                has_audio: false,
+            },
+            StitchInput {
+                is_bumper: false,
+                duration: 58.0,
+                has_audio: true,
+            },
+            StitchInput {
+                is_bumper: true,
+                duration: 4.0,
+                has_audio: true,
+            },
+        ];
+
+        let filter = stitch_filter(&inputs, 60.0);
+        assert!(filter.starts_with("[0:v]scale=1080:1920:force_original_aspect_ratio=decrease"));
+        assert!(filter.contains("[1:v]setsar=1,fps=60.000,format=yuv420p[v1]"));
+        // Silent intro, faded outro, main audio untouched
+        assert!(filter.contains("anullsrc=r=48000:cl=stereo,atrim=duration=3.000[a0]"));
+        assert!(filter.contains("afade=t=out:st=3.500:d=0.500[a2]"));
+        assert!(!filter.contains("[1:a]aformat=sample_rates=48000:channel_layouts=stereo,afade"));
+        assert!(filter.ends_with("[v0][a0][v1][a1][v2][a2]concat=n=3:v=1:a=1[v][a]"));
+    }
+
+    #[test]
+    fn test_mix_filter_ducking() {
+        let mut levels = AudioLevels {
+            ducking: true,
+            ..AudioLevels::default()
+        };
+
+        let filter = mix_filter(&levels, true, 60.0);
+        assert!(filter.starts_with("[0:a]volume=0.6,asplit=2[game_audio][sidechain];"));
+        assert!(filter.contains("afade=t=out:st=57:d=3[bg_music];"));
+        assert!(filter.contains(
+            "[bg_music][sidechain]sidechaincompress=threshold=0.05:ratio=8:attack=20:release=400[ducked_music];"
+        ));
+        assert!(
+            filter.ends_with("[game_audio][ducked_music]amix=inputs=2:duration=first[audio_out]")
+        );
+
+        levels.ducking = false;
+        let filter = mix_filter(&levels, false, 60.0);
+        assert!(filter.starts_with("[0:a]volume=0.6[game_audio];[1:a]volume=0.8,"));
+        assert!(!filter.contains("sidechain"));
+        assert!(filter.ends_with("[game_audio][bg_music]amix=inputs=2:duration=first[audio_out]"));
+    }
+
+    #[test]
+    fn test_end_card_template() {
+        let card: EndCard = serde_json::from_str(r#"{"channel_name": "Faker Fan"}"#).unwrap();
+        assert_eq!(card.duration, 3.0);
+
+        let template = card.template();
+        assert_eq!(template.elements.len(), 2);
+        assert!(matches!(
+            &template.elements[1],
+            CanvasElement::Text { content, .. } if content == "Faker Fan"
+        ));
+
+        let card = EndCard {
+            logo_path: Some("/test/logo.png".to_string()),
+            ..card
+        };
+        assert!(matches!(
+            card.template().elements.last(),
+            Some(CanvasElement::Image { width: 360, .. })
+        ));
+    }
+
+    #[test]
+    fn test_trim_plan() {
+        let clips = vec![
+            create_test_clip(1, 5, 20.0, "Pentakill"),
+            create_test_clip(2, 3, 40.0, "Baron"),
+        ];
+
+        // Fits 60s (with the 10% buffer), nothing trimmed
+        assert_eq!(trim_plan(&clips, 120), vec![None, None]);
+
+        // 60s of clips for a 54s budget: both trimmed from the center
+        let plan = trim_plan(&clips, 60);
+        let (start, duration) = plan[0].unwrap();
+        assert!((duration - 18.0).abs() < 0.001);
+        assert!((start - 1.0).abs() < 0.001);
+        assert!((plan[1].unwrap().1 - 36.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_canvas_facecam_element() {
+        let json = r#"{
+            "id": "facecam",
+            "name": "Streamer",
+            "background": {"type": "color", "value": "black"},
+            "elements": [{
+                "type": "facecam",
+                "id": "cam",
+                "width": 360,
+                "height": 270,
+                "position": {"x": 5.0, "y": 70.0}
+            }]
+        }"#;
+
+        let template: CanvasTemplate = serde_json::from_str(json).unwrap();
+        assert!(template.has_facecam());
+    }
+
+    #[test]
+    fn test_canvas_champion_slots() {
+        let json = r#"{
+            "id": "main",
+            "name": "Champion main",
+            "background": {"type": "champion_splash"},
+            "elements": [{
+                "type": "champion_icon",
+                "id": "icon",
+                "width": 200,
+                "height": 200,
+                "position": {"x": 40.0, "y": 5.0}
+            }]
+        }"#;
+
+        let template: CanvasTemplate = serde_json::from_str(json).unwrap();
+        assert!(template.has_champion_slots());
+
+        let assets = ChampionAssets {
+            name: "Kai'Sa".to_string(),
+            id: "Kaisa".to_string(),
+            version: "14.20.1".to_string(),
+            icon_path: "ddragon/14.20.1/champion/Kaisa.png".to_string(),
+            splash_path: "ddragon/14.20.1/splash/Kaisa_0.jpg".to_string(),
+        };
+        let filled = template.with_champion(&assets);
+        assert!(!filled.has_champion_slots());
+        assert!(matches!(
+            &filled.background,
+            BackgroundLayer::Image { path } if path == "ddragon/14.20.1/splash/Kaisa_0.jpg"
+        ));
+        assert!(matches!(
+            &filled.elements[0],
+            CanvasElement::Image { id, path, width: 200, .. }
+                if id == "icon" && path == "ddragon/14.20.1/champion/Kaisa.png"
+        ));
+    }
+
+    #[test]
+    fn test_main_champion() {
+        let storage = create_test_storage();
+        let games = [
+            ("main_champion_1", "Ahri"),
+            ("main_champion_2", "Yasuo"),
+            ("main_champion_3", "Unknown"),
+            ("main_champion_4", "Yasuo"),
+        ];
+        for (game_id, champion) in games {
+            let game = crate::storage::models::GameMetadata {
+                game_id: game_id.to_string(),
+                champion: champion.to_string(),
+                game_mode: "CLASSIC".to_string(),
+                start_time: chrono::Utc::now(),
+                end_time: None,
+                result: None,
+                kda: None,
+                patch: None,
+            };
+            storage.save_game_metadata(game_id, &game).unwrap();
+        }
+
+        let ids = |ids: &[&str]| ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            main_champion(
+                &storage,
+                &ids(&["main_champion_1", "main_champion_2", "main_champion_4"])
+            )
+            .as_deref(),
+            Some("Yasuo")
+        );
+        // Ties go to the earliest game; unknown champions don't count
+        assert_eq!(
+            main_champion(
+                &storage,
+                &ids(&["main_champion_3", "main_champion_1", "main_champion_2"])
+            )
+            .as_deref(),
+            Some("Ahri")
+        );
+        assert_eq!(main_champion(&storage, &ids(&["main_champion_3"])), None);
+    }
+
+    #[tokio::test]
+    async fn test_run_anyway_releases_waiting_job() {
+        let activity = Arc::new(GameActivity::new());
+        activity.set(Some(true));
+        let composer = Arc::new(
+            AutoComposer::new(Arc::new(VideoProcessor::new()), create_test_storage())
+                .with_game_activity(activity),
+        );
+        assert!(!composer.run_anyway("auto_edit_1"));
+
+        let waiter = {
+            let composer = Arc::clone(&composer);
+            tokio::spawn(async move { composer.wait_for_game_end("auto_edit_1").await })
+        };
+        while !composer.run_anyway("auto_edit_1") {
+            tokio::task::yield_now().await;
+        }
+
+        // Released while the game is still running
+        tokio::time::timeout(std::time::Duration::from_secs(5), waiter)
+            .await
+            .expect("job should be released")
+            .unwrap();
+        assert!(!composer.run_anyway("auto_edit_1"));
     }
 }
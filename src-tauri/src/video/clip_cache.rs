@@ -0,0 +1,195 @@
+//! Cache of trimmed and normalized clips
+//!
+//! Auto-edit re-trims the same clips every time it's rerun with small
+//! changes. Intermediate outputs are stored under a key derived from the
+//! source file (path, size and modification time) and the operation's
+//! parameters, so a rerun with the same inputs reuses them.
+//!
+//! The cache is capped in size; least recently used entries are evicted
+//! first.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{debug, info, warn};
+
+/// Default size limit (2 GB)
+pub const DEFAULT_MAX_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+/// Entries used this recently are never evicted, so a running job can't lose
+/// a clip it's about to concatenate
+const MIN_ENTRY_AGE: Duration = Duration::from_secs(10 * 60);
+
+pub struct ClipCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+impl ClipCache {
+    pub fn new(dir: PathBuf, max_bytes: u64) -> Self {
+        Self { dir, max_bytes }
+    }
+
+    /// Cache key for running the operation described by `params` on `source`
+    ///
+    /// Returns None if `source` can't be read.
+    pub fn key(source: &Path, params: &str) -> Option<String> {
+        let metadata = std::fs::metadata(source).ok()?;
+        let modified = metadata
+            .modified()
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        let mut hasher = DefaultHasher::new();
+        source.hash(&mut hasher);
+        metadata.len().hash(&mut hasher);
+        modified.hash(&mut hasher);
+        params.hash(&mut hasher);
+        Some(format!("{:016x}", hasher.finish()))
+    }
+
+    /// Path of a cached output, if there is one
+    pub fn get(&self, key: &str) -> Option<PathBuf> {
+        let path = self.entry_path(key);
+        if !path.is_file() {
+            return None;
+        }
+
+        // Mark as recently used
+        if let Err(e) = std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(SystemTime::now()))
+        {
+            debug!("Failed to touch cache entry {:?}: {}", path, e);
+        }
+
+        debug!("Clip cache hit: {}", key);
+        Some(path)
+    }
+
+    /// Where to write the output for `key` before calling [`Self::insert`]
+    pub fn pending_path(&self, key: &str) -> std::io::Result<PathBuf> {
+        std::fs::create_dir_all(&self.dir)?;
+        Ok(self.dir.join(format!("{}.partial.mp4", key)))
+    }
+
+    /// Move a finished output into the cache, returning its cached path
+    ///
+    /// Evicts old entries if the cache is over its size limit.
+    pub fn insert(&self, key: &str, pending: &Path) -> std::io::Result<PathBuf> {
+        let path = self.entry_path(key);
+        std::fs::rename(pending, &path)?;
+
+        if let Err(e) = self.evict() {
+            warn!("Failed to evict clip cache entries: {}", e);
+        }
+        Ok(path)
+    }
+
+    /// Remove least recently used entries until the cache fits its limit
+    ///
+    /// Returns the number of bytes freed.
+    pub fn evict(&self) -> std::io::Result<u64> {
+        let mut entries: Vec<(PathBuf, u64, SystemTime)> = std::fs::read_dir(&self.dir)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                let modified = metadata.modified().ok()?;
+                metadata
+                    .is_file()
+                    .then(|| (entry.path(), metadata.len(), modified))
+            })
+            .collect();
+
+        let mut total: u64 = entries.iter().map(|(_, size, _)| size).sum();
+        if total <= self.max_bytes {
+            return Ok(0);
+        }
+
+        entries.sort_by_key(|(_, _, modified)| *modified);
+        let now = SystemTime::now();
+        let mut freed = 0;
+        for (path, size, modified) in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            let stale = now
+                .duration_since(modified)
+                .is_ok_and(|age| age >= MIN_ENTRY_AGE);
+            if !stale {
+                continue;
+            }
+
+            std::fs::remove_file(&path)?;
+            total -= size;
+            freed += size;
+        }
+
+        info!("Evicted {} MB from clip cache", freed / 1024 / 1024);
+        Ok(freed)
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.mp4", key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_key_depends_on_source_and_params() {
+        let temp_dir = tempdir().unwrap();
+        let source = temp_dir.path().join("clip.mp4");
+        std::fs::write(&source, b"video").unwrap();
+
+        let key = ClipCache::key(&source, "trim:1.000:5.000").unwrap();
+        assert_eq!(
+            ClipCache::key(&source, "trim:1.000:5.000"),
+            Some(key.clone())
+        );
+        assert_ne!(
+            ClipCache::key(&source, "trim:2.000:5.000"),
+            Some(key.clone())
+        );
+
+        // Re-recorded clip with the same name
+        std::fs::write(&source, b"longer video").unwrap();
+        assert_ne!(ClipCache::key(&source, "trim:1.000:5.000"), Some(key));
+
+        assert!(ClipCache::key(&temp_dir.path().join("missing.mp4"), "").is_none());
+    }
+
+    #[test]
+    fn test_insert_and_evict() {
+        let temp_dir = tempdir().unwrap();
+        let cache = ClipCache::new(temp_dir.path().join("cache"), 10);
+
+        let pending = cache.pending_path("old").unwrap();
+        std::fs::write(&pending, b"12345678").unwrap();
+        let old = cache.insert("old", &pending).unwrap();
+        assert_eq!(cache.get("old"), Some(old.clone()));
+        assert!(cache.get("missing").is_none());
+
+        // Backdate the first entry so it's eligible for eviction
+        let an_hour_ago = SystemTime::now() - Duration::from_secs(60 * 60);
+        std::fs::File::options()
+            .write(true)
+            .open(&old)
+            .unwrap()
+            .set_modified(an_hour_ago)
+            .unwrap();
+
+        let pending = cache.pending_path("new").unwrap();
+        std::fs::write(&pending, b"12345678").unwrap();
+        cache.insert("new", &pending).unwrap();
+
+        assert!(!old.exists());
+        assert!(cache.get("new").is_some());
+    }
+}
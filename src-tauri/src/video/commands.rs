@@ -1,9 +1,24 @@
 use crate::auth::middleware::{require_auth, require_tier};
 use crate::auth::SubscriptionTier;
+use crate::jobs::{JobKind, JobPriority};
+use crate::quota::MeteredFeature;
 use crate::storage::models::ClipMetadata;
+use crate::telemetry::TelemetryEvent;
+use crate::utils::error::AppError;
 use crate::utils::security;
-use crate::video::{AutoEditConfig, AutoEditProgress, AutoEditResult, VideoProcessor};
+use crate::video::animated::{self, AnimatedFormat};
+use crate::video::archive::{self, ArchiveCodec, ArchiveReport};
+use crate::video::audio_export::{self, AudioExportFormat};
+use crate::video::auto_composer::fill_champion_slots;
+use crate::video::markers::{self, MarkerFormat};
+use crate::video::music::{MusicLibrary, MusicMood, MusicTrack};
+use crate::video::upload_thumbnail;
+use crate::video::vod::{self, VodImport};
+use crate::video::{
+    export, AutoEditConfig, AutoEditProgress, AutoEditResult, ReelsExport, VideoProcessor,
+};
 use crate::AppState;
+use std::collections::HashMap;
 use std::path::PathBuf;
 use tauri::State;
 
@@ -11,17 +26,17 @@ use tauri::State;
 pub async fn get_clips(
     state: State<'_, AppState>,
     game_id: String,
-) -> Result<Vec<ClipMetadata>, String> {
+) -> Result<Vec<ClipMetadata>, AppError> {
     // Require authentication
-    require_auth(&state.auth).map_err(|e| e.to_string())?;
+    require_auth(&state.auth)?;
 
     // Validate game_id (prevent SQL injection)
-    let validated_game_id = security::validate_game_id(&game_id).map_err(|e| e.to_string())?;
+    let validated_game_id = security::validate_game_id(&game_id)?;
 
     state
         .storage
         .load_clip_metadata(&validated_game_id)
-        .map_err(|e| e.to_string())
+        .map_err(AppError::from)
 }
 
 /// Extract a clip from a video file (PRO feature)
@@ -32,18 +47,15 @@ pub async fn extract_clip(
     output_path: String,
     start_time: f64,
     duration: f64,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     // Require PRO tier for manual clip extraction
-    require_tier(&state.auth, SubscriptionTier::Pro).map_err(|e| e.to_string())?;
+    require_tier(&state.auth, SubscriptionTier::Pro)?;
 
     // Security validation
-    let validated_input =
-        security::validate_video_input_path(&input_path).map_err(|e| e.to_string())?;
-    let validated_output =
-        security::validate_video_output_path(&output_path).map_err(|e| e.to_string())?;
-    let validated_start_time =
-        security::validate_time_offset(start_time).map_err(|e| e.to_string())?;
-    let validated_duration = security::validate_duration(duration).map_err(|e| e.to_string())?;
+    let validated_input = security::validate_video_input_path(&input_path)?;
+    let validated_output = security::validate_video_output_path(&output_path)?;
+    let validated_start_time = security::validate_time_offset(start_time)?;
+    let validated_duration = security::validate_duration(duration)?;
 
     let processor = VideoProcessor::new();
 
@@ -54,8 +66,7 @@ pub async fn extract_clip(
             validated_start_time,
             validated_duration,
         )
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
 
     Ok(result_path.to_string_lossy().to_string())
 }
@@ -66,27 +77,24 @@ pub async fn compose_shorts(
     state: State<'_, AppState>,
     clip_paths: Vec<String>,
     output_path: String,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     // Require PRO tier for YouTube Shorts composition
-    require_tier(&state.auth, SubscriptionTier::Pro).map_err(|e| e.to_string())?;
+    require_tier(&state.auth, SubscriptionTier::Pro)?;
 
     // Security validation
-    let validated_clips: Result<Vec<PathBuf>, String> = clip_paths
+    let validated_clips = clip_paths
         .iter()
-        .map(|p| security::validate_video_input_path(p).map_err(|e| e.to_string()))
-        .collect();
-    let validated_clips = validated_clips?;
+        .map(|p| security::validate_video_input_path(p))
+        .collect::<Result<Vec<PathBuf>, _>>()?;
 
-    let validated_output =
-        security::validate_video_output_path(&output_path).map_err(|e| e.to_string())?;
+    let validated_output = security::validate_video_output_path(&output_path)?;
 
     let processor = VideoProcessor::new();
 
     // Standard YouTube Shorts resolution: 1080x1920 (9:16)
     let result_path = processor
         .compose_shorts(&validated_clips, validated_output, 1080, 1920)
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
 
     Ok(result_path.to_string_lossy().to_string())
 }
@@ -98,24 +106,46 @@ pub async fn generate_thumbnail(
     input_path: String,
     output_path: String,
     time_offset: f64,
-) -> Result<String, String> {
+) -> Result<String, AppError> {
     // Require PRO tier for thumbnail generation
-    require_tier(&state.auth, SubscriptionTier::Pro).map_err(|e| e.to_string())?;
+    require_tier(&state.auth, SubscriptionTier::Pro)?;
 
     // Security validation
-    let validated_input =
-        security::validate_video_input_path(&input_path).map_err(|e| e.to_string())?;
-    let validated_output =
-        security::validate_thumbnail_path(&output_path).map_err(|e| e.to_string())?;
-    let validated_time_offset =
-        security::validate_time_offset(time_offset).map_err(|e| e.to_string())?;
+    let validated_input = security::validate_video_input_path(&input_path)?;
+    let validated_output = security::validate_thumbnail_path(&output_path)?;
+    let validated_time_offset = security::validate_time_offset(time_offset)?;
 
     let processor = VideoProcessor::new();
 
     let result_path = processor
         .generate_thumbnail(validated_input, validated_output, validated_time_offset)
-        .await
-        .map_err(|e| e.to_string())?;
+        .await?;
+
+    Ok(result_path.to_string_lossy().to_string())
+}
+
+/// Save a single full-resolution frame of a clip as PNG
+///
+/// Used by the editor for custom thumbnails and screenshots.
+#[tauri::command]
+pub async fn capture_frame(
+    state: State<'_, AppState>,
+    clip_path: String,
+    timestamp: f64,
+    output: String,
+) -> Result<String, AppError> {
+    // Require authentication
+    require_auth(&state.auth)?;
+
+    let validated_input = security::validate_video_input_path(&clip_path)?;
+    let validated_output = security::validate_frame_path(&output)?;
+    let validated_timestamp = security::validate_time_offset(timestamp)?;
+
+    let processor = VideoProcessor::new();
+
+    let result_path = processor
+        .capture_frame(validated_input, validated_output, validated_timestamp)
+        .await?;
 
     Ok(result_path.to_string_lossy().to_string())
 }
@@ -125,89 +155,292 @@ pub async fn generate_thumbnail(
 pub async fn get_video_duration(
     state: State<'_, AppState>,
     input_path: String,
-) -> Result<f64, String> {
+) -> Result<f64, AppError> {
     // Require authentication
-    require_auth(&state.auth).map_err(|e| e.to_string())?;
+    require_auth(&state.auth)?;
 
     // Security validation
-    let validated_input =
-        security::validate_video_input_path(&input_path).map_err(|e| e.to_string())?;
+    let validated_input = security::validate_video_input_path(&input_path)?;
 
     let processor = VideoProcessor::new();
 
-    let duration = processor
-        .get_duration(validated_input)
-        .await
-        .map_err(|e| e.to_string())?;
+    let duration = processor.get_duration(validated_input).await?;
 
     Ok(duration)
 }
 
+/// Import an externally recorded VOD of a game and cut its events into clips (PRO feature)
+///
+/// `offset_secs` is the VOD time at which the game clock reads 0:00. Without
+/// it the offset is read from the in-game clock.
+#[tauri::command]
+pub async fn import_vod(
+    state: State<'_, AppState>,
+    path: String,
+    game_id: String,
+    offset_secs: Option<f64>,
+) -> Result<VodImport, AppError> {
+    // Require PRO tier, like manual clip extraction
+    require_tier(&state.auth, SubscriptionTier::Pro)?;
+
+    // Security validation
+    let validated_path = security::validate_video_input_path(&path)?;
+    let validated_game_id = security::validate_game_id(&game_id)?;
+    let validated_offset = offset_secs
+        .map(|offset| security::validate_range(offset, -7200.0, 7200.0, "VOD offset"))
+        .transpose()?;
+
+    let timing = state.recording_settings.read().await.clip_timing.clone();
+    let import = vod::import_vod(
+        &state.storage,
+        &timing,
+        &validated_path,
+        &validated_game_id,
+        validated_offset,
+    )
+    .await?;
+
+    for clip in &import.clips {
+        let job = JobKind::GenerateThumbnail {
+            game_id: validated_game_id.clone(),
+            clip_path: PathBuf::from(&clip.file_path),
+        };
+        if let Err(e) = state.jobs.enqueue(job, JobPriority::Low).await {
+            tracing::warn!("Failed to queue thumbnail generation: {}", e);
+        }
+    }
+
+    Ok(import)
+}
+
+/// Export a game's events as markers for external editors
+///
+/// Writes an EDL, CSV or YouTube chapter list to `output_path` with times
+/// relative to a VOD whose game clock reads 0:00 at `offset_secs` (default 0).
+#[tauri::command]
+pub async fn export_markers(
+    state: State<'_, AppState>,
+    game_id: String,
+    format: MarkerFormat,
+    output_path: String,
+    offset_secs: Option<f64>,
+) -> Result<String, AppError> {
+    // Require authentication
+    require_auth(&state.auth)?;
+
+    // Security validation
+    let validated_game_id = security::validate_game_id(&game_id)?;
+    let validated_output = security::validate_markers_path(&output_path)?;
+    let validated_offset = security::validate_range(
+        offset_secs.unwrap_or_default(),
+        -7200.0,
+        7200.0,
+        "VOD offset",
+    )?;
+
+    let events = state.storage.load_events(&validated_game_id)?;
+    if events.is_empty() {
+        return Err(format!("No events stored for game {}", validated_game_id).into());
+    }
+
+    let content = markers::render_markers(&validated_game_id, &events, validated_offset, format);
+    tokio::fs::write(&validated_output, content).await?;
+
+    Ok(validated_output.to_string_lossy().to_string())
+}
+
 /// Delete a clip from storage
 #[tauri::command]
 pub async fn delete_clip(
     state: State<'_, AppState>,
     clip_file_path: String,
     game_id: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     // Require authentication
-    require_auth(&state.auth).map_err(|e| e.to_string())?;
+    require_auth(&state.auth)?;
 
     // Security validation
-    let validated_path =
-        security::validate_video_input_path(&clip_file_path).map_err(|e| e.to_string())?;
-    let validated_game_id = security::validate_game_id(&game_id).map_err(|e| e.to_string())?;
-
-    // Delete the video file
-    if validated_path.exists() {
-        std::fs::remove_file(&validated_path).map_err(|e| e.to_string())?;
-        tracing::info!("Deleted clip file: {:?}", validated_path);
-    }
+    let validated_path = security::validate_video_input_path(&clip_file_path)?;
+    let validated_game_id = security::validate_game_id(&game_id)?;
 
-    // Delete from JSON storage
+    // Move the clip to the recycle bin (also removes it from JSON storage)
     state
         .storage
-        .delete_clip_metadata(&validated_game_id, &clip_file_path)
-        .map_err(|e| format!("Failed to delete clip metadata: {}", e))?;
+        .trash_clip(&validated_game_id, &clip_file_path)?;
 
-    tracing::info!(
-        "Successfully deleted clip and metadata: {:?}",
-        validated_path
-    );
+    tracing::info!("Moved clip to trash: {:?}", validated_path);
     Ok(())
 }
 
+/// Export a video with the Instagram Reels preset
+///
+/// Transcodes to Reels specs (1080x1920, max 90s, 8-bit SDR) into the
+/// "Reels Export" folder and writes the caption and hashtags to a `.txt`
+/// file next to it. The frontend opens the folder for the share handoff.
+#[tauri::command]
+pub async fn export_reels(
+    state: State<'_, AppState>,
+    input_path: String,
+    caption: String,
+    hashtags: Vec<String>,
+) -> Result<ReelsExport, AppError> {
+    // Require authentication
+    require_auth(&state.auth)?;
+
+    // Security validation
+    let validated_input = security::validate_video_input_path(&input_path)?;
+
+    let export_dir = export::export_dir(state.storage.base_path());
+
+    export::export_reels(&validated_input, &export_dir, &caption, &hashtags)
+        .await
+        .map_err(|e| {
+            tracing::error!("Reels export failed: {}", e);
+            AppError::from(e)
+        })
+}
+
+/// Re-encode a game's clips with a high-compression codec to save space
+///
+/// The originals are replaced; clips that wouldn't get smaller are left
+/// alone. Defaults to HEVC.
+#[tauri::command]
+pub async fn archive_game(
+    state: State<'_, AppState>,
+    game_id: String,
+    codec: Option<ArchiveCodec>,
+) -> Result<ArchiveReport, AppError> {
+    // Require authentication
+    require_auth(&state.auth)?;
+
+    let validated_game_id = security::validate_game_id(&game_id)?;
+
+    let codec = codec.unwrap_or_default();
+    archive::archive_game(&state.storage, &validated_game_id, codec)
+        .await
+        .map_err(|e| {
+            tracing::error!("Archiving game {} failed: {}", validated_game_id, e);
+            AppError::from(e)
+        })
+}
+
+/// Export a clip's audio next to it as MP3 (default) or WAV
+///
+/// With `mic_only`, only the microphone track is exported (voice comms).
+/// Returns the path of the audio file.
+#[tauri::command]
+pub async fn extract_audio(
+    state: State<'_, AppState>,
+    clip_path: String,
+    format: Option<AudioExportFormat>,
+    mic_only: Option<bool>,
+) -> Result<String, AppError> {
+    // Require authentication
+    require_auth(&state.auth)?;
+
+    let validated_input = security::validate_video_input_path(&clip_path)?;
+
+    let output = audio_export::extract_audio(
+        &validated_input,
+        format.unwrap_or_default(),
+        mic_only.unwrap_or(false),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Audio export of {} failed: {}", clip_path, e);
+        AppError::from(e)
+    })?;
+
+    Ok(output.to_string_lossy().to_string())
+}
+
+/// Export a few seconds of a clip as a looping GIF (default) or WebP
+///
+/// Written next to the clip, at `width` pixels wide (defaults: 15 fps,
+/// 480px). Returns the path of the animation.
+#[tauri::command]
+pub async fn export_gif(
+    state: State<'_, AppState>,
+    clip_path: String,
+    start: f64,
+    duration: f64,
+    fps: Option<u32>,
+    width: Option<u32>,
+    format: Option<AnimatedFormat>,
+) -> Result<String, AppError> {
+    // Require authentication
+    require_auth(&state.auth)?;
+
+    let validated_input = security::validate_video_input_path(&clip_path)?;
+    let validated_start = security::validate_time_offset(start)?;
+    let validated_duration =
+        security::validate_range(duration, 0.1, animated::MAX_DURATION_SECS, "duration")?;
+    let validated_fps = security::validate_range(
+        fps.unwrap_or(15) as f64,
+        animated::MIN_FPS as f64,
+        animated::MAX_FPS as f64,
+        "fps",
+    )? as u32;
+    let validated_width = security::validate_range(
+        width.unwrap_or(480) as f64,
+        animated::MIN_WIDTH as f64,
+        animated::MAX_WIDTH as f64,
+        "width",
+    )? as u32;
+
+    let output = animated::export_animated(
+        &validated_input,
+        validated_start,
+        validated_duration,
+        validated_fps,
+        validated_width,
+        format.unwrap_or_default(),
+    )
+    .await
+    .map_err(|e| {
+        tracing::error!("Animated export of {} failed: {}", clip_path, e);
+        AppError::from(e)
+    })?;
+
+    Ok(output.to_string_lossy().to_string())
+}
+
 /// Start auto-edit composition for YouTube Shorts
 ///
 /// This is the main entry point for automated Shorts generation.
 /// It will intelligently select clips, apply canvas overlays, mix audio,
 /// and produce a final 60/120/180 second video ready for upload.
 ///
-/// Quota limits:
+/// Quota limits (see `quota::default_limit`, overridable remotely):
 /// - FREE tier: 5 auto-edits per month
 /// - PRO tier: Unlimited
+///
+/// While a game is running the job stays queued until it ends, unless
+/// `run_anyway` is set or `run_job_anyway` is called with its job ID.
 #[tauri::command]
 pub async fn start_auto_edit(
     state: State<'_, AppState>,
-    config: AutoEditConfig,
-) -> Result<AutoEditResult, String> {
+    mut config: AutoEditConfig,
+    run_anyway: Option<bool>,
+) -> Result<AutoEditResult, AppError> {
     // Require authentication (both FREE and PRO can use auto-edit)
-    require_auth(&state.auth).map_err(|e| e.to_string())?;
+    let user = require_auth(&state.auth)?;
+    config.user_id = Some(user.id);
 
-    // Check tier and quota
-    let tier = state.auth.get_tier().map_err(|e| e.to_string())?;
-    let is_pro = matches!(tier, SubscriptionTier::Pro);
+    let bumpers = [&config.intro_path, &config.outro_path];
+    for path in bumpers.into_iter().flatten() {
+        security::validate_video_input_path(path)?;
+    }
 
     // Check quota before starting
-    let remaining = state
-        .storage
-        .check_auto_edit_quota(is_pro)
-        .map_err(|e| format!("Quota check failed: {}", e))?;
+    let quota = state.quota.check(MeteredFeature::AutoEdit)?;
 
     tracing::info!(
-        "Auto-edit quota check passed: tier={:?}, remaining={}",
-        tier,
-        if is_pro { "unlimited".to_string() } else { remaining.to_string() }
+        "Auto-edit quota check passed: tier={}, remaining={}",
+        quota.tier,
+        quota
+            .remaining
+            .map_or_else(|| "unlimited".to_string(), |r| r.to_string())
     );
 
     // Generate unique job ID
@@ -219,27 +452,32 @@ pub async fn start_auto_edit(
         config.target_duration
     );
 
+    if !run_anyway.unwrap_or(false) {
+        state.auto_composer.wait_for_game_end(&job_id).await;
+    }
+
     // Start auto-composition
-    let result = state
-        .auto_composer
-        .compose(config, job_id.clone())
-        .await
-        .map_err(|e| {
-            tracing::error!("Auto-edit failed for job {}: {}", job_id, e);
-            format!("Auto-edit failed: {}", e)
-        })?;
-
-    // Increment usage counter on success (only for FREE tier, PRO is unlimited)
-    if !is_pro {
-        state
-            .storage
-            .increment_auto_edit_usage()
-            .map_err(|e| {
-                tracing::error!("Failed to increment usage: {}", e);
-                // Don't fail the whole operation if usage increment fails
-                format!("Warning: Usage tracking failed: {}", e)
-            })
-            .ok();
+    let result = state.auto_composer.compose(config, job_id.clone()).await;
+
+    state
+        .telemetry
+        .record(TelemetryEvent::AutoEditCompleted {
+            success: result.is_ok(),
+        })
+        .await;
+
+    let result = result.map_err(|e| {
+        tracing::error!("Auto-edit failed for job {}: {}", job_id, e);
+        AppError::from(e)
+    })?;
+
+    // Count usage on success
+    match state.quota.record(MeteredFeature::AutoEdit) {
+        Ok(status) => state.notification_center.quota_recorded(&status),
+        Err(e) => {
+            // Don't fail the whole operation if usage tracking fails
+            tracing::error!("Failed to record auto-edit usage: {}", e);
+        }
     }
 
     tracing::info!("Auto-edit completed successfully: {:?}", result.output_path);
@@ -253,9 +491,9 @@ pub async fn start_auto_edit(
 #[tauri::command]
 pub async fn get_auto_edit_progress(
     state: State<'_, AppState>,
-) -> Result<Option<AutoEditProgress>, String> {
+) -> Result<Option<AutoEditProgress>, AppError> {
     // Require authentication
-    require_auth(&state.auth).map_err(|e| e.to_string())?;
+    require_auth(&state.auth)?;
 
     let progress = state.auto_composer.get_progress().await;
     Ok(progress)
@@ -270,14 +508,11 @@ pub async fn get_auto_edit_progress(
 pub async fn save_canvas_template(
     state: State<'_, AppState>,
     template: crate::video::CanvasTemplate,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     // Require authentication
-    require_auth(&state.auth).map_err(|e| e.to_string())?;
+    require_auth(&state.auth)?;
 
-    state
-        .storage
-        .save_canvas_template(&template)
-        .map_err(|e| format!("Failed to save canvas template: {}", e))?;
+    state.storage.save_canvas_template(&template)?;
 
     Ok(())
 }
@@ -287,18 +522,14 @@ pub async fn save_canvas_template(
 pub async fn load_canvas_template(
     state: State<'_, AppState>,
     template_id: String,
-) -> Result<crate::video::CanvasTemplate, String> {
+) -> Result<crate::video::CanvasTemplate, AppError> {
     // Require authentication
-    require_auth(&state.auth).map_err(|e| e.to_string())?;
+    require_auth(&state.auth)?;
 
     // Security validation
-    let validated_template_id =
-        security::validate_template_id(&template_id).map_err(|e| e.to_string())?;
+    let validated_template_id = security::validate_template_id(&template_id)?;
 
-    let template = state
-        .storage
-        .load_canvas_template(&validated_template_id)
-        .map_err(|e| format!("Failed to load canvas template: {}", e))?;
+    let template = state.storage.load_canvas_template(&validated_template_id)?;
 
     Ok(template)
 }
@@ -307,14 +538,11 @@ pub async fn load_canvas_template(
 #[tauri::command]
 pub async fn list_canvas_templates(
     state: State<'_, AppState>,
-) -> Result<Vec<crate::storage::CanvasTemplateInfo>, String> {
+) -> Result<Vec<crate::storage::CanvasTemplateInfo>, AppError> {
     // Require authentication
-    require_auth(&state.auth).map_err(|e| e.to_string())?;
+    require_auth(&state.auth)?;
 
-    let templates = state
-        .storage
-        .list_canvas_templates()
-        .map_err(|e| format!("Failed to list canvas templates: {}", e))?;
+    let templates = state.storage.list_canvas_templates()?;
 
     Ok(templates)
 }
@@ -324,18 +552,200 @@ pub async fn list_canvas_templates(
 pub async fn delete_canvas_template(
     state: State<'_, AppState>,
     template_id: String,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     // Require authentication
-    require_auth(&state.auth).map_err(|e| e.to_string())?;
+    require_auth(&state.auth)?;
 
     // Security validation
-    let validated_template_id =
-        security::validate_template_id(&template_id).map_err(|e| e.to_string())?;
+    let validated_template_id = security::validate_template_id(&template_id)?;
 
     state
         .storage
-        .delete_canvas_template(&validated_template_id)
-        .map_err(|e| format!("Failed to delete canvas template: {}", e))?;
+        .delete_canvas_template(&validated_template_id)?;
 
     Ok(())
 }
+
+/// Render the YouTube thumbnail of an auto-edit result (1280x720)
+///
+/// Combines the frame at `timestamp` (the middle of the short by default)
+/// with the text and images of canvas template `template_id`, its champion
+/// icon showing the champion of the short's games. Uploads of the result use
+/// it unless another thumbnail is given.
+#[tauri::command]
+pub async fn generate_upload_thumbnail(
+    state: State<'_, AppState>,
+    result_id: String,
+    template_id: Option<String>,
+    timestamp: Option<f64>,
+) -> Result<String, AppError> {
+    // Require authentication
+    require_auth(&state.auth)?;
+
+    let validated_result_id = security::validate_id(&result_id, 100)?;
+    let result = state.storage.load_auto_edit_result(&validated_result_id)?;
+    let template = match template_id {
+        Some(template_id) => {
+            let validated_template_id = security::validate_template_id(&template_id)?;
+            let template = state.storage.load_canvas_template(&validated_template_id)?;
+            Some(
+                fill_champion_slots(&template, &state.storage, &state.ddragon, &result.game_ids)
+                    .await,
+            )
+        }
+        None => None,
+    };
+
+    let video_path = PathBuf::from(&result.output_path);
+    let timestamp = match timestamp {
+        Some(timestamp) => security::validate_time_offset(timestamp)?,
+        None => VideoProcessor::new().get_duration(&video_path).await? / 2.0,
+    };
+
+    let output_path = video_path.with_file_name(format!(
+        "{}_youtube_thumbnail.jpg",
+        video_path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("short")
+    ));
+    upload_thumbnail::render(&video_path, timestamp, template.as_ref(), &output_path).await?;
+
+    state
+        .storage
+        .set_auto_edit_upload_thumbnail(&validated_result_id, &output_path)?;
+
+    Ok(output_path.to_string_lossy().to_string())
+}
+
+// ========================================================================
+// Clip Selection Feedback
+// ========================================================================
+
+/// Current auto-selection weight of each event type, learned from manual
+/// selections or set by the user (1.0 = no preference)
+#[tauri::command]
+pub async fn get_selection_weights(
+    state: State<'_, AppState>,
+) -> Result<HashMap<String, f64>, AppError> {
+    let user = require_auth(&state.auth)?;
+
+    let feedback = state.storage.load_selection_feedback(&user.id)?;
+    Ok(feedback.weights())
+}
+
+/// Pin the auto-selection weight of an event type (0.0-2.0), or go back to
+/// the learned weight with `None`
+#[tauri::command]
+pub async fn set_selection_weight(
+    state: State<'_, AppState>,
+    event_type: String,
+    weight: Option<f64>,
+) -> Result<(), AppError> {
+    let user = require_auth(&state.auth)?;
+
+    let mut feedback = state.storage.load_selection_feedback(&user.id)?;
+    match weight {
+        Some(weight) => {
+            let weight = security::validate_range(weight, 0.0, 2.0, "weight")?;
+            feedback.overrides.insert(event_type, weight);
+        }
+        None => {
+            feedback.overrides.remove(&event_type);
+        }
+    }
+
+    state
+        .storage
+        .save_selection_feedback(&user.id, &feedback)
+        .map_err(AppError::from)
+}
+
+/// Forget learned selection preferences and pinned weights
+#[tauri::command]
+pub async fn reset_selection_feedback(state: State<'_, AppState>) -> Result<(), AppError> {
+    let user = require_auth(&state.auth)?;
+
+    state
+        .storage
+        .save_selection_feedback(&user.id, &Default::default())
+        .map_err(AppError::from)
+}
+
+// ========================================================================
+// Music Library
+// ========================================================================
+
+/// List tracks in the background music library
+#[tauri::command]
+pub async fn list_music_tracks(state: State<'_, AppState>) -> Result<Vec<MusicTrack>, AppError> {
+    require_auth(&state.auth)?;
+
+    MusicLibrary::open(state.storage.base_path())
+        .tracks()
+        .map_err(AppError::from)
+}
+
+/// Copy an audio file into the music library
+///
+/// `title` defaults to the file name.
+#[tauri::command]
+pub async fn import_music_track(
+    state: State<'_, AppState>,
+    source_path: String,
+    title: Option<String>,
+    mood: MusicMood,
+    bpm: Option<u32>,
+) -> Result<MusicTrack, AppError> {
+    require_auth(&state.auth)?;
+
+    let source = security::validate_audio_path(&source_path)?;
+    let title = title
+        .filter(|title| !title.trim().is_empty())
+        .or_else(|| {
+            source
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+        })
+        .unwrap_or_default();
+
+    let duration = VideoProcessor::new().get_duration(&source).await?;
+
+    MusicLibrary::open(state.storage.base_path())
+        .import(&source, title, mood, bpm, duration)
+        .map_err(AppError::from)
+}
+
+/// Change a library track's mood and BPM
+#[tauri::command]
+pub async fn tag_music_track(
+    state: State<'_, AppState>,
+    track_id: String,
+    mood: MusicMood,
+    bpm: Option<u32>,
+) -> Result<MusicTrack, AppError> {
+    require_auth(&state.auth)?;
+
+    let track_id = security::validate_id(&track_id, 100)?;
+
+    MusicLibrary::open(state.storage.base_path())
+        .tag(&track_id, mood, bpm)?
+        .ok_or_else(|| AppError::Other(format!("Music track not found: {}", track_id)))
+}
+
+/// Remove a track from the music library
+///
+/// Returns false if no track has that ID.
+#[tauri::command]
+pub async fn delete_music_track(
+    state: State<'_, AppState>,
+    track_id: String,
+) -> Result<bool, AppError> {
+    require_auth(&state.auth)?;
+
+    let track_id = security::validate_id(&track_id, 100)?;
+
+    MusicLibrary::open(state.storage.base_path())
+        .remove(&track_id)
+        .map_err(AppError::from)
+}
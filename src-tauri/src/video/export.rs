@@ -0,0 +1,202 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::process::Command as TokioCommand;
+use tracing::info;
+
+use super::{execute_ffmpeg_command, Result, VideoError};
+
+/// Instagram Reels export preset
+///
+/// 1080x1920 H.264 High / yuv420p (8-bit SDR) with BT.709 colour tags, AAC
+/// stereo audio, capped at 90 seconds. Container metadata is dropped so HDR
+/// side data from the source never reaches the export.
+pub struct ReelsPreset;
+
+impl ReelsPreset {
+    pub const WIDTH: u32 = 1080;
+    pub const HEIGHT: u32 = 1920;
+    pub const MAX_DURATION_SECS: f64 = 90.0;
+    pub const FRAME_RATE: u32 = 30;
+    pub const VIDEO_BITRATE: &'static str = "5M";
+    pub const AUDIO_BITRATE: &'static str = "128k";
+
+    /// Folder (inside the app data dir) exports are written to
+    pub const EXPORT_DIR: &'static str = "Reels Export";
+
+    /// Fit into 1080x1920 (letterboxed), square pixels, 8-bit 4:2:0
+    pub fn video_filter() -> String {
+        format!(
+            "scale={w}:{h}:force_original_aspect_ratio=decrease,\
+             pad={w}:{h}:(ow-iw)/2:(oh-ih)/2,setsar=1,fps={fps},format=yuv420p",
+            w = Self::WIDTH,
+            h = Self::HEIGHT,
+            fps = Self::FRAME_RATE
+        )
+    }
+}
+
+/// Files written by a Reels export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReelsExport {
+    pub video_path: String,
+    /// Caption + hashtags, ready to paste into Instagram
+    pub caption_path: String,
+    pub duration_secs: f64,
+}
+
+/// Caption text with hashtags on their own paragraph (`#` added where missing)
+pub fn format_caption(caption: &str, hashtags: &[String]) -> String {
+    let hashtags = hashtags
+        .iter()
+        .map(|tag| tag.trim().trim_start_matches('#'))
+        .filter(|tag| !tag.is_empty())
+        .map(|tag| format!("#{}", tag.replace(char::is_whitespace, "")))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    match (caption.trim(), hashtags.is_empty()) {
+        (caption, true) => format!("{}\n", caption),
+        ("", false) => format!("{}\n", hashtags),
+        (caption, false) => format!("{}\n\n{}\n", caption, hashtags),
+    }
+}
+
+/// Transcode a video with the Reels preset into `export_dir`, writing the
+/// caption file next to it
+pub async fn export_reels(
+    input_path: &Path,
+    export_dir: &Path,
+    caption: &str,
+    hashtags: &[String],
+) -> Result<ReelsExport> {
+    if !input_path.exists() {
+        return Err(VideoError::FileNotFound {
+            path: input_path.display().to_string(),
+        });
+    }
+
+    tokio::fs::create_dir_all(export_dir).await.map_err(|_| {
+        VideoError::OutputDirectoryNotFound {
+            path: export_dir.display().to_string(),
+        }
+    })?;
+
+    let stem = input_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "video".to_string());
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    let video_path = export_dir.join(format!("{}_reels_{}.mp4", stem, timestamp));
+    let caption_path = video_path.with_extension("txt");
+
+    info!(
+        "Exporting Reels video: {:?} -> {:?}",
+        input_path, video_path
+    );
+
+    let mut command = TokioCommand::new("ffmpeg");
+    command.args([
+        "-i",
+        path_str(input_path)?,
+        "-t",
+        &ReelsPreset::MAX_DURATION_SECS.to_string(),
+        "-map",
+        "0:v:0",
+        "-map",
+        "0:a:0?",
+        "-map_metadata",
+        "-1", // Drop source metadata (incl. HDR side data)
+        "-vf",
+        &ReelsPreset::video_filter(),
+        "-c:v",
+        "libx264",
+        "-profile:v",
+        "high",
+        "-preset",
+        "medium",
+        "-b:v",
+        ReelsPreset::VIDEO_BITRATE,
+        "-maxrate",
+        ReelsPreset::VIDEO_BITRATE,
+        "-bufsize",
+        "10M",
+        "-color_primaries",
+        "bt709",
+        "-color_trc",
+        "bt709",
+        "-colorspace",
+        "bt709",
+        "-c:a",
+        "aac",
+        "-b:a",
+        ReelsPreset::AUDIO_BITRATE,
+        "-ar",
+        "48000",
+        "-ac",
+        "2",
+        "-movflags",
+        "+faststart",
+        "-y",
+        path_str(&video_path)?,
+    ]);
+
+    execute_ffmpeg_command(&mut command).await?;
+
+    tokio::fs::write(&caption_path, format_caption(caption, hashtags))
+        .await
+        .map_err(|_| VideoError::FileAccessError {
+            path: caption_path.display().to_string(),
+        })?;
+
+    let duration_secs = super::VideoProcessor::new()
+        .get_duration(&video_path)
+        .await?;
+
+    info!("Reels export complete: {:?}", video_path);
+    Ok(ReelsExport {
+        video_path: video_path.to_string_lossy().to_string(),
+        caption_path: caption_path.to_string_lossy().to_string(),
+        duration_secs,
+    })
+}
+
+/// Export directory under the app data dir
+pub fn export_dir(base_path: &Path) -> PathBuf {
+    base_path.join(ReelsPreset::EXPORT_DIR)
+}
+
+fn path_str(path: &Path) -> Result<&str> {
+    path.to_str().ok_or_else(|| VideoError::FileAccessError {
+        path: path.display().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reels_video_filter() {
+        let filter = ReelsPreset::video_filter();
+        assert!(filter.starts_with("scale=1080:1920:force_original_aspect_ratio=decrease"));
+        assert!(filter.contains("pad=1080:1920"));
+        assert!(filter.ends_with("format=yuv420p"));
+    }
+
+    #[test]
+    fn test_format_caption() {
+        let hashtags = vec![
+            "leagueoflegends".to_string(),
+            "#pentakill".to_string(),
+            " lol shorts ".to_string(),
+            "#".to_string(),
+        ];
+
+        assert_eq!(
+            format_caption("Insane teamfight", &hashtags),
+            "Insane teamfight\n\n#leagueoflegends #pentakill #lolshorts\n"
+        );
+        assert_eq!(format_caption("Just a caption", &[]), "Just a caption\n");
+        assert_eq!(format_caption("  ", &hashtags[..1]), "#leagueoflegends\n");
+    }
+}
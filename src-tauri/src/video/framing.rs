@@ -0,0 +1,236 @@
+//! Fitting landscape gameplay into a vertical frame
+//!
+//! A 9:16 crop of a 16:9 recording keeps less than a third of its width, so
+//! where the crop sits decides whether the fight makes it into the short.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// How each clip is fitted into the output frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum Framing {
+    /// Crop the middle of the frame
+    #[default]
+    Center,
+    /// Keep the whole frame, padding above and below
+    Letterbox,
+    /// Crop around the player's champion
+    ///
+    /// The camera follows the champion, so it sits near the middle of the
+    /// screen. The crop is tightened to leave out the scoreboard and ability
+    /// bar; the kill feed and minimap are outside it either way.
+    AutoReframe,
+    /// Crop around a fixed point of the frame
+    Focus {
+        /// X position as percentage (0-100)
+        x: f32,
+        /// Y position as percentage (0-100)
+        y: f32,
+        /// Share of the source height to keep (1.0 = full height)
+        #[serde(default = "default_zoom")]
+        zoom: f32,
+    },
+}
+
+fn default_zoom() -> f32 {
+    1.0
+}
+
+/// Champion position and crop height used by [`Framing::AutoReframe`]
+const CHAMPION_FOCUS: (f32, f32) = (50.0, 46.0);
+const CHAMPION_ZOOM: f32 = 0.8;
+
+impl Framing {
+    /// FFmpeg video filter producing a `width`x`height` frame
+    pub fn filter(&self, width: u32, height: u32) -> String {
+        match *self {
+            Framing::Center => format!(
+                "scale=-1:{h}:force_original_aspect_ratio=increase,crop={w}:{h},setsar=1",
+                w = width,
+                h = height
+            ),
+            Framing::Letterbox => format!(
+                "scale={w}:{h}:force_original_aspect_ratio=decrease,pad={w}:{h}:(ow-iw)/2:(oh-ih)/2,setsar=1",
+                w = width,
+                h = height
+            ),
+            Framing::AutoReframe => {
+                focus_filter(CHAMPION_FOCUS.0, CHAMPION_FOCUS.1, CHAMPION_ZOOM, width, height)
+            }
+            Framing::Focus { x, y, zoom } => focus_filter(x, y, zoom, width, height),
+        }
+    }
+}
+
+/// Part of the source frame shown enlarged at the top of the short
+///
+/// Defaults to League's kill feed, so multikills stay readable on a phone;
+/// point it at the scoreboard to show that instead.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KillFeedOverlay {
+    /// Source region as percentages (0-100) of the 16:9 frame
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Width in the output as a percentage of the output width
+    pub output_width: f32,
+    /// Distance from the top of the output as a percentage of its height
+    pub top: f32,
+}
+
+impl Default for KillFeedOverlay {
+    fn default() -> Self {
+        Self {
+            x: 78.0,
+            y: 12.0,
+            width: 22.0,
+            height: 26.0,
+            output_width: 90.0,
+            top: 4.0,
+        }
+    }
+}
+
+/// Framing plus anything copied from the source frame on top of it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShortsLayout {
+    pub framing: Framing,
+    pub kill_feed: Option<KillFeedOverlay>,
+    /// 3D LUT (.cube) color grading the gameplay
+    pub color_lut: Option<PathBuf>,
+}
+
+impl ShortsLayout {
+    /// FFmpeg video filter producing a `width`x`height` frame
+    pub fn filter(&self, width: u32, height: u32) -> String {
+        let layout = self.layout_filter(width, height);
+        match &self.color_lut {
+            // Graded before the kill feed is copied, so it matches
+            Some(lut) => format!(
+                "lut3d=file='{}':interp=tetrahedral,{}",
+                filter_path(lut),
+                layout
+            ),
+            None => layout,
+        }
+    }
+
+    fn layout_filter(&self, width: u32, height: u32) -> String {
+        let framed = self.framing.filter(width, height);
+        let Some(feed) = self.kill_feed else {
+            return framed;
+        };
+
+        let pct = |value: f32| value.clamp(0.0, 100.0) / 100.0;
+        let feed_width = (width as f32 * pct(feed.output_width)) as u32 / 2 * 2;
+        format!(
+            "split[src][feed];[src]{framed}[base];\
+             [feed]crop=iw*{w}:ih*{h}:iw*{x}:ih*{y},scale={feed_width}:-2[kill_feed];\
+             [base][kill_feed]overlay=(W-w)/2:H*{top}",
+            w = pct(feed.width),
+            h = pct(feed.height),
+            x = pct(feed.x),
+            y = pct(feed.y),
+            top = pct(feed.top),
+        )
+    }
+}
+
+/// `path` as a filter option value (the drive colon would end the option)
+pub(crate) fn filter_path(path: &Path) -> String {
+    path.to_string_lossy()
+        .replace('\\', "/")
+        .replace(':', "\\:")
+        .replace('\'', "'\\''")
+}
+
+/// Crop a `width`:`height` window centered on (`x`%, `y`%) of the source,
+/// clamped to the frame, then scale it to the output size
+fn focus_filter(x: f32, y: f32, zoom: f32, width: u32, height: u32) -> String {
+    let zoom = zoom.clamp(0.1, 1.0);
+    let x = x.clamp(0.0, 100.0) / 100.0;
+    let y = y.clamp(0.0, 100.0) / 100.0;
+
+    format!(
+        "crop=w=min(iw\\,ih*{zoom}*{width}/{height}):h=ih*{zoom}:\
+         x=clip(iw*{x}-ow/2\\,0\\,iw-ow):y=clip(ih*{y}-oh/2\\,0\\,ih-oh),\
+         scale={width}:{height},setsar=1"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_framing_filters() {
+        assert!(Framing::Center
+            .filter(1080, 1920)
+            .contains("crop=1080:1920"));
+        assert!(Framing::Letterbox
+            .filter(1080, 1920)
+            .contains("pad=1080:1920"));
+
+        let filter = Framing::Focus {
+            x: 25.0,
+            y: 150.0,
+            zoom: 0.5,
+        }
+        .filter(1080, 1920);
+        assert!(filter.contains("h=ih*0.5:"));
+        assert!(filter.contains("x=clip(iw*0.25-ow/2"));
+        assert!(filter.contains("y=clip(ih*1-oh/2"));
+        assert!(filter.ends_with("scale=1080:1920,setsar=1"));
+    }
+
+    #[test]
+    fn test_kill_feed_overlay_filter() {
+        let layout = ShortsLayout {
+            framing: Framing::Center,
+            kill_feed: Some(KillFeedOverlay::default()),
+            color_lut: None,
+        };
+        let filter = layout.filter(1080, 1920);
+        assert!(filter.starts_with("split[src][feed];[src]scale=-1:1920"));
+        assert!(filter.contains("crop=iw*0.22:ih*0.26:iw*0.78:ih*0.12,scale=972:-2"));
+        assert!(filter.ends_with("overlay=(W-w)/2:H*0.04"));
+
+        let layout = ShortsLayout::default();
+        assert_eq!(
+            layout.filter(1080, 1920),
+            Framing::Center.filter(1080, 1920)
+        );
+    }
+
+    #[test]
+    fn test_color_lut_filter() {
+        let layout = ShortsLayout {
+            color_lut: Some(PathBuf::from(r"C:\LUTs\teal orange.cube")),
+            ..Default::default()
+        };
+        let filter = layout.filter(1080, 1920);
+        assert!(filter.starts_with(
+            r"lut3d=file='C\:/LUTs/teal orange.cube':interp=tetrahedral,scale=-1:1920"
+        ));
+    }
+
+    #[test]
+    fn test_framing_deserialization() {
+        let framing: Framing = serde_json::from_str(r#"{"mode": "auto_reframe"}"#).unwrap();
+        assert_eq!(framing, Framing::AutoReframe);
+
+        let framing: Framing =
+            serde_json::from_str(r#"{"mode": "focus", "x": 30, "y": 40}"#).unwrap();
+        assert_eq!(
+            framing,
+            Framing::Focus {
+                x: 30.0,
+                y: 40.0,
+                zoom: 1.0
+            }
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! Event marker export for external editors
+//!
+//! Writes a game's events as markers relative to a VOD of the game, so
+//! Premiere/Resolve users (or YouTube chapters) can jump straight to the
+//! highlights.
+
+use serde::{Deserialize, Serialize};
+
+use crate::notifications::event_label;
+use crate::storage::models::EventData;
+
+/// Timecode frame rate of exported EDLs
+const EDL_FPS: f64 = 30.0;
+
+/// YouTube ignores chapters shorter than this
+const MIN_CHAPTER_SECS: f64 = 10.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MarkerFormat {
+    /// CMX3600 EDL with Resolve marker comments
+    Edl,
+    Csv,
+    /// Chapter list for a YouTube description
+    Youtube,
+}
+
+/// A marker at a VOD position
+#[derive(Debug, Clone, PartialEq)]
+struct Marker {
+    time: f64, // VOD time in seconds
+    label: String,
+    priority: u8,
+    participants: Vec<String>,
+}
+
+/// Render `events` as markers in `format`
+///
+/// `offset_secs` is the VOD time at which the game clock reads 0:00;
+/// events before the start of the VOD are left out.
+pub fn render_markers(
+    title: &str,
+    events: &[EventData],
+    offset_secs: f64,
+    format: MarkerFormat,
+) -> String {
+    let mut events: Vec<&EventData> = events.iter().collect();
+    events.sort_by(|a, b| a.timestamp.total_cmp(&b.timestamp));
+    events.dedup_by_key(|event| event.event_id);
+
+    let markers: Vec<Marker> = events
+        .into_iter()
+        .map(|event| Marker {
+            time: event.timestamp + offset_secs,
+            label: event_label(&event.event_type),
+            priority: event.priority,
+            participants: event.participants.clone(),
+        })
+        .filter(|marker| marker.time >= 0.0)
+        .collect();
+
+    match format {
+        MarkerFormat::Edl => render_edl(title, &markers),
+        MarkerFormat::Csv => render_csv(&markers),
+        MarkerFormat::Youtube => render_chapters(&markers),
+    }
+}
+
+fn render_edl(title: &str, markers: &[Marker]) -> String {
+    let mut edl = format!("TITLE: {}\nFCM: NON-DROP FRAME\n\n", title);
+
+    for (i, marker) in markers.iter().enumerate() {
+        let start = timecode(marker.time);
+        let end = timecode(marker.time + 1.0 / EDL_FPS);
+        edl.push_str(&format!(
+            "{:03}  001      V     C        {} {} {} {}  \n",
+            i + 1,
+            start,
+            end,
+            start,
+            end
+        ));
+        // Resolve imports these as timeline markers
+        edl.push_str(&format!(
+            " |C:{} |M:{} |D:1\n\n",
+            resolve_color(marker.priority),
+            marker.label
+        ));
+    }
+
+    edl
+}
+
+fn render_csv(markers: &[Marker]) -> String {
+    let mut csv = String::from("Name,Timecode,Seconds,Priority,Participants\n");
+
+    for marker in markers {
+        csv.push_str(&format!(
+            "{},{},{:.1},{},{}\n",
+            csv_field(&marker.label),
+            timecode(marker.time),
+            marker.time,
+            marker.priority,
+            csv_field(&marker.participants.join("; "))
+        ));
+    }
+
+    csv
+}
+
+/// YouTube chapters have to start at 0:00 and be at least 10 seconds long
+fn render_chapters(markers: &[Marker]) -> String {
+    let mut chapters = vec![(0.0, "Start".to_string())];
+
+    for marker in markers {
+        let last = chapters.last_mut().expect("chapters start non-empty");
+        if marker.time - last.0 < MIN_CHAPTER_SECS {
+            // Too close to the previous chapter; fold into it
+            if last.0 > 0.0 && !last.1.contains(&marker.label) {
+                last.1 = format!("{} + {}", last.1, marker.label);
+            }
+            continue;
+        }
+        chapters.push((marker.time, marker.label.clone()));
+    }
+
+    chapters
+        .iter()
+        .map(|(time, label)| format!("{} {}\n", chapter_time(*time), label))
+        .collect()
+}
+
+/// `HH:MM:SS:FF` timecode at [`EDL_FPS`]
+fn timecode(secs: f64) -> String {
+    let total_frames = (secs.max(0.0) * EDL_FPS).round() as u64;
+    let fps = EDL_FPS as u64;
+    let frames = total_frames % fps;
+    let total_secs = total_frames / fps;
+
+    format!(
+        "{:02}:{:02}:{:02}:{:02}",
+        total_secs / 3600,
+        total_secs / 60 % 60,
+        total_secs % 60,
+        frames
+    )
+}
+
+/// `M:SS` (or `H:MM:SS` past an hour), as YouTube expects
+fn chapter_time(secs: f64) -> String {
+    let secs = secs.max(0.0) as u64;
+
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+fn resolve_color(priority: u8) -> &'static str {
+    match priority {
+        5 => "ResolveColorRed",
+        4 => "ResolveColorYellow",
+        3 => "ResolveColorGreen",
+        _ => "ResolveColorBlue",
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::models::EventType;
+
+    fn event(event_id: u64, event_type: EventType, timestamp: f64) -> EventData {
+        EventData {
+            event_id,
+            event_type,
+            timestamp,
+            priority: 3,
+            participants: vec!["Player".to_string(), "Ally, Jr".to_string()],
+            details: None,
+        }
+    }
+
+    fn events() -> Vec<EventData> {
+        vec![
+            event(2, EventType::Multikill(2), 125.5),
+            event(1, EventType::ChampionKill, 120.0),
+            event(3, EventType::BaronKill, 1300.0),
+            event(3, EventType::BaronKill, 1300.0), // Duplicate
+        ]
+    }
+
+    #[test]
+    fn test_timecodes() {
+        assert_eq!(timecode(0.0), "00:00:00:00");
+        assert_eq!(timecode(3725.5), "01:02:05:15");
+        assert_eq!(chapter_time(65.0), "1:05");
+        assert_eq!(chapter_time(3725.0), "1:02:05");
+    }
+
+    #[test]
+    fn test_render_csv() {
+        let csv = render_markers("game", &events(), 30.0, MarkerFormat::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[1], "Kill,00:02:30:00,150.0,3,\"Player; Ally, Jr\"");
+        assert!(lines[2].starts_with("Double Kill,00:02:35:15,155.5"));
+        assert!(lines[3].starts_with("Baron,"));
+    }
+
+    #[test]
+    fn test_render_edl() {
+        let edl = render_markers("game_1", &events(), 0.0, MarkerFormat::Edl);
+
+        assert!(edl.starts_with("TITLE: game_1\nFCM: NON-DROP FRAME\n"));
+        assert!(edl.contains("001  001      V     C        00:02:00:00 00:02:00:01"));
+        assert!(edl.contains("|C:ResolveColorGreen |M:Double Kill |D:1"));
+        assert!(!edl.contains("004  "));
+    }
+
+    #[test]
+    fn test_render_chapters() {
+        let chapters = render_markers("game", &events(), 0.0, MarkerFormat::Youtube);
+
+        // Double kill is less than 10s after the kill, so they share a chapter
+        assert_eq!(
+            chapters,
+            "0:00 Start\n2:00 Kill + Double Kill\n21:40 Baron\n"
+        );
+    }
+
+    #[test]
+    fn test_events_before_vod_are_skipped() {
+        let csv = render_markers("game", &events(), -122.0, MarkerFormat::Csv);
+        assert_eq!(csv.lines().count(), 3); // Header, double kill, baron
+    }
+
+    #[test]
+    fn test_markers_cover_every_saved_window() {
+        let temp_dir = std::env::temp_dir().join("lolshorts_test_marker_windows");
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let storage = crate::storage::Storage::new(&temp_dir).unwrap();
+
+        // Each saved clip adds its window's events, as export_markers reads them
+        let events = events();
+        storage.add_events("game", &events[..2]).unwrap();
+        storage.add_events("game", &events[2..]).unwrap();
+
+        let stored = storage.load_events("game").unwrap();
+        let csv = render_markers("game", &stored, 0.0, MarkerFormat::Csv);
+        assert_eq!(csv.lines().count(), 4); // Header, kill, double kill, baron
+
+        // Cleanup
+        let _ = std::fs::remove_dir_all(temp_dir);
+    }
+}
@@ -1,17 +1,44 @@
+pub mod animated;
+pub mod archive;
+pub mod audio_export;
 pub mod auto_composer;
+pub mod clip_cache;
 pub mod commands;
+pub mod export;
+pub mod framing;
+pub mod markers;
+pub mod music;
 pub mod performance;
 pub mod processor;
+pub mod speed_ramp;
 pub mod thumbnail;
+pub mod upload_thumbnail;
+pub mod vod;
 
 pub use auto_composer::{
     AutoComposer, AutoEditConfig, AutoEditProgress, AutoEditResult, CanvasTemplate,
 };
+pub use export::{ReelsExport, ReelsPreset};
+pub use framing::{Framing, KillFeedOverlay, ShortsLayout};
 pub use processor::VideoProcessor;
 
+use crate::events::{AppEvent, EventBus, VideoProgress};
+use crate::utils::cleanup::process::TrackedPid;
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 use thiserror::Error;
 
+/// Default limit for a single FFmpeg run (see [`set_ffmpeg_timeout`])
+pub const DEFAULT_FFMPEG_TIMEOUT_SECS: u64 = 30 * 60;
+
+static FFMPEG_TIMEOUT_SECS: AtomicU64 = AtomicU64::new(DEFAULT_FFMPEG_TIMEOUT_SECS);
+
+/// Kill FFmpeg commands that run longer than `secs` (performance settings)
+pub fn set_ffmpeg_timeout(secs: u64) {
+    FFMPEG_TIMEOUT_SECS.store(secs.max(1), Ordering::Relaxed);
+}
+
 /// Video processing errors with user-friendly messages
 #[derive(Debug, Error)]
 pub enum VideoError {
@@ -198,19 +225,26 @@ pub async fn execute_ffmpeg_command(command: &mut tokio::process::Command) -> Re
         }
     })?;
 
-    // Capture stderr for error messages
-    let mut stderr_output = String::new();
-    if let Some(mut stderr) = child.stderr.take() {
-        stderr.read_to_string(&mut stderr_output).await.ok();
-    }
+    let _tracked = TrackedPid::new(child.id());
+    let timeout_secs = FFMPEG_TIMEOUT_SECS.load(Ordering::Relaxed);
 
-    // Wait for command to complete
-    let status = child
-        .wait()
-        .await
-        .map_err(|e| VideoError::ProcessingError {
+    let run = async {
+        // Capture stderr for error messages
+        let mut stderr_output = String::new();
+        if let Some(mut stderr) = child.stderr.take() {
+            stderr.read_to_string(&mut stderr_output).await.ok();
+        }
+
+        // Wait for command to complete
+        child.wait().await.map(|status| (status, stderr_output))
+    };
+    let limit = Duration::from_secs(timeout_secs);
+    let (status, stderr_output) = match tokio::time::timeout(limit, run).await {
+        Ok(result) => result.map_err(|e| VideoError::ProcessingError {
             message: format!("Failed to wait for FFmpeg process: {}", e),
-        })?;
+        })?,
+        Err(_) => return Err(kill_timed_out(&mut child, timeout_secs).await),
+    };
 
     // Check exit status
     if !status.success() {
@@ -220,6 +254,117 @@ pub async fn execute_ffmpeg_command(command: &mut tokio::process::Command) -> Re
     Ok(())
 }
 
+/// Run an FFmpeg command, reporting its progress as it encodes
+///
+/// Progress is read from `-progress pipe:1` and converted to a percentage of
+/// `total_secs` (the output duration). Each whole-percent step is sent to the
+/// frontend as a `video:progress` event for `operation` and passed to
+/// `on_progress`.
+pub async fn execute_ffmpeg_command_with_progress(
+    command: &mut tokio::process::Command,
+    operation: &str,
+    total_secs: f64,
+    events: &EventBus,
+    mut on_progress: impl FnMut(f64) + Send,
+) -> Result<()> {
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+
+    command.args(["-progress", "pipe:1", "-nostats"]);
+    command.stderr(std::process::Stdio::piped());
+    command.stdout(std::process::Stdio::piped());
+
+    let mut child = command.spawn().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            VideoError::FfmpegNotFound
+        } else {
+            VideoError::ProcessingError {
+                message: format!("Failed to spawn FFmpeg process: {}", e),
+            }
+        }
+    })?;
+
+    let _tracked = TrackedPid::new(child.id());
+    let timeout_secs = FFMPEG_TIMEOUT_SECS.load(Ordering::Relaxed);
+
+    // Drain stderr alongside stdout so neither pipe fills up and blocks FFmpeg
+    let stderr_task = child.stderr.take().map(|mut stderr| {
+        tokio::spawn(async move {
+            let mut output = String::new();
+            stderr.read_to_string(&mut output).await.ok();
+            output
+        })
+    });
+
+    let mut reported = None;
+    let mut report = |percent: f64| {
+        let whole = percent.floor() as u32;
+        if reported.is_some_and(|last| last >= whole) {
+            return;
+        }
+        reported = Some(whole);
+        events.emit(AppEvent::VideoProgress(VideoProgress {
+            operation: operation.to_string(),
+            percent: whole as f64,
+        }));
+        on_progress(whole as f64);
+    };
+
+    let stdout = child.stdout.take();
+    let run = async {
+        if let Some(stdout) = stdout {
+            let mut lines = BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if line == "progress=end" {
+                    report(100.0);
+                } else if let Some(secs) = progress_time_secs(&line) {
+                    if total_secs > 0.0 {
+                        report((secs / total_secs * 100.0).clamp(0.0, 99.0));
+                    }
+                }
+            }
+        }
+        child.wait().await
+    };
+    let limit = Duration::from_secs(timeout_secs);
+    let status = match tokio::time::timeout(limit, run).await {
+        Ok(result) => result.map_err(|e| VideoError::ProcessingError {
+            message: format!("Failed to wait for FFmpeg process: {}", e),
+        })?,
+        Err(_) => return Err(kill_timed_out(&mut child, timeout_secs).await),
+    };
+
+    let stderr_output = match stderr_task {
+        Some(task) => task.await.unwrap_or_default(),
+        None => String::new(),
+    };
+
+    if !status.success() {
+        return Err(VideoError::from_ffmpeg_stderr(&stderr_output));
+    }
+
+    Ok(())
+}
+
+/// Kill an FFmpeg process that ran past the timeout
+async fn kill_timed_out(child: &mut tokio::process::Child, timeout_secs: u64) -> VideoError {
+    tracing::error!("FFmpeg still running after {}s, killing it", timeout_secs);
+    if let Err(e) = child.kill().await {
+        tracing::warn!("Failed to kill FFmpeg process: {}", e);
+    }
+    VideoError::Timeout { timeout_secs }
+}
+
+/// Encoded time in seconds from an FFmpeg `-progress` line
+///
+/// `out_time_ms` is in microseconds too, despite the name.
+fn progress_time_secs(line: &str) -> Option<f64> {
+    let (key, value) = line.split_once('=')?;
+    match key {
+        "out_time_us" | "out_time_ms" => value.trim().parse::<i64>().ok().map(|us| us as f64 / 1e6),
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClipInfo {
     pub id: i64,
@@ -229,6 +374,18 @@ pub struct ClipInfo {
     pub file_path: String,
     pub thumbnail_path: Option<String>,
     pub duration: Option<f64>,
+    /// Highlight score (0-100) from the clip's V2 metadata
+    #[serde(default)]
+    pub score: Option<u8>,
+}
+
+impl ClipInfo {
+    /// Highlight score, estimated from the 1-5 priority for clips saved
+    /// before scoring existed
+    pub fn highlight_score(&self) -> u8 {
+        self.score
+            .unwrap_or_else(|| (self.priority.clamp(0, 5) * 20) as u8)
+    }
 }
 
 #[cfg(test)]
@@ -239,4 +396,12 @@ mod tests {
     fn test_video_processor_creation() {
         let _processor = VideoProcessor::new();
     }
+
+    #[test]
+    fn test_progress_time_secs() {
+        assert_eq!(progress_time_secs("out_time_us=12500000"), Some(12.5));
+        assert_eq!(progress_time_secs("out_time_ms=1000000"), Some(1.0));
+        assert_eq!(progress_time_secs("out_time_us=N/A"), None);
+        assert_eq!(progress_time_secs("frame=120"), None);
+    }
 }
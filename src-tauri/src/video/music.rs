@@ -0,0 +1,270 @@
+//! Local library of background music
+//!
+//! Imported tracks are copied to `<data_dir>/music/` and listed in
+//! `library.json` with a mood and optional BPM, so auto-edit can pick a
+//! fitting track itself when the user asks for "surprise me".
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use super::{Result, VideoError};
+use crate::storage;
+
+const INDEX_FILE: &str = "library.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MusicMood {
+    Hype,
+    Chill,
+    Epic,
+    Funny,
+    Emotional,
+}
+
+/// A track in the music library
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MusicTrack {
+    pub id: String,
+    pub title: String,
+    /// File name inside the library folder
+    pub file_name: String,
+    pub mood: MusicMood,
+    pub bpm: Option<u32>,
+    /// Length in seconds
+    pub duration: f64,
+    pub imported_at: chrono::DateTime<chrono::Utc>,
+}
+
+pub struct MusicLibrary {
+    dir: PathBuf,
+}
+
+impl MusicLibrary {
+    /// Library stored under the app data directory
+    pub fn open(data_dir: &Path) -> Self {
+        Self {
+            dir: data_dir.join("music"),
+        }
+    }
+
+    /// All imported tracks, oldest first
+    pub fn tracks(&self) -> Result<Vec<MusicTrack>> {
+        let index = self.dir.join(INDEX_FILE);
+        if !index.exists() {
+            return Ok(Vec::new());
+        }
+
+        storage::read_json(&index).map_err(|e| VideoError::ProcessingError {
+            message: format!("Failed to read music library: {}", e),
+        })
+    }
+
+    /// Full path of a track's audio file
+    pub fn path(&self, track: &MusicTrack) -> PathBuf {
+        self.dir.join(&track.file_name)
+    }
+
+    /// Copy `source` into the library
+    pub fn import(
+        &self,
+        source: &Path,
+        title: String,
+        mood: MusicMood,
+        bpm: Option<u32>,
+        duration: f64,
+    ) -> Result<MusicTrack> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let extension = source
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("mp3")
+            .to_lowercase();
+        let track = MusicTrack {
+            file_name: format!("{}.{}", id, extension),
+            id,
+            title,
+            mood,
+            bpm,
+            duration,
+            imported_at: chrono::Utc::now(),
+        };
+
+        std::fs::create_dir_all(&self.dir).map_err(|e| VideoError::ProcessingError {
+            message: format!("Failed to create music library: {}", e),
+        })?;
+        std::fs::copy(source, self.path(&track)).map_err(|_| VideoError::FileAccessError {
+            path: source.display().to_string(),
+        })?;
+
+        let mut tracks = self.tracks()?;
+        tracks.push(track.clone());
+        self.save(&tracks)?;
+
+        info!("Imported music track {:?} ({:?})", track.title, track.mood);
+        Ok(track)
+    }
+
+    /// Change a track's mood and BPM
+    ///
+    /// Returns None if there's no track with that ID.
+    pub fn tag(&self, id: &str, mood: MusicMood, bpm: Option<u32>) -> Result<Option<MusicTrack>> {
+        let mut tracks = self.tracks()?;
+        let Some(track) = tracks.iter_mut().find(|track| track.id == id) else {
+            return Ok(None);
+        };
+        track.mood = mood;
+        track.bpm = bpm;
+        let track = track.clone();
+
+        self.save(&tracks)?;
+        Ok(Some(track))
+    }
+
+    /// Delete a track and its file
+    ///
+    /// Returns false if there's no track with that ID.
+    pub fn remove(&self, id: &str) -> Result<bool> {
+        let mut tracks = self.tracks()?;
+        let Some(idx) = tracks.iter().position(|track| track.id == id) else {
+            return Ok(false);
+        };
+        let track = tracks.remove(idx);
+
+        self.save(&tracks)?;
+        let _ = std::fs::remove_file(self.path(&track));
+        Ok(true)
+    }
+
+    /// Path of a random track suited to a `duration`-second video
+    ///
+    /// Returns None if the library is empty.
+    pub fn pick_music(&self, mood: Option<MusicMood>, duration: f64) -> Result<Option<PathBuf>> {
+        let tracks = self.tracks()?;
+        let track = pick_music(&tracks, mood, duration, &mut rand::thread_rng());
+        Ok(track.map(|track| self.path(track)))
+    }
+
+    fn save(&self, tracks: &[MusicTrack]) -> Result<()> {
+        let json =
+            serde_json::to_string_pretty(tracks).map_err(|e| VideoError::ProcessingError {
+                message: format!("Failed to serialize music library: {}", e),
+            })?;
+
+        let index = self.dir.join(INDEX_FILE);
+        storage::write_atomic(&index, json).map_err(|_| VideoError::FileAccessError {
+            path: index.display().to_string(),
+        })
+    }
+}
+
+/// Choose a track for a `duration`-second video
+///
+/// Tracks with the requested mood are preferred (any mood if none match),
+/// and among those, ones long enough to play without looping.
+pub fn pick_music<'a>(
+    tracks: &'a [MusicTrack],
+    mood: Option<MusicMood>,
+    duration: f64,
+    rng: &mut impl Rng,
+) -> Option<&'a MusicTrack> {
+    let matching: Vec<&MusicTrack> = tracks
+        .iter()
+        .filter(|track| mood.is_none_or(|mood| track.mood == mood))
+        .collect();
+    let candidates = if matching.is_empty() {
+        tracks.iter().collect()
+    } else {
+        matching
+    };
+
+    let long_enough: Vec<&MusicTrack> = candidates
+        .iter()
+        .copied()
+        .filter(|track| track.duration >= duration)
+        .collect();
+    if long_enough.is_empty() {
+        candidates.choose(rng).copied()
+    } else {
+        long_enough.choose(rng).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use tempfile::tempdir;
+
+    fn track(id: &str, mood: MusicMood, duration: f64) -> MusicTrack {
+        MusicTrack {
+            id: id.to_string(),
+            title: id.to_string(),
+            file_name: format!("{}.mp3", id),
+            mood,
+            bpm: None,
+            duration,
+            imported_at: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_pick_music() {
+        let tracks = vec![
+            track("short_hype", MusicMood::Hype, 30.0),
+            track("long_hype", MusicMood::Hype, 120.0),
+            track("chill", MusicMood::Chill, 120.0),
+        ];
+        let mut rng = StdRng::seed_from_u64(7);
+
+        for _ in 0..10 {
+            let picked = pick_music(&tracks, Some(MusicMood::Hype), 60.0, &mut rng).unwrap();
+            assert_eq!(picked.id, "long_hype");
+        }
+
+        // Nothing matches the mood, so any track will do
+        assert!(pick_music(&tracks, Some(MusicMood::Epic), 60.0, &mut rng).is_some());
+        assert!(pick_music(&[], None, 60.0, &mut rng).is_none());
+    }
+
+    #[test]
+    fn test_import_tag_and_remove() {
+        let temp_dir = tempdir().unwrap();
+        let source = temp_dir.path().join("Song.MP3");
+        std::fs::write(&source, b"audio").unwrap();
+
+        let library = MusicLibrary::open(temp_dir.path());
+        assert!(library.tracks().unwrap().is_empty());
+
+        let track = library
+            .import(&source, "Song".to_string(), MusicMood::Epic, None, 90.0)
+            .unwrap();
+        assert!(track.file_name.ends_with(".mp3"));
+        assert!(library.path(&track).exists());
+        assert_eq!(library.tracks().unwrap(), vec![track.clone()]);
+
+        let tagged = library
+            .tag(&track.id, MusicMood::Hype, Some(128))
+            .unwrap()
+            .unwrap();
+        assert_eq!(tagged.bpm, Some(128));
+        assert_eq!(library.tracks().unwrap()[0].mood, MusicMood::Hype);
+        assert!(library
+            .tag("missing", MusicMood::Hype, None)
+            .unwrap()
+            .is_none());
+
+        assert_eq!(
+            library.pick_music(Some(MusicMood::Hype), 60.0).unwrap(),
+            Some(library.path(&tagged))
+        );
+
+        assert!(library.remove(&track.id).unwrap());
+        assert!(!library.path(&track).exists());
+        assert!(!library.remove(&track.id).unwrap());
+    }
+}
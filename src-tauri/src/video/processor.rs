@@ -1,22 +1,100 @@
 #![allow(dead_code)]
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use tokio::process::Command as TokioCommand;
-use tracing::info;
+use tracing::{info, warn};
 
-use super::{execute_ffmpeg_command, Result, VideoError};
+use super::framing::ShortsLayout;
+use super::speed_ramp::{self, SpeedSegment};
+use super::{execute_ffmpeg_command, execute_ffmpeg_command_with_progress, Result, VideoError};
+use crate::events::EventBus;
+
+/// Highest frame rate clips are normalized to before concatenation
+const MAX_CONCAT_FPS: u32 = 60;
+
+/// Video properties that have to match for clips to concatenate cleanly
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MediaInfo {
+    pub codec: String,
+    pub width: u32,
+    pub height: u32,
+    /// Average frame rate
+    pub fps: f64,
+    pub pixel_format: String,
+    /// Frame timing is irregular (average rate differs from the nominal one)
+    pub variable_frame_rate: bool,
+    pub has_audio: bool,
+    /// Titles of the audio tracks, in order (empty for untitled ones)
+    pub audio_tracks: Vec<String>,
+}
+
+impl MediaInfo {
+    /// Whether the two can be concatenated without re-encoding
+    pub fn matches(&self, other: &MediaInfo) -> bool {
+        self.codec == other.codec
+            && self.width == other.width
+            && self.height == other.height
+            && (self.fps - other.fps).abs() < 0.01
+            && self.pixel_format == other.pixel_format
+            && self.audio_tracks.len() == other.audio_tracks.len()
+    }
+}
+
+/// Resolution and constant frame rate mismatched clips are converted to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConcatTarget {
+    pub width: u32,
+    pub height: u32,
+    pub fps: u32,
+}
+
+impl ConcatTarget {
+    /// Largest resolution and highest frame rate among the clips
+    pub fn for_clips(infos: &[MediaInfo]) -> Option<Self> {
+        let largest = infos.iter().max_by_key(|info| info.width * info.height)?;
+        let fps = infos
+            .iter()
+            .map(|info| info.fps.round() as u32)
+            .max()
+            .unwrap_or(MAX_CONCAT_FPS)
+            .clamp(1, MAX_CONCAT_FPS);
+
+        Some(Self {
+            // libx264 with yuv420p needs even dimensions
+            width: largest.width & !1,
+            height: largest.height & !1,
+            fps,
+        })
+    }
+}
+
+/// Whether clips have to be normalized before they can be concatenated
+pub fn needs_normalization(infos: &[MediaInfo]) -> bool {
+    infos.iter().any(|info| info.variable_frame_rate)
+        || infos.windows(2).any(|pair| !pair[0].matches(&pair[1]))
+}
 
 /// FFmpeg video processor for clip extraction and composition
 pub struct VideoProcessor {
     ffmpeg_path: String,
+    events: Arc<EventBus>,
 }
 
 impl VideoProcessor {
     pub fn new() -> Self {
         Self {
             ffmpeg_path: "ffmpeg".to_string(), // Assumes FFmpeg is in PATH or bundled
+            events: Arc::new(EventBus::new()),
         }
     }
 
+    /// Publish encoding progress on `events`
+    pub fn with_events(mut self, events: Arc<EventBus>) -> Self {
+        self.events = events;
+        self
+    }
+
     /// Extract a clip from a video file
     ///
     /// # Arguments
@@ -80,7 +158,14 @@ impl VideoProcessor {
             })?,
         ]);
 
-        execute_ffmpeg_command(&mut command).await?;
+        execute_ffmpeg_command_with_progress(
+            &mut command,
+            "extract_clip",
+            duration,
+            &self.events,
+            |_| {},
+        )
+        .await?;
 
         // Verify output file was created
         if !output.exists() {
@@ -109,6 +194,28 @@ impl VideoProcessor {
         output_path: impl AsRef<Path>,
         target_width: u32,
         target_height: u32,
+    ) -> Result<PathBuf> {
+        self.compose_shorts_with_progress(
+            clip_paths,
+            output_path,
+            target_width,
+            target_height,
+            ShortsLayout::default(),
+            |_| {},
+        )
+        .await
+    }
+
+    /// [`compose_shorts`](Self::compose_shorts) with the given layout,
+    /// passing the encoding percentage (0-100) to `on_progress`
+    pub async fn compose_shorts_with_progress(
+        &self,
+        clip_paths: &[PathBuf],
+        output_path: impl AsRef<Path>,
+        target_width: u32,
+        target_height: u32,
+        layout: ShortsLayout,
+        mut on_progress: impl FnMut(f64) + Send,
     ) -> Result<PathBuf> {
         let output = output_path.as_ref();
 
@@ -147,18 +254,24 @@ impl VideoProcessor {
         // If only one clip, just scale and crop it
         if clip_paths.len() == 1 {
             return self
-                .scale_and_crop_clip(&clip_paths[0], output, target_width, target_height)
+                .scale_and_crop_clip(
+                    &clip_paths[0],
+                    output,
+                    target_width,
+                    target_height,
+                    layout,
+                    on_progress,
+                )
                 .await;
         }
 
-        // Multiple clips: create concat file and then compose
-        let concat_file = output
-            .parent()
-            .unwrap_or_else(|| Path::new("."))
-            .join("concat_list.txt");
+        // Multiple clips: make sure they share a format, then concat and compose
+        let work_dir = output.parent().unwrap_or_else(|| Path::new("."));
+        let concat_clips = self.normalize_for_concat(clip_paths, work_dir).await?;
+        let concat_file = work_dir.join("concat_list.txt");
 
         // Write concat file
-        let concat_content: String = clip_paths
+        let concat_content: String = concat_clips
             .iter()
             .map(|p| format!("file '{}'\n", p.to_str().unwrap()))
             .collect();
@@ -183,7 +296,7 @@ impl VideoProcessor {
                     path: concat_file.display().to_string(),
                 })?,
             "-vf",
-            &format!("scale={}:{},setsar=1", target_width, target_height),
+            &layout.filter(target_width, target_height),
             "-c:v",
             "libx264",
             "-preset",
@@ -200,10 +313,21 @@ impl VideoProcessor {
             })?,
         ]);
 
-        let result = execute_ffmpeg_command(&mut command).await;
+        let total_secs = self.total_duration(&concat_clips).await;
+        let result = execute_ffmpeg_command_with_progress(
+            &mut command,
+            "compose_shorts",
+            total_secs,
+            &self.events,
+            &mut on_progress,
+        )
+        .await;
 
-        // Clean up concat file
+        // Clean up concat file and normalized copies
         let _ = tokio::fs::remove_file(&concat_file).await;
+        for path in concat_clips.iter().filter(|p| !clip_paths.contains(p)) {
+            let _ = tokio::fs::remove_file(path).await;
+        }
 
         result.map_err(|e| VideoError::ConcatenationError {
             reason: e.to_string(),
@@ -220,6 +344,55 @@ impl VideoProcessor {
         Ok(output.to_path_buf())
     }
 
+    /// Convert clips to a common resolution and constant frame rate if they
+    /// don't already share one
+    ///
+    /// The concat demuxer needs every input to have the same stream layout;
+    /// VFR clips or clips from different sources otherwise fail to merge or
+    /// drift out of sync. Returns the original paths if nothing needs to
+    /// change, otherwise re-encoded copies written to `work_dir`.
+    pub async fn normalize_for_concat(
+        &self,
+        clip_paths: &[PathBuf],
+        work_dir: &Path,
+    ) -> Result<Vec<PathBuf>> {
+        let mut infos = Vec::with_capacity(clip_paths.len());
+        for path in clip_paths {
+            infos.push(self.analyze(path).await?);
+        }
+
+        let Some(target) = ConcatTarget::for_clips(&infos) else {
+            return Ok(clip_paths.to_vec());
+        };
+        if !needs_normalization(&infos) {
+            return Ok(clip_paths.to_vec());
+        }
+
+        warn!(
+            "Clips differ in format or have variable frame rate, normalizing to {}x{}@{}fps",
+            target.width, target.height, target.fps
+        );
+
+        let mut normalized = Vec::with_capacity(clip_paths.len());
+        for (idx, (path, info)) in clip_paths.iter().zip(&infos).enumerate() {
+            let output = work_dir.join(format!("concat_normalized_{}.mp4", idx));
+            let mut command = TokioCommand::new(&self.ffmpeg_path);
+            command.args(normalize_args(path, &output, info, target));
+
+            if let Err(e) = execute_ffmpeg_command(&mut command).await {
+                for path in &normalized {
+                    let _ = tokio::fs::remove_file(path).await;
+                }
+                return Err(VideoError::ConcatenationError {
+                    reason: format!("Failed to normalize {}: {}", path.display(), e),
+                });
+            }
+            normalized.push(output);
+        }
+
+        Ok(normalized)
+    }
+
     /// Scale and crop a single clip to target dimensions (9:16)
     async fn scale_and_crop_clip(
         &self,
@@ -227,17 +400,15 @@ impl VideoProcessor {
         output: &Path,
         target_width: u32,
         target_height: u32,
+        layout: ShortsLayout,
+        on_progress: impl FnMut(f64) + Send,
     ) -> Result<PathBuf> {
         info!(
-            "Scaling and cropping clip: {:?} -> {:?} ({}x{})",
-            input, output, target_width, target_height
+            "Scaling and cropping clip: {:?} -> {:?} ({}x{}, {:?})",
+            input, output, target_width, target_height, layout
         );
 
-        // Calculate scale filter (scale to cover target, then crop)
-        let filter = format!(
-            "scale=-1:{}:force_original_aspect_ratio=increase,crop={}:{},setsar=1",
-            target_height, target_width, target_height
-        );
+        let filter = layout.filter(target_width, target_height);
 
         let mut command = TokioCommand::new(&self.ffmpeg_path);
         command.args([
@@ -263,7 +434,15 @@ impl VideoProcessor {
             })?,
         ]);
 
-        execute_ffmpeg_command(&mut command).await?;
+        let total_secs = self.total_duration(&[input.to_path_buf()]).await;
+        execute_ffmpeg_command_with_progress(
+            &mut command,
+            "compose_shorts",
+            total_secs,
+            &self.events,
+            on_progress,
+        )
+        .await?;
 
         Ok(output.to_path_buf())
     }
@@ -339,46 +518,183 @@ impl VideoProcessor {
         Ok(output.to_path_buf())
     }
 
-    /// Get video duration in seconds
-    pub async fn get_duration(&self, input_path: impl AsRef<Path>) -> Result<f64> {
+    /// Save the frame at `timestamp` as a lossless PNG, at the video's own
+    /// resolution (for custom thumbnails and social posts)
+    pub async fn capture_frame(
+        &self,
+        input_path: impl AsRef<Path>,
+        output_path: impl AsRef<Path>,
+        timestamp: f64,
+    ) -> Result<PathBuf> {
         let input = input_path.as_ref();
+        let output = output_path.as_ref();
 
         if !input.exists() {
             return Err(VideoError::FileNotFound {
                 path: input.display().to_string(),
             });
         }
+        if let Some(parent) = output.parent() {
+            if !parent.exists() {
+                return Err(VideoError::OutputDirectoryNotFound {
+                    path: parent.display().to_string(),
+                });
+            }
+        }
+
+        // Seeking before -i snaps to the nearest frame at or after timestamp
+        let mut command = TokioCommand::new(&self.ffmpeg_path);
+        command
+            .args(["-ss", &timestamp.to_string(), "-i"])
+            .arg(input)
+            .args(["-frames:v", "1", "-c:v", "png", "-pix_fmt", "rgb24", "-y"])
+            .arg(output);
+
+        execute_ffmpeg_command(&mut command).await?;
+
+        // Past the end of the video FFmpeg succeeds without writing a frame
+        if !output.exists() {
+            return Err(VideoError::ProcessingError {
+                message: format!("No frame at {:.2}s", timestamp),
+            });
+        }
+
+        info!("Captured frame at {:.2}s: {:?}", timestamp, output);
+        Ok(output.to_path_buf())
+    }
 
-        let output = TokioCommand::new("ffprobe")
-            .args([
-                "-v",
-                "error",
+    /// Normalize audio loudness to an EBU R128 target (single-pass loudnorm)
+    ///
+    /// # Arguments
+    /// * `input_path` - Path to input video file
+    /// * `output_path` - Path to output video file
+    /// * `target_lufs` - Integrated loudness target in LUFS (e.g. -14.0 for YouTube)
+    ///
+    /// # Returns
+    /// Path to the normalized video (video stream is copied, audio re-encoded)
+    pub async fn normalize_audio(
+        &self,
+        input_path: impl AsRef<Path>,
+        output_path: impl AsRef<Path>,
+        target_lufs: f64,
+    ) -> Result<PathBuf> {
+        let input = input_path.as_ref();
+        let output = output_path.as_ref();
+
+        info!(
+            "Normalizing audio: {:?} -> {:?} (target: {} LUFS)",
+            input, output, target_lufs
+        );
+
+        if !input.exists() {
+            return Err(VideoError::FileNotFound {
+                path: input.display().to_string(),
+            });
+        }
+
+        let mut command = TokioCommand::new(&self.ffmpeg_path);
+        command.args([
+            "-i",
+            input.to_str().ok_or_else(|| VideoError::FileAccessError {
+                path: input.display().to_string(),
+            })?,
+            "-af",
+            &loudnorm_filter(target_lufs),
+            "-c:v",
+            "copy", // Only audio needs re-encoding
+            "-c:a",
+            "aac",
+            "-b:a",
+            "192k",
+            "-ar",
+            "48000", // loudnorm upsamples internally, restore a standard rate
+            "-y",
+            output.to_str().ok_or_else(|| VideoError::FileAccessError {
+                path: output.display().to_string(),
+            })?,
+        ]);
+
+        let total_secs = self.total_duration(&[input.to_path_buf()]).await;
+        execute_ffmpeg_command_with_progress(
+            &mut command,
+            "normalize_audio",
+            total_secs,
+            &self.events,
+            |_| {},
+        )
+        .await
+        .map_err(|e| VideoError::AudioMixingError {
+            reason: format!("Loudness normalization failed: {}", e),
+        })?;
+
+        Ok(output.to_path_buf())
+    }
+
+    /// Re-time a clip, playing each of `segments` at its own speed
+    ///
+    /// Video and audio are both re-encoded; audio keeps its pitch (atempo).
+    pub async fn apply_speed_ramp(
+        &self,
+        input_path: impl AsRef<Path>,
+        output_path: impl AsRef<Path>,
+        segments: &[SpeedSegment],
+    ) -> Result<PathBuf> {
+        let input = input_path.as_ref();
+        let output = output_path.as_ref();
+
+        info!(
+            "Applying speed ramp: {:?} -> {:?} ({} segments)",
+            input,
+            output,
+            segments.len()
+        );
+
+        if !input.exists() {
+            return Err(VideoError::FileNotFound {
+                path: input.display().to_string(),
+            });
+        }
+
+        let has_audio = self.analyze(input).await?.has_audio;
+        let mut command = TokioCommand::new(&self.ffmpeg_path);
+        command
+            .arg("-i")
+            .arg(input)
+            .args(["-filter_complex", &speed_ramp::filter(segments, has_audio)])
+            .args(["-map", "[v]"]);
+        if has_audio {
+            command.args(["-map", "[a]", "-c:a", "aac", "-b:a", "192k"]);
+        }
+        command
+            .args(["-c:v", "libx264", "-preset", "veryfast", "-crf", "20", "-y"])
+            .arg(output);
+
+        let total_secs = self.total_duration(&[input.to_path_buf()]).await;
+        execute_ffmpeg_command_with_progress(
+            &mut command,
+            "speed_ramp",
+            total_secs,
+            &self.events,
+            |_| {},
+        )
+        .await?;
+
+        Ok(output.to_path_buf())
+    }
+
+    /// Get video duration in seconds
+    pub async fn get_duration(&self, input_path: impl AsRef<Path>) -> Result<f64> {
+        let duration_str = ffprobe(
+            input_path.as_ref(),
+            &[
                 "-show_entries",
                 "format=duration",
                 "-of",
                 "default=noprint_wrappers=1:nokey=1",
-                input.to_str().ok_or_else(|| VideoError::FileAccessError {
-                    path: input.display().to_string(),
-                })?,
-            ])
-            .output()
-            .await
-            .map_err(|e| {
-                if e.kind() == std::io::ErrorKind::NotFound {
-                    VideoError::FfmpegNotFound
-                } else {
-                    VideoError::ProcessingError {
-                        message: format!("Failed to execute ffprobe: {}", e),
-                    }
-                }
-            })?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(VideoError::from_ffmpeg_stderr(&stderr));
-        }
+            ],
+        )
+        .await?;
 
-        let duration_str = String::from_utf8_lossy(&output.stdout);
         let duration =
             duration_str
                 .trim()
@@ -389,6 +705,232 @@ impl VideoProcessor {
 
         Ok(duration)
     }
+
+    /// Combined duration of `paths`, or 0 if any can't be probed (progress
+    /// is then not reported)
+    async fn total_duration(&self, paths: &[PathBuf]) -> f64 {
+        let mut total = 0.0;
+        for path in paths {
+            match self.get_duration(path).await {
+                Ok(duration) => total += duration,
+                Err(_) => return 0.0,
+            }
+        }
+        total
+    }
+
+    /// Probe codec, resolution, frame rate, pixel format and audio tracks of
+    /// a video
+    pub async fn analyze(&self, input_path: impl AsRef<Path>) -> Result<MediaInfo> {
+        let json = ffprobe(
+            input_path.as_ref(),
+            &[
+                "-show_entries",
+                "stream=codec_type,codec_name,width,height,pix_fmt,r_frame_rate,avg_frame_rate:stream_tags=title",
+                "-of",
+                "json",
+            ],
+        )
+        .await?;
+
+        parse_media_info(&json)
+    }
+
+    /// Titles of a video's audio tracks, in order (empty for untitled ones)
+    pub async fn audio_track_titles(&self, input_path: impl AsRef<Path>) -> Result<Vec<String>> {
+        let titles = ffprobe(
+            input_path.as_ref(),
+            &[
+                "-select_streams",
+                "a",
+                "-show_entries",
+                "stream_tags=title",
+                "-of",
+                "csv=p=0",
+            ],
+        )
+        .await?;
+
+        Ok(titles
+            .lines()
+            .map(|title| title.trim().to_string())
+            .collect())
+    }
+}
+
+/// Run ffprobe on `input` and return its stdout
+async fn ffprobe(input: &Path, args: &[&str]) -> Result<String> {
+    if !input.exists() {
+        return Err(VideoError::FileNotFound {
+            path: input.display().to_string(),
+        });
+    }
+
+    let output = TokioCommand::new("ffprobe")
+        .args(["-v", "error"])
+        .args(args)
+        .arg(input.to_str().ok_or_else(|| VideoError::FileAccessError {
+            path: input.display().to_string(),
+        })?)
+        .output()
+        .await
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                VideoError::FfmpegNotFound
+            } else {
+                VideoError::ProcessingError {
+                    message: format!("Failed to execute ffprobe: {}", e),
+                }
+            }
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(VideoError::from_ffmpeg_stderr(&stderr));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[derive(Deserialize)]
+struct ProbeOutput {
+    #[serde(default)]
+    streams: Vec<ProbeStream>,
+}
+
+#[derive(Deserialize)]
+struct ProbeStream {
+    codec_type: String,
+    #[serde(default)]
+    codec_name: String,
+    width: Option<u32>,
+    height: Option<u32>,
+    pix_fmt: Option<String>,
+    r_frame_rate: Option<String>,
+    avg_frame_rate: Option<String>,
+    #[serde(default)]
+    tags: ProbeTags,
+}
+
+#[derive(Default, Deserialize)]
+struct ProbeTags {
+    #[serde(default)]
+    title: String,
+}
+
+/// Parse `ffprobe -show_entries stream=... -of json` output
+fn parse_media_info(json: &str) -> Result<MediaInfo> {
+    let probe: ProbeOutput =
+        serde_json::from_str(json).map_err(|e| VideoError::ProcessingError {
+            message: format!("Failed to parse ffprobe output: {}", e),
+        })?;
+
+    let video = probe
+        .streams
+        .iter()
+        .find(|stream| stream.codec_type == "video")
+        .ok_or_else(|| VideoError::ProcessingError {
+            message: "No video stream found".to_string(),
+        })?;
+
+    let nominal_fps = video.r_frame_rate.as_deref().and_then(parse_frame_rate);
+    let average_fps = video.avg_frame_rate.as_deref().and_then(parse_frame_rate);
+    let fps = average_fps.or(nominal_fps).unwrap_or(0.0);
+
+    // CFR files report the same nominal and average rate (within rounding)
+    let variable_frame_rate = match (nominal_fps, average_fps) {
+        (Some(nominal), Some(average)) => (nominal - average).abs() / nominal > 0.01,
+        _ => false,
+    };
+
+    let audio_tracks: Vec<String> = probe
+        .streams
+        .iter()
+        .filter(|stream| stream.codec_type == "audio")
+        .map(|stream| stream.tags.title.clone())
+        .collect();
+
+    Ok(MediaInfo {
+        codec: video.codec_name.clone(),
+        width: video.width.unwrap_or(0),
+        height: video.height.unwrap_or(0),
+        fps,
+        pixel_format: video.pix_fmt.clone().unwrap_or_default(),
+        variable_frame_rate,
+        has_audio: !audio_tracks.is_empty(),
+        audio_tracks,
+    })
+}
+
+/// Parse an FFmpeg rational frame rate ("60/1", "30000/1001")
+fn parse_frame_rate(rate: &str) -> Option<f64> {
+    let (num, den) = rate.split_once('/')?;
+    let num: f64 = num.parse().ok()?;
+    let den: f64 = den.parse().ok()?;
+    (num > 0.0 && den > 0.0).then_some(num / den)
+}
+
+/// FFmpeg arguments converting a clip to `target` (letterboxed, CFR, H.264/AAC)
+///
+/// Clips without audio get a silent track so every input has the same streams.
+fn normalize_args(
+    input: &Path,
+    output: &Path,
+    info: &MediaInfo,
+    target: ConcatTarget,
+) -> Vec<String> {
+    let mut args: Vec<String> = vec!["-i".into(), input.to_string_lossy().into()];
+    if !info.has_audio {
+        args.extend([
+            "-f".into(),
+            "lavfi".into(),
+            "-i".into(),
+            "anullsrc=channel_layout=stereo:sample_rate=48000".into(),
+        ]);
+    }
+
+    args.extend([
+        "-map".into(),
+        "0:v:0".into(),
+        "-map".into(),
+        if info.has_audio { "0:a:0" } else { "1:a:0" }.into(),
+        "-vf".into(),
+        format!(
+            "scale={w}:{h}:force_original_aspect_ratio=decrease,pad={w}:{h}:(ow-iw)/2:(oh-ih)/2,setsar=1,fps={fps}",
+            w = target.width,
+            h = target.height,
+            fps = target.fps
+        ),
+        "-c:v".into(),
+        "libx264".into(),
+        "-preset".into(),
+        "fast".into(),
+        "-crf".into(),
+        "18".into(),
+        "-pix_fmt".into(),
+        "yuv420p".into(),
+        "-c:a".into(),
+        "aac".into(),
+        "-b:a".into(),
+        "192k".into(),
+        "-ar".into(),
+        "48000".into(),
+        "-ac".into(),
+        "2".into(),
+    ]);
+    if !info.has_audio {
+        args.push("-shortest".into());
+    }
+    args.extend(["-y".into(), output.to_string_lossy().into()]);
+    args
+}
+
+/// Build an EBU R128 loudnorm filter for the given integrated loudness target
+///
+/// The target is clamped to the range accepted by FFmpeg (-70 to -5 LUFS).
+pub fn loudnorm_filter(target_lufs: f64) -> String {
+    let target = target_lufs.clamp(-70.0, -5.0);
+    format!("loudnorm=I={:.1}:TP=-1.5:LRA=11", target)
 }
 
 impl Default for VideoProcessor {
@@ -407,6 +949,14 @@ mod tests {
         assert_eq!(processor.ffmpeg_path, "ffmpeg");
     }
 
+    #[test]
+    fn test_loudnorm_filter() {
+        assert_eq!(loudnorm_filter(-14.0), "loudnorm=I=-14.0:TP=-1.5:LRA=11");
+        // Out-of-range targets are clamped
+        assert_eq!(loudnorm_filter(0.0), "loudnorm=I=-5.0:TP=-1.5:LRA=11");
+        assert_eq!(loudnorm_filter(-100.0), "loudnorm=I=-70.0:TP=-1.5:LRA=11");
+    }
+
     #[test]
     fn test_scale_filter_generation() {
         // Test 9:16 aspect ratio calculation
@@ -422,6 +972,77 @@ mod tests {
         assert!(filter.contains("crop=1080:1920"));
     }
 
+    fn media_info(width: u32, height: u32, fps: f64) -> MediaInfo {
+        MediaInfo {
+            codec: "h264".to_string(),
+            width,
+            height,
+            fps,
+            pixel_format: "yuv420p".to_string(),
+            variable_frame_rate: false,
+            has_audio: true,
+            audio_tracks: vec![String::new()],
+        }
+    }
+
+    #[test]
+    fn test_parse_media_info() {
+        let json = r#"{"streams": [
+            {"codec_type": "video", "codec_name": "h264", "width": 1920, "height": 1080,
+             "pix_fmt": "yuv420p", "r_frame_rate": "60/1", "avg_frame_rate": "9000/193"},
+            {"codec_type": "audio", "codec_name": "aac",
+             "r_frame_rate": "0/0", "avg_frame_rate": "0/0"},
+            {"codec_type": "audio", "codec_name": "aac",
+             "r_frame_rate": "0/0", "avg_frame_rate": "0/0", "tags": {"title": "Microphone"}}
+        ]}"#;
+
+        let info = parse_media_info(json).unwrap();
+        assert_eq!((info.width, info.height), (1920, 1080));
+        assert!((info.fps - 46.63).abs() < 0.01);
+        assert!(info.variable_frame_rate);
+        assert!(info.has_audio);
+        assert_eq!(info.audio_tracks, vec!["", "Microphone"]);
+
+        assert_eq!(parse_frame_rate("30000/1001").map(f64::round), Some(30.0));
+        assert_eq!(parse_frame_rate("0/0"), None);
+        assert!(parse_media_info(r#"{"streams": []}"#).is_err());
+    }
+
+    #[test]
+    fn test_concat_normalization() {
+        let cfr = media_info(1920, 1080, 60.0);
+        assert!(!needs_normalization(&[cfr.clone(), cfr.clone()]));
+
+        let vfr = MediaInfo {
+            variable_frame_rate: true,
+            ..cfr.clone()
+        };
+        assert!(needs_normalization(&[cfr.clone(), vfr]));
+
+        let small = media_info(1280, 719, 29.97);
+        assert!(needs_normalization(&[cfr.clone(), small.clone()]));
+        assert_eq!(
+            ConcatTarget::for_clips(&[small.clone(), cfr]),
+            Some(ConcatTarget {
+                width: 1920,
+                height: 1080,
+                fps: 60,
+            })
+        );
+
+        let silent = MediaInfo {
+            has_audio: false,
+            audio_tracks: vec![],
+            ..small
+        };
+        let target = ConcatTarget::for_clips(std::slice::from_ref(&silent)).unwrap();
+        assert_eq!((target.width, target.height, target.fps), (1280, 718, 30));
+        let args = normalize_args(Path::new("in.mp4"), Path::new("out.mp4"), &silent, target);
+        assert!(args.iter().any(|a| a.starts_with("anullsrc")));
+        assert!(args.iter().any(|a| a.ends_with("fps=30")));
+        assert!(args.contains(&"-shortest".to_string()));
+    }
+
     // Integration tests require FFmpeg to be installed
     #[tokio::test]
     #[ignore] // Requires FFmpeg and test video file
@@ -0,0 +1,171 @@
+//! Speed ramping around highlight moments
+//!
+//! Slows a clip down around each event (the kill, the objective steal) and
+//! speeds up the stretches in between where the player is just walking
+//! around. Event times come from the clip's v2 metadata.
+
+/// Seconds of slow motion on each side of an event
+const SLOW_MOTION_WINDOW_SECS: f64 = 0.75;
+
+/// Footage this far from any event counts as travel time
+const TRAVEL_GAP_SECS: f64 = 4.0;
+
+/// Segments shorter than this are merged into their neighbours
+const MIN_SEGMENT_SECS: f64 = 0.1;
+
+/// Part of a clip played at `speed` (2.0 = twice as fast)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpeedSegment {
+    pub start: f64,
+    pub end: f64,
+    pub speed: f64,
+}
+
+/// Split a clip of `duration` seconds into speed segments
+///
+/// `events` are times within the clip. `intensity` (0.0-1.0) scales the
+/// effect: at 1.0 events play at half speed and travel time at double speed.
+/// Returns an empty list if there's nothing to change.
+pub fn plan(duration: f64, events: &[f64], intensity: f64) -> Vec<SpeedSegment> {
+    let intensity = intensity.clamp(0.0, 1.0);
+    let events: Vec<f64> = events
+        .iter()
+        .copied()
+        .filter(|t| (0.0..=duration).contains(t))
+        .collect();
+    if events.is_empty() || intensity == 0.0 || duration <= 0.0 {
+        return Vec::new();
+    }
+
+    let slow = 1.0 - 0.5 * intensity;
+    let fast = 1.0 + intensity;
+
+    // Boundaries where the speed may change
+    let mut cuts = vec![0.0, duration];
+    for &event in &events {
+        cuts.extend([
+            event - TRAVEL_GAP_SECS,
+            event - SLOW_MOTION_WINDOW_SECS,
+            event + SLOW_MOTION_WINDOW_SECS,
+            event + TRAVEL_GAP_SECS,
+        ]);
+    }
+    cuts.retain(|t| (0.0..=duration).contains(t));
+    cuts.sort_by(|a, b| a.total_cmp(b));
+    cuts.dedup_by(|a, b| (*a - *b).abs() < MIN_SEGMENT_SECS);
+    if let Some(last) = cuts.last_mut() {
+        *last = duration;
+    }
+
+    let mut segments: Vec<SpeedSegment> = Vec::new();
+    for pair in cuts.windows(2) {
+        let (start, end) = (pair[0], pair[1]);
+        let middle = (start + end) / 2.0;
+        let distance = events
+            .iter()
+            .map(|event| (event - middle).abs())
+            .fold(f64::INFINITY, f64::min);
+
+        let speed = if distance <= SLOW_MOTION_WINDOW_SECS {
+            slow
+        } else if distance >= TRAVEL_GAP_SECS {
+            fast
+        } else {
+            1.0
+        };
+
+        match segments.last_mut() {
+            Some(last) if last.speed == speed => last.end = end,
+            _ => segments.push(SpeedSegment { start, end, speed }),
+        }
+    }
+
+    if segments.iter().all(|segment| segment.speed == 1.0) {
+        return Vec::new();
+    }
+    segments
+}
+
+/// FFmpeg filter graph playing `segments` back to back as `[v]` (and `[a]`)
+pub fn filter(segments: &[SpeedSegment], has_audio: bool) -> String {
+    let mut parts = Vec::new();
+    let mut concat_inputs = String::new();
+
+    for (idx, segment) in segments.iter().enumerate() {
+        parts.push(format!(
+            "[0:v]trim=start={:.3}:end={:.3},setpts=(PTS-STARTPTS)/{:.3}[v{}]",
+            segment.start, segment.end, segment.speed, idx
+        ));
+        concat_inputs.push_str(&format!("[v{}]", idx));
+
+        if has_audio {
+            parts.push(format!(
+                "[0:a]atrim=start={:.3}:end={:.3},asetpts=PTS-STARTPTS,atempo={:.3}[a{}]",
+                segment.start, segment.end, segment.speed, idx
+            ));
+            concat_inputs.push_str(&format!("[a{}]", idx));
+        }
+    }
+
+    let (audio_streams, outputs) = if has_audio { (1, "[v][a]") } else { (0, "[v]") };
+    parts.push(format!(
+        "{}concat=n={}:v=1:a={}{}",
+        concat_inputs,
+        segments.len(),
+        audio_streams,
+        outputs
+    ));
+    parts.join(";")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_slows_events_and_speeds_up_travel() {
+        let segments = plan(20.0, &[15.0], 1.0);
+
+        assert_eq!(segments.first().unwrap().start, 0.0);
+        assert_eq!(segments.last().unwrap().end, 20.0);
+        assert!(segments.windows(2).all(|w| w[0].end == w[1].start));
+
+        // Walking to the fight is sped up, the kill itself slowed down
+        assert_eq!(segments[0].speed, 2.0);
+        assert_eq!(segments[0].end, 11.0);
+        let kill = segments.iter().find(|s| s.start <= 15.0 && s.end >= 15.0);
+        assert_eq!(kill.unwrap().speed, 0.5);
+    }
+
+    #[test]
+    fn test_plan_without_events_or_intensity() {
+        assert!(plan(20.0, &[], 1.0).is_empty());
+        assert!(plan(20.0, &[10.0], 0.0).is_empty());
+        assert!(plan(20.0, &[30.0], 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_filter() {
+        let segments = [
+            SpeedSegment {
+                start: 0.0,
+                end: 5.0,
+                speed: 1.5,
+            },
+            SpeedSegment {
+                start: 5.0,
+                end: 6.5,
+                speed: 0.75,
+            },
+        ];
+
+        let filter = filter(&segments, true);
+        assert!(filter.contains("[0:v]trim=start=0.000:end=5.000,setpts=(PTS-STARTPTS)/1.500[v0]"));
+        assert!(filter.contains("atempo=0.750[a1]"));
+        assert!(filter.ends_with("[v0][a0][v1][a1]concat=n=2:v=1:a=1[v][a]"));
+
+        let filter = super::filter(&segments, false);
+        assert!(!filter.contains("atrim"));
+        assert!(filter.ends_with("[v0][v1]concat=n=2:v=1:a=0[v]"));
+    }
+}
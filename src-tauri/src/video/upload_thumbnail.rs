@@ -0,0 +1,190 @@
+//! Custom YouTube thumbnails for composed shorts
+//!
+//! A frame of the short fitted into 1280x720 over a blurred copy of itself,
+//! with the text and image elements of a canvas template drawn on top.
+//! Templates are laid out for the 1080x1920 short, so element positions stay
+//! percentages and sizes are scaled by the width ratio. Animations, the
+//! template background and the facecam slot don't apply to a still and are
+//! left out.
+
+use std::path::{Path, PathBuf};
+use tokio::process::Command as TokioCommand;
+use tracing::{info, warn};
+
+use super::auto_composer::{CanvasElement, CanvasTemplate};
+use super::framing::filter_path;
+use super::{execute_ffmpeg_command, Result, VideoError};
+
+/// YouTube's recommended thumbnail size
+pub const WIDTH: u32 = 1280;
+pub const HEIGHT: u32 = 720;
+
+/// Width canvas templates are designed at
+const CANVAS_WIDTH: u32 = 1080;
+
+/// Render the frame of `video_path` at `timestamp` (with `template`'s
+/// elements) to `output_path` as JPEG
+pub async fn render(
+    video_path: &Path,
+    timestamp: f64,
+    template: Option<&CanvasTemplate>,
+    output_path: &Path,
+) -> Result<PathBuf> {
+    if !video_path.exists() {
+        return Err(VideoError::FileNotFound {
+            path: video_path.display().to_string(),
+        });
+    }
+
+    let mut command = TokioCommand::new("ffmpeg");
+    command
+        .args(["-ss", &timestamp.to_string(), "-i"])
+        .arg(video_path)
+        .args(["-filter_complex", &thumbnail_filter(template)])
+        .args(["-frames:v", "1", "-q:v", "2", "-y"])
+        .arg(output_path);
+
+    execute_ffmpeg_command(&mut command).await?;
+
+    if !output_path.exists() {
+        return Err(VideoError::ProcessingError {
+            message: format!("No frame at {:.2}s", timestamp),
+        });
+    }
+
+    info!("Rendered upload thumbnail: {:?}", output_path);
+    Ok(output_path.to_path_buf())
+}
+
+/// Filter graph producing the thumbnail from input 0
+fn thumbnail_filter(template: Option<&CanvasTemplate>) -> String {
+    // Blurred fill behind the frame fitted to the height
+    let mut filter = format!(
+        "[0:v]split[fill][frame];\
+         [fill]scale={w}:{h}:force_original_aspect_ratio=increase,crop={w}:{h},boxblur=20[bg];\
+         [frame]scale={w}:{h}:force_original_aspect_ratio=decrease[fg];\
+         [bg][fg]overlay=(W-w)/2:(H-h)/2,setsar=1[base]",
+        w = WIDTH,
+        h = HEIGHT
+    );
+
+    let Some(template) = template else {
+        filter.push_str(";[base]null");
+        return filter;
+    };
+
+    let scale = WIDTH as f32 / CANVAS_WIDTH as f32;
+    let mut last = "base".to_string();
+
+    // Images first so text stays readable on top of them
+    for (idx, element) in template.elements.iter().enumerate() {
+        if let CanvasElement::Image {
+            path,
+            width,
+            height,
+            position,
+            ..
+        } = element
+        {
+            if !Path::new(path).exists() {
+                warn!("Overlay image not found: {}", path);
+                continue;
+            }
+
+            let (x, y) = pixel_position(position.x, position.y);
+            filter.push_str(&format!(
+                ";movie='{}',scale={}:{}[img{idx}];[{last}][img{idx}]overlay={}:{}[out{idx}]",
+                filter_path(Path::new(path)),
+                (*width as f32 * scale).round() as u32,
+                (*height as f32 * scale).round() as u32,
+                x,
+                y,
+                idx = idx,
+                last = last
+            ));
+            last = format!("out{}", idx);
+        }
+    }
+
+    let mut texts = Vec::new();
+    for element in &template.elements {
+        if let CanvasElement::Text {
+            content,
+            font,
+            size,
+            color,
+            outline,
+            position,
+            ..
+        } = element
+        {
+            let (x, y) = pixel_position(position.x, position.y);
+            let mut drawtext = format!(
+                "drawtext=text='{}':fontfile={}:fontsize={}:fontcolor={}:x={}:y={}",
+                content.replace('\'', "\\'"),
+                font,
+                (*size as f32 * scale).round() as u32,
+                color,
+                x,
+                y
+            );
+            if let Some(outline_color) = outline {
+                drawtext.push_str(&format!(":borderw=3:bordercolor={}", outline_color));
+            }
+            texts.push(drawtext);
+        }
+    }
+
+    if texts.is_empty() {
+        filter.push_str(&format!(";[{}]null", last));
+    } else {
+        filter.push_str(&format!(";[{}]{}", last, texts.join(",")));
+    }
+    filter
+}
+
+/// Percentage position on the thumbnail in pixels
+fn pixel_position(x: f32, y: f32) -> (u32, u32) {
+    (
+        (x * WIDTH as f32 / 100.0) as u32,
+        (y * HEIGHT as f32 / 100.0) as u32,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::video::auto_composer::{BackgroundLayer, Position};
+
+    #[test]
+    fn test_thumbnail_filter() {
+        let plain = thumbnail_filter(None);
+        assert!(plain.contains("crop=1280:720,boxblur=20[bg]"));
+        assert!(plain.ends_with("[base];[base]null"));
+
+        let template = CanvasTemplate {
+            id: "pentakill".to_string(),
+            name: "Pentakill".to_string(),
+            background: BackgroundLayer::Color {
+                value: "black".to_string(),
+            },
+            elements: vec![CanvasElement::Text {
+                id: "title".to_string(),
+                content: "PENTAKILL".to_string(),
+                font: "Arial".to_string(),
+                size: 108,
+                color: "white".to_string(),
+                outline: Some("black".to_string()),
+                position: Position { x: 10.0, y: 50.0 },
+                animation: None,
+            }],
+            color_lut: None,
+        };
+
+        // Positions are percentages of 1280x720, sizes scaled from 1080 wide
+        let filter = thumbnail_filter(Some(&template));
+        assert!(filter.ends_with(
+            "[base]drawtext=text='PENTAKILL':fontfile=Arial:fontsize=128:fontcolor=white:x=128:y=360:borderw=3:bordercolor=black"
+        ));
+    }
+}
@@ -0,0 +1,379 @@
+//! Importing externally recorded VODs
+//!
+//! A full-game recording (e.g. from OBS) is lined up with the game's stored
+//! events and each event is cut out of it as a regular clip. The offset
+//! between VOD time and game time is either given by the user or read from
+//! the in-game clock (OCR through the `tesseract` CLI).
+
+use serde::Serialize;
+use std::path::Path;
+use tokio::process::Command as TokioCommand;
+use tracing::{debug, info, warn};
+
+use super::{execute_ffmpeg_command, Result, VideoError, VideoProcessor};
+use crate::settings::models::ClipTimingSettings;
+use crate::storage::models::{ClipMetadata, EventData, EventType};
+use crate::storage::{ClipMetadataV2, Storage};
+
+/// Game clock area of a frame as fractions (x, y, width, height)
+///
+/// Top right corner of the HUD at the default HUD scale.
+const CLOCK_REGION: (f64, f64, f64, f64) = (0.955, 0.0, 0.045, 0.03);
+
+/// Positions in the VOD (fractions of its length) where the clock is read
+const CLOCK_SAMPLES: [f64; 5] = [0.2, 0.35, 0.5, 0.65, 0.8];
+
+/// Clock readings within this many seconds agree on the offset
+const OFFSET_TOLERANCE_SECS: f64 = 2.0;
+
+/// Clips cut from an imported VOD
+#[derive(Debug, Clone, Serialize)]
+pub struct VodImport {
+    /// VOD time (seconds) at which the game clock reads 0:00
+    pub offset_secs: f64,
+    pub clips: Vec<ClipMetadataV2>,
+}
+
+/// Cut a game's stored events out of a VOD of that game
+///
+/// `offset_secs` is the VOD time at which the game clock reads 0:00; it's
+/// detected from the in-game clock if not given. Events outside the VOD are
+/// skipped.
+pub async fn import_vod(
+    storage: &Storage,
+    timing: &ClipTimingSettings,
+    vod_path: &Path,
+    game_id: &str,
+    offset_secs: Option<f64>,
+) -> Result<VodImport> {
+    let events = storage
+        .load_events(game_id)
+        .map_err(|e| VideoError::ProcessingError {
+            message: format!("Failed to load events for game {}: {}", game_id, e),
+        })?;
+    if events.is_empty() {
+        return Err(VideoError::ProcessingError {
+            message: format!("No events stored for game {}", game_id),
+        });
+    }
+
+    let processor = VideoProcessor::new();
+    let vod_duration = processor.get_duration(vod_path).await?;
+    let offset = match offset_secs {
+        Some(offset) => offset,
+        None => detect_vod_offset(vod_path, vod_duration).await?,
+    };
+    info!(
+        "Importing VOD {:?} for game {} (offset: {:.1}s, {} events)",
+        vod_path,
+        game_id,
+        offset,
+        events.len()
+    );
+
+    let clips_dir = storage.game_path(game_id);
+    std::fs::create_dir_all(&clips_dir).map_err(|e| VideoError::ProcessingError {
+        message: format!("Failed to create clips directory: {}", e),
+    })?;
+
+    let mut clips = Vec::new();
+    for event in &events {
+        let Some((start, duration)) = vod_cut(event, offset, vod_duration, timing) else {
+            warn!(
+                "Event {} at {:.0}s is outside the VOD - skipped",
+                event.event_id, event.timestamp
+            );
+            continue;
+        };
+
+        let output = clips_dir.join(format!(
+            "vod_{}_{}.mp4",
+            event.event_id, event.timestamp as u32
+        ));
+        if let Err(e) = processor
+            .extract_clip(vod_path, &output, start, duration)
+            .await
+        {
+            warn!("Failed to cut event {} from VOD: {}", event.event_id, e);
+            continue;
+        }
+
+        let clip = vod_clip_metadata(event, game_id, &output, start - offset, duration);
+        storage
+            .save_clip_metadata_v2(game_id, &clip)
+            .map_err(|e| VideoError::ProcessingError {
+                message: format!("Failed to save clip metadata: {}", e),
+            })?;
+        clips.push(clip);
+    }
+
+    info!("Cut {} clips from VOD", clips.len());
+
+    Ok(VodImport {
+        offset_secs: offset,
+        clips,
+    })
+}
+
+/// V2 metadata for `event` cut from a VOD, starting at game time `clip_start`
+fn vod_clip_metadata(
+    event: &EventData,
+    game_id: &str,
+    clip_path: &Path,
+    clip_start: f64,
+    duration: f64,
+) -> ClipMetadataV2 {
+    let metadata = ClipMetadata {
+        file_path: clip_path.to_string_lossy().to_string(),
+        thumbnail_path: None,
+        event_type: event.event_type.clone(),
+        event_time: clip_start,
+        priority: event.priority,
+        duration,
+        created_at: chrono::Utc::now(),
+    };
+
+    let mut clip = ClipMetadataV2::from(metadata);
+    clip.game_id = game_id.to_string();
+    clip.primary_event.event_id = event.event_id;
+    clip.primary_event.timestamp = event.timestamp;
+    clip.primary_event.clip_timestamp = event.timestamp - clip_start;
+    clip.primary_event.details = event.details.clone();
+
+    // Participants are stored as killer followed by assisters
+    if let Some((killer, assisters)) = event.participants.split_first() {
+        clip.primary_event.killer = Some(killer.clone());
+        clip.primary_event.assisters = assisters.to_vec();
+    }
+
+    clip.event_window.pre_duration = event.timestamp - clip_start;
+    clip.event_window.post_duration = clip_start + duration - event.timestamp;
+    clip.add_tag("vod".to_string());
+    clip
+}
+
+/// Start and duration of `event`'s clip in a VOD, if it's inside the VOD
+fn vod_cut(
+    event: &EventData,
+    offset: f64,
+    vod_duration: f64,
+    timing: &ClipTimingSettings,
+) -> Option<(f64, f64)> {
+    let vod_time = event.timestamp + offset;
+    if !(0.0..=vod_duration).contains(&vod_time) {
+        return None;
+    }
+
+    let timing = timing.get_timing_for_event(timing_key(&event.event_type));
+    let start = (vod_time - timing.pre_duration as f64).max(0.0);
+    let end = (vod_time + timing.post_duration as f64).min(vod_duration);
+
+    Some((start, end - start))
+}
+
+/// Clip timing settings key for a stored event type
+fn timing_key(event_type: &EventType) -> &'static str {
+    match event_type {
+        EventType::Multikill(_) => "multikill",
+        EventType::Death => "death",
+        EventType::Custom(name) if name == "Steal" => "steal",
+        _ => "kill", // Default for other events
+    }
+}
+
+/// Find the VOD offset by reading the in-game clock at several points
+pub async fn detect_vod_offset(vod_path: &Path, vod_duration: f64) -> Result<f64> {
+    let mut offsets = Vec::new();
+
+    for fraction in CLOCK_SAMPLES {
+        let vod_time = vod_duration * fraction;
+        match read_game_clock(vod_path, vod_time).await? {
+            Some(clock) => offsets.push(vod_time - clock),
+            None => debug!("No game clock found at {:.0}s", vod_time),
+        }
+    }
+
+    consensus_offset(&offsets).ok_or_else(|| VideoError::ProcessingError {
+        message: "Couldn't read the game clock in the VOD. Enter the offset manually.".to_string(),
+    })
+}
+
+/// Read the in-game clock (seconds) from the VOD frame at `vod_time`
+async fn read_game_clock(vod_path: &Path, vod_time: f64) -> Result<Option<f64>> {
+    let frame = std::env::temp_dir().join(format!("lolshorts_clock_{}.png", uuid::Uuid::new_v4()));
+    let (x, y, width, height) = CLOCK_REGION;
+    // Upscaled grayscale crop reads much more reliably
+    let filter = format!(
+        "crop=iw*{}:ih*{}:iw*{}:ih*{},scale=iw*4:ih*4,format=gray",
+        width, height, x, y
+    );
+
+    let mut command = TokioCommand::new("ffmpeg");
+    command.args([
+        "-ss",
+        &vod_time.to_string(),
+        "-i",
+        vod_path
+            .to_str()
+            .ok_or_else(|| VideoError::FileAccessError {
+                path: vod_path.display().to_string(),
+            })?,
+        "-frames:v",
+        "1",
+        "-vf",
+        &filter,
+        "-y",
+        frame.to_str().ok_or_else(|| VideoError::FileAccessError {
+            path: frame.display().to_string(),
+        })?,
+    ]);
+    execute_ffmpeg_command(&mut command).await?;
+
+    let output = TokioCommand::new("tesseract")
+        .arg(&frame)
+        .args([
+            "stdout",
+            "--psm",
+            "7",
+            "-c",
+            "tessedit_char_whitelist=0123456789:",
+        ])
+        .output()
+        .await;
+    let _ = tokio::fs::remove_file(&frame).await;
+
+    let output = output.map_err(|e| VideoError::ProcessingError {
+        message: if e.kind() == std::io::ErrorKind::NotFound {
+            "Reading the game clock requires Tesseract OCR. Install it or enter the offset manually."
+                .to_string()
+        } else {
+            format!("Failed to execute tesseract: {}", e)
+        },
+    })?;
+
+    Ok(parse_game_clock(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse an in-game clock reading ("12:34") into seconds
+fn parse_game_clock(text: &str) -> Option<f64> {
+    let text: String = text
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == ':')
+        .collect();
+    let (minutes, seconds) = text.split_once(':')?;
+    let minutes: u32 = minutes.parse().ok()?;
+    let seconds: u32 = seconds.parse().ok()?;
+
+    (seconds < 60).then_some((minutes * 60 + seconds) as f64)
+}
+
+/// Average of the largest group of offsets that agree with each other
+///
+/// Discards OCR misreads; at least two readings have to agree.
+fn consensus_offset(offsets: &[f64]) -> Option<f64> {
+    let best = offsets
+        .iter()
+        .map(|&candidate| {
+            offsets
+                .iter()
+                .copied()
+                .filter(|offset| (offset - candidate).abs() <= OFFSET_TOLERANCE_SECS)
+                .collect::<Vec<_>>()
+        })
+        .max_by_key(|group| group.len())?;
+
+    (best.len() >= 2).then(|| best.iter().sum::<f64>() / best.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(event_type: EventType, timestamp: f64) -> EventData {
+        EventData {
+            event_id: 7,
+            event_type,
+            timestamp,
+            priority: 2,
+            participants: vec!["Player".to_string(), "Ally".to_string()],
+            details: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_game_clock() {
+        assert_eq!(parse_game_clock("12:34\n"), Some(754.0));
+        assert_eq!(parse_game_clock(" 0:05 "), Some(5.0));
+        assert_eq!(parse_game_clock("1234"), None);
+        assert_eq!(parse_game_clock("12:75"), None);
+        assert_eq!(parse_game_clock(""), None);
+    }
+
+    #[test]
+    fn test_consensus_offset() {
+        // One misread clock is ignored
+        assert_eq!(consensus_offset(&[30.0, 31.0, 412.0, 30.5]), Some(30.5));
+        assert_eq!(consensus_offset(&[30.0]), None);
+        assert_eq!(consensus_offset(&[30.0, 90.0]), None);
+        assert_eq!(consensus_offset(&[]), None);
+    }
+
+    #[test]
+    fn test_vod_cut() {
+        let timing = ClipTimingSettings::default();
+        let kill = timing.get_timing_for_event("kill");
+        let pre = kill.pre_duration as f64;
+        let post = kill.post_duration as f64;
+
+        // VOD started 30s before the game clock hit 0:00
+        let (start, duration) = vod_cut(
+            &event(EventType::ChampionKill, 300.0),
+            30.0,
+            1800.0,
+            &timing,
+        )
+        .unwrap();
+        assert_eq!(start, 330.0 - pre);
+        assert_eq!(duration, pre + post);
+
+        // Clamped to the start of the VOD
+        let (start, _) =
+            vod_cut(&event(EventType::ChampionKill, 1.0), 0.0, 1800.0, &timing).unwrap();
+        assert_eq!(start, 0.0);
+
+        // Outside the VOD
+        assert!(vod_cut(
+            &event(EventType::ChampionKill, 1790.0),
+            30.0,
+            1800.0,
+            &timing
+        )
+        .is_none());
+        assert!(vod_cut(
+            &event(EventType::ChampionKill, 10.0),
+            -60.0,
+            1800.0,
+            &timing
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_vod_clip_metadata() {
+        let clip = vod_clip_metadata(
+            &event(EventType::Multikill(2), 300.0),
+            "game_1",
+            Path::new("/clips/game_1/vod_7_300.mp4"),
+            290.0,
+            15.0,
+        );
+
+        assert_eq!(clip.game_id, "game_1");
+        assert_eq!(clip.game_time_start, 290.0);
+        assert_eq!(clip.game_time_end, 305.0);
+        assert_eq!(clip.primary_event.clip_timestamp, 10.0);
+        assert_eq!(clip.primary_event.killer.as_deref(), Some("Player"));
+        assert_eq!(clip.primary_event.assisters, vec!["Ally".to_string()]);
+        assert!(clip.tags.contains(&"vod".to_string()));
+    }
+}
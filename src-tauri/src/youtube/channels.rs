@@ -0,0 +1,149 @@
+/// Linked YouTube channels
+///
+/// Each channel keeps its own OAuth credentials, keyed by channel ID. Only the
+/// active channel's credentials are loaded into the OAuth client at a time.
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::models::{ChannelSummary, YouTubeChannel};
+use super::oauth::YouTubeCredentials;
+
+/// A channel and the credentials used to upload to it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkedChannel {
+    pub channel: YouTubeChannel,
+    pub credentials: YouTubeCredentials,
+}
+
+/// All linked channels (persisted as the `youtube_channels` setting)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChannelRegistry {
+    pub active_channel_id: Option<String>,
+    pub channels: BTreeMap<String, LinkedChannel>,
+}
+
+impl ChannelRegistry {
+    /// Add or re-link a channel and make it active
+    pub fn link(&mut self, channel: YouTubeChannel, credentials: YouTubeCredentials) {
+        self.active_channel_id = Some(channel.id.clone());
+        self.channels.insert(
+            channel.id.clone(),
+            LinkedChannel {
+                channel,
+                credentials,
+            },
+        );
+    }
+
+    pub fn active(&self) -> Option<&LinkedChannel> {
+        self.active_channel_id
+            .as_ref()
+            .and_then(|id| self.channels.get(id))
+    }
+
+    /// Store refreshed credentials for the active channel
+    pub fn update_active_credentials(&mut self, credentials: YouTubeCredentials) {
+        if let Some(id) = &self.active_channel_id {
+            if let Some(linked) = self.channels.get_mut(id) {
+                linked.credentials = credentials;
+            }
+        }
+    }
+
+    /// Make a linked channel active, returning it (`None` if not linked)
+    pub fn switch(&mut self, channel_id: &str) -> Option<&LinkedChannel> {
+        if !self.channels.contains_key(channel_id) {
+            return None;
+        }
+
+        self.active_channel_id = Some(channel_id.to_string());
+        self.channels.get(channel_id)
+    }
+
+    /// Remove a channel; if it was active, the next linked channel becomes active
+    pub fn unlink(&mut self, channel_id: &str) -> Option<LinkedChannel> {
+        let removed = self.channels.remove(channel_id);
+
+        if self.active_channel_id.as_deref() == Some(channel_id) {
+            self.active_channel_id = self.channels.keys().next().cloned();
+        }
+
+        removed
+    }
+
+    pub fn summaries(&self) -> Vec<ChannelSummary> {
+        self.channels
+            .values()
+            .map(|linked| ChannelSummary {
+                channel: linked.channel.clone(),
+                active: self.active_channel_id.as_deref() == Some(linked.channel.id.as_str()),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel(id: &str) -> YouTubeChannel {
+        YouTubeChannel {
+            id: id.to_string(),
+            title: format!("Channel {}", id),
+            thumbnail_url: None,
+        }
+    }
+
+    fn credentials(token: &str) -> YouTubeCredentials {
+        YouTubeCredentials {
+            access_token: token.to_string(),
+            refresh_token: Some("refresh".to_string()),
+            expires_at: None,
+            token_type: "Bearer".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_link_and_switch() {
+        let mut registry = ChannelRegistry::default();
+        registry.link(channel("a"), credentials("token_a"));
+        registry.link(channel("b"), credentials("token_b"));
+
+        assert_eq!(registry.active().unwrap().channel.id, "b");
+
+        let switched = registry.switch("a").unwrap();
+        assert_eq!(switched.credentials.access_token, "token_a");
+        assert_eq!(registry.active_channel_id.as_deref(), Some("a"));
+
+        assert!(registry.switch("missing").is_none());
+        assert_eq!(registry.active_channel_id.as_deref(), Some("a"));
+
+        let summaries = registry.summaries();
+        assert_eq!(summaries.len(), 2);
+        assert!(summaries.iter().any(|s| s.channel.id == "a" && s.active));
+        assert!(summaries.iter().any(|s| s.channel.id == "b" && !s.active));
+    }
+
+    #[test]
+    fn test_unlink_active_falls_back() {
+        let mut registry = ChannelRegistry::default();
+        registry.link(channel("a"), credentials("token_a"));
+        registry.link(channel("b"), credentials("token_b"));
+
+        registry.unlink("b").unwrap();
+        assert_eq!(registry.active_channel_id.as_deref(), Some("a"));
+
+        registry.unlink("a").unwrap();
+        assert!(registry.active().is_none());
+        assert!(registry.channels.is_empty());
+    }
+
+    #[test]
+    fn test_update_active_credentials() {
+        let mut registry = ChannelRegistry::default();
+        registry.link(channel("a"), credentials("old"));
+        registry.update_active_credentials(credentials("new"));
+
+        assert_eq!(registry.active().unwrap().credentials.access_token, "new");
+    }
+}
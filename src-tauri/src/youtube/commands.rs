@@ -1,14 +1,30 @@
-use std::path::PathBuf;
+use anyhow::Context;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tauri::State;
+use tokio::sync::RwLock;
 use tracing::{error, info, warn};
 
 use super::callback_server::CallbackServer;
-use super::models::{AuthStatus, QuotaInfo, UploadHistoryEntry};
+use super::channels::ChannelRegistry;
+use super::drafts;
+use super::errors::UploadError;
+use super::models::{
+    AuthStatus, ChannelSummary, FailedUpload, QuotaInfo, UploadHistoryEntry, UploadHistoryStatus,
+    VideoStats, YouTubeChannel,
+};
 use super::oauth::{YouTubeCredentials, YouTubeOAuthClient};
+use super::quota::QuotaTracker;
+use super::suggestions::{self, MetadataSuggestion};
 use super::upload::{PrivacyStatus, UploadProgress, VideoMetadata, YouTubeUploadClient, YouTubeVideo};
-use crate::storage::Storage;
+use crate::jobs::{JobKind, JobPriority};
+use crate::notifications::center::{NotificationCenter, NotificationKind};
+use crate::notifications::Notification;
+use crate::quota::MeteredFeature;
+use crate::storage::{Storage, UploadDraft};
+use crate::telemetry::TelemetryEvent;
 use crate::utils::security;
+use crate::AppState;
 
 /// YouTube manager state
 #[derive(Clone)]
@@ -16,6 +32,12 @@ pub struct YouTubeManager {
     pub oauth_client: Arc<YouTubeOAuthClient>,
     pub upload_client: Arc<YouTubeUploadClient>,
     pub storage: Arc<Storage>,
+    pub channels: Arc<RwLock<ChannelRegistry>>,
+    /// API units used today, counted by the upload client
+    pub quota: Arc<QuotaTracker>,
+    /// Read-held by running uploads so the active channel can't change under
+    /// them; write-held to change the active channel
+    upload_lock: Arc<RwLock<()>>,
 }
 
 impl YouTubeManager {
@@ -30,37 +52,312 @@ impl YouTubeManager {
             client_secret,
             redirect_uri,
         )?);
-        let upload_client = Arc::new(YouTubeUploadClient::new(Arc::clone(&oauth_client)));
+        let quota = Arc::new(QuotaTracker::new(Arc::clone(&storage)));
+        let upload_client = Arc::new(YouTubeUploadClient::new(
+            Arc::clone(&oauth_client),
+            Arc::clone(&quota),
+        ));
 
         Ok(Self {
             oauth_client,
             upload_client,
             storage,
+            channels: Arc::new(RwLock::new(ChannelRegistry::default())),
+            quota,
+            upload_lock: Arc::new(RwLock::new(())),
         })
     }
 
-    /// Load stored credentials from storage
+    /// Manager using the OAuth client from `YOUTUBE_CLIENT_ID`,
+    /// `YOUTUBE_CLIENT_SECRET` and `YOUTUBE_REDIRECT_URI`
+    pub fn from_env(storage: Arc<Storage>) -> anyhow::Result<Self> {
+        let client_id = std::env::var("YOUTUBE_CLIENT_ID")
+            .unwrap_or_else(|_| "your-client-id.apps.googleusercontent.com".to_string());
+        let client_secret = std::env::var("YOUTUBE_CLIENT_SECRET")
+            .unwrap_or_else(|_| "your-client-secret".to_string());
+        let redirect_uri = std::env::var("YOUTUBE_REDIRECT_URI")
+            .unwrap_or_else(|_| "http://localhost:8080/oauth2/callback".to_string());
+
+        Self::new(client_id, client_secret, redirect_uri, storage)
+    }
+
+    /// Warn in `notifications` when the daily API quota runs low
+    pub fn with_notifications(self, notifications: Arc<NotificationCenter>) -> Self {
+        self.quota.set_notifications(notifications);
+        self
+    }
+
+    /// Load linked channels from storage and activate the last used one
+    ///
+    /// Single-account credentials from older versions are migrated to a
+    /// linked channel when the channel can be looked up.
     pub async fn load_credentials(&self) -> anyhow::Result<()> {
+        if let Ok(channels_json) = self.storage.get_setting("youtube_channels").await {
+            let registry: ChannelRegistry = serde_json::from_str(&channels_json)?;
+            if registry.channels.is_empty() {
+                return self.load_legacy_credentials().await;
+            }
+            if let Some(active) = registry.active() {
+                self.oauth_client
+                    .set_credentials(active.credentials.clone())
+                    .await;
+            }
+            info!(
+                "Loaded {} linked YouTube channel(s) from storage",
+                registry.channels.len()
+            );
+            *self.channels.write().await = registry;
+            return Ok(());
+        }
+
+        self.load_legacy_credentials().await
+    }
+
+    /// Load single-account credentials and try to link their channel
+    async fn load_legacy_credentials(&self) -> anyhow::Result<()> {
         if let Ok(creds_json) = self.storage.get_setting("youtube_credentials").await {
             if let Ok(credentials) = serde_json::from_str::<YouTubeCredentials>(&creds_json) {
                 self.oauth_client.set_credentials(credentials).await;
                 info!("YouTube credentials loaded from storage");
+
+                match self.link_channel().await {
+                    Ok(channel) => {
+                        self.storage.remove_setting("youtube_credentials").await?;
+                        info!("Migrated YouTube credentials to channel {}", channel.id);
+                    }
+                    Err(e) => warn!("Failed to migrate YouTube credentials: {}", e),
+                }
             }
         }
         Ok(())
     }
 
-    /// Save credentials to storage
+    /// Save linked channels (including the active channel's current tokens) to storage
     pub async fn save_credentials(&self) -> anyhow::Result<()> {
+        let mut channels = self.channels.write().await;
         if let Some(credentials) = self.oauth_client.get_credentials().await {
-            let creds_json = serde_json::to_string(&credentials)?;
-            self.storage
-                .set_setting("youtube_credentials", &creds_json)
-                .await?;
-            info!("YouTube credentials saved to storage");
+            channels.update_active_credentials(credentials);
+        }
+
+        let channels_json = serde_json::to_string(&*channels)?;
+        self.storage
+            .set_setting("youtube_channels", &channels_json)
+            .await?;
+        info!("YouTube credentials saved to storage");
+        Ok(())
+    }
+
+    /// Link the channel of the freshly authenticated account and make it active
+    pub async fn link_channel(&self) -> anyhow::Result<YouTubeChannel> {
+        let _upload = self.upload_lock.write().await;
+
+        let credentials = self
+            .oauth_client
+            .get_credentials()
+            .await
+            .context("No credentials available. Please authenticate first.")?;
+        let channel = self.upload_client.get_my_channel().await?;
+
+        // Token may have been refreshed during the lookup
+        let credentials = self
+            .oauth_client
+            .get_credentials()
+            .await
+            .unwrap_or(credentials);
+
+        self.channels
+            .write()
+            .await
+            .link(channel.clone(), credentials);
+        self.save_credentials().await?;
+
+        info!("Linked YouTube channel: {} ({})", channel.title, channel.id);
+        Ok(channel)
+    }
+
+    /// Make a linked channel active, waiting for any running upload to finish
+    pub async fn switch_channel(&self, channel_id: &str) -> anyhow::Result<YouTubeChannel> {
+        let _upload = self.upload_lock.write().await;
+        self.activate(channel_id).await
+    }
+
+    /// Remove the active channel, falling back to another linked channel if any
+    pub async fn unlink_active_channel(&self) -> anyhow::Result<()> {
+        let _upload = self.upload_lock.write().await;
+
+        let next = {
+            let mut channels = self.channels.write().await;
+            if let Some(id) = channels.active_channel_id.clone() {
+                channels.unlink(&id);
+            }
+            channels.active().map(|linked| linked.credentials.clone())
+        };
+
+        match next {
+            Some(credentials) => self.oauth_client.set_credentials(credentials).await,
+            None => {
+                self.oauth_client.clear_credentials().await;
+                // Unmigrated credentials from older versions
+                self.storage.remove_setting("youtube_credentials").await?;
+            }
+        }
+
+        self.save_credentials().await
+    }
+
+    /// Upload a video to the given linked channel, or the active one if `None`
+    ///
+    /// Selecting a channel makes it the active channel. Waits for a slot in
+    /// the upload queue; uploads to the active channel can run side by side.
+    pub async fn upload_video(
+        &self,
+        channel_id: Option<&str>,
+        video_path: &Path,
+        metadata: VideoMetadata,
+        thumbnail_path: Option<&Path>,
+    ) -> anyhow::Result<YouTubeVideo> {
+        let _slot = crate::uploads::queue().acquire().await;
+
+        let mut _upload = self.upload_lock.read().await;
+        if let Some(channel_id) = channel_id {
+            let active = self.channels.read().await.active_channel_id.clone();
+            if active.as_deref() != Some(channel_id) {
+                // Wait for uploads to the other channel to finish before switching
+                drop(_upload);
+                let switching = self.upload_lock.write().await;
+                self.activate(channel_id).await?;
+                _upload = switching.downgrade();
+            }
+        }
+
+        let result = self
+            .upload_client
+            .upload_video(video_path, metadata, thumbnail_path)
+            .await;
+
+        // Persist tokens refreshed during the upload
+        if let Err(e) = self.save_credentials().await {
+            warn!("Failed to save YouTube credentials after upload: {}", e);
+        }
+
+        result
+    }
+
+    /// Update upload history view counts from the Videos API
+    ///
+    /// Does nothing when no YouTube account is linked.
+    pub async fn refresh_history_stats(&self) -> anyhow::Result<()> {
+        if self.oauth_client.get_credentials().await.is_none() {
+            return Ok(());
+        }
+
+        let mut history: Vec<UploadHistoryEntry> =
+            match self.storage.get_setting("youtube_upload_history").await {
+                Ok(json) => serde_json::from_str(&json)?,
+                Err(_) => return Ok(()),
+            };
+        if history.is_empty() {
+            return Ok(());
+        }
+
+        let video_ids: Vec<String> = history
+            .iter()
+            .filter(|e| e.status == UploadHistoryStatus::Uploaded)
+            .map(|e| e.video_id.clone())
+            .collect();
+        if video_ids.is_empty() {
+            return Ok(());
+        }
+        let stats = self.upload_client.get_video_stats(&video_ids).await?;
+
+        let mut updated = 0;
+        for entry in &mut history {
+            if let Some(s) = stats.iter().find(|s| s.video_id == entry.video_id) {
+                entry.view_count = Some(s.view_count);
+                updated += 1;
+            }
+        }
+
+        let history_json = serde_json::to_string(&history)?;
+        self.storage
+            .set_setting("youtube_upload_history", &history_json)
+            .await?;
+
+        info!("Refreshed view counts for {} uploaded video(s)", updated);
+        Ok(())
+    }
+
+    /// Keep a failed upload in the history (replacing an earlier failure of
+    /// the same video) so it can be retried
+    pub async fn record_failed_upload(&self, failure: FailedUpload) -> anyhow::Result<()> {
+        let mut history = self.load_history().await;
+        history.retain(|entry| !is_failure_of(entry, &failure.video_path));
+        history.insert(0, UploadHistoryEntry::failed(failure));
+        history.truncate(100);
+
+        self.save_history(&history).await
+    }
+
+    /// Remove the failed upload of `video_path` from the history after it
+    /// went through
+    pub async fn clear_failed_upload(&self, video_path: &Path) -> anyhow::Result<()> {
+        let mut history = self.load_history().await;
+        let before = history.len();
+        history.retain(|entry| !is_failure_of(entry, video_path));
+
+        if history.len() == before {
+            return Ok(());
         }
+        self.save_history(&history).await
+    }
+
+    /// Upload history from storage (empty if there is none)
+    async fn load_history(&self) -> Vec<UploadHistoryEntry> {
+        self.storage
+            .get_setting("youtube_upload_history")
+            .await
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    async fn save_history(&self, history: &[UploadHistoryEntry]) -> anyhow::Result<()> {
+        let history_json = serde_json::to_string(history)?;
+        self.storage
+            .set_setting("youtube_upload_history", &history_json)
+            .await?;
         Ok(())
     }
+
+    /// Swap the OAuth client over to a linked channel (caller write-holds `upload_lock`)
+    async fn activate(&self, channel_id: &str) -> anyhow::Result<YouTubeChannel> {
+        let mut channels = self.channels.write().await;
+        if channels.active_channel_id.as_deref() == Some(channel_id) {
+            if let Some(active) = channels.active() {
+                return Ok(active.channel.clone());
+            }
+        }
+
+        // Keep tokens refreshed for the outgoing channel
+        if let Some(credentials) = self.oauth_client.get_credentials().await {
+            channels.update_active_credentials(credentials);
+        }
+
+        let linked = channels
+            .switch(channel_id)
+            .cloned()
+            .with_context(|| format!("YouTube channel not linked: {}", channel_id))?;
+        drop(channels);
+
+        self.oauth_client.set_credentials(linked.credentials).await;
+        self.save_credentials().await?;
+
+        info!(
+            "Switched YouTube channel: {} ({})",
+            linked.channel.title, linked.channel.id
+        );
+        Ok(linked.channel)
+    }
 }
 
 /// Start YouTube OAuth2 authentication flow
@@ -124,9 +421,9 @@ pub async fn youtube_start_auth_with_server(
                     .await
                 {
                     Ok(_) => {
-                        // Save credentials
-                        if let Err(e) = youtube_clone.save_credentials().await {
-                            error!("Failed to save credentials after auto-complete: {}", e);
+                        // Link the account's channel and save credentials
+                        if let Err(e) = youtube_clone.link_channel().await {
+                            error!("Failed to link channel after auto-complete: {}", e);
                         } else {
                             info!("YouTube authentication auto-completed successfully");
                         }
@@ -174,10 +471,10 @@ pub async fn youtube_complete_auth(
             format!("Authentication failed: {}", e)
         })?;
 
-    // Save credentials
-    youtube.save_credentials().await.map_err(|e| {
-        error!("Failed to save credentials: {}", e);
-        "Failed to save credentials".to_string()
+    // Link the account's channel and save credentials
+    youtube.link_channel().await.map_err(|e| {
+        error!("Failed to link channel: {}", e);
+        format!("Failed to link channel: {}", e)
     })?;
 
     info!("YouTube authentication completed successfully");
@@ -190,6 +487,12 @@ pub async fn youtube_get_auth_status(
     youtube: State<'_, YouTubeManager>,
 ) -> Result<AuthStatus, String> {
     let credentials = youtube.oauth_client.get_credentials().await;
+    let active_channel = youtube
+        .channels
+        .read()
+        .await
+        .active()
+        .map(|linked| linked.channel.clone());
 
     Ok(AuthStatus {
         authenticated: credentials.is_some(),
@@ -198,6 +501,29 @@ pub async fn youtube_get_auth_status(
             .as_ref()
             .and_then(|c| c.refresh_token.as_ref())
             .is_some(),
+        active_channel,
+    })
+}
+
+/// List linked YouTube channels
+#[tauri::command]
+pub async fn youtube_list_channels(
+    youtube: State<'_, YouTubeManager>,
+) -> Result<Vec<ChannelSummary>, String> {
+    Ok(youtube.channels.read().await.summaries())
+}
+
+/// Switch the channel uploads go to
+///
+/// Waits for a running upload to finish before switching.
+#[tauri::command]
+pub async fn youtube_switch_channel(
+    youtube: State<'_, YouTubeManager>,
+    channel_id: String,
+) -> Result<YouTubeChannel, String> {
+    youtube.switch_channel(&channel_id).await.map_err(|e| {
+        error!("Failed to switch YouTube channel: {}", e);
+        format!("Failed to switch channel: {}", e)
     })
 }
 
@@ -210,15 +536,23 @@ pub async fn youtube_get_auth_status(
 /// * `tags` - Array of video tags
 /// * `privacy_status` - Privacy status (public, unlisted, private)
 /// * `thumbnail_path` - Optional path to custom thumbnail
+/// * `channel_id` - Linked channel to upload to (defaults to the active channel)
+/// * `result_id` - Auto-edit result being uploaded; its generated upload
+///   thumbnail is used when `thumbnail_path` isn't given, its upload draft
+///   fills in metadata that isn't given, and placeholders in the metadata
+///   are filled in from its games
 #[tauri::command]
 pub async fn youtube_upload_video(
+    state: State<'_, AppState>,
     youtube: State<'_, YouTubeManager>,
     video_path: String,
-    title: String,
-    description: String,
-    tags: Vec<String>,
-    privacy_status: String,
+    title: Option<String>,
+    description: Option<String>,
+    tags: Option<Vec<String>>,
+    privacy_status: Option<String>,
     thumbnail_path: Option<String>,
+    channel_id: Option<String>,
+    result_id: Option<String>,
 ) -> Result<YouTubeVideo, String> {
     info!("Starting YouTube video upload: {}", video_path);
 
@@ -246,36 +580,274 @@ pub async fn youtube_upload_video(
         }
         Some(thumb_path)
     } else {
-        None
+        result_id
+            .as_deref()
+            .and_then(|id| state.storage.load_auto_edit_result(id).ok())
+            .and_then(|result| result.upload_thumbnail_path)
+            .map(PathBuf::from)
+            .filter(|path| path.exists())
     };
 
-    // Parse privacy status
-    let privacy = match privacy_status.to_lowercase().as_str() {
-        "public" => PrivacyStatus::Public,
-        "unlisted" => PrivacyStatus::Unlisted,
-        "private" => PrivacyStatus::Private,
-        _ => return Err("Invalid privacy status. Must be: public, unlisted, or private".to_string()),
+    // Fill in what wasn't given from the result's upload draft
+    let draft = match result_id.as_deref() {
+        Some(id) => state
+            .storage
+            .load_upload_draft(id)
+            .map_err(|e| format!("Failed to load upload draft: {}", e))?,
+        None => None,
+    };
+    let mut upload = UploadDraft {
+        title: title
+            .or_else(|| draft.as_ref().map(|d| d.title.clone()))
+            .ok_or_else(|| "Video title is required".to_string())?,
+        description: description
+            .or_else(|| draft.as_ref().map(|d| d.description.clone()))
+            .unwrap_or_default(),
+        tags: tags
+            .or_else(|| draft.as_ref().map(|d| d.tags.clone()))
+            .unwrap_or_default(),
+        privacy_status: privacy_status
+            .or_else(|| draft.as_ref().map(|d| d.privacy_status.clone()))
+            .ok_or_else(|| "Privacy status is required".to_string())?,
+        updated_at: None,
     };
 
+    if let Some(id) = result_id.as_deref() {
+        match drafts::ResultSource::load(&state.storage, id) {
+            Ok(source) => upload = drafts::resolve(&upload, &source.placeholder_values()),
+            Err(e) => warn!("Failed to fill in metadata placeholders: {}", e),
+        }
+    }
+    if upload.title.trim().is_empty() {
+        return Err("Video title is required".to_string());
+    }
+
+    // Parse privacy status
+    let privacy = parse_privacy_status(&upload.privacy_status)?;
+
     // Create metadata
     let metadata = VideoMetadata {
-        title,
-        description,
-        tags,
+        title: upload.title,
+        description: upload.description,
+        tags: upload.tags,
         category_id: "20".to_string(), // Gaming category
         privacy_status: privacy,
         made_for_kids: false,
     };
 
+    state
+        .quota
+        .check(MeteredFeature::YoutubeUpload)
+        .map_err(|e| e.to_string())?;
+
     // Upload video
-    youtube
-        .upload_client
-        .upload_video(&video_path, metadata, thumbnail_path.as_deref())
-        .await
-        .map_err(|e| {
-            error!("Video upload failed: {}", e);
-            format!("Upload failed: {}", e)
+    let result = youtube
+        .upload_video(
+            channel_id.as_deref(),
+            &video_path,
+            metadata.clone(),
+            thumbnail_path.as_deref(),
+        )
+        .await;
+
+    state
+        .telemetry
+        .record(TelemetryEvent::UploadCompleted {
+            success: result.is_ok(),
         })
+        .await;
+
+    let video = match result {
+        Ok(video) => video,
+        Err(e) => {
+            error!("Video upload failed: {}", e);
+            let upload_error = UploadError::of(&e);
+
+            // Keep it in the history so the user can retry it later
+            let failure = FailedUpload::new(
+                &upload_error,
+                video_path.clone(),
+                metadata.clone(),
+                thumbnail_path.clone(),
+                channel_id.clone(),
+            );
+            if let Err(history_error) = youtube.record_failed_upload(failure).await {
+                error!("Failed to record failed upload: {}", history_error);
+            }
+
+            // Quota, auth and validation errors fail the same way on retry
+            if !upload_error.retryable {
+                return Err(format!("Upload failed: {}", upload_error));
+            }
+
+            // Retry in the background so a flaky connection doesn't lose the upload
+            let retry = JobKind::YoutubeUpload {
+                video_path,
+                metadata,
+                thumbnail_path,
+                channel_id,
+            };
+            return match state.jobs.enqueue(retry, JobPriority::Normal).await {
+                Ok(_) => Err(format!("Upload failed, queued for retry: {}", upload_error)),
+                Err(queue_error) => {
+                    error!("Failed to queue upload retry: {}", queue_error);
+                    Err(format!("Upload failed: {}", upload_error))
+                }
+            };
+        }
+    };
+
+    if let Err(e) = youtube.clear_failed_upload(&video_path).await {
+        warn!("Failed to clear failed upload from history: {}", e);
+    }
+    if let Some(id) = result_id.as_deref() {
+        if let Err(e) = state.storage.delete_upload_draft(id) {
+            warn!("Failed to delete upload draft: {}", e);
+        }
+    }
+
+    match state.quota.record(MeteredFeature::YoutubeUpload) {
+        Ok(status) => state.notification_center.quota_recorded(&status),
+        Err(e) => error!("Failed to record YouTube upload usage: {}", e),
+    }
+
+    state
+        .notifications
+        .notify_in_background(Notification::UploadCompleted {
+            video_id: video.id.clone(),
+            title: video.title.clone(),
+            thumbnail_url: video.thumbnail_url.clone(),
+        });
+
+    state.notification_center.push(
+        NotificationKind::UploadCompleted,
+        "Uploaded to YouTube",
+        video.title.clone(),
+    );
+
+    Ok(video)
+}
+
+/// Whether `entry` is the failed upload of `video_path`
+fn is_failure_of(entry: &UploadHistoryEntry, video_path: &Path) -> bool {
+    entry
+        .failure
+        .as_ref()
+        .is_some_and(|failed| failed.video_path == video_path)
+}
+
+/// Parse a privacy status (public, unlisted, private)
+fn parse_privacy_status(privacy_status: &str) -> Result<PrivacyStatus, String> {
+    match privacy_status.to_lowercase().as_str() {
+        "public" => Ok(PrivacyStatus::Public),
+        "unlisted" => Ok(PrivacyStatus::Unlisted),
+        "private" => Ok(PrivacyStatus::Private),
+        _ => Err("Invalid privacy status. Must be: public, unlisted, or private".to_string()),
+    }
+}
+
+/// Save the YouTube metadata prepared for an auto-edit result
+///
+/// Uploads of the result use the draft for metadata they aren't given.
+/// Text may contain placeholders (`{champion}`, `{kda}`, ... see
+/// [`drafts::PLACEHOLDERS`]) filled in from the result's games on upload.
+///
+/// # Arguments
+/// * `result_id` - Auto-edit result the draft is for
+/// * `metadata` - Title, description, tags and privacy status
+#[tauri::command]
+pub async fn save_upload_draft(
+    state: State<'_, AppState>,
+    result_id: String,
+    metadata: UploadDraft,
+) -> Result<UploadDraft, String> {
+    let result_id = security::validate_id(&result_id, 100).map_err(|e| e.to_string())?;
+    state
+        .storage
+        .load_auto_edit_result(&result_id)
+        .map_err(|e| e.to_string())?;
+    parse_privacy_status(&metadata.privacy_status)?;
+
+    let draft = UploadDraft {
+        updated_at: Some(chrono::Utc::now()),
+        ..metadata
+    };
+    state
+        .storage
+        .save_upload_draft(&result_id, &draft)
+        .map_err(|e| format!("Failed to save upload draft: {}", e))?;
+
+    Ok(draft)
+}
+
+/// Get the upload draft of an auto-edit result (`None` if there is none)
+#[tauri::command]
+pub async fn get_upload_draft(
+    state: State<'_, AppState>,
+    result_id: String,
+) -> Result<Option<UploadDraft>, String> {
+    let result_id = security::validate_id(&result_id, 100).map_err(|e| e.to_string())?;
+    state
+        .storage
+        .load_upload_draft(&result_id)
+        .map_err(|e| e.to_string())
+}
+
+/// Suggest titles and hashtags for uploading an auto-edit result
+///
+/// Made locally from the champion, biggest play, KDA and outcome of the
+/// result's games, in English and Korean.
+#[tauri::command]
+pub async fn suggest_video_metadata(
+    state: State<'_, AppState>,
+    result_id: String,
+) -> Result<Vec<MetadataSuggestion>, String> {
+    let result_id = security::validate_id(&result_id, 100).map_err(|e| e.to_string())?;
+    let source = drafts::ResultSource::load(&state.storage, &result_id)
+        .map_err(|e| format!("Failed to load auto-edit result: {}", e))?;
+
+    Ok(suggestions::suggest(&source))
+}
+
+/// Retry a failed upload from the upload history in the background
+///
+/// # Arguments
+/// * `video_path` - Video path of the failed history entry
+///
+/// Returns the ID of the queued upload job.
+#[tauri::command]
+pub async fn youtube_retry_upload(
+    state: State<'_, AppState>,
+    youtube: State<'_, YouTubeManager>,
+    video_path: String,
+) -> Result<String, String> {
+    let history = youtube.load_history().await;
+    let failure = history
+        .into_iter()
+        .filter_map(|entry| entry.failure)
+        .find(|failure| failure.video_path == Path::new(&video_path))
+        .ok_or_else(|| "No failed upload for this video".to_string())?;
+
+    if !failure.video_path.exists() {
+        return Err("Video file not found".to_string());
+    }
+
+    info!(
+        "Retrying {} upload of {:?}",
+        failure.kind, failure.video_path
+    );
+
+    let retry = JobKind::YoutubeUpload {
+        video_path: failure.video_path,
+        metadata: failure.metadata,
+        thumbnail_path: failure.thumbnail_path,
+        channel_id: failure.channel_id,
+    };
+    state
+        .jobs
+        .enqueue(retry, JobPriority::High)
+        .await
+        .map_err(|e| format!("Failed to queue upload retry: {}", e))
 }
 
 /// Get current upload progress
@@ -307,6 +879,30 @@ pub async fn youtube_get_video_details(
         })
 }
 
+/// Get view, like and comment counts for uploaded videos
+///
+/// # Arguments
+/// * `video_ids` - YouTube video IDs
+#[tauri::command]
+pub async fn youtube_get_video_stats(
+    youtube: State<'_, YouTubeManager>,
+    video_ids: Vec<String>,
+) -> Result<Vec<VideoStats>, String> {
+    // Validate video IDs
+    if video_ids.iter().any(|id| id.is_empty() || id.len() > 50) {
+        return Err("Invalid video ID".to_string());
+    }
+
+    youtube
+        .upload_client
+        .get_video_stats(&video_ids)
+        .await
+        .map_err(|e| {
+            error!("Failed to get video stats: {}", e);
+            format!("Failed to get video stats: {}", e)
+        })
+}
+
 /// Get upload history from storage
 #[tauri::command]
 pub async fn youtube_get_upload_history(
@@ -334,16 +930,12 @@ pub async fn youtube_add_to_history(
         privacy_status: video.privacy_status,
         thumbnail_url: video.thumbnail_url,
         view_count: video.view_count,
+        status: UploadHistoryStatus::Uploaded,
+        failure: None,
     };
 
     // Load existing history
-    let mut history: Vec<UploadHistoryEntry> = youtube
-        .storage
-        .get_setting("youtube_upload_history")
-        .await
-        .ok()
-        .and_then(|json| serde_json::from_str(&json).ok())
-        .unwrap_or_default();
+    let mut history = youtube.load_history().await;
 
     // Add new entry
     history.insert(0, entry);
@@ -352,14 +944,10 @@ pub async fn youtube_add_to_history(
     history.truncate(100);
 
     // Save updated history
-    let history_json = serde_json::to_string(&history).map_err(|e| e.to_string())?;
     youtube
-        .storage
-        .set_setting("youtube_upload_history", &history_json)
+        .save_history(&history)
         .await
-        .map_err(|e| e.to_string())?;
-
-    Ok(())
+        .map_err(|e| e.to_string())
 }
 
 /// Get YouTube API quota information
@@ -367,16 +955,8 @@ pub async fn youtube_add_to_history(
 pub async fn youtube_get_quota_info(
     youtube: State<'_, YouTubeManager>,
 ) -> Result<QuotaInfo, String> {
-    // Load used quota from storage (tracked locally)
-    let used: u64 = youtube
-        .storage
-        .get_setting("youtube_quota_used")
-        .await
-        .ok()
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(0);
-
-    Ok(QuotaInfo::new(used))
+    // Tracked locally; YouTube doesn't report usage
+    Ok(youtube.quota.info().await)
 }
 
 /// Log out from YouTube (clear credentials)
@@ -384,17 +964,11 @@ pub async fn youtube_get_quota_info(
 pub async fn youtube_logout(youtube: State<'_, YouTubeManager>) -> Result<(), String> {
     info!("Logging out from YouTube");
 
-    youtube.oauth_client.clear_credentials().await;
-
-    // Clear stored credentials
-    youtube
-        .storage
-        .remove_setting("youtube_credentials")
-        .await
-        .map_err(|e| {
-            error!("Failed to clear credentials: {}", e);
-            "Failed to clear credentials".to_string()
-        })?;
+    // Unlink the active channel; another linked channel takes over if any
+    youtube.unlink_active_channel().await.map_err(|e| {
+        error!("Failed to clear credentials: {}", e);
+        "Failed to clear credentials".to_string()
+    })?;
 
     info!("YouTube logout completed");
     Ok(())
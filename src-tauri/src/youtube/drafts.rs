@@ -0,0 +1,299 @@
+//! Placeholders in upload drafts
+//!
+//! Titles, descriptions and tags of an [`UploadDraft`] can refer to the games
+//! an auto-edit result was made from, e.g. `"{champion} {kda} | {result}"`.
+//! They're filled in when the upload starts; placeholders without a value
+//! (no KDA recorded) become empty, unknown ones are kept as written.
+
+use std::collections::HashMap;
+
+use crate::notifications::event_label;
+use crate::storage::models::{EventType, GameResult};
+use crate::storage::{AutoEditResultMetadata, ClipMetadata, GameMetadata, Storage, UploadDraft};
+
+/// Placeholders that can be used in upload drafts
+pub const PLACEHOLDERS: [&str; 12] = [
+    "champion",
+    "event",
+    "kda",
+    "kills",
+    "deaths",
+    "assists",
+    "result",
+    "game_mode",
+    "patch",
+    "date",
+    "duration",
+    "clips",
+];
+
+/// Placeholder values for an auto-edit result
+pub type PlaceholderValues = HashMap<&'static str, String>;
+
+/// An auto-edit result with the games (and their clips) it was made from
+pub struct ResultSource {
+    pub result: AutoEditResultMetadata,
+    pub games: Vec<GameMetadata>,
+    pub clips: Vec<ClipMetadata>,
+}
+
+impl ResultSource {
+    /// Load auto-edit result `result_id` and its games
+    ///
+    /// Games whose metadata can't be loaded anymore are left out.
+    pub fn load(storage: &Storage, result_id: &str) -> anyhow::Result<Self> {
+        let result = storage.load_auto_edit_result(result_id)?;
+        let games = result
+            .game_ids
+            .iter()
+            .filter_map(|game_id| storage.load_game_metadata(game_id).ok())
+            .collect();
+        let clips = result
+            .game_ids
+            .iter()
+            .filter_map(|game_id| storage.load_clip_metadata(game_id).ok())
+            .flatten()
+            .collect();
+
+        Ok(Self {
+            result,
+            games,
+            clips,
+        })
+    }
+
+    /// Champions played, without duplicates
+    pub fn champions(&self) -> Vec<&str> {
+        let mut champions: Vec<&str> = Vec::new();
+        for game in &self.games {
+            if !game.champion.is_empty() && !champions.contains(&game.champion.as_str()) {
+                champions.push(&game.champion);
+            }
+        }
+        champions
+    }
+
+    /// The biggest play of the games (highest clip priority)
+    pub fn top_event(&self) -> Option<&EventType> {
+        self.clips
+            .iter()
+            .max_by_key(|clip| clip.priority)
+            .map(|clip| &clip.event_type)
+    }
+
+    /// Outcome of the first game that recorded one
+    pub fn game_result(&self) -> Option<&GameResult> {
+        self.games.iter().find_map(|game| game.result.as_ref())
+    }
+
+    /// Patch of the first game that recorded one ("14.20")
+    pub fn patch(&self) -> Option<&str> {
+        self.games.iter().find_map(|game| game.patch.as_deref())
+    }
+
+    /// Values for the placeholders
+    ///
+    /// Results spanning several games list every champion and add up the KDA.
+    pub fn placeholder_values(&self) -> PlaceholderValues {
+        let mut values = PlaceholderValues::new();
+
+        values.insert("champion", self.champions().join(" / "));
+        if let Some(event_type) = self.top_event() {
+            values.insert("event", event_label(event_type));
+        }
+
+        let kdas: Vec<_> = self
+            .games
+            .iter()
+            .filter_map(|game| game.kda.as_ref())
+            .collect();
+        if !kdas.is_empty() {
+            let kills: u32 = kdas.iter().map(|kda| kda.kills).sum();
+            let deaths: u32 = kdas.iter().map(|kda| kda.deaths).sum();
+            let assists: u32 = kdas.iter().map(|kda| kda.assists).sum();
+            values.insert("kda", format!("{}/{}/{}", kills, deaths, assists));
+            values.insert("kills", kills.to_string());
+            values.insert("deaths", deaths.to_string());
+            values.insert("assists", assists.to_string());
+        }
+
+        if let Some(game_result) = self.game_result() {
+            let label = match game_result {
+                GameResult::Win => "Victory",
+                GameResult::Loss => "Defeat",
+                GameResult::Remake => "Remake",
+            };
+            values.insert("result", label.to_string());
+        }
+
+        if let Some(game) = self.games.first() {
+            values.insert("game_mode", game.game_mode.clone());
+        }
+        if let Some(patch) = self.patch() {
+            values.insert("patch", patch.to_string());
+        }
+        values.insert(
+            "date",
+            self.result
+                .created_at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d")
+                .to_string(),
+        );
+        values.insert("duration", format!("{:.0}s", self.result.duration));
+        values.insert("clips", self.result.clip_count.to_string());
+
+        values
+    }
+}
+
+/// `template` with its `{placeholder}`s replaced
+pub fn fill(template: &str, values: &PlaceholderValues) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        let placeholder = after
+            .find('}')
+            .map(|end| &after[..end])
+            .and_then(|name| PLACEHOLDERS.iter().find(|known| **known == name));
+
+        match placeholder {
+            Some(name) => {
+                filled.push_str(values.get(name).map(String::as_str).unwrap_or(""));
+                rest = &after[name.len() + 1..];
+            }
+            None => {
+                filled.push('{');
+                rest = after;
+            }
+        }
+    }
+
+    filled.push_str(rest);
+    filled
+}
+
+/// `draft` with its placeholders filled in
+///
+/// Whitespace left by empty placeholders is collapsed in the title, and tags
+/// that end up empty are dropped.
+pub fn resolve(draft: &UploadDraft, values: &PlaceholderValues) -> UploadDraft {
+    UploadDraft {
+        title: fill(&draft.title, values)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" "),
+        description: fill(&draft.description, values),
+        tags: draft
+            .tags
+            .iter()
+            .map(|tag| fill(tag, values).trim().to_string())
+            .filter(|tag| !tag.is_empty())
+            .collect(),
+        privacy_status: draft.privacy_status.clone(),
+        updated_at: draft.updated_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::models::KDA;
+    use chrono::Utc;
+
+    fn game(champion: &str, kda: Option<(u32, u32, u32)>) -> GameMetadata {
+        GameMetadata {
+            game_id: format!("game_{}", champion),
+            champion: champion.to_string(),
+            game_mode: "CLASSIC".to_string(),
+            start_time: Utc::now(),
+            end_time: None,
+            result: Some(GameResult::Win),
+            kda: kda.map(|(kills, deaths, assists)| KDA {
+                kills,
+                deaths,
+                assists,
+            }),
+            patch: Some("14.20".to_string()),
+        }
+    }
+
+    fn clip(event_type: EventType) -> ClipMetadata {
+        ClipMetadata {
+            file_path: "clip.mp4".to_string(),
+            thumbnail_path: None,
+            priority: event_type.default_priority(),
+            event_type,
+            event_time: 600.0,
+            duration: 12.0,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn source(games: Vec<GameMetadata>, clips: Vec<ClipMetadata>) -> ResultSource {
+        ResultSource {
+            result: result(),
+            games,
+            clips,
+        }
+    }
+
+    fn result() -> AutoEditResultMetadata {
+        AutoEditResultMetadata {
+            result_id: "result_1".to_string(),
+            job_id: "job_1".to_string(),
+            output_path: "short.mp4".to_string(),
+            thumbnail_path: None,
+            upload_thumbnail_path: None,
+            created_at: Utc::now(),
+            duration: 59.6,
+            clip_count: 4,
+            game_ids: vec!["game_Yasuo".to_string()],
+            target_duration: 60,
+            canvas_template_name: None,
+            has_background_music: false,
+            youtube_status: None,
+            file_size_bytes: 0,
+        }
+    }
+
+    #[test]
+    fn test_fill_placeholders() {
+        let games = vec![
+            game("Yasuo", Some((12, 3, 7))),
+            game("Yone", Some((5, 1, 2))),
+        ];
+        let clips = vec![clip(EventType::ChampionKill), clip(EventType::Multikill(4))];
+        let values = source(games, clips).placeholder_values();
+
+        assert_eq!(
+            fill("{champion} {kda} | {result} in {duration}", &values),
+            "Yasuo / Yone 17/4/9 | Victory in 60s"
+        );
+        assert_eq!(fill("{event}!", &values), "Quadra Kill!");
+        assert_eq!(fill("Patch {patch}", &values), "Patch 14.20");
+        // Unknown placeholders and stray braces stay as written
+        assert_eq!(fill("{rank} {clips} clips {", &values), "{rank} 4 clips {");
+    }
+
+    #[test]
+    fn test_resolve_draft_without_kda() {
+        let values = source(vec![game("Yasuo", None)], Vec::new()).placeholder_values();
+        let draft = UploadDraft {
+            title: "{champion} {kda} outplay".to_string(),
+            description: "{clips} clips".to_string(),
+            tags: vec!["{champion}".to_string(), "{kills}".to_string()],
+            privacy_status: "unlisted".to_string(),
+            updated_at: None,
+        };
+
+        let resolved = resolve(&draft, &values);
+        assert_eq!(resolved.title, "Yasuo outplay");
+        assert_eq!(resolved.description, "4 clips");
+        assert_eq!(resolved.tags, vec!["Yasuo".to_string()]);
+    }
+}
@@ -0,0 +1,179 @@
+//! What went wrong with a YouTube upload
+//!
+//! Upload failures are sorted into a few kinds so the app knows what to do
+//! with them: network hiccups and 5xx responses are retried with backoff,
+//! an expired token is refreshed and retried, while quota, auth and
+//! validation errors need the user (or the next quota day) before another
+//! attempt can succeed.
+
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UploadErrorKind {
+    /// Token revoked or refresh failed; the channel needs to be reconnected
+    Auth,
+    /// Daily API quota or the channel's upload limit is used up
+    Quota,
+    /// Connection problems, timeouts, rate limiting and YouTube server errors
+    Network,
+    /// Rejected request or unreadable video (title too long, bad file, ...)
+    Validation,
+}
+
+impl fmt::Display for UploadErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            UploadErrorKind::Auth => "authentication",
+            UploadErrorKind::Quota => "quota",
+            UploadErrorKind::Network => "network",
+            UploadErrorKind::Validation => "validation",
+        };
+        f.write_str(label)
+    }
+}
+
+/// A classified upload failure
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{kind} error: {message}")]
+pub struct UploadError {
+    pub kind: UploadErrorKind,
+    pub message: String,
+    /// Another attempt may succeed without the user doing anything
+    pub retryable: bool,
+}
+
+/// `errors[].reason` values YouTube reports for used up quota
+const QUOTA_REASONS: [&str; 4] = [
+    "quotaExceeded",
+    "dailyLimitExceeded",
+    "uploadLimitExceeded",
+    "rateLimitExceeded",
+];
+
+impl UploadError {
+    pub fn new(kind: UploadErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            retryable: kind == UploadErrorKind::Network,
+        }
+    }
+
+    /// Classify an error response of the YouTube Data API
+    pub fn from_response(status: StatusCode, body: &str) -> Self {
+        let reasons = error_reasons(body);
+        let is_quota = reasons
+            .iter()
+            .any(|reason| QUOTA_REASONS.contains(&reason.as_str()));
+
+        let kind = match status {
+            StatusCode::UNAUTHORIZED => UploadErrorKind::Auth,
+            StatusCode::FORBIDDEN if is_quota => UploadErrorKind::Quota,
+            StatusCode::FORBIDDEN => UploadErrorKind::Auth,
+            StatusCode::REQUEST_TIMEOUT | StatusCode::TOO_MANY_REQUESTS => UploadErrorKind::Network,
+            status if status.is_server_error() => UploadErrorKind::Network,
+            _ => UploadErrorKind::Validation,
+        };
+
+        let message = error_message(body).unwrap_or_else(|| format!("YouTube returned {}", status));
+        Self::new(kind, message)
+    }
+
+    /// Classify a request that never got a response
+    pub fn from_request(error: &reqwest::Error) -> Self {
+        if error.is_builder() {
+            Self::new(UploadErrorKind::Validation, error.to_string())
+        } else {
+            Self::new(UploadErrorKind::Network, error.to_string())
+        }
+    }
+
+    /// The classified failure behind `error`, if it's an upload error
+    ///
+    /// Anything else (missing file, failed lookup after the upload) counts
+    /// as a non-retryable validation error.
+    pub fn of(error: &anyhow::Error) -> Self {
+        error
+            .chain()
+            .find_map(|cause| cause.downcast_ref::<UploadError>())
+            .cloned()
+            .unwrap_or_else(|| Self::new(UploadErrorKind::Validation, format!("{:#}", error)))
+    }
+}
+
+/// `error.errors[].reason` of an API error body
+fn error_reasons(body: &str) -> Vec<String> {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|json| json["error"]["errors"].as_array().cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|error| error["reason"].as_str().map(str::to_string))
+        .collect()
+}
+
+/// `error.message` of an API error body
+fn error_message(body: &str) -> Option<String> {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()?
+        .pointer("/error/message")?
+        .as_str()
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_error(code: u16, reason: &str) -> String {
+        serde_json::json!({
+            "error": {
+                "code": code,
+                "message": format!("{} happened", reason),
+                "errors": [{ "reason": reason, "domain": "youtube.quota" }]
+            }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_classify_responses() {
+        let quota =
+            UploadError::from_response(StatusCode::FORBIDDEN, &api_error(403, "quotaExceeded"));
+        assert_eq!(quota.kind, UploadErrorKind::Quota);
+        assert_eq!(quota.message, "quotaExceeded happened");
+        assert!(!quota.retryable);
+
+        let forbidden = UploadError::from_response(
+            StatusCode::FORBIDDEN,
+            &api_error(403, "insufficientPermissions"),
+        );
+        assert_eq!(forbidden.kind, UploadErrorKind::Auth);
+
+        let expired = UploadError::from_response(StatusCode::UNAUTHORIZED, "");
+        assert_eq!(expired.kind, UploadErrorKind::Auth);
+        assert_eq!(expired.message, "YouTube returned 401 Unauthorized");
+
+        let server = UploadError::from_response(StatusCode::SERVICE_UNAVAILABLE, "");
+        assert_eq!(server.kind, UploadErrorKind::Network);
+        assert!(server.retryable);
+
+        let invalid =
+            UploadError::from_response(StatusCode::BAD_REQUEST, &api_error(400, "invalidTitle"));
+        assert_eq!(invalid.kind, UploadErrorKind::Validation);
+        assert!(!invalid.retryable);
+    }
+
+    #[test]
+    fn test_of_finds_wrapped_errors() {
+        let error = anyhow::Error::new(UploadError::new(UploadErrorKind::Quota, "used up"))
+            .context("Upload failed");
+        assert_eq!(UploadError::of(&error).kind, UploadErrorKind::Quota);
+
+        let other = anyhow::anyhow!("Video file not found");
+        assert_eq!(UploadError::of(&other).kind, UploadErrorKind::Validation);
+    }
+}
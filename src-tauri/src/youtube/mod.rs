@@ -1,14 +1,25 @@
 pub mod callback_server;
+pub mod channels;
 pub mod commands;
+pub mod drafts;
+pub mod errors;
 pub mod models;
 pub mod oauth;
+pub mod quota;
+pub mod suggestions;
 pub mod upload;
 
 // Re-export commonly used types for convenience
 pub use callback_server::CallbackServer;
+pub use channels::{ChannelRegistry, LinkedChannel};
 pub use commands::YouTubeManager;
-pub use models::{AuthStatus, QuotaInfo, UploadHistoryEntry};
+pub use errors::{UploadError, UploadErrorKind};
+pub use models::{
+    AuthStatus, ChannelSummary, FailedUpload, QuotaInfo, UploadHistoryEntry, VideoStats,
+    YouTubeChannel,
+};
 pub use oauth::{YouTubeCredentials, YouTubeOAuthClient};
+pub use quota::{ApiCall, QuotaTracker};
 pub use upload::{
     PrivacyStatus, UploadProgress, UploadStatus, VideoMetadata, YouTubeUploadClient, YouTubeVideo,
 };
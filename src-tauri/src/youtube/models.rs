@@ -1,4 +1,8 @@
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use super::errors::{UploadError, UploadErrorKind};
+use super::upload::VideoMetadata;
 
 /// YouTube authentication status
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -6,17 +10,106 @@ pub struct AuthStatus {
     pub authenticated: bool,
     pub expires_at: Option<i64>,
     pub has_refresh_token: bool,
+    /// Channel that uploads currently go to
+    pub active_channel: Option<YouTubeChannel>,
+}
+
+/// YouTube channel linked to the app
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YouTubeChannel {
+    pub id: String,
+    pub title: String,
+    pub thumbnail_url: Option<String>,
+}
+
+/// Linked channel as listed in the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelSummary {
+    #[serde(flatten)]
+    pub channel: YouTubeChannel,
+    pub active: bool,
 }
 
 /// Upload history entry
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UploadHistoryEntry {
-    pub video_id: String,
+    pub video_id: String, // Empty for failed uploads
     pub title: String,
-    pub uploaded_at: i64, // Unix timestamp
+    pub uploaded_at: i64, // Unix timestamp (of the failure for failed uploads)
     pub privacy_status: String,
     pub thumbnail_url: Option<String>,
     pub view_count: Option<u64>,
+    #[serde(default)]
+    pub status: UploadHistoryStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure: Option<FailedUpload>,
+}
+
+impl UploadHistoryEntry {
+    /// Entry for an upload that gave up, keeping what's needed to retry it
+    pub fn failed(failure: FailedUpload) -> Self {
+        Self {
+            video_id: String::new(),
+            title: failure.metadata.title.clone(),
+            uploaded_at: chrono::Utc::now().timestamp(),
+            privacy_status: format!("{:?}", failure.metadata.privacy_status).to_lowercase(),
+            thumbnail_url: None,
+            view_count: None,
+            status: UploadHistoryStatus::Failed,
+            failure: Some(failure),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UploadHistoryStatus {
+    #[default]
+    Uploaded,
+    /// Kept until a retry succeeds
+    Failed,
+}
+
+/// Why an upload failed and what it was, for retrying it later
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailedUpload {
+    pub kind: UploadErrorKind,
+    pub error: String,
+    /// Retried in the background; otherwise the user has to retry it
+    pub retryable: bool,
+    pub video_path: PathBuf,
+    pub metadata: VideoMetadata,
+    pub thumbnail_path: Option<PathBuf>,
+    pub channel_id: Option<String>,
+}
+
+impl FailedUpload {
+    pub fn new(
+        error: &UploadError,
+        video_path: PathBuf,
+        metadata: VideoMetadata,
+        thumbnail_path: Option<PathBuf>,
+        channel_id: Option<String>,
+    ) -> Self {
+        Self {
+            kind: error.kind,
+            error: error.message.clone(),
+            retryable: error.retryable,
+            video_path,
+            metadata,
+            thumbnail_path,
+            channel_id,
+        }
+    }
+}
+
+/// Public statistics of an uploaded video
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VideoStats {
+    pub video_id: String,
+    pub view_count: u64,
+    pub like_count: Option<u64>, // Hidden when the owner disables likes
+    pub comment_count: Option<u64>, // Hidden when comments are disabled
 }
 
 /// YouTube quota information
@@ -0,0 +1,234 @@
+//! Local accounting of the YouTube Data API quota
+//!
+//! The API grants a fixed number of units per day, reset at midnight Pacific
+//! time, and every call costs units (an upload far more than anything else).
+//! YouTube doesn't report how much is used, so each call that reaches the API
+//! is counted here and saved with the Pacific date it belongs to; the count
+//! starts over once that date has passed, also across restarts.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, OnceLock};
+use tokio::sync::Mutex;
+use tracing::{debug, warn};
+
+use super::models::QuotaInfo;
+use crate::notifications::center::{NotificationCenter, NotificationKind};
+use crate::storage::Storage;
+
+/// Setting the day's usage is saved under
+const USAGE_SETTING: &str = "youtube_quota_usage";
+
+/// Uploads left below which the user is warned
+const LOW_UPLOADS_REMAINING: u64 = 2;
+
+/// API calls made by the app and what they cost
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiCall {
+    /// `videos.insert`
+    VideoInsert,
+    /// `thumbnails.set`
+    ThumbnailSet,
+    /// `videos.list` (details and statistics)
+    VideoList,
+    /// `channels.list`
+    ChannelList,
+}
+
+impl ApiCall {
+    /// Quota units charged per call
+    pub fn cost(self) -> u64 {
+        match self {
+            ApiCall::VideoInsert => QuotaInfo::UPLOAD_COST,
+            ApiCall::ThumbnailSet => 50,
+            ApiCall::VideoList | ApiCall::ChannelList => 1,
+        }
+    }
+}
+
+/// Units used on one quota day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct DailyUsage {
+    /// Date in Pacific time
+    day: NaiveDate,
+    used: u64,
+}
+
+impl DailyUsage {
+    /// Units used on `today` (nothing if the saved day is over)
+    fn used_on(&self, today: NaiveDate) -> u64 {
+        if self.day == today {
+            self.used
+        } else {
+            0
+        }
+    }
+
+    /// Add `cost` units on `today`, starting over on a new day
+    fn add(&mut self, cost: u64, today: NaiveDate) {
+        self.used = self.used_on(today) + cost;
+        self.day = today;
+    }
+}
+
+/// Quota day `now` falls on
+fn quota_day(now: DateTime<Utc>) -> NaiveDate {
+    now.with_timezone(&chrono_tz::US::Pacific).date_naive()
+}
+
+/// Counts API calls against the daily quota
+pub struct QuotaTracker {
+    storage: Arc<Storage>,
+    /// Loaded from storage on first use
+    usage: Mutex<Option<DailyUsage>>,
+    notifications: OnceLock<Arc<NotificationCenter>>,
+}
+
+impl QuotaTracker {
+    pub fn new(storage: Arc<Storage>) -> Self {
+        Self {
+            storage,
+            usage: Mutex::new(None),
+            notifications: OnceLock::new(),
+        }
+    }
+
+    /// Warn in the notification center when the quota runs low
+    pub fn set_notifications(&self, notifications: Arc<NotificationCenter>) {
+        let _ = self.notifications.set(notifications);
+    }
+
+    /// Quota used today
+    pub async fn info(&self) -> QuotaInfo {
+        let mut usage = self.usage.lock().await;
+        let usage = self.loaded(&mut usage).await;
+        QuotaInfo::new(usage.used_on(quota_day(Utc::now())))
+    }
+
+    /// Count a call that reached the API
+    pub async fn record(&self, call: ApiCall) {
+        let today = quota_day(Utc::now());
+
+        let (before, after) = {
+            let mut usage = self.usage.lock().await;
+            let usage = self.loaded(&mut usage).await;
+
+            let before = QuotaInfo::new(usage.used_on(today));
+            usage.add(call.cost(), today);
+            let after = QuotaInfo::new(usage.used);
+
+            match serde_json::to_string(usage) {
+                Ok(json) => {
+                    if let Err(e) = self.storage.set_setting(USAGE_SETTING, &json).await {
+                        warn!("Failed to save YouTube quota usage: {}", e);
+                    }
+                }
+                Err(e) => warn!("Failed to serialize YouTube quota usage: {}", e),
+            }
+            (before, after)
+        };
+
+        debug!(
+            "YouTube quota: {:?} cost {}, {} of {} used today",
+            call,
+            call.cost(),
+            after.used,
+            after.daily_limit
+        );
+
+        if let (Some((title, message)), Some(notifications)) =
+            (low_quota_warning(&before, &after), self.notifications.get())
+        {
+            notifications.push(NotificationKind::QuotaLow, title, message);
+        }
+    }
+
+    /// The saved usage, loading it on first use
+    async fn loaded<'a>(&self, usage: &'a mut Option<DailyUsage>) -> &'a mut DailyUsage {
+        let loaded = match usage.take() {
+            Some(loaded) => loaded,
+            None => self
+                .storage
+                .get_setting(USAGE_SETTING)
+                .await
+                .ok()
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or(DailyUsage {
+                    day: quota_day(Utc::now()),
+                    used: 0,
+                }),
+        };
+        usage.insert(loaded)
+    }
+}
+
+/// Title and message when a call just left fewer than two uploads for today
+fn low_quota_warning(before: &QuotaInfo, after: &QuotaInfo) -> Option<(String, String)> {
+    if before.uploads_remaining() < LOW_UPLOADS_REMAINING
+        || after.uploads_remaining() >= LOW_UPLOADS_REMAINING
+    {
+        return None;
+    }
+
+    let remaining = after.uploads_remaining();
+    let message = match remaining {
+        0 => {
+            "No YouTube uploads left today. The quota resets at midnight Pacific time.".to_string()
+        }
+        n => format!(
+            "{} YouTube upload{} left today. The quota resets at midnight Pacific time.",
+            n,
+            if n == 1 { "" } else { "s" }
+        ),
+    };
+    Some(("YouTube quota almost used up".to_string(), message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_resets_on_new_day() {
+        let monday = NaiveDate::from_ymd_opt(2024, 3, 4).unwrap();
+        let tuesday = monday.succ_opt().unwrap();
+
+        let mut usage = DailyUsage {
+            day: monday,
+            used: 0,
+        };
+        usage.add(ApiCall::VideoInsert.cost(), monday);
+        usage.add(ApiCall::ThumbnailSet.cost(), monday);
+        assert_eq!(usage.used_on(monday), 1_650);
+        assert_eq!(usage.used_on(tuesday), 0);
+
+        usage.add(ApiCall::VideoList.cost(), tuesday);
+        assert_eq!(
+            usage,
+            DailyUsage {
+                day: tuesday,
+                used: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_quota_day_is_pacific() {
+        // 07:00 UTC is still the previous evening in California
+        let now = "2024-03-05T07:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(quota_day(now), NaiveDate::from_ymd_opt(2024, 3, 4).unwrap());
+    }
+
+    #[test]
+    fn test_low_quota_warning_once() {
+        // 2 uploads left -> 1 left: warn
+        let warning = low_quota_warning(&QuotaInfo::new(6_500), &QuotaInfo::new(8_100));
+        assert!(warning.unwrap().1.starts_with("1 YouTube upload left"));
+
+        // Already below the mark
+        assert!(low_quota_warning(&QuotaInfo::new(8_100), &QuotaInfo::new(8_101)).is_none());
+
+        // Still enough left
+        assert!(low_quota_warning(&QuotaInfo::new(0), &QuotaInfo::new(1_600)).is_none());
+    }
+}
@@ -0,0 +1,305 @@
+//! Title and hashtag suggestions for uploads
+//!
+//! Generated locally from the games of an auto-edit result with a few title
+//! templates per language. Templates use the upload draft placeholders and
+//! are skipped when one of them has no value (a KDA title for a game without
+//! KDA), so every suggestion reads as a finished title.
+
+use serde::{Deserialize, Serialize};
+
+use super::drafts::{self, PlaceholderValues, ResultSource, PLACEHOLDERS};
+use crate::notifications::event_label;
+use crate::storage::models::{EventType, GameResult};
+
+/// YouTube cuts titles off after this many characters
+const MAX_TITLE_CHARS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    En,
+    Ko,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::En, Language::Ko];
+
+    /// Title templates, most specific first
+    fn title_templates(self) -> &'static [&'static str] {
+        match self {
+            Language::En => &[
+                "{champion} {event} | {kda}",
+                "This {champion} {event} is insane",
+                "{event} on {champion} ({result})",
+                "{champion} {kda} {result}",
+                "{champion} {event} | Patch {patch}",
+                "{champion} highlights",
+            ],
+            Language::Ko => &[
+                "{champion} {event} | {kda}",
+                "미쳐버린 {champion} {event}",
+                "{champion} 장인의 {event} ({result})",
+                "{champion} {kda} {result}",
+                "{patch} 패치 {champion} {event}",
+                "{champion} 하이라이트",
+            ],
+        }
+    }
+
+    /// Hashtags every upload gets
+    fn base_hashtags(self) -> &'static [&'static str] {
+        match self {
+            Language::En => &["#shorts", "#LeagueOfLegends", "#lol"],
+            Language::Ko => &["#shorts", "#롤", "#리그오브레전드"],
+        }
+    }
+
+    fn event_label(self, event_type: &EventType) -> String {
+        match self {
+            Language::En => event_label(event_type),
+            Language::Ko => match event_type {
+                EventType::ChampionKill => "킬".to_string(),
+                EventType::Multikill(2) => "더블킬".to_string(),
+                EventType::Multikill(3) => "트리플킬".to_string(),
+                EventType::Multikill(4) => "쿼드라킬".to_string(),
+                EventType::Multikill(5) => "펜타킬".to_string(),
+                EventType::Multikill(n) => format!("{}연속 킬", n),
+                EventType::TurretKill => "포탑 파괴".to_string(),
+                EventType::InhibitorKill => "억제기 파괴".to_string(),
+                EventType::DragonKill => "드래곤".to_string(),
+                EventType::BaronKill => "바론".to_string(),
+                EventType::Ace => "에이스".to_string(),
+                EventType::FirstBlood => "퍼스트 블러드".to_string(),
+                EventType::Death => "데스".to_string(),
+                EventType::Custom(name) => name.clone(),
+            },
+        }
+    }
+
+    /// Hashtag for a patch ("#Patch1420", "#1420패치")
+    fn patch_hashtag(self, patch: &str) -> Option<String> {
+        match self {
+            Language::En => hashtag(&format!("Patch{}", patch)),
+            Language::Ko => hashtag(&format!("{}패치", patch)),
+        }
+    }
+
+    fn result_label(self, result: &GameResult) -> &'static str {
+        match (self, result) {
+            (Language::En, GameResult::Win) => "Victory",
+            (Language::En, GameResult::Loss) => "Defeat",
+            (Language::En, GameResult::Remake) => "Remake",
+            (Language::Ko, GameResult::Win) => "승리",
+            (Language::Ko, GameResult::Loss) => "패배",
+            (Language::Ko, GameResult::Remake) => "다시하기",
+        }
+    }
+}
+
+/// Suggested titles and hashtags in one language
+#[derive(Debug, Clone, Serialize)]
+pub struct MetadataSuggestion {
+    pub language: Language,
+    pub titles: Vec<String>,
+    /// With the leading `#`
+    pub hashtags: Vec<String>,
+}
+
+/// Suggestions in every language for `source`
+pub fn suggest(source: &ResultSource) -> Vec<MetadataSuggestion> {
+    let values = source.placeholder_values();
+    let champions = source.champions();
+
+    Language::ALL
+        .into_iter()
+        .map(|language| {
+            suggest_in(
+                language,
+                values.clone(),
+                &champions,
+                source.top_event(),
+                source.game_result(),
+            )
+        })
+        .collect()
+}
+
+fn suggest_in(
+    language: Language,
+    mut values: PlaceholderValues,
+    champions: &[&str],
+    event: Option<&EventType>,
+    result: Option<&GameResult>,
+) -> MetadataSuggestion {
+    if let Some(event_type) = event {
+        values.insert("event", language.event_label(event_type));
+    }
+    if let Some(result) = result {
+        values.insert("result", language.result_label(result).to_string());
+    }
+
+    let mut titles: Vec<String> = Vec::new();
+    for template in language.title_templates() {
+        if !has_values(template, &values) {
+            continue;
+        }
+        let title = drafts::fill(template, &values)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        if title.chars().count() <= MAX_TITLE_CHARS && !titles.contains(&title) {
+            titles.push(title);
+        }
+    }
+
+    let mut hashtags: Vec<String> = language
+        .base_hashtags()
+        .iter()
+        .map(|tag| tag.to_string())
+        .collect();
+    let event_tag = event
+        .filter(|event_type| is_hashtag_worthy(event_type))
+        .map(|event_type| language.event_label(event_type));
+    let patch_tag = values
+        .get("patch")
+        .and_then(|patch| language.patch_hashtag(patch));
+    let tags = champions
+        .iter()
+        .filter_map(|champion| hashtag(champion))
+        .chain(event_tag.as_deref().and_then(hashtag))
+        .chain(patch_tag);
+    for tag in tags {
+        if !hashtags.contains(&tag) {
+            hashtags.push(tag);
+        }
+    }
+
+    MetadataSuggestion {
+        language,
+        titles,
+        hashtags,
+    }
+}
+
+/// Whether every placeholder in `template` has a value
+fn has_values(template: &str, values: &PlaceholderValues) -> bool {
+    PLACEHOLDERS
+        .iter()
+        .filter(|name| template.contains(&format!("{{{}}}", name)))
+        .all(|name| values.get(name).is_some_and(|value| !value.is_empty()))
+}
+
+/// Plays people search for (not every kill or turret)
+fn is_hashtag_worthy(event_type: &EventType) -> bool {
+    matches!(
+        event_type,
+        EventType::Multikill(_)
+            | EventType::BaronKill
+            | EventType::DragonKill
+            | EventType::Ace
+            | EventType::FirstBlood
+    )
+}
+
+/// `text` as a hashtag: letters and digits only ("Kai'Sa" -> "#KaiSa")
+fn hashtag(text: &str) -> Option<String> {
+    let tag: String = text.chars().filter(|c| c.is_alphanumeric()).collect();
+    if tag.is_empty() {
+        None
+    } else {
+        Some(format!("#{}", tag))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(kda: Option<&str>) -> PlaceholderValues {
+        let mut values = PlaceholderValues::new();
+        values.insert("champion", "Kai'Sa".to_string());
+        values.insert("event", "Penta Kill".to_string());
+        if let Some(kda) = kda {
+            values.insert("kda", kda.to_string());
+        }
+        values
+    }
+
+    fn values_on_patch(patch: &str) -> PlaceholderValues {
+        let mut values = values(None);
+        values.insert("patch", patch.to_string());
+        values
+    }
+
+    #[test]
+    fn test_suggest_english() {
+        let suggestion = suggest_in(
+            Language::En,
+            values(Some("14/2/6")),
+            &["Kai'Sa"],
+            Some(&EventType::Multikill(5)),
+            Some(&GameResult::Win),
+        );
+
+        assert_eq!(suggestion.titles[0], "Kai'Sa Penta Kill | 14/2/6");
+        assert!(suggestion
+            .titles
+            .contains(&"Penta Kill on Kai'Sa (Victory)".to_string()));
+        assert_eq!(
+            suggestion.hashtags,
+            vec![
+                "#shorts",
+                "#LeagueOfLegends",
+                "#lol",
+                "#KaiSa",
+                "#PentaKill"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_suggest_korean_without_kda() {
+        let suggestion = suggest_in(
+            Language::Ko,
+            values(None),
+            &["Kai'Sa"],
+            Some(&EventType::Multikill(5)),
+            None,
+        );
+
+        // Templates needing the KDA or the result are left out
+        assert_eq!(
+            suggestion.titles,
+            vec!["미쳐버린 Kai'Sa 펜타킬", "Kai'Sa 하이라이트"]
+        );
+        assert!(suggestion.hashtags.contains(&"#펜타킬".to_string()));
+        assert!(suggestion.hashtags.contains(&"#롤".to_string()));
+    }
+
+    #[test]
+    fn test_suggest_patch() {
+        let english = suggest_in(
+            Language::En,
+            values_on_patch("14.20"),
+            &["Kai'Sa"],
+            Some(&EventType::Multikill(5)),
+            None,
+        );
+        assert!(english
+            .titles
+            .contains(&"Kai'Sa Penta Kill | Patch 14.20".to_string()));
+        assert_eq!(english.hashtags.last().unwrap(), "#Patch1420");
+
+        let korean = suggest_in(
+            Language::Ko,
+            values_on_patch("14.20"),
+            &["Kai'Sa"],
+            Some(&EventType::Multikill(5)),
+            None,
+        );
+        assert!(korean
+            .titles
+            .contains(&"14.20 패치 Kai'Sa 펜타킬".to_string()));
+        assert_eq!(korean.hashtags.last().unwrap(), "#1420패치");
+    }
+}
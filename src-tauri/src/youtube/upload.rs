@@ -1,18 +1,32 @@
 use anyhow::{Context, Result};
-use reqwest::{multipart, Client};
+use reqwest::{multipart, Client, Response, StatusCode};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::fs::File;
 use tokio::io::AsyncReadExt;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
 
+use super::errors::{UploadError, UploadErrorKind};
+use super::models::{VideoStats, YouTubeChannel};
 use super::oauth::YouTubeOAuthClient;
+use super::quota::{ApiCall, QuotaTracker};
+use crate::utils::retry::{self, retry_with_condition, RetryOperation};
 
 /// YouTube Data API v3 base URL
 const YOUTUBE_API_BASE: &str = "https://www.googleapis.com/youtube/v3";
 
+/// Maximum video IDs per Videos API request
+const MAX_IDS_PER_REQUEST: usize = 50;
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Timeout of API requests other than the video upload, which is paced by
+/// the upload queue and only times out per chunk
+const API_TIMEOUT: Duration = Duration::from_secs(60);
+
 /// Video metadata for YouTube upload
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VideoMetadata {
@@ -72,13 +86,14 @@ pub struct YouTubeUploadClient {
     oauth_client: Arc<YouTubeOAuthClient>,
     http_client: Client,
     progress: Arc<RwLock<Option<UploadProgress>>>,
+    quota: Arc<QuotaTracker>,
 }
 
 impl YouTubeUploadClient {
-    /// Create new YouTube upload client
-    pub fn new(oauth_client: Arc<YouTubeOAuthClient>) -> Self {
+    /// Create new YouTube upload client, counting its API calls on `quota`
+    pub fn new(oauth_client: Arc<YouTubeOAuthClient>, quota: Arc<QuotaTracker>) -> Self {
         let http_client = Client::builder()
-            .timeout(std::time::Duration::from_secs(600)) // 10 minutes for upload
+            .connect_timeout(CONNECT_TIMEOUT)
             .build()
             .expect("Failed to create HTTP client");
 
@@ -86,11 +101,16 @@ impl YouTubeUploadClient {
             oauth_client,
             http_client,
             progress: Arc::new(RwLock::new(None)),
+            quota,
         }
     }
 
     /// Upload video to YouTube
     ///
+    /// Network and server errors are retried with backoff, and an expired
+    /// token is refreshed before the next attempt. Failures carry an
+    /// [`UploadError`] saying what went wrong.
+    ///
     /// # Arguments
     /// * `video_path` - Path to video file
     /// * `metadata` - Video metadata (title, description, tags, etc.)
@@ -114,12 +134,26 @@ impl YouTubeUploadClient {
         })
         .await;
 
-        // Get valid access token
-        let access_token = self
-            .oauth_client
-            .get_valid_token()
-            .await
-            .context("Failed to get valid access token")?;
+        // Don't send a video the API will reject for quota anyway
+        let quota = self.quota.info().await;
+        if !quota.can_upload() {
+            return Err(UploadError::new(
+                UploadErrorKind::Quota,
+                format!(
+                    "Daily YouTube API quota used up ({} of {} units)",
+                    quota.used, quota.daily_limit
+                ),
+            )
+            .into());
+        }
+
+        // Fail early without credentials, before reading the whole video
+        self.oauth_client.get_valid_token().await.map_err(|e| {
+            UploadError::new(
+                UploadErrorKind::Auth,
+                format!("Failed to get valid access token: {:#}", e),
+            )
+        })?;
 
         // Read video file
         let mut file = File::open(video_path)
@@ -138,6 +172,10 @@ impl YouTubeUploadClient {
 
         debug!("Video file size: {} bytes", file_size);
 
+        crate::uploads::queue()
+            .check_bandwidth(file_size)
+            .map_err(|message| UploadError::new(UploadErrorKind::Validation, message))?;
+
         // Update progress to uploading
         self.update_progress(UploadProgress {
             bytes_uploaded: 0,
@@ -164,53 +202,56 @@ impl YouTubeUploadClient {
             }
         });
 
-        // Create multipart form
-        let part_metadata = multipart::Part::text(video_resource.to_string())
-            .mime_str("application/json")
-            .context("Failed to create metadata part")?;
-
-        let part_video = multipart::Part::bytes(video_data)
-            .mime_str("video/*")
-            .context("Failed to create video part")?;
-
-        let form = multipart::Form::new()
-            .part("snippet", part_metadata)
-            .part("media", part_video);
-
-        // Upload video
+        // Upload video (the multipart form is rebuilt for each attempt)
         let upload_url = format!(
             "{}/videos?uploadType=multipart&part=snippet,status",
             YOUTUBE_API_BASE
         );
+        let video_data = bytes::Bytes::from(video_data);
+        let last_error = std::sync::Mutex::new(None::<UploadError>);
+
+        let response = retry_with_condition(
+            retry::configured(RetryOperation::Upload),
+            "YouTube upload",
+            |e: &UploadError| e.retryable,
+            || {
+                let metadata = video_resource.to_string();
+                let video_data = video_data.clone();
+                let (upload_url, last_error) = (&upload_url, &last_error);
+
+                async move {
+                    let result = self.send_upload(upload_url, metadata, video_data).await;
+                    if let Err(e) = &result {
+                        *last_error.lock().unwrap_or_else(|e| e.into_inner()) = Some(e.clone());
+                    }
+                    result
+                }
+            },
+        )
+        .await;
 
-        let response = self
-            .http_client
-            .post(&upload_url)
-            .bearer_auth(&access_token)
-            .multipart(form)
-            .send()
-            .await
-            .context("Failed to send upload request")?;
-
-        if !response.status().is_success() {
-            let error_text = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "Unknown error".to_string());
-            error!("Upload failed: {}", error_text);
-
-            self.update_progress(UploadProgress {
-                bytes_uploaded: 0,
-                total_bytes: file_size,
-                percentage: 0.0,
-                status: UploadStatus::Failed,
-                video_id: None,
-                error: Some(error_text.clone()),
-            })
-            .await;
-
-            return Err(anyhow::anyhow!("YouTube upload failed: {}", error_text));
-        }
+        let response = match response {
+            Ok(response) => response,
+            Err(e) => {
+                let upload_error = last_error
+                    .into_inner()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .unwrap_or_else(|| UploadError::new(UploadErrorKind::Network, e.to_string()));
+                error!("Upload failed: {}", upload_error);
+
+                self.update_progress(UploadProgress {
+                    bytes_uploaded: 0,
+                    total_bytes: file_size,
+                    percentage: 0.0,
+                    status: UploadStatus::Failed,
+                    video_id: None,
+                    error: Some(upload_error.to_string()),
+                })
+                .await;
+
+                return Err(upload_error.into());
+            }
+        };
 
         let upload_response: serde_json::Value = response
             .json()
@@ -259,6 +300,66 @@ impl YouTubeUploadClient {
         Ok(video)
     }
 
+    /// One attempt at the multipart upload request
+    ///
+    /// A 401 refreshes the access token and is retryable if that worked.
+    async fn send_upload(
+        &self,
+        upload_url: &str,
+        metadata: String,
+        video_data: bytes::Bytes,
+    ) -> std::result::Result<Response, UploadError> {
+        let access_token = self.oauth_client.get_valid_token().await.map_err(|e| {
+            UploadError::new(
+                UploadErrorKind::Auth,
+                format!("Failed to get valid access token: {:#}", e),
+            )
+        })?;
+
+        let video_len = video_data.len() as u64;
+        let part_metadata = multipart::Part::text(metadata)
+            .mime_str("application/json")
+            .map_err(|e| UploadError::from_request(&e))?;
+        // Sent at the upload queue's pace (bandwidth cap, pause)
+        let video_body = reqwest::Body::wrap_stream(crate::uploads::throttled_body(video_data));
+        let part_video = multipart::Part::stream_with_length(video_body, video_len)
+            .mime_str("video/*")
+            .map_err(|e| UploadError::from_request(&e))?;
+        let form = multipart::Form::new()
+            .part("snippet", part_metadata)
+            .part("media", part_video);
+
+        let response = self
+            .http_client
+            .post(upload_url)
+            .bearer_auth(&access_token)
+            .multipart(form)
+            .send()
+            .await
+            .map_err(|e| UploadError::from_request(&e))?;
+        self.quota.record(ApiCall::VideoInsert).await;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        let mut error = UploadError::from_response(status, &body);
+
+        // Expired or revoked token: the next attempt uses a fresh one
+        if status == StatusCode::UNAUTHORIZED {
+            match self.oauth_client.refresh_token().await {
+                Ok(_) => error.retryable = true,
+                Err(e) => {
+                    error.message = format!("{} (token refresh failed: {:#})", error.message, e)
+                }
+            }
+        }
+
+        Err(error)
+    }
+
     /// Upload custom thumbnail for video
     async fn upload_thumbnail(&self, video_id: &str, thumbnail_path: &Path) -> Result<()> {
         info!(
@@ -279,11 +380,13 @@ impl YouTubeUploadClient {
         let response = self
             .http_client
             .post(&thumbnail_url)
+            .timeout(API_TIMEOUT)
             .bearer_auth(&access_token)
             .header("Content-Type", "image/jpeg")
             .body(thumbnail_data)
             .send()
             .await?;
+        self.quota.record(ApiCall::ThumbnailSet).await;
 
         if !response.status().is_success() {
             let error_text = response.text().await?;
@@ -306,9 +409,11 @@ impl YouTubeUploadClient {
         let response = self
             .http_client
             .get(&url)
+            .timeout(API_TIMEOUT)
             .bearer_auth(&access_token)
             .send()
             .await?;
+        self.quota.record(ApiCall::VideoList).await;
 
         if !response.status().is_success() {
             let error_text = response.text().await?;
@@ -356,6 +461,88 @@ impl YouTubeUploadClient {
         })
     }
 
+    /// Get view, like and comment counts for videos
+    ///
+    /// Videos that don't exist or aren't visible to the account are omitted.
+    pub async fn get_video_stats(&self, video_ids: &[String]) -> Result<Vec<VideoStats>> {
+        let mut stats = Vec::with_capacity(video_ids.len());
+
+        for chunk in video_ids.chunks(MAX_IDS_PER_REQUEST) {
+            let access_token = self.oauth_client.get_valid_token().await?;
+
+            let url = format!(
+                "{}/videos?part=statistics&id={}",
+                YOUTUBE_API_BASE,
+                chunk.join(",")
+            );
+
+            let response = self
+                .http_client
+                .get(&url)
+                .timeout(API_TIMEOUT)
+                .bearer_auth(&access_token)
+                .send()
+                .await?;
+            self.quota.record(ApiCall::VideoList).await;
+
+            if !response.status().is_success() {
+                let error_text = response.text().await?;
+                return Err(anyhow::anyhow!(
+                    "Failed to get video statistics: {}",
+                    error_text
+                ));
+            }
+
+            let data: serde_json::Value = response.json().await?;
+            let items = data["items"].as_array().cloned().unwrap_or_default();
+            stats.extend(items.iter().filter_map(parse_video_stats));
+        }
+
+        debug!("Fetched statistics for {} video(s)", stats.len());
+        Ok(stats)
+    }
+
+    /// Get the channel owned by the authenticated account
+    pub async fn get_my_channel(&self) -> Result<YouTubeChannel> {
+        let access_token = self.oauth_client.get_valid_token().await?;
+
+        let url = format!("{}/channels?part=snippet&mine=true", YOUTUBE_API_BASE);
+
+        let response = self
+            .http_client
+            .get(&url)
+            .timeout(API_TIMEOUT)
+            .bearer_auth(&access_token)
+            .send()
+            .await?;
+        self.quota.record(ApiCall::ChannelList).await;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(anyhow::anyhow!("Failed to get channel: {}", error_text));
+        }
+
+        let data: serde_json::Value = response.json().await?;
+        let channel = data["items"]
+            .as_array()
+            .and_then(|items| items.first())
+            .context("No YouTube channel found for this account")?;
+
+        Ok(YouTubeChannel {
+            id: channel["id"]
+                .as_str()
+                .context("Channel response missing id")?
+                .to_string(),
+            title: channel["snippet"]["title"]
+                .as_str()
+                .unwrap_or("")
+                .to_string(),
+            thumbnail_url: channel["snippet"]["thumbnails"]["default"]["url"]
+                .as_str()
+                .map(|s| s.to_string()),
+        })
+    }
+
     /// Get current upload progress
     pub async fn get_progress(&self) -> Option<UploadProgress> {
         self.progress.read().await.clone()
@@ -374,6 +561,22 @@ impl YouTubeUploadClient {
     }
 }
 
+/// Parse one item of a Videos API `statistics` response (counts are strings)
+fn parse_video_stats(item: &serde_json::Value) -> Option<VideoStats> {
+    let count = |field: &str| {
+        item["statistics"][field]
+            .as_str()
+            .and_then(|s| s.parse::<u64>().ok())
+    };
+
+    Some(VideoStats {
+        video_id: item["id"].as_str()?.to_string(),
+        view_count: count("viewCount").unwrap_or(0),
+        like_count: count("likeCount"),
+        comment_count: count("commentCount"),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -408,6 +611,26 @@ mod tests {
         assert_eq!(progress.status, UploadStatus::Uploading);
     }
 
+    #[test]
+    fn test_parse_video_stats() {
+        let item = serde_json::json!({
+            "id": "abc123",
+            "statistics": {
+                "viewCount": "1500",
+                "likeCount": "42",
+                "favoriteCount": "0"
+            }
+        });
+
+        let stats = parse_video_stats(&item).unwrap();
+        assert_eq!(stats.video_id, "abc123");
+        assert_eq!(stats.view_count, 1500);
+        assert_eq!(stats.like_count, Some(42));
+        assert_eq!(stats.comment_count, None);
+
+        assert!(parse_video_stats(&serde_json::json!({ "statistics": {} })).is_none());
+    }
+
     #[test]
     fn test_privacy_status_serialization() {
         let json = serde_json::to_string(&PrivacyStatus::Public).unwrap();
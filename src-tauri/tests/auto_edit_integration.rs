@@ -39,6 +39,7 @@ fn create_test_clip(
         file_path: file_path.to_string(),
         thumbnail_path: None,
         duration: Some(duration),
+        score: None,
     }
 }
 
@@ -47,10 +48,7 @@ fn create_basic_config() -> AutoEditConfig {
     AutoEditConfig {
         target_duration: 60,
         game_ids: vec!["test_game_1".to_string()],
-        selected_clip_ids: None,
-        canvas_template: None,
-        background_music: None,
-        audio_levels: AudioLevels::default(),
+        ..Default::default()
     }
 }
 
@@ -71,6 +69,7 @@ fn create_test_canvas_template() -> CanvasTemplate {
                 color: "#FFD700".to_string(),
                 outline: Some("#000000".to_string()),
                 position: Position { x: 50.0, y: 10.0 },
+                animation: None,
             },
             CanvasElement::Image {
                 id: "logo".to_string(),
@@ -78,8 +77,10 @@ fn create_test_canvas_template() -> CanvasTemplate {
                 width: 100,
                 height: 100,
                 position: Position { x: 50.0, y: 90.0 },
+                animation: None,
             },
         ],
+        color_lut: None,
     }
 }
 
@@ -96,10 +97,7 @@ async fn test_target_duration_validation() {
         let config = AutoEditConfig {
             target_duration: duration,
             game_ids: vec!["game1".to_string()],
-            selected_clip_ids: None,
-            canvas_template: None,
-            background_music: None,
-            audio_levels: AudioLevels::default(),
+            ..Default::default()
         };
 
         assert!(config.target_duration == 60 || config.target_duration == 120 || config.target_duration == 180);
@@ -138,6 +136,7 @@ async fn test_audio_levels_custom() {
     let levels = AudioLevels {
         game_audio: 70,
         background_music: 30,
+        ducking: false,
     };
 
     // Custom levels should be respected
@@ -170,10 +169,7 @@ async fn test_clip_selection_by_priority() {
     let config = AutoEditConfig {
         target_duration: 60,
         game_ids: vec!["game1".to_string()],
-        selected_clip_ids: None,
-        canvas_template: None,
-        background_music: None,
-        audio_levels: AudioLevels::default(),
+        ..Default::default()
     };
 
     let selected = composer.select_clips(&clips, &config).await.unwrap();
@@ -207,10 +203,7 @@ async fn test_clip_selection_fits_duration() {
     let config = AutoEditConfig {
         target_duration: 60,
         game_ids: vec!["game1".to_string()],
-        selected_clip_ids: None,
-        canvas_template: None,
-        background_music: None,
-        audio_levels: AudioLevels::default(),
+        ..Default::default()
     };
 
     let selected = composer.select_clips(&clips, &config).await.unwrap();
@@ -240,9 +233,7 @@ async fn test_manual_clip_selection() {
         target_duration: 60,
         game_ids: vec!["game1".to_string()],
         selected_clip_ids: Some(vec![1, 3]),
-        canvas_template: None,
-        background_music: None,
-        audio_levels: AudioLevels::default(),
+        ..Default::default()
     };
 
     let selected = composer.select_clips(&clips, &config).await.unwrap();
@@ -281,10 +272,7 @@ async fn test_clip_selection_single_long_clip() {
     let config = AutoEditConfig {
         target_duration: 60,
         game_ids: vec!["game1".to_string()],
-        selected_clip_ids: None,
-        canvas_template: None,
-        background_music: None,
-        audio_levels: AudioLevels::default(),
+        ..Default::default()
     };
 
     let selected = composer.select_clips(&clips, &config).await.unwrap();
@@ -329,6 +317,7 @@ async fn test_canvas_element_text() {
             color,
             outline,
             position,
+            ..
         } => {
             assert_eq!(id, "title");
             assert_eq!(content, "PENTAKILL!");
@@ -355,6 +344,7 @@ async fn test_canvas_element_image() {
             width,
             height,
             position,
+            ..
         } => {
             assert_eq!(id, "logo");
             assert_eq!(path, "/test/logo.png");
@@ -415,6 +405,8 @@ async fn test_background_music_config() {
     let music = BackgroundMusic {
         file_path: "/test/music.mp3".to_string(),
         loop_music: true,
+        surprise_me: false,
+        mood: None,
     };
 
     assert!(!music.file_path.is_empty());
@@ -427,16 +419,19 @@ async fn test_audio_mixing_config() {
     let config = AutoEditConfig {
         target_duration: 60,
         game_ids: vec!["game1".to_string()],
-        selected_clip_ids: None,
-        canvas_template: None,
         background_music: Some(BackgroundMusic {
             file_path: "/test/music.mp3".to_string(),
             loop_music: true,
+            surprise_me: false,
+            mood: None,
         }),
         audio_levels: AudioLevels {
             game_audio: 70,
             background_music: 30,
+            ducking: true,
         },
+        loudness_target: Some(-14.0),
+        ..Default::default()
     };
 
     // Validate music configuration
@@ -493,11 +488,10 @@ async fn test_progress_tracking_initialization() {
 async fn test_config_validation_no_games() {
     let config = AutoEditConfig {
         target_duration: 60,
-        game_ids: vec![], // Empty game list
+        game_ids: vec![],
+        // Empty game list
         selected_clip_ids: None,
-        canvas_template: None,
-        background_music: None,
-        audio_levels: AudioLevels::default(),
+        ..Default::default()
     };
 
     // Should have at least one game
@@ -519,10 +513,10 @@ async fn test_invalid_manual_selection() {
     let config = AutoEditConfig {
         target_duration: 60,
         game_ids: vec!["game1".to_string()],
-        selected_clip_ids: Some(vec![99]), // Non-existent clip ID
+        selected_clip_ids: Some(vec![99]),
+        // Non-existent clip ID
         canvas_template: None,
-        background_music: None,
-        audio_levels: AudioLevels::default(),
+        ..Default::default()
     };
 
     let result = composer.select_clips(&clips, &config).await;
@@ -586,10 +580,7 @@ async fn test_multiple_concurrent_compositions() {
             let config = AutoEditConfig {
                 target_duration: 60,
                 game_ids: vec![format!("game_{}", i)],
-                selected_clip_ids: None,
-                canvas_template: None,
-                background_music: None,
-                audio_levels: AudioLevels::default(),
+                ..Default::default()
             };
 
             assert!(!config.game_ids.is_empty());
@@ -670,6 +661,7 @@ async fn test_audio_levels_serialization() {
     let levels = AudioLevels {
         game_audio: 70,
         background_music: 30,
+        ducking: false,
     };
 
     // Should serialize to JSON
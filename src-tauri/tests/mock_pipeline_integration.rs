@@ -0,0 +1,118 @@
+// Integration test for the clip pipeline without screen capture
+// MockRecorder → AutoClipManager → Storage → AutoComposer
+
+use lolshorts::recording::auto_clip_manager::AutoClipManager;
+use lolshorts::recording::live_client::{ClipFactors, EventTrigger};
+use lolshorts::recording::mock::MockRecorder;
+use lolshorts::recording::{GameEvent, Recorder};
+use lolshorts::settings::models::RecordingSettings;
+use lolshorts::storage::Storage;
+use lolshorts::video::auto_composer::{AutoComposer, AutoEditConfig};
+use lolshorts::video::VideoProcessor;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::RwLock;
+
+const GAME_ID: &str = "mock_game";
+
+/// Render a short test pattern with FFmpeg (None if FFmpeg is missing)
+fn render_sample_clip(dir: &Path) -> Option<PathBuf> {
+    let path = dir.join("sample.mp4");
+    let status = std::process::Command::new("ffmpeg")
+        .args([
+            "-f",
+            "lavfi",
+            "-i",
+            "testsrc=duration=5:size=1280x720:rate=30",
+            "-f",
+            "lavfi",
+            "-i",
+            "sine=frequency=440:duration=5",
+            "-c:v",
+            "libx264",
+            "-c:a",
+            "aac",
+            "-shortest",
+            "-y",
+        ])
+        .arg(&path)
+        .output()
+        .ok()?
+        .status;
+
+    status.success().then_some(path)
+}
+
+fn kill_event(event_id: u64, event_time: f64) -> GameEvent {
+    GameEvent {
+        event_id,
+        event_name: "Multikill".to_string(),
+        event_time,
+        killer_name: Some("Me".to_string()),
+        victim_name: Some("Enemy".to_string()),
+        assisters: vec![],
+        priority: 3,
+        timestamp: Instant::now(),
+    }
+}
+
+#[tokio::test]
+async fn test_mock_recording_to_auto_edit() {
+    let temp_dir = std::env::temp_dir().join("lolshorts_mock_pipeline_test");
+    let _ = std::fs::remove_dir_all(&temp_dir);
+    std::fs::create_dir_all(&temp_dir).unwrap();
+
+    let Some(sample) = render_sample_clip(&temp_dir) else {
+        println!("FFmpeg not available, skipping mock pipeline test");
+        return;
+    };
+
+    let storage = Arc::new(Storage::new(&temp_dir).unwrap());
+    let recorder = Arc::new(
+        MockRecorder::new(temp_dir.join("recordings"), temp_dir.join("clips"))
+            .with_sample_clip(sample),
+    );
+    recorder.start_replay_buffer().await.unwrap();
+
+    let mut settings = RecordingSettings::default();
+    settings.clip_timing.merge_consecutive_events = false;
+    let manager = AutoClipManager::new(
+        recorder.clone(),
+        Arc::clone(&storage),
+        Arc::new(RwLock::new(settings)),
+    );
+    manager.set_current_game(Some(GAME_ID.to_string())).await;
+
+    for (i, event_time) in [300.0, 600.0, 900.0].into_iter().enumerate() {
+        manager
+            .process_event(
+                EventTrigger::Multikill(3),
+                kill_event(i as u64 + 1, event_time),
+                ClipFactors::default(),
+            )
+            .await
+            .unwrap();
+    }
+    recorder.stop_replay_buffer().await.unwrap();
+
+    let clips = storage.load_clip_metadata(GAME_ID).unwrap();
+    assert_eq!(clips.len(), 3);
+
+    let composer = AutoComposer::new(Arc::new(VideoProcessor::new()), Arc::clone(&storage))
+        .with_output_dir(temp_dir.join("auto_edits"));
+    let config = AutoEditConfig {
+        target_duration: 60,
+        game_ids: vec![GAME_ID.to_string()],
+        ..Default::default()
+    };
+
+    let result = composer
+        .compose(config, "mock_pipeline".to_string())
+        .await
+        .unwrap();
+    assert!(result.clip_count > 0);
+    assert!(Path::new(&result.output_path).exists());
+
+    let _ = std::fs::remove_dir_all(&temp_dir);
+}
@@ -19,6 +19,7 @@ import {
 import { Play, Trash2, Edit, Download, Search, Filter } from 'lucide-react';
 import { invoke } from '@tauri-apps/api/core';
 import { toast } from '@/components/ui/use-toast';
+import { errorMessage } from '@/types/error';
 
 interface Clip {
   id: number;
@@ -70,7 +71,7 @@ export function ClipLibrary() {
       console.error('Failed to load games:', error);
       toast({
         title: 'Failed to Load Games',
-        description: String(error),
+        description: errorMessage(error),
         variant: 'destructive',
       });
     } finally {
@@ -90,7 +91,7 @@ export function ClipLibrary() {
       console.error('Failed to load clips:', error);
       toast({
         title: 'Failed to Load Clips',
-        description: String(error),
+        description: errorMessage(error),
         variant: 'destructive',
       });
       // Set empty array on error
@@ -123,7 +124,7 @@ export function ClipLibrary() {
     } catch (error) {
       toast({
         title: 'Failed to Delete',
-        description: String(error),
+        description: errorMessage(error),
         variant: 'destructive',
       });
     }
@@ -13,6 +13,7 @@ import { Label } from "@/components/ui/label";
 import { Alert, AlertDescription } from "@/components/ui/alert";
 import { Crown, Check, AlertCircle } from "lucide-react";
 import { open } from "@tauri-apps/plugin-shell";
+import { errorMessage } from "@/types/error";
 
 interface PaymentModalProps {
   isOpen: boolean;
@@ -48,7 +49,7 @@ export function PaymentModal({ isOpen, onClose }: PaymentModalProps) {
       onClose();
     } catch (err) {
       console.error("Failed to create subscription:", err);
-      setError(err as string);
+      setError(errorMessage(err));
     } finally {
       setIsProcessing(false);
     }
@@ -19,6 +19,7 @@ import { Label } from '@/components/ui/label';
 import { invoke } from '@tauri-apps/api/core';
 import { Play, Square, Save, Settings } from 'lucide-react';
 import { toast } from '@/components/ui/use-toast';
+import { errorMessage } from '@/types/error';
 
 interface RecordingSettings {
   audio_enabled: boolean;
@@ -50,7 +51,7 @@ export function RecordingControls() {
     } catch (error) {
       toast({
         title: 'Failed to Start',
-        description: String(error),
+        description: errorMessage(error),
         variant: 'destructive',
       });
     } finally {
@@ -70,7 +71,7 @@ export function RecordingControls() {
     } catch (error) {
       toast({
         title: 'Failed to Stop',
-        description: String(error),
+        description: errorMessage(error),
         variant: 'destructive',
       });
     } finally {
@@ -91,7 +92,7 @@ export function RecordingControls() {
     } catch (error) {
       toast({
         title: 'Failed to Save Replay',
-        description: String(error),
+        description: errorMessage(error),
         variant: 'destructive',
       });
     } finally {
@@ -110,7 +111,7 @@ export function RecordingControls() {
     } catch (error) {
       toast({
         title: 'Failed to Save Settings',
-        description: String(error),
+        description: errorMessage(error),
         variant: 'destructive',
       });
     } finally {
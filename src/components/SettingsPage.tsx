@@ -23,6 +23,7 @@ import { Badge } from '@/components/ui/badge';
 import { Settings, Video, Mic, Zap, Info, Save, RotateCcw } from 'lucide-react';
 import { invoke } from '@tauri-apps/api/core';
 import { toast } from '@/components/ui/use-toast';
+import { errorMessage } from '@/types/error';
 
 interface RecordingSettings {
   video_quality: 'low' | 'medium' | 'high' | 'ultra';
@@ -98,7 +99,7 @@ export function SettingsPage() {
     } catch (error) {
       toast({
         title: 'Failed to Save',
-        description: String(error),
+        description: errorMessage(error),
         variant: 'destructive',
       });
     } finally {
@@ -119,7 +120,7 @@ export function SettingsPage() {
     } catch (error) {
       toast({
         title: 'Failed to Reset',
-        description: String(error),
+        description: errorMessage(error),
         variant: 'destructive',
       });
     } finally {
@@ -22,6 +22,7 @@ import {
   DollarSign,
   Loader2
 } from "lucide-react";
+import { errorMessage } from "@/types/error";
 
 interface SubscriptionManagementProps {
   isOpen: boolean;
@@ -69,7 +70,7 @@ export function SubscriptionManagement({
       setSubscription(details);
     } catch (err) {
       console.error("Failed to load subscription:", err);
-      setError(err as string);
+      setError(errorMessage(err));
     } finally {
       setIsLoading(false);
     }
@@ -91,7 +92,7 @@ export function SubscriptionManagement({
       alert(t('settings.account.cancelSuccess'));
     } catch (err) {
       console.error("Failed to cancel subscription:", err);
-      setError(err as string);
+      setError(errorMessage(err));
     } finally {
       setIsCancelling(false);
     }
@@ -8,12 +8,26 @@ import {
   AutoEditResult,
   VideoError,
 } from '@/types/autoEdit';
+import { errorMessage, isAppError } from '@/types/error';
 import { useAutoEditStore } from '@/stores/autoEditStore';
 
 /**
- * Parse backend error string into structured VideoError
+ * Structured VideoError of a rejected command
+ *
+ * Video commands reject with an `AppError`; plain string errors are parsed
+ * for the error type and suggestions.
  */
-function parseVideoError(errorString: string): VideoError {
+function parseVideoError(err: unknown): VideoError {
+  if (isAppError(err)) {
+    return {
+      message: err.message,
+      error_type: err.category,
+      recovery_suggestions: err.suggestions,
+      technical_details: `Error ${err.code}`,
+    };
+  }
+
+  const errorString = String(err);
   // Try to extract error type and recovery suggestions from the error message
   const lines = errorString.split('\n').map(line => line.trim()).filter(Boolean);
 
@@ -111,10 +125,8 @@ export function useAutoEdit() {
 
       return result;
     } catch (err) {
-      const errorMsg = err as string;
-      const parsedError = parseVideoError(errorMsg);
-      setError(errorMsg);
-      setStoreError(parsedError);
+      setError(errorMessage(err));
+      setStoreError(parseVideoError(err));
       throw err;
     } finally {
       setIsLoading(false);
@@ -187,8 +199,7 @@ export function useAutoEdit() {
     try {
       await invoke('save_canvas_template', { template });
     } catch (err) {
-      const errorMsg = err as string;
-      setError(errorMsg);
+      setError(errorMessage(err));
       throw err;
     } finally {
       setIsLoading(false);
@@ -210,8 +221,7 @@ export function useAutoEdit() {
       });
       return template;
     } catch (err) {
-      const errorMsg = err as string;
-      setError(errorMsg);
+      setError(errorMessage(err));
       throw err;
     } finally {
       setIsLoading(false);
@@ -229,8 +239,7 @@ export function useAutoEdit() {
       const templates = await invoke<CanvasTemplateInfo[]>('list_canvas_templates');
       return templates;
     } catch (err) {
-      const errorMsg = err as string;
-      setError(errorMsg);
+      setError(errorMessage(err));
       throw err;
     } finally {
       setIsLoading(false);
@@ -249,8 +258,7 @@ export function useAutoEdit() {
     try {
       await invoke('delete_canvas_template', { templateId });
     } catch (err) {
-      const errorMsg = err as string;
-      setError(errorMsg);
+      setError(errorMessage(err));
       throw err;
     } finally {
       setIsLoading(false);
@@ -1,6 +1,7 @@
 import { invoke } from '@tauri-apps/api/core';
 import { useState, useCallback } from 'react';
 import { AutoEditResultMetadata, YouTubeUploadStatus } from '@/types/autoEdit';
+import { errorMessage } from '@/types/error';
 
 export function useAutoEditResults() {
   const [loading, setLoading] = useState(false);
@@ -13,7 +14,7 @@ export function useAutoEditResults() {
       const results = await invoke<AutoEditResultMetadata[]>('get_auto_edit_results');
       return results;
     } catch (err) {
-      const errorMsg = err instanceof Error ? err.message : String(err);
+      const errorMsg = errorMessage(err);
       setError(errorMsg);
       throw err;
     } finally {
@@ -28,7 +29,7 @@ export function useAutoEditResults() {
       const result = await invoke<AutoEditResultMetadata>('get_auto_edit_result', { resultId });
       return result;
     } catch (err) {
-      const errorMsg = err instanceof Error ? err.message : String(err);
+      const errorMsg = errorMessage(err);
       setError(errorMsg);
       throw err;
     } finally {
@@ -42,7 +43,7 @@ export function useAutoEditResults() {
     try {
       await invoke('delete_auto_edit_result', { resultId, deleteFile });
     } catch (err) {
-      const errorMsg = err instanceof Error ? err.message : String(err);
+      const errorMsg = errorMessage(err);
       setError(errorMsg);
       throw err;
     } finally {
@@ -57,7 +58,7 @@ export function useAutoEditResults() {
       try {
         await invoke('update_auto_edit_youtube_status', { resultId, status });
       } catch (err) {
-        const errorMsg = err instanceof Error ? err.message : String(err);
+        const errorMsg = errorMessage(err);
         setError(errorMsg);
         throw err;
       } finally {
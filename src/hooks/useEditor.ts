@@ -3,6 +3,7 @@ import { listen } from '@tauri-apps/api/event';
 import { useState, useCallback, useEffect } from 'react';
 import { ClipMetadata } from './useStorage';
 import { useEditorStore, CompositionSettings, TimelineClip } from '@/stores/editorStore';
+import { errorMessage } from '@/types/error';
 
 export interface ClipInput {
   file_path: string;
@@ -79,7 +80,7 @@ export function useEditor() {
       setAvailableClips(clips);
       return clips;
     } catch (err) {
-      const errorMsg = err as string;
+      const errorMsg = errorMessage(err);
       setError(errorMsg);
       throw err;
     } finally {
@@ -106,6 +107,26 @@ export function useEditor() {
     }
   }, []);
 
+  /**
+   * Save the frame at a timestamp as a full-resolution PNG
+   */
+  const captureFrame = useCallback(async (
+    clipPath: string,
+    timestamp: number,
+    output: string
+  ): Promise<string> => {
+    try {
+      return await invoke<string>('capture_frame', {
+        clipPath,
+        timestamp,
+        output,
+      });
+    } catch (err) {
+      console.error('Failed to capture frame:', err);
+      throw err;
+    }
+  }, []);
+
   /**
    * Compose multiple clips into a single Short video
    */
@@ -141,7 +162,7 @@ export function useEditor() {
       setExportOutputPath(result);
       return result;
     } catch (err) {
-      const errorMsg = err as string;
+      const errorMsg = errorMessage(err);
       setError(errorMsg);
       setExportStatus('error');
       setExportError(errorMsg);
@@ -172,7 +193,7 @@ export function useEditor() {
       });
       return clipPath;
     } catch (err) {
-      const errorMsg = err as string;
+      const errorMsg = errorMessage(err);
       setError(errorMsg);
       throw err;
     } finally {
@@ -185,6 +206,7 @@ export function useEditor() {
     error,
     loadGameClips,
     generateThumbnail,
+    captureFrame,
     composeShorts,
     extractClip,
   };
@@ -1,11 +1,13 @@
 import { invoke } from '@tauri-apps/api/core';
 import { useState, useCallback } from 'react';
+import { errorMessage } from '@/types/error';
 
 export interface GameMetadata {
   game_id: string;
   summoner_name: string;
   champion: string;
   game_mode: string;
+  patch?: string | null; // "14.20"
   game_start_time: string;
   game_duration: number;
   result: string;
@@ -53,7 +55,7 @@ export function useStorage() {
       const games = await invoke<string[]>('list_games');
       return games;
     } catch (err) {
-      const errorMsg = err instanceof Error ? err.message : String(err);
+      const errorMsg = errorMessage(err);
       setError(errorMsg);
       throw err;
     } finally {
@@ -68,7 +70,7 @@ export function useStorage() {
       const metadata = await invoke<GameMetadata>('get_game_metadata', { gameId });
       return metadata;
     } catch (err) {
-      const errorMsg = err instanceof Error ? err.message : String(err);
+      const errorMsg = errorMessage(err);
       setError(errorMsg);
       throw err;
     } finally {
@@ -86,7 +88,7 @@ export function useStorage() {
       );
       return games;
     } catch (err) {
-      const errorMsg = err instanceof Error ? err.message : String(err);
+      const errorMsg = errorMessage(err);
       setError(errorMsg);
       throw err;
     } finally {
@@ -100,7 +102,7 @@ export function useStorage() {
     try {
       await invoke('save_game_metadata', { gameId, metadata });
     } catch (err) {
-      const errorMsg = err instanceof Error ? err.message : String(err);
+      const errorMsg = errorMessage(err);
       setError(errorMsg);
       throw err;
     } finally {
@@ -115,7 +117,7 @@ export function useStorage() {
       const events = await invoke<EventData[]>('get_game_events', { gameId });
       return events;
     } catch (err) {
-      const errorMsg = err instanceof Error ? err.message : String(err);
+      const errorMsg = errorMessage(err);
       setError(errorMsg);
       throw err;
     } finally {
@@ -129,7 +131,7 @@ export function useStorage() {
     try {
       await invoke('save_game_events', { gameId, events });
     } catch (err) {
-      const errorMsg = err instanceof Error ? err.message : String(err);
+      const errorMsg = errorMessage(err);
       setError(errorMsg);
       throw err;
     } finally {
@@ -143,7 +145,7 @@ export function useStorage() {
     try {
       await invoke('save_clip_metadata', { gameId, clip });
     } catch (err) {
-      const errorMsg = err instanceof Error ? err.message : String(err);
+      const errorMsg = errorMessage(err);
       setError(errorMsg);
       throw err;
     } finally {
@@ -157,7 +159,7 @@ export function useStorage() {
     try {
       await invoke('delete_game', { gameId });
     } catch (err) {
-      const errorMsg = err instanceof Error ? err.message : String(err);
+      const errorMsg = errorMessage(err);
       setError(errorMsg);
       throw err;
     } finally {
@@ -172,7 +174,7 @@ export function useStorage() {
       const stats = await invoke<StorageStats>('get_storage_stats');
       return stats;
     } catch (err) {
-      const errorMsg = err instanceof Error ? err.message : String(err);
+      const errorMsg = errorMessage(err);
       setError(errorMsg);
       throw err;
     } finally {
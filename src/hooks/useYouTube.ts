@@ -5,6 +5,8 @@ import {
   VideoMetadata,
   UploadProgress,
   UploadHistoryEntry,
+  UploadDraft,
+  MetadataSuggestion,
   QuotaInfo,
   AuthStatus,
 } from '@/types/youtube';
@@ -267,6 +269,79 @@ export function useYouTube() {
     []
   );
 
+  /**
+   * Save the upload metadata prepared for an auto-edit result
+   */
+  const saveUploadDraft = useCallback(
+    async (resultId: string, metadata: UploadDraft): Promise<UploadDraft> => {
+      setError(null);
+
+      try {
+        return await invoke<UploadDraft>('save_upload_draft', {
+          resultId,
+          metadata,
+        });
+      } catch (err) {
+        const errorMsg = err as string;
+        setError(errorMsg);
+        throw err;
+      }
+    },
+    []
+  );
+
+  /**
+   * Get the upload draft of an auto-edit result
+   */
+  const getUploadDraft = useCallback(
+    async (resultId: string): Promise<UploadDraft | null> => {
+      try {
+        return await invoke<UploadDraft | null>('get_upload_draft', {
+          resultId,
+        });
+      } catch (err) {
+        console.error('Failed to load upload draft:', err);
+        return null;
+      }
+    },
+    []
+  );
+
+  /**
+   * Suggest titles and hashtags (English and Korean) for an auto-edit result
+   */
+  const suggestVideoMetadata = useCallback(
+    async (resultId: string): Promise<MetadataSuggestion[]> => {
+      try {
+        return await invoke<MetadataSuggestion[]>('suggest_video_metadata', {
+          resultId,
+        });
+      } catch (err) {
+        console.error('Failed to suggest video metadata:', err);
+        return [];
+      }
+    },
+    []
+  );
+
+  /**
+   * Retry a failed upload from the history in the background
+   */
+  const retryUpload = useCallback(
+    async (videoPath: string): Promise<string> => {
+      setError(null);
+
+      try {
+        return await invoke<string>('youtube_retry_upload', { videoPath });
+      } catch (err) {
+        const errorMsg = err as string;
+        setError(errorMsg);
+        throw err;
+      }
+    },
+    []
+  );
+
   /**
    * Get quota information
    */
@@ -338,6 +413,10 @@ export function useYouTube() {
     startProgressPolling,
     stopProgressPolling,
     getUploadHistory,
+    retryUpload,
+    saveUploadDraft,
+    getUploadDraft,
+    suggestVideoMetadata,
     getQuotaInfo,
     getVideoDetails,
     checkAuthStatus,
@@ -1,6 +1,7 @@
 import { invoke } from "@tauri-apps/api/core";
 import { create } from "zustand";
 import { persist } from "zustand/middleware";
+import { errorMessage } from "@/types/error";
 
 // Types matching Rust backend
 export interface User {
@@ -69,7 +70,7 @@ export const useAuthStore = create<AuthState>()(
           });
         } catch (error) {
           set({
-            error: error as string,
+            error: errorMessage(error),
             isLoading: false,
           });
           throw error;
@@ -97,7 +98,7 @@ export const useAuthStore = create<AuthState>()(
           });
         } catch (error) {
           set({
-            error: error as string,
+            error: errorMessage(error),
             isLoading: false,
           });
           throw error;
@@ -116,7 +117,7 @@ export const useAuthStore = create<AuthState>()(
           });
         } catch (error) {
           set({
-            error: error as string,
+            error: errorMessage(error),
             isLoading: false,
           });
           throw error;
@@ -166,7 +167,7 @@ export const useAuthStore = create<AuthState>()(
           }
         } catch (error) {
           set({
-            error: error as string,
+            error: errorMessage(error),
             isLoading: false,
             user: null,
             isAuthenticated: false,
@@ -6,6 +6,7 @@ import { Button } from "@/components/ui/button";
 import { Alert, AlertDescription } from "@/components/ui/alert";
 import { CheckCircle2, AlertCircle, Loader2 } from "lucide-react";
 import { useAuthStore } from "@/lib/auth";
+import { errorMessage as describeError } from "@/types/error";
 
 export function PaymentSuccess() {
   const searchParams = useSearch({ from: "/payment/success" }) as Record<string, string>;
@@ -60,7 +61,7 @@ export function PaymentSuccess() {
       setStatus("success");
     } catch (error) {
       console.error("Payment confirmation failed:", error);
-      setErrorMessage(error as string);
+      setErrorMessage(describeError(error));
       setStatus("error");
     }
   };
@@ -15,7 +15,8 @@ export interface Position {
 export type BackgroundLayer =
   | { type: 'Color'; value: string }        // Hex color: "#RRGGBB"
   | { type: 'Gradient'; value: string }     // Two colors: "#RRGGBB:#RRGGBB"
-  | { type: 'Image'; path: string };        // File path to background image
+  | { type: 'Image'; path: string }         // File path to background image
+  | { type: 'champion_splash' };            // Splash art of the champion played
 
 export type CanvasElement =
   | {
@@ -33,6 +34,12 @@ export type CanvasElement =
       width: number;     // Width in pixels
       height: number;    // Height in pixels
       position: Position;
+    }
+  | {
+      type: 'champion_icon'; // Icon of the champion played
+      width: number;     // Width in pixels
+      height: number;    // Height in pixels
+      position: Position;
     };
 
 export interface CanvasTemplate {
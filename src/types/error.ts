@@ -0,0 +1,45 @@
+// Errors returned by backend commands
+
+export type ErrorCategory =
+  | 'recording'
+  | 'video'
+  | 'storage'
+  | 'network'
+  | 'auth'
+  | 'validation'
+  | 'internal';
+
+/**
+ * Structured error of commands returning `AppError` (video, storage,
+ * settings, cloud, auth, payment and upload commands).
+ * `code` is stable across releases; `message` is meant for the user.
+ */
+export interface AppError {
+  code: number;
+  category: ErrorCategory;
+  message: string;
+  suggestions: string[];
+}
+
+export function isAppError(err: unknown): err is AppError {
+  return (
+    typeof err === 'object' &&
+    err !== null &&
+    typeof (err as AppError).code === 'number' &&
+    typeof (err as AppError).message === 'string'
+  );
+}
+
+/**
+ * Message of a rejected command, whether it returned an `AppError` or a
+ * plain string
+ */
+export function errorMessage(err: unknown): string {
+  if (isAppError(err)) {
+    return err.message;
+  }
+  if (err instanceof Error) {
+    return err.message;
+  }
+  return String(err);
+}
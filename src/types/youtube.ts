@@ -24,6 +24,25 @@ export interface VideoMetadata {
   category_id: string;
 }
 
+/**
+ * Metadata prepared for an auto-edit result before uploading it.
+ * Text may contain placeholders like {champion}, {kda} or {result}.
+ */
+export interface UploadDraft {
+  title: string;
+  description: string;
+  tags: string[];
+  privacy_status: 'public' | 'unlisted' | 'private';
+  updated_at?: string | null;
+}
+
+/** Suggested titles and hashtags for an auto-edit result in one language */
+export interface MetadataSuggestion {
+  language: 'en' | 'ko';
+  titles: string[];
+  hashtags: string[];
+}
+
 export interface UploadProgress {
   uploaded_bytes: number;
   total_bytes: number;